@@ -1197,6 +1197,12 @@ impl<'a, 'gc> Activation<'a, 'gc> {
     fn action_get_url(&mut self, action: GetUrl) -> Result<FrameControl<'gc>, Error<'gc>> {
         let target = action.target.decode(self.encoding());
         let url = action.url.decode(self.encoding());
+        if globals::print_job::is_print_url(&url) {
+            let target = AvmString::new(self.gc(), target).into();
+            globals::print_job::print(self, target)?;
+            return Ok(FrameControl::Continue);
+        }
+
         // TODO: Use `StageObject::get_level_by_path`.
         if target.starts_with(WStr::from_units(b"_level")) && target.len() > 6 {
             match target[6..].parse::<i32>() {
@@ -1256,6 +1262,11 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             return Ok(FrameControl::Continue);
         }
 
+        if globals::print_job::is_print_url(&url) {
+            globals::print_job::print(self, target_val)?;
+            return Ok(FrameControl::Continue);
+        }
+
         // TODO: Use `StageObject::get_level_by_path`.
         let level_target = if target.starts_with(WStr::from_units(b"_level")) && target.len() >= 6 {
             match target[6..].parse::<f64>() {
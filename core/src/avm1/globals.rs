@@ -47,6 +47,7 @@ pub(crate) mod netstream;
 pub(crate) mod number;
 mod object;
 mod point;
+pub(crate) mod print_job;
 mod rectangle;
 mod selection;
 pub(crate) mod shared_object;
@@ -60,6 +61,7 @@ pub(crate) mod system_ime;
 pub(crate) mod system_security;
 pub(crate) mod text_field;
 mod text_format;
+pub(crate) mod text_snapshot;
 pub(crate) mod transform;
 mod video;
 pub(crate) mod xml;
@@ -480,6 +482,7 @@ pub struct SystemPrototypes<'gc> {
     pub sound: Object<'gc>,
     pub text_field: Object<'gc>,
     pub text_format: Object<'gc>,
+    pub text_snapshot: Object<'gc>,
     pub array: Object<'gc>,
     pub array_constructor: Object<'gc>,
     pub xml_node_constructor: Object<'gc>,
@@ -586,6 +589,8 @@ pub fn create_globals<'gc>(
     let netstream_proto = netstream::create_proto(context, object_proto, function_proto);
     let netconnection_proto = netconnection::create_proto(context, object_proto, function_proto);
     let xml_socket_proto = xml_socket::create_proto(context, object_proto, function_proto);
+    let print_job_proto = print_job::create_proto(context, object_proto, function_proto);
+    let text_snapshot_proto = text_snapshot::create_proto(context, object_proto, function_proto);
 
     //TODO: These need to be constructors and should also set `.prototype` on each one
     let object = object::create_object_object(context, object_proto, function_proto);
@@ -688,6 +693,8 @@ pub fn create_globals<'gc>(
     let netstream = netstream::create_class(context, netstream_proto, function_proto);
     let netconnection = netconnection::create_class(context, netconnection_proto, function_proto);
     let xml_socket = xml_socket::create_class(context, xml_socket_proto, function_proto);
+    let print_job = print_job::create_class(context, print_job_proto, function_proto);
+    let text_snapshot = text_snapshot::create_class(context, text_snapshot_proto, function_proto);
 
     let flash = ScriptObject::new(gc_context, Some(object_proto));
 
@@ -1106,6 +1113,18 @@ pub fn create_globals<'gc>(
         xml_socket.into(),
         Attribute::DONT_ENUM,
     );
+    globals.define_value(
+        gc_context,
+        "PrintJob",
+        print_job.into(),
+        Attribute::DONT_ENUM,
+    );
+    globals.define_value(
+        gc_context,
+        "TextSnapshot",
+        text_snapshot.into(),
+        Attribute::DONT_ENUM,
+    );
 
     define_properties_on(GLOBAL_DECLS, context, globals, function_proto);
 
@@ -1119,6 +1138,7 @@ pub fn create_globals<'gc>(
             sound: sound_proto,
             text_field: text_field_proto,
             text_format: text_format_proto,
+            text_snapshot: text_snapshot_proto,
             array: array_proto,
             array_constructor: array,
             xml_node_constructor: xmlnode,
@@ -568,6 +568,7 @@ fn draw<'gc>(
                 Transform {
                     matrix,
                     color_transform,
+                    matrix3d: None,
                 },
                 smoothing,
                 blend_mode,
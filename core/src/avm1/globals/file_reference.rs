@@ -5,6 +5,7 @@ use crate::avm1::globals::as_broadcaster::BroadcasterFunctions;
 use crate::avm1::property_decl::{define_properties_on, Declaration};
 use crate::avm1::{Executable, NativeObject, Object, ScriptObject, TObject, Value};
 use crate::avm1_stub;
+use crate::backend::navigator::{multipart_form_data, Request};
 use crate::backend::ui::{FileDialogResult, FileFilter};
 use crate::context::GcContext;
 use crate::string::AvmString;
@@ -397,17 +398,16 @@ pub fn upload<'gc>(
                 _ => return Ok(false.into()),
             }
 
+            let file_reference = file_reference.0.read();
+            let file_name = file_reference.name.as_deref().unwrap_or("file");
+            let body = multipart_form_data(&[], "Filedata", file_name, &file_reference.data);
+            let file_size = file_reference.data.len() as u64;
+
             let process = activation.context.load_manager.upload_file(
                 activation.context.player.clone(),
                 this,
-                url_string,
-                file_reference.0.read().data.clone(),
-                file_reference
-                    .0
-                    .read()
-                    .name
-                    .clone()
-                    .unwrap_or_else(|| "file".to_string()),
+                Request::post(url_string, Some(body)),
+                file_size,
             );
 
             activation.context.navigator.spawn_future(process);
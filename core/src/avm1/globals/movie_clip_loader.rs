@@ -143,10 +143,15 @@ fn get_progress<'gc>(
         };
         let result = ScriptObject::new(activation.context.gc_context, None);
         if let Some(target) = target {
+            let bytes_loaded = match target.as_movie_clip() {
+                // A movie that is still loading has only loaded what has been preloaded so far.
+                Some(mc) if !target.movie().is_loaded() => mc.compressed_loaded_bytes() as usize,
+                _ => target.movie().compressed_len(),
+            };
             result.define_value(
                 activation.context.gc_context,
                 "bytesLoaded",
-                target.movie().compressed_len().into(),
+                bytes_loaded.into(),
                 Attribute::empty(),
             );
             result.define_value(
@@ -11,7 +11,7 @@ use crate::context::{GcContext, UpdateContext};
 use crate::net_connection::{NetConnectionHandle, NetConnections, ResponderCallback};
 use crate::string::AvmString;
 use flash_lso::packet::Header;
-use flash_lso::types::Value as AMFValue;
+use flash_lso::types::{AMFVersion, Value as AMFValue};
 use gc_arena::{Collect, Gc};
 use ruffle_wstr::WStr;
 use std::cell::Cell;
@@ -75,6 +75,63 @@ impl<'gc> NetConnection<'gc> {
         Ok(())
     }
 
+    /// Call `onStatus` with a status object sent by a server.
+    ///
+    /// Any properties of `info` take precedence over the default `code` and
+    /// `level`.
+    pub fn on_server_status_event(
+        context: &mut UpdateContext<'gc>,
+        this: Object<'gc>,
+        code: &'static str,
+        level: &'static str,
+        info: Option<&AMFValue>,
+    ) -> Result<(), Error<'gc>> {
+        let Some(root_clip) = context.stage.root_clip() else {
+            tracing::warn!("Ignored NetConnection callback as there's no root movie");
+            return Ok(());
+        };
+        let mut activation = Activation::from_nothing(
+            context,
+            ActivationIdentifier::root("[NetConnection connect]"),
+            root_clip,
+        );
+        let info = match info {
+            Some(AMFValue::AMF3(info)) => Some(&**info),
+            info => info,
+        };
+        let event = match info.map(|info| {
+            let reader = flash_lso::read::Reader::default();
+            let mut reference_cache = BTreeMap::default();
+            deserialize_value(
+                &mut activation,
+                info,
+                &reader.amf0_decoder,
+                &mut reference_cache,
+            )
+        }) {
+            Some(Value::Object(event)) => event,
+            _ => {
+                let constructor = activation.context.avm1.prototypes().object_constructor;
+                constructor
+                    .construct(&mut activation, &[])?
+                    .coerce_to_object(&mut activation)
+            }
+        };
+        if !event.has_property(&mut activation, "code".into()) {
+            event.set("code", code.into(), &mut activation)?;
+        }
+        if !event.has_property(&mut activation, "level".into()) {
+            event.set("level", level.into(), &mut activation)?;
+        }
+        this.call_method(
+            "onStatus".into(),
+            &[event.into()],
+            &mut activation,
+            ExecutionReason::Special,
+        )?;
+        Ok(())
+    }
+
     // [NA] I have no idea why this is a thing. It's similar in AVM2 too.
     pub fn on_empty_status_event(
         context: &mut UpdateContext<'gc>,
@@ -321,12 +378,27 @@ fn connect<'gc>(
     {
         // HTTP(S) is for Flash Remoting, which is just POST requests to the URL.
         NetConnections::connect_to_flash_remoting(activation.context, this, url.to_string());
+    } else if url.starts_with(WStr::from_units(b"rtmp://")) {
+        // Any extra arguments are sent to the server along with the connect command.
+        let mut arguments = Vec::new();
+        for arg in args.iter().skip(1) {
+            arguments.push(serialize(activation, *arg));
+        }
+
+        // AVM1 only ever uses AMF0.
+        NetConnections::connect_to_rtmp(
+            activation.context,
+            this,
+            url.to_string(),
+            AMFVersion::AMF0,
+            arguments,
+        );
     } else {
         avm1_stub!(
             activation,
             "NetConnection",
             "connect",
-            "with non-null, non-http command"
+            "with non-null, non-http, non-rtmp command"
         );
     }
 
@@ -9,7 +9,7 @@ use crate::streams::NetStream;
 pub fn constructor<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     let netstream = NetStream::new(activation.context.gc_context, Some(this.into()));
     this.set_native(
@@ -17,6 +17,10 @@ pub fn constructor<'gc>(
         NativeObject::NetStream(netstream),
     );
 
+    if let Some(Value::Object(connection)) = args.get(0) {
+        netstream.set_connection(activation.context.gc_context, Some((*connection).into()));
+    }
+
     Ok(this.into())
 }
 
@@ -26,7 +30,9 @@ const PROTO_DECLS: &[Declaration] = declare_properties! {
     "bytesLoaded" => property(get_bytes_loaded);
     "bytesTotal" => property(get_bytes_total);
     "time" => property(get_time);
+    "close" => method(close; DONT_ENUM | DONT_DELETE);
     "play" => method(play; DONT_ENUM | DONT_DELETE);
+    "publish" => method(publish; DONT_ENUM | DONT_DELETE);
     "pause" => method(pause; DONT_ENUM | DONT_DELETE);
     "seek" => method(seek; DONT_ENUM | DONT_DELETE);
     "setBufferTime" => method(set_buffer_time; DONT_ENUM | DONT_DELETE);
@@ -100,6 +106,44 @@ fn play<'gc>(
     Ok(Value::Undefined)
 }
 
+fn close<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let NativeObject::NetStream(ns) = this.native() {
+        ns.close(activation.context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn publish<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let NativeObject::NetStream(ns) = this.native() {
+        match args.get(0) {
+            None | Some(Value::Undefined) | Some(Value::Null) | Some(Value::Bool(false)) => {
+                // Publishing `false` or nothing stops publishing.
+                ns.close(activation.context);
+            }
+            Some(name) => {
+                let name = name.coerce_to_string(activation)?.to_string();
+                let publish_type = match args.get(1) {
+                    None | Some(Value::Undefined) | Some(Value::Null) => "live".to_string(),
+                    Some(publish_type) => publish_type.coerce_to_string(activation)?.to_string(),
+                };
+
+                ns.publish(activation.context, name, publish_type);
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
 fn pause<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
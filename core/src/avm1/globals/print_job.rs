@@ -0,0 +1,268 @@
+//! PrintJob object and the global print functions
+
+use crate::avm1::activation::Activation;
+use crate::avm1::error::Error;
+use crate::avm1::function::FunctionObject;
+use crate::avm1::object::{NativeObject, Object};
+use crate::avm1::property_decl::{define_properties_on, Declaration};
+use crate::avm1::{Executable, ScriptObject, TObject, Value};
+use crate::avm1_stub;
+use crate::backend::ui::PrintPage;
+use crate::context::GcContext;
+use crate::display_object::DisplayObject;
+use crate::printing;
+use crate::string::{AvmString, WStr};
+use gc_arena::{Collect, Gc};
+use std::cell::{Cell, RefCell};
+use swf::{Rectangle, Twips};
+
+#[derive(Default, Collect)]
+#[collect(require_static)]
+struct PrintJobData {
+    started: Cell<bool>,
+    pages: RefCell<Vec<PrintPage>>,
+}
+
+#[derive(Clone, Copy, Collect)]
+#[collect(no_drop)]
+pub struct PrintJob<'gc>(Gc<'gc, PrintJobData>);
+
+impl<'gc> PrintJob<'gc> {
+    fn cast(value: Value<'gc>) -> Option<Self> {
+        if let Value::Object(object) = value {
+            if let NativeObject::PrintJob(print_job) = object.native() {
+                return Some(print_job);
+            }
+        }
+        None
+    }
+}
+
+const PROTO_DECLS: &[Declaration] = declare_properties! {
+    "paperWidth" => property(paper_width);
+    "paperHeight" => property(paper_height);
+    "pageWidth" => property(page_width);
+    "pageHeight" => property(page_height);
+    "orientation" => property(orientation);
+    "start" => method(start);
+    "addPage" => method(add_page);
+    "send" => method(send);
+};
+
+/// Returns `value` if `this` is a started print job, or `undefined` otherwise.
+fn if_started<'gc>(this: Object<'gc>, value: impl Into<Value<'gc>>) -> Value<'gc> {
+    match PrintJob::cast(this.into()) {
+        Some(print_job) if print_job.0.started.get() => value.into(),
+        _ => Value::Undefined,
+    }
+}
+
+fn paper_width<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(if_started(this, printing::PAPER_WIDTH))
+}
+
+fn paper_height<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(if_started(this, printing::PAPER_HEIGHT))
+}
+
+fn page_width<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(if_started(this, printing::PAGE_WIDTH))
+}
+
+fn page_height<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(if_started(this, printing::PAGE_HEIGHT))
+}
+
+fn orientation<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(if_started(
+        this,
+        AvmString::new_utf8(activation.gc(), "portrait"),
+    ))
+}
+
+fn start<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(print_job) = PrintJob::cast(this.into()) else {
+        return Ok(false.into());
+    };
+    if print_job.0.started.get() || !activation.context.ui.can_print() {
+        return Ok(false.into());
+    }
+
+    print_job.0.started.set(true);
+    Ok(true.into())
+}
+
+/// Read a print area in the form `{xMin, xMax, yMin, yMax}`, in pixels.
+fn print_area<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+) -> Result<Rectangle<Twips>, Error<'gc>> {
+    let mut coord = |name: &'static str| -> Result<Twips, Error<'gc>> {
+        let value = object.get(name, activation)?.coerce_to_f64(activation)?;
+        Ok(Twips::from_pixels(value))
+    };
+    Ok(Rectangle {
+        x_min: coord("xMin")?,
+        x_max: coord("xMax")?,
+        y_min: coord("yMin")?,
+        y_max: coord("yMax")?,
+    })
+}
+
+/// Resolve the target of `PrintJob.addPage` or `print`, which may be a level number,
+/// a path or a movie clip.
+fn resolve_target<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    target: Value<'gc>,
+) -> Result<Option<DisplayObject<'gc>>, Error<'gc>> {
+    if let Value::Number(level) = target {
+        return Ok(activation.get_level(level as i32));
+    }
+    let start = activation.target_clip_or_root();
+    activation.resolve_target_display_object(start, target, false)
+}
+
+fn add_page<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(print_job) = PrintJob::cast(this.into()) else {
+        return Ok(false.into());
+    };
+    if !print_job.0.started.get() {
+        return Ok(false.into());
+    }
+
+    let target = args.get(0).copied().unwrap_or(Value::Undefined);
+    let Some(target) = resolve_target(activation, target)? else {
+        return Ok(false.into());
+    };
+
+    let print_area = match args.get(1) {
+        Some(Value::Object(object)) => Some(print_area(activation, *object)?),
+        _ => None,
+    };
+
+    // Pages are always printed as bitmaps, so the `printAsBitmap` option has no effect.
+    if let Some(frame) = args.get(3) {
+        if !matches!(frame, Value::Undefined | Value::Null) {
+            avm1_stub!(activation, "PrintJob", "addPage", "with frameNum");
+        }
+    }
+
+    let Some(page) = printing::render_page(activation.context, target, print_area) else {
+        return Ok(false.into());
+    };
+    let page = printing::to_print_page(activation.context.renderer, page);
+    print_job.0.pages.borrow_mut().push(page);
+
+    Ok(true.into())
+}
+
+fn send<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(print_job) = PrintJob::cast(this.into()) {
+        if print_job.0.started.replace(false) {
+            let pages = print_job.0.pages.take();
+            activation.context.ui.print(pages);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn constructor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let print_job = PrintJob(Gc::new(activation.gc(), Default::default()));
+    this.set_native(activation.gc(), NativeObject::PrintJob(print_job));
+    Ok(this.into())
+}
+
+pub fn create_proto<'gc>(
+    context: &mut GcContext<'_, 'gc>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let print_job_proto = ScriptObject::new(context.gc_context, Some(proto));
+    define_properties_on(PROTO_DECLS, context, print_job_proto, fn_proto);
+    print_job_proto.into()
+}
+
+pub fn create_class<'gc>(
+    context: &mut GcContext<'_, 'gc>,
+    print_job_proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    FunctionObject::constructor(
+        context.gc_context,
+        Executable::Native(constructor),
+        constructor_to_fn!(constructor),
+        fn_proto,
+        print_job_proto,
+    )
+}
+
+/// Check whether a `getURL` URL is a request from one of the global `print` functions,
+/// such as `print:#bframe` or `printasbitmap:#bmax`.
+pub fn is_print_url(url: &WStr) -> bool {
+    [&b"print:"[..], &b"printasbitmap:"[..]]
+        .iter()
+        .any(|prefix| {
+            let prefix = WStr::from_units(*prefix);
+            url.len() >= prefix.len() && url[..prefix.len()].eq_ignore_case(prefix)
+        })
+}
+
+/// Handle a call to one of the global `print` functions, which print the current frame
+/// of `target` as a single page.
+pub fn print<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    target: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    if !activation.context.ui.can_print() {
+        return Ok(());
+    }
+
+    // TODO: The bounding box option (`bmax`, `bframe` or `bmovie`) and frames labelled `#p`
+    // are ignored; only the current frame is printed, cropped to its bounds.
+    let Some(target) = resolve_target(activation, target)? else {
+        return Ok(());
+    };
+    if let Some(page) = printing::render_page(activation.context, target, None) {
+        let page = printing::to_print_page(activation.context.renderer, page);
+        activation.context.ui.print(vec![page]);
+    }
+
+    Ok(())
+}
@@ -38,9 +38,18 @@ fn allow_insecure_domain<'gc>(
 fn load_policy_file<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm1_stub!(activation, "System.security", "loadPolicyFile");
+    let url = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?
+        .to_utf8_lossy()
+        .into_owned();
+    match activation.context.navigator.resolve_url(&url) {
+        Ok(url) => activation.context.policy_files.load_policy_file(url),
+        Err(e) => tracing::warn!("System.security.loadPolicyFile: Invalid URL {url}: {e}"),
+    }
     Ok(Value::Undefined)
 }
 
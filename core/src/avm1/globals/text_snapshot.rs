@@ -0,0 +1,271 @@
+//! TextSnapshot object
+
+use crate::avm1::activation::Activation;
+use crate::avm1::error::Error;
+use crate::avm1::function::FunctionObject;
+use crate::avm1::object::{NativeObject, Object};
+use crate::avm1::property_decl::{define_properties_on, Declaration};
+use crate::avm1::{ArrayObject, Executable, ScriptObject, TObject, Value};
+use crate::context::GcContext;
+use crate::display_object::DisplayObject;
+use crate::string::AvmString;
+use crate::text_snapshot::TextSnapshot;
+use swf::{Point, Twips};
+
+const PROTO_DECLS: &[Declaration] = declare_properties! {
+    "getCount" => method(get_count; DONT_ENUM | DONT_DELETE);
+    "setSelected" => method(set_selected; DONT_ENUM | DONT_DELETE);
+    "getSelected" => method(get_selected; DONT_ENUM | DONT_DELETE);
+    "getText" => method(get_text; DONT_ENUM | DONT_DELETE);
+    "getSelectedText" => method(get_selected_text; DONT_ENUM | DONT_DELETE);
+    "hitTestTextNearPos" => method(hit_test_text_near_pos; DONT_ENUM | DONT_DELETE);
+    "findText" => method(find_text; DONT_ENUM | DONT_DELETE);
+    "setSelectColor" => method(set_select_color; DONT_ENUM | DONT_DELETE);
+    "getTextRunInfo" => method(get_text_run_info; DONT_ENUM | DONT_DELETE);
+};
+
+/// Create a `TextSnapshot` object reading the static text of `container`.
+pub fn create<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    container: DisplayObject<'gc>,
+) -> Object<'gc> {
+    let object = ScriptObject::new(
+        activation.context.gc_context,
+        Some(activation.context.avm1.prototypes().text_snapshot),
+    );
+    object.set_native(
+        activation.context.gc_context,
+        NativeObject::TextSnapshot(container),
+    );
+    object.into()
+}
+
+fn snapshot<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+) -> Option<TextSnapshot<'gc>> {
+    match this.native() {
+        NativeObject::TextSnapshot(container) => {
+            Some(TextSnapshot::new(activation.context.library, container))
+        }
+        _ => None,
+    }
+}
+
+fn arg<'gc>(args: &[Value<'gc>], index: usize) -> Value<'gc> {
+    args.get(index).copied().unwrap_or(Value::Undefined)
+}
+
+fn get_count<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(snapshot(activation, this).map_or(Value::Undefined, |s| s.char_count().into()))
+}
+
+fn set_selected<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let begin = arg(args, 0).coerce_to_i32(activation)?;
+    let end = arg(args, 1).coerce_to_i32(activation)?;
+    let selected = arg(args, 2).as_bool(activation.swf_version());
+
+    if let Some(snapshot) = snapshot(activation, this) {
+        let range = snapshot.clamp_range(begin, end);
+        snapshot.set_selected(activation.context.gc_context, range, selected);
+    }
+    Ok(Value::Undefined)
+}
+
+fn get_selected<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let begin = arg(args, 0).coerce_to_i32(activation)?;
+    let end = arg(args, 1).coerce_to_i32(activation)?;
+
+    let Some(snapshot) = snapshot(activation, this) else {
+        return Ok(Value::Undefined);
+    };
+    let range = snapshot.clamp_range(begin, end);
+    Ok(snapshot.is_selected(range).into())
+}
+
+fn get_text<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let begin = arg(args, 0).coerce_to_i32(activation)?;
+    let end = arg(args, 1).coerce_to_i32(activation)?;
+    let include_line_endings = arg(args, 2).as_bool(activation.swf_version());
+
+    let Some(snapshot) = snapshot(activation, this) else {
+        return Ok(Value::Undefined);
+    };
+    let text = snapshot.text(snapshot.clamp_range(begin, end), include_line_endings);
+    Ok(AvmString::new_utf8(activation.context.gc_context, text).into())
+}
+
+fn get_selected_text<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let include_line_endings = arg(args, 0).as_bool(activation.swf_version());
+
+    let Some(snapshot) = snapshot(activation, this) else {
+        return Ok(Value::Undefined);
+    };
+    let text = snapshot.selected_text(include_line_endings);
+    Ok(AvmString::new_utf8(activation.context.gc_context, text).into())
+}
+
+fn hit_test_text_near_pos<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let point = Point::new(
+        Twips::from_pixels(arg(args, 0).coerce_to_f64(activation)?),
+        Twips::from_pixels(arg(args, 1).coerce_to_f64(activation)?),
+    );
+    let max_distance = match arg(args, 2) {
+        Value::Undefined => 0.0,
+        value => value.coerce_to_f64(activation)?,
+    };
+
+    let Some(snapshot) = snapshot(activation, this) else {
+        return Ok(Value::Undefined);
+    };
+    let index = snapshot.hit_test_near_pos(point, max_distance);
+    Ok(index.map_or(-1.0, |i| i as f64).into())
+}
+
+fn find_text<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let begin = arg(args, 0).coerce_to_i32(activation)?.max(0) as usize;
+    let needle = arg(args, 1).coerce_to_string(activation)?;
+    let case_sensitive = arg(args, 2).as_bool(activation.swf_version());
+
+    let Some(snapshot) = snapshot(activation, this) else {
+        return Ok(Value::Undefined);
+    };
+    let index = snapshot.find_text(begin, &needle.to_utf8_lossy(), case_sensitive);
+    Ok(index.map_or(-1, |i| i as i32).into())
+}
+
+fn set_select_color<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let color = match arg(args, 0) {
+        Value::Undefined => 0xFFFF00,
+        value => value.coerce_to_u32(activation)?,
+    };
+
+    if let Some(snapshot) = snapshot(activation, this) {
+        snapshot.set_select_color(
+            activation.context.gc_context,
+            swf::Color::from_rgb(color, 255),
+        );
+    }
+    Ok(Value::Undefined)
+}
+
+fn get_text_run_info<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let begin = arg(args, 0).coerce_to_i32(activation)?;
+    let end = arg(args, 1).coerce_to_i32(activation)?;
+
+    let Some(snapshot) = snapshot(activation, this) else {
+        return Ok(Value::Undefined);
+    };
+    let range = snapshot.clamp_range(begin, end);
+
+    let mut runs = Vec::new();
+    for (index, c) in snapshot.chars()[range.clone()]
+        .iter()
+        .enumerate()
+        .map(|(i, c)| (i + range.start, c))
+    {
+        let run = ScriptObject::new(
+            activation.context.gc_context,
+            Some(activation.context.avm1.prototypes().object),
+        );
+        let font = AvmString::new_utf8(activation.context.gc_context, &c.info.font_name);
+        let properties: [(&str, Value<'gc>); 11] = [
+            ("indexInRun", index.into()),
+            ("selected", c.is_selected().into()),
+            ("font", font.into()),
+            ("color", c.info.color.to_rgb().into()),
+            ("height", c.info.height.to_pixels().into()),
+            ("matrix_a", c.matrix.a.into()),
+            ("matrix_b", c.matrix.b.into()),
+            ("matrix_c", c.matrix.c.into()),
+            ("matrix_d", c.matrix.d.into()),
+            ("matrix_tx", c.matrix.tx.to_pixels().into()),
+            ("matrix_ty", c.matrix.ty.to_pixels().into()),
+        ];
+        for (name, value) in properties {
+            run.set(name, value, activation)?;
+        }
+        for (i, corner) in c.corners().into_iter().enumerate() {
+            let x = AvmString::new_utf8(activation.context.gc_context, format!("corner{i}x"));
+            let y = AvmString::new_utf8(activation.context.gc_context, format!("corner{i}y"));
+            run.set(x, corner.x.to_pixels().into(), activation)?;
+            run.set(y, corner.y.to_pixels().into(), activation)?;
+        }
+        runs.push(run.into());
+    }
+
+    Ok(ArrayObject::new(
+        activation.context.gc_context,
+        activation.context.avm1.prototypes().array,
+        runs,
+    )
+    .into())
+}
+
+fn constructor<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this.into())
+}
+
+pub fn create_proto<'gc>(
+    context: &mut GcContext<'_, 'gc>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let text_snapshot_proto = ScriptObject::new(context.gc_context, Some(proto));
+    define_properties_on(PROTO_DECLS, context, text_snapshot_proto, fn_proto);
+    text_snapshot_proto.into()
+}
+
+pub fn create_class<'gc>(
+    context: &mut GcContext<'_, 'gc>,
+    text_snapshot_proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    FunctionObject::constructor(
+        context.gc_context,
+        Executable::Native(constructor),
+        constructor_to_fn!(constructor),
+        fn_proto,
+        text_snapshot_proto,
+    )
+}
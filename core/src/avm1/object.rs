@@ -14,6 +14,7 @@ use crate::avm1::globals::glow_filter::GlowFilter;
 use crate::avm1::globals::gradient_filter::GradientFilter;
 use crate::avm1::globals::local_connection::LocalConnection;
 use crate::avm1::globals::netconnection::NetConnection;
+use crate::avm1::globals::print_job::PrintJob;
 use crate::avm1::globals::shared_object::SharedObject;
 use crate::avm1::globals::transform::TransformObject;
 use crate::avm1::globals::xml::Xml;
@@ -68,6 +69,8 @@ pub enum NativeObject<'gc> {
     FileReference(FileReferenceObject<'gc>),
     NetConnection(NetConnection<'gc>),
     LocalConnection(LocalConnection<'gc>),
+    PrintJob(PrintJob<'gc>),
+    TextSnapshot(DisplayObject<'gc>),
 }
 
 /// Represents an object that can be directly interacted with by the AVM
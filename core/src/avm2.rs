@@ -6,6 +6,7 @@ use crate::avm2::class::AllocatorFn;
 use crate::avm2::error::make_error_1107;
 use crate::avm2::globals::SystemClasses;
 use crate::avm2::method::{Method, NativeMethodImpl};
+use crate::avm2::sampler::Sampler;
 use crate::avm2::scope::ScopeChain;
 use crate::avm2::script::{Script, TranslationUnit};
 use crate::context::{GcContext, UpdateContext};
@@ -31,12 +32,13 @@ macro_rules! avm_debug {
 }
 
 pub mod activation;
-mod amf;
+pub(crate) mod amf;
 pub mod api_version;
 mod array;
 pub mod bytearray;
 mod call_stack;
 mod class;
+pub mod debugger;
 mod domain;
 mod dynamic_map;
 mod e4x;
@@ -45,6 +47,7 @@ mod events;
 mod filters;
 mod flv;
 mod function;
+mod globalization;
 pub mod globals;
 mod metadata;
 mod method;
@@ -58,6 +61,7 @@ pub mod property;
 mod property_map;
 mod qname;
 mod regexp;
+mod sampler;
 mod scope;
 pub mod script;
 #[cfg(feature = "known_stubs")]
@@ -69,6 +73,7 @@ mod value;
 pub mod vector;
 mod verify;
 mod vtable;
+pub mod worker;
 
 pub use crate::avm2::activation::Activation;
 pub use crate::avm2::array::ArrayStorage;
@@ -91,6 +96,7 @@ pub use crate::avm2::value::Value;
 use self::api_version::ApiVersion;
 use self::object::WeakObject;
 use self::scope::Scope;
+use self::worker::WorkerObjects;
 use num_traits::FromPrimitive;
 
 const BROADCAST_WHITELIST: [&str; 4] = ["enterFrame", "exitFrame", "frameConstructed", "render"];
@@ -115,6 +121,9 @@ pub struct Avm2<'gc> {
     /// The current call stack of the player.
     call_stack: GcRefLock<'gc, CallStack<'gc>>,
 
+    /// The state of `flash.sampler`.
+    sampler: Sampler<'gc>,
+
     /// This domain is used exclusively for classes from playerglobals
     playerglobals_domain: Domain<'gc>,
 
@@ -147,8 +156,10 @@ pub struct Avm2<'gc> {
     pub flash_utils_internal: Namespace<'gc>,
     pub flash_geom_internal: Namespace<'gc>,
     pub flash_events_internal: Namespace<'gc>,
+    pub flash_text_internal: Namespace<'gc>,
     pub flash_text_engine_internal: Namespace<'gc>,
     pub flash_net_internal: Namespace<'gc>,
+    pub flash_globalization_internal: Namespace<'gc>,
 
     #[collect(require_static)]
     native_method_table: &'static [Option<(&'static str, NativeMethodImpl)>],
@@ -183,6 +194,9 @@ pub struct Avm2<'gc> {
     alias_to_class_map: FnvHashMap<AvmString<'gc>, ClassObject<'gc>>,
     class_to_alias_map: FnvHashMap<Class<'gc>, AvmString<'gc>>,
 
+    /// The objects representing state shared with other workers.
+    pub worker_objects: WorkerObjects<'gc>,
+
     /// The api version of our root movie clip. Note - this is used as the
     /// api version for swfs loaded via `Loader`, overriding the api version
     /// specified in the loaded SWF. This is only used for API versioning (hiding
@@ -195,6 +209,10 @@ pub struct Avm2<'gc> {
     pub debug_output: bool,
 
     pub optimizer_enabled: bool,
+
+    /// Whether a `paste` event is being dispatched, during which the system
+    /// clipboard may be read outside of AIR.
+    pub dispatching_paste: bool,
 }
 
 impl<'gc> Avm2<'gc> {
@@ -218,6 +236,7 @@ impl<'gc> Avm2<'gc> {
             stack: Vec::new(),
             scope_stack: Vec::new(),
             call_stack: GcRefLock::new(context.gc_context, CallStack::new().into()),
+            sampler: Sampler::new(),
             playerglobals_domain,
             stage_domain,
             system_classes: None,
@@ -248,8 +267,10 @@ impl<'gc> Avm2<'gc> {
             flash_utils_internal: Namespace::internal("flash.utils", context),
             flash_geom_internal: Namespace::internal("flash.geom", context),
             flash_events_internal: Namespace::internal("flash.events", context),
+            flash_text_internal: Namespace::internal("flash.text", context),
             flash_text_engine_internal: Namespace::internal("flash.text.engine", context),
             flash_net_internal: Namespace::internal("flash.net", context),
+            flash_globalization_internal: Namespace::internal("flash.globalization", context),
 
             native_method_table: Default::default(),
             native_instance_allocator_table: Default::default(),
@@ -261,6 +282,7 @@ impl<'gc> Avm2<'gc> {
 
             alias_to_class_map: Default::default(),
             class_to_alias_map: Default::default(),
+            worker_objects: Default::default(),
 
             // Set the lowest version for now - this will be overridden when we set our movie
             root_api_version: ApiVersion::AllVersions,
@@ -269,6 +291,8 @@ impl<'gc> Avm2<'gc> {
             debug_output: false,
 
             optimizer_enabled: true,
+
+            dispatching_paste: false,
         }
     }
 
@@ -645,8 +669,16 @@ impl<'gc> Avm2<'gc> {
     }
 
     /// Pushes an executable on the call stack
-    pub fn push_call(&self, mc: &Mutation<'gc>, method: Method<'gc>, class: Option<Class<'gc>>) {
-        self.call_stack.borrow_mut(mc).push(method, class)
+    pub fn push_call(
+        &mut self,
+        mc: &Mutation<'gc>,
+        method: Method<'gc>,
+        class: Option<Class<'gc>>,
+    ) {
+        self.call_stack.borrow_mut(mc).push(method, class);
+        if self.sampler.is_sampling() {
+            self.sampler.record_call(method, &self.call_stack.borrow());
+        }
     }
 
     /// Pushes script initializer (global init) on the call stack
@@ -663,6 +695,14 @@ impl<'gc> Avm2<'gc> {
         self.call_stack
     }
 
+    pub fn sampler(&self) -> &Sampler<'gc> {
+        &self.sampler
+    }
+
+    pub fn sampler_mut(&mut self) -> &mut Sampler<'gc> {
+        &mut self.sampler
+    }
+
     #[cold]
     fn stack_overflow(&self) {
         tracing::warn!("Avm2::push: Stack overflow");
@@ -2,6 +2,7 @@
 
 use crate::avm2::array::ArrayStorage;
 use crate::avm2::class::Class;
+use crate::avm2::debugger::{describe_value, StackFrame, StopInfo, StopReason, Variable};
 use crate::avm2::domain::Domain;
 use crate::avm2::e4x::{escape_attribute_value, escape_element_value};
 use crate::avm2::error::{
@@ -14,6 +15,7 @@ use crate::avm2::object::{
 };
 use crate::avm2::object::{Object, TObject};
 use crate::avm2::op::Op;
+use crate::avm2::sampler;
 use crate::avm2::scope::{search_scope_stack, Scope, ScopeChain};
 use crate::avm2::script::Script;
 use crate::avm2::value::Value;
@@ -21,7 +23,7 @@ use crate::avm2::Multiname;
 use crate::avm2::Namespace;
 use crate::avm2::{Avm2, Error};
 use crate::context::{GcContext, UpdateContext};
-use crate::string::{AvmAtom, AvmString};
+use crate::string::{AvmAtom, AvmString, WString};
 use crate::tag_utils::SwfMovie;
 use gc_arena::Gc;
 use smallvec::SmallVec;
@@ -30,6 +32,7 @@ use std::sync::Arc;
 use swf::avm2::types::{
     Exception, Index, Method as AbcMethod, MethodFlags as AbcMethodFlags, Namespace as AbcNamespace,
 };
+use web_time::Instant;
 
 use super::error::make_mismatch_error;
 
@@ -814,6 +817,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
 
                     self.clear_scope();
                     self.ip = e.target_offset as i32;
+                    self.context.avm2_debugger.on_exception_caught();
                     return Ok(FrameControl::Continue);
                 }
             }
@@ -822,6 +826,83 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         Err(Error::AvmError(error))
     }
 
+    /// The depth of the call stack, which identifies frames in the debugger.
+    fn call_depth(&self) -> usize {
+        self.context.avm2.call_stack().borrow().len()
+    }
+
+    /// Stop in the AVM2 debugger, reporting the state of this frame.
+    ///
+    /// With a connected client, this blocks until the client resumes execution.
+    /// Otherwise, the debug UI suspends the player until it's resumed.
+    fn debugger_stop(&mut self, reason: StopReason) {
+        let depth = self.call_depth();
+        let debugger = &*self.context.avm2_debugger;
+
+        let backtrace = self
+            .context
+            .avm2
+            .call_stack()
+            .borrow()
+            .iter()
+            .enumerate()
+            .map(|(i, call)| {
+                let mut name = WString::new();
+                call.display(&mut name);
+                StackFrame {
+                    name: name.to_string(),
+                    position: debugger.position(depth - i),
+                }
+            })
+            .collect();
+
+        let locals = debugger
+            .register_names(depth)
+            .iter()
+            .filter(|(register, _)| (*register as usize) < self.local_registers.0.len())
+            .map(|(register, name)| Variable::new(name.clone(), self.local_register(*register)))
+            .collect();
+
+        let registers = self
+            .local_registers
+            .0
+            .iter()
+            .enumerate()
+            .map(|(i, value)| Variable::new(format!("r{i}"), *value))
+            .collect();
+
+        let local_scopes = self.scope_frame().iter().rev();
+        let outer_scopes = (0..).map_while(|i| self.outer.get(i)).collect::<Vec<_>>();
+        let scope = local_scopes
+            .chain(outer_scopes.iter().rev())
+            .enumerate()
+            .map(|(i, scope)| {
+                let kind = if scope.with() { "with" } else { "scope" };
+                Variable::new(format!("{kind}{i}"), scope.values().into())
+            })
+            .collect();
+
+        let stack = self.context.avm2.stack[self.stack_depth..]
+            .iter()
+            .enumerate()
+            .map(|(i, value)| Variable::new(format!("s{i}"), *value))
+            .collect();
+
+        let stopped_at = Instant::now();
+        self.context.avm2_debugger.stop(StopInfo {
+            reason,
+            depth,
+            backtrace,
+            locals,
+            registers,
+            scope,
+            stack,
+        });
+
+        // Time spent stopped in the debugger doesn't count towards the script timeout.
+        self.context.update_start += stopped_at.elapsed();
+    }
+
     /// Run a single action from a given action reader.
     #[inline(always)]
     fn do_next_opcode(
@@ -1045,6 +1126,13 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             };
 
             if let Err(error) = result {
+                if let Error::AvmError(value) = &error {
+                    if self.context.avm2_debugger.is_enabled()
+                        && self.context.avm2_debugger.should_break_on_throw()
+                    {
+                        self.debugger_stop(StopReason::Exception(describe_value(*value)));
+                    }
+                }
                 return self.handle_err(method, error);
             }
             result
@@ -1862,6 +1950,8 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         let scope = self.create_scopechain();
 
         let new_fn = FunctionObject::from_function(self, method_entry, scope)?;
+        let function_class = self.avm2().classes().function;
+        sampler::record_allocation(self, new_fn.into(), Some(function_class))?;
 
         self.push_stack(new_fn);
 
@@ -1904,6 +1994,8 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         let args = self.pop_stack_args(num_args);
         let array = ArrayStorage::from_args(&args[..]);
         let array_obj = ArrayObject::from_storage(self, array)?;
+        let array_class = self.avm2().classes().array;
+        sampler::record_allocation(self, array_obj, Some(array_class))?;
 
         self.push_stack(array_obj);
 
@@ -3071,13 +3163,13 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         Ok(FrameControl::Continue)
     }
 
-    #[cfg(feature = "avm_debug")]
     fn op_debug(
         &mut self,
         is_local_register: bool,
         register_name: AvmAtom<'gc>,
         register: u8,
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
+        #[cfg(feature = "avm_debug")]
         if is_local_register {
             if (register as usize) < self.local_registers.0.len() {
                 let value = self.local_register(register as u32);
@@ -3093,44 +3185,65 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             avm_debug!(self.avm2(), "Unknown debugging mode!");
         }
 
-        Ok(FrameControl::Continue)
-    }
+        if is_local_register && self.context.avm2_debugger.is_enabled() {
+            let depth = self.call_depth();
+            self.context.avm2_debugger.on_register_name(
+                depth,
+                register as u32,
+                register_name.to_string(),
+            );
+        }
 
-    #[cfg(not(feature = "avm_debug"))]
-    fn op_debug(
-        &mut self,
-        _is_local_register: bool,
-        _register_name: AvmAtom<'gc>,
-        _register: u8,
-    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         Ok(FrameControl::Continue)
     }
 
-    #[cfg(feature = "avm_debug")]
     fn op_debug_file(&mut self, file_name: AvmAtom<'gc>) -> Result<FrameControl<'gc>, Error<'gc>> {
         avm_debug!(self.avm2(), "File: {file_name}");
 
-        Ok(FrameControl::Continue)
-    }
+        if self.context.avm2_debugger.is_enabled() {
+            let depth = self.call_depth();
+            let started_at = Instant::now();
+            self.context
+                .avm2_debugger
+                .on_file(depth, &file_name.to_string());
+
+            // The debugger may have waited for its client to set breakpoints.
+            self.context.update_start += started_at.elapsed();
+        }
 
-    #[cfg(not(feature = "avm_debug"))]
-    fn op_debug_file(&mut self, _file_name: AvmAtom<'gc>) -> Result<FrameControl<'gc>, Error<'gc>> {
         Ok(FrameControl::Continue)
     }
 
     fn op_debug_line(&mut self, line_num: u32) -> Result<FrameControl<'gc>, Error<'gc>> {
         avm_debug!(self.avm2(), "Line: {line_num}");
 
+        if self.context.avm2_debugger.is_enabled() {
+            let depth = self.call_depth();
+            if let Some(reason) = self.context.avm2_debugger.on_line(depth, line_num) {
+                self.debugger_stop(reason);
+            }
+        }
+
         Ok(FrameControl::Continue)
     }
 
     fn op_bkpt(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         // while a debugger is not attached, this is a no-op
+        if self.context.avm2_debugger.is_enabled() {
+            self.debugger_stop(StopReason::BreakpointOpcode);
+        }
+
         Ok(FrameControl::Continue)
     }
 
-    fn op_bkpt_line(&mut self, _line_num: u32) -> Result<FrameControl<'gc>, Error<'gc>> {
+    fn op_bkpt_line(&mut self, line_num: u32) -> Result<FrameControl<'gc>, Error<'gc>> {
         // while a debugger is not attached, this is a no-op
+        if self.context.avm2_debugger.is_enabled() {
+            let depth = self.call_depth();
+            self.context.avm2_debugger.on_line(depth, line_num);
+            self.debugger_stop(StopReason::BreakpointOpcode);
+        }
+
         Ok(FrameControl::Continue)
     }
 
@@ -0,0 +1,764 @@
+//! AVM2 debugger
+//!
+//! Movies compiled in debug mode contain `debugfile`, `debugline` and `debug`
+//! opcodes, which tell us where in the original source code we are and what the
+//! local registers are called. The debugger follows these to stop on source
+//! breakpoints, step through the source, and break when an exception is thrown.
+//!
+//! When execution stops, the state of the current frame is captured in a
+//! [`StopInfo`]. If a [`DebuggerConnection`] with a connected client is
+//! attached, execution is blocked until the client resumes it. Otherwise, the
+//! stop is kept for the debug UI, which suspends the player until it's resumed.
+
+#[cfg(not(target_family = "wasm"))]
+pub mod fdb;
+#[cfg(not(target_family = "wasm"))]
+pub mod server;
+
+use crate::avm2::object::TObject;
+use crate::avm2::Value;
+use std::collections::{BTreeSet, HashSet};
+use std::fmt;
+
+/// A location in the source code of a movie.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SourcePosition {
+    /// The normalized path of the source file, see [`normalize_file_name`].
+    pub file: String,
+    pub line: u32,
+}
+
+impl fmt::Display for SourcePosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.file, self.line)
+    }
+}
+
+/// A source breakpoint.
+///
+/// The file of a breakpoint may be a partial path, which matches any file
+/// ending with the same path components, e.g. `Main.as` or `com/example/Main.as`.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Breakpoint {
+    pub file: String,
+    pub line: u32,
+}
+
+impl Breakpoint {
+    pub fn new(file: &str, line: u32) -> Self {
+        Self {
+            file: normalize_file_name(file),
+            line,
+        }
+    }
+
+    pub fn matches(&self, position: &SourcePosition) -> bool {
+        if self.line != position.line {
+            return false;
+        }
+
+        match position.file.strip_suffix(self.file.as_str()) {
+            Some(prefix) => prefix.is_empty() || prefix.ends_with('/'),
+            None => false,
+        }
+    }
+}
+
+impl fmt::Display for Breakpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.file, self.line)
+    }
+}
+
+/// Normalizes the name of a source file as emitted in `debugfile` opcodes.
+///
+/// The Flex compiler separates the source path, package path and file name
+/// with semicolons (`C:\src;com\example;Main.as`). We turn these into a single
+/// path with forward slashes (`C:/src/com/example/Main.as`).
+pub fn normalize_file_name(file: &str) -> String {
+    let path = file
+        .split([';', '\\', '/'])
+        .filter(|component| !component.is_empty())
+        .collect::<Vec<_>>()
+        .join("/");
+    if file.starts_with('/') {
+        format!("/{path}")
+    } else {
+        path
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StepMode {
+    /// Stop on the next line, entering called functions.
+    Into,
+
+    /// Stop on the next line of the current function, or of its callers.
+    Over,
+
+    /// Stop on the next line once the current function has returned.
+    Out,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StopReason {
+    /// A source breakpoint was reached.
+    Breakpoint,
+
+    /// A `bkpt` or `bkptline` opcode was executed.
+    BreakpointOpcode,
+
+    /// A step has finished.
+    Step,
+
+    /// The client asked to pause execution.
+    Pause,
+
+    /// An exception was thrown. This contains the description of the
+    /// thrown value.
+    Exception(String),
+}
+
+/// A frame of the call stack, as seen by the debugger.
+#[derive(Clone, Debug)]
+pub struct StackFrame {
+    pub name: String,
+    pub position: Option<SourcePosition>,
+}
+
+/// A named value in a stopped frame.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Variable {
+    pub name: String,
+    pub value: VariableValue,
+}
+
+impl Variable {
+    pub fn new(name: impl Into<String>, value: Value<'_>) -> Self {
+        Self {
+            name: name.into(),
+            value: VariableValue::new(value),
+        }
+    }
+}
+
+/// A copy of a value in a stopped frame.
+///
+/// Objects can't outlive the stop, so only their description is kept.
+#[derive(Clone, Debug, PartialEq)]
+pub enum VariableValue {
+    Undefined,
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Object(String),
+}
+
+impl fmt::Display for VariableValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VariableValue::Undefined => write!(f, "undefined"),
+            VariableValue::Null => write!(f, "null"),
+            VariableValue::Bool(value) => write!(f, "{value}"),
+            VariableValue::Number(value) => write!(f, "{value}"),
+            VariableValue::String(value) => write!(f, "{value:?}"),
+            VariableValue::Object(description) => write!(f, "{description}"),
+        }
+    }
+}
+
+/// The state of the interpreter when execution stopped.
+#[derive(Clone, Debug)]
+pub struct StopInfo {
+    pub reason: StopReason,
+
+    /// The depth of the call stack of the stopped frame.
+    pub depth: usize,
+
+    /// The call stack, from the innermost frame outwards.
+    pub backtrace: Vec<StackFrame>,
+
+    /// The local registers that were named by `debug` opcodes.
+    pub locals: Vec<Variable>,
+
+    /// All local registers.
+    pub registers: Vec<Variable>,
+
+    /// The scope chain, from the innermost scope outwards.
+    pub scope: Vec<Variable>,
+
+    /// The operand stack, from the bottom of the frame's stack to the top.
+    pub stack: Vec<Variable>,
+}
+
+impl StopInfo {
+    pub fn position(&self) -> Option<&SourcePosition> {
+        self.backtrace
+            .first()
+            .and_then(|frame| frame.position.as_ref())
+    }
+}
+
+/// A request sent to the debugger by a client.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DebuggerCommand {
+    SetBreakpoint(Breakpoint),
+    ClearBreakpoint(Breakpoint),
+    ListBreakpoints,
+    BreakOnThrow(bool),
+    Pause,
+    Continue,
+    Step(StepMode),
+    Backtrace,
+    Locals,
+    Registers,
+    Scope,
+    Stack,
+}
+
+/// A message sent by the debugger to a client.
+#[derive(Clone, Debug)]
+pub enum DebuggerEvent {
+    /// The last command was successful.
+    Ok,
+
+    /// The last command failed.
+    Error(String),
+
+    /// Execution has stopped.
+    Stopped(StopInfo),
+
+    Breakpoints(Vec<Breakpoint>),
+    Backtrace(Vec<StackFrame>),
+    Variables(Vec<Variable>),
+}
+
+/// A channel between the debugger and a client that controls it.
+pub trait DebuggerConnection {
+    /// Whether a client is currently attached to this connection.
+    fn is_connected(&self) -> bool;
+
+    /// Returns the next command sent by the client, without blocking.
+    fn poll_command(&mut self) -> Option<DebuggerCommand>;
+
+    /// Blocks until the client sends a command.
+    ///
+    /// Returns `None` if the client disconnected.
+    fn wait_for_command(&mut self) -> Option<DebuggerCommand>;
+
+    /// Sends an event to the client.
+    fn send(&mut self, event: DebuggerEvent);
+
+    /// Called the first time code from a source file runs, with the file name
+    /// as written in the movie.
+    ///
+    /// This may block to give the client a chance to set breakpoints in it.
+    fn file_loaded(&mut self, _file: &str) {}
+
+    /// Forwards a `trace` message to the client.
+    fn trace(&mut self, _message: &str) {}
+}
+
+/// What the debugger knows about an active frame.
+#[derive(Clone, Debug, Default)]
+struct FrameState {
+    file: Option<String>,
+    line: u32,
+    register_names: Vec<(u32, String)>,
+}
+
+impl FrameState {
+    fn position(&self) -> Option<SourcePosition> {
+        self.file.as_ref().map(|file| SourcePosition {
+            file: file.clone(),
+            line: self.line,
+        })
+    }
+}
+
+#[derive(Default)]
+pub struct Avm2Debugger {
+    enabled: bool,
+    breakpoints: BTreeSet<Breakpoint>,
+    break_on_throw: bool,
+    pause_requested: bool,
+
+    /// The pending step, with the call depth it was started from.
+    step: Option<(StepMode, usize)>,
+
+    /// The state of each frame of the call stack, indexed by depth - 1.
+    ///
+    /// Entries of frames that have returned are discarded lazily, the next
+    /// time a shallower frame reports its position.
+    frames: Vec<FrameState>,
+
+    /// Whether the debugger already stopped for the exception that is
+    /// currently propagating.
+    unwinding: bool,
+
+    /// The normalized names of the source files that have been run.
+    known_files: HashSet<String>,
+
+    /// The last time execution stopped.
+    last_stop: Option<StopInfo>,
+
+    /// Whether the last stop is waiting to be resumed from the debug UI.
+    suspended: bool,
+
+    connection: Option<Box<dyn DebuggerConnection>>,
+}
+
+impl Avm2Debugger {
+    pub fn new(connection: Option<Box<dyn DebuggerConnection>>) -> Self {
+        Self {
+            enabled: connection.is_some(),
+            connection,
+            ..Default::default()
+        }
+    }
+
+    #[inline(always)]
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        if !enabled {
+            self.frames.clear();
+            self.step = None;
+            self.pause_requested = false;
+            self.suspended = false;
+        }
+    }
+
+    pub fn is_client_connected(&self) -> bool {
+        self.connection
+            .as_ref()
+            .is_some_and(|connection| connection.is_connected())
+    }
+
+    pub fn breakpoints(&self) -> impl Iterator<Item = &Breakpoint> {
+        self.breakpoints.iter()
+    }
+
+    pub fn set_breakpoint(&mut self, breakpoint: Breakpoint) {
+        self.breakpoints.insert(breakpoint);
+    }
+
+    pub fn clear_breakpoint(&mut self, breakpoint: &Breakpoint) -> bool {
+        self.breakpoints.remove(breakpoint)
+    }
+
+    pub fn break_on_throw(&self) -> bool {
+        self.break_on_throw
+    }
+
+    pub fn set_break_on_throw(&mut self, break_on_throw: bool) {
+        self.break_on_throw = break_on_throw;
+    }
+
+    /// Stops execution on the next source line.
+    pub fn request_pause(&mut self) {
+        self.pause_requested = true;
+    }
+
+    pub fn last_stop(&self) -> Option<&StopInfo> {
+        self.last_stop.as_ref()
+    }
+
+    /// Whether execution stopped without a client to resume it, and is
+    /// waiting for [`Self::resume`].
+    pub fn is_suspended(&self) -> bool {
+        self.suspended
+    }
+
+    /// Resumes execution after a stop that was kept for the debug UI.
+    ///
+    /// As the interpreter has already moved on, a step will stop relative to
+    /// the depth of the last stop, once the player is running again.
+    pub fn resume(&mut self, step: Option<StepMode>) {
+        self.suspended = false;
+        self.step = step.zip(self.last_stop.as_ref().map(|stop| stop.depth));
+    }
+
+    /// Returns the source position of the frame at the given call depth.
+    pub fn position(&self, depth: usize) -> Option<SourcePosition> {
+        self.frames
+            .get(depth.checked_sub(1)?)
+            .and_then(FrameState::position)
+    }
+
+    /// Returns the registers that the frame at the given call depth named.
+    pub fn register_names(&self, depth: usize) -> &[(u32, String)] {
+        match depth.checked_sub(1).and_then(|i| self.frames.get(i)) {
+            Some(frame) => &frame.register_names,
+            None => &[],
+        }
+    }
+
+    fn frame_mut(&mut self, depth: usize) -> &mut FrameState {
+        let index = depth.saturating_sub(1);
+        self.frames.truncate(index + 1);
+        if self.frames.len() <= index {
+            self.frames.resize_with(index + 1, Default::default);
+        }
+        &mut self.frames[index]
+    }
+
+    /// Called when the frame at the given depth enters a source file.
+    ///
+    /// This blocks if the client wants to set breakpoints in a new file.
+    pub fn on_file(&mut self, depth: usize, raw_file: &str) {
+        let file = normalize_file_name(raw_file);
+        if let Some(connection) = &mut self.connection {
+            if !self.known_files.contains(&file) {
+                self.known_files.insert(file.clone());
+                connection.file_loaded(raw_file);
+            }
+        }
+
+        let frame = self.frame_mut(depth);
+        if frame.file.as_ref() != Some(&file) {
+            *frame = FrameState {
+                file: Some(file),
+                ..Default::default()
+            };
+        }
+    }
+
+    /// Called when the frame at the given depth names one of its registers.
+    pub fn on_register_name(&mut self, depth: usize, register: u32, name: String) {
+        let names = &mut self.frame_mut(depth).register_names;
+        match names.iter_mut().find(|(r, _)| *r == register) {
+            Some(entry) => entry.1 = name,
+            None => names.push((register, name)),
+        }
+    }
+
+    /// Called when the frame at the given depth reaches a new source line.
+    ///
+    /// Returns why execution should stop here, if it should.
+    pub fn on_line(&mut self, depth: usize, line: u32) -> Option<StopReason> {
+        self.unwinding = false;
+        self.poll_commands();
+
+        let frame = self.frame_mut(depth);
+        frame.line = line;
+        let position = frame.position();
+
+        // The rest of the current frame of the movie still runs while the
+        // player is suspended, so only the first stop is kept.
+        if self.suspended {
+            return None;
+        }
+
+        if std::mem::take(&mut self.pause_requested) {
+            return Some(StopReason::Pause);
+        }
+
+        let step_done = match self.step {
+            Some((StepMode::Into, _)) => true,
+            Some((StepMode::Over, from)) => depth <= from,
+            Some((StepMode::Out, from)) => depth < from,
+            None => false,
+        };
+        if step_done {
+            return Some(StopReason::Step);
+        }
+
+        let position = position?;
+        self.breakpoints
+            .iter()
+            .any(|breakpoint| breakpoint.matches(&position))
+            .then_some(StopReason::Breakpoint)
+    }
+
+    /// Whether an exception that was just thrown should stop execution.
+    pub fn should_break_on_throw(&self) -> bool {
+        self.break_on_throw && !self.unwinding
+    }
+
+    /// Called when a thrown exception was caught by a handler.
+    pub fn on_exception_caught(&mut self) {
+        self.unwinding = false;
+    }
+
+    /// Stops execution, reporting the given state.
+    ///
+    /// With a connected client, this blocks until the client resumes
+    /// execution.
+    pub fn stop(&mut self, info: StopInfo) {
+        self.step = None;
+        self.pause_requested = false;
+        if matches!(info.reason, StopReason::Exception(_)) {
+            self.unwinding = true;
+        }
+
+        if !self.is_client_connected() {
+            // Keep the first stop until the debug UI resumes the player.
+            if !self.suspended {
+                self.last_stop = Some(info);
+                self.suspended = true;
+            }
+            return;
+        }
+
+        let depth = info.depth;
+        self.send(DebuggerEvent::Stopped(info.clone()));
+        self.last_stop = Some(info);
+        self.suspended = false;
+
+        while let Some(command) = self.connection.as_mut().and_then(|c| c.wait_for_command()) {
+            if let Some(step) = self.handle_command(command, Some(depth)) {
+                self.step = step.zip(Some(depth));
+                return;
+            }
+        }
+
+        // The client went away, so let the movie run.
+        self.step = None;
+    }
+
+    /// Forwards a `trace` message to the client.
+    pub fn trace(&mut self, message: &str) {
+        if let Some(connection) = &mut self.connection {
+            connection.trace(message);
+        }
+    }
+
+    /// Handles the commands the client sent while the movie was running.
+    pub fn poll_commands(&mut self) {
+        while let Some(command) = self.connection.as_mut().and_then(|c| c.poll_command()) {
+            self.handle_command(command, None);
+        }
+    }
+
+    /// Handles a command, given the depth of the stopped frame if execution
+    /// is stopped.
+    ///
+    /// Returns `Some` if the command resumes execution, with the step to take.
+    fn handle_command(
+        &mut self,
+        command: DebuggerCommand,
+        stopped_depth: Option<usize>,
+    ) -> Option<Option<StepMode>> {
+        let stop = stopped_depth.and(self.last_stop.as_ref());
+        let event = match command {
+            DebuggerCommand::SetBreakpoint(breakpoint) => {
+                self.breakpoints.insert(breakpoint);
+                DebuggerEvent::Ok
+            }
+            DebuggerCommand::ClearBreakpoint(breakpoint) => {
+                if self.breakpoints.remove(&breakpoint) {
+                    DebuggerEvent::Ok
+                } else {
+                    DebuggerEvent::Error(format!("no breakpoint at {breakpoint}"))
+                }
+            }
+            DebuggerCommand::ListBreakpoints => {
+                DebuggerEvent::Breakpoints(self.breakpoints.iter().cloned().collect())
+            }
+            DebuggerCommand::BreakOnThrow(break_on_throw) => {
+                self.break_on_throw = break_on_throw;
+                DebuggerEvent::Ok
+            }
+            DebuggerCommand::Pause => {
+                if stopped_depth.is_none() {
+                    self.pause_requested = true;
+                }
+                DebuggerEvent::Ok
+            }
+            DebuggerCommand::Continue | DebuggerCommand::Step(_) if stopped_depth.is_none() => {
+                DebuggerEvent::Error("not stopped".to_string())
+            }
+            DebuggerCommand::Continue => {
+                self.send(DebuggerEvent::Ok);
+                return Some(None);
+            }
+            DebuggerCommand::Step(mode) => {
+                self.send(DebuggerEvent::Ok);
+                return Some(Some(mode));
+            }
+            DebuggerCommand::Backtrace => match stop {
+                Some(stop) => DebuggerEvent::Backtrace(stop.backtrace.clone()),
+                None => DebuggerEvent::Error("not stopped".to_string()),
+            },
+            DebuggerCommand::Locals => variables(stop.map(|stop| &stop.locals)),
+            DebuggerCommand::Registers => variables(stop.map(|stop| &stop.registers)),
+            DebuggerCommand::Scope => variables(stop.map(|stop| &stop.scope)),
+            DebuggerCommand::Stack => variables(stop.map(|stop| &stop.stack)),
+        };
+        self.send(event);
+        None
+    }
+
+    fn send(&mut self, event: DebuggerEvent) {
+        if let Some(connection) = &mut self.connection {
+            connection.send(event);
+        }
+    }
+}
+
+fn variables(variables: Option<&Vec<Variable>>) -> DebuggerEvent {
+    match variables {
+        Some(variables) => DebuggerEvent::Variables(variables.clone()),
+        None => DebuggerEvent::Error("not stopped".to_string()),
+    }
+}
+
+impl VariableValue {
+    /// Copies a value for the debugger, without running any ActionScript.
+    pub fn new(value: Value<'_>) -> Self {
+        match value {
+            Value::Undefined => VariableValue::Undefined,
+            Value::Null => VariableValue::Null,
+            Value::Bool(value) => VariableValue::Bool(value),
+            Value::Number(value) => VariableValue::Number(value),
+            Value::Integer(value) => VariableValue::Number(value.into()),
+            Value::String(value) => VariableValue::String(value.to_string()),
+            Value::Object(object) => {
+                VariableValue::Object(if let Some(class) = object.as_class_object() {
+                    format!(
+                        "[class {}]",
+                        class.inner_class_definition().name().local_name()
+                    )
+                } else if let Some(error) = object.as_error_object() {
+                    match error.display() {
+                        Ok(display) => display.to_string(),
+                        Err(_) => "[object Error]".to_string(),
+                    }
+                } else {
+                    format!("[object {}]", object.instance_class().name().local_name())
+                })
+            }
+        }
+    }
+}
+
+/// Describes a value for the debugger, without running any ActionScript.
+pub fn describe_value(value: Value<'_>) -> String {
+    VariableValue::new(value).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_flex_file_names() {
+        assert_eq!(
+            normalize_file_name(r"C:\dev\src;com\example;Main.as"),
+            "C:/dev/src/com/example/Main.as"
+        );
+        assert_eq!(
+            normalize_file_name("/home/dev/src;;Main.as"),
+            "/home/dev/src/Main.as"
+        );
+        assert_eq!(normalize_file_name("Main.as"), "Main.as");
+    }
+
+    #[test]
+    fn breakpoints_match_path_suffixes() {
+        let position = SourcePosition {
+            file: normalize_file_name(r"C:\dev\src;com\example;Main.as"),
+            line: 12,
+        };
+
+        assert!(Breakpoint::new("Main.as", 12).matches(&position));
+        assert!(Breakpoint::new(r"com\example\Main.as", 12).matches(&position));
+        assert!(Breakpoint::new("C:/dev/src/com/example/Main.as", 12).matches(&position));
+        assert!(!Breakpoint::new("Main.as", 13).matches(&position));
+        assert!(!Breakpoint::new("ain.as", 12).matches(&position));
+        assert!(!Breakpoint::new("other/Main.as", 12).matches(&position));
+    }
+
+    #[test]
+    fn steps_stop_relative_to_the_starting_depth() {
+        let mut debugger = Avm2Debugger::new(None);
+        debugger.set_enabled(true);
+        debugger.on_file(2, "Main.as");
+
+        debugger.step = Some((StepMode::Over, 2));
+        assert_eq!(debugger.on_line(3, 1), None);
+        assert_eq!(debugger.on_line(2, 5), Some(StopReason::Step));
+
+        debugger.step = Some((StepMode::Out, 2));
+        assert_eq!(debugger.on_line(2, 6), None);
+        assert_eq!(debugger.on_line(1, 9), Some(StopReason::Step));
+
+        debugger.step = Some((StepMode::Into, 1));
+        assert_eq!(debugger.on_line(2, 1), Some(StopReason::Step));
+    }
+
+    fn stop_info(debugger: &Avm2Debugger, reason: StopReason, depth: usize) -> StopInfo {
+        StopInfo {
+            reason,
+            depth,
+            backtrace: vec![StackFrame {
+                name: "Test/test()".to_string(),
+                position: debugger.position(depth),
+            }],
+            locals: vec![],
+            registers: vec![],
+            scope: vec![],
+            stack: vec![],
+        }
+    }
+
+    #[test]
+    fn breakpoints_suspend_until_stepped() {
+        let mut debugger = Avm2Debugger::new(None);
+        debugger.set_enabled(true);
+        debugger.set_breakpoint(Breakpoint::new("Main.as", 4));
+        debugger.on_file(1, "Main.as");
+
+        assert_eq!(debugger.on_line(1, 4), Some(StopReason::Breakpoint));
+        debugger.stop(stop_info(&debugger, StopReason::Breakpoint, 1));
+        assert!(debugger.is_suspended());
+
+        // Nothing else stops the movie until the debug UI resumes it.
+        debugger.on_file(2, "Main.as");
+        assert_eq!(debugger.on_line(2, 4), None);
+        assert_eq!(debugger.on_line(1, 5), None);
+        assert!(debugger.is_suspended());
+
+        debugger.resume(Some(StepMode::Over));
+        assert!(!debugger.is_suspended());
+        assert_eq!(debugger.on_line(2, 8), None);
+        assert_eq!(debugger.on_line(1, 6), Some(StopReason::Step));
+        debugger.stop(stop_info(&debugger, StopReason::Step, 1));
+        assert!(debugger.is_suspended());
+        assert_eq!(
+            debugger.last_stop().and_then(StopInfo::position),
+            Some(&SourcePosition {
+                file: "Main.as".to_string(),
+                line: 6
+            })
+        );
+
+        debugger.resume(None);
+        assert!(!debugger.is_suspended());
+        assert_eq!(debugger.on_line(1, 7), None);
+        assert_eq!(debugger.on_line(1, 4), Some(StopReason::Breakpoint));
+    }
+
+    #[test]
+    fn breakpoints_stop_on_their_line() {
+        let mut debugger = Avm2Debugger::new(None);
+        debugger.set_enabled(true);
+        debugger.set_breakpoint(Breakpoint::new("Main.as", 4));
+        debugger.on_file(1, r"C:\src;;Main.as");
+
+        assert_eq!(debugger.on_line(1, 3), None);
+        assert_eq!(debugger.on_line(1, 4), Some(StopReason::Breakpoint));
+        assert_eq!(
+            debugger.position(1),
+            Some(SourcePosition {
+                file: "C:/src/Main.as".to_string(),
+                line: 4
+            })
+        );
+    }
+}
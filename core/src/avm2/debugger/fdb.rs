@@ -0,0 +1,850 @@
+//! A connector for the Flash Player debugger protocol, as spoken by `fdb` and
+//! IDEs built on the Flex SDK's debugger library.
+//!
+//! Unlike our own debug server, the debugger listens (usually on port 7935)
+//! and the player connects to it. Every message is framed as a little-endian
+//! `u32` length of the payload and a `u32` message type, followed by the
+//! payload. Payloads are made of little-endian integers and null-terminated
+//! UTF-8 strings. We announce 4-byte pointers, which we use as frame ids.
+//!
+//! Source files are reported as modules with ids starting at 1, and locations
+//! are packed into a `u32` as `line << 16 | module`. The player stops to let
+//! the debugger set breakpoints when the first file is loaded, and syncs with
+//! it after reporting each later file.
+//!
+//! Only the stopped frame can be inspected, and objects are reported as
+//! strings describing them, as they can't be kept around after the stop.
+
+use crate::avm2::debugger::{
+    normalize_file_name, Breakpoint, DebuggerCommand, DebuggerConnection, DebuggerEvent,
+    SourcePosition, StepMode, StopInfo, StopReason, Variable, VariableValue,
+};
+use std::collections::{BTreeSet, VecDeque};
+use std::io::{self, Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::thread;
+use std::time::Duration;
+
+/// The port the debugger listens on by default.
+pub const DEFAULT_PORT: u16 = 7935;
+
+/// The version of the debugger protocol we speak.
+const PROTOCOL_VERSION: u32 = 10;
+
+/// How long to wait for a debugger to accept the connection.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// The largest message we accept from the debugger.
+const MAX_MESSAGE_LENGTH: usize = 16 * 1024 * 1024;
+
+/// Message types sent by the player, named like in the Flex SDK.
+mod player_message {
+    pub const TRACE: u32 = 5;
+    pub const SCRIPT: u32 = 14;
+    pub const ASK_BREAKPOINTS: u32 = 15;
+    pub const BREAK_AT: u32 = 16;
+    pub const CONTINUE: u32 = 17;
+    pub const SET_BREAKPOINT: u32 = 19;
+    pub const NUM_SCRIPT: u32 = 20;
+    pub const REMOVE_BREAKPOINT: u32 = 22;
+    pub const PROCESS_TAG: u32 = 25;
+    pub const VERSION: u32 = 26;
+    pub const BREAK_AT_EXT: u32 = 27;
+    pub const SQUELCH: u32 = 29;
+    pub const FRAME: u32 = 31;
+    pub const OPTION: u32 = 32;
+    pub const GET_SWF: u32 = 34;
+    pub const GET_SWD: u32 = 35;
+    pub const ERROR_EXCEPTION: u32 = 36;
+    pub const BREAK_REASON: u32 = 40;
+    pub const SWF_INFO: u32 = 42;
+}
+
+/// Message types sent by the debugger, named like in the Flex SDK.
+mod debugger_message {
+    pub const EXIT: u32 = 13;
+    pub const CONTINUE: u32 = 15;
+    pub const STOP_DEBUG: u32 = 16;
+    pub const SET_BREAKPOINTS: u32 = 17;
+    pub const REMOVE_BREAKPOINTS: u32 = 18;
+    pub const REMOVE_ALL_BREAKPOINTS: u32 = 19;
+    pub const STEP_OVER: u32 = 20;
+    pub const STEP_INTO: u32 = 21;
+    pub const STEP_OUT: u32 = 22;
+    pub const PROCESSED_TAG: u32 = 23;
+    pub const SET_SQUELCH: u32 = 24;
+    pub const GET_FRAME: u32 = 26;
+    pub const SET_OPTION: u32 = 28;
+    pub const STEP_CONTINUE: u32 = 31;
+    pub const GET_SWF: u32 = 32;
+    pub const GET_SWD: u32 = 33;
+    pub const GET_BREAK_REASON: u32 = 35;
+    pub const SWF_INFO: u32 = 38;
+    pub const PASS_ALL_EXCEPTIONS_TO_DEBUGGER: u32 = 51;
+}
+
+/// Why the player is suspended, as reported in `InBreakReason`.
+mod suspend_reason {
+    pub const UNKNOWN: u16 = 0;
+    pub const BREAKPOINT: u16 = 1;
+    pub const FAULT: u16 = 3;
+    pub const STOP_REQUEST: u16 = 4;
+    pub const STEP: u16 = 5;
+    pub const HALT_OPCODE: u16 = 6;
+    pub const SCRIPT_LOADED: u16 = 7;
+}
+
+/// Variable types, which are the AMF0 type markers.
+mod variable_type {
+    pub const NUMBER: u16 = 0;
+    pub const BOOLEAN: u16 = 1;
+    pub const STRING: u16 = 2;
+    pub const NULL: u16 = 5;
+    pub const UNDEFINED: u16 = 6;
+}
+
+/// The variable flag for locals of a function.
+const VARIABLE_IS_LOCAL: u32 = 0x20;
+
+/// The header of a SWD file, holding the debug information of a SWF.
+const SWD_HEADER: &[u8] = b"FWD\x07";
+
+/// The SWD tag describing a source file.
+const SWD_DEBUG_SCRIPT: u32 = 0;
+
+/// The SWD tag mapping a source line to a bytecode offset.
+const SWD_DEBUG_OFFSET: u32 = 1;
+
+/// A message, as its type and payload.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Message {
+    pub kind: u32,
+    pub data: Vec<u8>,
+}
+
+impl Message {
+    pub fn new(kind: u32) -> Self {
+        Self {
+            kind,
+            data: Vec::new(),
+        }
+    }
+
+    pub fn byte(mut self, value: u8) -> Self {
+        self.data.push(value);
+        self
+    }
+
+    pub fn word(mut self, value: u16) -> Self {
+        self.data.extend_from_slice(&value.to_le_bytes());
+        self
+    }
+
+    pub fn dword(mut self, value: u32) -> Self {
+        self.data.extend_from_slice(&value.to_le_bytes());
+        self
+    }
+
+    pub fn ptr(self, value: u32) -> Self {
+        self.dword(value)
+    }
+
+    pub fn string(mut self, value: &str) -> Self {
+        self.data.extend_from_slice(value.as_bytes());
+        self.data.push(0);
+        self
+    }
+
+    pub fn bytes(mut self, value: &[u8]) -> Self {
+        self.data.extend_from_slice(value);
+        self
+    }
+
+    pub fn reader(&self) -> MessageReader<'_> {
+        MessageReader { data: &self.data }
+    }
+
+    pub fn read_from(reader: &mut impl Read) -> io::Result<Self> {
+        let mut header = [0; 8];
+        reader.read_exact(&mut header)?;
+        let length = u32::from_le_bytes([header[0], header[1], header[2], header[3]]) as usize;
+        let kind = u32::from_le_bytes([header[4], header[5], header[6], header[7]]);
+        if length > MAX_MESSAGE_LENGTH {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Debugger message of {length} bytes is too long"),
+            ));
+        }
+
+        let mut data = vec![0; length];
+        reader.read_exact(&mut data)?;
+        Ok(Self { kind, data })
+    }
+
+    pub fn write_to(&self, writer: &mut impl Write) -> io::Result<()> {
+        let mut buffer = Vec::with_capacity(self.data.len() + 8);
+        buffer.extend_from_slice(&(self.data.len() as u32).to_le_bytes());
+        buffer.extend_from_slice(&self.kind.to_le_bytes());
+        buffer.extend_from_slice(&self.data);
+        writer.write_all(&buffer)
+    }
+}
+
+/// Reads the fields of a message payload in order.
+pub struct MessageReader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> MessageReader<'a> {
+    fn take(&mut self, length: usize) -> Option<&'a [u8]> {
+        if self.data.len() < length {
+            return None;
+        }
+        let (value, rest) = self.data.split_at(length);
+        self.data = rest;
+        Some(value)
+    }
+
+    pub fn byte(&mut self) -> Option<u8> {
+        self.take(1).map(|bytes| bytes[0])
+    }
+
+    pub fn word(&mut self) -> Option<u16> {
+        self.take(2)
+            .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    pub fn dword(&mut self) -> Option<u32> {
+        self.take(4)
+            .map(|bytes| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    pub fn ptr(&mut self) -> Option<u32> {
+        self.dword()
+    }
+
+    pub fn string(&mut self) -> Option<String> {
+        let end = self.data.iter().position(|&byte| byte == 0)?;
+        let value = String::from_utf8_lossy(&self.data[..end]).into_owned();
+        self.data = &self.data[end + 1..];
+        Some(value)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+}
+
+/// Packs a module and line into a breakpoint location.
+pub fn pack_location(module: u32, line: u32) -> u32 {
+    (line << 16) | (module & 0xffff)
+}
+
+/// Unpacks a breakpoint location into its module and line.
+pub fn unpack_location(location: u32) -> (u32, u32) {
+    (location & 0xffff, location >> 16)
+}
+
+/// What the connection is waiting for while blocking on the debugger.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Wait {
+    /// A command that resumes a stop.
+    Command,
+
+    /// `OutContinue`, after asking for breakpoints.
+    Continue,
+
+    /// `OutProcessedTag`, after reporting a new file.
+    ProcessedTag,
+}
+
+/// A connection to a Flash Player debugger such as `fdb`.
+pub struct FdbConnection {
+    stream: TcpStream,
+    messages: Receiver<Option<Message>>,
+    connected: bool,
+
+    /// The normalized names of the source files, indexed by module id - 1.
+    modules: Vec<String>,
+
+    /// The breakpoints the debugger set, as packed locations.
+    breakpoints: BTreeSet<u32>,
+
+    /// Commands decoded while waiting for something else.
+    commands: VecDeque<DebuggerCommand>,
+
+    /// The state of the player when it last stopped, until it resumes.
+    stop: Option<StopInfo>,
+
+    /// Whether the player is stopped waiting for breakpoints.
+    asking_breakpoints: bool,
+
+    /// Whether `OutProcessedTag` arrived since we last reported a file.
+    processed_tag: bool,
+
+    /// Whether `trace` messages are squelched.
+    squelch: bool,
+}
+
+impl FdbConnection {
+    /// Connects to a debugger listening on the given address, and sends it
+    /// our version.
+    pub fn connect(addr: SocketAddr) -> io::Result<Self> {
+        let stream = TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT)?;
+        stream.set_nodelay(true)?;
+        let mut reader = stream.try_clone()?;
+        let (sender, messages) = mpsc::channel();
+
+        thread::Builder::new()
+            .name("Flash debugger connection".to_string())
+            .spawn(move || read_messages(&mut reader, sender))?;
+
+        let mut connection = Self {
+            stream,
+            messages,
+            connected: true,
+            modules: Vec::new(),
+            breakpoints: BTreeSet::new(),
+            commands: VecDeque::new(),
+            stop: None,
+            asking_breakpoints: false,
+            processed_tag: false,
+            squelch: false,
+        };
+        connection.write(
+            Message::new(player_message::VERSION)
+                .dword(PROTOCOL_VERSION)
+                .byte(4),
+        );
+
+        tracing::info!("Connected to Flash debugger at {addr}");
+        Ok(connection)
+    }
+
+    fn write(&mut self, message: Message) {
+        if !self.connected {
+            return;
+        }
+        if let Err(e) = message.write_to(&mut self.stream) {
+            tracing::warn!("Couldn't write to Flash debugger: {e}");
+            self.connected = false;
+        }
+    }
+
+    fn module_id(&self, file: &str) -> Option<u32> {
+        self.modules
+            .iter()
+            .position(|module| module == file)
+            .map(|i| i as u32 + 1)
+    }
+
+    fn location(&self, position: Option<&SourcePosition>) -> u32 {
+        position
+            .and_then(|position| {
+                let module = self.module_id(&position.file)?;
+                Some(pack_location(module, position.line))
+            })
+            .unwrap_or(0)
+    }
+
+    fn breakpoint(&self, location: u32) -> Option<Breakpoint> {
+        let (module, line) = unpack_location(location);
+        let file = self.modules.get((module as usize).checked_sub(1)?)?;
+        Some(Breakpoint::new(file, line))
+    }
+
+    /// Blocks until the debugger sends what we're waiting for, queueing any
+    /// other commands.
+    ///
+    /// Returns `None` if the debugger disconnected or we weren't waiting for a
+    /// command.
+    fn wait(&mut self, wait: Wait) -> Option<DebuggerCommand> {
+        loop {
+            if wait == Wait::Command {
+                if let Some(command) = self.commands.pop_front() {
+                    return Some(command);
+                }
+            }
+            if !self.connected {
+                return None;
+            }
+
+            let Ok(Some(message)) = self.messages.recv() else {
+                self.connected = false;
+                return None;
+            };
+            match self.handle_message(message) {
+                Some(DebuggerCommand::Continue) if wait == Wait::Continue => return None,
+                Some(command) => self.commands.push_back(command),
+                None if wait == Wait::ProcessedTag && std::mem::take(&mut self.processed_tag) => {
+                    return None;
+                }
+                None => {}
+            }
+        }
+    }
+
+    /// Handles a message from the debugger, answering queries directly.
+    ///
+    /// Returns the command for the debugger, if the message is one. Changes to
+    /// breakpoints are queued instead, as there may be several of them.
+    fn handle_message(&mut self, message: Message) -> Option<DebuggerCommand> {
+        let mut reader = message.reader();
+        match message.kind {
+            debugger_message::CONTINUE | debugger_message::STEP_CONTINUE => {
+                Some(DebuggerCommand::Continue)
+            }
+            debugger_message::STEP_INTO => Some(DebuggerCommand::Step(StepMode::Into)),
+            debugger_message::STEP_OVER => Some(DebuggerCommand::Step(StepMode::Over)),
+            debugger_message::STEP_OUT => Some(DebuggerCommand::Step(StepMode::Out)),
+            debugger_message::SET_BREAKPOINTS | debugger_message::REMOVE_BREAKPOINTS => {
+                let set = message.kind == debugger_message::SET_BREAKPOINTS;
+                let count = reader.dword().unwrap_or(0);
+                let mut changed = Vec::new();
+                for _ in 0..count {
+                    let Some(location) = reader.dword() else {
+                        break;
+                    };
+                    let Some(breakpoint) = self.breakpoint(location) else {
+                        continue;
+                    };
+                    if set {
+                        self.breakpoints.insert(location);
+                        self.commands
+                            .push_back(DebuggerCommand::SetBreakpoint(breakpoint));
+                    } else if self.breakpoints.remove(&location) {
+                        self.commands
+                            .push_back(DebuggerCommand::ClearBreakpoint(breakpoint));
+                    }
+                    changed.push(location);
+                }
+
+                let reply = if set {
+                    player_message::SET_BREAKPOINT
+                } else {
+                    player_message::REMOVE_BREAKPOINT
+                };
+                let reply = changed
+                    .iter()
+                    .fold(Message::new(reply).dword(changed.len() as u32), |m, &l| {
+                        m.dword(l)
+                    });
+                self.write(reply);
+                None
+            }
+            debugger_message::REMOVE_ALL_BREAKPOINTS => {
+                for location in std::mem::take(&mut self.breakpoints) {
+                    if let Some(breakpoint) = self.breakpoint(location) {
+                        self.commands
+                            .push_back(DebuggerCommand::ClearBreakpoint(breakpoint));
+                    }
+                }
+                None
+            }
+            debugger_message::PASS_ALL_EXCEPTIONS_TO_DEBUGGER => {
+                Some(DebuggerCommand::BreakOnThrow(true))
+            }
+            debugger_message::SET_OPTION => {
+                let name = reader.string().unwrap_or_default();
+                let mut value = reader.string().unwrap_or_default();
+                let command = match name.as_str() {
+                    "break_on_fault" => Some(DebuggerCommand::BreakOnThrow(value == "on")),
+                    // We only report lines in the packed format.
+                    name if name.starts_with("wide_line") => {
+                        value = "off".to_string();
+                        None
+                    }
+                    _ => None,
+                };
+                self.write(
+                    Message::new(player_message::OPTION)
+                        .string(&name)
+                        .string(&value),
+                );
+                command
+            }
+            debugger_message::SET_SQUELCH => {
+                let squelch = reader.dword().unwrap_or(0);
+                self.squelch = squelch != 0;
+                self.write(Message::new(player_message::SQUELCH).dword(squelch));
+                None
+            }
+            debugger_message::GET_BREAK_REASON => {
+                let reason = match self.stop.as_ref().map(|stop| &stop.reason) {
+                    Some(StopReason::Breakpoint) => suspend_reason::BREAKPOINT,
+                    Some(StopReason::BreakpointOpcode) => suspend_reason::HALT_OPCODE,
+                    Some(StopReason::Step) => suspend_reason::STEP,
+                    Some(StopReason::Pause) => suspend_reason::STOP_REQUEST,
+                    Some(StopReason::Exception(_)) => suspend_reason::FAULT,
+                    None if self.asking_breakpoints => suspend_reason::SCRIPT_LOADED,
+                    None => suspend_reason::UNKNOWN,
+                };
+                self.write(
+                    Message::new(player_message::BREAK_REASON)
+                        .word(reason)
+                        .word(0)
+                        .dword(0)
+                        .dword(0)
+                        .dword(0),
+                );
+                None
+            }
+            debugger_message::GET_FRAME => {
+                let depth = reader.dword().unwrap_or(0);
+                let mut reply = Message::new(player_message::FRAME).dword(depth);
+                if let Some(stop) = self.stop.as_ref().filter(|_| depth == 0) {
+                    for variable in &stop.locals {
+                        reply = write_variable(reply, variable, VARIABLE_IS_LOCAL);
+                    }
+                    for variable in &stop.scope {
+                        reply = write_variable(reply, variable, 0);
+                    }
+                }
+                self.write(reply);
+                None
+            }
+            debugger_message::GET_SWD => {
+                let swd = self.swd();
+                self.write(
+                    Message::new(player_message::GET_SWD)
+                        .dword(swd.len() as u32)
+                        .bytes(&swd),
+                );
+                None
+            }
+            debugger_message::GET_SWF => {
+                // The debugger only needs the SWF to disassemble it.
+                self.write(Message::new(player_message::GET_SWF).dword(0));
+                None
+            }
+            debugger_message::SWF_INFO => {
+                self.write(Message::new(player_message::SWF_INFO).word(0));
+                None
+            }
+            debugger_message::PROCESSED_TAG => {
+                self.processed_tag = true;
+                None
+            }
+            debugger_message::STOP_DEBUG | debugger_message::EXIT => {
+                tracing::info!("Flash debugger detached");
+                self.connected = false;
+                None
+            }
+            kind => {
+                tracing::debug!("Ignoring Flash debugger message {kind}");
+                None
+            }
+        }
+    }
+
+    /// Builds a SWD file with the line information we know about.
+    fn swd(&self) -> Vec<u8> {
+        let mut swd = Message::new(0).bytes(SWD_HEADER);
+        for (i, file) in self.modules.iter().enumerate() {
+            swd = swd
+                .dword(SWD_DEBUG_SCRIPT)
+                .dword(i as u32 + 1)
+                .dword(0)
+                .string(file)
+                .string("");
+        }
+        for &location in &self.breakpoints {
+            let (module, line) = unpack_location(location);
+            swd = swd
+                .dword(SWD_DEBUG_OFFSET)
+                .dword(module)
+                .dword(line)
+                .dword(0);
+        }
+        swd.data
+    }
+}
+
+fn write_variable(message: Message, variable: &Variable, flags: u32) -> Message {
+    let message = message.ptr(0).string(&variable.name);
+    match &variable.value {
+        VariableValue::Undefined => message.word(variable_type::UNDEFINED).dword(flags),
+        VariableValue::Null => message.word(variable_type::NULL).dword(flags),
+        VariableValue::Bool(value) => message
+            .word(variable_type::BOOLEAN)
+            .dword(flags)
+            .byte(*value as u8),
+        VariableValue::Number(value) => message
+            .word(variable_type::NUMBER)
+            .dword(flags)
+            .string(&value.to_string()),
+        VariableValue::String(value) | VariableValue::Object(value) => message
+            .word(variable_type::STRING)
+            .dword(flags)
+            .string(value),
+    }
+}
+
+fn read_messages(reader: &mut TcpStream, sender: Sender<Option<Message>>) {
+    loop {
+        match Message::read_from(reader) {
+            Ok(message) => {
+                if sender.send(Some(message)).is_err() {
+                    return;
+                }
+            }
+            Err(e) => {
+                if e.kind() != io::ErrorKind::UnexpectedEof {
+                    tracing::warn!("Couldn't read from Flash debugger: {e}");
+                }
+                let _ = sender.send(None);
+                return;
+            }
+        }
+    }
+}
+
+impl DebuggerConnection for FdbConnection {
+    fn is_connected(&self) -> bool {
+        self.connected
+    }
+
+    fn poll_command(&mut self) -> Option<DebuggerCommand> {
+        loop {
+            if let Some(command) = self.commands.pop_front() {
+                return Some(command);
+            }
+            if !self.connected {
+                return None;
+            }
+
+            match self.messages.try_recv() {
+                Ok(Some(message)) => {
+                    if let Some(command) = self.handle_message(message) {
+                        return Some(command);
+                    }
+                }
+                Ok(None) | Err(TryRecvError::Disconnected) => self.connected = false,
+                Err(TryRecvError::Empty) => return None,
+            }
+        }
+    }
+
+    fn wait_for_command(&mut self) -> Option<DebuggerCommand> {
+        let command = self.wait(Wait::Command);
+        if matches!(
+            command,
+            Some(DebuggerCommand::Continue | DebuggerCommand::Step(_)) | None
+        ) {
+            self.stop = None;
+            self.write(Message::new(player_message::CONTINUE));
+        }
+        command
+    }
+
+    fn send(&mut self, event: DebuggerEvent) {
+        // Other events answer requests of our own protocol, which the Flash
+        // debugger doesn't make.
+        let DebuggerEvent::Stopped(info) = event else {
+            return;
+        };
+
+        if let StopReason::Exception(description) = &info.reason {
+            self.write(Message::new(player_message::ERROR_EXCEPTION).string(description));
+        }
+
+        let location = self.location(info.position());
+        let name = info
+            .backtrace
+            .first()
+            .map(|frame| frame.name.as_str())
+            .unwrap_or_default();
+        self.write(
+            Message::new(player_message::BREAK_AT)
+                .dword(location)
+                .ptr(info.depth as u32)
+                .string(name),
+        );
+
+        let mut frames = Message::new(player_message::BREAK_AT_EXT)
+            .dword(location)
+            .dword(info.backtrace.len().saturating_sub(1) as u32);
+        for (i, frame) in info.backtrace.iter().enumerate().skip(1) {
+            frames = frames
+                .dword(self.location(frame.position.as_ref()))
+                .ptr(info.depth.saturating_sub(i) as u32)
+                .string(&frame.name);
+        }
+        self.write(frames);
+
+        self.stop = Some(info);
+    }
+
+    fn file_loaded(&mut self, file: &str) {
+        if !self.connected {
+            return;
+        }
+
+        self.modules.push(normalize_file_name(file));
+        let module = self.modules.len() as u32;
+        self.write(Message::new(player_message::NUM_SCRIPT).dword(module));
+        self.write(
+            Message::new(player_message::SCRIPT)
+                .dword(module)
+                .dword(0)
+                .string(file)
+                .string("")
+                .ptr(0),
+        );
+
+        if module == 1 {
+            // Like Flash Player, wait for breakpoints before running any code.
+            self.asking_breakpoints = true;
+            self.write(Message::new(player_message::ASK_BREAKPOINTS));
+            self.wait(Wait::Continue);
+            self.asking_breakpoints = false;
+            self.write(Message::new(player_message::CONTINUE));
+        } else {
+            self.write(Message::new(player_message::PROCESS_TAG));
+            self.wait(Wait::ProcessedTag);
+        }
+    }
+
+    fn trace(&mut self, message: &str) {
+        if !self.squelch {
+            self.write(Message::new(player_message::TRACE).string(message));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::debugger::{Avm2Debugger, StackFrame};
+    use std::net::TcpListener;
+
+    fn expect(stream: &mut TcpStream, kind: u32) -> Message {
+        let message = Message::read_from(stream).unwrap();
+        assert_eq!(message.kind, kind, "unexpected message {message:?}");
+        message
+    }
+
+    #[test]
+    fn encodes_messages() {
+        let message = Message::new(player_message::BREAK_AT)
+            .dword(pack_location(2, 40))
+            .ptr(1)
+            .string("Main/run()");
+        let mut buffer = Vec::new();
+        message.write_to(&mut buffer).unwrap();
+        assert_eq!(&buffer[..8], &[19, 0, 0, 0, 16, 0, 0, 0]);
+
+        let decoded = Message::read_from(&mut buffer.as_slice()).unwrap();
+        assert_eq!(decoded, message);
+        let mut reader = decoded.reader();
+        assert_eq!(reader.dword().map(unpack_location), Some((2, 40)));
+        assert_eq!(reader.ptr(), Some(1));
+        assert_eq!(reader.string().as_deref(), Some("Main/run()"));
+        assert!(reader.is_empty());
+        assert_eq!(reader.byte(), None);
+    }
+
+    #[test]
+    fn debugs_with_a_scripted_client() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let client = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            stream
+                .set_read_timeout(Some(Duration::from_secs(5)))
+                .unwrap();
+
+            let version = expect(&mut stream, player_message::VERSION);
+            assert_eq!(
+                version.data,
+                [PROTOCOL_VERSION.to_le_bytes().as_slice(), &[4]].concat()
+            );
+
+            expect(&mut stream, player_message::NUM_SCRIPT);
+            let script = expect(&mut stream, player_message::SCRIPT);
+            let mut reader = script.reader();
+            assert_eq!(reader.dword(), Some(1));
+            assert_eq!(reader.dword(), Some(0));
+            assert_eq!(reader.string().as_deref(), Some(r"C:\src;;Main.as"));
+            expect(&mut stream, player_message::ASK_BREAKPOINTS);
+
+            Message::new(debugger_message::SET_BREAKPOINTS)
+                .dword(1)
+                .dword(pack_location(1, 4))
+                .write_to(&mut stream)
+                .unwrap();
+            let reply = expect(&mut stream, player_message::SET_BREAKPOINT);
+            assert_eq!(reply.reader().dword(), Some(1));
+            Message::new(debugger_message::CONTINUE)
+                .write_to(&mut stream)
+                .unwrap();
+            expect(&mut stream, player_message::CONTINUE);
+
+            let break_at = expect(&mut stream, player_message::BREAK_AT);
+            let mut reader = break_at.reader();
+            assert_eq!(reader.dword(), Some(pack_location(1, 4)));
+            assert_eq!(reader.ptr(), Some(1));
+            assert_eq!(reader.string().as_deref(), Some("Main/run()"));
+            let break_at_ext = expect(&mut stream, player_message::BREAK_AT_EXT);
+            let mut reader = break_at_ext.reader();
+            assert_eq!(reader.dword(), Some(pack_location(1, 4)));
+            assert_eq!(reader.dword(), Some(0));
+
+            Message::new(debugger_message::GET_BREAK_REASON)
+                .write_to(&mut stream)
+                .unwrap();
+            let reason = expect(&mut stream, player_message::BREAK_REASON);
+            assert_eq!(reason.reader().word(), Some(suspend_reason::BREAKPOINT));
+
+            Message::new(debugger_message::GET_FRAME)
+                .dword(0)
+                .write_to(&mut stream)
+                .unwrap();
+            let frame = expect(&mut stream, player_message::FRAME);
+            let mut reader = frame.reader();
+            assert_eq!(reader.dword(), Some(0));
+            assert_eq!(reader.ptr(), Some(0));
+            assert_eq!(reader.string().as_deref(), Some("count"));
+            assert_eq!(reader.word(), Some(variable_type::NUMBER));
+            assert_eq!(reader.dword(), Some(VARIABLE_IS_LOCAL));
+            assert_eq!(reader.string().as_deref(), Some("3"));
+            assert!(reader.is_empty());
+
+            Message::new(debugger_message::STEP_OVER)
+                .write_to(&mut stream)
+                .unwrap();
+            expect(&mut stream, player_message::CONTINUE);
+
+            let trace = expect(&mut stream, player_message::TRACE);
+            assert_eq!(trace.reader().string().as_deref(), Some("hello"));
+        });
+
+        let connection = FdbConnection::connect(addr).unwrap();
+        let mut debugger = Avm2Debugger::new(Some(Box::new(connection)));
+        debugger.on_file(1, r"C:\src;;Main.as");
+        assert_eq!(debugger.on_line(1, 3), None);
+        assert_eq!(debugger.on_line(1, 4), Some(StopReason::Breakpoint));
+
+        debugger.stop(StopInfo {
+            reason: StopReason::Breakpoint,
+            depth: 1,
+            backtrace: vec![StackFrame {
+                name: "Main/run()".to_string(),
+                position: debugger.position(1),
+            }],
+            locals: vec![Variable {
+                name: "count".to_string(),
+                value: VariableValue::Number(3.0),
+            }],
+            registers: vec![],
+            scope: vec![],
+            stack: vec![],
+        });
+        assert_eq!(debugger.on_line(1, 5), Some(StopReason::Step));
+
+        debugger.trace("hello");
+        client.join().unwrap();
+    }
+}
@@ -0,0 +1,365 @@
+//! A line-based debug protocol over TCP.
+//!
+//! Each request is a single line of text:
+//!
+//! * `break <file>:<line>`, `delete <file>:<line>`, `breakpoints`
+//! * `catch on`, `catch off` (break when an exception is thrown)
+//! * `pause`, `continue`, `step`, `next`, `finish`
+//! * `backtrace`, `locals`, `registers`, `scope`, `stack`
+//!
+//! Requests are answered with `ok`, `error <message>`, or a list of `frame`,
+//! `var` or `breakpoint` lines terminated by `end`. When execution stops, the
+//! server sends `stopped <reason> <file>:<line>`, where the location is `?` if
+//! it's unknown. Exceptions are followed by the description of the thrown value.
+
+use crate::avm2::debugger::{
+    Breakpoint, DebuggerCommand, DebuggerConnection, DebuggerEvent, StepMode, StopReason,
+};
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use thiserror::Error;
+
+/// The greeting sent to clients when they connect.
+pub const GREETING: &str = "ruffle-avm2-debugger 1";
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ProtocolError {
+    #[error("unknown command {0:?}")]
+    UnknownCommand(String),
+
+    #[error("expected <file>:<line>, got {0:?}")]
+    InvalidLocation(String),
+
+    #[error("expected on or off, got {0:?}")]
+    InvalidToggle(String),
+}
+
+/// Parses a request line into a debugger command.
+pub fn parse_command(line: &str) -> Result<DebuggerCommand, ProtocolError> {
+    let line = line.trim();
+    let (command, argument) = line.split_once(' ').unwrap_or((line, ""));
+    let argument = argument.trim();
+
+    Ok(match command {
+        "break" | "b" => DebuggerCommand::SetBreakpoint(parse_location(argument)?),
+        "delete" | "d" => DebuggerCommand::ClearBreakpoint(parse_location(argument)?),
+        "breakpoints" => DebuggerCommand::ListBreakpoints,
+        "catch" => match argument {
+            "on" => DebuggerCommand::BreakOnThrow(true),
+            "off" => DebuggerCommand::BreakOnThrow(false),
+            _ => return Err(ProtocolError::InvalidToggle(argument.to_string())),
+        },
+        "pause" => DebuggerCommand::Pause,
+        "continue" | "c" => DebuggerCommand::Continue,
+        "step" | "s" => DebuggerCommand::Step(StepMode::Into),
+        "next" | "n" => DebuggerCommand::Step(StepMode::Over),
+        "finish" | "f" => DebuggerCommand::Step(StepMode::Out),
+        "backtrace" | "bt" => DebuggerCommand::Backtrace,
+        "locals" => DebuggerCommand::Locals,
+        "registers" => DebuggerCommand::Registers,
+        "scope" => DebuggerCommand::Scope,
+        "stack" => DebuggerCommand::Stack,
+        _ => return Err(ProtocolError::UnknownCommand(command.to_string())),
+    })
+}
+
+fn parse_location(location: &str) -> Result<Breakpoint, ProtocolError> {
+    location
+        .rsplit_once(':')
+        .and_then(|(file, line)| Some((file, line.parse().ok()?)))
+        .filter(|(file, _)| !file.is_empty())
+        .map(|(file, line)| Breakpoint::new(file, line))
+        .ok_or_else(|| ProtocolError::InvalidLocation(location.to_string()))
+}
+
+/// Formats an event as the lines that are sent to the client.
+pub fn format_event(event: &DebuggerEvent) -> String {
+    match event {
+        DebuggerEvent::Ok => "ok\n".to_string(),
+        DebuggerEvent::Error(message) => format!("error {message}\n"),
+        DebuggerEvent::Stopped(info) => {
+            let location = info
+                .position()
+                .map_or_else(|| "?".to_string(), ToString::to_string);
+            match &info.reason {
+                StopReason::Breakpoint => format!("stopped breakpoint {location}\n"),
+                StopReason::BreakpointOpcode => format!("stopped bkpt {location}\n"),
+                StopReason::Step => format!("stopped step {location}\n"),
+                StopReason::Pause => format!("stopped pause {location}\n"),
+                StopReason::Exception(value) => {
+                    format!("stopped exception {location} {value}\n")
+                }
+            }
+        }
+        DebuggerEvent::Breakpoints(breakpoints) => {
+            let mut output = String::new();
+            for breakpoint in breakpoints {
+                output.push_str(&format!("breakpoint {breakpoint}\n"));
+            }
+            output.push_str("end\n");
+            output
+        }
+        DebuggerEvent::Backtrace(frames) => {
+            let mut output = String::new();
+            for (i, frame) in frames.iter().enumerate() {
+                let location = frame
+                    .position
+                    .as_ref()
+                    .map_or_else(|| "?".to_string(), ToString::to_string);
+                output.push_str(&format!("frame {i} {location} {}\n", frame.name));
+            }
+            output.push_str("end\n");
+            output
+        }
+        DebuggerEvent::Variables(variables) => {
+            let mut output = String::new();
+            for variable in variables {
+                output.push_str(&format!("var {} = {}\n", variable.name, variable.value));
+            }
+            output.push_str("end\n");
+            output
+        }
+    }
+}
+
+enum ClientMessage {
+    Command(DebuggerCommand),
+    Disconnected,
+}
+
+type SharedClient = Arc<Mutex<Option<TcpStream>>>;
+
+/// A debugger connection that accepts one TCP client at a time.
+pub struct DebugServer {
+    local_addr: SocketAddr,
+    client: SharedClient,
+    messages: Receiver<ClientMessage>,
+}
+
+impl DebugServer {
+    /// Starts listening for clients on the given address.
+    pub fn bind(addr: impl ToSocketAddrs) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        let local_addr = listener.local_addr()?;
+        let client = SharedClient::default();
+        let (sender, messages) = mpsc::channel();
+
+        let thread_client = client.clone();
+        thread::Builder::new()
+            .name("AVM2 debug server".to_string())
+            .spawn(move || accept_clients(listener, thread_client, sender))?;
+
+        tracing::info!("AVM2 debugger listening on {local_addr}");
+        Ok(Self {
+            local_addr,
+            client,
+            messages,
+        })
+    }
+
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+}
+
+impl DebuggerConnection for DebugServer {
+    fn is_connected(&self) -> bool {
+        self.client.lock().expect("Client lock").is_some()
+    }
+
+    fn poll_command(&mut self) -> Option<DebuggerCommand> {
+        loop {
+            match self.messages.try_recv() {
+                Ok(ClientMessage::Command(command)) => return Some(command),
+                Ok(ClientMessage::Disconnected) => continue,
+                Err(TryRecvError::Empty | TryRecvError::Disconnected) => return None,
+            }
+        }
+    }
+
+    fn wait_for_command(&mut self) -> Option<DebuggerCommand> {
+        match self.messages.recv() {
+            Ok(ClientMessage::Command(command)) => Some(command),
+            Ok(ClientMessage::Disconnected) | Err(_) => None,
+        }
+    }
+
+    fn send(&mut self, event: DebuggerEvent) {
+        send_to_client(&self.client, &format_event(&event));
+    }
+}
+
+fn send_to_client(client: &SharedClient, message: &str) {
+    let mut client = client.lock().expect("Client lock");
+    if let Some(stream) = client.as_mut() {
+        if let Err(e) = stream.write_all(message.as_bytes()) {
+            tracing::warn!("Couldn't write to AVM2 debugger client: {e}");
+            *client = None;
+        }
+    }
+}
+
+fn accept_clients(listener: TcpListener, client: SharedClient, sender: Sender<ClientMessage>) {
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                tracing::warn!("Couldn't accept AVM2 debugger client: {e}");
+                continue;
+            }
+        };
+        let reader = match stream.try_clone() {
+            Ok(reader) => BufReader::new(reader),
+            Err(e) => {
+                tracing::warn!("Couldn't accept AVM2 debugger client: {e}");
+                continue;
+            }
+        };
+
+        *client.lock().expect("Client lock") = Some(stream);
+        send_to_client(&client, &format!("{GREETING}\n"));
+
+        for line in reader.lines() {
+            let Ok(line) = line else {
+                break;
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            match parse_command(&line) {
+                Ok(command) => {
+                    if sender.send(ClientMessage::Command(command)).is_err() {
+                        // The player is gone.
+                        return;
+                    }
+                }
+                Err(e) => send_to_client(&client, &format!("error {e}\n")),
+            }
+        }
+
+        *client.lock().expect("Client lock") = None;
+        if sender.send(ClientMessage::Disconnected).is_err() {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::debugger::{SourcePosition, StackFrame, StopInfo, Variable, VariableValue};
+    use std::time::Duration;
+
+    #[test]
+    fn parses_commands() {
+        assert_eq!(
+            parse_command("break com/example/Main.as:12"),
+            Ok(DebuggerCommand::SetBreakpoint(Breakpoint::new(
+                "com/example/Main.as",
+                12
+            )))
+        );
+        assert_eq!(
+            parse_command(r"d C:\src\Main.as:3"),
+            Ok(DebuggerCommand::ClearBreakpoint(Breakpoint::new(
+                "C:/src/Main.as",
+                3
+            )))
+        );
+        assert_eq!(
+            parse_command("catch on"),
+            Ok(DebuggerCommand::BreakOnThrow(true))
+        );
+        assert_eq!(
+            parse_command(" next "),
+            Ok(DebuggerCommand::Step(StepMode::Over))
+        );
+        assert_eq!(
+            parse_command("break Main.as"),
+            Err(ProtocolError::InvalidLocation("Main.as".to_string()))
+        );
+        assert_eq!(
+            parse_command("catch maybe"),
+            Err(ProtocolError::InvalidToggle("maybe".to_string()))
+        );
+        assert_eq!(
+            parse_command("jump"),
+            Err(ProtocolError::UnknownCommand("jump".to_string()))
+        );
+    }
+
+    #[test]
+    fn formats_events() {
+        let info = StopInfo {
+            reason: StopReason::Exception("Error: boom".to_string()),
+            depth: 1,
+            backtrace: vec![StackFrame {
+                name: "Main/run()".to_string(),
+                position: Some(SourcePosition {
+                    file: "Main.as".to_string(),
+                    line: 7,
+                }),
+            }],
+            locals: vec![],
+            registers: vec![],
+            scope: vec![],
+            stack: vec![],
+        };
+        assert_eq!(
+            format_event(&DebuggerEvent::Stopped(info.clone())),
+            "stopped exception Main.as:7 Error: boom\n"
+        );
+        assert_eq!(
+            format_event(&DebuggerEvent::Backtrace(info.backtrace)),
+            "frame 0 Main.as:7 Main/run()\nend\n"
+        );
+        assert_eq!(
+            format_event(&DebuggerEvent::Variables(vec![Variable {
+                name: "count".to_string(),
+                value: VariableValue::Number(3.0),
+            }])),
+            "var count = 3\nend\n"
+        );
+    }
+
+    #[test]
+    fn forwards_commands_from_a_client() {
+        let mut server = DebugServer::bind("127.0.0.1:0").unwrap();
+        let stream = TcpStream::connect(server.local_addr()).unwrap();
+        stream
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut writer = stream;
+
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        assert_eq!(line, format!("{GREETING}\n"));
+        assert!(server.is_connected());
+
+        writer.write_all(b"bogus\nbreak Main.as:4\n").unwrap();
+        line.clear();
+        reader.read_line(&mut line).unwrap();
+        assert_eq!(line, "error unknown command \"bogus\"\n");
+        assert_eq!(
+            server.wait_for_command(),
+            Some(DebuggerCommand::SetBreakpoint(Breakpoint::new(
+                "Main.as", 4
+            )))
+        );
+
+        server.send(DebuggerEvent::Ok);
+        line.clear();
+        reader.read_line(&mut line).unwrap();
+        assert_eq!(line, "ok\n");
+
+        drop(writer);
+        drop(reader);
+        assert_eq!(server.wait_for_command(), None);
+        assert!(!server.is_connected());
+    }
+}
@@ -0,0 +1,1097 @@
+//! Locale-aware formatting, parsing and collation for `flash.globalization`
+
+mod locale_data;
+
+pub use locale_data::{LocaleData, LOCALES};
+
+use std::cmp::Ordering;
+use std::fmt::Write;
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+/// The ID of the default locale of the user, as `LocaleID.DEFAULT`.
+pub const DEFAULT_LOCALE_ID: &str = "i-default";
+
+/// The result of the last operation of a `flash.globalization` object,
+/// as reported by `lastOperationStatus`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OperationStatus {
+    NoError,
+    IllegalArgumentError,
+    ParseError,
+    UsingDefaultWarning,
+    UsingFallbackWarning,
+}
+
+impl OperationStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            OperationStatus::NoError => "noError",
+            OperationStatus::IllegalArgumentError => "illegalArgumentError",
+            OperationStatus::ParseError => "parseError",
+            OperationStatus::UsingDefaultWarning => "usingDefaultWarning",
+            OperationStatus::UsingFallbackWarning => "usingFallbackWarning",
+        }
+    }
+}
+
+/// The parts of a locale ID, such as `zh-Hant-TW@collation=pinyin`.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct LocaleName {
+    pub language: String,
+    pub script: String,
+    pub region: String,
+    pub variant: String,
+    pub keywords: Vec<(String, String)>,
+}
+
+impl LocaleName {
+    /// Parse a locale ID, accepting both `-` and `_` as separators.
+    pub fn parse(name: &str) -> Self {
+        let (name, keywords) = name.split_once('@').unwrap_or((name, ""));
+        let keywords = keywords
+            .split(';')
+            .filter_map(|keyword| keyword.split_once('='))
+            .map(|(key, value)| (key.trim().to_owned(), value.trim().to_owned()))
+            .collect();
+
+        let mut parts = name.split(['-', '_']).filter(|part| !part.is_empty());
+        let mut locale = LocaleName {
+            language: parts.next().unwrap_or_default().to_ascii_lowercase(),
+            keywords,
+            ..Default::default()
+        };
+
+        let mut variants = Vec::new();
+        for part in parts {
+            let is_alphabetic = part.chars().all(|c| c.is_ascii_alphabetic());
+            if locale.script.is_empty()
+                && locale.region.is_empty()
+                && variants.is_empty()
+                && part.len() == 4
+                && is_alphabetic
+            {
+                let mut script = part.to_ascii_lowercase();
+                script[..1].make_ascii_uppercase();
+                locale.script = script;
+            } else if locale.region.is_empty()
+                && variants.is_empty()
+                && ((part.len() == 2 && is_alphabetic)
+                    || (part.len() == 3 && part.chars().all(|c| c.is_ascii_digit())))
+            {
+                locale.region = part.to_ascii_uppercase();
+            } else {
+                variants.push(part.to_ascii_uppercase());
+            }
+        }
+        locale.variant = variants.join("_");
+
+        locale
+    }
+
+    /// The canonical form of this locale ID.
+    pub fn canonical(&self) -> String {
+        let mut name = self.language.clone();
+        for part in [&self.script, &self.region, &self.variant] {
+            if !part.is_empty() {
+                name.push('-');
+                name.push_str(part);
+            }
+        }
+        if !self.keywords.is_empty() {
+            name.push('@');
+            let keywords: Vec<_> = self
+                .keywords
+                .iter()
+                .map(|(key, value)| format!("{key}={value}"))
+                .collect();
+            name.push_str(&keywords.join(";"));
+        }
+        name
+    }
+
+    pub fn is_rtl(&self) -> bool {
+        matches!(
+            self.script.as_str(),
+            "Arab" | "Hebr" | "Syrc" | "Thaa" | "Nkoo" | "Adlm"
+        ) || (self.script.is_empty()
+            && matches!(
+                self.language.as_str(),
+                "ar" | "he" | "iw" | "fa" | "ur" | "yi" | "ps" | "sd" | "ug" | "dv" | "ckb"
+            ))
+    }
+}
+
+/// Find the locale data to use for the requested locale ID.
+///
+/// `LocaleID.DEFAULT` resolves to `user_locale`, the language of the user
+/// interface. Locales without data fall back to a locale of the same
+/// language, or to the default locale.
+pub fn resolve_locale(
+    requested: &str,
+    user_locale: &str,
+) -> (&'static LocaleData, OperationStatus) {
+    let requested = if requested == DEFAULT_LOCALE_ID {
+        user_locale
+    } else {
+        requested
+    };
+    let requested = LocaleName::parse(requested);
+
+    let find = |predicate: &dyn Fn(&LocaleName) -> bool| {
+        LOCALES
+            .iter()
+            .find(|locale| predicate(&LocaleName::parse(locale.name)))
+    };
+
+    if let Some(locale) =
+        find(&|locale| locale.language == requested.language && locale.region == requested.region)
+    {
+        return (locale, OperationStatus::NoError);
+    }
+
+    if let Some(locale) = find(&|locale| locale.language == requested.language) {
+        return (locale, OperationStatus::UsingFallbackWarning);
+    }
+
+    (&LOCALES[0], OperationStatus::UsingDefaultWarning)
+}
+
+/// Order locale IDs from `have` by how well they match the locales in `want`.
+///
+/// Locales in `have` that don't match any wanted locale are left out.
+pub fn preferred_locales<'a>(want: &[&str], have: &[&'a str]) -> Vec<&'a str> {
+    let have_names: Vec<_> = have.iter().map(|name| LocaleName::parse(name)).collect();
+    let mut result: Vec<&'a str> = Vec::new();
+
+    for wanted in want {
+        let wanted = LocaleName::parse(wanted);
+        // Exact matches come first, then matches on the language only.
+        for exact in [true, false] {
+            for (name, have) in have.iter().zip(&have_names) {
+                let matches = have.language == wanted.language
+                    && (!exact || (have.region == wanted.region && have.script == wanted.script));
+                if matches && !result.contains(name) {
+                    result.push(name);
+                }
+            }
+        }
+    }
+
+    result
+}
+
+/// The options of a `NumberFormatter` or `CurrencyFormatter`.
+pub struct NumberFormat<'a> {
+    pub decimal_separator: &'a str,
+    pub grouping_separator: &'a str,
+    pub grouping_pattern: &'a str,
+    pub fractional_digits: usize,
+    pub use_grouping: bool,
+    pub leading_zero: bool,
+    pub trailing_zeros: bool,
+
+    /// The code point of the zero digit to use, as `NationalDigitsType`.
+    pub digits_type: u32,
+}
+
+impl NumberFormat<'_> {
+    /// Parse a grouping pattern such as `3;2;*` into its group sizes, from
+    /// the decimal separator outwards, and whether the last size repeats.
+    fn grouping_sizes(&self) -> (Vec<usize>, bool) {
+        let mut sizes = Vec::new();
+        let mut repeat = false;
+        for part in self.grouping_pattern.split(';') {
+            match part.trim() {
+                "*" => repeat = true,
+                size => match size.parse::<usize>() {
+                    Ok(size) if size > 0 => sizes.push(size),
+                    _ => break,
+                },
+            }
+        }
+        (sizes, repeat)
+    }
+
+    fn group(&self, integer: &str) -> String {
+        let (sizes, repeat) = self.grouping_sizes();
+        if !self.use_grouping || sizes.is_empty() {
+            return integer.to_owned();
+        }
+
+        let digits: Vec<char> = integer.chars().collect();
+        let mut groups = Vec::new();
+        let mut end = digits.len();
+        let mut sizes_iter = sizes.iter();
+        let mut size = sizes_iter.next().copied();
+        while let Some(group_size) = size {
+            if end <= group_size {
+                break;
+            }
+            groups.push(digits[end - group_size..end].iter().collect::<String>());
+            end -= group_size;
+            size = sizes_iter
+                .next()
+                .copied()
+                .or(if repeat { Some(group_size) } else { None });
+        }
+        groups.push(digits[..end].iter().collect());
+        groups.reverse();
+        groups.join(self.grouping_separator)
+    }
+
+    fn localize_digits(&self, text: &str) -> String {
+        let zero = match char::from_u32(self.digits_type) {
+            Some(zero) if zero != '0' => zero as u32,
+            _ => return text.to_owned(),
+        };
+        text.chars()
+            .map(|c| match c.to_digit(10) {
+                Some(digit) => char::from_u32(zero + digit).unwrap_or(c),
+                None => c,
+            })
+            .collect()
+    }
+
+    /// Format the absolute value of `value`, without any sign.
+    pub fn format_abs(&self, value: f64) -> String {
+        let value = value.abs();
+        if value.is_nan() {
+            return "NaN".to_owned();
+        }
+        if value.is_infinite() {
+            return "∞".to_owned();
+        }
+
+        let formatted = format!("{:.*}", self.fractional_digits, value);
+        let (integer, mut fraction) = formatted
+            .split_once('.')
+            .map(|(integer, fraction)| (integer.to_owned(), fraction.to_owned()))
+            .unwrap_or((formatted, String::new()));
+
+        if !self.trailing_zeros {
+            fraction.truncate(fraction.trim_end_matches('0').len());
+        }
+
+        let mut result = if integer == "0" && !self.leading_zero && !fraction.is_empty() {
+            String::new()
+        } else {
+            self.group(&integer)
+        };
+        if !fraction.is_empty() {
+            result.push_str(self.decimal_separator);
+            result.push_str(&fraction);
+        }
+        self.localize_digits(&result)
+    }
+}
+
+/// Apply a `NumberFormatter.negativeNumberFormat` pattern to a formatted number.
+pub fn apply_negative_number_format(number: &str, negative_symbol: &str, format: u32) -> String {
+    match format {
+        0 => format!("({number})"),
+        2 => format!("{negative_symbol} {number}"),
+        3 => format!("{number}{negative_symbol}"),
+        4 => format!("{number} {negative_symbol}"),
+        _ => format!("{negative_symbol}{number}"),
+    }
+}
+
+/// Apply a `CurrencyFormatter.positiveCurrencyFormat` or
+/// `negativeCurrencyFormat` pattern to a formatted amount.
+pub fn apply_currency_format(
+    number: &str,
+    currency: &str,
+    negative_symbol: &str,
+    is_negative: bool,
+    format: u32,
+) -> String {
+    let (n, c, m) = (number, currency, negative_symbol);
+    if !is_negative {
+        return match format {
+            1 => format!("{n}{c}"),
+            2 => format!("{c} {n}"),
+            3 => format!("{n} {c}"),
+            _ => format!("{c}{n}"),
+        };
+    }
+
+    match format {
+        0 => format!("({c}{n})"),
+        2 => format!("{c}{m}{n}"),
+        3 => format!("{c}{n}{m}"),
+        4 => format!("({n}{c})"),
+        5 => format!("{m}{n}{c}"),
+        6 => format!("{n}{m}{c}"),
+        7 => format!("{n}{c}{m}"),
+        8 => format!("{m}{n} {c}"),
+        9 => format!("{m}{c} {n}"),
+        10 => format!("{n} {c}{m}"),
+        11 => format!("{c} {n}{m}"),
+        12 => format!("{c} {m}{n}"),
+        13 => format!("{n}{m} {c}"),
+        14 => format!("({c} {n})"),
+        15 => format!("({n} {c})"),
+        _ => format!("{m}{c}{n}"),
+    }
+}
+
+/// A number parsed from a string.
+#[derive(Debug, PartialEq)]
+pub struct ParsedNumber {
+    pub value: f64,
+
+    /// The byte range of the number within the parsed string.
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Parse a string containing a number and optional whitespace.
+///
+/// Negative numbers are recognized with `negative_symbol` before or after
+/// the number, or with parentheses around it.
+pub fn parse_number(
+    text: &str,
+    decimal_separator: &str,
+    grouping_separator: &str,
+    negative_symbol: &str,
+) -> Option<ParsedNumber> {
+    let start = text.len() - text.trim_start().len();
+    let end = text.trim_end().len();
+    if start >= end {
+        return None;
+    }
+    let mut number = &text[start..end];
+    let mut number_start = start;
+    let mut is_negative = false;
+
+    if number.starts_with('(') && number.ends_with(')') && number.len() >= 2 {
+        number = &number[1..number.len() - 1];
+        number_start += 1;
+        is_negative = true;
+    } else if !negative_symbol.is_empty() {
+        if let Some(rest) = number.strip_prefix(negative_symbol) {
+            number_start += number.len() - rest.trim_start().len();
+            number = rest.trim_start();
+            is_negative = true;
+        } else if let Some(rest) = number.strip_suffix(negative_symbol) {
+            number = rest.trim_end();
+            is_negative = true;
+        }
+    }
+
+    let value = parse_digits(number, decimal_separator, grouping_separator)?;
+    Some(ParsedNumber {
+        value: if is_negative { -value } else { value },
+        start: number_start,
+        end: number_start + number.len(),
+    })
+}
+
+fn parse_digits(text: &str, decimal_separator: &str, grouping_separator: &str) -> Option<f64> {
+    let mut digits = String::new();
+    let mut rest = text;
+    let mut seen_decimal = false;
+    let mut seen_digit = false;
+
+    while let Some(c) = rest.chars().next() {
+        if let Some(digit) = national_digit_value(c) {
+            digits.push(char::from_digit(digit, 10)?);
+            seen_digit = true;
+            rest = &rest[c.len_utf8()..];
+        } else if !seen_decimal
+            && !decimal_separator.is_empty()
+            && rest.starts_with(decimal_separator)
+        {
+            digits.push('.');
+            seen_decimal = true;
+            rest = &rest[decimal_separator.len()..];
+        } else if !seen_decimal
+            && seen_digit
+            && !grouping_separator.is_empty()
+            && rest.starts_with(grouping_separator)
+        {
+            rest = &rest[grouping_separator.len()..];
+        } else {
+            return None;
+        }
+    }
+
+    if !seen_digit {
+        return None;
+    }
+    digits.parse().ok()
+}
+
+/// The value of a decimal digit in any of the scripts of `NationalDigitsType`.
+fn national_digit_value(c: char) -> Option<u32> {
+    const ZEROS: &[u32] = &[
+        0x30, 0x660, 0x6f0, 0x7c0, 0x966, 0x9e6, 0xa66, 0xae6, 0xb66, 0xbe6, 0xc66, 0xce6, 0xd66,
+        0xe50, 0xed0, 0xf20, 0x1040, 0x1090, 0x17e0, 0x1810, 0x1946, 0x19d0, 0x1b50, 0x1bb0,
+        0x1c40, 0x1c50, 0xa8d0, 0xa900, 0xaa50, 0xff10,
+    ];
+    let c = c as u32;
+    ZEROS
+        .iter()
+        .find(|&&zero| (zero..zero + 10).contains(&c))
+        .map(|zero| c - zero)
+}
+
+/// Split a formatted currency amount into its number and currency string.
+///
+/// Returns the parsed amount, and the currency symbol or code found
+/// around it.
+pub fn parse_currency(
+    text: &str,
+    decimal_separator: &str,
+    grouping_separator: &str,
+    negative_symbol: &str,
+) -> Option<(f64, String)> {
+    let is_currency_char = |c: char| {
+        !c.is_whitespace()
+            && national_digit_value(c).is_none()
+            && !decimal_separator.starts_with(c)
+            && !grouping_separator.starts_with(c)
+            && !negative_symbol.starts_with(c)
+            && c != '('
+            && c != ')'
+    };
+
+    let mut currency = String::new();
+    let mut number = String::new();
+    for c in text.chars() {
+        if is_currency_char(c) {
+            currency.push(c);
+        } else {
+            number.push(c);
+        }
+    }
+
+    let parsed = parse_number(
+        &number,
+        decimal_separator,
+        grouping_separator,
+        negative_symbol,
+    )
+    .or_else(|| {
+        // The currency may have been between the sign and the number.
+        let number = number.split_whitespace().collect::<String>();
+        parse_number(
+            &number,
+            decimal_separator,
+            grouping_separator,
+            negative_symbol,
+        )
+    })?;
+    Some((parsed.value, currency))
+}
+
+/// A date and time, broken down into its fields.
+#[derive(Clone, Copy, Debug)]
+pub struct DateTimeFields {
+    pub year: i32,
+
+    /// The month, from 1 to 12.
+    pub month: u32,
+
+    /// The day of the month, from 1.
+    pub day: u32,
+
+    /// The day of the week, where 0 is Sunday.
+    pub weekday: u32,
+
+    /// The day of the year, from 1.
+    pub ordinal: u32,
+    pub hour: u32,
+    pub minute: u32,
+    pub second: u32,
+    pub millisecond: u32,
+
+    /// The offset from UTC, in seconds.
+    pub offset: i32,
+}
+
+/// The style of the names of months and weekdays, as `DateTimeNameStyle`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NameStyle {
+    Full,
+    LongAbbreviation,
+    ShortAbbreviation,
+}
+
+impl NameStyle {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "full" => Some(NameStyle::Full),
+            "longAbbreviation" => Some(NameStyle::LongAbbreviation),
+            "shortAbbreviation" => Some(NameStyle::ShortAbbreviation),
+            _ => None,
+        }
+    }
+}
+
+/// Shorten a name to a single letter, or a number for numeric names.
+fn narrow_name(name: &str) -> String {
+    let number: String = name.chars().take_while(|c| c.is_ascii_digit()).collect();
+    if !number.is_empty() {
+        return number;
+    }
+    name.chars().next().map(String::from).unwrap_or_default()
+}
+
+/// The names of the months of `locale`.
+pub fn month_names(locale: &LocaleData, style: NameStyle, standalone: bool) -> Vec<String> {
+    let full = match locale.standalone_months {
+        Some(standalone_months) if standalone => standalone_months,
+        _ => locale.months,
+    };
+    match style {
+        NameStyle::Full => full.iter().map(|name| name.to_string()).collect(),
+        NameStyle::LongAbbreviation => locale
+            .short_months
+            .iter()
+            .map(|name| name.to_string())
+            .collect(),
+        NameStyle::ShortAbbreviation => locale
+            .short_months
+            .iter()
+            .map(|name| narrow_name(name))
+            .collect(),
+    }
+}
+
+/// The names of the days of the week of `locale`, starting on Sunday.
+pub fn weekday_names(locale: &LocaleData, style: NameStyle) -> Vec<String> {
+    match style {
+        NameStyle::Full => locale
+            .weekdays
+            .iter()
+            .map(|name| name.to_string())
+            .collect(),
+        NameStyle::LongAbbreviation => locale
+            .short_weekdays
+            .iter()
+            .map(|name| name.to_string())
+            .collect(),
+        NameStyle::ShortAbbreviation => locale
+            .short_weekdays
+            .iter()
+            .map(|name| narrow_name(name))
+            .collect(),
+    }
+}
+
+/// A style of `DateTimeStyle`, other than `custom`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DateTimeStyle {
+    Long,
+    Medium,
+    Short,
+    None,
+}
+
+impl DateTimeStyle {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "long" => Some(DateTimeStyle::Long),
+            "medium" => Some(DateTimeStyle::Medium),
+            "short" => Some(DateTimeStyle::Short),
+            "none" => Some(DateTimeStyle::None),
+            _ => None,
+        }
+    }
+
+    fn pattern(self, patterns: &[&'static str; 3]) -> Option<&'static str> {
+        match self {
+            DateTimeStyle::Long => Some(patterns[0]),
+            DateTimeStyle::Medium => Some(patterns[1]),
+            DateTimeStyle::Short => Some(patterns[2]),
+            DateTimeStyle::None => None,
+        }
+    }
+}
+
+/// The pattern used to format dates in the given styles.
+pub fn date_time_pattern(
+    locale: &LocaleData,
+    date_style: DateTimeStyle,
+    time_style: DateTimeStyle,
+) -> String {
+    let date = date_style.pattern(&locale.date_patterns);
+    let time = time_style.pattern(&locale.time_patterns);
+    match (date, time) {
+        (Some(date), Some(time)) => format!("{date} {time}"),
+        (Some(pattern), None) | (None, Some(pattern)) => pattern.to_owned(),
+        (None, None) => String::new(),
+    }
+}
+
+/// Format a date with a pattern of the Unicode Technical Standard #35,
+/// as used by `DateTimeFormatter.setDateTimePattern`.
+pub fn format_date_time(pattern: &str, date: &DateTimeFields, locale: &LocaleData) -> String {
+    let mut result = String::new();
+    let mut chars = pattern.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == '\'' {
+            // `''` is a literal quote, otherwise quotes delimit literal text.
+            if chars.peek() == Some(&'\'') {
+                chars.next();
+                result.push('\'');
+                continue;
+            }
+            while let Some(c) = chars.next() {
+                if c == '\'' {
+                    if chars.peek() == Some(&'\'') {
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                result.push(c);
+            }
+            continue;
+        }
+
+        if !c.is_ascii_alphabetic() {
+            result.push(c);
+            continue;
+        }
+
+        let mut count = 1;
+        while chars.peek() == Some(&c) {
+            chars.next();
+            count += 1;
+        }
+        format_field(&mut result, c, count, date, locale);
+    }
+
+    result
+}
+
+fn format_field(
+    result: &mut String,
+    field: char,
+    count: usize,
+    date: &DateTimeFields,
+    locale: &LocaleData,
+) {
+    let mut number = |value: i64, width: usize| {
+        let _ = write!(result, "{value:0width$}");
+    };
+    let name_style = match count {
+        4 => NameStyle::Full,
+        5 => NameStyle::ShortAbbreviation,
+        _ => NameStyle::LongAbbreviation,
+    };
+
+    match field {
+        'G' => result.push_str(if date.year > 0 { "AD" } else { "BC" }),
+        'y' if count == 2 => number(i64::from(date.year).rem_euclid(100), 2),
+        'y' => number(i64::from(date.year), count),
+        'Q' if count <= 2 => number(i64::from((date.month - 1) / 3 + 1), count),
+        'Q' => {
+            let _ = write!(result, "Q{}", (date.month - 1) / 3 + 1);
+        }
+        'M' | 'L' if count <= 2 => number(i64::from(date.month), count),
+        'M' | 'L' => {
+            result.push_str(&month_names(locale, name_style, field == 'L')[date.month as usize - 1])
+        }
+        'd' => number(i64::from(date.day), count),
+        'D' => number(i64::from(date.ordinal), count),
+        'F' => number(i64::from((date.day - 1) / 7 + 1), count),
+        'w' => {
+            // The week containing January 1st is the first week of the year.
+            let first_weekday = (date.weekday + 7 * 53 - (date.ordinal - 1)) % 7;
+            let offset = (first_weekday + 7 - locale.first_weekday) % 7;
+            number(i64::from((date.ordinal - 1 + offset) / 7 + 1), count);
+        }
+        'W' => {
+            let first_weekday = (date.weekday + 7 * 5 - (date.day - 1)) % 7;
+            let offset = (first_weekday + 7 - locale.first_weekday) % 7;
+            number(i64::from((date.day - 1 + offset) / 7 + 1), count);
+        }
+        'E' => result.push_str(&weekday_names(locale, name_style)[date.weekday as usize]),
+        'a' => result.push_str(locale.am_pm[(date.hour >= 12) as usize]),
+        'h' => number(i64::from((date.hour + 11) % 12 + 1), count),
+        'H' => number(i64::from(date.hour), count),
+        'K' => number(i64::from(date.hour % 12), count),
+        'k' => number(i64::from((date.hour + 23) % 24 + 1), count),
+        'm' => number(i64::from(date.minute), count),
+        's' => number(i64::from(date.second), count),
+        'S' => {
+            let fraction = format!("{:03}", date.millisecond);
+            let mut fraction: String = fraction.chars().take(count).collect();
+            while fraction.len() < count {
+                fraction.push('0');
+            }
+            result.push_str(&fraction);
+        }
+        'z' | 'Z' | 'v' | 'V' => {
+            let sign = if date.offset < 0 { '-' } else { '+' };
+            let hours = date.offset.abs() / 3600;
+            let minutes = date.offset.abs() / 60 % 60;
+            if field == 'Z' && count < 4 {
+                let _ = write!(result, "{sign}{hours:02}{minutes:02}");
+            } else {
+                let _ = write!(result, "GMT{sign}{hours:02}:{minutes:02}");
+            }
+        }
+        _ => {
+            for _ in 0..count {
+                result.push(field);
+            }
+        }
+    }
+}
+
+/// The options of a `Collator`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CollatorOptions {
+    pub ignore_case: bool,
+    pub ignore_character_width: bool,
+    pub ignore_diacritics: bool,
+    pub ignore_kana_type: bool,
+    pub ignore_symbols: bool,
+    pub numeric_comparison: bool,
+}
+
+impl CollatorOptions {
+    /// The options of `CollatorMode.MATCHING`.
+    pub fn matching() -> Self {
+        Self {
+            ignore_case: true,
+            ignore_character_width: true,
+            ignore_diacritics: true,
+            ignore_kana_type: true,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum CollationLevel {
+    /// Base letters only.
+    Primary,
+
+    /// Base letters and diacritics.
+    Secondary,
+
+    /// Base letters, diacritics and case.
+    Tertiary,
+}
+
+fn collation_key(text: &str, level: CollationLevel, options: CollatorOptions) -> Vec<char> {
+    let decomposed: Vec<char> = if options.ignore_character_width {
+        text.nfkd().collect()
+    } else {
+        text.nfd().collect()
+    };
+
+    decomposed
+        .into_iter()
+        .filter(|&c| {
+            !(is_combining_mark(c)
+                && (level == CollationLevel::Primary || options.ignore_diacritics))
+        })
+        .filter(|&c| !options.ignore_symbols || c.is_alphanumeric() || is_combining_mark(c))
+        .map(|c| {
+            if options.ignore_kana_type && ('\u{30a1}'..='\u{30f6}').contains(&c) {
+                // Katakana sort as the equivalent Hiragana.
+                char::from_u32(c as u32 - 0x60).unwrap_or(c)
+            } else {
+                c
+            }
+        })
+        .flat_map(|c| {
+            let fold_case = level != CollationLevel::Tertiary || options.ignore_case;
+            let lowercase: Vec<char> = if fold_case {
+                c.to_lowercase().collect()
+            } else {
+                vec![c]
+            };
+            lowercase
+        })
+        .collect()
+}
+
+/// Compare two collation keys, comparing runs of digits by their value
+/// when `numeric` is set.
+fn compare_keys(a: &[char], b: &[char], numeric: bool, level: CollationLevel) -> Ordering {
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if numeric && a[i].is_ascii_digit() && b[j].is_ascii_digit() {
+            let a_end = i + a[i..].iter().take_while(|c| c.is_ascii_digit()).count();
+            let b_end = j + b[j..].iter().take_while(|c| c.is_ascii_digit()).count();
+            let a_digits: String = a[i..a_end].iter().collect();
+            let b_digits: String = b[j..b_end].iter().collect();
+            let a_digits = a_digits.trim_start_matches('0');
+            let b_digits = b_digits.trim_start_matches('0');
+            let ordering = a_digits
+                .len()
+                .cmp(&b_digits.len())
+                .then_with(|| a_digits.cmp(b_digits));
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+            i = a_end;
+            j = b_end;
+            continue;
+        }
+
+        let ordering = if level == CollationLevel::Tertiary && a[i] != b[j] {
+            // Lowercase letters sort before their uppercase forms.
+            let a_lower: String = a[i].to_lowercase().collect();
+            let b_lower: String = b[j].to_lowercase().collect();
+            a_lower
+                .cmp(&b_lower)
+                .then_with(|| b[j].is_lowercase().cmp(&a[i].is_lowercase()))
+        } else {
+            a[i].cmp(&b[j])
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+        i += 1;
+        j += 1;
+    }
+    (a.len() - i).cmp(&(b.len() - j))
+}
+
+/// Compare two strings for sorting, as `Collator.compare`.
+///
+/// Strings are first compared by their base letters, then by their
+/// diacritics, and finally by their case.
+pub fn collate(a: &str, b: &str, options: CollatorOptions) -> Ordering {
+    [
+        CollationLevel::Primary,
+        CollationLevel::Secondary,
+        CollationLevel::Tertiary,
+    ]
+    .into_iter()
+    .map(|level| {
+        compare_keys(
+            &collation_key(a, level, options),
+            &collation_key(b, level, options),
+            options.numeric_comparison,
+            level,
+        )
+    })
+    .find(|ordering| *ordering != Ordering::Equal)
+    .unwrap_or(Ordering::Equal)
+}
+
+/// Whether the locale uses the Turkic rules for dotted and dotless `i`.
+fn uses_turkic_casing(locale: &str) -> bool {
+    matches!(LocaleName::parse(locale).language.as_str(), "tr" | "az")
+}
+
+/// Convert text to uppercase, as `StringTools.toUpperCase`.
+pub fn to_uppercase(text: &str, locale: &str) -> String {
+    if uses_turkic_casing(locale) {
+        text.chars()
+            .map(|c| match c {
+                'i' => "İ".to_owned(),
+                'ı' => "I".to_owned(),
+                c => c.to_uppercase().collect(),
+            })
+            .collect()
+    } else {
+        text.to_uppercase()
+    }
+}
+
+/// Convert text to lowercase, as `StringTools.toLowerCase`.
+pub fn to_lowercase(text: &str, locale: &str) -> String {
+    if uses_turkic_casing(locale) {
+        text.chars()
+            .map(|c| match c {
+                'I' => "ı".to_owned(),
+                'İ' => "i".to_owned(),
+                c => c.to_lowercase().collect(),
+            })
+            .collect()
+    } else {
+        text.to_lowercase()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn locale(name: &str) -> &'static LocaleData {
+        LOCALES.iter().find(|locale| locale.name == name).unwrap()
+    }
+
+    fn number_format(locale: &LocaleData) -> NumberFormat<'_> {
+        NumberFormat {
+            decimal_separator: locale.decimal_separator,
+            grouping_separator: locale.grouping_separator,
+            grouping_pattern: locale.grouping_pattern,
+            fractional_digits: 2,
+            use_grouping: true,
+            leading_zero: true,
+            trailing_zeros: true,
+            digits_type: 0x30,
+        }
+    }
+
+    #[test]
+    fn locale_names() {
+        let name = LocaleName::parse("zh_hant_tw@collation=pinyin");
+        assert_eq!(name.language, "zh");
+        assert_eq!(name.script, "Hant");
+        assert_eq!(name.region, "TW");
+        assert_eq!(name.canonical(), "zh-Hant-TW@collation=pinyin");
+        assert!(LocaleName::parse("ar-EG").is_rtl());
+        assert!(!LocaleName::parse("en-US").is_rtl());
+    }
+
+    #[test]
+    fn locale_resolution() {
+        let (data, status) = resolve_locale("de-DE", "en-US");
+        assert_eq!((data.name, status), ("de-DE", OperationStatus::NoError));
+        let (data, status) = resolve_locale("fr-CA", "en-US");
+        assert_eq!(
+            (data.name, status),
+            ("fr-FR", OperationStatus::UsingFallbackWarning)
+        );
+        let (data, status) = resolve_locale("xx", "en-US");
+        assert_eq!(
+            (data.name, status),
+            ("en-US", OperationStatus::UsingDefaultWarning)
+        );
+        let (data, _) = resolve_locale(DEFAULT_LOCALE_ID, "ja-JP");
+        assert_eq!(data.name, "ja-JP");
+        assert_eq!(
+            preferred_locales(&["fr-CA", "en"], &["en-US", "fr-FR", "de-DE"]),
+            vec!["fr-FR", "en-US"]
+        );
+    }
+
+    #[test]
+    fn number_formatting() {
+        let format = number_format(locale("en-US"));
+        assert_eq!(format.format_abs(1234567.891), "1,234,567.89");
+        assert_eq!(format.format_abs(0.5), "0.50");
+
+        let format = NumberFormat {
+            trailing_zeros: false,
+            leading_zero: false,
+            ..number_format(locale("de-DE"))
+        };
+        assert_eq!(format.format_abs(0.5), ",5");
+        assert_eq!(format.format_abs(1234.0), "1.234");
+
+        let format = NumberFormat {
+            grouping_pattern: "3;2;*",
+            digits_type: 0x966,
+            ..number_format(locale("en-US"))
+        };
+        assert_eq!(format.format_abs(1234567.0), "१२,३४,५६७.००");
+
+        assert_eq!(apply_negative_number_format("1.5", "-", 0), "(1.5)");
+        assert_eq!(apply_negative_number_format("1.5", "-", 4), "1.5 -");
+        assert_eq!(apply_currency_format("1,50", "€", "-", true, 8), "-1,50 €");
+        assert_eq!(apply_currency_format("1.50", "$", "-", false, 0), "$1.50");
+    }
+
+    #[test]
+    fn number_parsing() {
+        assert_eq!(
+            parse_number(" -1,234.5 ", ".", ",", "-"),
+            Some(ParsedNumber {
+                value: -1234.5,
+                start: 2,
+                end: 9,
+            })
+        );
+        assert_eq!(
+            parse_number("(12)", ".", ",", "-").map(|parsed| parsed.value),
+            Some(-12.0)
+        );
+        assert_eq!(
+            parse_number("1.234,5", ",", ".", "-").map(|parsed| parsed.value),
+            Some(1234.5)
+        );
+        assert_eq!(parse_number("12abc", ".", ",", "-"), None);
+        assert_eq!(
+            parse_currency("-1.234,50 €", ",", ".", "-"),
+            Some((-1234.5, "€".to_owned()))
+        );
+    }
+
+    #[test]
+    fn date_formatting() {
+        let date = DateTimeFields {
+            year: 2001,
+            month: 2,
+            day: 3,
+            weekday: 6,
+            ordinal: 34,
+            hour: 16,
+            minute: 5,
+            second: 6,
+            millisecond: 78,
+            offset: 20700,
+        };
+        let en = locale("en-US");
+        let pattern = date_time_pattern(en, DateTimeStyle::Long, DateTimeStyle::Long);
+        assert_eq!(
+            format_date_time(&pattern, &date, en),
+            "Saturday, February 3, 2001 4:05:06 PM"
+        );
+        assert_eq!(
+            format_date_time("yy-MM-dd'T'HH:mm:ss.SSS Z ''", &date, en),
+            "01-02-03T16:05:06.078 +0545 '"
+        );
+        let ru = locale("ru-RU");
+        assert_eq!(
+            format_date_time("d MMMM, LLLL", &date, ru),
+            "3 февраля, февраль"
+        );
+        assert_eq!(weekday_names(en, NameStyle::ShortAbbreviation)[0], "S");
+        assert_eq!(
+            month_names(locale("ja-JP"), NameStyle::ShortAbbreviation, true)[11],
+            "12"
+        );
+    }
+
+    #[test]
+    fn collation() {
+        let sorting = CollatorOptions::default();
+        assert_eq!(collate("a", "B", sorting), Ordering::Less);
+        assert_eq!(collate("a", "A", sorting), Ordering::Less);
+        assert_eq!(collate("é", "f", sorting), Ordering::Less);
+        assert_eq!(collate("e", "é", sorting), Ordering::Less);
+        assert_eq!(
+            collate("Éa", "ea", CollatorOptions::matching()),
+            Ordering::Equal
+        );
+        assert_eq!(
+            collate("ｶ", "か", CollatorOptions::matching()),
+            Ordering::Equal
+        );
+
+        let numeric = CollatorOptions {
+            numeric_comparison: true,
+            ..Default::default()
+        };
+        assert_eq!(collate("file10", "file9", sorting), Ordering::Less);
+        assert_eq!(collate("file10", "file9", numeric), Ordering::Greater);
+
+        let ignore_symbols = CollatorOptions {
+            ignore_symbols: true,
+            ..Default::default()
+        };
+        assert_eq!(collate("a-b", "ab", ignore_symbols), Ordering::Equal);
+    }
+
+    #[test]
+    fn casing() {
+        assert_eq!(to_uppercase("istanbul", "tr-TR"), "İSTANBUL");
+        assert_eq!(to_lowercase("DIŞ", "tr-TR"), "dış");
+        assert_eq!(to_uppercase("straße", "de-DE"), "STRASSE");
+    }
+}
@@ -0,0 +1,788 @@
+//! Locale data for `flash.globalization`.
+//!
+//! Flash Player asks the operating system for this data, so its output
+//! differs between platforms. These tables were transcribed by hand from the
+//! Unicode CLDR, version 39 (the `main/<locale>/numbers`, `currencies` and
+//! `ca-gregorian` data, and `supplemental/weekData` for the first weekday).
+//! The date and time patterns are the CLDR `full`, `medium` and `short`
+//! formats, with `y` written as `yyyy`.
+//!
+//! Where the Windows locale data differs, which is what Flash Player uses on
+//! the platform most content was made for, the tables follow Windows instead:
+//!
+//! - `en-US`, `es-ES` and `it-IT` use four-digit years in short dates, and
+//!   `es-ES` pads the day and month to two digits.
+//! - `en-GB` abbreviates September as "Sep", not "Sept".
+//! - `fr-FR` groups digits with a no-break space, not a narrow no-break space.
+//! - `es-ES` writes "a. m." and "p. m." with a plain space.
+//! - `nl-NL` abbreviates months without a trailing period.
+//! - `sv-SE` uses a hyphen-minus as the negative symbol, not U+2212.
+//! - `ru-RU` leaves the weekday out of long dates, and doesn't pad hours.
+//! - `zh-CN` starts the week on Monday and uses a 24-hour clock.
+
+/// The formatting conventions of a single locale.
+pub struct LocaleData {
+    /// The canonical ID of this locale, such as `en-US`.
+    pub name: &'static str,
+
+    pub decimal_separator: &'static str,
+    pub grouping_separator: &'static str,
+    pub grouping_pattern: &'static str,
+    pub negative_symbol: &'static str,
+
+    /// The pattern of negative numbers, as used by `NumberFormatter.negativeNumberFormat`.
+    pub negative_number_format: u32,
+
+    pub currency_iso_code: &'static str,
+    pub currency_symbol: &'static str,
+    pub currency_fractional_digits: u32,
+
+    /// The pattern of positive amounts, as used by `CurrencyFormatter.positiveCurrencyFormat`.
+    pub positive_currency_format: u32,
+
+    /// The pattern of negative amounts, as used by `CurrencyFormatter.negativeCurrencyFormat`.
+    pub negative_currency_format: u32,
+
+    /// The names of the months, when used in a date.
+    pub months: [&'static str; 12],
+
+    /// The names of the months, when used on their own.
+    ///
+    /// This is `None` when they are the same as `months`.
+    pub standalone_months: Option<[&'static str; 12]>,
+
+    pub short_months: [&'static str; 12],
+
+    /// The names of the days of the week, starting on Sunday.
+    pub weekdays: [&'static str; 7],
+
+    pub short_weekdays: [&'static str; 7],
+
+    pub am_pm: [&'static str; 2],
+
+    /// The first day of the week, where 0 is Sunday.
+    pub first_weekday: u32,
+
+    /// The long, medium and short date patterns.
+    pub date_patterns: [&'static str; 3],
+
+    /// The long, medium and short time patterns.
+    pub time_patterns: [&'static str; 3],
+}
+
+const EN_MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+const EN_SHORT_MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+const EN_WEEKDAYS: [&str; 7] = [
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+
+const EN_SHORT_WEEKDAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+
+const NUMERIC_MONTHS_JA: [&str; 12] = [
+    "1月", "2月", "3月", "4月", "5月", "6月", "7月", "8月", "9月", "10月", "11月", "12月",
+];
+
+/// The locales with formatting data, the first of which is the default.
+pub static LOCALES: &[LocaleData] = &[
+    LocaleData {
+        name: "en-US",
+        decimal_separator: ".",
+        grouping_separator: ",",
+        grouping_pattern: "3;*",
+        negative_symbol: "-",
+        negative_number_format: 1,
+        currency_iso_code: "USD",
+        currency_symbol: "$",
+        currency_fractional_digits: 2,
+        positive_currency_format: 0,
+        negative_currency_format: 1,
+        months: EN_MONTHS,
+        standalone_months: None,
+        short_months: EN_SHORT_MONTHS,
+        weekdays: EN_WEEKDAYS,
+        short_weekdays: EN_SHORT_WEEKDAYS,
+        am_pm: ["AM", "PM"],
+        first_weekday: 0,
+        date_patterns: ["EEEE, MMMM d, yyyy", "MMM d, yyyy", "M/d/yyyy"],
+        time_patterns: ["h:mm:ss a", "h:mm:ss a", "h:mm a"],
+    },
+    LocaleData {
+        name: "en-GB",
+        decimal_separator: ".",
+        grouping_separator: ",",
+        grouping_pattern: "3;*",
+        negative_symbol: "-",
+        negative_number_format: 1,
+        currency_iso_code: "GBP",
+        currency_symbol: "£",
+        currency_fractional_digits: 2,
+        positive_currency_format: 0,
+        negative_currency_format: 1,
+        months: EN_MONTHS,
+        standalone_months: None,
+        short_months: EN_SHORT_MONTHS,
+        weekdays: EN_WEEKDAYS,
+        short_weekdays: EN_SHORT_WEEKDAYS,
+        am_pm: ["am", "pm"],
+        first_weekday: 1,
+        date_patterns: ["EEEE, d MMMM yyyy", "d MMM yyyy", "dd/MM/yyyy"],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+    },
+    LocaleData {
+        name: "fr-FR",
+        decimal_separator: ",",
+        grouping_separator: "\u{a0}",
+        grouping_pattern: "3;*",
+        negative_symbol: "-",
+        negative_number_format: 1,
+        currency_iso_code: "EUR",
+        currency_symbol: "€",
+        currency_fractional_digits: 2,
+        positive_currency_format: 3,
+        negative_currency_format: 8,
+        months: [
+            "janvier",
+            "février",
+            "mars",
+            "avril",
+            "mai",
+            "juin",
+            "juillet",
+            "août",
+            "septembre",
+            "octobre",
+            "novembre",
+            "décembre",
+        ],
+        standalone_months: None,
+        short_months: [
+            "janv.", "févr.", "mars", "avr.", "mai", "juin", "juil.", "août", "sept.", "oct.",
+            "nov.", "déc.",
+        ],
+        weekdays: [
+            "dimanche", "lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi",
+        ],
+        short_weekdays: ["dim.", "lun.", "mar.", "mer.", "jeu.", "ven.", "sam."],
+        am_pm: ["AM", "PM"],
+        first_weekday: 1,
+        date_patterns: ["EEEE d MMMM yyyy", "d MMM yyyy", "dd/MM/yyyy"],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+    },
+    LocaleData {
+        name: "de-DE",
+        decimal_separator: ",",
+        grouping_separator: ".",
+        grouping_pattern: "3;*",
+        negative_symbol: "-",
+        negative_number_format: 1,
+        currency_iso_code: "EUR",
+        currency_symbol: "€",
+        currency_fractional_digits: 2,
+        positive_currency_format: 3,
+        negative_currency_format: 8,
+        months: [
+            "Januar",
+            "Februar",
+            "März",
+            "April",
+            "Mai",
+            "Juni",
+            "Juli",
+            "August",
+            "September",
+            "Oktober",
+            "November",
+            "Dezember",
+        ],
+        standalone_months: None,
+        short_months: [
+            "Jan.", "Feb.", "März", "Apr.", "Mai", "Juni", "Juli", "Aug.", "Sept.", "Okt.", "Nov.",
+            "Dez.",
+        ],
+        weekdays: [
+            "Sonntag",
+            "Montag",
+            "Dienstag",
+            "Mittwoch",
+            "Donnerstag",
+            "Freitag",
+            "Samstag",
+        ],
+        short_weekdays: ["So.", "Mo.", "Di.", "Mi.", "Do.", "Fr.", "Sa."],
+        am_pm: ["AM", "PM"],
+        first_weekday: 1,
+        date_patterns: ["EEEE, d. MMMM yyyy", "dd.MM.yyyy", "dd.MM.yy"],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+    },
+    LocaleData {
+        name: "es-ES",
+        decimal_separator: ",",
+        grouping_separator: ".",
+        grouping_pattern: "3;*",
+        negative_symbol: "-",
+        negative_number_format: 1,
+        currency_iso_code: "EUR",
+        currency_symbol: "€",
+        currency_fractional_digits: 2,
+        positive_currency_format: 3,
+        negative_currency_format: 8,
+        months: [
+            "enero",
+            "febrero",
+            "marzo",
+            "abril",
+            "mayo",
+            "junio",
+            "julio",
+            "agosto",
+            "septiembre",
+            "octubre",
+            "noviembre",
+            "diciembre",
+        ],
+        standalone_months: None,
+        short_months: [
+            "ene", "feb", "mar", "abr", "may", "jun", "jul", "ago", "sept", "oct", "nov", "dic",
+        ],
+        weekdays: [
+            "domingo",
+            "lunes",
+            "martes",
+            "miércoles",
+            "jueves",
+            "viernes",
+            "sábado",
+        ],
+        short_weekdays: ["dom", "lun", "mar", "mié", "jue", "vie", "sáb"],
+        am_pm: ["a. m.", "p. m."],
+        first_weekday: 1,
+        date_patterns: ["EEEE, d 'de' MMMM 'de' yyyy", "d MMM yyyy", "dd/MM/yyyy"],
+        time_patterns: ["H:mm:ss", "H:mm:ss", "H:mm"],
+    },
+    LocaleData {
+        name: "it-IT",
+        decimal_separator: ",",
+        grouping_separator: ".",
+        grouping_pattern: "3;*",
+        negative_symbol: "-",
+        negative_number_format: 1,
+        currency_iso_code: "EUR",
+        currency_symbol: "€",
+        currency_fractional_digits: 2,
+        positive_currency_format: 3,
+        negative_currency_format: 8,
+        months: [
+            "gennaio",
+            "febbraio",
+            "marzo",
+            "aprile",
+            "maggio",
+            "giugno",
+            "luglio",
+            "agosto",
+            "settembre",
+            "ottobre",
+            "novembre",
+            "dicembre",
+        ],
+        standalone_months: None,
+        short_months: [
+            "gen", "feb", "mar", "apr", "mag", "giu", "lug", "ago", "set", "ott", "nov", "dic",
+        ],
+        weekdays: [
+            "domenica",
+            "lunedì",
+            "martedì",
+            "mercoledì",
+            "giovedì",
+            "venerdì",
+            "sabato",
+        ],
+        short_weekdays: ["dom", "lun", "mar", "mer", "gio", "ven", "sab"],
+        am_pm: ["AM", "PM"],
+        first_weekday: 1,
+        date_patterns: ["EEEE d MMMM yyyy", "d MMM yyyy", "dd/MM/yyyy"],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+    },
+    LocaleData {
+        name: "pt-BR",
+        decimal_separator: ",",
+        grouping_separator: ".",
+        grouping_pattern: "3;*",
+        negative_symbol: "-",
+        negative_number_format: 1,
+        currency_iso_code: "BRL",
+        currency_symbol: "R$",
+        currency_fractional_digits: 2,
+        positive_currency_format: 2,
+        negative_currency_format: 9,
+        months: [
+            "janeiro",
+            "fevereiro",
+            "março",
+            "abril",
+            "maio",
+            "junho",
+            "julho",
+            "agosto",
+            "setembro",
+            "outubro",
+            "novembro",
+            "dezembro",
+        ],
+        standalone_months: None,
+        short_months: [
+            "jan.", "fev.", "mar.", "abr.", "mai.", "jun.", "jul.", "ago.", "set.", "out.", "nov.",
+            "dez.",
+        ],
+        weekdays: [
+            "domingo",
+            "segunda-feira",
+            "terça-feira",
+            "quarta-feira",
+            "quinta-feira",
+            "sexta-feira",
+            "sábado",
+        ],
+        short_weekdays: ["dom.", "seg.", "ter.", "qua.", "qui.", "sex.", "sáb."],
+        am_pm: ["AM", "PM"],
+        first_weekday: 0,
+        date_patterns: [
+            "EEEE, d 'de' MMMM 'de' yyyy",
+            "d 'de' MMM 'de' yyyy",
+            "dd/MM/yyyy",
+        ],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+    },
+    LocaleData {
+        name: "nl-NL",
+        decimal_separator: ",",
+        grouping_separator: ".",
+        grouping_pattern: "3;*",
+        negative_symbol: "-",
+        negative_number_format: 1,
+        currency_iso_code: "EUR",
+        currency_symbol: "€",
+        currency_fractional_digits: 2,
+        positive_currency_format: 2,
+        negative_currency_format: 12,
+        months: [
+            "januari",
+            "februari",
+            "maart",
+            "april",
+            "mei",
+            "juni",
+            "juli",
+            "augustus",
+            "september",
+            "oktober",
+            "november",
+            "december",
+        ],
+        standalone_months: None,
+        short_months: [
+            "jan", "feb", "mrt", "apr", "mei", "jun", "jul", "aug", "sep", "okt", "nov", "dec",
+        ],
+        weekdays: [
+            "zondag",
+            "maandag",
+            "dinsdag",
+            "woensdag",
+            "donderdag",
+            "vrijdag",
+            "zaterdag",
+        ],
+        short_weekdays: ["zo", "ma", "di", "wo", "do", "vr", "za"],
+        am_pm: ["a.m.", "p.m."],
+        first_weekday: 1,
+        date_patterns: ["EEEE d MMMM yyyy", "d MMM yyyy", "dd-MM-yyyy"],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+    },
+    LocaleData {
+        name: "sv-SE",
+        decimal_separator: ",",
+        grouping_separator: "\u{a0}",
+        grouping_pattern: "3;*",
+        negative_symbol: "-",
+        negative_number_format: 1,
+        currency_iso_code: "SEK",
+        currency_symbol: "kr",
+        currency_fractional_digits: 2,
+        positive_currency_format: 3,
+        negative_currency_format: 8,
+        months: [
+            "januari",
+            "februari",
+            "mars",
+            "april",
+            "maj",
+            "juni",
+            "juli",
+            "augusti",
+            "september",
+            "oktober",
+            "november",
+            "december",
+        ],
+        standalone_months: None,
+        short_months: [
+            "jan.", "feb.", "mars", "apr.", "maj", "juni", "juli", "aug.", "sep.", "okt.", "nov.",
+            "dec.",
+        ],
+        weekdays: [
+            "söndag", "måndag", "tisdag", "onsdag", "torsdag", "fredag", "lördag",
+        ],
+        short_weekdays: ["sön", "mån", "tis", "ons", "tors", "fre", "lör"],
+        am_pm: ["fm", "em"],
+        first_weekday: 1,
+        date_patterns: ["EEEE d MMMM yyyy", "d MMM yyyy", "yyyy-MM-dd"],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+    },
+    LocaleData {
+        name: "pl-PL",
+        decimal_separator: ",",
+        grouping_separator: "\u{a0}",
+        grouping_pattern: "3;*",
+        negative_symbol: "-",
+        negative_number_format: 1,
+        currency_iso_code: "PLN",
+        currency_symbol: "zł",
+        currency_fractional_digits: 2,
+        positive_currency_format: 3,
+        negative_currency_format: 8,
+        months: [
+            "stycznia",
+            "lutego",
+            "marca",
+            "kwietnia",
+            "maja",
+            "czerwca",
+            "lipca",
+            "sierpnia",
+            "września",
+            "października",
+            "listopada",
+            "grudnia",
+        ],
+        standalone_months: Some([
+            "styczeń",
+            "luty",
+            "marzec",
+            "kwiecień",
+            "maj",
+            "czerwiec",
+            "lipiec",
+            "sierpień",
+            "wrzesień",
+            "październik",
+            "listopad",
+            "grudzień",
+        ]),
+        short_months: [
+            "sty", "lut", "mar", "kwi", "maj", "cze", "lip", "sie", "wrz", "paź", "lis", "gru",
+        ],
+        weekdays: [
+            "niedziela",
+            "poniedziałek",
+            "wtorek",
+            "środa",
+            "czwartek",
+            "piątek",
+            "sobota",
+        ],
+        short_weekdays: ["niedz.", "pon.", "wt.", "śr.", "czw.", "pt.", "sob."],
+        am_pm: ["AM", "PM"],
+        first_weekday: 1,
+        date_patterns: ["EEEE, d MMMM yyyy", "d MMM yyyy", "dd.MM.yyyy"],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+    },
+    LocaleData {
+        name: "ru-RU",
+        decimal_separator: ",",
+        grouping_separator: "\u{a0}",
+        grouping_pattern: "3;*",
+        negative_symbol: "-",
+        negative_number_format: 1,
+        currency_iso_code: "RUB",
+        currency_symbol: "₽",
+        currency_fractional_digits: 2,
+        positive_currency_format: 3,
+        negative_currency_format: 8,
+        months: [
+            "января",
+            "февраля",
+            "марта",
+            "апреля",
+            "мая",
+            "июня",
+            "июля",
+            "августа",
+            "сентября",
+            "октября",
+            "ноября",
+            "декабря",
+        ],
+        standalone_months: Some([
+            "январь",
+            "февраль",
+            "март",
+            "апрель",
+            "май",
+            "июнь",
+            "июль",
+            "август",
+            "сентябрь",
+            "октябрь",
+            "ноябрь",
+            "декабрь",
+        ]),
+        short_months: [
+            "янв.",
+            "февр.",
+            "мар.",
+            "апр.",
+            "мая",
+            "июн.",
+            "июл.",
+            "авг.",
+            "сент.",
+            "окт.",
+            "нояб.",
+            "дек.",
+        ],
+        weekdays: [
+            "воскресенье",
+            "понедельник",
+            "вторник",
+            "среда",
+            "четверг",
+            "пятница",
+            "суббота",
+        ],
+        short_weekdays: ["вс", "пн", "вт", "ср", "чт", "пт", "сб"],
+        am_pm: ["AM", "PM"],
+        first_weekday: 1,
+        date_patterns: ["d MMMM yyyy 'г.'", "d MMM yyyy 'г.'", "dd.MM.yyyy"],
+        time_patterns: ["H:mm:ss", "H:mm:ss", "H:mm"],
+    },
+    LocaleData {
+        name: "tr-TR",
+        decimal_separator: ",",
+        grouping_separator: ".",
+        grouping_pattern: "3;*",
+        negative_symbol: "-",
+        negative_number_format: 1,
+        currency_iso_code: "TRY",
+        currency_symbol: "₺",
+        currency_fractional_digits: 2,
+        positive_currency_format: 0,
+        negative_currency_format: 1,
+        months: [
+            "Ocak", "Şubat", "Mart", "Nisan", "Mayıs", "Haziran", "Temmuz", "Ağustos", "Eylül",
+            "Ekim", "Kasım", "Aralık",
+        ],
+        standalone_months: None,
+        short_months: [
+            "Oca", "Şub", "Mar", "Nis", "May", "Haz", "Tem", "Ağu", "Eyl", "Eki", "Kas", "Ara",
+        ],
+        weekdays: [
+            "Pazar",
+            "Pazartesi",
+            "Salı",
+            "Çarşamba",
+            "Perşembe",
+            "Cuma",
+            "Cumartesi",
+        ],
+        short_weekdays: ["Paz", "Pzt", "Sal", "Çar", "Per", "Cum", "Cmt"],
+        am_pm: ["ÖÖ", "ÖS"],
+        first_weekday: 1,
+        date_patterns: ["d MMMM yyyy EEEE", "d MMM yyyy", "d.MM.yyyy"],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+    },
+    LocaleData {
+        name: "he-IL",
+        decimal_separator: ".",
+        grouping_separator: ",",
+        grouping_pattern: "3;*",
+        negative_symbol: "-",
+        negative_number_format: 1,
+        currency_iso_code: "ILS",
+        currency_symbol: "₪",
+        currency_fractional_digits: 2,
+        positive_currency_format: 3,
+        negative_currency_format: 8,
+        months: [
+            "ינואר",
+            "פברואר",
+            "מרץ",
+            "אפריל",
+            "מאי",
+            "יוני",
+            "יולי",
+            "אוגוסט",
+            "ספטמבר",
+            "אוקטובר",
+            "נובמבר",
+            "דצמבר",
+        ],
+        standalone_months: None,
+        short_months: [
+            "ינו׳", "פבר׳", "מרץ", "אפר׳", "מאי", "יוני", "יולי", "אוג׳", "ספט׳", "אוק׳", "נוב׳",
+            "דצמ׳",
+        ],
+        weekdays: [
+            "יום ראשון",
+            "יום שני",
+            "יום שלישי",
+            "יום רביעי",
+            "יום חמישי",
+            "יום שישי",
+            "יום שבת",
+        ],
+        short_weekdays: [
+            "יום א׳",
+            "יום ב׳",
+            "יום ג׳",
+            "יום ד׳",
+            "יום ה׳",
+            "יום ו׳",
+            "שבת",
+        ],
+        am_pm: ["לפנה״צ", "אחה״צ"],
+        first_weekday: 0,
+        date_patterns: ["EEEE, d בMMMM yyyy", "d בMMM yyyy", "d.M.yyyy"],
+        time_patterns: ["H:mm:ss", "H:mm:ss", "H:mm"],
+    },
+    LocaleData {
+        name: "ja-JP",
+        decimal_separator: ".",
+        grouping_separator: ",",
+        grouping_pattern: "3;*",
+        negative_symbol: "-",
+        negative_number_format: 1,
+        currency_iso_code: "JPY",
+        currency_symbol: "￥",
+        currency_fractional_digits: 0,
+        positive_currency_format: 0,
+        negative_currency_format: 1,
+        months: NUMERIC_MONTHS_JA,
+        standalone_months: None,
+        short_months: NUMERIC_MONTHS_JA,
+        weekdays: [
+            "日曜日",
+            "月曜日",
+            "火曜日",
+            "水曜日",
+            "木曜日",
+            "金曜日",
+            "土曜日",
+        ],
+        short_weekdays: ["日", "月", "火", "水", "木", "金", "土"],
+        am_pm: ["午前", "午後"],
+        first_weekday: 0,
+        date_patterns: ["yyyy年M月d日EEEE", "yyyy/MM/dd", "yyyy/MM/dd"],
+        time_patterns: ["H:mm:ss", "H:mm:ss", "H:mm"],
+    },
+    LocaleData {
+        name: "zh-CN",
+        decimal_separator: ".",
+        grouping_separator: ",",
+        grouping_pattern: "3;*",
+        negative_symbol: "-",
+        negative_number_format: 1,
+        currency_iso_code: "CNY",
+        currency_symbol: "¥",
+        currency_fractional_digits: 2,
+        positive_currency_format: 0,
+        negative_currency_format: 1,
+        months: [
+            "一月",
+            "二月",
+            "三月",
+            "四月",
+            "五月",
+            "六月",
+            "七月",
+            "八月",
+            "九月",
+            "十月",
+            "十一月",
+            "十二月",
+        ],
+        standalone_months: None,
+        short_months: NUMERIC_MONTHS_JA,
+        weekdays: [
+            "星期日",
+            "星期一",
+            "星期二",
+            "星期三",
+            "星期四",
+            "星期五",
+            "星期六",
+        ],
+        short_weekdays: ["周日", "周一", "周二", "周三", "周四", "周五", "周六"],
+        am_pm: ["上午", "下午"],
+        first_weekday: 1,
+        date_patterns: ["yyyy年M月d日EEEE", "yyyy年M月d日", "yyyy/M/d"],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+    },
+    LocaleData {
+        name: "ko-KR",
+        decimal_separator: ".",
+        grouping_separator: ",",
+        grouping_pattern: "3;*",
+        negative_symbol: "-",
+        negative_number_format: 1,
+        currency_iso_code: "KRW",
+        currency_symbol: "₩",
+        currency_fractional_digits: 0,
+        positive_currency_format: 0,
+        negative_currency_format: 1,
+        months: [
+            "1월", "2월", "3월", "4월", "5월", "6월", "7월", "8월", "9월", "10월", "11월", "12월",
+        ],
+        standalone_months: None,
+        short_months: [
+            "1월", "2월", "3월", "4월", "5월", "6월", "7월", "8월", "9월", "10월", "11월", "12월",
+        ],
+        weekdays: [
+            "일요일",
+            "월요일",
+            "화요일",
+            "수요일",
+            "목요일",
+            "금요일",
+            "토요일",
+        ],
+        short_weekdays: ["일", "월", "화", "수", "목", "금", "토"],
+        am_pm: ["오전", "오후"],
+        first_weekday: 0,
+        date_patterns: ["yyyy년 M월 d일 EEEE", "yyyy. M. d.", "yy. M. d."],
+        time_patterns: ["a h:mm:ss", "a h:mm:ss", "a h:mm"],
+    },
+];
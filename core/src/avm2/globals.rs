@@ -105,20 +105,29 @@ pub struct SystemClasses<'gc> {
     pub object_vector: ClassObject<'gc>,
     pub soundtransform: ClassObject<'gc>,
     pub soundchannel: ClassObject<'gc>,
+    pub microphone: ClassObject<'gc>,
+    pub camera: ClassObject<'gc>,
     pub bitmap: ClassObject<'gc>,
     pub bitmapdata: ClassObject<'gc>,
+    pub pngencoderoptions: ClassObject<'gc>,
+    pub jpegencoderoptions: ClassObject<'gc>,
+    pub jpegxrencoderoptions: ClassObject<'gc>,
     pub date: ClassObject<'gc>,
     pub qname: ClassObject<'gc>,
     pub mouseevent: ClassObject<'gc>,
     pub progressevent: ClassObject<'gc>,
     pub httpstatusevent: ClassObject<'gc>,
     pub textevent: ClassObject<'gc>,
+    pub dataevent: ClassObject<'gc>,
     pub errorevent: ClassObject<'gc>,
     pub ioerrorevent: ClassObject<'gc>,
     pub securityerrorevent: ClassObject<'gc>,
     pub transform: ClassObject<'gc>,
     pub colortransform: ClassObject<'gc>,
     pub matrix: ClassObject<'gc>,
+    pub matrix3d: ClassObject<'gc>,
+    pub vector3d: ClassObject<'gc>,
+    pub perspectiveprojection: ClassObject<'gc>,
     pub illegaloperationerror: ClassObject<'gc>,
     pub eventdispatcher: ClassObject<'gc>,
     pub rectangle: ClassObject<'gc>,
@@ -139,6 +148,7 @@ pub struct SystemClasses<'gc> {
     pub uncaughterrorevents: ClassObject<'gc>,
     pub statictext: ClassObject<'gc>,
     pub textlinemetrics: ClassObject<'gc>,
+    pub textsnapshot: ClassObject<'gc>,
     pub stage3d: ClassObject<'gc>,
     pub context3d: ClassObject<'gc>,
     pub indexbuffer3d: ClassObject<'gc>,
@@ -171,12 +181,26 @@ pub struct SystemClasses<'gc> {
     pub filefilter: ClassObject<'gc>,
     pub font: ClassObject<'gc>,
     pub textline: ClassObject<'gc>,
+    pub groupelement: ClassObject<'gc>,
+    pub graphicelement: ClassObject<'gc>,
+    pub spacejustifier: ClassObject<'gc>,
+    pub numberparseresult: ClassObject<'gc>,
+    pub currencyparseresult: ClassObject<'gc>,
     pub sampledataevent: ClassObject<'gc>,
+    pub activityevent: ClassObject<'gc>,
     pub avm1movie: ClassObject<'gc>,
     pub focusevent: ClassObject<'gc>,
     pub dictionary: ClassObject<'gc>,
     pub id3info: ClassObject<'gc>,
     pub textrun: ClassObject<'gc>,
+    pub sample: ClassObject<'gc>,
+    pub newobjectsample: ClassObject<'gc>,
+    pub deleteobjectsample: ClassObject<'gc>,
+    pub stackframe: ClassObject<'gc>,
+    pub worker: ClassObject<'gc>,
+    pub messagechannel: ClassObject<'gc>,
+    pub mutex: ClassObject<'gc>,
+    pub condition: ClassObject<'gc>,
 }
 
 impl<'gc> SystemClasses<'gc> {
@@ -235,20 +259,29 @@ impl<'gc> SystemClasses<'gc> {
             object_vector: object,
             soundtransform: object,
             soundchannel: object,
+            microphone: object,
+            camera: object,
             bitmap: object,
             bitmapdata: object,
+            pngencoderoptions: object,
+            jpegencoderoptions: object,
+            jpegxrencoderoptions: object,
             date: object,
             qname: object,
             mouseevent: object,
             progressevent: object,
             httpstatusevent: object,
             textevent: object,
+            dataevent: object,
             errorevent: object,
             ioerrorevent: object,
             securityerrorevent: object,
             transform: object,
             colortransform: object,
             matrix: object,
+            matrix3d: object,
+            vector3d: object,
+            perspectiveprojection: object,
             illegaloperationerror: object,
             eventdispatcher: object,
             rectangle: object,
@@ -269,6 +302,7 @@ impl<'gc> SystemClasses<'gc> {
             uncaughterrorevents: object,
             statictext: object,
             textlinemetrics: object,
+            textsnapshot: object,
             stage3d: object,
             context3d: object,
             indexbuffer3d: object,
@@ -301,12 +335,26 @@ impl<'gc> SystemClasses<'gc> {
             filefilter: object,
             font: object,
             textline: object,
+            groupelement: object,
+            graphicelement: object,
+            spacejustifier: object,
+            numberparseresult: object,
+            currencyparseresult: object,
             sampledataevent: object,
+            activityevent: object,
             avm1movie: object,
             focusevent: object,
             dictionary: object,
             id3info: object,
             textrun: object,
+            sample: object,
+            newobjectsample: object,
+            deleteobjectsample: object,
+            stackframe: object,
+            worker: object,
+            messagechannel: object,
+            mutex: object,
+            condition: object,
         }
     }
 }
@@ -769,6 +817,13 @@ fn load_playerglobal<'gc>(
             ("flash.display", "AVM1Movie", avm1movie),
             ("flash.display", "Bitmap", bitmap),
             ("flash.display", "BitmapData", bitmapdata),
+            ("flash.display", "PNGEncoderOptions", pngencoderoptions),
+            ("flash.display", "JPEGEncoderOptions", jpegencoderoptions),
+            (
+                "flash.display",
+                "JPEGXREncoderOptions",
+                jpegxrencoderoptions
+            ),
             ("flash.display", "Scene", scene),
             ("flash.display", "FrameLabel", framelabel),
             ("flash.display", "IGraphicsData", igraphicsdata),
@@ -820,6 +875,7 @@ fn load_playerglobal<'gc>(
             ("flash.events", "Event", event),
             ("flash.events", "EventDispatcher", eventdispatcher),
             ("flash.events", "TextEvent", textevent),
+            ("flash.events", "DataEvent", dataevent),
             ("flash.events", "ErrorEvent", errorevent),
             ("flash.events", "KeyboardEvent", keyboardevent),
             ("flash.events", "ProgressEvent", progressevent),
@@ -835,12 +891,17 @@ fn load_playerglobal<'gc>(
             ("flash.events", "ContextMenuEvent", contextmenuevent),
             ("flash.events", "FocusEvent", focusevent),
             ("flash.geom", "Matrix", matrix),
+            ("flash.geom", "Matrix3D", matrix3d),
+            ("flash.geom", "Vector3D", vector3d),
+            ("flash.geom", "PerspectiveProjection", perspectiveprojection),
             ("flash.geom", "Point", point),
             ("flash.geom", "Rectangle", rectangle),
             ("flash.geom", "Transform", transform),
             ("flash.geom", "ColorTransform", colortransform),
             ("flash.media", "ID3Info", id3info),
             ("flash.media", "SoundChannel", soundchannel),
+            ("flash.media", "Microphone", microphone),
+            ("flash.media", "Camera", camera),
             ("flash.media", "SoundTransform", soundtransform),
             ("flash.media", "Video", video),
             ("flash.net", "URLVariables", urlvariables),
@@ -849,13 +910,31 @@ fn load_playerglobal<'gc>(
             ("flash.utils", "ByteArray", bytearray),
             ("flash.utils", "Dictionary", dictionary),
             ("flash.system", "ApplicationDomain", application_domain),
+            ("flash.system", "Worker", worker),
+            ("flash.system", "MessageChannel", messagechannel),
+            ("flash.concurrent", "Mutex", mutex),
+            ("flash.concurrent", "Condition", condition),
             ("flash.text", "Font", font),
             ("flash.text", "StaticText", statictext),
             ("flash.text", "TextFormat", textformat),
             ("flash.text", "TextField", textfield),
             ("flash.text", "TextLineMetrics", textlinemetrics),
+            ("flash.text", "TextSnapshot", textsnapshot),
             ("flash.text", "TextRun", textrun),
             ("flash.text.engine", "TextLine", textline),
+            ("flash.text.engine", "GroupElement", groupelement),
+            ("flash.text.engine", "GraphicElement", graphicelement),
+            ("flash.text.engine", "SpaceJustifier", spacejustifier),
+            (
+                "flash.globalization",
+                "NumberParseResult",
+                numberparseresult
+            ),
+            (
+                "flash.globalization",
+                "CurrencyParseResult",
+                currencyparseresult
+            ),
             ("flash.filters", "BevelFilter", bevelfilter),
             ("flash.filters", "BitmapFilter", bitmapfilter),
             ("flash.filters", "BlurFilter", blurfilter),
@@ -872,6 +951,11 @@ fn load_playerglobal<'gc>(
             ("flash.filters", "GradientGlowFilter", gradientglowfilter),
             ("flash.filters", "ShaderFilter", shaderfilter),
             ("flash.events", "SampleDataEvent", sampledataevent),
+            ("flash.events", "ActivityEvent", activityevent),
+            ("flash.sampler", "Sample", sample),
+            ("flash.sampler", "NewObjectSample", newobjectsample),
+            ("flash.sampler", "DeleteObjectSample", deleteobjectsample),
+            ("flash.sampler", "StackFrame", stackframe),
         ]
     );
 
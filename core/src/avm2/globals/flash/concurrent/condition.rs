@@ -0,0 +1,162 @@
+//! `flash.concurrent.Condition` native methods
+
+use super::mutex::mutex_id;
+use crate::avm2::activation::Activation;
+use crate::avm2::error::illegal_operation_error;
+use crate::avm2::object::Object;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::worker::{self, shared_object, ConditionId, SharedRef};
+use crate::avm2::Error;
+use std::time::Duration;
+
+pub fn native_instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation.super_init(this, &[])?;
+    Ok(Value::Undefined)
+}
+
+fn condition_id<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+) -> Option<ConditionId> {
+    match activation.avm2().worker_objects.shared_ref(this)? {
+        SharedRef::Condition(id) => Some(id),
+        _ => None,
+    }
+}
+
+/// The error thrown when a worker that doesn't own the mutex calls `method`.
+fn mutex_not_owned<'gc>(activation: &mut Activation<'_, 'gc>, method: &str) -> Error<'gc> {
+    let code = match method {
+        "notify" => 1516,
+        "notifyAll" => 1517,
+        _ => 1518,
+    };
+    let message =
+        format!("Error #{code}: Condition cannot {method} if associated mutex is not owned.");
+    match illegal_operation_error(activation, &message, code) {
+        Ok(error) => Error::AvmError(error),
+        Err(error) => error,
+    }
+}
+
+/// Implements `Condition`'s `init` method, which is called from the constructor
+pub fn init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mutex = args.get_object(activation, 0, "mutex")?;
+    if let Some(mutex) = mutex_id(activation, mutex) {
+        let id = activation
+            .context
+            .worker_group
+            .borrow_mut()
+            .create_condition(mutex);
+        activation
+            .avm2()
+            .worker_objects
+            .register(SharedRef::Condition(id), this);
+    }
+    Ok(Value::Undefined)
+}
+
+/// Implements `Condition.mutex`
+pub fn get_mutex<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(id) = condition_id(activation, this) else {
+        return Ok(Value::Null);
+    };
+    let mutex = activation.context.worker_group.borrow().condition_mutex(id);
+    Ok(shared_object(activation, SharedRef::Mutex(mutex))?.into())
+}
+
+/// Notify the first worker waiting on the condition, or all of them.
+fn notify_waiting<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    all: bool,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(id) = condition_id(activation, this) else {
+        return Ok(Value::Undefined);
+    };
+    let worker = activation.context.worker_id;
+    let result = activation
+        .context
+        .worker_group
+        .borrow_mut()
+        .notify(id, worker, all);
+    if result.is_err() {
+        let method = if all { "notifyAll" } else { "notify" };
+        return Err(mutex_not_owned(activation, method));
+    }
+    Ok(Value::Undefined)
+}
+
+/// Implements `Condition.notify`
+pub fn notify<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    notify_waiting(activation, this, false)
+}
+
+/// Implements `Condition.notifyAll`
+pub fn notify_all<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    notify_waiting(activation, this, true)
+}
+
+/// Implements `Condition.wait`
+///
+/// The mutex is unlocked while waiting, and locked again before returning.
+pub fn wait<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(id) = condition_id(activation, this) else {
+        return Ok(false.into());
+    };
+    let timeout = args.get_f64(activation, 0)?;
+    let timeout = (timeout >= 0.0).then(|| Duration::from_secs_f64(timeout / 1000.0));
+
+    let worker = activation.context.worker_id;
+    let result = activation
+        .context
+        .worker_group
+        .borrow_mut()
+        .start_wait(id, worker);
+    let Ok(depth) = result else {
+        return Err(mutex_not_owned(activation, "wait"));
+    };
+
+    let waited = worker::block_worker(activation.context, timeout, |group| {
+        group.is_notified(id, worker)
+    });
+    let notified = activation
+        .context
+        .worker_group
+        .borrow_mut()
+        .stop_wait(id, worker);
+
+    waited.map_err(|e| Error::RustError(Box::new(e)))?;
+
+    let mutex = activation.context.worker_group.borrow().condition_mutex(id);
+    worker::block_worker(activation.context, None, |group| {
+        group.try_relock(mutex, worker, depth)
+    })
+    .map_err(|e| Error::RustError(Box::new(e)))?;
+    Ok(notified.into())
+}
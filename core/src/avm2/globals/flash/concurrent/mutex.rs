@@ -0,0 +1,100 @@
+//! `flash.concurrent.Mutex` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::error::illegal_operation_error;
+use crate::avm2::object::Object;
+use crate::avm2::value::Value;
+use crate::avm2::worker::{self, MutexId, SharedRef};
+use crate::avm2::Error;
+
+pub fn native_instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation.super_init(this, &[])?;
+    Ok(Value::Undefined)
+}
+
+pub(super) fn mutex_id<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+) -> Option<MutexId> {
+    match activation.avm2().worker_objects.shared_ref(this)? {
+        SharedRef::Mutex(id) => Some(id),
+        _ => None,
+    }
+}
+
+/// Implements `Mutex`'s `init` method, which is called from the constructor
+pub fn init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let id = activation.context.worker_group.borrow_mut().create_mutex();
+    activation
+        .avm2()
+        .worker_objects
+        .register(SharedRef::Mutex(id), this);
+    Ok(Value::Undefined)
+}
+
+/// Implements `Mutex.lock`
+pub fn lock<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(id) = mutex_id(activation, this) else {
+        return Ok(Value::Undefined);
+    };
+    let worker = activation.context.worker_id;
+    // Without a timeout, this only returns once the mutex is locked.
+    worker::block_worker(activation.context, None, |group| group.try_lock(id, worker))
+        .map_err(|e| Error::RustError(Box::new(e)))?;
+    Ok(Value::Undefined)
+}
+
+/// Implements `Mutex.tryLock`
+pub fn try_lock<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(id) = mutex_id(activation, this) else {
+        return Ok(false.into());
+    };
+    let worker = activation.context.worker_id;
+    let locked = activation
+        .context
+        .worker_group
+        .borrow_mut()
+        .try_lock(id, worker);
+    Ok(locked.into())
+}
+
+/// Implements `Mutex.unlock`
+pub fn unlock<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(id) = mutex_id(activation, this) else {
+        return Ok(Value::Undefined);
+    };
+    let worker = activation.context.worker_id;
+    let result = activation
+        .context
+        .worker_group
+        .borrow_mut()
+        .unlock(id, worker);
+    if result.is_err() {
+        return Err(Error::AvmError(illegal_operation_error(
+            activation,
+            "Error #1514: Mutex cannot be unlocked if it is not owned.",
+            1514,
+        )?));
+    }
+    Ok(Value::Undefined)
+}
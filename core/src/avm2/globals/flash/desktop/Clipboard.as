@@ -1,54 +1,165 @@
 package flash.desktop {
-    import __ruffle__.stub_getter;
-    import __ruffle__.stub_method;
-    import flash.system.System;
+    import flash.filesystem.File;
 
     public class Clipboard {
-        private static var _generalClipboard = new Clipboard();
+        // `ClipboardFormats.FILE_LIST_FORMAT`, which is only defined in AIR.
+        private static const FILE_LIST_FORMAT:String = "air:file list";
+
+        private static var _generalClipboard:Clipboard = null;
 
         public static function get generalClipboard(): Clipboard {
+            if (Clipboard._generalClipboard == null) {
+                Clipboard._constructingGeneral = true;
+                Clipboard._generalClipboard = new Clipboard();
+                Clipboard._constructingGeneral = false;
+                Clipboard._generalClipboard._isGeneral = true;
+            }
             return Clipboard._generalClipboard;
         }
 
+        // Whether the general clipboard is being constructed, which is the
+        // only time this class can be instantiated outside of AIR.
+        private static var _constructingGeneral:Boolean = false;
+
+        // Whether this is the general clipboard, whose standard formats are
+        // stored on the system clipboard.
+        private var _isGeneral:Boolean = false;
+
+        // The data of the formats that are stored in this object, by format.
+        private var _data:Object = {};
+
+        // The handlers of formats whose data is rendered on demand, by format.
+        private var _handlers:Object = {};
+
         function Clipboard() {
-            // TODO: This should only be callable in AIR
+            if (!Clipboard._constructingGeneral && !isAir()) {
+                throw new ArgumentError("Error #2012: Clipboard$ class cannot be instantiated.", 2012);
+            }
+        }
+
+        private static function checkNonNull(value:Object, name:String):void {
+            if (value == null) {
+                throw new TypeError("Error #2007: Parameter " + name + " must be non-null.", 2007);
+            }
+        }
+
+        private function isSystemData(format:String):Boolean {
+            return this._isGeneral && isSystemFormat(format);
         }
 
+        private static native function isAir():Boolean;
+        private static native function isSystemFormat(format:String):Boolean;
+        private native function systemFormats():Array;
+        private native function getSystemData(format:String):Object;
+        private native function setSystemData(format:String, data:Object):Boolean;
+        private native function clearSystemData(format:String):void;
+        private native function clearSystem():void;
+
         public function get formats(): Array {
-            stub_getter("flash.desktop.Clipboard", "formats");
-            return new Array();
+            var formats:Array = this._isGeneral ? this.systemFormats() : [];
+            for (var format:String in this._data) {
+                formats.push(format);
+            }
+            for (format in this._handlers) {
+                if (formats.indexOf(format) == -1) {
+                    formats.push(format);
+                }
+            }
+            return formats;
         }
 
         public function clear(): void {
-            stub_method("flash.desktop.Clipboard", "clear");
+            this._data = {};
+            this._handlers = {};
+            if (this._isGeneral) {
+                this.clearSystem();
+            }
         }
 
         public function clearData(format: String): void {
-            stub_method("flash.desktop.Clipboard", "clearData");
+            checkNonNull(format, "format");
+            delete this._data[format];
+            delete this._handlers[format];
+            if (this.isSystemData(format)) {
+                this.clearSystemData(format);
+            }
         }
 
         public function getData(format: String, transferMode: String = ClipboardTransferMode.ORIGINAL_PREFERRED): Object {
-            stub_method("flash.desktop.Clipboard", "getData");
+            checkNonNull(format, "format");
+            if (transferMode != ClipboardTransferMode.ORIGINAL_ONLY &&
+                transferMode != ClipboardTransferMode.ORIGINAL_PREFERRED &&
+                transferMode != ClipboardTransferMode.CLONE_ONLY &&
+                transferMode != ClipboardTransferMode.CLONE_PREFERRED) {
+                throw new ArgumentError("Error #2008: Parameter transferMode must be one of the accepted values.", 2008);
+            }
+
+            if (format in this._handlers) {
+                // The handler is only called once, and its data is kept.
+                var handler:Function = this._handlers[format];
+                delete this._handlers[format];
+                this._data[format] = handler();
+            }
+            if (format in this._data) {
+                return this._data[format];
+            }
+
+            if (this.isSystemData(format)) {
+                var data:Object = this.getSystemData(format);
+                if (format == FILE_LIST_FORMAT && data != null) {
+                    var files:Array = [];
+                    for each (var path:String in data) {
+                        files.push(new File(path));
+                    }
+                    return files;
+                }
+                return data;
+            }
             return null;
         }
 
         public function hasFormat(format: String): Boolean {
-            stub_method("flash.desktop.Clipboard", "hasFormat");
-            return false;
+            checkNonNull(format, "format");
+            if (format in this._data || format in this._handlers) {
+                return true;
+            }
+            return this.isSystemData(format) && this.systemFormats().indexOf(format) != -1;
         }
 
         public function setData(format: String, data: Object, serializable: Boolean = true): Boolean {
-            stub_method("flash.desktop.Clipboard", "setData");
-            if (format == ClipboardFormats.TEXT_FORMAT) {
-                System.setClipboard(data);
-                return true;
+            checkNonNull(format, "format");
+            delete this._handlers[format];
+
+            if (this.isSystemData(format)) {
+                if (format == FILE_LIST_FORMAT && data is Array) {
+                    var paths:Array = [];
+                    for each (var file:Object in data) {
+                        if (file is File) {
+                            paths.push(File(file).nativePath);
+                        }
+                    }
+                    data = paths;
+                }
+                return this.setSystemData(format, data);
             }
-            return false;
+
+            this._data[format] = data;
+            return true;
         }
 
         public function setDataHandler(format: String, handler: Function, serializable: Boolean = true): Boolean {
-            stub_method("flash.desktop.Clipboard", "setDataHandler");
-            return false;
+            checkNonNull(format, "format");
+            checkNonNull(handler, "handler");
+
+            if (this.isSystemData(format)) {
+                // The system clipboard can't render data on demand,
+                // so the handler is called right away.
+                return this.setData(format, handler(), serializable);
+            }
+
+            delete this._data[format];
+            this._handlers[format] = handler;
+            return true;
         }
     }
 }
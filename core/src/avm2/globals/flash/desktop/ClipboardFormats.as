@@ -15,6 +15,7 @@ package flash.desktop
         public static const BITMAP_FORMAT:String = "air:bitmap";
 
         // An array of files (AIR only).
+        [API("661")]
         public static const FILE_LIST_FORMAT:String = "air:file list";
 
         // File promise list (AIR only).
@@ -0,0 +1,269 @@
+//! `flash.desktop.Clipboard` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::bytearray::ByteArrayStorage;
+use crate::avm2::error::security_error;
+use crate::avm2::object::{ArrayObject, BitmapDataObject, ByteArrayObject, Object, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::{ArrayStorage, Error};
+use crate::backend::ui::{ClipboardBitmap, ClipboardContent, ClipboardFormat};
+use crate::bitmap::bitmap_data::{BitmapData, BitmapDataWrapper, Color};
+use crate::string::AvmString;
+use crate::PlayerRuntime;
+use gc_arena::GcCell;
+use std::path::PathBuf;
+
+/// The name of a format in `ClipboardFormats`.
+fn format_name(format: ClipboardFormat) -> &'static str {
+    match format {
+        ClipboardFormat::Text => "air:text",
+        ClipboardFormat::Html => "air:html",
+        ClipboardFormat::RichText => "air:rtf",
+        ClipboardFormat::Url => "air:url",
+        ClipboardFormat::Bitmap => "air:bitmap",
+        ClipboardFormat::FileList => "air:file list",
+    }
+}
+
+fn runtime_is_air(activation: &mut Activation<'_, '_>) -> bool {
+    activation.avm2().player_runtime == PlayerRuntime::AIR
+}
+
+/// Outside of AIR, the system clipboard may only be read while a `paste`
+/// event is being dispatched.
+fn check_system_readable<'gc>(activation: &mut Activation<'_, 'gc>) -> Result<(), Error<'gc>> {
+    if runtime_is_air(activation) || activation.avm2().dispatching_paste {
+        return Ok(());
+    }
+    Err(Error::AvmError(security_error(
+        activation,
+        "Error #2179: The Clipboard.generalClipboard object may only be read while processing a flash.events.Event.PASTE event.",
+        2179,
+    )?))
+}
+
+/// Get the system clipboard format named in `args[0]`.
+///
+/// Other formats are only stored in the `Clipboard` object itself. Lists of
+/// files are only supported in AIR.
+fn format_arg<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    args: &[Value<'gc>],
+) -> Result<Option<ClipboardFormat>, Error<'gc>> {
+    let name = args.get_string_non_null(activation, 0, "format")?;
+    let is_air = runtime_is_air(activation);
+    Ok([
+        ClipboardFormat::Text,
+        ClipboardFormat::Html,
+        ClipboardFormat::RichText,
+        ClipboardFormat::Url,
+        ClipboardFormat::Bitmap,
+        ClipboardFormat::FileList,
+    ]
+    .into_iter()
+    .filter(|format| is_air || *format != ClipboardFormat::FileList)
+    .find(|format| &name == format_name(*format).as_bytes()))
+}
+
+/// Implements `Clipboard.isAir`
+pub fn is_air<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(runtime_is_air(activation).into())
+}
+
+/// Implements `Clipboard.isSystemFormat`
+pub fn is_system_format<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(format_arg(activation, args)?.is_some().into())
+}
+
+/// Implements `Clipboard.systemFormats`
+pub fn system_formats<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    check_system_readable(activation)?;
+
+    let is_air = runtime_is_air(activation);
+    let formats: Vec<Value<'gc>> = activation
+        .context
+        .ui
+        .clipboard_contents()
+        .formats()
+        .into_iter()
+        .filter(|format| is_air || *format != ClipboardFormat::FileList)
+        .map(|format| format_name(format).into())
+        .collect();
+    Ok(ArrayObject::from_storage(activation, ArrayStorage::from_args(&formats))?.into())
+}
+
+/// Implements `Clipboard.getSystemData`
+pub fn get_system_data<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    check_system_readable(activation)?;
+
+    let Some(format) = format_arg(activation, args)? else {
+        return Ok(Value::Null);
+    };
+
+    let contents = activation.context.ui.clipboard_contents();
+    let value = match format {
+        ClipboardFormat::Text => contents.text.map(|text| string_value(activation, text)),
+        ClipboardFormat::Html => contents.html.map(|html| string_value(activation, html)),
+        ClipboardFormat::Url => contents.url.map(|url| string_value(activation, url)),
+        ClipboardFormat::RichText => match contents.rich_text {
+            Some(rich_text) => {
+                let storage = ByteArrayStorage::from_vec(rich_text);
+                Some(ByteArrayObject::from_storage(activation, storage)?.into())
+            }
+            None => None,
+        },
+        ClipboardFormat::Bitmap => match contents.bitmap {
+            Some(bitmap) => Some(bitmap_data_from_clipboard(activation, bitmap)?),
+            None => None,
+        },
+        ClipboardFormat::FileList => match contents.file_list {
+            Some(files) => {
+                let paths: Vec<Value<'gc>> = files
+                    .iter()
+                    .map(|path| string_value(activation, path.to_string_lossy().into_owned()))
+                    .collect();
+                Some(ArrayObject::from_storage(activation, ArrayStorage::from_args(&paths))?.into())
+            }
+            None => None,
+        },
+    };
+    Ok(value.unwrap_or(Value::Null))
+}
+
+fn string_value<'gc>(activation: &mut Activation<'_, 'gc>, string: String) -> Value<'gc> {
+    AvmString::new_utf8(activation.gc(), string).into()
+}
+
+fn bitmap_data_from_clipboard<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    bitmap: ClipboardBitmap,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let pixels = bitmap
+        .rgba
+        .chunks_exact(4)
+        .map(|p| Color::argb(p[3], p[0], p[1], p[2]).to_premultiplied_alpha(true))
+        .collect();
+    let bitmap_data = BitmapData::new_with_pixels(bitmap.width, bitmap.height, true, pixels);
+    let bitmap_data = BitmapDataWrapper::new(GcCell::new(activation.gc(), bitmap_data));
+    let class = activation.avm2().classes().bitmapdata;
+    Ok(BitmapDataObject::from_bitmap_data_internal(activation, bitmap_data, class)?.into())
+}
+
+/// Implements `Clipboard.setSystemData`
+pub fn set_system_data<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(format) = format_arg(activation, args)? else {
+        return Ok(false.into());
+    };
+    let data = args.get_value(1);
+
+    let mut contents = activation.context.ui.clipboard_contents();
+    match format {
+        ClipboardFormat::Text => {
+            contents.text = Some(data.coerce_to_string(activation)?.to_string())
+        }
+        ClipboardFormat::Html => {
+            contents.html = Some(data.coerce_to_string(activation)?.to_string())
+        }
+        ClipboardFormat::Url => contents.url = Some(data.coerce_to_string(activation)?.to_string()),
+        ClipboardFormat::RichText => {
+            let Some(bytes) = data.as_object().and_then(|data| data.as_bytearray_object()) else {
+                return Ok(false.into());
+            };
+            contents.rich_text = Some(bytes.storage().bytes().to_vec());
+        }
+        ClipboardFormat::Bitmap => {
+            let Some(bitmap_data) = data.as_object().and_then(|data| data.as_bitmap_data()) else {
+                return Ok(false.into());
+            };
+            contents.bitmap = Some(clipboard_bitmap(activation, bitmap_data));
+        }
+        ClipboardFormat::FileList => {
+            let Some(array) = data.as_object() else {
+                return Ok(false.into());
+            };
+            let paths: Vec<Value<'gc>> = array
+                .as_array_storage()
+                .map(|storage| storage.iter().flatten().collect())
+                .unwrap_or_default();
+            let mut files = Vec::with_capacity(paths.len());
+            for path in paths {
+                files.push(PathBuf::from(
+                    path.coerce_to_string(activation)?.to_string(),
+                ));
+            }
+            contents.file_list = Some(files);
+        }
+    }
+    activation.context.ui.set_clipboard_contents(contents);
+
+    Ok(true.into())
+}
+
+fn clipboard_bitmap<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    bitmap_data: BitmapDataWrapper<'gc>,
+) -> ClipboardBitmap {
+    let bitmap_data = bitmap_data.sync(activation.context.renderer);
+    let bitmap_data = bitmap_data.read();
+    let rgba = bitmap_data
+        .pixels()
+        .iter()
+        .flat_map(|color| {
+            let color = color.to_un_multiplied_alpha();
+            [color.red(), color.green(), color.blue(), color.alpha()]
+        })
+        .collect();
+    ClipboardBitmap {
+        width: bitmap_data.width(),
+        height: bitmap_data.height(),
+        rgba,
+    }
+}
+
+/// Implements `Clipboard.clearSystemData`
+pub fn clear_system_data<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(format) = format_arg(activation, args)? {
+        let mut contents = activation.context.ui.clipboard_contents();
+        contents.clear_format(format);
+        activation.context.ui.set_clipboard_contents(contents);
+    }
+    Ok(Value::Undefined)
+}
+
+/// Implements `Clipboard.clearSystem`
+pub fn clear_system<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation
+        .context
+        .ui
+        .set_clipboard_contents(ClipboardContent::default());
+    Ok(Value::Undefined)
+}
@@ -64,6 +64,9 @@ package flash.display {
             sourceBitmapData:BitmapData, sourceRect:Rectangle, destPoint:Point, operation:String, threshold:uint, color:uint = 0, mask:uint = 0xFFFFFFFF, copySource:Boolean = false
         ):uint;
         public native function compare(otherBitmapData:BitmapData):Object;
+        [API("680")]
+        public native function encode(rect:Rectangle, compressor:Object, byteArray:ByteArray = null):ByteArray;
+        public native function histogram(hRect:Rectangle = null):Vector.<Vector.<Number>>;
         public native function pixelDissolve(
             sourceBitmapData:BitmapData, sourceRect:Rectangle, destPoint:Point, randomSeed:int = 0, numPixels:int = 0,
             fillColor:uint = 0
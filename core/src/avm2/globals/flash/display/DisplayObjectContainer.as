@@ -44,9 +44,6 @@ package flash.display {
         public native function getObjectsUnderPoint(point:Point):Array;
         public native function areInaccessibleObjectsUnderPoint(point:Point):Boolean;
 
-        public function get textSnapshot():TextSnapshot {
-            stub_getter("flash.display.DisplayObjectContainer", "textSnapshot")
-            return new TextSnapshot();
-        }
+        public native function get textSnapshot():TextSnapshot;
     }
 }
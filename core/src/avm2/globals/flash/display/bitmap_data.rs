@@ -3,7 +3,8 @@
 use crate::avm2::activation::Activation;
 use crate::avm2::bytearray::ByteArrayStorage;
 use crate::avm2::error::{
-    argument_error, make_error_2004, make_error_2007, make_error_2008, range_error, Error2004Type,
+    argument_error, make_error_2004, make_error_2007, make_error_2008, range_error, security_error,
+    Error2004Type,
 };
 use crate::avm2::filters::FilterAvm2Ext;
 pub use crate::avm2::object::bitmap_data_allocator;
@@ -17,10 +18,12 @@ use crate::bitmap::bitmap_data::{
     BitmapData, BitmapDataWrapper, ChannelOptions, ThresholdOperation,
 };
 use crate::bitmap::bitmap_data::{BitmapDataDrawError, IBitmapDrawable};
+use crate::bitmap::encoder::{self, ImageEncoding};
 use crate::bitmap::{is_size_valid, operations};
 use crate::character::{Character, CompressedBitmap};
-use crate::display_object::TDisplayObject;
+use crate::display_object::{DisplayObject, TDisplayObject};
 use crate::ecma_conversions::round_to_even;
+use crate::policy_file::PolicyFiles;
 use crate::swf::BlendMode;
 use gc_arena::GcCell;
 use ruffle_render::filters::Filter;
@@ -33,7 +36,7 @@ use swf::{Rectangle, Twips};
 // and `y + height` as floating point operations before
 // `round_to_even`, which is needed to match Flash Player's
 // rounding behavior.
-fn get_rectangle_x_y_width_height<'gc>(
+pub(crate) fn get_rectangle_x_y_width_height<'gc>(
     activation: &mut Activation<'_, 'gc>,
     rectangle: Object<'gc>,
 ) -> Result<(i32, i32, i32, i32), Error<'gc>> {
@@ -885,6 +888,23 @@ pub fn hit_test<'gc>(
     Ok(false.into())
 }
 
+/// Throw a `SecurityError` if `source` contains content from another domain
+/// whose pixels may not be read.
+fn check_draw_access<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    source: DisplayObject<'gc>,
+) -> Result<(), Error<'gc>> {
+    if let Some(url) = PolicyFiles::find_unreadable_content(activation.context, source) {
+        let message = format!(
+            "Error #2122: Security sandbox violation: BitmapData.draw: {} cannot access {}. A policy file is required, but the checkPolicyFile flag was not set when this media was loaded.",
+            activation.context.swf.url(),
+            url
+        );
+        return Err(Error::AvmError(security_error(activation, &message, 2122)?));
+    }
+    Ok(())
+}
+
 /// Implements `BitmapData.draw`
 pub fn draw<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -931,6 +951,7 @@ pub fn draw<'gc>(
         let source = args.get_object(activation, 0, "source")?;
 
         let source = if let Some(source_object) = source.as_display_object() {
+            check_draw_access(activation, source_object)?;
             IBitmapDrawable::DisplayObject(source_object)
         } else if let Some(source_bitmap) = source.as_bitmap_data() {
             IBitmapDrawable::BitmapData(source_bitmap)
@@ -1010,6 +1031,7 @@ pub fn draw_with_quality<'gc>(
         let source = args.get_object(activation, 0, "source")?;
 
         let source = if let Some(source_object) = source.as_display_object() {
+            check_draw_access(activation, source_object)?;
             IBitmapDrawable::DisplayObject(source_object)
         } else if let Some(source_bitmap) = source.as_bitmap_data() {
             IBitmapDrawable::BitmapData(source_bitmap)
@@ -1594,3 +1616,124 @@ pub fn merge<'gc>(
 
     Ok(Value::Undefined)
 }
+
+/// Implements `BitmapData.encode`.
+pub fn encode<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(bitmap_data) = this.as_bitmap_data() else {
+        return Ok(Value::Undefined);
+    };
+    bitmap_data.check_valid(activation)?;
+
+    let rectangle = args.get_object(activation, 0, "rect")?;
+    let compressor = args.get_object(activation, 1, "compressor")?;
+    let byte_array = match args.try_get_object(activation, 2) {
+        Some(byte_array) => byte_array,
+        None => ByteArrayObject::from_storage(activation, ByteArrayStorage::new())?,
+    };
+
+    let classes = activation.avm2().classes();
+    let png_class = classes.pngencoderoptions.inner_class_definition();
+    let jpeg_class = classes.jpegencoderoptions.inner_class_definition();
+    let jpeg_xr_class = classes.jpegxrencoderoptions.inner_class_definition();
+
+    let encoding = if compressor.is_of_type(png_class) {
+        ImageEncoding::Png {
+            fast_compression: compressor
+                .get_public_property("fastCompression", activation)?
+                .coerce_to_boolean(),
+        }
+    } else if compressor.is_of_type(jpeg_class) {
+        ImageEncoding::Jpeg {
+            quality: compressor
+                .get_public_property("quality", activation)?
+                .coerce_to_u32(activation)?,
+        }
+    } else if compressor.is_of_type(jpeg_xr_class) {
+        avm2_stub_method!(
+            activation,
+            "flash.display.BitmapData",
+            "encode",
+            "with JPEGXREncoderOptions"
+        );
+        return Ok(byte_array.into());
+    } else {
+        return Err(make_error_2004(activation, Error2004Type::ArgumentError));
+    };
+
+    let (x, y, width, height) = get_rectangle_x_y_width_height(activation, rectangle)?;
+    let (width, height, pixels) = operations::get_pixels_as_rgba(
+        activation.context.renderer,
+        bitmap_data,
+        x,
+        y,
+        width,
+        height,
+    );
+    if width == 0 || height == 0 {
+        return Ok(byte_array.into());
+    }
+
+    match encoder::encode(width, height, &pixels, bitmap_data.transparency(), encoding) {
+        Ok(encoded) => {
+            let mut storage = byte_array.as_bytearray_mut().unwrap();
+            storage
+                .write_bytes(&encoded)
+                .map_err(|e| e.to_avm(activation))?;
+        }
+        Err(e) => {
+            tracing::error!("BitmapData.encode: {}", e);
+            return Err(make_error_2004(activation, Error2004Type::ArgumentError));
+        }
+    }
+
+    Ok(byte_array.into())
+}
+
+/// Implements `BitmapData.histogram`.
+pub fn histogram<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(bitmap_data) = this.as_bitmap_data() else {
+        return Ok(Value::Undefined);
+    };
+    bitmap_data.check_valid(activation)?;
+
+    let (x, y, width, height) = match args.try_get_object(activation, 0) {
+        Some(rectangle) => get_rectangle_x_y_width_height(activation, rectangle)?,
+        None => (
+            0,
+            0,
+            bitmap_data.width() as i32,
+            bitmap_data.height() as i32,
+        ),
+    };
+    let histogram = operations::histogram(
+        activation.context.renderer,
+        bitmap_data,
+        x,
+        y,
+        width,
+        height,
+    );
+
+    let number_class = activation.avm2().classes().number.inner_class_definition();
+    let mut channels: Vec<Value<'gc>> = Vec::with_capacity(histogram.len());
+    for counts in histogram {
+        let counts = counts.iter().map(|count| (*count as f64).into()).collect();
+        let storage = VectorStorage::from_values(counts, false, Some(number_class));
+        channels.push(VectorObject::from_vector(storage, activation)?.into());
+    }
+
+    let channel_class = channels
+        .first()
+        .and_then(|channel| channel.as_object())
+        .map(|channel| channel.instance_class());
+    let storage = VectorStorage::from_values(channels, false, channel_class);
+    Ok(VectorObject::from_vector(storage, activation)?.into())
+}
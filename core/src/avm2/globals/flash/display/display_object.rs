@@ -19,6 +19,7 @@ use crate::vminterface::Instantiator;
 use crate::{avm2_stub_getter, avm2_stub_setter};
 use ruffle_render::blend::ExtendedBlendMode;
 use ruffle_render::filters::Filter;
+use ruffle_render::matrix3d::Matrix3D;
 use std::str::FromStr;
 
 pub fn display_object_allocator<'gc>(
@@ -386,93 +387,129 @@ pub fn set_y<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `z`'s getter.
 pub fn get_z<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.display.DisplayObject", "z");
-    Ok(0.into())
+    if let Some(dobj) = this.as_display_object() {
+        return Ok(dobj.z().into());
+    }
+
+    Ok(Value::Undefined)
 }
 
+/// Implements `z`'s setter.
 pub fn set_z<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_setter!(activation, "flash.display.DisplayObject", "z");
+    if let Some(dobj) = this.as_display_object() {
+        let z = args.get_f64(activation, 0)?;
+        dobj.set_z(activation.context.gc_context, z);
+    }
+
     Ok(Value::Undefined)
 }
 
+/// Implements `rotationX`'s getter.
 pub fn get_rotation_x<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.display.DisplayObject", "rotationX");
-    Ok(0.into())
+    if let Some(dobj) = this.as_display_object() {
+        return Ok(f64::from(dobj.rotation_x()).into());
+    }
+
+    Ok(Value::Undefined)
 }
 
+/// Implements `rotationX`'s setter.
 pub fn set_rotation_x<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_setter!(activation, "flash.display.DisplayObject", "rotationX");
+    if let Some(dobj) = this.as_display_object() {
+        let new_rotation = args.get_f64(activation, 0)?;
+        dobj.set_rotation_x(activation.context.gc_context, Degrees::from(new_rotation));
+    }
+
     Ok(Value::Undefined)
 }
 
+/// Implements `rotationY`'s getter.
 pub fn get_rotation_y<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.display.DisplayObject", "rotationY");
-    Ok(0.into())
+    if let Some(dobj) = this.as_display_object() {
+        return Ok(f64::from(dobj.rotation_y()).into());
+    }
+
+    Ok(Value::Undefined)
 }
 
+/// Implements `rotationY`'s setter.
 pub fn set_rotation_y<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_setter!(activation, "flash.display.DisplayObject", "rotationY");
+    if let Some(dobj) = this.as_display_object() {
+        let new_rotation = args.get_f64(activation, 0)?;
+        dobj.set_rotation_y(activation.context.gc_context, Degrees::from(new_rotation));
+    }
+
     Ok(Value::Undefined)
 }
 
+/// Implements `rotationZ`'s getter, which is the same as `rotation`.
 pub fn get_rotation_z<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.display.DisplayObject", "rotationZ");
-    Ok(0.into())
+    get_rotation(activation, this, args)
 }
 
+/// Implements `rotationZ`'s setter, which is the same as `rotation`.
 pub fn set_rotation_z<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_setter!(activation, "flash.display.DisplayObject", "rotationZ");
-    Ok(Value::Undefined)
+    set_rotation(activation, this, args)
 }
 
+/// Implements `scaleZ`'s getter.
 pub fn get_scale_z<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.display.DisplayObject", "scaleZ");
-    Ok(1.into())
+    if let Some(dobj) = this.as_display_object() {
+        return Ok(dobj.scale_z().into());
+    }
+
+    Ok(Value::Undefined)
 }
 
+/// Implements `scaleZ`'s setter.
 pub fn set_scale_z<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_setter!(activation, "flash.display.DisplayObject", "scaleZ");
+    if let Some(dobj) = this.as_display_object() {
+        let new_scale = args.get_f64(activation, 0)?;
+        dobj.set_scale_z(activation.context.gc_context, new_scale);
+    }
+
     Ok(Value::Undefined)
 }
 
@@ -969,6 +1006,79 @@ pub fn global_to_local<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn local3d_to_global<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.as_display_object() {
+        let point = args.get_object(activation, 0, "point3d")?;
+        let x = point
+            .get_public_property("x", activation)?
+            .coerce_to_number(activation)?;
+        let y = point
+            .get_public_property("y", activation)?
+            .coerce_to_number(activation)?;
+        let z = point
+            .get_public_property("z", activation)?
+            .coerce_to_number(activation)?;
+
+        // A 2D object is projected as though it had an identity 3D transform.
+        let matrix3d = dobj.local_to_global_matrix3d().unwrap_or_else(|| {
+            dobj.perspective_matrix() * Matrix3D::from(dobj.local_to_global_matrix())
+        });
+        let twips = Twips::TWIPS_PER_PIXEL as f64;
+        let [global_x, global_y, _, w] =
+            matrix3d.transform_point([x * twips, y * twips, z * twips]);
+        return Ok(activation
+            .avm2()
+            .classes()
+            .point
+            .construct(
+                activation,
+                &[(global_x / w / twips).into(), (global_y / w / twips).into()],
+            )?
+            .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn global_to_local_3d<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.as_display_object() {
+        let point = args.get_object(activation, 0, "point")?;
+        let x = point
+            .get_public_property("x", activation)?
+            .coerce_to_number(activation)?;
+        let y = point
+            .get_public_property("y", activation)?
+            .coerce_to_number(activation)?;
+
+        // The result is the point on this object's Z = 0 plane under the given global point.
+        let global = Point::from_pixels(x, y);
+        let local = dobj.global_to_local(global).unwrap_or(global);
+        return Ok(activation
+            .avm2()
+            .classes()
+            .vector3d
+            .construct(
+                activation,
+                &[
+                    local.x.to_pixels().into(),
+                    local.y.to_pixels().into(),
+                    0.into(),
+                ],
+            )?
+            .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn get_bounds<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
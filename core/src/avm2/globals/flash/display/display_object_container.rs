@@ -8,7 +8,7 @@ use crate::avm2::error::{argument_error, make_error_2025, range_error};
 use crate::avm2::object::{Object, TObject};
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
-use crate::avm2::{ArrayObject, ArrayStorage, Error};
+use crate::avm2::{ArrayObject, ArrayStorage, Error, Multiname};
 use crate::avm2_stub_method;
 use crate::context::UpdateContext;
 use crate::display_object::HitTestOptions;
@@ -662,3 +662,19 @@ pub fn set_tab_children<'gc>(
 
     Ok(Value::Undefined)
 }
+
+/// Implements `DisplayObjectContainer.textSnapshot`'s getter
+pub fn get_text_snapshot<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let class = activation.avm2().classes().textsnapshot;
+    let snapshot = class.construct(activation, &[])?;
+    snapshot.set_property(
+        &Multiname::new(activation.avm2().flash_text_internal, "_container"),
+        this.into(),
+        activation,
+    )?;
+    Ok(snapshot.into())
+}
@@ -14,11 +14,13 @@ use crate::avm2::ClassObject;
 use crate::avm2::Multiname;
 use crate::avm2::{Error, Object};
 use crate::avm2_stub_method;
-use crate::backend::navigator::{NavigationMethod, Request};
+use crate::backend::navigator::{NavigationMethod, OwnedFuture, Request};
 use crate::display_object::LoaderDisplay;
 use crate::display_object::MovieClip;
+use crate::loader::Error as LoaderError;
 use crate::loader::LoadManager;
 use crate::loader::MovieLoaderVMData;
+use crate::policy_file::PolicyFiles;
 use crate::tag_utils::SwfMovie;
 use std::sync::Arc;
 
@@ -117,7 +119,7 @@ pub fn load<'gc>(
         activation.context.player.clone(),
         content.into(),
         request,
-        Some(url),
+        Some(url.clone()),
         MovieLoaderVMData::Avm2 {
             loader_info,
             context,
@@ -126,6 +128,25 @@ pub fn load<'gc>(
                 .expect("Missing caller domain in Loader.load"),
         },
     );
+
+    // With `checkPolicyFile`, the policy file of the content is loaded before
+    // the content, to decide whether its pixels may be read.
+    let check_policy_file = match context {
+        Some(context) => context
+            .get_public_property("checkPolicyFile", activation)?
+            .coerce_to_boolean(),
+        None => false,
+    };
+    let future: OwnedFuture<(), LoaderError> = match check_policy_file
+        .then(|| PolicyFiles::content_access_check(activation.context, &url))
+        .flatten()
+    {
+        Some(access_check) => Box::pin(async move {
+            access_check.await;
+            future.await
+        }),
+        None => future,
+    };
     activation.context.navigator.spawn_future(future);
 
     Ok(Value::Undefined)
@@ -0,0 +1,26 @@
+package flash.events {
+    [API("681")]
+    public class PermissionEvent extends Event {
+        public static const PERMISSION_STATUS:String = "permissionStatus";
+
+        private var _status:String;
+
+        public function PermissionEvent(type:String, bubbles:Boolean = false, cancelable:Boolean = false, status:String = "denied") {
+            super(type, bubbles, cancelable);
+            this._status = status;
+        }
+
+        // The status of the requested permission, one of the `PermissionStatus` constants.
+        public function get status():String {
+            return this._status;
+        }
+
+        override public function clone():Event {
+            return new PermissionEvent(this.type, this.bubbles, this.cancelable, this.status);
+        }
+
+        override public function toString():String {
+            return this.formatToString("PermissionEvent", "type", "bubbles", "cancelable", "eventPhase", "status");
+        }
+    }
+}
@@ -9,8 +9,11 @@ package flash.filesystem {
     public class File extends FileReference {
         private static var _applicationDirectory:File = null;
 
+        private var _nativePath:String;
+
         public function File(path:String = null) {
             stub_constructor("flash.filesystem.File");
+            this._nativePath = path;
         }
 
         public static function get applicationDirectory():File {
@@ -21,6 +24,14 @@ package flash.filesystem {
             return _applicationDirectory;
         }
 
+        public function get nativePath():String {
+            return this._nativePath;
+        }
+
+        public function set nativePath(value:String):void {
+            this._nativePath = value;
+        }
+
         public function resolvePath(path:String):File {
             stub_method("flash.filesystem.File", "resolvePath");
 
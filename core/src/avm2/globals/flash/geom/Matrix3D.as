@@ -1,7 +1,6 @@
 // Based on the MIT-licensed OpenFL code https://github.com/openfl/openfl/blob/develop/src/openfl/geom/Matrix3D.hx
 
 package flash.geom {
-	import __ruffle__.stub_method;
 
 	public class Matrix3D {
 
@@ -410,18 +409,77 @@ package flash.geom {
 		}
 
 		public function pointAt(pos:Vector3D, at:Vector3D = null, up:Vector3D = null):void {
-			stub_method("flash.geom.Matrix3D", "pointAt");
+			if (at == null) {
+				at = new Vector3D(0, 0, -1);
+			}
+			if (up == null) {
+				up = new Vector3D(0, -1, 0);
+			}
+
+			// The direction we want the local 'at' axis to point in
+			var forward:Vector3D = pos.subtract(this.position);
+			if (forward.normalize() == 0) {
+				return;
+			}
+
+			// Build an orthonormal frame for the local axes, and one for the target orientation.
+			// Both frames share the same 'up' reference, so the object doesn't roll.
+			var localForward:Vector3D = at.clone();
+			if (localForward.normalize() == 0) {
+				return;
+			}
+			var localUp:Vector3D = orthogonalUp(up, localForward);
+			var localSide:Vector3D = localForward.crossProduct(localUp);
+			var worldUp:Vector3D = orthogonalUp(up, forward);
+			var worldSide:Vector3D = forward.crossProduct(worldUp);
+
+			// Keep the existing scale
+			var scaleX:Number = new Vector3D(_rawData[0], _rawData[1], _rawData[2]).length;
+			var scaleY:Number = new Vector3D(_rawData[4], _rawData[5], _rawData[6]).length;
+			var scaleZ:Number = new Vector3D(_rawData[8], _rawData[9], _rawData[10]).length;
+
+			// The rotation maps each local axis onto its counterpart in the target frame
+			var localAxes:Array = [localForward, localSide, localUp];
+			var worldAxes:Array = [forward, worldSide, worldUp];
+			var scale:Array = [scaleX, scaleY, scaleZ];
+			var components:Array = ["x", "y", "z"];
+			for (var column = 0; column < 3; column++) {
+				var component:String = components[column];
+				for (var row = 0; row < 3; row++) {
+					var rowComponent:String = components[row];
+					var value:Number = 0;
+					for (var axis = 0; axis < 3; axis++) {
+						value += worldAxes[axis][rowComponent] * localAxes[axis][component];
+					}
+					_rawData[column * 4 + row] = value * scale[column];
+				}
+			}
+			_rawData[3] = 0;
+			_rawData[7] = 0;
+			_rawData[11] = 0;
+			_rawData[15] = 1;
+		}
+
+		// Returns the component of 'up' perpendicular to the (normalized) 'forward' direction,
+		// falling back to an arbitrary perpendicular axis if they're parallel.
+		private static function orthogonalUp(up:Vector3D, forward:Vector3D):Vector3D {
+			var projected:Vector3D = forward.clone();
+			projected.scaleBy(up.dotProduct(forward));
+			var result:Vector3D = up.subtract(projected);
+			if (result.normalize() == 0) {
+				result = Math.abs(forward.x) < 0.9 ? new Vector3D(1, 0, 0) : new Vector3D(0, 1, 0);
+				projected = forward.clone();
+				projected.scaleBy(result.dotProduct(forward));
+				result = result.subtract(projected);
+				result.normalize();
+			}
+			return result;
 		}
 
 		// Based on OpenFL: https://github.com/openfl/openfl/blob/971a4c9e43b5472fd84d73920a2b7c1b3d8d9257/src/openfl/geom/Matrix3D.hx#L1437
 		public function recompose(components:Vector.<Vector3D>, orientationStyle:String = "eulerAngles"):Boolean {
 			checkOrientation(orientationStyle);
 
-			if (orientationStyle == Orientation3D.QUATERNION) {
-				// Flash throws exceptions from 'recompose' certain values of 'components',
-				// which we need to reproduce. See the 'matrix3d_compose' test
-				stub_method("flash.geom.Matrix3D", "recompose", "Orientation3D.QUATERNION");
-			}
 			// RUFFLE - unlike in OpenFL, we continue on even if some of the 'scale' components are 0
 			if (components.length < 3) {
 				return false;
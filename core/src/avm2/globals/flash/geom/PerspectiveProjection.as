@@ -1,44 +1,69 @@
 package flash.geom {
-    import __ruffle__.stub_constructor;
-    import __ruffle__.stub_getter;
-    import __ruffle__.stub_method;
-    import __ruffle__.stub_setter;
     import flash.geom.Matrix3D;
     import flash.geom.Point;
 
     public class PerspectiveProjection {
-        // Getters are stubbed with what seem to be Flash's default values
+        private var _fieldOfView:Number = 55;
+        private var _projectionCenter:Point;
+
+        // The focal length is derived from the field of view and the width of the stage.
+        private var _stageWidth:Number;
+
+        // The transform this projection was read from, which is kept in sync with it.
+        internal var _owner:Transform;
+
         public function PerspectiveProjection() {
-            stub_constructor("flash.geom.PerspectiveProjection");
+            this._stageWidth = this.stageWidth;
+            this._projectionCenter = new Point(this._stageWidth / 2, this.stageHeight / 2);
         }
 
+        private native function get stageWidth():Number;
+        private native function get stageHeight():Number;
+
         public function get fieldOfView():Number {
-            stub_getter("flash.geom.PerspectiveProjection", "fieldOfView");
-            return 55;
+            return this._fieldOfView;
         }
         public function set fieldOfView(value:Number) {
-            stub_setter("flash.geom.PerspectiveProjection", "fieldOfView");
+            if (!(value > 0 && value < 180)) {
+                throw new ArgumentError("Error #2182: Invalid fieldOfView value.  The value must be greater than 0 and less than 180.", 2182);
+            }
+            this._fieldOfView = value;
+            this.update();
         }
 
         public function get focalLength():Number {
-            stub_getter("flash.geom.PerspectiveProjection", "focalLength");
-            return 480.25;
+            return (this._stageWidth / 2) / Math.tan(this._fieldOfView * Math.PI / 360);
         }
         public function set focalLength(value:Number) {
-            stub_setter("flash.geom.PerspectiveProjection", "focalLength");
+            if (!(value > 0)) {
+                throw new ArgumentError("Error #2186: Invalid focalLength " + value + ".", 2186);
+            }
+            this._fieldOfView = Math.atan((this._stageWidth / 2) / value) * 360 / Math.PI;
+            this.update();
         }
 
         public function get projectionCenter():Point {
-            stub_getter("flash.geom.PerspectiveProjection", "projectionCenter");
-            return new Point(250, 250);
+            return this._projectionCenter.clone();
         }
         public function set projectionCenter(value:Point) {
-            stub_setter("flash.geom.PerspectiveProjection", "projectionCenter");
+            this._projectionCenter = value.clone();
+            this.update();
+        }
+
+        private function update():void {
+            if (this._owner != null) {
+                this._owner.perspectiveProjection = this;
+            }
         }
 
         public function toMatrix3D():Matrix3D {
-            stub_method("flash.geom.PerspectiveProjection", "toMatrix3D");
-            return new Matrix3D();
+            var focalLength:Number = this.focalLength;
+            return new Matrix3D(Vector.<Number>([
+                focalLength, 0, 0, 0,
+                0, focalLength, 0, 0,
+                0, 0, 1, 1,
+                0, 0, 0, 0
+            ]));
         }
     }
-}
\ No newline at end of file
+}
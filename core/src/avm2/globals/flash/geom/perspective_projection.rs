@@ -0,0 +1,21 @@
+//! `flash.geom.PerspectiveProjection` native methods
+
+use crate::avm2::{Activation, Error, Object, Value};
+
+/// Implements `stageWidth`'s getter.
+pub fn get_stage_width<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation.context.stage.stage_size().0.into())
+}
+
+/// Implements `stageHeight`'s getter.
+pub fn get_stage_height<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation.context.stage.stage_size().1.into())
+}
@@ -1,9 +1,12 @@
+use crate::avm2::object::VectorObject;
 use crate::avm2::parameters::ParametersExt;
+use crate::avm2::vector::VectorStorage;
 use crate::avm2::Multiname;
 use crate::avm2::{Activation, Error, Object, TObject, Value};
 use crate::avm2_stub_getter;
-use crate::display_object::TDisplayObject;
-use crate::prelude::{DisplayObject, Matrix, Twips};
+use crate::display_object::{PerspectiveProjection, TDisplayObject};
+use crate::prelude::{DisplayObject, Matrix, Point, Twips};
+use ruffle_render::matrix3d::Matrix3D;
 use ruffle_render::quality::StageQuality;
 use swf::{ColorTransform, Fixed8, Rectangle};
 
@@ -64,7 +67,12 @@ pub fn get_matrix<'gc>(
     this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let matrix = *get_display_object(this, activation)?.base().matrix();
+    let dobj = get_display_object(this, activation)?;
+    if dobj.base().matrix3d().is_some() {
+        // Only one of `matrix` and `matrix3D` is ever set.
+        return Ok(Value::Null);
+    }
+    let matrix = *dobj.base().matrix();
     matrix_to_object(matrix, activation)
 }
 
@@ -123,6 +131,127 @@ pub fn get_concatenated_matrix<'gc>(
     }
 }
 
+pub fn get_matrix_3d<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let matrix3d = get_display_object(this, activation)?
+        .base()
+        .matrix3d()
+        .copied();
+    match matrix3d {
+        Some(matrix3d) => matrix3d_to_object(matrix3d, activation),
+        None => Ok(Value::Null),
+    }
+}
+
+pub fn set_matrix_3d<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let matrix3d = match args.try_get_object(activation, 0) {
+        Some(object) => Some(object_to_matrix3d(object, activation)?),
+        None => None,
+    };
+    let dobj = get_display_object(this, activation)?;
+    dobj.set_matrix3d(activation.context.gc_context, matrix3d);
+    if let Some(parent) = dobj.parent() {
+        parent.invalidate_cached_bitmap(activation.context.gc_context);
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn get_perspective_projection<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let dobj = get_display_object(this, activation)?;
+    let projection = dobj.base().perspective_projection();
+    let class = activation.avm2().classes().perspectiveprojection;
+    let object = match projection {
+        Some(projection) => {
+            let object = class.construct(activation, &[])?;
+            object.set_public_property(
+                "fieldOfView",
+                projection.field_of_view.into(),
+                activation,
+            )?;
+            let center = activation.avm2().classes().point.construct(
+                activation,
+                &[
+                    projection.center.x.to_pixels().into(),
+                    projection.center.y.to_pixels().into(),
+                ],
+            )?;
+            object.set_public_property("projectionCenter", center.into(), activation)?;
+            object
+        }
+        // The root always has a projection, which defaults to the center of the stage.
+        None if dobj.is_root() || dobj.as_stage().is_some() => class.construct(activation, &[])?,
+        None => return Ok(Value::Null),
+    };
+
+    // Changes to the returned projection are applied back to this transform.
+    object.set_property(
+        &Multiname::new(activation.avm2().flash_geom_internal, "_owner"),
+        this.into(),
+        activation,
+    )?;
+    Ok(object.into())
+}
+
+pub fn set_perspective_projection<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let projection = match args.try_get_object(activation, 0) {
+        Some(object) => {
+            let field_of_view = object
+                .get_public_property("fieldOfView", activation)?
+                .coerce_to_number(activation)?;
+            let center = object
+                .get_public_property("projectionCenter", activation)?
+                .coerce_to_object(activation)?;
+            let x = center
+                .get_public_property("x", activation)?
+                .coerce_to_number(activation)?;
+            let y = center
+                .get_public_property("y", activation)?
+                .coerce_to_number(activation)?;
+            Some(PerspectiveProjection {
+                field_of_view,
+                center: Point::new(Twips::from_pixels(x), Twips::from_pixels(y)),
+            })
+        }
+        None => None,
+    };
+    let dobj = get_display_object(this, activation)?;
+    dobj.base_mut(activation.context.gc_context)
+        .set_perspective_projection(projection);
+    dobj.invalidate_cached_bitmap(activation.context.gc_context);
+    Ok(Value::Undefined)
+}
+
+pub fn get_relative_matrix_3d<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let dobj = get_display_object(this, activation)?;
+    let relative_to = args
+        .get_object(activation, 0, "relativeTo")?
+        .as_display_object()
+        .expect("relativeTo is not a DisplayObject");
+    let Some(relative_inverse) = relative_to.concatenated_matrix3d().inverse() else {
+        return Ok(Value::Null);
+    };
+    matrix3d_to_object(relative_inverse * dobj.concatenated_matrix3d(), activation)
+}
+
 pub fn get_concatenated_color_transform<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
@@ -247,6 +376,52 @@ pub fn object_to_matrix<'gc>(
     Ok(Matrix { a, b, c, d, tx, ty })
 }
 
+/// Converts a 3D matrix in twips to a `Matrix3D` object in pixels.
+pub fn matrix3d_to_object<'gc>(
+    matrix3d: Matrix3D,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let matrix3d = Matrix3D::TWIPS_TO_PIXELS * matrix3d * Matrix3D::PIXELS_TO_TWIPS;
+    let number = activation.avm2().classes().number.inner_class_definition();
+    let storage = VectorStorage::from_values(
+        matrix3d
+            .raw_data
+            .iter()
+            .map(|value| (*value).into())
+            .collect(),
+        false,
+        Some(number),
+    );
+    let raw_data = VectorObject::from_vector(storage, activation)?;
+    let object = activation
+        .avm2()
+        .classes()
+        .matrix3d
+        .construct(activation, &[raw_data.into()])?;
+    Ok(object.into())
+}
+
+/// Converts a `Matrix3D` object in pixels to a 3D matrix in twips.
+pub fn object_to_matrix3d<'gc>(
+    object: Object<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<Matrix3D, Error<'gc>> {
+    let raw_data = object
+        .get_public_property("rawData", activation)?
+        .coerce_to_object(activation)?;
+    let values: Vec<_> = raw_data
+        .as_vector_storage()
+        .expect("rawData is not a Vector")
+        .iter()
+        .collect();
+
+    let mut matrix3d = Matrix3D::IDENTITY;
+    for (dst, value) in matrix3d.raw_data.iter_mut().zip(values) {
+        *dst = value.coerce_to_number(activation)?;
+    }
+    Ok(Matrix3D::PIXELS_TO_TWIPS * matrix3d * Matrix3D::TWIPS_TO_PIXELS)
+}
+
 pub fn get_pixel_bounds<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
@@ -0,0 +1,214 @@
+//! `flash.globalization` namespace
+
+use crate::avm2::activation::Activation;
+use crate::avm2::globalization::{
+    resolve_locale, LocaleData, NumberFormat, OperationStatus, LOCALES,
+};
+use crate::avm2::object::{Object, TObject, VectorObject};
+use crate::avm2::value::Value;
+use crate::avm2::vector::VectorStorage;
+use crate::avm2::{Error, Multiname};
+use crate::string::AvmString;
+
+pub mod collator;
+pub mod currency_formatter;
+pub mod date_time_formatter;
+pub mod locale_id;
+pub mod number_formatter;
+pub mod string_tools;
+
+fn get_internal<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    name: &'static str,
+) -> Result<Value<'gc>, Error<'gc>> {
+    this.get_property(
+        &Multiname::new(activation.avm2().flash_globalization_internal, name),
+        activation,
+    )
+}
+
+fn set_internal<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    name: &'static str,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    this.set_property(
+        &Multiname::new(activation.avm2().flash_globalization_internal, name),
+        value,
+        activation,
+    )
+}
+
+/// Set the `lastOperationStatus` of a `flash.globalization` object.
+fn set_status<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    status: OperationStatus,
+) -> Result<(), Error<'gc>> {
+    set_internal(
+        activation,
+        this,
+        "_lastOperationStatus",
+        status.as_str().into(),
+    )
+}
+
+/// The locale ID of the user interface, which `LocaleID.DEFAULT` refers to.
+fn user_locale(activation: &mut Activation<'_, '_>) -> String {
+    activation.context.ui.language().to_string()
+}
+
+/// Resolve the locale requested by a `flash.globalization` object, and set
+/// its `actualLocaleIDName` and `lastOperationStatus` accordingly.
+fn init_locale<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    requested: AvmString<'gc>,
+) -> Result<&'static LocaleData, Error<'gc>> {
+    let user_locale = user_locale(activation);
+    let (locale, status) = resolve_locale(&requested.to_utf8_lossy(), &user_locale);
+    set_internal(activation, this, "_actualLocaleIDName", locale.name.into())?;
+    set_status(activation, this, status)?;
+    Ok(locale)
+}
+
+/// The locale data of a `flash.globalization` object, from its `actualLocaleIDName`.
+fn locale_data<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+) -> Result<&'static LocaleData, Error<'gc>> {
+    let name = this
+        .get_public_property("actualLocaleIDName", activation)?
+        .coerce_to_string(activation)?;
+    Ok(LOCALES
+        .iter()
+        .find(|locale| name.to_utf8_lossy() == locale.name)
+        .unwrap_or(&LOCALES[0]))
+}
+
+fn get_string<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    name: &'static str,
+) -> Result<String, Error<'gc>> {
+    Ok(this
+        .get_public_property(name, activation)?
+        .coerce_to_string(activation)?
+        .to_utf8_lossy()
+        .into_owned())
+}
+
+/// Create a `Vector.<String>` from `strings`.
+fn string_vector<'gc, S: AsRef<str>>(
+    activation: &mut Activation<'_, 'gc>,
+    strings: impl IntoIterator<Item = S>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let strings = strings
+        .into_iter()
+        .map(|string| AvmString::new_utf8(activation.gc(), string.as_ref()).into())
+        .collect();
+    let storage = VectorStorage::from_values(
+        strings,
+        false,
+        Some(activation.avm2().classes().string.inner_class_definition()),
+    );
+    Ok(VectorObject::from_vector(storage, activation)?.into())
+}
+
+/// Implements the `getAvailableLocaleIDNames` methods of all `flash.globalization` classes
+fn available_locale_id_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    string_vector(activation, LOCALES.iter().map(|locale| locale.name))
+}
+
+/// The formatting options shared by `NumberFormatter` and `CurrencyFormatter`.
+struct FormatOptions {
+    decimal_separator: String,
+    grouping_separator: String,
+    grouping_pattern: String,
+    negative_symbol: String,
+    fractional_digits: usize,
+    use_grouping: bool,
+    leading_zero: bool,
+    trailing_zeros: bool,
+    digits_type: u32,
+}
+
+impl FormatOptions {
+    fn read<'gc>(
+        activation: &mut Activation<'_, 'gc>,
+        this: Object<'gc>,
+    ) -> Result<Self, Error<'gc>> {
+        Ok(Self {
+            decimal_separator: get_string(activation, this, "decimalSeparator")?,
+            grouping_separator: get_string(activation, this, "groupingSeparator")?,
+            grouping_pattern: get_string(activation, this, "groupingPattern")?,
+            negative_symbol: get_string(activation, this, "negativeSymbol")?,
+            fractional_digits: this
+                .get_public_property("fractionalDigits", activation)?
+                .coerce_to_i32(activation)?
+                .max(0) as usize,
+            use_grouping: this
+                .get_public_property("useGrouping", activation)?
+                .coerce_to_boolean(),
+            leading_zero: this
+                .get_public_property("leadingZero", activation)?
+                .coerce_to_boolean(),
+            trailing_zeros: this
+                .get_public_property("trailingZeros", activation)?
+                .coerce_to_boolean(),
+            digits_type: this
+                .get_public_property("digitsType", activation)?
+                .coerce_to_u32(activation)?,
+        })
+    }
+
+    fn number_format(&self) -> NumberFormat<'_> {
+        NumberFormat {
+            decimal_separator: &self.decimal_separator,
+            grouping_separator: &self.grouping_separator,
+            grouping_pattern: &self.grouping_pattern,
+            fractional_digits: self.fractional_digits,
+            use_grouping: self.use_grouping,
+            leading_zero: self.leading_zero,
+            trailing_zeros: self.trailing_zeros,
+            digits_type: self.digits_type,
+        }
+    }
+}
+
+/// Set the separators and symbols shared by `NumberFormatter` and
+/// `CurrencyFormatter` to the conventions of `locale`.
+fn init_format<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    locale: &'static LocaleData,
+) -> Result<(), Error<'gc>> {
+    set_internal(
+        activation,
+        this,
+        "_decimalSeparator",
+        AvmString::new_utf8(activation.gc(), locale.decimal_separator).into(),
+    )?;
+    set_internal(
+        activation,
+        this,
+        "_groupingSeparator",
+        AvmString::new_utf8(activation.gc(), locale.grouping_separator).into(),
+    )?;
+    set_internal(
+        activation,
+        this,
+        "_groupingPattern",
+        locale.grouping_pattern.into(),
+    )?;
+    set_internal(
+        activation,
+        this,
+        "_negativeSymbol",
+        AvmString::new_utf8(activation.gc(), locale.negative_symbol).into(),
+    )
+}
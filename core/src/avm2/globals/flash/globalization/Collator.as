@@ -0,0 +1,100 @@
+package flash.globalization {
+    import flash.globalization.CollatorMode;
+    import flash.globalization.LastOperationStatus;
+
+    public final class Collator {
+        internal var _actualLocaleIDName:String;
+        internal var _lastOperationStatus:String = LastOperationStatus.NO_ERROR;
+
+        private var _localeIDName:String;
+        private var _ignoreCase:Boolean = false;
+        private var _ignoreCharacterWidth:Boolean = false;
+        private var _ignoreDiacritics:Boolean = false;
+        private var _ignoreKanaType:Boolean = false;
+        private var _ignoreSymbols:Boolean = false;
+        private var _numericComparison:Boolean = false;
+
+        public function Collator(requestedLocaleIDName:String, initialMode:String = "sorting") {
+            if (requestedLocaleIDName == null) {
+                throw new TypeError("Error #2007: Parameter requestedLocaleIDName must be non-null.", 2007);
+            }
+            if (initialMode == null) {
+                throw new TypeError("Error #2007: Parameter initialMode must be non-null.", 2007);
+            }
+            if (initialMode != CollatorMode.SORTING && initialMode != CollatorMode.MATCHING) {
+                throw new ArgumentError("Error #2008: Parameter initialMode must be one of the accepted values.", 2008);
+            }
+            this._localeIDName = requestedLocaleIDName;
+            this.init(requestedLocaleIDName);
+
+            if (initialMode == CollatorMode.MATCHING) {
+                this._ignoreCase = true;
+                this._ignoreCharacterWidth = true;
+                this._ignoreDiacritics = true;
+                this._ignoreKanaType = true;
+            }
+        }
+
+        private native function init(requestedLocaleIDName:String):void;
+
+        public function get actualLocaleIDName():String {
+            return this._actualLocaleIDName;
+        }
+
+        public function get ignoreCase():Boolean {
+            return this._ignoreCase;
+        }
+        public function set ignoreCase(value:Boolean):void {
+            this._ignoreCase = value;
+        }
+
+        public function get ignoreCharacterWidth():Boolean {
+            return this._ignoreCharacterWidth;
+        }
+        public function set ignoreCharacterWidth(value:Boolean):void {
+            this._ignoreCharacterWidth = value;
+        }
+
+        public function get ignoreDiacritics():Boolean {
+            return this._ignoreDiacritics;
+        }
+        public function set ignoreDiacritics(value:Boolean):void {
+            this._ignoreDiacritics = value;
+        }
+
+        public function get ignoreKanaType():Boolean {
+            return this._ignoreKanaType;
+        }
+        public function set ignoreKanaType(value:Boolean):void {
+            this._ignoreKanaType = value;
+        }
+
+        public function get ignoreSymbols():Boolean {
+            return this._ignoreSymbols;
+        }
+        public function set ignoreSymbols(value:Boolean):void {
+            this._ignoreSymbols = value;
+        }
+
+        public function get lastOperationStatus():String {
+            return this._lastOperationStatus;
+        }
+
+        public function get numericComparison():Boolean {
+            return this._numericComparison;
+        }
+        public function set numericComparison(value:Boolean):void {
+            this._numericComparison = value;
+        }
+
+        public function get requestedLocaleIDName():String {
+            return this._localeIDName;
+        }
+
+        public native function compare(string1:String, string2:String):int;
+
+        public native function equals(string1:String, string2:String):Boolean;
+
+        public static native function getAvailableLocaleIDNames():Vector.<String>;
+    }
+}
@@ -1,61 +1,41 @@
 package flash.globalization {
-    import __ruffle__.stub_constructor;
-    import __ruffle__.stub_getter;
-    import __ruffle__.stub_method;
     import flash.globalization.LastOperationStatus;
 
     public final class LocaleID {
         public static const DEFAULT:String = "i-default";
 
-        private var _name:String;
+        internal var _lastOperationStatus:String = LastOperationStatus.NO_ERROR;
+        internal var _name:String;
 
         public function LocaleID(name:String) {
-            stub_constructor("flash.globalization.LocaleID");
-            this._name = name;
+            if (name == null) {
+                throw new TypeError("Error #2007: Parameter name must be non-null.", 2007);
+            }
+            this.init(name);
         }
 
+        private native function init(name:String):void;
+
         public function get lastOperationStatus():String {
-            stub_getter("flash.globalization.LocaleID", "lastOperationStatus");
-            return LastOperationStatus.NO_ERROR;
+            return this._lastOperationStatus;
         }
 
         public function get name():String {
             return this._name;
         }
 
-        public static function determinePreferredLocales(want:Vector.<String>, have:Vector.<String>, keyword:String = "userinterface"):Vector.<String> {
-            stub_method("flash.globalization.LocaleID", "determinePreferredLocales");
-            return null;
-        }
+        public static native function determinePreferredLocales(want:Vector.<String>, have:Vector.<String>, keyword:String = "userinterface"):Vector.<String>;
 
-        public function getKeysAndValues():Object {
-            stub_method("flash.globalization.LocaleID", "getKeysAndValues");
-            return new Object();
-        }
+        public native function getKeysAndValues():Object;
 
-        public function getLanguage():String {
-            stub_method("flash.globalization.LocaleID", "getLanguage");
-            return this._name;
-        }
+        public native function getLanguage():String;
 
-        public function getRegion():String {
-            stub_method("flash.globalization.LocaleID", "getRegion");
-            return "";
-        }
+        public native function getRegion():String;
 
-        public function getScript():String {
-            stub_method("flash.globalization.LocaleID", "getScript");
-            return "";
-        }
+        public native function getScript():String;
 
-        public function getVariant():String {
-            stub_method("flash.globalization.LocaleID", "getVariant");
-            return "";
-        }
+        public native function getVariant():String;
 
-        public function isRightToLeft():Boolean {
-            stub_method("flash.globalization.LocaleID", "isRightToLeft");
-            return false;
-        }
+        public native function isRightToLeft():Boolean;
     }
 }
@@ -1,32 +1,36 @@
 package flash.globalization {
-    import __ruffle__.stub_constructor;
-    import __ruffle__.stub_getter;
-    import __ruffle__.stub_method;
     import flash.globalization.LastOperationStatus;
     import flash.globalization.NationalDigitsType;
     import flash.globalization.NumberParseResult;
 
     public final class NumberFormatter {
-        private var _decimalSeparator = ".";
-        private var _digitsType = NationalDigitsType.EUROPEAN;
-        private var _fractionalDigits = 2;
-        private var _groupingPattern = "3;*";
-        private var _groupingSeparator = ",";
-        private var _leadingZero = true;
+        internal var _actualLocaleIDName:String;
+        internal var _lastOperationStatus:String = LastOperationStatus.NO_ERROR;
+
+        internal var _decimalSeparator:String = ".";
+        private var _digitsType:uint = NationalDigitsType.EUROPEAN;
+        private var _fractionalDigits:int = 2;
+        internal var _groupingPattern:String = "3;*";
+        internal var _groupingSeparator:String = ",";
+        private var _leadingZero:Boolean = true;
         private var _localeIDName:String;
-        private var _negativeNumberFormat = 1;
-        private var _negativeSymbol = "-";
-        private var _trailingZeros = true;
-        private var _useGrouping = true;
+        internal var _negativeNumberFormat:uint = 1;
+        internal var _negativeSymbol:String = "-";
+        private var _trailingZeros:Boolean = true;
+        private var _useGrouping:Boolean = true;
 
         public function NumberFormatter(requestedLocaleIDName:String) {
-            stub_constructor("flash.globalization.NumberFormatter");
+            if (requestedLocaleIDName == null) {
+                throw new TypeError("Error #2007: Parameter requestedLocaleIDName must be non-null.", 2007);
+            }
             this._localeIDName = requestedLocaleIDName;
+            this.init(requestedLocaleIDName);
         }
 
+        private native function init(requestedLocaleIDName:String):void;
+
         public function get actualLocaleIDName():String {
-            stub_getter("flash.globalization.NumberFormatter", "actualLocaleIDName");
-            return this._localeIDName;
+            return this._actualLocaleIDName;
         }
 
         public function get decimalSeparator():String {
@@ -65,8 +69,7 @@ package flash.globalization {
         }
 
         public function get lastOperationStatus():String {
-            stub_getter("flash.globalization.NumberFormatter", "lastOperationStatus");
-            return LastOperationStatus.NO_ERROR;
+            return this._lastOperationStatus;
         }
 
         public function get leadingZero():Boolean {
@@ -110,34 +113,16 @@ package flash.globalization {
             this._useGrouping = value;
         }
 
-        public function formatInt(value:int):String {
-            stub_method("flash.globalization.NumberFormatter", "formatInt");
-            return value.toString();
-        }
+        public native function formatInt(value:int):String;
 
-        public function formatNumber(value:Number):String {
-            stub_method("flash.globalization.NumberFormatter", "formatNumber");
-            return value.toString();
-        }
+        public native function formatNumber(value:Number):String;
 
-        public function formatUint(value:uint):String {
-            stub_method("flash.globalization.NumberFormatter", "formatUint");
-            return value.toString();
-        }
+        public native function formatUint(value:uint):String;
 
-        public static function getAvailableLocaleIDNames():Vector.<String> {
-            stub_method("flash.globalization.NumberFormatter", "getAvailableLocaleIDNames");
-            return new <String>["en-US"];
-        }
+        public static native function getAvailableLocaleIDNames():Vector.<String>;
 
-        public function parse(parseString:String):NumberParseResult {
-            stub_method("flash.globalization.NumberFormatter", "parse");
-            return new NumberParseResult();
-        }
+        public native function parse(parseString:String):NumberParseResult;
 
-        public function parseNumber(parseString:String):Number {
-            stub_method("flash.globalization.NumberFormatter", "parseNumber");
-            return Number(parseString);
-        }
+        public native function parseNumber(parseString:String):Number;
     }
 }
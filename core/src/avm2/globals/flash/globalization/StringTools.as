@@ -0,0 +1,38 @@
+package flash.globalization {
+    import flash.globalization.LastOperationStatus;
+
+    public final class StringTools {
+        internal var _actualLocaleIDName:String;
+        internal var _lastOperationStatus:String = LastOperationStatus.NO_ERROR;
+
+        private var _localeIDName:String;
+
+        public function StringTools(requestedLocaleIDName:String) {
+            if (requestedLocaleIDName == null) {
+                throw new TypeError("Error #2007: Parameter requestedLocaleIDName must be non-null.", 2007);
+            }
+            this._localeIDName = requestedLocaleIDName;
+            this.init(requestedLocaleIDName);
+        }
+
+        private native function init(requestedLocaleIDName:String):void;
+
+        public function get actualLocaleIDName():String {
+            return this._actualLocaleIDName;
+        }
+
+        public function get lastOperationStatus():String {
+            return this._lastOperationStatus;
+        }
+
+        public function get requestedLocaleIDName():String {
+            return this._localeIDName;
+        }
+
+        public static native function getAvailableLocaleIDNames():Vector.<String>;
+
+        public native function toLowerCase(s:String):String;
+
+        public native function toUpperCase(s:String):String;
+    }
+}
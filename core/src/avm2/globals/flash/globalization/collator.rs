@@ -0,0 +1,88 @@
+//! `flash.globalization.Collator` native methods
+
+use super::{available_locale_id_names, init_locale, set_status};
+use crate::avm2::activation::Activation;
+use crate::avm2::globalization::{collate, CollatorOptions, OperationStatus};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use std::cmp::Ordering;
+
+/// Implements `Collator`'s `init` method, which is called from the constructor
+pub fn init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let requested = args.get_string(activation, 0)?;
+    init_locale(activation, this, requested)?;
+    Ok(Value::Undefined)
+}
+
+fn get_bool<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    name: &'static str,
+) -> Result<bool, Error<'gc>> {
+    Ok(this
+        .get_public_property(name, activation)?
+        .coerce_to_boolean())
+}
+
+fn compare_args<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Ordering, Error<'gc>> {
+    let string1 = args.get_string_non_null(activation, 0, "string1")?;
+    let string2 = args.get_string_non_null(activation, 1, "string2")?;
+
+    let options = CollatorOptions {
+        ignore_case: get_bool(activation, this, "ignoreCase")?,
+        ignore_character_width: get_bool(activation, this, "ignoreCharacterWidth")?,
+        ignore_diacritics: get_bool(activation, this, "ignoreDiacritics")?,
+        ignore_kana_type: get_bool(activation, this, "ignoreKanaType")?,
+        ignore_symbols: get_bool(activation, this, "ignoreSymbols")?,
+        numeric_comparison: get_bool(activation, this, "numericComparison")?,
+    };
+
+    set_status(activation, this, OperationStatus::NoError)?;
+    Ok(collate(
+        &string1.to_utf8_lossy(),
+        &string2.to_utf8_lossy(),
+        options,
+    ))
+}
+
+/// Implements `Collator.compare`
+pub fn compare<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let result = match compare_args(activation, this, args)? {
+        Ordering::Less => -1,
+        Ordering::Equal => 0,
+        Ordering::Greater => 1,
+    };
+    Ok(result.into())
+}
+
+/// Implements `Collator.equals`
+pub fn equals<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok((compare_args(activation, this, args)? == Ordering::Equal).into())
+}
+
+/// Implements `Collator.getAvailableLocaleIDNames`
+pub fn get_available_locale_id_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    available_locale_id_names(activation)
+}
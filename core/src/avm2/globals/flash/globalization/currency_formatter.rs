@@ -0,0 +1,131 @@
+//! `flash.globalization.CurrencyFormatter` native methods
+
+use super::{
+    available_locale_id_names, get_string, init_format, init_locale, set_internal, set_status,
+    FormatOptions,
+};
+use crate::avm2::activation::Activation;
+use crate::avm2::globalization::{apply_currency_format, parse_currency, OperationStatus};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::string::AvmString;
+
+/// Implements `CurrencyFormatter`'s `init` method, which is called from the constructor
+pub fn init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let requested = args.get_string(activation, 0)?;
+    let locale = init_locale(activation, this, requested)?;
+    init_format(activation, this, locale)?;
+    set_internal(
+        activation,
+        this,
+        "_currencyISOCode",
+        locale.currency_iso_code.into(),
+    )?;
+    set_internal(
+        activation,
+        this,
+        "_currencySymbol",
+        AvmString::new_utf8(activation.gc(), locale.currency_symbol).into(),
+    )?;
+    set_internal(
+        activation,
+        this,
+        "_fractionalDigits",
+        locale.currency_fractional_digits.into(),
+    )?;
+    set_internal(
+        activation,
+        this,
+        "_positiveCurrencyFormat",
+        locale.positive_currency_format.into(),
+    )?;
+    set_internal(
+        activation,
+        this,
+        "_negativeCurrencyFormat",
+        locale.negative_currency_format.into(),
+    )?;
+    Ok(Value::Undefined)
+}
+
+/// Implements `CurrencyFormatter.format`
+pub fn format<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let value = args.get_f64(activation, 0)?;
+    let with_currency_symbol = args.get_bool(1);
+
+    let options = FormatOptions::read(activation, this)?;
+    let currency = if with_currency_symbol {
+        get_string(activation, this, "currencySymbol")?
+    } else {
+        get_string(activation, this, "currencyISOCode")?
+    };
+    let is_negative = value < 0.0;
+    let currency_format = if is_negative {
+        this.get_public_property("negativeCurrencyFormat", activation)?
+    } else {
+        this.get_public_property("positiveCurrencyFormat", activation)?
+    }
+    .coerce_to_u32(activation)?;
+
+    let number = options.number_format().format_abs(value);
+    let formatted = apply_currency_format(
+        &number,
+        &currency,
+        &options.negative_symbol,
+        is_negative,
+        currency_format,
+    );
+
+    set_status(activation, this, OperationStatus::NoError)?;
+    Ok(AvmString::new_utf8(activation.gc(), formatted).into())
+}
+
+/// Implements `CurrencyFormatter.parse`
+pub fn parse<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let text = args.get_string_non_null(activation, 0, "inputString")?;
+    let options = FormatOptions::read(activation, this)?;
+
+    let parsed = parse_currency(
+        &text.to_utf8_lossy(),
+        &options.decimal_separator,
+        &options.grouping_separator,
+        &options.negative_symbol,
+    );
+
+    let result_class = activation.avm2().classes().currencyparseresult;
+    let result = match parsed {
+        Some((value, symbol)) => {
+            set_status(activation, this, OperationStatus::NoError)?;
+            let symbol = AvmString::new_utf8(activation.gc(), symbol);
+            result_class.construct(activation, &[value.into(), symbol.into()])?
+        }
+        None => {
+            set_status(activation, this, OperationStatus::ParseError)?;
+            result_class.construct(activation, &[])?
+        }
+    };
+    Ok(result.into())
+}
+
+/// Implements `CurrencyFormatter.getAvailableLocaleIDNames`
+pub fn get_available_locale_id_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    available_locale_id_names(activation)
+}
@@ -0,0 +1,183 @@
+//! `flash.globalization.DateTimeFormatter` native methods
+
+use super::{
+    available_locale_id_names, get_internal, init_locale, locale_data, set_internal, set_status,
+    string_vector,
+};
+use crate::avm2::activation::Activation;
+use crate::avm2::error::make_error_2008;
+use crate::avm2::globalization::{
+    date_time_pattern, format_date_time, month_names, weekday_names, DateTimeFields, DateTimeStyle,
+    NameStyle, OperationStatus,
+};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::locale::get_timezone;
+use crate::string::AvmString;
+use chrono::{DateTime, Datelike, Offset, TimeZone, Timelike, Utc};
+
+/// Implements `DateTimeFormatter`'s `init` method, which is called from the constructor
+pub fn init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let requested = args.get_string(activation, 0)?;
+    init_locale(activation, this, requested)?;
+    set_date_time_styles(activation, this, &args[1..])
+}
+
+/// Implements `DateTimeFormatter.setDateTimeStyles`
+pub fn set_date_time_styles<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let date_style_name = args.get_string_non_null(activation, 0, "dateStyle")?;
+    let time_style_name = args.get_string_non_null(activation, 1, "timeStyle")?;
+
+    let Some(date_style) = DateTimeStyle::from_name(&date_style_name.to_utf8_lossy()) else {
+        return Err(make_error_2008(activation, "dateStyle"));
+    };
+    let Some(time_style) = DateTimeStyle::from_name(&time_style_name.to_utf8_lossy()) else {
+        return Err(make_error_2008(activation, "timeStyle"));
+    };
+
+    let locale = locale_data(activation, this)?;
+    let pattern = date_time_pattern(locale, date_style, time_style);
+    set_internal(activation, this, "_dateStyle", date_style_name.into())?;
+    set_internal(activation, this, "_timeStyle", time_style_name.into())?;
+    set_internal(
+        activation,
+        this,
+        "_dateTimePattern",
+        AvmString::new_utf8(activation.gc(), pattern).into(),
+    )?;
+    set_status(activation, this, OperationStatus::NoError)?;
+    Ok(Value::Undefined)
+}
+
+fn date_time_fields<Tz: TimeZone>(date_time: DateTime<Tz>) -> DateTimeFields {
+    DateTimeFields {
+        year: date_time.year(),
+        month: date_time.month(),
+        day: date_time.day(),
+        weekday: date_time.weekday().num_days_from_sunday(),
+        ordinal: date_time.ordinal(),
+        hour: date_time.hour(),
+        minute: date_time.minute(),
+        second: date_time.second(),
+        millisecond: date_time.timestamp_subsec_millis(),
+        offset: date_time.offset().fix().local_minus_utc(),
+    }
+}
+
+fn format_date<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+    utc: bool,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let date = args.get_object(activation, 0, "dateTime")?;
+    let Some(date_time) = date.as_date_object().and_then(|date| date.date_time()) else {
+        // An invalid date can't be formatted.
+        set_status(activation, this, OperationStatus::IllegalArgumentError)?;
+        return Ok(AvmString::default().into());
+    };
+
+    let fields = if utc {
+        date_time_fields(date_time)
+    } else {
+        date_time_fields(date_time.with_timezone(&get_timezone()))
+    };
+    let pattern =
+        get_internal(activation, this, "_dateTimePattern")?.coerce_to_string(activation)?;
+    let locale = locale_data(activation, this)?;
+    let formatted = format_date_time(&pattern.to_utf8_lossy(), &fields, locale);
+
+    set_status(activation, this, OperationStatus::NoError)?;
+    Ok(AvmString::new_utf8(activation.gc(), formatted).into())
+}
+
+/// Implements `DateTimeFormatter.format`
+pub fn format<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    format_date(activation, this, args, false)
+}
+
+/// Implements `DateTimeFormatter.formatUTC`
+pub fn format_utc<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    format_date(activation, this, args, true)
+}
+
+/// Read the `nameStyle` and `context` arguments of `getMonthNames` and `getWeekdayNames`.
+fn name_style_args<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    args: &[Value<'gc>],
+) -> Result<(NameStyle, bool), Error<'gc>> {
+    let name_style = args.get_string_non_null(activation, 0, "nameStyle")?;
+    let context = args.get_string_non_null(activation, 1, "context")?;
+
+    let Some(name_style) = NameStyle::from_name(&name_style.to_utf8_lossy()) else {
+        return Err(make_error_2008(activation, "nameStyle"));
+    };
+    let standalone = match &*context.to_utf8_lossy() {
+        "standalone" => true,
+        "format" => false,
+        _ => return Err(make_error_2008(activation, "context")),
+    };
+    Ok((name_style, standalone))
+}
+
+/// Implements `DateTimeFormatter.getMonthNames`
+pub fn get_month_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let (name_style, standalone) = name_style_args(activation, args)?;
+    let locale = locale_data(activation, this)?;
+    set_status(activation, this, OperationStatus::NoError)?;
+    string_vector(activation, month_names(locale, name_style, standalone))
+}
+
+/// Implements `DateTimeFormatter.getWeekdayNames`
+pub fn get_weekday_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let (name_style, _) = name_style_args(activation, args)?;
+    let locale = locale_data(activation, this)?;
+    set_status(activation, this, OperationStatus::NoError)?;
+    string_vector(activation, weekday_names(locale, name_style))
+}
+
+/// Implements `DateTimeFormatter.getFirstWeekday`
+pub fn get_first_weekday<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let locale = locale_data(activation, this)?;
+    set_status(activation, this, OperationStatus::NoError)?;
+    Ok(locale.first_weekday.into())
+}
+
+/// Implements `DateTimeFormatter.getAvailableLocaleIDNames`
+pub fn get_available_locale_id_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    available_locale_id_names(activation)
+}
@@ -0,0 +1,143 @@
+//! `flash.globalization.LocaleID` native methods
+
+use super::{get_string, set_internal, set_status, string_vector};
+use crate::avm2::activation::Activation;
+use crate::avm2::globalization::{preferred_locales, LocaleName, OperationStatus};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::string::AvmString;
+
+fn locale_name<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+) -> Result<LocaleName, Error<'gc>> {
+    let name = get_string(activation, this, "name")?;
+    set_status(activation, this, OperationStatus::NoError)?;
+    Ok(LocaleName::parse(&name))
+}
+
+/// Implements `LocaleID`'s `init` method, which is called from the constructor
+pub fn init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = args.get_string(activation, 0)?;
+    let canonical = LocaleName::parse(&name.to_utf8_lossy()).canonical();
+    set_internal(
+        activation,
+        this,
+        "_name",
+        AvmString::new_utf8(activation.gc(), canonical).into(),
+    )?;
+    set_status(activation, this, OperationStatus::NoError)?;
+    Ok(Value::Undefined)
+}
+
+/// Implements `LocaleID.getLanguage`
+pub fn get_language<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let language = locale_name(activation, this)?.language;
+    Ok(AvmString::new_utf8(activation.gc(), language).into())
+}
+
+/// Implements `LocaleID.getRegion`
+pub fn get_region<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let region = locale_name(activation, this)?.region;
+    Ok(AvmString::new_utf8(activation.gc(), region).into())
+}
+
+/// Implements `LocaleID.getScript`
+pub fn get_script<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let script = locale_name(activation, this)?.script;
+    Ok(AvmString::new_utf8(activation.gc(), script).into())
+}
+
+/// Implements `LocaleID.getVariant`
+pub fn get_variant<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let variant = locale_name(activation, this)?.variant;
+    Ok(AvmString::new_utf8(activation.gc(), variant).into())
+}
+
+/// Implements `LocaleID.getKeysAndValues`
+pub fn get_keys_and_values<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let keywords = locale_name(activation, this)?.keywords;
+    let object = activation
+        .avm2()
+        .classes()
+        .object
+        .construct(activation, &[])?;
+    for (key, value) in keywords {
+        let key = AvmString::new_utf8(activation.gc(), key);
+        let value = AvmString::new_utf8(activation.gc(), value);
+        object.set_public_property(key, value.into(), activation)?;
+    }
+    Ok(object.into())
+}
+
+/// Implements `LocaleID.isRightToLeft`
+pub fn is_right_to_left<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(locale_name(activation, this)?.is_rtl().into())
+}
+
+/// Read the `Vector.<String>` at `args[index]`.
+fn strings_arg<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    args: &[Value<'gc>],
+    index: usize,
+    name: &'static str,
+) -> Result<Vec<String>, Error<'gc>> {
+    let vector = args.get_object(activation, index, name)?;
+    let values: Vec<_> = vector
+        .as_vector_storage()
+        .map(|storage| storage.iter().collect())
+        .unwrap_or_default();
+    values
+        .into_iter()
+        .map(|value| {
+            Ok(value
+                .coerce_to_string(activation)?
+                .to_utf8_lossy()
+                .into_owned())
+        })
+        .collect()
+}
+
+/// Implements `LocaleID.determinePreferredLocales`
+pub fn determine_preferred_locales<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let want = strings_arg(activation, args, 0, "want")?;
+    let have = strings_arg(activation, args, 1, "have")?;
+
+    let want: Vec<&str> = want.iter().map(String::as_str).collect();
+    let have: Vec<&str> = have.iter().map(String::as_str).collect();
+    string_vector(activation, preferred_locales(&want, &have))
+}
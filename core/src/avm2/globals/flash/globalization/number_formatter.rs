@@ -0,0 +1,154 @@
+//! `flash.globalization.NumberFormatter` native methods
+
+use super::{
+    available_locale_id_names, init_format, init_locale, set_internal, set_status, FormatOptions,
+};
+use crate::avm2::activation::Activation;
+use crate::avm2::globalization::{
+    apply_negative_number_format, parse_number as parse_localized_number, OperationStatus,
+};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::string::{AvmString, WStrToUtf8};
+
+/// Implements `NumberFormatter`'s `init` method, which is called from the constructor
+pub fn init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let requested = args.get_string(activation, 0)?;
+    let locale = init_locale(activation, this, requested)?;
+    init_format(activation, this, locale)?;
+    set_internal(
+        activation,
+        this,
+        "_negativeNumberFormat",
+        locale.negative_number_format.into(),
+    )?;
+    Ok(Value::Undefined)
+}
+
+fn format<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    value: f64,
+    is_integer: bool,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let options = FormatOptions::read(activation, this)?;
+    let negative_number_format = this
+        .get_public_property("negativeNumberFormat", activation)?
+        .coerce_to_u32(activation)?;
+
+    let mut number_format = options.number_format();
+    if is_integer {
+        number_format.fractional_digits = 0;
+    }
+    let number = number_format.format_abs(value);
+    let formatted = if value < 0.0 {
+        apply_negative_number_format(&number, &options.negative_symbol, negative_number_format)
+    } else {
+        number
+    };
+
+    set_status(activation, this, OperationStatus::NoError)?;
+    Ok(AvmString::new_utf8(activation.gc(), formatted).into())
+}
+
+/// Implements `NumberFormatter.formatInt`
+pub fn format_int<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let value = args.get_i32(activation, 0)?;
+    format(activation, this, value.into(), true)
+}
+
+/// Implements `NumberFormatter.formatUint`
+pub fn format_uint<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let value = args.get_u32(activation, 0)?;
+    format(activation, this, value.into(), true)
+}
+
+/// Implements `NumberFormatter.formatNumber`
+pub fn format_number<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let value = args.get_f64(activation, 0)?;
+    format(activation, this, value, false)
+}
+
+/// Implements `NumberFormatter.parse`
+pub fn parse<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let text = args.get_string_non_null(activation, 0, "parseString")?;
+    let options = FormatOptions::read(activation, this)?;
+
+    let to_utf8 = WStrToUtf8::new(&text);
+    let parsed = parse_localized_number(
+        &to_utf8.to_utf8_lossy(),
+        &options.decimal_separator,
+        &options.grouping_separator,
+        &options.negative_symbol,
+    );
+
+    let result_class = activation.avm2().classes().numberparseresult;
+    let result = match parsed {
+        Some(parsed) => {
+            set_status(activation, this, OperationStatus::NoError)?;
+            let start = to_utf8.utf16_index(parsed.start).unwrap_or_default();
+            let end = to_utf8.utf16_index(parsed.end).unwrap_or(text.len());
+            result_class.construct(activation, &[parsed.value.into(), start.into(), end.into()])?
+        }
+        None => {
+            set_status(activation, this, OperationStatus::ParseError)?;
+            result_class.construct(activation, &[])?
+        }
+    };
+    Ok(result.into())
+}
+
+/// Implements `NumberFormatter.parseNumber`
+pub fn parse_number<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let text = args.get_string_non_null(activation, 0, "parseString")?;
+    let options = FormatOptions::read(activation, this)?;
+
+    let parsed = parse_localized_number(
+        &text.to_utf8_lossy(),
+        &options.decimal_separator,
+        &options.grouping_separator,
+        &options.negative_symbol,
+    );
+    let status = if parsed.is_some() {
+        OperationStatus::NoError
+    } else {
+        OperationStatus::ParseError
+    };
+    set_status(activation, this, status)?;
+    Ok(parsed.map_or(f64::NAN, |parsed| parsed.value).into())
+}
+
+/// Implements `NumberFormatter.getAvailableLocaleIDNames`
+pub fn get_available_locale_id_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    available_locale_id_names(activation)
+}
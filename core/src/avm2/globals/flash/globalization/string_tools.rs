@@ -0,0 +1,74 @@
+//! `flash.globalization.StringTools` native methods
+
+use super::{available_locale_id_names, get_string, init_locale, set_status};
+use crate::avm2::activation::Activation;
+use crate::avm2::globalization::{to_lowercase, to_uppercase, OperationStatus, DEFAULT_LOCALE_ID};
+use crate::avm2::object::Object;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::string::AvmString;
+
+/// Implements `StringTools`'s `init` method, which is called from the constructor
+pub fn init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let requested = args.get_string(activation, 0)?;
+    init_locale(activation, this, requested)?;
+    Ok(Value::Undefined)
+}
+
+/// The locale whose casing rules apply.
+///
+/// Casing rules exist for locales that have no formatting data, such as
+/// `az`, so the requested locale is preferred over the actual one.
+fn casing_locale<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+) -> Result<String, Error<'gc>> {
+    let requested = get_string(activation, this, "requestedLocaleIDName")?;
+    if requested == DEFAULT_LOCALE_ID {
+        get_string(activation, this, "actualLocaleIDName")
+    } else {
+        Ok(requested)
+    }
+}
+
+/// Implements `StringTools.toLowerCase`
+pub fn to_lower_case<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let text = args.get_string_non_null(activation, 0, "s")?;
+    let locale = casing_locale(activation, this)?;
+    let lowercase = to_lowercase(&text.to_utf8_lossy(), &locale);
+
+    set_status(activation, this, OperationStatus::NoError)?;
+    Ok(AvmString::new_utf8(activation.gc(), lowercase).into())
+}
+
+/// Implements `StringTools.toUpperCase`
+pub fn to_upper_case<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let text = args.get_string_non_null(activation, 0, "s")?;
+    let locale = casing_locale(activation, this)?;
+    let uppercase = to_uppercase(&text.to_utf8_lossy(), &locale);
+
+    set_status(activation, this, OperationStatus::NoError)?;
+    Ok(AvmString::new_utf8(activation.gc(), uppercase).into())
+}
+
+/// Implements `StringTools.getAvailableLocaleIDNames`
+pub fn get_available_locale_id_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    available_locale_id_names(activation)
+}
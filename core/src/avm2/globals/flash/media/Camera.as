@@ -4,126 +4,95 @@ package flash.media {
     import flash.geom.Rectangle;
     import flash.display.BitmapData;
 
+    [Ruffle(NativeInstanceInit)]
     public final class Camera extends EventDispatcher {
-        [API("682")]
-        public function copyToByteArray(rect:Rectangle, destination:ByteArray) {
-            __ruffle__.stub_method("flash.media.Camera", "copyToByteArray");
+        private var _bandwidth:int = 16384;
+        private var _keyFrameInterval:int = 15;
+        private var _loopback:Boolean = false;
+        private var _motionLevel:int = 50;
+        private var _motionTimeout:int = 2000;
+        private var _quality:int = 0;
+
+        public function Camera() {
+            throw new ArgumentError("Error #2012: Camera$ class cannot be instantiated.", 2012);
         }
 
         [API("682")]
-        public function copyToVector(rect:Rectangle, destination:Vector.<uint>) {
-            __ruffle__.stub_method("flash.media.Camera", "copyToVector");
-        }
+        public native function copyToByteArray(rect:Rectangle, destination:ByteArray):void;
 
         [API("682")]
-        public function drawToBitmapData(destination:BitmapData) {
-            __ruffle__.stub_method("flash.media.Camera", "drawToBitmapData");
-        }
+        public native function copyToVector(rect:Rectangle, destination:Vector.<uint>):void;
 
-        public static function getCamera(name: String = null):Camera {
-            __ruffle__.stub_method("flash.media.Camera", "getCamera");
-            return null;
-        }
+        [API("682")]
+        public native function drawToBitmapData(destination:BitmapData):void;
+
+        public static native function getCamera(name:String = null):Camera;
 
         public function setKeyFrameInterval(keyFrameInterval:int) {
-            __ruffle__.stub_method("flash.media.Camera", "setKeyFrameInterval");
+            this._keyFrameInterval = keyFrameInterval;
         }
 
         public function setLoopback(compress:Boolean = false) {
-            __ruffle__.stub_method("flash.media.Camera", "setLoopback");
+            this._loopback = compress;
         }
 
-        public function setMode(width:int, height:int, fps:Number, favorArea:Boolean = true) {
-            __ruffle__.stub_method("flash.media.Camera", "setMode");
-        }
+        public native function setMode(width:int, height:int, fps:Number, favorArea:Boolean = true):void;
 
         public function setMotionLevel(motionLevel:int, timeout:int = 2000) {
-            __ruffle__.stub_method("flash.media.Camera", "setMotionLevel");
+            this._motionLevel = motionLevel;
+            this._motionTimeout = timeout;
         }
 
         public function setQuality(bandwidth:int, quality:int) {
-            __ruffle__.stub_method("flash.media.Camera", "setQuality");
+            this._bandwidth = bandwidth;
+            this._quality = quality;
         }
 
-        public function get activityLevel(): Number {
-            __ruffle__.stub_getter("flash.media.Camera", "activityLevel");
-            return 0;
-        }
+        public native function get activityLevel(): Number;
 
         public function get bandwidth(): int {
-            __ruffle__.stub_getter("flash.media.Camera", "bandwidth");
-            return 0;
+            return this._bandwidth;
         }
 
-        public function get currentFPS(): Number {
-            __ruffle__.stub_getter("flash.media.Camera", "currentFPS");
-            return 0;
-        }
+        public native function get currentFPS(): Number;
 
-        public function get fps(): Number {
-            __ruffle__.stub_getter("flash.media.Camera", "fps");
-            return 0;
-        }
+        public native function get fps(): Number;
 
-        public function get height(): int {
-            __ruffle__.stub_getter("flash.media.Camera", "height");
-            return 0;
-        }
+        public native function get height(): int;
 
-        public function get index(): int {
-            __ruffle__.stub_getter("flash.media.Camera", "index");
-            return 0;
-        }
+        public native function get index(): int;
 
         public static function get isSupported(): Boolean {
-            __ruffle__.stub_getter("flash.media.Camera", "isSupported");
-            return false;
+            return names.length > 0;
         }
 
         public function get keyFrameInterval(): int {
-            __ruffle__.stub_getter("flash.media.Camera", "keyFrameInterval");
-            return 0;
+            return this._keyFrameInterval;
         }
 
         public function get loopback(): Boolean {
-            __ruffle__.stub_getter("flash.media.Camera", "loopback");
-            return false;
+            return this._loopback;
         }
 
         public function get motionLevel(): int {
-            __ruffle__.stub_getter("flash.media.Camera", "motionLevel");
-            return 0;
+            return this._motionLevel;
         }
 
         public function get motionTimeout(): int {
-            __ruffle__.stub_getter("flash.media.Camera", "motionTimeout");
-            return 0;
+            return this._motionTimeout;
         }
 
-        public function get muted(): Boolean {
-            __ruffle__.stub_getter("flash.media.Camera", "muted");
-            return true;
-        }
+        public native function get muted(): Boolean;
 
-        public function get name(): String {
-            __ruffle__.stub_getter("flash.media.Camera", "name");
-            return "";
-        }
+        public native function get name(): String;
+
+        public static native function get names(): Array;
 
-        public static function get names(): Array {
-            __ruffle__.stub_getter("flash.media.Camera", "names");
-            return [];
-        }
-        
         public function get quality(): int {
-            __ruffle__.stub_getter("flash.media.Camera", "quality");
-            return 0;
-        }
-        
-        public function get width(): int {
-            __ruffle__.stub_getter("flash.media.Camera", "width");
-            return 0;
+            return this._quality;
         }
+
+        public native function get width(): int;
     }
 
-}
\ No newline at end of file
+}
@@ -1,153 +1,122 @@
 package flash.media {
     import flash.events.EventDispatcher;
 
+    [Ruffle(NativeInstanceInit)]
     public final class Microphone extends EventDispatcher {
+        private var _codec:String = SoundCodec.NELLYMOSER;
+        private var _enableVAD:Boolean = true;
+        private var _encodeQuality:int = 6;
+        private var _enhancedOptions:MicrophoneEnhancedOptions = new MicrophoneEnhancedOptions();
+        private var _framesPerPacket:int = 2;
+        private var _noiseSuppressionLevel:int = -30;
+        private var _soundTransform:SoundTransform = new SoundTransform();
+        private var _useEchoSuppression:Boolean = false;
+
+        public function Microphone() {
+            throw new ArgumentError("Error #2012: Microphone$ class cannot be instantiated.", 2012);
+        }
+
         public static function getEnhancedMicrophone(index:int = -1):Microphone {
             __ruffle__.stub_method("flash.media.Microphone", "getEnhancedMicrophone");
-            return new Microphone();
+            return getMicrophone(index);
         }
 
-        public static function getMicrophone(index:int = -1):Microphone {
-            __ruffle__.stub_method("flash.media.Microphone", "getMicrophone");
-            return new Microphone();
-        }
+        public static native function getMicrophone(index:int = -1):Microphone;
 
         public function setLoopBack(isLooped:Boolean=true) {
             __ruffle__.stub_method("flash.media.Microphone", "setLoopBack");
         }
 
-        public function setSilenceLevel(silenceLevel:Number, timeout:int = -1) {
-            __ruffle__.stub_method("flash.media.Microphone", "setSilenceLevel");
-        }
+        public native function setSilenceLevel(silenceLevel:Number, timeout:int = -1):void;
 
         public function setUseEchoSuppression(isEchoSuppressed:Boolean) {
-            __ruffle__.stub_method("flash.media.Microphone", "setUseEchoSuppression");
+            this._useEchoSuppression = isEchoSuppressed;
         }
 
-        public function get activityLevel():Number {
-            __ruffle__.stub_getter("flash.media.Microphone", "activityLevel");
-            return 0.0;
-        }
+        public native function get activityLevel():Number;
 
         public function get codec():String {
-            __ruffle__.stub_getter("flash.media.Microphone", "codec");
-            return "";
+            return this._codec;
         }
 
         public function set codec(codec:String) {
-            __ruffle__.stub_setter("flash.media.Microphone", "codec");
+            this._codec = codec;
         }
 
         public function get enableVAD():Boolean {
-            __ruffle__.stub_getter("flash.media.Microphone", "enableVAD");
-            return false;
+            return this._enableVAD;
         }
 
         public function set enableVAD(isEnabled:Boolean) {
-            __ruffle__.stub_setter("flash.media.Microphone", "enableVAD");
+            this._enableVAD = isEnabled;
         }
 
         public function get encodeQuality():int {
-            __ruffle__.stub_getter("flash.media.Microphone", "encodeQuality");
-            return 0;
+            return this._encodeQuality;
         }
 
         public function set encodeQuality(quality:int) {
-            __ruffle__.stub_setter("flash.media.Microphone", "encodeQuality");
+            this._encodeQuality = quality;
         }
 
         public function get enhancedOptions():MicrophoneEnhancedOptions {
-            __ruffle__.stub_getter("flash.media.Microphone", "enhancedOptions");
-            return new MicrophoneEnhancedOptions();
+            return this._enhancedOptions;
         }
 
         public function set enhancedOptions(params:MicrophoneEnhancedOptions) {
-            __ruffle__.stub_setter("flash.media.Microphone", "enhancedOptions");
+            this._enhancedOptions = params;
         }
 
         public function get framesPerPacket():int {
-            __ruffle__.stub_getter("flash.media.Microphone", "framesPerPacket");
-            return 0;
+            return this._framesPerPacket;
         }
 
         public function set framesPerPacket(fpp:int) {
-            __ruffle__.stub_setter("flash.media.Microphone", "framesPerPacket");
+            this._framesPerPacket = fpp;
         }
 
-        public function get gain():Number {
-            __ruffle__.stub_getter("flash.media.Microphone", "gain");
-            return 0.0;
-        }
+        public native function get gain():Number;
 
-        public function set gain(gain:Number) {
-            __ruffle__.stub_setter("flash.media.Microphone", "gain");
-        }
+        public native function set gain(gain:Number):void;
 
-        public function get index():int {
-            __ruffle__.stub_getter("flash.media.Microphone", "index");
-            return 0;
-        }
+        public native function get index():int;
 
         public static function get isSupported():Boolean {
-            __ruffle__.stub_getter("flash.media.Microphone", "isSupported");
-            return false;
+            return names.length > 0;
         }
 
-        public function get muted():Boolean {
-            __ruffle__.stub_getter("flash.media.Microphone", "muted");
-            return true;
-        }
+        public native function get muted():Boolean;
 
-        public function get name():Boolean {
-            __ruffle__.stub_getter("flash.media.Microphone", "name");
-            return "";
-        }
+        public native function get name():String;
 
-        public static function get names():Array {
-            __ruffle__.stub_getter("flash.media.Microphone", "names");
-            return [];
-        }
+        public static native function get names():Array;
 
         public function get noiseSuppressionLevel():int {
-            __ruffle__.stub_getter("flash.media.Microphone", "noiseSuppressionLevel");
-            return 0;
+            return this._noiseSuppressionLevel;
         }
 
         public function set noiseSuppressionLevel(level:int) {
-            __ruffle__.stub_setter("flash.media.Microphone", "noiseSuppressionLevel");
+            this._noiseSuppressionLevel = level;
         }
 
-        public function get rate():int {
-            __ruffle__.stub_getter("flash.media.Microphone", "rate");
-            return 0;
-        }
+        public native function get rate():int;
 
-        public function set rate(level:int) {
-            __ruffle__.stub_setter("flash.media.Microphone", "rate");
-        }
+        public native function set rate(rate:int):void;
 
-        public function get silenceLevel():int {
-            __ruffle__.stub_getter("flash.media.Microphone", "silenceLevel");
-            return 0;
-        }
+        public native function get silenceLevel():Number;
 
-        public function get silenceTimeout():int {
-            __ruffle__.stub_getter("flash.media.Microphone", "silenceTimeout");
-            return 0;
-        }
+        public native function get silenceTimeout():int;
 
-        public function get soundTransform():flash.media.SoundTransform {
-            __ruffle__.stub_getter("flash.media.Microphone", "soundTransform");
-            return new SoundTransform();
+        public function get soundTransform():SoundTransform {
+            return this._soundTransform;
         }
 
-        public function set soundTransform(tf:flash.media.SoundTransform) {
-            __ruffle__.stub_setter("flash.media.Microphone", "soundTransform");
+        public function set soundTransform(tf:SoundTransform) {
+            this._soundTransform = tf;
         }
 
         public function get useEchoSuppression():Boolean {
-            __ruffle__.stub_getter("flash.media.Microphone", "useEchoSuppression");
-            return false;
+            return this._useEchoSuppression;
         }
     }
-}
\ No newline at end of file
+}
@@ -50,6 +50,8 @@ package flash.media
 
         public native function attachNetStream(netStream: NetStream);
 
+        public native function attachCamera(camera: Camera):void;
+
         public function clear():void {
             stub_method("flash.media.Video", "clear");
         }
@@ -0,0 +1,298 @@
+//! `flash.media.Camera` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::bytearray::ByteArrayStorage;
+use crate::avm2::globals::flash::display::bitmap_data::get_rectangle_x_y_width_height;
+use crate::avm2::object::{ArrayObject, Object, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::worker::construct_native;
+use crate::avm2::Error;
+use crate::backend::capture::CameraFrame;
+use crate::bitmap::operations;
+use crate::capture::Camera;
+use crate::string::AvmString;
+
+pub fn native_instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation.super_init(this, &[])?;
+    Ok(Value::Undefined)
+}
+
+fn camera<'gc>(activation: &mut Activation<'_, 'gc>, this: Object<'gc>) -> Option<Camera<'gc>> {
+    activation.context.capture_manager.camera_by_object(this)
+}
+
+/// Read the pixels of a region of a frame as ARGB colors, clamped to the frame.
+fn frame_pixels(frame: &CameraFrame, x: i32, y: i32, width: i32, height: i32) -> Vec<u32> {
+    let clamp = |value: i32, max: u32| value.clamp(0, max as i32) as u32;
+    let (x_min, x_max) = (clamp(x, frame.width), clamp(x + width, frame.width));
+    let (y_min, y_max) = (clamp(y, frame.height), clamp(y + height, frame.height));
+
+    let mut pixels = Vec::with_capacity(((x_max - x_min) * (y_max - y_min)) as usize);
+    for y in y_min..y_max {
+        for x in x_min..x_max {
+            let i = ((y * frame.width + x) * 4) as usize;
+            let [r, g, b, a] = [
+                frame.rgba[i],
+                frame.rgba[i + 1],
+                frame.rgba[i + 2],
+                frame.rgba[i + 3],
+            ];
+            pixels.push(u32::from_be_bytes([a, r, g, b]));
+        }
+    }
+    pixels
+}
+
+/// Implements `Camera.getCamera`
+pub fn get_camera<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // Cameras are named by their index, and `null` means the default camera.
+    let index = match args.try_get_string(activation, 0)? {
+        Some(name) => match name.to_utf8_lossy().parse::<usize>() {
+            Ok(index) => index,
+            Err(_) => return Ok(Value::Null),
+        },
+        None => 0,
+    };
+    if index >= activation.context.capture.camera_names().len() {
+        return Ok(Value::Null);
+    }
+
+    if let Some(camera) = activation.context.capture_manager.camera_by_index(index) {
+        return Ok(camera.object().into());
+    }
+
+    let class = activation.avm2().classes().camera;
+    let object = construct_native(activation, class)?;
+    Camera::open(activation.context, index, object);
+    Ok(object.into())
+}
+
+/// Implements `Camera.names`
+pub fn get_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let names: Vec<Value<'gc>> = activation
+        .context
+        .capture
+        .camera_names()
+        .iter()
+        .map(|name| AvmString::new_utf8(activation.context.gc_context, name).into())
+        .collect();
+    Ok(ArrayObject::from_storage(activation, ArrayStorage::from_args(&names))?.into())
+}
+
+/// Implements `Camera.setMode`
+pub fn set_mode<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let width = args.get_i32(activation, 0)?.max(1) as u32;
+    let height = args.get_i32(activation, 1)?.max(1) as u32;
+    let fps = args.get_f64(activation, 2)?;
+    if let Some(camera) = camera(activation, this) {
+        camera.set_mode(activation.context, width, height, fps);
+    }
+    Ok(Value::Undefined)
+}
+
+/// Implements `Camera.copyToByteArray`
+pub fn copy_to_byte_array<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let rectangle = args.get_object(activation, 0, "rect")?;
+    let destination = args.get_object(activation, 1, "destination")?;
+    let (x, y, width, height) = get_rectangle_x_y_width_height(activation, rectangle)?;
+
+    let Some(camera) = camera(activation, this) else {
+        return Ok(Value::Undefined);
+    };
+    let pixels = match &*camera.frame() {
+        Some(frame) => frame_pixels(frame, x, y, width, height),
+        None => return Ok(Value::Undefined),
+    };
+
+    let mut storage = destination
+        .as_bytearray_mut()
+        .ok_or("ArgumentError: Parameter must be a bytearray")?;
+    for pixel in pixels {
+        storage
+            .write_unsigned_int(pixel)
+            .map_err(|e| e.to_avm(activation))?;
+    }
+    Ok(Value::Undefined)
+}
+
+/// Implements `Camera.copyToVector`
+pub fn copy_to_vector<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let rectangle = args.get_object(activation, 0, "rect")?;
+    let destination = args.get_object(activation, 1, "destination")?;
+    let (x, y, width, height) = get_rectangle_x_y_width_height(activation, rectangle)?;
+
+    let Some(camera) = camera(activation, this) else {
+        return Ok(Value::Undefined);
+    };
+    let pixels = match &*camera.frame() {
+        Some(frame) => frame_pixels(frame, x, y, width, height),
+        None => return Ok(Value::Undefined),
+    };
+
+    let mut vector = destination
+        .as_vector_storage_mut(activation.context.gc_context)
+        .ok_or("ArgumentError: Parameter must be a vector")?;
+    if vector.length() < pixels.len() {
+        vector.resize(pixels.len(), activation)?;
+    }
+    for (index, pixel) in pixels.into_iter().enumerate() {
+        vector.set(index, pixel.into(), activation)?;
+    }
+    Ok(Value::Undefined)
+}
+
+/// Implements `Camera.drawToBitmapData`
+pub fn draw_to_bitmap_data<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let destination = args.get_object(activation, 0, "destination")?;
+    let Some(bitmap_data) = destination.as_bitmap_data() else {
+        return Ok(Value::Undefined);
+    };
+    bitmap_data.check_valid(activation)?;
+
+    let Some(camera) = camera(activation, this) else {
+        return Ok(Value::Undefined);
+    };
+    // Frames that are larger than the destination are cropped.
+    let (width, height, pixels) = match &*camera.frame() {
+        Some(frame) => {
+            let width = frame.width.min(bitmap_data.width()) as i32;
+            let height = frame.height.min(bitmap_data.height()) as i32;
+            (width, height, frame_pixels(frame, 0, 0, width, height))
+        }
+        None => return Ok(Value::Undefined),
+    };
+
+    let mut storage = ByteArrayStorage::new();
+    for pixel in pixels {
+        storage
+            .write_unsigned_int(pixel)
+            .map_err(|e| e.to_avm(activation))?;
+    }
+    storage.set_position(0);
+
+    operations::set_pixels_from_byte_array(
+        activation.context.gc_context,
+        activation.context.renderer,
+        bitmap_data,
+        0,
+        0,
+        width,
+        height,
+        &mut storage,
+    )
+    .map_err(|e| e.to_avm(activation))?;
+    Ok(Value::Undefined)
+}
+
+/// Implements `Camera.activityLevel`
+pub fn get_activity_level<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(camera(activation, this)
+        .map_or(-1.0, |c| c.activity_level())
+        .into())
+}
+
+/// Implements `Camera.currentFPS`
+pub fn get_current_fps<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(camera(activation, this)
+        .map_or(0.0, |c| c.current_fps())
+        .into())
+}
+
+/// Implements `Camera.fps`
+pub fn get_fps<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(camera(activation, this).map_or(0.0, |c| c.fps()).into())
+}
+
+/// Implements `Camera.width`
+pub fn get_width<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(camera(activation, this).map_or(0, |c| c.width()).into())
+}
+
+/// Implements `Camera.height`
+pub fn get_height<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(camera(activation, this).map_or(0, |c| c.height()).into())
+}
+
+/// Implements `Camera.index`
+pub fn get_index<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(camera(activation, this)
+        .map_or(0, |c| c.index() as i32)
+        .into())
+}
+
+/// Implements `Camera.muted`
+pub fn get_muted<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(camera(activation, this).map_or(true, |c| c.muted()).into())
+}
+
+/// Implements `Camera.name`
+pub fn get_name<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(camera) = camera(activation, this) else {
+        return Ok("".into());
+    };
+    let names = activation.context.capture.camera_names();
+    let name = names.get(camera.index()).map_or("", String::as_str);
+    Ok(AvmString::new_utf8(activation.context.gc_context, name).into())
+}
@@ -0,0 +1,203 @@
+//! `flash.media.Microphone` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::object::{ArrayObject, Object};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::worker::construct_native;
+use crate::avm2::Error;
+use crate::capture::Microphone;
+use crate::string::AvmString;
+
+pub fn native_instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation.super_init(this, &[])?;
+    Ok(Value::Undefined)
+}
+
+fn microphone<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+) -> Option<Microphone<'gc>> {
+    activation
+        .context
+        .capture_manager
+        .microphone_by_object(this)
+}
+
+/// Implements `Microphone.getMicrophone`
+pub fn get_microphone<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // An index of -1 means the default microphone, which is the first one.
+    let index = args.get_i32(activation, 0)?.max(0) as usize;
+    if index >= activation.context.capture.microphone_names().len() {
+        return Ok(Value::Null);
+    }
+
+    if let Some(microphone) = activation
+        .context
+        .capture_manager
+        .microphone_by_index(index)
+    {
+        return Ok(microphone.object().into());
+    }
+
+    let class = activation.avm2().classes().microphone;
+    let object = construct_native(activation, class)?;
+    Microphone::open(activation.context, index, object);
+    Ok(object.into())
+}
+
+/// Implements `Microphone.names`
+pub fn get_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let names: Vec<Value<'gc>> = activation
+        .context
+        .capture
+        .microphone_names()
+        .iter()
+        .map(|name| AvmString::new_utf8(activation.context.gc_context, name).into())
+        .collect();
+    Ok(ArrayObject::from_storage(activation, ArrayStorage::from_args(&names))?.into())
+}
+
+/// Implements `Microphone.activityLevel`
+pub fn get_activity_level<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(microphone(activation, this)
+        .map_or(-1.0, |m| m.activity_level())
+        .into())
+}
+
+/// Implements `Microphone.gain`'s getter
+pub fn get_gain<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(microphone(activation, this)
+        .map_or(50.0, |m| m.gain())
+        .into())
+}
+
+/// Implements `Microphone.gain`'s setter
+pub fn set_gain<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let gain = args.get_f64(activation, 0)?;
+    if let Some(microphone) = microphone(activation, this) {
+        microphone.set_gain(activation.context, gain);
+    }
+    Ok(Value::Undefined)
+}
+
+/// Implements `Microphone.index`
+pub fn get_index<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(microphone(activation, this)
+        .map_or(0, |m| m.index() as i32)
+        .into())
+}
+
+/// Implements `Microphone.muted`
+pub fn get_muted<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(microphone(activation, this)
+        .map_or(true, |m| m.muted())
+        .into())
+}
+
+/// Implements `Microphone.name`
+pub fn get_name<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(microphone) = microphone(activation, this) else {
+        return Ok("".into());
+    };
+    let names = activation.context.capture.microphone_names();
+    let name = names.get(microphone.index()).map_or("", String::as_str);
+    Ok(AvmString::new_utf8(activation.context.gc_context, name).into())
+}
+
+/// Implements `Microphone.rate`'s getter
+pub fn get_rate<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(microphone(activation, this).map_or(8, |m| m.rate()).into())
+}
+
+/// Implements `Microphone.rate`'s setter
+pub fn set_rate<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let rate = args.get_i32(activation, 0)?;
+    if let Some(microphone) = microphone(activation, this) {
+        if let Ok(rate) = u32::try_from(rate) {
+            microphone.set_rate(activation.context, rate);
+        }
+    }
+    Ok(Value::Undefined)
+}
+
+/// Implements `Microphone.silenceLevel`
+pub fn get_silence_level<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(microphone(activation, this)
+        .map_or(10.0, |m| m.silence_level())
+        .into())
+}
+
+/// Implements `Microphone.silenceTimeout`
+pub fn get_silence_timeout<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(microphone(activation, this)
+        .map_or(2000, |m| m.silence_timeout())
+        .into())
+}
+
+/// Implements `Microphone.setSilenceLevel`
+pub fn set_silence_level<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let level = args.get_f64(activation, 0)?;
+    let timeout = args.get_i32(activation, 1)?;
+    if let Some(microphone) = microphone(activation, this) {
+        microphone.set_silence_level(activation.context, level, timeout);
+    }
+    Ok(Value::Undefined)
+}
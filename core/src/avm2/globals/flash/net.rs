@@ -8,6 +8,7 @@ use crate::backend::navigator::NavigationMethod;
 use indexmap::IndexMap;
 
 pub mod file_reference;
+pub mod file_reference_list;
 pub mod local_connection;
 pub mod net_connection;
 pub mod net_stream;
@@ -16,6 +17,7 @@ pub mod responder;
 pub mod shared_object;
 pub mod socket;
 pub mod url_loader;
+pub mod url_stream;
 pub mod xml_socket;
 
 fn object_to_index_map<'gc>(
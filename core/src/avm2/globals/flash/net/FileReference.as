@@ -1,8 +1,9 @@
 package flash.net
 {
     import flash.events.EventDispatcher;
+    import flash.events.PermissionEvent;
+    import flash.permissions.PermissionStatus;
     import flash.utils.ByteArray;
-    import __ruffle__.stub_method;
 
     [Ruffle(InstanceAllocator)]
     public class FileReference extends EventDispatcher
@@ -10,6 +11,12 @@ package flash.net
         public function FileReference() {
         }
 
+        // There are no permissions to request outside of AIR on mobile.
+        [API("681")]
+        public static function get permissionStatus(): String {
+            return PermissionStatus.GRANTED;
+        }
+
         public native function get creationDate(): Date;
 
         public function get creator(): String {
@@ -37,30 +44,22 @@ package flash.net
 
         public native function browse(typeFilter:Array = null): Boolean;
 
-        public function cancel():void {
-            stub_method("flash.net.FileReference", "cancel");
-        }
+        public native function cancel():void;
 
-        public function download(request:URLRequest, defaultFileName:String = null):void {
-            stub_method("flash.net.FileReference", "download");
-        }
+        public native function download(request:URLRequest, defaultFileName:String = null):void;
 
         public native function load():void;
 
         [API("681")]
         public function requestPermission():void {
-            stub_method("flash.net.FileReference", "requestPermission");
+            this.dispatchEvent(new PermissionEvent(PermissionEvent.PERMISSION_STATUS, false, false, PermissionStatus.GRANTED));
         }
 
         public native function save(data:*, defaultFileName:String = null):void;
 
-        public function upload(request:URLRequest, uploadDataFieldName:String = "Filedata", testUpload:Boolean = false):void {
-            stub_method("flash.net.FileReference", "upload");
-        }
+        public native function upload(request:URLRequest, uploadDataFieldName:String = "Filedata", testUpload:Boolean = false):void;
 
         [API("681")]
-        public function uploadUnencoded(request:URLRequest):void {
-            stub_method("flash.net.FileReference", "uploadUnencoded");
-        }
+        public native function uploadUnencoded(request:URLRequest):void;
     }
 }
\ No newline at end of file
@@ -18,9 +18,11 @@ package flash.net {
         public static const DIRECT_CONNECTIONS: String = "directConnections";
 
         public function NetStream(connection:NetConnection, peer:String = CONNECT_TO_FMS) {
-
+            this.init(connection);
         }
 
+        private native function init(connection:NetConnection):void;
+
         public function appendBytes(bytes:ByteArray) {
             stub_method("flash.net.NetStream", "appendBytes");
         }
@@ -41,9 +43,7 @@ package flash.net {
             stub_method("flash.net.NetStream", "attachCamera");
         }
 
-        public function close() {
-            stub_method("flash.net.NetStream", "close");
-        }
+        public native function close():void;
 
         [API("674")]
         public function dispose() {
@@ -63,9 +63,7 @@ package flash.net {
             stub_method("flash.net.NetStream", "preloadEmbeddedData");
         }
 
-        public function publish(name:String=null, type:String=null) {
-            stub_method("flash.net.NetStream", "publish");
-        }
+        public native function publish(name:String=null, type:String=null):void;
 
         public function receiveAudio(flag:Boolean) {
             stub_method("flash.net.NetStream", "receiveAudio");
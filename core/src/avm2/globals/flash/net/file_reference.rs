@@ -1,10 +1,39 @@
 use crate::avm2::bytearray::ByteArrayStorage;
 use crate::avm2::error::{argument_error, error, make_error_2037, make_error_2097};
+use crate::avm2::globals::flash::display::loader::request_from_url_request;
 pub use crate::avm2::object::file_reference_allocator;
-use crate::avm2::object::{ByteArrayObject, DateObject, FileReference};
+use crate::avm2::object::{ByteArrayObject, DateObject, FileReference, FileReferenceObject};
+use crate::avm2::parameters::ParametersExt;
 use crate::avm2::{Activation, Avm2, Error, EventObject, Object, TObject, Value};
+use crate::backend::navigator::{multipart_form_data, Request};
 use crate::backend::ui::FileFilter;
 use crate::string::AvmString;
+use url::{form_urlencoded, Url};
+
+fn make_error_2174<'gc>(activation: &mut Activation<'_, 'gc>) -> Error<'gc> {
+    match error(
+        activation,
+        "Error #2174: Only one download, upload, load or save operation can be active at a time on each FileReference.",
+        2174,
+    ) {
+        Ok(err) => Error::AvmError(err),
+        Err(err) => err,
+    }
+}
+
+/// Throw an error if a download or upload of this `FileReference` is in progress.
+fn check_no_transfer_in_progress<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: FileReferenceObject<'gc>,
+) -> Result<(), Error<'gc>> {
+    let in_progress = this
+        .loader()
+        .is_some_and(|handle| activation.context.load_manager.get_loader(handle).is_some());
+    if in_progress {
+        return Err(make_error_2174(activation));
+    }
+    Ok(())
+}
 
 pub fn get_creation_date<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -119,15 +148,13 @@ pub fn get_type<'gc>(
     Ok(type_)
 }
 
-pub fn browse<'gc>(
+/// Get the file filters of the `typeFilter` array given in `value`.
+pub fn file_filters<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    this: Object<'gc>,
-    args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error<'gc>> {
-    let this = this.as_file_reference().unwrap();
-
+    value: Value<'gc>,
+) -> Result<Vec<FileFilter>, Error<'gc>> {
     let mut filters = Vec::new();
-    if let Value::Object(obj) = args[0] {
+    if let Value::Object(obj) = value {
         if let Some(array_storage) = obj.as_array_storage() {
             for filter in array_storage.iter() {
                 if let Some(Value::Object(obj)) = filter {
@@ -171,6 +198,17 @@ pub fn browse<'gc>(
         }
     }
 
+    Ok(filters)
+}
+
+pub fn browse<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_file_reference().unwrap();
+    let filters = file_filters(activation, args[0])?;
+
     let dialog = activation.context.ui.display_file_open_dialog(filters);
     let result = match dialog {
         Some(dialog) => {
@@ -273,8 +311,154 @@ pub fn save<'gc>(
 
             activation.context.navigator.spawn_future(process);
         }
-        None => return Err(Error::AvmError(error(activation, "Error #2174: Only one download, upload, load or save operation can be active at a time on each FileReference.", 2174)?)),
+        None => return Err(make_error_2174(activation)),
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn cancel<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_file_reference().unwrap();
+
+    // Removing the loader makes the download or upload stop without dispatching any more events.
+    if let Some(handle) = this.loader() {
+        activation.context.load_manager.remove_loader(handle);
+        this.set_loader(None);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn download<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_file_reference().unwrap();
+    let url_request = args.get_object(activation, 0, "request")?;
+    check_no_transfer_in_progress(activation, this)?;
+
+    let request = request_from_url_request(activation, url_request)?;
+    let file_name = match args.try_get_string(activation, 1)? {
+        Some(file_name) => file_name.to_string(),
+        None => {
+            // Use the end of the path as the file name.
+            let path = request.url().split(['?', '#']).next().unwrap_or_default();
+            path.rsplit('/').next().unwrap_or_default().to_string()
+        }
+    };
+    let domain = Url::parse(request.url())
+        .ok()
+        .and_then(|url| url.domain().map(str::to_string))
+        .unwrap_or_else(|| "<unknown domain>".to_string());
+
+    // Create and spawn dialog
+    let dialog = activation.context.ui.display_file_save_dialog(
+        file_name,
+        format!("Select location for download from {}", domain),
+    );
+
+    match dialog {
+        Some(dialog) => {
+            let process = activation.context.load_manager.download_file_dialog_avm2(
+                activation.context.player.clone(),
+                this,
+                dialog,
+                request,
+            );
+
+            activation.context.navigator.spawn_future(process);
+        }
+        None => return Err(make_error_2174(activation)),
     }
 
     Ok(Value::Undefined)
 }
+
+/// The name and contents of the selected file, for uploading it.
+fn file_to_upload<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: FileReferenceObject<'gc>,
+) -> Result<(String, Vec<u8>), Error<'gc>> {
+    match *this.file_reference() {
+        FileReference::None => Err(make_error_2037(activation)),
+        FileReference::FileDialogResult(ref dialog_result) => Ok((
+            dialog_result.file_name().unwrap_or_default(),
+            dialog_result.contents().to_vec(),
+        )),
+    }
+}
+
+fn spawn_upload<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: FileReferenceObject<'gc>,
+    request: Request,
+    body: (Vec<u8>, String),
+    file_size: u64,
+) {
+    // Uploads are always sent with POST, but keep the URL and headers of the request.
+    let mut upload_request = Request::post(request.url().to_string(), Some(body));
+    upload_request.set_headers(request.headers().clone());
+
+    let process = activation.context.load_manager.upload_file_avm2(
+        activation.context.player.clone(),
+        this,
+        upload_request,
+        file_size,
+    );
+    activation.context.navigator.spawn_future(process);
+}
+
+pub fn upload<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_file_reference().unwrap();
+    let url_request = args.get_object(activation, 0, "request")?;
+    let field_name = args.get_string_non_null(activation, 1, "uploadDataFieldName")?;
+    // `testUpload` only makes Flash Player on Windows send an empty request first, which we don't need.
+
+    let (file_name, data) = file_to_upload(activation, this)?;
+    check_no_transfer_in_progress(activation, this)?;
+
+    // The variables of a POST request are sent as additional fields of the form.
+    let request = request_from_url_request(activation, url_request)?;
+    let fields: Vec<(String, String)> = match request.body() {
+        Some((body, content_type)) if content_type == "application/x-www-form-urlencoded" => {
+            form_urlencoded::parse(body).into_owned().collect()
+        }
+        _ => Vec::new(),
+    };
+
+    let body = multipart_form_data(&fields, &field_name.to_string(), &file_name, &data);
+    spawn_upload(activation, this, request, body, data.len() as u64);
+    Ok(Value::Undefined)
+}
+
+pub fn upload_unencoded<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this = this.as_file_reference().unwrap();
+    let url_request = args.get_object(activation, 0, "request")?;
+
+    let (_, data) = file_to_upload(activation, this)?;
+    check_no_transfer_in_progress(activation, this)?;
+
+    // The file is sent as the body of the request, as is.
+    let request = request_from_url_request(activation, url_request)?;
+    let content_type = url_request
+        .get_public_property("contentType", activation)?
+        .coerce_to_string(activation)?
+        .to_string();
+    let file_size = data.len() as u64;
+
+    spawn_upload(activation, this, request, (data, content_type), file_size);
+    Ok(Value::Undefined)
+}
@@ -0,0 +1,32 @@
+//! `flash.net.FileReferenceList` native methods
+
+use crate::avm2::globals::flash::net::file_reference::file_filters;
+use crate::avm2::{Activation, Error, Object, Value};
+
+/// Implements `FileReferenceList.browse`
+pub fn browse<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let filters = file_filters(activation, args[0])?;
+
+    let dialog = activation
+        .context
+        .ui
+        .display_file_open_multiple_dialog(filters);
+    let result = match dialog {
+        Some(dialog) => {
+            let process = activation
+                .context
+                .load_manager
+                .select_multiple_files_dialog_avm2(activation.context.player.clone(), this, dialog);
+
+            activation.context.navigator.spawn_future(process);
+            true
+        }
+        None => false,
+    };
+
+    Ok(result.into())
+}
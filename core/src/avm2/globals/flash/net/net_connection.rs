@@ -36,12 +36,39 @@ pub fn connect<'gc>(
     {
         // HTTP(S) is for Flash Remoting, which is just POST requests to the URL.
         NetConnections::connect_to_flash_remoting(activation.context, connection, url.to_string());
+    } else if url.starts_with(WStr::from_units(b"rtmp://")) {
+        // Any extra arguments are sent to the server along with the connect command.
+        let mut arguments = Vec::new();
+        let mut object_table = FnvHashMap::default();
+        for arg in args.iter().skip(1) {
+            if let Some(value) =
+                serialize_value(activation, *arg, AMFVersion::AMF0, &mut object_table)
+            {
+                arguments.push(value);
+            }
+        }
+
+        let object_encoding = match this
+            .get_public_property("objectEncoding", activation)?
+            .coerce_to_u32(activation)?
+        {
+            0 => AMFVersion::AMF0,
+            _ => AMFVersion::AMF3,
+        };
+
+        NetConnections::connect_to_rtmp(
+            activation.context,
+            connection,
+            url.to_string(),
+            object_encoding,
+            arguments,
+        );
     } else {
         avm2_stub_method!(
             activation,
             "flash.net.NetConnection",
             "connect",
-            "with non-null, non-http command"
+            "with non-null, non-http, non-rtmp command"
         );
     }
 
@@ -4,6 +4,59 @@ use crate::avm2::{Activation, Error, Object, TObject, Value};
 
 pub use crate::avm2::object::netstream_allocator as net_stream_allocator;
 
+pub fn init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(ns) = this.as_netstream() {
+        let connection = args
+            .try_get_object(activation, 0)
+            .and_then(|o| o.as_net_connection())
+            .map(|c| c.into());
+
+        ns.set_connection(activation.context.gc_context, connection);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(ns) = this.as_netstream() {
+        ns.close(activation.context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn publish<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(ns) = this.as_netstream() {
+        let name = args.try_get_string(activation, 0)?;
+        let publish_type = args.try_get_string(activation, 1)?;
+
+        if let Some(name) = name {
+            ns.publish(
+                activation.context,
+                name.to_string(),
+                publish_type.map_or_else(|| "live".to_string(), |t| t.to_string()),
+            );
+        } else {
+            // Publishing `null` stops publishing.
+            ns.close(activation.context);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn get_bytes_loaded<'gc>(
     _activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
@@ -7,6 +7,7 @@ use crate::avm2::parameters::ParametersExt;
 use crate::avm2::string::AvmString;
 use crate::avm2::{Activation, Error, Object, TObject, Value};
 use crate::context::UpdateContext;
+use crate::socket::Sockets;
 use encoding_rs::Encoding;
 use encoding_rs::UTF_8;
 use flash_lso::amf0::read::AMF0Decoder;
@@ -41,11 +42,12 @@ pub fn connect<'gc>(
         .try_into()
         .map_err(|_| invalid_port_number(activation))?;
 
-    let UpdateContext {
-        sockets, navigator, ..
-    } = activation.context;
-
-    sockets.connect_avm2(*navigator, socket, host.to_utf8_lossy().into_owned(), port);
+    Sockets::connect_avm2(
+        activation.context,
+        socket,
+        host.to_utf8_lossy().into_owned(),
+        port,
+    );
 
     Ok(Value::Undefined)
 }
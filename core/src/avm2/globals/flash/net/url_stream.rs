@@ -0,0 +1,73 @@
+//! `flash.net.URLStream` native function definitions
+
+use crate::avm2::activation::Activation;
+use crate::avm2::globals::flash::display::loader::request_from_url_request;
+use crate::avm2::object::TObject;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::{Error, Multiname, Object};
+
+/// Implements `URLStream.startLoad`
+pub fn start_load<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let request = args.get_object(activation, 0, "request")?;
+    let request = request_from_url_request(activation, request)?;
+
+    let future = activation.context.load_manager.load_url_stream(
+        activation.context.player.clone(),
+        this,
+        request,
+    );
+    activation.context.navigator.spawn_future(future);
+    Ok(Value::Undefined)
+}
+
+/// Implements `URLStream.cancelLoad`
+pub fn cancel_load<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation.context.load_manager.cancel_url_stream(this);
+    Ok(Value::Undefined)
+}
+
+/// Append downloaded bytes to the buffer of a `URLStream`.
+///
+/// Bytes that have already been read are discarded, so that the buffer of a
+/// long-lived stream only holds unread data.
+pub fn append_data<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    stream: Object<'gc>,
+    data: &[u8],
+) -> Result<(), Error<'gc>> {
+    let buffer = stream
+        .get_property(
+            &Multiname::new(activation.avm2().flash_net_internal, "_buffer"),
+            activation,
+        )?
+        .as_object()
+        .ok_or("URLStream buffer is not set")?;
+    let mut storage = buffer
+        .as_bytearray_mut()
+        .ok_or("URLStream buffer is not a ByteArray")?;
+
+    let position = storage.position();
+    if position > 0 {
+        let unread = storage.bytes().get(position..).unwrap_or_default().to_vec();
+        storage.clear();
+        storage
+            .write_bytes(&unread)
+            .map_err(|e| e.to_avm(activation))?;
+        storage.set_position(0);
+    }
+
+    let len = storage.len();
+    storage
+        .write_at(data, len)
+        .map_err(|e| e.to_avm(activation))?;
+    Ok(())
+}
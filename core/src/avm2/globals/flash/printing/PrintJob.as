@@ -1,6 +1,101 @@
 package flash.printing {
+    import flash.display.BitmapData;
+    import flash.display.Sprite;
     import flash.events.EventDispatcher;
+    import flash.geom.Rectangle;
+    import __ruffle__.stub_method;
 
     public class PrintJob extends EventDispatcher {
+        // These must match the paper sizes in `core/src/printing.rs`.
+        private static const PAPER_WIDTH:int = 612;
+        private static const PAPER_HEIGHT:int = 792;
+        private static const PAGE_MARGIN:int = 18;
+
+        // Only one print job can be in progress at a time.
+        private static var _jobInProgress:Boolean = false;
+
+        private var _started:Boolean = false;
+        private var _pages:Array = [];
+
+        public function PrintJob() {
+        }
+
+        [API("667")]
+        public static function get isSupported():Boolean {
+            return canPrint();
+        }
+
+        public function get orientation():String {
+            return _started ? PrintJobOrientation.PORTRAIT : null;
+        }
+
+        public function get paperWidth():int {
+            return _started ? PAPER_WIDTH : 0;
+        }
+
+        public function get paperHeight():int {
+            return _started ? PAPER_HEIGHT : 0;
+        }
+
+        public function get pageWidth():int {
+            return _started ? PAPER_WIDTH - 2 * PAGE_MARGIN : 0;
+        }
+
+        public function get pageHeight():int {
+            return _started ? PAPER_HEIGHT - 2 * PAGE_MARGIN : 0;
+        }
+
+        public function start():Boolean {
+            if (_started || _jobInProgress || !canPrint()) {
+                return false;
+            }
+            _started = true;
+            _jobInProgress = true;
+            return true;
+        }
+
+        public function addPage(sprite:Sprite, printArea:Rectangle = null, options:PrintJobOptions = null, frameNum:int = 0):void {
+            if (!_started) {
+                throw new Error("Error #2057: The page could not be added to the print job.", 2057);
+            }
+            if (frameNum != 0) {
+                stub_method("flash.printing.PrintJob", "addPage", "with frameNum");
+            }
+            // Pages are always printed as bitmaps, so `options.printAsBitmap` has no effect.
+            var page:BitmapData = renderPage(sprite, printArea);
+            if (page == null) {
+                throw new Error("Error #2057: The page could not be added to the print job.", 2057);
+            }
+            _pages.push(page);
+        }
+
+        public function send():void {
+            if (!_started) {
+                return;
+            }
+            sendPages(_pages);
+            finish();
+        }
+
+        public function terminate():void {
+            finish();
+        }
+
+        private function finish():void {
+            for each (var page:BitmapData in _pages) {
+                page.dispose();
+            }
+            _pages = [];
+            if (_started) {
+                _started = false;
+                _jobInProgress = false;
+            }
+        }
+
+        private static native function canPrint():Boolean;
+
+        private native function renderPage(sprite:Sprite, printArea:Rectangle):BitmapData;
+
+        private native function sendPages(pages:Array):void;
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,66 @@
+//! `flash.printing.PrintJob` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::globals::flash::display::display_object::object_to_rectangle;
+use crate::avm2::object::{BitmapDataObject, Object, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::printing;
+
+/// Implements `PrintJob.canPrint`
+pub fn can_print<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation.context.ui.can_print().into())
+}
+
+/// Implements `PrintJob.renderPage`
+pub fn render_page<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let sprite = args.get_object(activation, 0, "sprite")?;
+    let Some(target) = sprite.as_display_object() else {
+        return Ok(Value::Null);
+    };
+
+    let print_area = match args.try_get_object(activation, 1) {
+        Some(rectangle) => Some(object_to_rectangle(activation, rectangle)?),
+        None => None,
+    };
+
+    let Some(page) = printing::render_page(activation.context, target, print_area) else {
+        return Ok(Value::Null);
+    };
+
+    let bitmapdata_class = activation.avm2().classes().bitmapdata;
+    Ok(BitmapDataObject::from_bitmap_data_internal(activation, page, bitmapdata_class)?.into())
+}
+
+/// Implements `PrintJob.sendPages`
+pub fn send_pages<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let pages = args.get_object(activation, 0, "pages")?;
+
+    let mut print_pages = Vec::new();
+    if let Some(array_storage) = pages.as_array_storage() {
+        for page in array_storage.iter().flatten() {
+            if let Some(bitmap_data) = page.as_object().and_then(|o| o.as_bitmap_data()) {
+                print_pages.push(printing::to_print_page(
+                    activation.context.renderer,
+                    bitmap_data,
+                ));
+            }
+        }
+    }
+
+    activation.context.ui.print(print_pages);
+    Ok(Value::Undefined)
+}
@@ -0,0 +1,329 @@
+//! `flash.sampler` namespace
+
+use crate::avm2::activation::Activation;
+use crate::avm2::object::{ArrayObject, Object, QNameObject, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::property::Property;
+use crate::avm2::sampler::{value_size, Sample, SampleKind};
+use crate::avm2::value::Value;
+use crate::avm2::vtable::VTable;
+use crate::avm2::{ArrayStorage, Error, Multiname};
+use crate::string::AvmString;
+
+pub mod new_object_sample;
+
+/// Implements `flash.sampler.startSampling`
+pub fn start_sampling<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation.avm2().sampler_mut().start();
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.sampler.pauseSampling`
+pub fn pause_sampling<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation.avm2().sampler_mut().pause();
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.sampler.stopSampling`
+pub fn stop_sampling<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation.avm2().sampler_mut().stop();
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.sampler.clearSamples`
+pub fn clear_samples<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation.avm2().sampler_mut().clear_samples();
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.sampler.setSamplerCallback`
+pub fn set_sampler_callback<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let callback = args.try_get_object(activation, 0);
+    activation.avm2().sampler_mut().set_callback(callback);
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.sampler.getSampleCount`
+pub fn get_sample_count<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mc = activation.context.gc_context;
+    let sampler = activation.avm2().sampler_mut();
+    sampler.sweep(mc);
+    Ok((sampler.samples().len() as f64).into())
+}
+
+/// Implements `flash.sampler.getSamples`
+pub fn get_samples<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mc = activation.context.gc_context;
+    let sampler = activation.avm2().sampler_mut();
+    sampler.sweep(mc);
+    let samples = sampler.samples().to_vec();
+
+    // Don't sample the objects we create to describe the samples.
+    let was_sampling = sampler.is_sampling();
+    sampler.pause();
+    let result = samples
+        .iter()
+        .map(|sample| sample_to_object(activation, sample).map(Value::from))
+        .collect::<Result<Vec<_>, _>>();
+    if was_sampling {
+        activation.avm2().sampler_mut().start();
+    }
+
+    let array = ArrayObject::from_storage(activation, ArrayStorage::from_args(&result?))?;
+    Ok(array.into())
+}
+
+fn sample_to_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    sample: &Sample<'gc>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    let stack = if sample.stack.is_empty() {
+        Value::Null
+    } else {
+        let stack_frame_class = activation.avm2().classes().stackframe;
+        let frames = sample
+            .stack
+            .iter()
+            .map(|name| {
+                let name = AvmString::new(activation.context.gc_context, name.clone());
+                stack_frame_class
+                    .construct(activation, &[name.into()])
+                    .map(Value::from)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        ArrayObject::from_storage(activation, ArrayStorage::from_args(&frames))?.into()
+    };
+
+    match sample.kind {
+        SampleKind::Cpu => {
+            let sample_class = activation.avm2().classes().sample;
+            sample_class.construct(activation, &[sample.time.into(), stack])
+        }
+        SampleKind::NewObject { id, class, size } => {
+            let new_object_sample_class = activation.avm2().classes().newobjectsample;
+            new_object_sample_class.construct(
+                activation,
+                &[
+                    sample.time.into(),
+                    stack,
+                    id.into(),
+                    class.map_or(Value::Null, Value::from),
+                    size.into(),
+                ],
+            )
+        }
+        SampleKind::DeleteObject { id, size } => {
+            let delete_object_sample_class = activation.avm2().classes().deleteobjectsample;
+            delete_object_sample_class.construct(
+                activation,
+                &[sample.time.into(), stack, id.into(), size.into()],
+            )
+        }
+    }
+}
+
+/// Implements `flash.sampler.getSize`
+pub fn get_size<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(value_size(args.get_value(0)).into())
+}
+
+/// Looks up the trait named by the `QName` argument at `index` on `object`.
+fn lookup_trait<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+    args: &[Value<'gc>],
+    index: usize,
+) -> Option<(VTable<'gc>, Property)> {
+    let name = args
+        .try_get_object(activation, index)
+        .and_then(|name| name.as_qname_object())?;
+    let vtable = object.vtable();
+    let property = vtable.get_trait(&name.name())?;
+    Some((vtable, property))
+}
+
+/// Implements `flash.sampler.getInvocationCount`
+pub fn get_invocation_count<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let object = args.get_object(activation, 0, "obj")?;
+
+    let method = if matches!(args.get_value(1), Value::Null | Value::Undefined) {
+        // Without a name, a class counts how many times its constructor was called.
+        object
+            .as_class_object()
+            .map(|class| class.inner_class_definition().instance_init())
+    } else {
+        match lookup_trait(activation, object, args, 1) {
+            Some((vtable, Property::Method { disp_id })) => vtable.get_method(disp_id),
+            _ => None,
+        }
+    };
+
+    let count = match method {
+        Some(method) => activation.avm2().sampler().invocation_count(method) as f64,
+        None => -1.0,
+    };
+    Ok(count.into())
+}
+
+/// Implements `flash.sampler.getGetterInvocationCount`
+pub fn get_getter_invocation_count<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let object = args.get_object(activation, 0, "obj")?;
+    let method = match lookup_trait(activation, object, args, 1) {
+        Some((vtable, Property::Virtual { get: Some(get), .. })) => vtable.get_method(get),
+        _ => None,
+    };
+
+    let count = match method {
+        Some(method) => activation.avm2().sampler().invocation_count(method) as f64,
+        None => -1.0,
+    };
+    Ok(count.into())
+}
+
+/// Implements `flash.sampler.getSetterInvocationCount`
+pub fn get_setter_invocation_count<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let object = args.get_object(activation, 0, "obj")?;
+    let method = match lookup_trait(activation, object, args, 1) {
+        Some((vtable, Property::Virtual { set: Some(set), .. })) => vtable.get_method(set),
+        _ => None,
+    };
+
+    let count = match method {
+        Some(method) => activation.avm2().sampler().invocation_count(method) as f64,
+        None => -1.0,
+    };
+    Ok(count.into())
+}
+
+/// Implements `flash.sampler.isGetterSetter`
+pub fn is_getter_setter<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let object = args.get_object(activation, 0, "obj")?;
+    let is_getter_setter = matches!(
+        lookup_trait(activation, object, args, 1),
+        Some((_, Property::Virtual { .. }))
+    );
+    Ok(is_getter_setter.into())
+}
+
+/// Implements `flash.sampler.getMemberNames`
+pub fn get_member_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let object = args.get_object(activation, 0, "obj")?;
+    let instance_names = args.get_bool(1);
+
+    let vtable = match object.as_class_object() {
+        Some(class) if instance_names => class.instance_vtable(),
+        _ => object.vtable(),
+    };
+    let names: Vec<_> = vtable
+        .resolved_traits()
+        .iter()
+        .map(|(name, namespace, _)| Multiname::new(namespace, name))
+        .collect();
+
+    let names = names
+        .into_iter()
+        .map(|name| QNameObject::from_name(activation, name).map(Value::from))
+        .collect::<Result<Vec<_>, _>>()?;
+    let array = ArrayObject::from_storage(activation, ArrayStorage::from_args(&names))?;
+    Ok(array.into())
+}
+
+/// Implements `flash.sampler.getLexicalScopes`
+pub fn get_lexical_scopes<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let function = args.get_object(activation, 0, "fun")?;
+    let Some(scope) = function.as_executable().map(|exec| exec.scope()) else {
+        return Ok(Value::Null);
+    };
+
+    let scopes: Vec<Value<'gc>> = (0..)
+        .map_while(|index| scope.get(index))
+        .map(|scope| scope.values().into())
+        .collect();
+    let array = ArrayObject::from_storage(activation, ArrayStorage::from_args(&scopes))?;
+    Ok(array.into())
+}
+
+/// Implements `flash.sampler.getSavedThis`
+pub fn get_saved_this<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let function = args.get_object(activation, 0, "fun")?;
+    let saved_this = function
+        .as_executable()
+        .and_then(|exec| exec.bound_receiver());
+    Ok(saved_this.map_or(Value::Null, Value::from))
+}
+
+/// Implements `flash.sampler.getMasterString`
+pub fn get_master_string<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // Substrings and concatenations can share the buffer of another string,
+    // which is what Flash calls the master string.
+    let string = args.get_string(activation, 0)?;
+    let master = string
+        .as_managed()
+        .and_then(|repr| repr.owner())
+        .map(AvmString::from);
+    Ok(master.map_or(Value::Null, Value::from))
+}
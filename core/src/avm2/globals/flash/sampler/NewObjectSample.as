@@ -1,19 +1,23 @@
 package flash.sampler {
-    import __ruffle__.stub_getter;
-
     public final class NewObjectSample extends Sample {
         public const id:Number;
      
         public const type:Class;
 
-        public function get object():* {
-            stub_getter("flash.sampler.NewObjectSample", "object");
-            return {};
+        private var _size:Number;
+
+        function NewObjectSample(time:Number = 0, stack:Array = null, id:Number = 0, type:Class = null, size:Number = 0) {
+            super(time, stack);
+            this.id = id;
+            this.type = type;
+            this._size = size;
         }
 
+        // Returns the sampled object, or `undefined` if it has been garbage collected.
+        public native function get object():*;
+
         public function get size():Number {
-            stub_getter("flash.sampler.NewObjectSample", "size");
-            return 0;
+            return this._size;
         }
     }
 }
@@ -7,6 +7,13 @@ package flash.sampler {
         public const line:uint;
    
         public const scriptID:Number;
+
+        function StackFrame(name:String = null, file:String = null, line:uint = 0, scriptID:Number = 0) {
+            this.name = name;
+            this.file = file;
+            this.line = line;
+            this.scriptID = scriptID;
+        }
       
         public function toString():String {
             if (this.file) {
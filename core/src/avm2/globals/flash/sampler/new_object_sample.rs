@@ -0,0 +1,22 @@
+//! `flash.sampler.NewObjectSample` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+
+/// Implements `NewObjectSample.object`'s getter
+pub fn get_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let id = this
+        .get_public_property("id", activation)?
+        .coerce_to_u32(activation)?;
+    let object = activation
+        .avm2()
+        .sampler()
+        .live_object(activation.context.gc_context, id);
+    Ok(object.map_or(Value::Undefined, Value::from))
+}
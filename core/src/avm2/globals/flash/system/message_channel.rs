@@ -0,0 +1,146 @@
+//! `flash.system.MessageChannel` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::error::io_error;
+use crate::avm2::object::Object;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::worker::{from_worker_value, to_worker_value, ChannelId, SharedRef, WorkerError};
+use crate::avm2::Error;
+use crate::avm2_stub_method;
+
+pub fn native_instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation.super_init(this, &[])?;
+    Ok(Value::Undefined)
+}
+
+fn channel_id<'gc>(activation: &mut Activation<'_, 'gc>, this: Object<'gc>) -> Option<ChannelId> {
+    match activation.avm2().worker_objects.shared_ref(this)? {
+        SharedRef::MessageChannel(id) => Some(id),
+        _ => None,
+    }
+}
+
+fn make_closed_error<'gc>(activation: &mut Activation<'_, 'gc>, error: WorkerError) -> Error<'gc> {
+    match io_error(activation, &error.to_string(), 0) {
+        Ok(err) => Error::AvmError(err),
+        Err(err) => err,
+    }
+}
+
+/// Implements `MessageChannel.messageAvailable`
+pub fn get_message_available<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(id) = channel_id(activation, this) else {
+        return Ok(false.into());
+    };
+    let queue_len = activation
+        .context
+        .worker_group
+        .borrow()
+        .channel_queue_len(id);
+    Ok((queue_len > 0).into())
+}
+
+/// Implements `MessageChannel.state`
+pub fn get_state<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(id) = channel_id(activation, this) else {
+        return Ok(Value::Undefined);
+    };
+    let state = activation.context.worker_group.borrow().channel_state(id);
+    Ok(state.as_str().into())
+}
+
+/// Implements `MessageChannel.close`
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(id) = channel_id(activation, this) {
+        activation
+            .context
+            .worker_group
+            .borrow_mut()
+            .close_channel(id);
+    }
+    Ok(Value::Undefined)
+}
+
+/// Implements `MessageChannel.receive`
+pub fn receive<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(id) = channel_id(activation, this) else {
+        return Ok(Value::Null);
+    };
+    let block_until_received = args.get_bool(0);
+
+    let message = activation.context.worker_group.borrow_mut().receive(id);
+    match message {
+        Ok(Some(message)) => from_worker_value(activation, &message),
+        Ok(None) => {
+            if block_until_received {
+                // The sending worker can't run while this one is blocked.
+                avm2_stub_method!(
+                    activation,
+                    "flash.system.MessageChannel",
+                    "receive",
+                    "with blockUntilReceived and no message available"
+                );
+            }
+            Ok(Value::Null)
+        }
+        Err(e) => Err(make_closed_error(activation, e)),
+    }
+}
+
+/// Implements `MessageChannel.send`
+pub fn send<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(id) = channel_id(activation, this) else {
+        return Ok(Value::Undefined);
+    };
+    let queue_limit = args.get_i32(activation, 1)?;
+
+    let queue_len = activation
+        .context
+        .worker_group
+        .borrow()
+        .channel_queue_len(id);
+    if queue_limit >= 0 && queue_len >= queue_limit as usize {
+        // The receiving worker can't run while this one is blocked, so the
+        // message is queued anyway.
+        avm2_stub_method!(
+            activation,
+            "flash.system.MessageChannel",
+            "send",
+            "with a full queue"
+        );
+    }
+
+    let message = to_worker_value(activation, args.get_value(0))?;
+    let result = activation
+        .context
+        .worker_group
+        .borrow_mut()
+        .send(id, message);
+    result.map_err(|e| make_closed_error(activation, e))?;
+    Ok(Value::Undefined)
+}
@@ -0,0 +1,151 @@
+//! `flash.system.Worker` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::object::Object;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::worker::{
+    from_worker_value, shared_object, to_worker_value, SharedRef, WorkerId, PRIMORDIAL_WORKER,
+};
+use crate::avm2::Error;
+
+pub fn native_instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation.super_init(this, &[])?;
+    Ok(Value::Undefined)
+}
+
+fn worker_id<'gc>(activation: &mut Activation<'_, 'gc>, this: Object<'gc>) -> Option<WorkerId> {
+    match activation.avm2().worker_objects.shared_ref(this)? {
+        SharedRef::Worker(id) => Some(id),
+        _ => None,
+    }
+}
+
+/// Implements `Worker.current`
+pub fn get_current<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let id = activation.context.worker_id;
+    Ok(shared_object(activation, SharedRef::Worker(id))?.into())
+}
+
+/// Implements `Worker.isPrimordial`
+pub fn get_is_primordial<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok((worker_id(activation, this) == Some(PRIMORDIAL_WORKER)).into())
+}
+
+/// Implements `Worker.state`
+pub fn get_state<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(id) = worker_id(activation, this) else {
+        return Ok(Value::Undefined);
+    };
+    let state = activation.context.worker_group.borrow().worker_state(id);
+    Ok(state.as_str().into())
+}
+
+/// Implements `Worker.start`
+pub fn start<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(id) = worker_id(activation, this) {
+        activation
+            .context
+            .worker_group
+            .borrow_mut()
+            .start_worker(id);
+    }
+    Ok(Value::Undefined)
+}
+
+/// Implements `Worker.terminate`
+pub fn terminate<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(id) = worker_id(activation, this) else {
+        return Ok(false.into());
+    };
+    let was_running = activation
+        .context
+        .worker_group
+        .borrow_mut()
+        .terminate_worker(id);
+    Ok(was_running.into())
+}
+
+/// Implements `Worker.getSharedProperty`
+pub fn get_shared_property<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(id) = worker_id(activation, this) else {
+        return Ok(Value::Undefined);
+    };
+    let key = args.get_string(activation, 0)?.to_utf8_lossy().into_owned();
+    let value = activation
+        .context
+        .worker_group
+        .borrow()
+        .shared_property(id, &key);
+    match value {
+        Some(value) => from_worker_value(activation, &value),
+        None => Ok(Value::Undefined),
+    }
+}
+
+/// Implements `Worker.setSharedProperty`
+pub fn set_shared_property<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(id) = worker_id(activation, this) else {
+        return Ok(Value::Undefined);
+    };
+    let key = args.get_string(activation, 0)?.to_utf8_lossy().into_owned();
+    let value = to_worker_value(activation, args.get_value(1))?;
+    activation
+        .context
+        .worker_group
+        .borrow_mut()
+        .set_shared_property(id, key, value);
+    Ok(Value::Undefined)
+}
+
+/// Implements `Worker.createMessageChannel`
+pub fn create_message_channel<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let receiver = args.get_object(activation, 0, "receiver")?;
+    let (Some(sender), Some(receiver)) =
+        (worker_id(activation, this), worker_id(activation, receiver))
+    else {
+        return Ok(Value::Null);
+    };
+    let channel = activation
+        .context
+        .worker_group
+        .borrow_mut()
+        .create_channel(sender, receiver);
+    Ok(shared_object(activation, SharedRef::MessageChannel(channel))?.into())
+}
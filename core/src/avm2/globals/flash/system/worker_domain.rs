@@ -0,0 +1,82 @@
+//! `flash.system.WorkerDomain` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::error::{make_error_2004, Error2004Type};
+use crate::avm2::object::{Object, TObject, VectorObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::vector::VectorStorage;
+use crate::avm2::worker::{construct_native, shared_object, SharedRef};
+use crate::avm2::Error;
+use crate::tag_utils::SwfMovie;
+
+pub fn native_instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation.super_init(this, &[])?;
+    Ok(Value::Undefined)
+}
+
+/// Implements `WorkerDomain.createCurrent`, which creates the object for
+/// `WorkerDomain.current`
+pub fn create_current<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let class = this
+        .as_class_object()
+        .expect("createCurrent is called on the WorkerDomain class");
+    Ok(construct_native(activation, class)?.into())
+}
+
+/// Implements `WorkerDomain.createWorker`
+pub fn create_worker<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let swf = args.get_object(activation, 0, "swf")?;
+    let Some(bytes) = swf.as_bytearray().map(|storage| storage.bytes().to_vec()) else {
+        return Err(make_error_2004(activation, Error2004Type::ArgumentError));
+    };
+
+    let url = activation.context.swf.url().to_string();
+    let movie = match SwfMovie::from_data(&bytes, url, None) {
+        Ok(movie) => movie,
+        Err(e) => {
+            tracing::error!("WorkerDomain.createWorker: invalid SWF: {}", e);
+            return Err(make_error_2004(activation, Error2004Type::ArgumentError));
+        }
+    };
+
+    let parent = activation.context.worker_id;
+    let worker = activation
+        .context
+        .worker_group
+        .borrow_mut()
+        .create_worker(parent, movie);
+    Ok(shared_object(activation, SharedRef::Worker(worker))?.into())
+}
+
+/// Implements `WorkerDomain.listWorkers`
+pub fn list_workers<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let ids = activation.context.worker_group.borrow().live_workers();
+    let workers = ids
+        .into_iter()
+        .map(|id| Ok(shared_object(activation, SharedRef::Worker(id))?.into()))
+        .collect::<Result<Vec<_>, Error<'gc>>>()?;
+
+    let storage = VectorStorage::from_values(
+        workers,
+        false,
+        Some(activation.avm2().classes().worker.inner_class_definition()),
+    );
+    Ok(VectorObject::from_vector(storage, activation)?.into())
+}
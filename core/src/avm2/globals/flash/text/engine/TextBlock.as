@@ -1,6 +1,4 @@
 package flash.text.engine {
-    import __ruffle__.stub_method;
-
     public final class TextBlock {
         public var userData;
 
@@ -16,7 +14,7 @@ package flash.text.engine {
 
         internal var _textLineCreationResult:String = null;
         internal var _firstLine:TextLine = null;
-
+        internal var _lastLine:TextLine = null;
 
         public function TextBlock(content:ContentElement = null,
                                   tabStops:Vector.<TabStop> = null,
@@ -134,22 +132,7 @@ package flash.text.engine {
 
         public native function createTextLine(previousLine:TextLine = null, width:Number = 1000000, lineOffset:Number = 0, fitSomething:Boolean = false):TextLine;
 
-        public function recreateTextLine(textLine:TextLine, previousLine:TextLine = null, width:Number = 1000000, lineOffset:Number = 0, fitSomething:Boolean = false):TextLine {
-            if (textLine == null) {
-                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
-            }
-
-            if (previousLine) {
-                return null;
-            }
-
-            stub_method("flash.text.engine.TextBlock", "recreateTextLine");
-
-            // FIXME: Properly recalculate new properties of new TextLine. Text layout
-            // modules often depend on this returning the same textLine, so we can't
-            // call `createTextLine` again.
-            return textLine;
-        }
+        public native function recreateTextLine(textLine:TextLine, previousLine:TextLine = null, width:Number = 1000000, lineOffset:Number = 0, fitSomething:Boolean = false):TextLine;
 
         public function get textLineCreationResult():String {
             return this._textLineCreationResult;
@@ -160,16 +143,40 @@ package flash.text.engine {
         }
 
         public function get lastLine():TextLine {
-            return this._firstLine;
+            return this._lastLine;
         }
-        
+
         public function releaseLines(start:TextLine, end:TextLine):void {
-            if (start != end || end != this._firstLine) {
-                stub_method("flash.text.engine.TextBlock", "releaseLines", "with start != end or multiple lines");
-                return;
+            if (start == null || end == null || start._textBlock != this || end._textBlock != this) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+
+            var before:TextLine = start._previousLine;
+            var after:TextLine = end._nextLine;
+
+            var line:TextLine = start;
+            while (line != null) {
+                var next:TextLine = line._nextLine;
+                line._validity = TextLineValidity.INVALID;
+                line._textBlock = null;
+                line._previousLine = null;
+                line._nextLine = null;
+                if (line == end) {
+                    break;
+                }
+                line = next;
+            }
+
+            if (before != null) {
+                before._nextLine = after;
+            } else {
+                this._firstLine = after;
+            }
+            if (after != null) {
+                after._previousLine = before;
+            } else {
+                this._lastLine = before;
             }
-            this._firstLine._validity = "invalid";
-            this._firstLine._textBlock = null;
         }
     }
 }
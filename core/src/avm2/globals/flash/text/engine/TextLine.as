@@ -1,8 +1,5 @@
 package flash.text.engine {
-    import __ruffle__.stub_getter;
-    import __ruffle__.stub_setter;
-    import __ruffle__.stub_method;
-
+    import flash.display.DisplayObject;
     import flash.display.DisplayObjectContainer;
     import flash.errors.IllegalOperationError;
     import flash.geom.Rectangle;
@@ -18,6 +15,10 @@ package flash.text.engine {
         internal var _textBlock:TextBlock = null;
         internal var _rawTextLength:int = 0;
         internal var _validity:String = "valid";
+        internal var _textBlockBeginIndex:int = 0;
+        internal var _unjustifiedTextWidth:Number = 0.0;
+        internal var _previousLine:TextLine = null;
+        internal var _nextLine:TextLine = null;
 
         public static const MAX_LINE_WIDTH:int = 1000000;
 
@@ -32,8 +33,7 @@ package flash.text.engine {
         }
 
         public function get textBlockBeginIndex():int {
-            stub_getter("flash.text.engine.TextLine", "textBlockBeginIndex");
-            return 0;
+            return this._textBlockBeginIndex;
         }
 
         public function get specifiedWidth():Number {
@@ -44,76 +44,62 @@ package flash.text.engine {
             return this._textBlock;
         }
 
-        public function get ascent():Number {
-            stub_getter("flash.text.engine.TextLine", "ascent");
-            return 12.0;
-        }
+        public native function get ascent():Number;
+        public native function get descent():Number;
 
-        public function get descent():Number {
-            stub_getter("flash.text.engine.TextLine", "descent");
-            return 3.0;
-        }
+        public native function get totalAscent():Number;
+        public native function get totalDescent():Number;
+        public native function get totalHeight():Number;
 
         public function get unjustifiedTextWidth():Number {
-            stub_getter("flash.text.engine.TextLine", "unjustifiedTextWidth");
-            return this._specifiedWidth;
+            return this._unjustifiedTextWidth;
         }
 
         public native function get textWidth():Number;
         public native function get textHeight():Number;
 
         public function get validity():String {
-            stub_getter("flash.text.engine.TextLine", "validity");
             return this._validity;
         }
 
         public function set validity(value:String):void {
-            stub_setter("flash.text.engine.TextLine", "validity");
+            if (value != TextLineValidity.INVALID &&
+                value != TextLineValidity.POSSIBLY_INVALID &&
+                value != TextLineValidity.STATIC &&
+                value != TextLineValidity.VALID) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+            // Lines can only be marked as valid by laying them out again.
+            if (value == TextLineValidity.VALID && this._validity != TextLineValidity.VALID) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
             this._validity = value;
         }
 
-        public function get hasGraphicElement():Boolean {
-            stub_getter("flash.text.engine.TextLine", "hasGraphicElement");
-            return false;
-        }
+        public native function get hasGraphicElement():Boolean;
 
-        public function get atomCount():int {
-            stub_getter("flash.text.engine.TextLine", "atomCount");
-            return this._rawTextLength;
-        }
+        public native function get atomCount():int;
 
         public function get nextLine():TextLine {
-            return null;
+            return this._nextLine;
         }
 
         public function get previousLine():TextLine {
-            return null;
+            return this._previousLine;
         }
 
-        public function getBaselinePosition(baseline:String):Number {
-            stub_method("flash.text.engine.TextLine", "getBaselinePosition");
-            return 0.0;
-        }
-
-        public function get hasTabs():Boolean {
-            stub_getter("flash.text.engine.TextLine", "hasTabs");
-            return false;
-        }
-
-        public function getAtomIndexAtPoint(stageX:Number, stageY:Number):int {
-            stub_method("flash.text.engine.TextLine", "getAtomIndexAtPoint");
-            return -1;
-        }
+        public native function getBaselinePosition(baseline:String):Number;
 
-        public function getAtomIndexAtCharIndex(charIndex:int):int {
-            stub_method("flash.text.engine.TextLine", "getAtomIndexAtCharIndex");
-            return -1;
-        }
+        public native function get hasTabs():Boolean;
 
-        public function getAtomBounds(index:int):Rectangle {
-            stub_method("flash.text.engine.TextLine", "getAtomBounds");
-            return new Rectangle(0, 0, 0, 0);
-        }
+        public native function getAtomIndexAtPoint(stageX:Number, stageY:Number):int;
+        public native function getAtomIndexAtCharIndex(charIndex:int):int;
+        public native function getAtomBounds(atomIndex:int):Rectangle;
+        public native function getAtomCenter(atomIndex:int):Number;
+        public native function getAtomTextBlockBeginIndex(atomIndex:int):int;
+        public native function getAtomTextBlockEndIndex(atomIndex:int):int;
+        public native function getAtomGraphic(atomIndex:int):DisplayObject;
+        public native function getAtomWordBoundaryOnLeft(atomIndex:int):Boolean;
 
         // This function does nothing in Flash Player 32
         public function flushAtomData():void { }
@@ -1,197 +1,769 @@
 use crate::avm2::activation::Activation;
-use crate::avm2::error::Error;
+use crate::avm2::error::{make_error_2004, Error, Error2004Type};
 use crate::avm2::globals::flash::display::display_object::initialize_for_allocator;
+use crate::avm2::globals::flash::text::engine::text_line;
 use crate::avm2::object::{Object, TObject};
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
 use crate::avm2::Multiname;
-use crate::avm2_stub_method;
-use crate::display_object::{EditText, TDisplayObject};
+use crate::display_object::{DisplayObject, EditText, TDisplayObject};
 use crate::html::TextFormat;
-use crate::string::WStr;
+use crate::string::{utils as string_utils, AvmString, WStr, WString};
+use std::ops::Range;
+use swf::Twips;
 
-pub fn create_text_line<'gc>(
+/// The character used in the raw text of a `GraphicElement`.
+const GRAPHIC_ELEMENT_CHAR: u16 = 0xFDEF;
+
+/// The character displayed in place of a `GraphicElement`.
+///
+/// This is a no-break space, so that lines never break around a graphic.
+const GRAPHIC_ELEMENT_DISPLAY_CHAR: u16 = 0xA0;
+
+/// A run of content sharing a single `ElementFormat`, produced by flattening
+/// the content of a `TextBlock`.
+struct ContentRun<'gc> {
+    /// The raw text of this run, as reported by `ContentElement.rawText`.
+    raw_text: WString,
+
+    /// The text that is actually laid out for this run.
+    display_text: WString,
+
+    format: Option<TextFormat>,
+
+    is_device_font: bool,
+
+    break_opportunity: BreakOpportunity,
+
+    /// The graphic of a `GraphicElement`, and its size in pixels.
+    graphic: Option<(DisplayObject<'gc>, f64, f64)>,
+}
+
+/// Where lines may break within a run, from `ElementFormat.breakOpportunity`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum BreakOpportunity {
+    /// Lines break at spaces, hyphens and around ideographs.
+    Auto,
+
+    /// Lines may break after any atom.
+    Any,
+
+    /// Lines never break within the run.
+    None,
+
+    /// Lines break after every atom.
+    All,
+}
+
+impl BreakOpportunity {
+    fn from_wstr(value: &WStr) -> Self {
+        if value == b"any" {
+            Self::Any
+        } else if value == b"none" {
+            Self::None
+        } else if value == b"all" {
+            Self::All
+        } else {
+            Self::Auto
+        }
+    }
+}
+
+/// Whether a line may or must end after an atom.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum LineBreak {
+    Prohibited,
+    Allowed,
+    Mandatory,
+}
+
+/// The content of a `TextBlock`, flattened into runs.
+struct BlockContent<'gc> {
+    runs: Vec<ContentRun<'gc>>,
+}
+
+impl<'gc> BlockContent<'gc> {
+    fn collect(
+        activation: &mut Activation<'_, 'gc>,
+        content: Object<'gc>,
+    ) -> Result<Self, Error<'gc>> {
+        let mut runs = Vec::new();
+        collect_runs(activation, content, None, &mut runs)?;
+        Ok(Self { runs })
+    }
+
+    fn len(&self) -> usize {
+        self.runs.iter().map(|run| run.raw_text.len()).sum()
+    }
+
+    /// Iterate over the runs that overlap `start..end`.
+    ///
+    /// This yields the overlapping range relative to `start`, the offset of
+    /// that range within the run, and the run itself.
+    fn runs_in(
+        &self,
+        start: usize,
+        end: usize,
+    ) -> impl Iterator<Item = (usize, usize, usize, &ContentRun<'gc>)> + '_ {
+        let mut run_start = 0;
+        self.runs.iter().filter_map(move |run| {
+            let run_end = run_start + run.raw_text.len();
+            let (overlap_start, overlap_end) = (run_start.max(start), run_end.min(end));
+            let offset = overlap_start.saturating_sub(run_start);
+            run_start = run_end;
+            (overlap_start < overlap_end).then_some((
+                overlap_start - start,
+                overlap_end - start,
+                offset,
+                run,
+            ))
+        })
+    }
+
+    fn display_text(&self, start: usize, end: usize) -> WString {
+        let mut text = WString::new();
+        for (line_start, line_end, offset, run) in self.runs_in(start, end) {
+            text.push_str(&run.display_text[offset..offset + line_end - line_start]);
+        }
+        text
+    }
+
+    fn break_opportunity_at(&self, index: usize) -> BreakOpportunity {
+        self.runs_in(index, index + 1)
+            .next()
+            .map_or(BreakOpportunity::Auto, |(_, _, _, run)| {
+                run.break_opportunity
+            })
+    }
+
+    /// Whether a line may end after the atom at `atom` of the text starting
+    /// at `begin`, which is followed by `next` unless it ends the text.
+    fn line_break_after(
+        &self,
+        text: &WStr,
+        begin: usize,
+        atom: &Range<usize>,
+        next: Option<&Range<usize>>,
+    ) -> LineBreak {
+        let last = text.at(atom.end - 1);
+        if matches!(last, 0x0A | 0x0D | 0x2028 | 0x2029) {
+            return LineBreak::Mandatory;
+        }
+        let Some(next) = next else {
+            return LineBreak::Allowed;
+        };
+
+        match self.break_opportunity_at(begin + atom.start) {
+            BreakOpportunity::All => LineBreak::Mandatory,
+            BreakOpportunity::Any => LineBreak::Allowed,
+            BreakOpportunity::None => LineBreak::Prohibited,
+            BreakOpportunity::Auto => {
+                // Spaces hang at the end of a line, so lines break after the last one.
+                let next_first = text.at(next.start);
+                let breaks = if is_space(last) {
+                    !is_space(next_first)
+                } else if is_space(next_first) {
+                    false
+                } else {
+                    last == b'-' as u16
+                        || last == 0x2010
+                        || is_ideographic(last)
+                        || is_ideographic(next_first)
+                };
+                if breaks {
+                    LineBreak::Allowed
+                } else {
+                    LineBreak::Prohibited
+                }
+            }
+        }
+    }
+}
+
+fn is_space(c: u16) -> bool {
+    matches!(c, 0x09 | 0x20 | 0x3000)
+}
+
+/// Whether a character is an ideograph, or a similar character that lines
+/// may break before or after.
+fn is_ideographic(c: u16) -> bool {
+    matches!(c,
+        0x3040..=0x30FF // Hiragana, Katakana
+        | 0x3400..=0x4DBF // CJK Unified Ideographs Extension A
+        | 0x4E00..=0x9FFF // CJK Unified Ideographs
+        | 0xAC00..=0xD7AF // Hangul Syllables
+        | 0xF900..=0xFAFF // CJK Compatibility Ideographs
+    )
+}
+
+fn collect_runs<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    this: Object<'gc>,
-    args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.text.TextBlock", "createTextLine");
+    element: Object<'gc>,
+    parent_format: Option<Object<'gc>>,
+    runs: &mut Vec<ContentRun<'gc>>,
+) -> Result<(), Error<'gc>> {
+    let element_format = element
+        .get_public_property("elementFormat", activation)?
+        .as_object()
+        .or(parent_format);
 
-    let previous_text_line = args.try_get_object(activation, 0);
-    let width = args.get_f64(activation, 1)?;
+    let group_element = activation
+        .avm2()
+        .classes()
+        .groupelement
+        .inner_class_definition();
+    let graphic_element = activation
+        .avm2()
+        .classes()
+        .graphicelement
+        .inner_class_definition();
 
-    let content = this.get_public_property("content", activation)?;
+    if element.is_of_type(group_element) {
+        let elements = element
+            .get_property(
+                &Multiname::new(activation.avm2().flash_text_engine_internal, "_elements"),
+                activation,
+            )?
+            .as_object();
+        let elements: Vec<_> = elements
+            .and_then(|elements| {
+                elements
+                    .as_vector_storage()
+                    .map(|storage| storage.iter().collect())
+            })
+            .unwrap_or_default();
 
-    let content = if matches!(content, Value::Null) {
-        return Ok(Value::Null);
+        for child in elements {
+            if let Some(child) = child.as_object() {
+                collect_runs(activation, child, element_format, runs)?;
+            }
+        }
+        return Ok(());
+    }
+
+    let graphic = if element.is_of_type(graphic_element) {
+        let graphic = element
+            .get_public_property("graphic", activation)?
+            .as_object()
+            .and_then(|graphic| graphic.as_display_object());
+        let width = element
+            .get_public_property("elementWidth", activation)?
+            .coerce_to_number(activation)?;
+        let height = element
+            .get_public_property("elementHeight", activation)?
+            .coerce_to_number(activation)?;
+        graphic.map(|graphic| (graphic, width, height))
     } else {
-        content.as_object().unwrap()
+        None
     };
 
-    let text = match previous_text_line {
-        Some(_) => {
-            // Some SWFs rely on eventually getting `null` from createLineText.
-            // TODO: Support multiple lines
-            this.set_property(
-                &Multiname::new(
-                    activation.avm2().flash_text_engine_internal,
-                    "_textLineCreationResult",
-                ),
-                "complete".into(),
-                activation,
-            )?;
-            return Ok(Value::Null);
-        }
-        // Get the content element's text property.
-        // TODO: GraphicElement?
-        None => {
-            let txt = content
-                .get_public_property("text", activation)
-                .unwrap_or("".into());
-
-            if matches!(txt, Value::Null) {
-                // FP returns a null TextLine when `o` is null- note that
-                // `o` is already coerced to a String because of the AS bindings.
-                return Ok(Value::Null);
-            } else {
-                txt.coerce_to_string(activation)
-                    .expect("Guaranteed by AS bindings")
-            }
+    let raw_text = match element.get_public_property("rawText", activation)? {
+        Value::Null | Value::Undefined => WString::new(),
+        text => text.coerce_to_string(activation)?.as_wstr().into(),
+    };
+
+    let (format, is_device_font, typographic_case) = match element_format {
+        Some(element_format) => {
+            let (format, is_device_font, typographic_case) =
+                element_format_to_text_format(activation, element_format)?;
+            (Some(format), is_device_font, Some(typographic_case))
         }
+        None => (None, true, None),
     };
 
-    let class = activation.avm2().classes().textline;
-    let movie = activation.caller_movie_or_root();
+    let break_opportunity = match element_format {
+        Some(element_format) => BreakOpportunity::from_wstr(
+            &element_format
+                .get_public_property("breakOpportunity", activation)?
+                .coerce_to_string(activation)?,
+        ),
+        None => BreakOpportunity::Auto,
+    };
 
-    // FIXME: TextLine should be its own DisplayObject
-    let display_object: EditText =
-        EditText::new_tlf(activation.context, movie, 0.0, 0.0, width, 15.0);
+    let uppercase = typographic_case.is_some_and(|case| &case == b"uppercase" || &case == b"caps");
+    let lowercase = typographic_case.is_some_and(|case| &case == b"lowercase");
+    let display_text: WString = raw_text
+        .iter()
+        .map(|c| match c {
+            GRAPHIC_ELEMENT_CHAR => GRAPHIC_ELEMENT_DISPLAY_CHAR,
+            c if uppercase => string_utils::swf_to_uppercase(c),
+            c if lowercase => string_utils::swf_to_lowercase(c),
+            c => c,
+        })
+        .collect();
 
-    display_object.set_text(text.as_wstr(), activation.context);
+    let format = match (&graphic, format) {
+        // The graphic is given room by spacing out the character standing in for it.
+        (Some((_, width, _)), format) => Some(TextFormat {
+            letter_spacing: Some(*width),
+            ..format.unwrap_or_default()
+        }),
+        (None, format) => format,
+    };
 
-    // FIXME: This needs to use `intrinsic_bounds` to measure the width
-    // of the provided text, and set the width of the EditText to that.
-    // Some games depend on this (e.g. Realm Grinder).
+    runs.push(ContentRun {
+        raw_text,
+        display_text,
+        format,
+        is_device_font,
+        break_opportunity,
+        graphic,
+    });
 
-    let element_format = content
-        .get_public_property("elementFormat", activation)?
-        .as_object();
+    Ok(())
+}
 
-    apply_format(activation, display_object, text.as_wstr(), element_format)?;
+/// Convert an `ElementFormat` to the `TextFormat` used to lay out its text.
+///
+/// This also returns whether the format uses device fonts, and its
+/// typographic case, which are not represented by `TextFormat`.
+fn element_format_to_text_format<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    element_format: Object<'gc>,
+) -> Result<(TextFormat, bool, AvmString<'gc>), Error<'gc>> {
+    // TODO: Support more ElementFormat properties
+    let color = element_format
+        .get_public_property("color", activation)?
+        .coerce_to_u32(activation)?;
+    let size = element_format
+        .get_public_property("fontSize", activation)?
+        .coerce_to_number(activation)?;
+    let kerning = element_format
+        .get_public_property("kerning", activation)?
+        .coerce_to_string(activation)?;
+    let tracking_left = element_format
+        .get_public_property("trackingLeft", activation)?
+        .coerce_to_number(activation)?;
+    let tracking_right = element_format
+        .get_public_property("trackingRight", activation)?
+        .coerce_to_number(activation)?;
+    let typographic_case = element_format
+        .get_public_property("typographicCase", activation)?
+        .coerce_to_string(activation)?;
 
-    let instance = initialize_for_allocator(activation, display_object.into(), class)?;
-    class.call_native_init(instance.into(), &[], activation)?;
+    let (font, bold, italic, is_device_font) = if let Value::Object(font_description) =
+        element_format.get_public_property("fontDescription", activation)?
+    {
+        (
+            Some(
+                font_description
+                    .get_public_property("fontName", activation)?
+                    .coerce_to_string(activation)?
+                    .as_wstr()
+                    .into(),
+            ),
+            Some(
+                &font_description
+                    .get_public_property("fontWeight", activation)?
+                    .coerce_to_string(activation)?
+                    == b"bold",
+            ),
+            Some(
+                &font_description
+                    .get_public_property("fontPosture", activation)?
+                    .coerce_to_string(activation)?
+                    == b"italic",
+            ),
+            &font_description
+                .get_public_property("fontLookup", activation)?
+                .coerce_to_string(activation)?
+                == b"device",
+        )
+    } else {
+        (None, None, None, true)
+    };
 
-    instance.set_property(
-        &Multiname::new(activation.avm2().flash_text_engine_internal, "_textBlock"),
-        this.into(),
-        activation,
-    )?;
+    let format = TextFormat {
+        color: Some(swf::Color::from_rgb(color, 0xFF)),
+        size: Some(size),
+        font,
+        bold,
+        italic,
+        kerning: Some(&kerning != b"off"),
+        letter_spacing: Some(tracking_left + tracking_right),
+        leading: Some(0.0),
+        ..TextFormat::default()
+    };
 
-    instance.set_property(
-        &Multiname::new(
-            activation.avm2().flash_text_engine_internal,
-            "_specifiedWidth",
-        ),
-        args.get_value(1),
-        activation,
-    )?;
+    Ok((format, is_device_font, typographic_case))
+}
 
-    instance.set_property(
-        &Multiname::new(
-            activation.avm2().flash_text_engine_internal,
-            "_rawTextLength",
-        ),
-        text.len().into(),
+fn get_internal<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+    name: &'static str,
+) -> Result<Value<'gc>, Error<'gc>> {
+    object.get_property(
+        &Multiname::new(activation.avm2().flash_text_engine_internal, name),
         activation,
-    )?;
+    )
+}
 
-    this.set_property(
-        &Multiname::new(
-            activation.avm2().flash_text_engine_internal,
-            "_textLineCreationResult",
-        ),
-        "success".into(),
+fn set_internal<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+    name: &'static str,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    object.set_property(
+        &Multiname::new(activation.avm2().flash_text_engine_internal, name),
+        value,
         activation,
-    )?;
+    )
+}
 
-    this.set_property(
-        &Multiname::new(activation.avm2().flash_text_engine_internal, "_firstLine"),
-        instance.into(),
-        activation,
-    )?;
+/// Replace the text of `edit_text` with the content in `start..end`.
+fn fill_line<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    edit_text: EditText<'gc>,
+    content: &BlockContent<'gc>,
+    start: usize,
+    end: usize,
+) {
+    let text = content.display_text(start, end);
+    edit_text.set_text(&text, activation.context);
 
-    Ok(instance.into())
+    let mut is_device_font = true;
+    for (index, (run_start, run_end, _, run)) in content.runs_in(start, end).enumerate() {
+        if index == 0 {
+            // TODO: Device fonts can't be enabled per run.
+            is_device_font = run.is_device_font;
+            if let Some(format) = &run.format {
+                edit_text.set_new_text_format(format.clone(), activation.context);
+            }
+        }
+        if let Some(format) = &run.format {
+            edit_text.set_text_format(run_start, run_end, format.clone(), activation.context);
+        }
+    }
+    edit_text.set_is_device_font(activation.context, is_device_font);
 }
 
-fn apply_format<'gc>(
+/// Find the length of the line starting at `begin`, given the rest of the
+/// text laid out on a single line in `edit_text`.
+///
+/// The line ends at the last break opportunity between its atoms that fits
+/// in `width`, with trailing spaces allowed to hang past it. Without such an
+/// opportunity, it ends at the last atom that fits. Returns `None` if not
+/// even the first atom fits, unless `fit_something` is set.
+fn find_line_length(
+    edit_text: EditText<'_>,
+    content: &BlockContent<'_>,
+    begin: usize,
+    width: f64,
+    fit_something: bool,
+) -> Option<usize> {
+    let text = edit_text.text();
+    let atoms = text_line::atoms(edit_text);
+    let Some(first_atom) = atoms.first() else {
+        return Some(0);
+    };
+    let max_x = Twips::from_pixels(width + EditText::INTERNAL_PADDING);
+
+    let mut line_end = None;
+    let mut last_fitting_atom = None;
+    for (index, atom) in atoms.iter().enumerate() {
+        let fits = is_space(text.at(atom.start))
+            || text_line::atom_bounds(edit_text, atom.clone()).x_max <= max_x;
+        if !fits {
+            break;
+        }
+        last_fitting_atom = Some(atom.end);
+        match content.line_break_after(&text, begin, atom, atoms.get(index + 1)) {
+            LineBreak::Mandatory => return Some(atom.end),
+            LineBreak::Allowed => line_end = Some(atom.end),
+            LineBreak::Prohibited => {}
+        }
+    }
+
+    line_end
+        .or(last_fitting_atom)
+        .or_else(|| fit_something.then_some(first_atom.end))
+}
+
+/// Spread `extra_width` across the atoms of the line held by `edit_text`.
+///
+/// Space justification widens the spaces of the line, while letter spacing
+/// (used by `EastAsianJustifier`, or `SpaceJustifier.letterSpacing`) widens
+/// every atom but the last one. Trailing spaces are left as they are.
+///
+/// As the line is rendered by an `EditText`, atoms are widened by adding
+/// letter spacing to their last character.
+fn justify_line<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    display_object: EditText<'gc>,
-    text: &WStr,
-    element_format: Option<Object<'gc>>,
-) -> Result<(), Error<'gc>> {
-    if let Some(element_format) = element_format {
-        // TODO: Support more ElementFormat properties
-        let color = element_format
-            .get_public_property("color", activation)?
-            .coerce_to_u32(activation)?;
-        let size = element_format
-            .get_public_property("fontSize", activation)?
-            .coerce_to_number(activation)?;
+    edit_text: EditText<'gc>,
+    extra_width: f64,
+    letter_spacing: bool,
+) {
+    let text = edit_text.text();
+    let mut atoms = text_line::atoms(edit_text);
+    while atoms.last().is_some_and(|atom| {
+        matches!(text.at(atom.start), 0x0A | 0x0D | 0x2028 | 0x2029)
+            || is_space(text.at(atom.start))
+    }) {
+        atoms.pop();
+    }
 
-        let (font, bold, italic, is_device_font) = if let Value::Object(font_description) =
-            element_format.get_public_property("fontDescription", activation)?
-        {
-            (
-                Some(
-                    font_description
-                        .get_public_property("fontName", activation)?
-                        .coerce_to_string(activation)?
-                        .as_wstr()
-                        .into(),
-                ),
-                Some(
-                    &font_description
-                        .get_public_property("fontWeight", activation)?
-                        .coerce_to_string(activation)?
-                        == b"bold",
-                ),
-                Some(
-                    &font_description
-                        .get_public_property("fontPosture", activation)?
-                        .coerce_to_string(activation)?
-                        == b"italic",
-                ),
-                &font_description
-                    .get_public_property("fontLookup", activation)?
-                    .coerce_to_string(activation)?
-                    == b"device",
-            )
-        } else {
-            (None, None, None, true)
-        };
+    let positions: Vec<usize> = if letter_spacing {
+        atoms
+            .iter()
+            .take(atoms.len().saturating_sub(1))
+            .map(|atom| atom.end - 1)
+            .collect()
+    } else {
+        atoms
+            .iter()
+            .filter(|atom| is_space(text.at(atom.start)))
+            .map(|atom| atom.end - 1)
+            .collect()
+    };
+    if positions.is_empty() {
+        return;
+    }
 
+    let extra_spacing = extra_width / positions.len() as f64;
+    for position in positions {
+        let letter_spacing = edit_text
+            .text_format(position, position + 1)
+            .letter_spacing
+            .unwrap_or_default();
         let format = TextFormat {
-            color: Some(swf::Color::from_rgb(color, 0xFF)),
-            size: Some(size),
-            font,
-            bold,
-            italic,
+            letter_spacing: Some(letter_spacing + extra_spacing),
             ..TextFormat::default()
         };
+        edit_text.set_text_format(position, position + 1, format, activation.context);
+    }
+}
+
+/// Lay out the next line of a `TextBlock`.
+///
+/// This creates a new `TextLine`, or reuses `existing_line` when
+/// recreating a line. Returns `None` when no line could be created, after
+/// setting the block's `textLineCreationResult` accordingly.
+fn layout_line<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    block: Object<'gc>,
+    existing_line: Option<Object<'gc>>,
+    previous_line: Option<Object<'gc>>,
+    width: f64,
+    fit_something: bool,
+) -> Result<Option<Object<'gc>>, Error<'gc>> {
+    let Some(content) = block
+        .get_public_property("content", activation)?
+        .as_object()
+    else {
+        return Ok(None);
+    };
+    let content = BlockContent::collect(activation, content)?;
+    let text_len = content.len();
 
-        display_object.set_is_device_font(activation.context, is_device_font);
-        display_object.set_text_format(0, text.len(), format.clone(), activation.context);
-        display_object.set_new_text_format(format, activation.context);
+    let begin = if let Some(previous_line) = previous_line {
+        let previous_begin = get_internal(activation, previous_line, "_textBlockBeginIndex")?
+            .coerce_to_u32(activation)?;
+        let previous_len =
+            get_internal(activation, previous_line, "_rawTextLength")?.coerce_to_u32(activation)?;
+        let begin = (previous_begin + previous_len) as usize;
+        if begin >= text_len {
+            set_internal(
+                activation,
+                block,
+                "_textLineCreationResult",
+                "complete".into(),
+            )?;
+            return Ok(None);
+        }
+        begin
     } else {
-        display_object.set_is_device_font(activation.context, true);
+        0
+    };
+
+    let padding = EditText::INTERNAL_PADDING;
+    let movie = activation.caller_movie_or_root();
+
+    let (display_object, instance) = match existing_line {
+        Some(line) => {
+            let display_object = line
+                .as_display_object()
+                .and_then(|display_object| display_object.as_edit_text())
+                .expect("TextLine is backed by an EditText");
+            (display_object, Some(line))
+        }
+        None => (
+            // FIXME: TextLine should be its own DisplayObject
+            EditText::new_tlf(activation.context, movie, 0.0, 0.0, width, 15.0),
+            None,
+        ),
+    };
+
+    // Lay out the rest of the text on a single line, to break it where it stops fitting.
+    fill_line(activation, display_object, &content, begin, text_len);
+    display_object.set_word_wrap(false, activation.context);
+    let Some(line_len) = find_line_length(display_object, &content, begin, width, fit_something)
+    else {
+        set_internal(
+            activation,
+            block,
+            "_textLineCreationResult",
+            "insufficientWidth".into(),
+        )?;
+        return Ok(None);
+    };
+
+    let end = begin + line_len;
+    fill_line(activation, display_object, &content, begin, end);
+
+    let unjustified_width = display_object
+        .measure_text(activation.context)
+        .0
+        .to_pixels();
+
+    if let Some(justifier) = block
+        .get_public_property("textJustifier", activation)?
+        .as_object()
+    {
+        let line_justification = justifier
+            .get_public_property("lineJustification", activation)?
+            .coerce_to_string(activation)?;
+        let is_last_line = end >= text_len;
+        let ends_paragraph = is_last_line
+            || content
+                .display_text(end - 1, end)
+                .iter()
+                .any(|c| c == b'\n' as u16 || c == b'\r' as u16);
+
+        let justify = if &line_justification == b"allIncludingLast" {
+            true
+        } else if &line_justification == b"allButLast" {
+            !is_last_line
+        } else if &line_justification == b"allButMandatoryBreak" {
+            !ends_paragraph
+        } else {
+            false
+        };
+
+        if justify && width > unjustified_width {
+            let letter_spacing = if justifier.is_of_type(
+                activation
+                    .avm2()
+                    .classes()
+                    .spacejustifier
+                    .inner_class_definition(),
+            ) {
+                justifier
+                    .get_public_property("letterSpacing", activation)?
+                    .coerce_to_boolean()
+            } else {
+                true
+            };
+            justify_line(
+                activation,
+                display_object,
+                width - unjustified_width,
+                letter_spacing,
+            );
+        }
     }
 
-    display_object.set_word_wrap(true, activation.context);
+    let (text_width, text_height) = display_object.measure_text(activation.context);
+    display_object.set_width(activation.context, text_width.to_pixels() + padding * 2.0);
+    display_object.set_height(activation.context, text_height.to_pixels());
 
-    let measured_text = display_object.measure_text(activation.context);
+    // Place graphics on the baseline, over the characters standing in for them.
+    let ascent = display_object
+        .layout_metrics(Some(0))
+        .map(|metrics| metrics.ascent)
+        .unwrap_or_default();
+    let mut inline_graphics = Vec::new();
+    for (run_start, _, _, run) in content.runs_in(begin, end) {
+        if let Some((graphic, _width, height)) = run.graphic {
+            if let Some(bounds) = display_object.char_bounds(run_start) {
+                graphic.set_x(activation.gc(), bounds.x_min);
+                graphic.set_y(
+                    activation.gc(),
+                    Twips::from_pixels(padding) + ascent - Twips::from_pixels(height),
+                );
+            }
+            inline_graphics.push((run_start, graphic));
+        }
+    }
+    display_object.set_inline_graphics(activation.gc(), inline_graphics);
 
-    display_object.set_height(activation.context, measured_text.1.to_pixels());
+    let instance = match instance {
+        Some(instance) => instance,
+        None => {
+            let class = activation.avm2().classes().textline;
+            let instance = initialize_for_allocator(activation, display_object.into(), class)?;
+            class.call_native_init(instance.into(), &[], activation)?;
+            instance
+        }
+    };
 
-    Ok(())
+    set_internal(activation, instance, "_textBlock", block.into())?;
+    set_internal(activation, instance, "_specifiedWidth", width.into())?;
+    set_internal(activation, instance, "_textBlockBeginIndex", begin.into())?;
+    set_internal(activation, instance, "_rawTextLength", line_len.into())?;
+    set_internal(
+        activation,
+        instance,
+        "_unjustifiedTextWidth",
+        unjustified_width.into(),
+    )?;
+    set_internal(activation, instance, "_validity", "valid".into())?;
+    set_internal(activation, instance, "_nextLine", Value::Null)?;
+
+    match previous_line {
+        Some(previous_line) => {
+            set_internal(activation, previous_line, "_nextLine", instance.into())?;
+            set_internal(activation, instance, "_previousLine", previous_line.into())?;
+        }
+        None => {
+            set_internal(activation, instance, "_previousLine", Value::Null)?;
+            set_internal(activation, block, "_firstLine", instance.into())?;
+        }
+    }
+    set_internal(activation, block, "_lastLine", instance.into())?;
+    set_internal(
+        activation,
+        block,
+        "_textLineCreationResult",
+        "success".into(),
+    )?;
+
+    Ok(Some(instance))
+}
+
+pub fn create_text_line<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let previous_line = args.try_get_object(activation, 0);
+    let width = args.get_f64(activation, 1)?;
+    let fit_something = args.get_bool(3);
+
+    let line = layout_line(activation, this, None, previous_line, width, fit_something)?;
+    Ok(line.map_or(Value::Null, Value::from))
+}
+
+pub fn recreate_text_line<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(text_line) = args.try_get_object(activation, 0) else {
+        return Err(make_error_2004(activation, Error2004Type::ArgumentError));
+    };
+    let previous_line = args.try_get_object(activation, 1);
+    let width = args.get_f64(activation, 2)?;
+    let fit_something = args.get_bool(4);
+
+    let line = layout_line(
+        activation,
+        this,
+        Some(text_line),
+        previous_line,
+        width,
+        fit_something,
+    )?;
+    Ok(line.map_or(Value::Null, Value::from))
 }
@@ -1,8 +1,14 @@
 use crate::avm2::activation::Activation;
-use crate::avm2::error::Error;
+use crate::avm2::error::{make_error_2006, Error};
 use crate::avm2::object::{Object, TObject};
+use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
-use crate::display_object::TDisplayObject;
+use crate::avm2::Multiname;
+use crate::display_object::{EditText, TDisplayObject};
+use crate::prelude::*;
+use ruffle_wstr::{utils as string_utils, WStrToUtf8};
+use std::ops::Range;
+use unic_segment::GraphemeIndices;
 
 pub fn native_instance_init<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -13,6 +19,65 @@ pub fn native_instance_init<'gc>(
     Ok(Value::Undefined)
 }
 
+fn edit_text<'gc>(this: Object<'gc>) -> EditText<'gc> {
+    this.as_display_object()
+        .and_then(|display_object| display_object.as_edit_text())
+        .expect("TextLine is backed by an EditText")
+}
+
+/// The atoms of a line, as ranges of its text.
+///
+/// An atom is an indivisible element of a line, which in Ruffle is always a
+/// grapheme cluster.
+pub fn atoms(edit_text: EditText<'_>) -> Vec<Range<usize>> {
+    let text = edit_text.text();
+    let to_utf8 = WStrToUtf8::new(&text);
+    let utf8 = to_utf8.to_utf8_lossy();
+
+    let mut starts: Vec<usize> = GraphemeIndices::new(&utf8)
+        .filter_map(|(utf8_index, _)| to_utf8.utf16_index(utf8_index))
+        .collect();
+    starts.push(text.len());
+    starts.windows(2).map(|range| range[0]..range[1]).collect()
+}
+
+/// Get the atom at the index given in `args[0]`, throwing a `RangeError`
+/// if it is out of bounds.
+fn atom_arg<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    edit_text: EditText<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Range<usize>, Error<'gc>> {
+    let index = args.get_i32(activation, 0)?;
+    let atoms = atoms(edit_text);
+    usize::try_from(index)
+        .ok()
+        .and_then(|index| atoms.get(index).cloned())
+        .ok_or_else(|| make_error_2006(activation))
+}
+
+/// The bounds of an atom, in the coordinate space of the TextLine.
+pub fn atom_bounds(edit_text: EditText<'_>, atom: Range<usize>) -> Rectangle<Twips> {
+    atom.filter_map(|position| edit_text.char_bounds(position))
+        .reduce(|a, b| a.union(&b))
+        .unwrap_or(Rectangle::ZERO)
+}
+
+fn text_block_begin_index<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+) -> Result<usize, Error<'gc>> {
+    Ok(this
+        .get_property(
+            &Multiname::new(
+                activation.avm2().flash_text_engine_internal,
+                "_textBlockBeginIndex",
+            ),
+            activation,
+        )?
+        .coerce_to_u32(activation)? as usize)
+}
+
 pub fn get_text_width<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
@@ -36,3 +101,241 @@ pub fn get_text_height<'gc>(
     let measured_text = edit_text.measure_text(activation.context);
     Ok(measured_text.1.to_pixels().into())
 }
+
+pub fn get_ascent<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let ascent = edit_text(this)
+        .layout_metrics(Some(0))
+        .map(|metrics| metrics.ascent)
+        .unwrap_or_default();
+    Ok(ascent.to_pixels().into())
+}
+
+pub fn get_descent<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let descent = edit_text(this)
+        .layout_metrics(Some(0))
+        .map(|metrics| metrics.descent)
+        .unwrap_or_default();
+    Ok(descent.to_pixels().into())
+}
+
+/// The ascent of the line, including any graphics rising above its text.
+fn total_ascent(edit_text: EditText<'_>) -> Twips {
+    let ascent = edit_text
+        .layout_metrics(Some(0))
+        .map(|metrics| metrics.ascent)
+        .unwrap_or_default();
+    let padding = Twips::from_pixels(EditText::INTERNAL_PADDING);
+    edit_text
+        .inline_graphics()
+        .into_iter()
+        .map(|graphic| padding + ascent - graphic.y())
+        .fold(ascent, Twips::max)
+}
+
+pub fn get_total_ascent<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(total_ascent(edit_text(this)).to_pixels().into())
+}
+
+pub fn get_total_descent<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    get_descent(activation, this, args)
+}
+
+pub fn get_total_height<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let edit_text = edit_text(this);
+    let descent = edit_text
+        .layout_metrics(Some(0))
+        .map(|metrics| metrics.descent)
+        .unwrap_or_default();
+    Ok((total_ascent(edit_text) + descent).to_pixels().into())
+}
+
+pub fn get_has_tabs<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(edit_text(this).text().contains(b'\t').into())
+}
+
+pub fn get_has_graphic_element<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(edit_text(this).has_inline_graphics().into())
+}
+
+pub fn get_atom_count<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(atoms(edit_text(this)).len().into())
+}
+
+pub fn get_atom_bounds<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let edit_text = edit_text(this);
+    let atom = atom_arg(activation, edit_text, args)?;
+    let bounds = atom_bounds(edit_text, atom);
+
+    let rectangle = activation.avm2().classes().rectangle.construct(
+        activation,
+        &[
+            bounds.x_min.to_pixels().into(),
+            bounds.y_min.to_pixels().into(),
+            bounds.width().to_pixels().into(),
+            bounds.height().to_pixels().into(),
+        ],
+    )?;
+    Ok(rectangle.into())
+}
+
+pub fn get_atom_center<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let edit_text = edit_text(this);
+    let atom = atom_arg(activation, edit_text, args)?;
+    let bounds = atom_bounds(edit_text, atom);
+
+    Ok(((bounds.x_min.to_pixels() + bounds.x_max.to_pixels()) / 2.0).into())
+}
+
+pub fn get_atom_index_at_point<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let stage_x = args.get_f64(activation, 0)?;
+    let stage_y = args.get_f64(activation, 1)?;
+
+    let edit_text = edit_text(this);
+    let global = Point::from_pixels(stage_x, stage_y);
+    let Some(local) = edit_text.global_to_local(global) else {
+        return Ok((-1).into());
+    };
+
+    let index = atoms(edit_text)
+        .into_iter()
+        .position(|atom| atom_bounds(edit_text, atom).contains(local));
+    Ok(index.map_or(-1, |index| index as i32).into())
+}
+
+pub fn get_atom_index_at_char_index<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let char_index = args.get_i32(activation, 0)?;
+    let begin_index = text_block_begin_index(activation, this)?;
+
+    let index = usize::try_from(char_index)
+        .ok()
+        .and_then(|char_index| char_index.checked_sub(begin_index))
+        .and_then(|position| {
+            atoms(edit_text(this))
+                .into_iter()
+                .position(|atom| atom.contains(&position))
+        });
+    Ok(index.map_or(-1, |index| index as i32).into())
+}
+
+pub fn get_atom_text_block_begin_index<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let atom = atom_arg(activation, edit_text(this), args)?;
+    let begin_index = text_block_begin_index(activation, this)?;
+    Ok((begin_index + atom.start).into())
+}
+
+pub fn get_atom_text_block_end_index<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let atom = atom_arg(activation, edit_text(this), args)?;
+    let begin_index = text_block_begin_index(activation, this)?;
+    Ok((begin_index + atom.end).into())
+}
+
+pub fn get_atom_graphic<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let edit_text = edit_text(this);
+    let atom = atom_arg(activation, edit_text, args)?;
+
+    Ok(edit_text
+        .inline_graphic_at(atom.start)
+        .map_or(Value::Null, |graphic| graphic.object2()))
+}
+
+pub fn get_atom_word_boundary_on_left<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let edit_text = edit_text(this);
+    let atom = atom_arg(activation, edit_text, args)?;
+    if atom.start == 0 {
+        return Ok(true.into());
+    }
+
+    let text = edit_text.text();
+    let is_boundary = string_utils::swf_is_whitespace(text.at(atom.start - 1))
+        != string_utils::swf_is_whitespace(text.at(atom.start));
+    Ok(is_boundary.into())
+}
+
+pub fn get_baseline_position<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let baseline = args.get_string(activation, 0)?;
+
+    let (ascent, descent) = edit_text(this)
+        .layout_metrics(Some(0))
+        .map(|metrics| (metrics.ascent.to_pixels(), metrics.descent.to_pixels()))
+        .unwrap_or_default();
+
+    // Positions are relative to the roman baseline.
+    let position = if &baseline == b"ascent" || &baseline == b"ideographicTop" {
+        -ascent
+    } else if &baseline == b"descent" || &baseline == b"ideographicBottom" {
+        descent
+    } else if &baseline == b"ideographicCenter" {
+        (descent - ascent) / 2.0
+    } else {
+        0.0
+    };
+    Ok(position.into())
+}
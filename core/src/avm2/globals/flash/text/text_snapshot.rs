@@ -0,0 +1,196 @@
+//! `flash.text.TextSnapshot` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::object::{ArrayObject, Object, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::{Error, Multiname};
+use crate::string::AvmString;
+use crate::text_snapshot::TextSnapshot;
+use swf::{Point, Twips};
+
+/// Build a snapshot of the static text of the container of this `TextSnapshot`.
+fn snapshot<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+) -> Result<Option<TextSnapshot<'gc>>, Error<'gc>> {
+    let container = this
+        .get_property(
+            &Multiname::new(activation.avm2().flash_text_internal, "_container"),
+            activation,
+        )?
+        .as_object()
+        .and_then(|o| o.as_display_object());
+    Ok(container.map(|container| TextSnapshot::new(activation.context.library, container)))
+}
+
+/// Implements `TextSnapshot.charCount`
+pub fn get_char_count<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(snapshot(activation, this)?
+        .map_or(0, |s| s.char_count() as i32)
+        .into())
+}
+
+/// Implements `TextSnapshot.findText`
+pub fn find_text<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let begin = args.get_i32(activation, 0)?.max(0) as usize;
+    let needle = args.get_string(activation, 1)?;
+    let case_sensitive = args.get_bool(2);
+
+    let index = snapshot(activation, this)?
+        .and_then(|s| s.find_text(begin, &needle.to_utf8_lossy(), case_sensitive));
+    Ok(index.map_or(-1, |i| i as i32).into())
+}
+
+/// Implements `TextSnapshot.getSelected`
+pub fn get_selected<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let begin = args.get_i32(activation, 0)?;
+    let end = args.get_i32(activation, 1)?;
+
+    let Some(snapshot) = snapshot(activation, this)? else {
+        return Ok(false.into());
+    };
+    let range = snapshot.clamp_range(begin, end);
+    Ok(snapshot.is_selected(range).into())
+}
+
+/// Implements `TextSnapshot.getSelectedText`
+pub fn get_selected_text<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let include_line_endings = args.get_bool(0);
+
+    let text = snapshot(activation, this)?
+        .map(|s| s.selected_text(include_line_endings))
+        .unwrap_or_default();
+    Ok(AvmString::new_utf8(activation.context.gc_context, text).into())
+}
+
+/// Implements `TextSnapshot.getText`
+pub fn get_text<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let begin = args.get_i32(activation, 0)?;
+    let end = args.get_i32(activation, 1)?;
+    let include_line_endings = args.get_bool(2);
+
+    let text = snapshot(activation, this)?
+        .map(|s| s.text(s.clamp_range(begin, end), include_line_endings))
+        .unwrap_or_default();
+    Ok(AvmString::new_utf8(activation.context.gc_context, text).into())
+}
+
+/// Implements `TextSnapshot.getTextRunInfo`
+pub fn get_text_run_info<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let begin = args.get_i32(activation, 0)?;
+    let end = args.get_i32(activation, 1)?;
+
+    let mut runs = Vec::new();
+    if let Some(snapshot) = snapshot(activation, this)? {
+        let range = snapshot.clamp_range(begin, end);
+        for (index, c) in snapshot.chars()[range.clone()]
+            .iter()
+            .enumerate()
+            .map(|(i, c)| (i + range.start, c))
+        {
+            let run = activation
+                .avm2()
+                .classes()
+                .object
+                .construct(activation, &[])?;
+            let font = AvmString::new_utf8(activation.context.gc_context, &c.info.font_name);
+            let properties: [(&str, Value<'gc>); 11] = [
+                ("indexInRun", (index as i32).into()),
+                ("selected", c.is_selected().into()),
+                ("font", font.into()),
+                ("color", c.info.color.to_rgb().into()),
+                ("height", c.info.height.to_pixels().into()),
+                ("matrix_a", f64::from(c.matrix.a).into()),
+                ("matrix_b", f64::from(c.matrix.b).into()),
+                ("matrix_c", f64::from(c.matrix.c).into()),
+                ("matrix_d", f64::from(c.matrix.d).into()),
+                ("matrix_tx", c.matrix.tx.to_pixels().into()),
+                ("matrix_ty", c.matrix.ty.to_pixels().into()),
+            ];
+            for (name, value) in properties {
+                run.set_public_property(name, value, activation)?;
+            }
+            for (i, corner) in c.corners().into_iter().enumerate() {
+                let x = AvmString::new_utf8(activation.context.gc_context, format!("corner{i}x"));
+                let y = AvmString::new_utf8(activation.context.gc_context, format!("corner{i}y"));
+                run.set_public_property(x, corner.x.to_pixels().into(), activation)?;
+                run.set_public_property(y, corner.y.to_pixels().into(), activation)?;
+            }
+            runs.push(run.into());
+        }
+    }
+    Ok(ArrayObject::from_storage(activation, ArrayStorage::from_args(&runs))?.into())
+}
+
+/// Implements `TextSnapshot.hitTestTextNearPos`
+pub fn hit_test_text_near_pos<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let point = Point::new(
+        Twips::from_pixels(args.get_f64(activation, 0)?),
+        Twips::from_pixels(args.get_f64(activation, 1)?),
+    );
+    let max_distance = args.get_f64(activation, 2)?;
+
+    let index = snapshot(activation, this)?.and_then(|s| s.hit_test_near_pos(point, max_distance));
+    Ok(index.map_or(-1.0, |i| i as f64).into())
+}
+
+/// Implements `TextSnapshot.setSelectColor`
+pub fn set_select_color<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let color = swf::Color::from_rgb(args.get_u32(activation, 0)?, 255);
+
+    if let Some(snapshot) = snapshot(activation, this)? {
+        snapshot.set_select_color(activation.context.gc_context, color);
+    }
+    Ok(Value::Undefined)
+}
+
+/// Implements `TextSnapshot.setSelected`
+pub fn set_selected<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let begin = args.get_i32(activation, 0)?;
+    let end = args.get_i32(activation, 1)?;
+    let selected = args.get_bool(2);
+
+    if let Some(snapshot) = snapshot(activation, this)? {
+        let range = snapshot.clamp_range(begin, end);
+        snapshot.set_selected(activation.context.gc_context, range, selected);
+    }
+    Ok(Value::Undefined)
+}
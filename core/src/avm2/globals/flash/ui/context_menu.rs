@@ -72,6 +72,21 @@ pub fn make_context_menu_state<'gc>(
 
     result.build_builtin_items(builtin_items, activation.context);
 
+    if let Some(menu) = menu {
+        if check_bool!(menu, "clipboardMenu", true) {
+            let mut clipboard_items = context_menu::ClipboardItemFlags::default();
+            if let Ok(Value::Object(items)) = menu.get_public_property("clipboardItems", activation)
+            {
+                clipboard_items.clear = check_bool!(items, "clear", true);
+                clipboard_items.copy = check_bool!(items, "copy", true);
+                clipboard_items.cut = check_bool!(items, "cut", true);
+                clipboard_items.paste = check_bool!(items, "paste", true);
+                clipboard_items.select_all = check_bool!(items, "selectAll", true);
+            }
+            result.build_clipboard_items(clipboard_items, activation.context);
+        }
+    }
+
     if let Some(menu) = menu {
         if let Ok(Value::Object(custom_items)) = menu.get_public_property("customItems", activation)
         {
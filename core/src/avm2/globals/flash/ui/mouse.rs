@@ -1,9 +1,15 @@
 //! `flash.ui.Mouse` builtin
 
 use crate::avm2::activation::Activation;
-use crate::avm2::object::Object;
+use crate::avm2::error::{make_error_2004, make_error_2008, Error2004Type};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
+use crate::backend::ui::{CustomCursor, CustomCursorFrame, MouseCursor};
+use crate::bitmap::operations;
+use crate::player::CursorOverride;
+use crate::string::AvmString;
 
 pub fn hide<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -22,3 +28,167 @@ pub fn show<'gc>(
     activation.context.ui.set_mouse_visible(true);
     Ok(Value::Undefined)
 }
+
+/// The names of the built-in cursors, as used by `flash.ui.MouseCursor`.
+const BUILTIN_CURSORS: [(&str, MouseCursor); 4] = [
+    ("arrow", MouseCursor::Arrow),
+    ("button", MouseCursor::Hand),
+    ("ibeam", MouseCursor::IBeam),
+    ("hand", MouseCursor::Grab),
+];
+
+/// Implements `Mouse.cursor`'s getter
+pub fn get_cursor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = match &activation.context.mouse_data.cursor_override {
+        None => "auto",
+        Some(CursorOverride::Builtin(cursor)) => BUILTIN_CURSORS
+            .iter()
+            .find(|(_, builtin)| builtin == cursor)
+            .map_or("auto", |(name, _)| name),
+        Some(CursorOverride::Custom(name)) => {
+            return Ok(AvmString::new_utf8(activation.context.gc_context, name).into());
+        }
+    };
+    Ok(name.into())
+}
+
+/// Implements `Mouse.cursor`'s setter
+pub fn set_cursor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = args.get_string(activation, 0)?.to_utf8_lossy().into_owned();
+
+    let cursor_override = if name == "auto" {
+        None
+    } else if let Some((_, cursor)) = BUILTIN_CURSORS.iter().find(|(n, _)| *n == name) {
+        Some(CursorOverride::Builtin(*cursor))
+    } else if activation
+        .context
+        .mouse_data
+        .custom_cursors
+        .contains_key(&name)
+    {
+        Some(CursorOverride::Custom(name))
+    } else {
+        return Err(make_error_2008(activation, "cursor"));
+    };
+
+    activation
+        .context
+        .mouse_data
+        .set_cursor_override(activation.context.ui, cursor_override);
+    Ok(Value::Undefined)
+}
+
+/// Implements `Mouse.supportsNativeCursor`
+pub fn get_supports_native_cursor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation.context.ui.supports_custom_mouse_cursor().into())
+}
+
+/// Implements `Mouse.registerCursor`
+pub fn register_cursor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = args.get_string(activation, 0)?.to_utf8_lossy().into_owned();
+    let cursor_data = args.get_object(activation, 1, "cursor")?;
+
+    let data = cursor_data.get_public_property("data", activation)?;
+    let mut bitmaps = Vec::new();
+    if let Some(data) = data.as_object() {
+        if let Some(storage) = data.as_vector_storage() {
+            bitmaps.extend(
+                storage
+                    .iter()
+                    .filter_map(|bitmap| bitmap.as_object()?.as_bitmap_data()),
+            );
+        }
+    }
+    if bitmaps.is_empty() {
+        return Err(make_error_2004(activation, Error2004Type::ArgumentError));
+    }
+
+    let frames = bitmaps
+        .into_iter()
+        .map(|bitmap_data| {
+            let (width, height, rgba) = operations::get_pixels_as_rgba(
+                activation.context.renderer,
+                bitmap_data,
+                0,
+                0,
+                bitmap_data.width() as i32,
+                bitmap_data.height() as i32,
+            );
+            CustomCursorFrame {
+                width,
+                height,
+                rgba,
+            }
+        })
+        .collect();
+
+    let hotspot = cursor_data.get_public_property("hotSpot", activation)?;
+    let hotspot = match hotspot.as_object() {
+        Some(point) => {
+            let x = point.get_public_property("x", activation)?;
+            let y = point.get_public_property("y", activation)?;
+            (
+                x.coerce_to_i32(activation)?.max(0) as u32,
+                y.coerce_to_i32(activation)?.max(0) as u32,
+            )
+        }
+        None => (0, 0),
+    };
+
+    let frame_rate = cursor_data
+        .get_public_property("frameRate", activation)?
+        .coerce_to_number(activation)?;
+    let frame_rate = if frame_rate.is_finite() {
+        frame_rate.max(0.0)
+    } else {
+        0.0
+    };
+
+    let cursor = CustomCursor {
+        frames,
+        hotspot,
+        frame_rate,
+    };
+    let mouse_data = &mut activation.context.mouse_data;
+    mouse_data.custom_cursors.insert(name.clone(), cursor);
+
+    // Show the new images straight away if the cursor is in use.
+    if mouse_data.cursor_override == Some(CursorOverride::Custom(name)) {
+        let cursor_override = mouse_data.cursor_override.clone();
+        mouse_data.set_cursor_override(activation.context.ui, cursor_override);
+    }
+    Ok(Value::Undefined)
+}
+
+/// Implements `Mouse.unregisterCursor`
+pub fn unregister_cursor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = args.get_string(activation, 0)?.to_utf8_lossy().into_owned();
+    let mouse_data = &mut activation.context.mouse_data;
+    mouse_data.custom_cursors.remove(&name);
+
+    // A cursor that is in use goes back to `MouseCursor.AUTO`.
+    if mouse_data.cursor_override == Some(CursorOverride::Custom(name)) {
+        mouse_data.set_cursor_override(activation.context.ui, None);
+    }
+    Ok(Value::Undefined)
+}
@@ -24,12 +24,50 @@ package flash.utils {
 		public native function get position():uint;
 		public native function set position(value:uint):void;
 
+		internal var _shareable:Boolean = false;
+
+		[API("684")]
+		public function get shareable():Boolean {
+			return this._shareable;
+		}
+
+		[API("684")]
+		public function set shareable(value:Boolean):void {
+			this._shareable = value;
+		}
+
 		public function ByteArray() {
 			this.objectEncoding = _defaultObjectEncoding;
 		}
 
 		public native function clear():void;
 
+		// Workers never run concurrently in Ruffle, so these don't need to be atomic.
+		[API("684")]
+		public function atomicCompareAndSwapIntAt(byteIndex:int, expectedValue:int, newValue:int):int {
+			if (byteIndex < 0 || byteIndex % 4 != 0) {
+				throw new RangeError("Error #1506: The specified range is invalid.", 1506);
+			}
+			var oldPosition:uint = this.position;
+			this.position = byteIndex;
+			var value:int = this.readInt();
+			if (value == expectedValue) {
+				this.position = byteIndex;
+				this.writeInt(newValue);
+			}
+			this.position = oldPosition;
+			return value;
+		}
+
+		[API("684")]
+		public function atomicCompareAndSwapLength(expectedLength:int, newLength:int):int {
+			var oldLength:int = this.length;
+			if (oldLength == expectedLength) {
+				this.length = newLength;
+			}
+			return oldLength;
+		}
+
 		public function deflate(): void {
 			this.compress("deflate");
 		}
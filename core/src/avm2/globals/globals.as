@@ -190,6 +190,7 @@ include "flash/events/KeyboardEvent.as"
 include "flash/events/NetDataEvent.as"
 include "flash/events/NetFilterEvent.as"
 include "flash/events/NetStatusEvent.as"
+include "flash/events/PermissionEvent.as"
 include "flash/events/PressAndTapGestureEvent.as"
 include "flash/events/ProgressEvent.as"
 include "flash/events/SampleDataEvent.as"
@@ -239,6 +240,7 @@ include "flash/geom/Transform.as"
 include "flash/geom/Utils3D.as"
 include "flash/geom/Vector3D.as"
 
+include "flash/globalization/Collator.as"
 include "flash/globalization/CollatorMode.as"
 include "flash/globalization/CurrencyParseResult.as"
 include "flash/globalization/CurrencyFormatter.as"
@@ -251,6 +253,7 @@ include "flash/globalization/LocaleID.as"
 include "flash/globalization/NationalDigitsType.as"
 include "flash/globalization/NumberFormatter.as"
 include "flash/globalization/NumberParseResult.as"
+include "flash/globalization/StringTools.as"
 
 include "flash/media/AudioDecoder.as"
 include "flash/media/AudioOutputChangeReason.as"
@@ -318,6 +321,8 @@ include "flash/filesystem/File.as" // File extends FileReference
 include "flash/net/drm/AuthenticationMethod.as"
 include "flash/net/drm/LoadVoucherSetting.as"
 
+include "flash/permissions/PermissionStatus.as"
+
 include "flash/printing/PrintJob.as"
 include "flash/printing/PrintJobOptions.as"
 include "flash/printing/PrintJobOrientation.as"
@@ -9,6 +9,7 @@ use crate::avm2::object::function_object::FunctionObject;
 use crate::avm2::object::script_object::ScriptObjectData;
 use crate::avm2::object::{Object, ObjectPtr, TObject};
 use crate::avm2::property::Property;
+use crate::avm2::sampler;
 use crate::avm2::scope::{Scope, ScopeChain};
 use crate::avm2::value::Value;
 use crate::avm2::vtable::{ClassBoundMethod, VTable};
@@ -804,6 +805,8 @@ impl<'gc> TObject<'gc> for ClassObject<'gc> {
 
         instance.install_instance_slots(activation.context.gc_context);
 
+        sampler::record_allocation(activation, instance, Some(self))?;
+
         self.call_init(instance.into(), arguments, activation)?;
 
         Ok(instance)
@@ -267,6 +267,18 @@ impl<'gc> EventObject<'gc> {
                 .unwrap();
         }
 
+        Self::net_status_event_with_info(activation, event_type, info_object)
+    }
+
+    /// Create a `NetStatusEvent` with an already constructed `info` object.
+    pub fn net_status_event_with_info<S>(
+        activation: &mut Activation<'_, 'gc>,
+        event_type: S,
+        info_object: Object<'gc>,
+    ) -> Object<'gc>
+    where
+        S: Into<AvmString<'gc>>,
+    {
         let event_type: AvmString<'gc> = event_type.into();
 
         let net_status_cls = activation.avm2().classes().netstatusevent;
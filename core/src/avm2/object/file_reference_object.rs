@@ -3,9 +3,10 @@ use crate::avm2::object::{ClassObject, Object, ObjectPtr, TObject};
 use crate::avm2::value::Value;
 use crate::avm2::{Activation, Error};
 use crate::backend::ui::FileDialogResult;
+use crate::loader::LoaderHandle;
 use gc_arena::{Collect, Gc};
 use gc_arena::{GcWeak, Mutation};
-use std::cell::{Cell, Ref, RefCell};
+use std::cell::{Cell, Ref, RefCell, RefMut};
 use std::fmt;
 
 pub fn file_reference_allocator<'gc>(
@@ -20,6 +21,7 @@ pub fn file_reference_allocator<'gc>(
             base,
             reference: RefCell::new(FileReference::None),
             loaded: Cell::new(false),
+            loader: Cell::new(None),
         },
     ))
     .into())
@@ -66,6 +68,10 @@ impl<'gc> FileReferenceObject<'gc> {
         self.0.reference.borrow()
     }
 
+    pub fn file_reference_mut(&self) -> RefMut<'_, FileReference> {
+        self.0.reference.borrow_mut()
+    }
+
     pub fn set_loaded(&self, value: bool) {
         self.0.loaded.set(value)
     }
@@ -73,6 +79,15 @@ impl<'gc> FileReferenceObject<'gc> {
     pub fn loaded(&self) -> bool {
         self.0.loaded.get()
     }
+
+    /// The loader of the download or upload that is currently in progress.
+    pub fn loader(&self) -> Option<LoaderHandle> {
+        self.0.loader.get()
+    }
+
+    pub fn set_loader(&self, handle: Option<LoaderHandle>) {
+        self.0.loader.set(handle)
+    }
 }
 
 pub enum FileReference {
@@ -90,6 +105,8 @@ pub struct FileReferenceObjectData<'gc> {
     reference: RefCell<FileReference>,
 
     loaded: Cell<bool>,
+
+    loader: Cell<Option<LoaderHandle>>,
 }
 
 const _: () = assert!(std::mem::offset_of!(FileReferenceObjectData, base) == 0);
@@ -0,0 +1,323 @@
+//! Memory and CPU sampling, as exposed by `flash.sampler`
+
+use crate::avm2::activation::Activation;
+use crate::avm2::call_stack::CallStack;
+use crate::avm2::method::Method;
+use crate::avm2::object::{ClassObject, Object, TObject, WeakObject};
+use crate::avm2::{Error, Value};
+use crate::string::{WStr, WString};
+use fnv::FnvHashMap;
+use gc_arena::{Collect, Mutation};
+use web_time::{Duration, Instant};
+
+/// How often a CPU sample (a sample of just the call stack) is taken while sampling.
+const CPU_SAMPLE_INTERVAL: Duration = Duration::from_millis(1);
+
+/// The number of buffered samples at which the sampler callback is called.
+const SAMPLE_BUFFER_SIZE: usize = 10_000;
+
+/// The estimated size of an empty object, in bytes.
+const OBJECT_SIZE: usize = 32;
+
+/// The estimated size of a string without its characters, in bytes.
+const STRING_SIZE: usize = 24;
+
+/// The estimated size of a single property or element, in bytes.
+const VALUE_SIZE: usize = 8;
+
+#[derive(Collect, Clone)]
+#[collect(no_drop)]
+pub struct Sample<'gc> {
+    /// The time this sample was taken, in microseconds since sampling started.
+    pub time: f64,
+
+    /// The call stack when this sample was taken, from the innermost call outwards.
+    #[collect(require_static)]
+    pub stack: Vec<WString>,
+
+    pub kind: SampleKind<'gc>,
+}
+
+#[derive(Collect, Clone, Copy)]
+#[collect(no_drop)]
+pub enum SampleKind<'gc> {
+    /// A periodic sample of the call stack, corresponding to `flash.sampler.Sample`.
+    Cpu,
+
+    /// An object was allocated, corresponding to `flash.sampler.NewObjectSample`.
+    NewObject {
+        id: u32,
+        class: Option<ClassObject<'gc>>,
+        size: usize,
+    },
+
+    /// A sampled object was garbage collected, corresponding to `flash.sampler.DeleteObjectSample`.
+    DeleteObject { id: u32, size: usize },
+}
+
+#[derive(Collect)]
+#[collect(no_drop)]
+struct LiveObject<'gc> {
+    object: WeakObject<'gc>,
+    size: usize,
+}
+
+/// The state of the `flash.sampler` API.
+///
+/// While sampling, this records the allocation and collection of AVM2 objects,
+/// periodic samples of the call stack, and how many times each method has been called.
+#[derive(Collect, Default)]
+#[collect(no_drop)]
+pub struct Sampler<'gc> {
+    /// When sampling was started, or `None` if it isn't running.
+    #[collect(require_static)]
+    start_time: Option<Instant>,
+
+    /// Whether sampling was paused by `pauseSampling`.
+    paused: bool,
+
+    /// When the last CPU sample was taken.
+    #[collect(require_static)]
+    last_cpu_sample: Option<Instant>,
+
+    /// The samples recorded since they were last cleared.
+    samples: Vec<Sample<'gc>>,
+
+    /// The sampled objects that haven't been collected yet, by ID.
+    live_objects: FnvHashMap<u32, LiveObject<'gc>>,
+
+    /// The ID to give to the next sampled object.
+    next_id: u32,
+
+    /// How many times each method was called while sampling.
+    ///
+    /// Holding on to the methods keeps them from being collected, so that a new method can't be
+    /// allocated in the place of one that was counted and inherit its count.
+    invocation_counts: FnvHashMap<Method<'gc>, u32>,
+
+    /// The function set by `setSamplerCallback`.
+    callback: Option<Object<'gc>>,
+
+    /// Whether the callback is currently running, to avoid calling it recursively.
+    in_callback: bool,
+}
+
+impl<'gc> Sampler<'gc> {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Whether samples are currently being recorded.
+    pub fn is_sampling(&self) -> bool {
+        self.start_time.is_some() && !self.paused
+    }
+
+    /// Starts (or resumes) sampling.
+    pub fn start(&mut self) {
+        if self.start_time.is_none() {
+            self.start_time = Some(Instant::now());
+        }
+        self.paused = false;
+    }
+
+    /// Pauses sampling, keeping all recorded samples.
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Stops sampling and discards everything recorded so far.
+    pub fn stop(&mut self) {
+        self.start_time = None;
+        self.paused = false;
+        self.last_cpu_sample = None;
+        self.samples.clear();
+        self.live_objects.clear();
+        self.invocation_counts.clear();
+    }
+
+    pub fn set_callback(&mut self, callback: Option<Object<'gc>>) {
+        self.callback = callback;
+    }
+
+    /// The time since sampling started, in microseconds.
+    fn time(&self) -> f64 {
+        self.start_time
+            .map(|start_time| Instant::now().duration_since(start_time).as_micros() as f64)
+            .unwrap_or_default()
+    }
+
+    fn capture_stack(call_stack: &CallStack<'gc>) -> Vec<WString> {
+        call_stack
+            .iter()
+            .map(|call| {
+                let mut name = WString::new();
+                call.display(&mut name);
+                // `StackFrame.toString` adds its own parentheses.
+                match name.strip_suffix(WStr::from_units(b"()")) {
+                    Some(stripped) => stripped.into(),
+                    None => name,
+                }
+            })
+            .collect()
+    }
+
+    /// Records a method call, counting its invocation and taking a CPU sample if one is due.
+    pub fn record_call(&mut self, method: Method<'gc>, call_stack: &CallStack<'gc>) {
+        *self.invocation_counts.entry(method).or_default() += 1;
+
+        let now = Instant::now();
+        let sample_due = self
+            .last_cpu_sample
+            .map_or(true, |last| now.duration_since(last) >= CPU_SAMPLE_INTERVAL);
+        if sample_due {
+            self.last_cpu_sample = Some(now);
+            let sample = Sample {
+                time: self.time(),
+                stack: Self::capture_stack(call_stack),
+                kind: SampleKind::Cpu,
+            };
+            self.samples.push(sample);
+        }
+    }
+
+    /// Records the allocation of an object.
+    ///
+    /// Returns the sampler callback if the sample buffer has filled up and it should be called.
+    fn record_allocation(
+        &mut self,
+        object: Object<'gc>,
+        class: Option<ClassObject<'gc>>,
+        call_stack: &CallStack<'gc>,
+    ) -> Option<Object<'gc>> {
+        let id = self.next_id;
+        self.next_id = self.next_id.wrapping_add(1);
+        let size = object_size(object);
+        self.live_objects.insert(
+            id,
+            LiveObject {
+                object: object.downgrade(),
+                size,
+            },
+        );
+        let sample = Sample {
+            time: self.time(),
+            stack: Self::capture_stack(call_stack),
+            kind: SampleKind::NewObject { id, class, size },
+        };
+        self.samples.push(sample);
+
+        if self.samples.len() >= SAMPLE_BUFFER_SIZE && !self.in_callback {
+            self.callback
+        } else {
+            None
+        }
+    }
+
+    /// Records a `DeleteObjectSample` for every sampled object that has since been collected.
+    ///
+    /// This is called after every garbage collection step, so that the samples are timed
+    /// right after their objects were collected.
+    pub fn sweep(&mut self, mc: &Mutation<'gc>) {
+        if self.start_time.is_none() {
+            return;
+        }
+
+        let time = self.time();
+        let mut deleted: Vec<_> = self
+            .live_objects
+            .iter()
+            .filter(|(_, live)| live.object.upgrade(mc).is_none())
+            .map(|(id, live)| (*id, live.size))
+            .collect();
+        deleted.sort_unstable_by_key(|(id, _)| *id);
+        for (id, size) in deleted {
+            self.live_objects.remove(&id);
+            self.samples.push(Sample {
+                time,
+                stack: Vec::new(),
+                kind: SampleKind::DeleteObject { id, size },
+            });
+        }
+    }
+
+    pub fn samples(&self) -> &[Sample<'gc>] {
+        &self.samples
+    }
+
+    pub fn clear_samples(&mut self) {
+        self.samples.clear();
+    }
+
+    /// Returns the sampled object with the given ID, if it's still alive.
+    pub fn live_object(&self, mc: &Mutation<'gc>, id: u32) -> Option<Object<'gc>> {
+        self.live_objects
+            .get(&id)
+            .and_then(|live| live.object.upgrade(mc))
+    }
+
+    /// How many times a method was called while sampling.
+    pub fn invocation_count(&self, method: Method<'gc>) -> u32 {
+        self.invocation_counts
+            .get(&method)
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+/// Records the allocation of an object by ActionScript, if sampling is enabled.
+pub fn record_allocation<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+    class: Option<ClassObject<'gc>>,
+) -> Result<(), Error<'gc>> {
+    let avm2 = &mut activation.context.avm2;
+    if !avm2.sampler().is_sampling() {
+        return Ok(());
+    }
+
+    let call_stack = avm2.call_stack();
+    let callback = avm2
+        .sampler_mut()
+        .record_allocation(object, class, &call_stack.borrow());
+    if let Some(callback) = callback {
+        activation.context.avm2.sampler_mut().in_callback = true;
+        let result = callback.call(Value::Null, &[], activation);
+        activation.context.avm2.sampler_mut().in_callback = false;
+        result?;
+    }
+
+    Ok(())
+}
+
+/// Estimates the memory used by a value, in bytes.
+///
+/// Ruffle's object layout doesn't match Flash Player's,
+/// so this is only an approximation based on the amount of data the value holds.
+pub fn value_size(value: Value<'_>) -> usize {
+    match value {
+        Value::Undefined | Value::Null => 0,
+        Value::Bool(_) | Value::Integer(_) => 4,
+        Value::Number(_) => 8,
+        Value::String(string) => {
+            let char_size = if string.is_wide() { 2 } else { 1 };
+            STRING_SIZE + string.len() * char_size
+        }
+        Value::Object(object) => object_size(object),
+    }
+}
+
+fn object_size(object: Object<'_>) -> usize {
+    let mut size = OBJECT_SIZE;
+    size += object.vtable().default_slots().len() * VALUE_SIZE;
+    size += object.base().values().as_hashmap().len() * VALUE_SIZE * 2;
+    if let Some(array) = object.as_array_storage() {
+        size += array.length() * VALUE_SIZE;
+    }
+    if let Some(vector) = object.as_vector_storage() {
+        size += vector.length() * VALUE_SIZE;
+    }
+    if let Some(bytearray) = object.as_bytearray() {
+        size += bytearray.len();
+    }
+    size
+}
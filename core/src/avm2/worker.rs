@@ -0,0 +1,1234 @@
+//! ActionScript workers
+//!
+//! Every worker runs in its own `Player`, and so in its own AVM2 instance and
+//! gc arena. The state that workers share - their lifecycle, their shared
+//! properties, message channels, mutexes, conditions and shareable
+//! `ByteArray`s - lives in a `WorkerGroup` outside of any arena. ActionScript
+//! objects refer to it by ID, and each arena keeps track of its objects for
+//! these IDs in a `WorkerObjects`.
+//!
+//! Workers are scheduled cooperatively: the primordial worker's player gives
+//! each background worker a turn after its own frame, in which it runs a
+//! tick of its player. Only one worker runs at a time, so shareable
+//! `ByteArray`s only need to be synchronized when switching workers. A
+//! background worker that blocks on a `Mutex` or `Condition` is parked in
+//! the middle of its tick, and continues on a later turn once it's unblocked.
+//! While the primordial worker is blocked, it gives the background workers
+//! turns until it can continue.
+//!
+//! The players of background workers trace to and load through the log and
+//! navigator of the primordial worker's player.
+
+use crate::avm2::amf::{deserialize_value, serialize_value};
+use crate::avm2::bytearray::ByteArrayStorage;
+use crate::avm2::object::{
+    scriptobject_allocator, ByteArrayObject, ClassObject, EventObject, Object, TObject,
+};
+use crate::avm2::{Activation, Avm2, Error, Multiname, Value};
+use crate::backend::log::LogBackend;
+use crate::backend::navigator::{
+    ErrorResponse, NavigationMethod, NavigatorBackend, OwnedFuture, Request, SuccessResponse,
+};
+use crate::context::UpdateContext;
+use crate::loader::Error as LoaderError;
+use crate::player::PlayerBuilder;
+use crate::socket::{SocketAction, SocketHandle};
+use crate::tag_utils::SwfMovie;
+use async_channel::{Receiver, Sender};
+use flash_lso::types::{AMFVersion, Value as AmfValue};
+use fnv::FnvHashMap;
+use gc_arena::Collect;
+use indexmap::IndexMap;
+use scheduler::{Turn, TurnEnd, Turns, WorkerRunner};
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::{Rc, Weak};
+use std::task::{Context, Poll, Waker};
+use std::time::Duration;
+use thiserror::Error;
+use url::{ParseError, Url};
+use web_time::Instant;
+
+mod scheduler;
+
+pub type WorkerId = u32;
+pub type ChannelId = u32;
+pub type MutexId = u32;
+pub type ConditionId = u32;
+pub type SharedBytesId = u32;
+
+/// The ID of the worker that runs the main SWF.
+pub const PRIMORDIAL_WORKER: WorkerId = 0;
+
+pub type WorkerGroupHandle = Rc<RefCell<WorkerGroup>>;
+
+/// The state of a worker, as in `flash.system.WorkerState`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WorkerState {
+    New,
+    Running,
+    Terminated,
+}
+
+impl WorkerState {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            WorkerState::New => "new",
+            WorkerState::Running => "running",
+            WorkerState::Terminated => "terminated",
+        }
+    }
+}
+
+/// The state of a message channel, as in `flash.system.MessageChannelState`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChannelState {
+    Open,
+    Closing,
+    Closed,
+}
+
+impl ChannelState {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ChannelState::Open => "open",
+            ChannelState::Closing => "closing",
+            ChannelState::Closed => "closed",
+        }
+    }
+}
+
+/// An object that is shared by reference between workers, rather than copied.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Collect)]
+#[collect(require_static)]
+pub enum SharedRef {
+    Worker(WorkerId),
+    MessageChannel(ChannelId),
+    Mutex(MutexId),
+    Condition(ConditionId),
+    ByteArray(SharedBytesId),
+}
+
+/// A value passed between workers with `Worker.setSharedProperty` or a
+/// `MessageChannel`.
+#[derive(Clone, Debug)]
+pub enum WorkerValue {
+    /// A value that is copied into the receiving worker, serialized as AMF3.
+    Copied(Rc<AmfValue>),
+
+    /// An object that is shared by reference.
+    Shared(SharedRef),
+}
+
+/// A notification for a worker, which is dispatched as an event to its
+/// object for the corresponding `SharedRef`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WorkerEvent {
+    /// `workerState`, dispatched to a `Worker`.
+    WorkerState(WorkerId),
+
+    /// `channelMessage`, dispatched to a `MessageChannel`.
+    ChannelMessage(ChannelId),
+
+    /// `channelState`, dispatched to a `MessageChannel`.
+    ChannelState(ChannelId),
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum WorkerError {
+    #[error("The message channel is closed")]
+    ChannelClosed,
+
+    #[error("The mutex is not owned by the current worker")]
+    MutexNotOwned,
+
+    #[error("A script in this movie has taken too long to execute and has been terminated.")]
+    ScriptTimeout,
+
+    #[error("The worker was terminated")]
+    Terminated,
+
+    /// Workers can't be parked on the web, so a background worker can't wait
+    /// for the workers further up the stack.
+    #[cfg(target_family = "wasm")]
+    #[error("The worker is blocked by workers that can't run until it continues")]
+    Unsupported,
+}
+
+struct WorkerData {
+    state: WorkerState,
+
+    /// The worker that created this one, which receives its `workerState` events.
+    parent: Option<WorkerId>,
+
+    /// The SWF of a background worker that hasn't been spawned yet.
+    movie: Option<SwfMovie>,
+
+    /// Runs the player of a background worker, once it was spawned.
+    runner: Option<WorkerRunner>,
+
+    /// Whether the background worker is parked in the middle of its tick.
+    parked: bool,
+
+    /// The futures of the worker that wait until it's no longer parked.
+    wakers: Vec<Waker>,
+
+    shared_properties: HashMap<String, WorkerValue>,
+
+    /// Events waiting to be dispatched in this worker.
+    events: Vec<WorkerEvent>,
+}
+
+impl WorkerData {
+    fn new(state: WorkerState, parent: Option<WorkerId>, movie: Option<SwfMovie>) -> Self {
+        Self {
+            state,
+            parent,
+            movie,
+            runner: None,
+            parked: false,
+            wakers: Vec::new(),
+            shared_properties: HashMap::new(),
+            events: Vec::new(),
+        }
+    }
+}
+
+struct ChannelData {
+    sender: WorkerId,
+    receiver: WorkerId,
+    state: ChannelState,
+    queue: VecDeque<WorkerValue>,
+}
+
+#[derive(Default)]
+struct MutexData {
+    owner: Option<WorkerId>,
+
+    /// How many times the owner has locked the mutex.
+    depth: u32,
+}
+
+struct ConditionData {
+    mutex: MutexId,
+
+    /// The workers waiting on this condition, in the order they started
+    /// waiting, and whether they were notified.
+    waiting: Vec<(WorkerId, bool)>,
+}
+
+struct SharedBytes {
+    bytes: Vec<u8>,
+
+    /// Incremented whenever a worker changes `bytes`.
+    version: u64,
+}
+
+/// The state shared by all workers of a SWF.
+pub struct WorkerGroup {
+    workers: Vec<WorkerData>,
+    channels: Vec<ChannelData>,
+    mutexes: Vec<MutexData>,
+    conditions: Vec<ConditionData>,
+    shared_bytes: Vec<SharedBytes>,
+
+    /// The backends of the primordial worker's player, once a background
+    /// worker was spawned.
+    backends: Option<SharedBackends>,
+}
+
+impl WorkerGroup {
+    /// Create the workers of a SWF, which initially only contain the running
+    /// primordial worker.
+    pub fn new() -> Self {
+        Self {
+            workers: vec![WorkerData::new(WorkerState::Running, None, None)],
+            channels: Vec::new(),
+            mutexes: Vec::new(),
+            conditions: Vec::new(),
+            shared_bytes: Vec::new(),
+            backends: None,
+        }
+    }
+
+    pub fn new_handle() -> WorkerGroupHandle {
+        Rc::new(RefCell::new(Self::new()))
+    }
+
+    /// Create a background worker running `movie`, which starts once
+    /// `start_worker` is called.
+    pub fn create_worker(&mut self, parent: WorkerId, movie: SwfMovie) -> WorkerId {
+        self.workers
+            .push(WorkerData::new(WorkerState::New, Some(parent), Some(movie)));
+        (self.workers.len() - 1) as WorkerId
+    }
+
+    /// Whether any background worker was created.
+    pub fn has_background_workers(&self) -> bool {
+        self.workers.len() > 1
+    }
+
+    /// All workers that haven't been terminated.
+    pub fn live_workers(&self) -> Vec<WorkerId> {
+        (0..self.workers.len() as WorkerId)
+            .filter(|id| self.worker_state(*id) != WorkerState::Terminated)
+            .collect()
+    }
+
+    pub fn worker_state(&self, worker: WorkerId) -> WorkerState {
+        self.workers[worker as usize].state
+    }
+
+    fn set_worker_state(&mut self, worker: WorkerId, state: WorkerState) {
+        let data = &mut self.workers[worker as usize];
+        data.state = state;
+        if let Some(parent) = data.parent {
+            self.push_event(parent, WorkerEvent::WorkerState(worker));
+        }
+    }
+
+    /// Start a new worker. Its player is spawned on the next tick of the
+    /// primordial worker.
+    pub fn start_worker(&mut self, worker: WorkerId) {
+        if self.worker_state(worker) == WorkerState::New {
+            self.set_worker_state(worker, WorkerState::Running);
+        }
+    }
+
+    /// Terminate a worker, returning whether it was running.
+    ///
+    /// The primordial worker can't be terminated. The player of a background
+    /// worker is stopped on the next turn it would get.
+    pub fn terminate_worker(&mut self, worker: WorkerId) -> bool {
+        if worker == PRIMORDIAL_WORKER || self.worker_state(worker) == WorkerState::Terminated {
+            return false;
+        }
+
+        let was_running = self.worker_state(worker) == WorkerState::Running;
+        self.set_worker_state(worker, WorkerState::Terminated);
+        let data = &mut self.workers[worker as usize];
+        data.movie = None;
+        data.events.clear();
+        was_running
+    }
+
+    /// Take the SWFs of the workers that were started, but don't have a player yet.
+    pub fn workers_to_spawn(&mut self) -> Vec<(WorkerId, SwfMovie)> {
+        self.workers
+            .iter_mut()
+            .enumerate()
+            .filter(|(_, data)| data.state == WorkerState::Running)
+            .filter_map(|(id, data)| Some((id as WorkerId, data.movie.take()?)))
+            .collect()
+    }
+
+    /// Spawn the player of a started background worker, which runs from its
+    /// next turn on.
+    pub fn spawn_worker(&mut self, worker: WorkerId, builder: PlayerBuilder) {
+        self.workers[worker as usize].runner = Some(WorkerRunner::spawn(worker, builder));
+    }
+
+    /// The background workers that were spawned and weren't stopped yet.
+    fn spawned_workers(&self) -> Vec<WorkerId> {
+        (0..self.workers.len() as WorkerId)
+            .filter(|id| self.workers[*id as usize].runner.is_some())
+            .collect()
+    }
+
+    /// The next turn of a spawned worker, and how to give it.
+    fn next_turn(&self, worker: WorkerId, dt: f64) -> Option<(Turn, Turns)> {
+        let data = &self.workers[worker as usize];
+        let turn = if data.parked {
+            Turn::Resume
+        } else {
+            Turn::Tick(dt)
+        };
+        Some((turn, data.runner.as_ref()?.turns()))
+    }
+
+    /// Record whether a worker was parked at the end of its turn, returning
+    /// the wakers of its futures if it's no longer parked.
+    fn set_parked(&mut self, worker: WorkerId, parked: bool) -> Vec<Waker> {
+        let data = &mut self.workers[worker as usize];
+        data.parked = parked;
+        if parked {
+            Vec::new()
+        } else {
+            std::mem::take(&mut data.wakers)
+        }
+    }
+
+    pub fn shared_property(&self, worker: WorkerId, key: &str) -> Option<WorkerValue> {
+        self.workers[worker as usize]
+            .shared_properties
+            .get(key)
+            .cloned()
+    }
+
+    pub fn set_shared_property(&mut self, worker: WorkerId, key: String, value: WorkerValue) {
+        self.workers[worker as usize]
+            .shared_properties
+            .insert(key, value);
+    }
+
+    fn push_event(&mut self, worker: WorkerId, event: WorkerEvent) {
+        let data = &mut self.workers[worker as usize];
+        if data.state != WorkerState::Terminated && !data.events.contains(&event) {
+            data.events.push(event);
+        }
+    }
+
+    /// Take the events waiting to be dispatched in `worker`.
+    pub fn take_events(&mut self, worker: WorkerId) -> Vec<WorkerEvent> {
+        std::mem::take(&mut self.workers[worker as usize].events)
+    }
+
+    pub fn create_channel(&mut self, sender: WorkerId, receiver: WorkerId) -> ChannelId {
+        self.channels.push(ChannelData {
+            sender,
+            receiver,
+            state: ChannelState::Open,
+            queue: VecDeque::new(),
+        });
+        (self.channels.len() - 1) as ChannelId
+    }
+
+    pub fn channel_state(&self, channel: ChannelId) -> ChannelState {
+        self.channels[channel as usize].state
+    }
+
+    pub fn channel_queue_len(&self, channel: ChannelId) -> usize {
+        self.channels[channel as usize].queue.len()
+    }
+
+    fn set_channel_state(&mut self, channel: ChannelId, state: ChannelState) {
+        let data = &mut self.channels[channel as usize];
+        data.state = state;
+        let (sender, receiver) = (data.sender, data.receiver);
+        self.push_event(sender, WorkerEvent::ChannelState(channel));
+        if receiver != sender {
+            self.push_event(receiver, WorkerEvent::ChannelState(channel));
+        }
+    }
+
+    /// Queue a message on a channel, notifying its receiver.
+    pub fn send(&mut self, channel: ChannelId, value: WorkerValue) -> Result<(), WorkerError> {
+        let data = &mut self.channels[channel as usize];
+        if data.state != ChannelState::Open {
+            return Err(WorkerError::ChannelClosed);
+        }
+        data.queue.push_back(value);
+        let receiver = data.receiver;
+        self.push_event(receiver, WorkerEvent::ChannelMessage(channel));
+        Ok(())
+    }
+
+    /// Take the next message of a channel, if there is one.
+    pub fn receive(&mut self, channel: ChannelId) -> Result<Option<WorkerValue>, WorkerError> {
+        let data = &mut self.channels[channel as usize];
+        if data.state == ChannelState::Closed {
+            return Err(WorkerError::ChannelClosed);
+        }
+        let value = data.queue.pop_front();
+        if data.state == ChannelState::Closing && data.queue.is_empty() {
+            self.set_channel_state(channel, ChannelState::Closed);
+        }
+        Ok(value)
+    }
+
+    /// Close a channel. It stays in the `Closing` state until its remaining
+    /// messages have been received.
+    pub fn close_channel(&mut self, channel: ChannelId) {
+        let data = &self.channels[channel as usize];
+        if data.state != ChannelState::Open {
+            return;
+        }
+        let state = if data.queue.is_empty() {
+            ChannelState::Closed
+        } else {
+            ChannelState::Closing
+        };
+        self.set_channel_state(channel, state);
+    }
+
+    pub fn create_mutex(&mut self) -> MutexId {
+        self.mutexes.push(MutexData::default());
+        (self.mutexes.len() - 1) as MutexId
+    }
+
+    pub fn owns_mutex(&self, mutex: MutexId, worker: WorkerId) -> bool {
+        self.mutexes[mutex as usize].owner == Some(worker)
+    }
+
+    /// Lock a mutex, unless it's owned by another worker. A worker can lock a
+    /// mutex it owns again, and must unlock it as many times.
+    pub fn try_lock(&mut self, mutex: MutexId, worker: WorkerId) -> bool {
+        let data = &mut self.mutexes[mutex as usize];
+        match data.owner {
+            Some(owner) if owner != worker => false,
+            _ => {
+                data.owner = Some(worker);
+                data.depth += 1;
+                true
+            }
+        }
+    }
+
+    pub fn unlock(&mut self, mutex: MutexId, worker: WorkerId) -> Result<(), WorkerError> {
+        let data = &mut self.mutexes[mutex as usize];
+        if data.owner != Some(worker) {
+            return Err(WorkerError::MutexNotOwned);
+        }
+        data.depth -= 1;
+        if data.depth == 0 {
+            data.owner = None;
+        }
+        Ok(())
+    }
+
+    /// Lock a mutex that the worker unlocked completely to wait on a
+    /// condition, as many times as it had locked it.
+    pub fn try_relock(&mut self, mutex: MutexId, worker: WorkerId, depth: u32) -> bool {
+        let data = &mut self.mutexes[mutex as usize];
+        if data.owner.is_some() {
+            return false;
+        }
+        data.owner = Some(worker);
+        data.depth = depth;
+        true
+    }
+
+    pub fn create_condition(&mut self, mutex: MutexId) -> ConditionId {
+        self.conditions.push(ConditionData {
+            mutex,
+            waiting: Vec::new(),
+        });
+        (self.conditions.len() - 1) as ConditionId
+    }
+
+    pub fn condition_mutex(&self, condition: ConditionId) -> MutexId {
+        self.conditions[condition as usize].mutex
+    }
+
+    /// Start waiting on a condition, unlocking its mutex completely so that
+    /// other workers can lock it. Returns how many times the worker had
+    /// locked the mutex.
+    pub fn start_wait(
+        &mut self,
+        condition: ConditionId,
+        worker: WorkerId,
+    ) -> Result<u32, WorkerError> {
+        let data = &mut self.conditions[condition as usize];
+        let mutex = &mut self.mutexes[data.mutex as usize];
+        if mutex.owner != Some(worker) {
+            return Err(WorkerError::MutexNotOwned);
+        }
+        let depth = std::mem::take(&mut mutex.depth);
+        mutex.owner = None;
+        data.waiting.push((worker, false));
+        Ok(depth)
+    }
+
+    pub fn is_notified(&self, condition: ConditionId, worker: WorkerId) -> bool {
+        self.conditions[condition as usize]
+            .waiting
+            .contains(&(worker, true))
+    }
+
+    /// Stop waiting on a condition, returning whether the worker was notified.
+    pub fn stop_wait(&mut self, condition: ConditionId, worker: WorkerId) -> bool {
+        let waiting = &mut self.conditions[condition as usize].waiting;
+        let Some(index) = waiting.iter().position(|(waiter, _)| *waiter == worker) else {
+            return false;
+        };
+        waiting.remove(index).1
+    }
+
+    /// Notify the first worker waiting on a condition, or all of them.
+    pub fn notify(
+        &mut self,
+        condition: ConditionId,
+        worker: WorkerId,
+        all: bool,
+    ) -> Result<(), WorkerError> {
+        let data = &mut self.conditions[condition as usize];
+        if self.mutexes[data.mutex as usize].owner != Some(worker) {
+            return Err(WorkerError::MutexNotOwned);
+        }
+        let waiting = data.waiting.iter_mut().filter(|(_, notified)| !notified);
+        for (_, notified) in waiting.take(if all { usize::MAX } else { 1 }) {
+            *notified = true;
+        }
+        Ok(())
+    }
+
+    /// Share the contents of a `ByteArray` between workers.
+    pub fn share_bytes(&mut self, bytes: Vec<u8>) -> SharedBytesId {
+        self.shared_bytes.push(SharedBytes { bytes, version: 0 });
+        (self.shared_bytes.len() - 1) as SharedBytesId
+    }
+
+    pub fn shared_bytes(&self, id: SharedBytesId) -> (&[u8], u64) {
+        let shared = &self.shared_bytes[id as usize];
+        (&shared.bytes, shared.version)
+    }
+
+    /// Update the contents of a shared `ByteArray`, returning its new version.
+    pub fn update_shared_bytes(&mut self, id: SharedBytesId, bytes: &[u8]) -> u64 {
+        let shared = &mut self.shared_bytes[id as usize];
+        if shared.bytes != bytes {
+            shared.bytes = bytes.to_vec();
+            shared.version += 1;
+        }
+        shared.version
+    }
+
+    fn has_shared_bytes(&self) -> bool {
+        !self.shared_bytes.is_empty()
+    }
+
+    pub fn shared_backends(&self) -> Option<&SharedBackends> {
+        self.backends.as_ref()
+    }
+
+    pub fn set_shared_backends(&mut self, backends: SharedBackends) {
+        self.backends = Some(backends);
+    }
+}
+
+impl Default for WorkerGroup {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The log and navigator of the primordial worker's player, shared with the
+/// players of background workers.
+pub struct SharedBackends {
+    log: Rc<dyn LogBackend>,
+    navigator: Rc<RefCell<Box<dyn NavigatorBackend>>>,
+}
+
+impl SharedBackends {
+    pub fn new(log: Box<dyn LogBackend>, navigator: Box<dyn NavigatorBackend>) -> Self {
+        Self {
+            log: log.into(),
+            navigator: Rc::new(RefCell::new(navigator)),
+        }
+    }
+
+    pub fn log(&self) -> SharedLogBackend {
+        SharedLogBackend(self.log.clone())
+    }
+
+    pub fn navigator(&self) -> SharedNavigatorBackend {
+        SharedNavigatorBackend {
+            navigator: self.navigator.clone(),
+            worker: None,
+        }
+    }
+
+    /// The navigator of a background worker, whose futures wait while the
+    /// worker is parked.
+    pub fn worker_navigator(
+        &self,
+        group: &WorkerGroupHandle,
+        worker: WorkerId,
+    ) -> SharedNavigatorBackend {
+        SharedNavigatorBackend {
+            navigator: self.navigator.clone(),
+            worker: Some((Rc::downgrade(group), worker)),
+        }
+    }
+}
+
+/// A proxy to the log of the primordial worker's player.
+pub struct SharedLogBackend(Rc<dyn LogBackend>);
+
+impl LogBackend for SharedLogBackend {
+    fn avm_trace(&self, message: &str) {
+        self.0.avm_trace(message);
+    }
+}
+
+/// A proxy to the navigator of the primordial worker's player.
+pub struct SharedNavigatorBackend {
+    navigator: Rc<RefCell<Box<dyn NavigatorBackend>>>,
+
+    /// The background worker this navigator belongs to.
+    worker: Option<(Weak<RefCell<WorkerGroup>>, WorkerId)>,
+}
+
+/// A future of a background worker, which waits while the worker is parked:
+/// the player of a parked worker stays locked until it continues.
+struct UnlessParked {
+    group: Weak<RefCell<WorkerGroup>>,
+    worker: WorkerId,
+    future: OwnedFuture<(), LoaderError>,
+}
+
+impl Future for UnlessParked {
+    type Output = Result<(), LoaderError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if let Some(group) = self.group.upgrade() {
+            let mut group = group.borrow_mut();
+            let data = &mut group.workers[self.worker as usize];
+            if data.parked {
+                data.wakers.push(cx.waker().clone());
+                return Poll::Pending;
+            }
+        }
+        self.future.as_mut().poll(cx)
+    }
+}
+
+impl NavigatorBackend for SharedNavigatorBackend {
+    fn navigate_to_url(
+        &self,
+        url: &str,
+        target: &str,
+        vars_method: Option<(NavigationMethod, IndexMap<String, String>)>,
+    ) {
+        self.navigator
+            .borrow()
+            .navigate_to_url(url, target, vars_method);
+    }
+
+    fn fetch(&self, request: Request) -> OwnedFuture<Box<dyn SuccessResponse>, ErrorResponse> {
+        self.navigator.borrow().fetch(request)
+    }
+
+    fn resolve_url(&self, url: &str) -> Result<Url, ParseError> {
+        self.navigator.borrow().resolve_url(url)
+    }
+
+    fn spawn_future(&mut self, future: OwnedFuture<(), LoaderError>) {
+        let future: OwnedFuture<(), LoaderError> = match &self.worker {
+            Some((group, worker)) => Box::pin(UnlessParked {
+                group: group.clone(),
+                worker: *worker,
+                future,
+            }),
+            None => future,
+        };
+        self.navigator.borrow_mut().spawn_future(future);
+    }
+
+    fn pre_process_url(&self, url: Url) -> Url {
+        self.navigator.borrow().pre_process_url(url)
+    }
+
+    fn connect_socket(
+        &mut self,
+        host: String,
+        port: u16,
+        timeout: Duration,
+        handle: SocketHandle,
+        receiver: Receiver<Vec<u8>>,
+        sender: Sender<SocketAction>,
+    ) {
+        self.navigator
+            .borrow_mut()
+            .connect_socket(host, port, timeout, handle, receiver, sender);
+    }
+}
+
+/// The objects of an AVM2 instance that represent shared worker state.
+#[derive(Collect, Default)]
+#[collect(no_drop)]
+pub struct WorkerObjects<'gc> {
+    objects: FnvHashMap<SharedRef, Object<'gc>>,
+    refs: FnvHashMap<Object<'gc>, SharedRef>,
+
+    /// The shareable `ByteArray`s of this worker, with the version of their
+    /// contents they were last synchronized with.
+    shared_byte_arrays: Vec<(Object<'gc>, SharedBytesId, u64)>,
+}
+
+impl<'gc> WorkerObjects<'gc> {
+    pub fn object(&self, shared: SharedRef) -> Option<Object<'gc>> {
+        self.objects.get(&shared).copied()
+    }
+
+    /// The shared state an object represents.
+    pub fn shared_ref(&self, object: Object<'gc>) -> Option<SharedRef> {
+        self.refs.get(&object).copied()
+    }
+
+    pub fn register(&mut self, shared: SharedRef, object: Object<'gc>) {
+        self.objects.insert(shared, object);
+        self.refs.insert(object, shared);
+    }
+}
+
+/// Create an object of a class that can't be constructed from ActionScript,
+/// such as a worker or a capture device, without running its constructor.
+pub fn construct_native<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    class: ClassObject<'gc>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    let instance = scriptobject_allocator(class, activation)?;
+    instance.install_instance_slots(activation.gc());
+    class.call_native_init(instance.into(), &[], activation)?;
+    Ok(instance)
+}
+
+/// Get the object of this worker for some shared state, creating it if needed.
+pub fn shared_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    shared: SharedRef,
+) -> Result<Object<'gc>, Error<'gc>> {
+    if let Some(object) = activation.avm2().worker_objects.object(shared) {
+        return Ok(object);
+    }
+
+    let classes = activation.avm2().classes();
+    let object = match shared {
+        SharedRef::Worker(_) => {
+            let class = classes.worker;
+            construct_native(activation, class)?
+        }
+        SharedRef::MessageChannel(_) => {
+            let class = classes.messagechannel;
+            construct_native(activation, class)?
+        }
+        SharedRef::Mutex(_) => {
+            let class = classes.mutex;
+            construct_native(activation, class)?
+        }
+        SharedRef::Condition(_) => {
+            let class = classes.condition;
+            construct_native(activation, class)?
+        }
+        SharedRef::ByteArray(id) => {
+            let (bytes, version) = {
+                let group = activation.context.worker_group.borrow();
+                let (bytes, version) = group.shared_bytes(id);
+                (bytes.to_vec(), version)
+            };
+            let object =
+                ByteArrayObject::from_storage(activation, ByteArrayStorage::from_vec(bytes))?;
+            object.set_property(
+                &Multiname::new(activation.avm2().flash_utils_internal, "_shareable"),
+                true.into(),
+                activation,
+            )?;
+            activation
+                .avm2()
+                .worker_objects
+                .shared_byte_arrays
+                .push((object, id, version));
+            object
+        }
+    };
+    activation.avm2().worker_objects.register(shared, object);
+    Ok(object)
+}
+
+/// Convert a value to be passed to another worker.
+///
+/// Workers, message channels, mutexes, conditions and shareable `ByteArray`s
+/// are passed by reference; anything else is copied using AMF3.
+pub fn to_worker_value<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    value: Value<'gc>,
+) -> Result<WorkerValue, Error<'gc>> {
+    if let Value::Object(object) = value {
+        if let Some(shared) = activation.avm2().worker_objects.shared_ref(object) {
+            return Ok(WorkerValue::Shared(shared));
+        }
+
+        if object.as_bytearray().is_some()
+            && object
+                .get_property(
+                    &Multiname::new(activation.avm2().flash_utils_internal, "_shareable"),
+                    activation,
+                )?
+                .coerce_to_boolean()
+        {
+            let bytes = object
+                .as_bytearray()
+                .expect("Object is a ByteArray")
+                .bytes()
+                .to_vec();
+            let id = activation
+                .context
+                .worker_group
+                .borrow_mut()
+                .share_bytes(bytes);
+            let worker_objects = &mut activation.avm2().worker_objects;
+            worker_objects.register(SharedRef::ByteArray(id), object);
+            worker_objects.shared_byte_arrays.push((object, id, 0));
+            return Ok(WorkerValue::Shared(SharedRef::ByteArray(id)));
+        }
+    }
+
+    let amf = serialize_value(activation, value, AMFVersion::AMF3, &mut Default::default())
+        .unwrap_or(AmfValue::Undefined);
+    Ok(WorkerValue::Copied(Rc::new(amf)))
+}
+
+/// Convert a value passed from another worker.
+pub fn from_worker_value<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    value: &WorkerValue,
+) -> Result<Value<'gc>, Error<'gc>> {
+    match value {
+        WorkerValue::Copied(amf) => deserialize_value(activation, amf),
+        WorkerValue::Shared(shared) => Ok(shared_object(activation, *shared)?.into()),
+    }
+}
+
+/// Copy the changes other workers made to shareable `ByteArray`s into this worker.
+pub fn receive_shared_bytes(context: &mut UpdateContext<'_>) {
+    let group = context.worker_group.borrow();
+    if !group.has_shared_bytes() {
+        return;
+    }
+
+    for (object, id, synced_version) in &mut context.avm2.worker_objects.shared_byte_arrays {
+        let (bytes, version) = group.shared_bytes(*id);
+        if version == *synced_version {
+            continue;
+        }
+        if let Some(mut storage) = object.as_bytearray_mut() {
+            storage.set_length(bytes.len());
+            storage.bytes_mut().copy_from_slice(bytes);
+        }
+        *synced_version = version;
+    }
+}
+
+/// Publish the changes this worker made to shareable `ByteArray`s to other workers.
+pub fn publish_shared_bytes(context: &mut UpdateContext<'_>) {
+    let mut group = context.worker_group.borrow_mut();
+    if !group.has_shared_bytes() {
+        return;
+    }
+
+    for (object, id, synced_version) in &mut context.avm2.worker_objects.shared_byte_arrays {
+        if let Some(storage) = object.as_bytearray() {
+            *synced_version = group.update_shared_bytes(*id, storage.bytes());
+        }
+    }
+}
+
+/// Give every spawned background worker a turn: a parked worker continues
+/// its tick, and any other worker runs a new tick. The players of terminated
+/// workers are stopped instead.
+///
+/// Returns whether any worker ran.
+pub fn run_background_workers(group: &WorkerGroupHandle, dt: f64) -> bool {
+    run_workers_except(group, dt, PRIMORDIAL_WORKER)
+}
+
+/// Stop the players of all background workers.
+pub fn stop_background_workers(group: &WorkerGroupHandle) {
+    let workers = group.borrow().spawned_workers();
+    for worker in workers {
+        let runner = {
+            let mut group = group.borrow_mut();
+            group.terminate_worker(worker);
+            group.workers[worker as usize].runner.take()
+        };
+        if let Some(runner) = runner {
+            runner.stop();
+        }
+    }
+}
+
+fn run_workers_except(group: &WorkerGroupHandle, dt: f64, except: WorkerId) -> bool {
+    let workers = group.borrow().spawned_workers();
+    let mut ran = false;
+    for worker in workers {
+        if worker == except {
+            continue;
+        }
+
+        if group.borrow().worker_state(worker) == WorkerState::Terminated {
+            let runner = group.borrow_mut().workers[worker as usize].runner.take();
+            if let Some(runner) = runner {
+                runner.stop();
+            }
+            continue;
+        }
+
+        // The worker can use the group during its turn, so it can't stay borrowed.
+        let Some((turn, turns)) = group.borrow().next_turn(worker, dt) else {
+            continue;
+        };
+        if let Some(end) = turns.run(turn) {
+            ran = true;
+            let wakers = group
+                .borrow_mut()
+                .set_parked(worker, end == TurnEnd::Parked);
+            wakers.into_iter().for_each(Waker::wake);
+        }
+    }
+    ran
+}
+
+/// Block the current worker until `ready` returns true.
+///
+/// A background worker is parked until a later turn meanwhile, while the
+/// primordial worker gives the background workers turns until it can
+/// continue.
+///
+/// Returns false if `ready` didn't return true before the `timeout`. Without
+/// a timeout, the primordial worker gives up after the maximum execution
+/// duration of scripts.
+pub fn block_worker(
+    context: &mut UpdateContext<'_>,
+    timeout: Option<Duration>,
+    mut ready: impl FnMut(&mut WorkerGroup) -> bool,
+) -> Result<bool, WorkerError> {
+    #[cfg(not(target_family = "wasm"))]
+    {
+        let baton = context.worker_group.borrow().workers[context.worker_id as usize]
+            .runner
+            .as_ref()
+            .and_then(|runner| runner.baton_on_current_thread());
+        if let Some(baton) = baton {
+            return park_worker(context, &baton, timeout, ready);
+        }
+    }
+
+    let worker = context.worker_id;
+    let group = context.worker_group.clone();
+    let start = Instant::now();
+    let mut last_tick = start;
+    loop {
+        if ready(&mut group.borrow_mut()) {
+            return Ok(true);
+        }
+
+        let now = Instant::now();
+        match timeout {
+            Some(timeout) if now - start >= timeout => return Ok(false),
+            None if now - start >= context.max_execution_duration => {
+                return Err(WorkerError::ScriptTimeout)
+            }
+            _ => {}
+        }
+
+        let dt = (now - last_tick).as_secs_f64() * 1000.0;
+        last_tick = now;
+        publish_shared_bytes(context);
+        let ran = run_workers_except(&group, dt, worker);
+        receive_shared_bytes(context);
+        if !ran {
+            // Nothing can change until this worker continues.
+            return match timeout {
+                Some(_) => Ok(false),
+                #[cfg(target_family = "wasm")]
+                None if worker != PRIMORDIAL_WORKER => Err(WorkerError::Unsupported),
+                None => Err(WorkerError::ScriptTimeout),
+            };
+        }
+    }
+}
+
+/// Park a background worker on its thread until `ready` returns true.
+#[cfg(not(target_family = "wasm"))]
+fn park_worker(
+    context: &mut UpdateContext<'_>,
+    baton: &scheduler::Baton,
+    timeout: Option<Duration>,
+    mut ready: impl FnMut(&mut WorkerGroup) -> bool,
+) -> Result<bool, WorkerError> {
+    let start = Instant::now();
+    loop {
+        if baton.is_stopped() {
+            return Err(WorkerError::Terminated);
+        }
+        if ready(&mut context.worker_group.borrow_mut()) {
+            return Ok(true);
+        }
+        if timeout.is_some_and(|timeout| start.elapsed() >= timeout) {
+            return Ok(false);
+        }
+
+        publish_shared_bytes(context);
+        let parked_at = Instant::now();
+        baton.end_turn(TurnEnd::Parked);
+        let turn = baton.wait_for_turn();
+        // Time spent parked doesn't count towards the script timeout.
+        context.update_start += parked_at.elapsed();
+        if turn == Turn::Stop {
+            return Err(WorkerError::Terminated);
+        }
+        receive_shared_bytes(context);
+    }
+}
+
+/// Dispatch the events other workers sent to this worker.
+pub fn dispatch_worker_events(context: &mut UpdateContext<'_>) {
+    let worker = context.worker_id;
+    let events = context.worker_group.borrow_mut().take_events(worker);
+
+    for event in events {
+        let (shared, event_type) = match event {
+            WorkerEvent::WorkerState(id) => (SharedRef::Worker(id), "workerState"),
+            WorkerEvent::ChannelMessage(id) => (SharedRef::MessageChannel(id), "channelMessage"),
+            WorkerEvent::ChannelState(id) => (SharedRef::MessageChannel(id), "channelState"),
+        };
+
+        if let Some(target) = context.avm2.worker_objects.object(shared) {
+            let event = EventObject::bare_default_event(context, event_type);
+            Avm2::dispatch_event(context, event, target);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn copied(value: f64) -> WorkerValue {
+        WorkerValue::Copied(Rc::new(AmfValue::Number(value)))
+    }
+
+    fn number(value: Option<WorkerValue>) -> Option<f64> {
+        match value? {
+            WorkerValue::Copied(amf) => match *amf {
+                AmfValue::Number(value) => Some(value),
+                _ => None,
+            },
+            WorkerValue::Shared(_) => None,
+        }
+    }
+
+    #[test]
+    fn worker_lifecycle() {
+        let mut group = WorkerGroup::new();
+        let worker = group.create_worker(PRIMORDIAL_WORKER, SwfMovie::empty(32));
+        assert_eq!(group.worker_state(worker), WorkerState::New);
+        assert!(group.workers_to_spawn().is_empty());
+
+        group.start_worker(worker);
+        assert_eq!(group.worker_state(worker), WorkerState::Running);
+        assert_eq!(group.workers_to_spawn().len(), 1);
+        assert!(group.workers_to_spawn().is_empty());
+        assert_eq!(
+            group.take_events(PRIMORDIAL_WORKER),
+            vec![WorkerEvent::WorkerState(worker)]
+        );
+
+        assert!(group.terminate_worker(worker));
+        assert!(!group.terminate_worker(worker));
+        assert!(!group.terminate_worker(PRIMORDIAL_WORKER));
+        assert_eq!(group.live_workers(), vec![PRIMORDIAL_WORKER]);
+    }
+
+    #[test]
+    fn channel_messages() {
+        let mut group = WorkerGroup::new();
+        let worker = group.create_worker(PRIMORDIAL_WORKER, SwfMovie::empty(32));
+        let channel = group.create_channel(PRIMORDIAL_WORKER, worker);
+
+        group.send(channel, copied(1.0)).unwrap();
+        group.send(channel, copied(2.0)).unwrap();
+        assert_eq!(
+            group.take_events(worker),
+            vec![WorkerEvent::ChannelMessage(channel)]
+        );
+
+        group.close_channel(channel);
+        assert_eq!(group.channel_state(channel), ChannelState::Closing);
+        assert_eq!(
+            group.send(channel, copied(3.0)),
+            Err(WorkerError::ChannelClosed)
+        );
+
+        assert_eq!(number(group.receive(channel).unwrap()), Some(1.0));
+        assert_eq!(number(group.receive(channel).unwrap()), Some(2.0));
+        assert_eq!(group.channel_state(channel), ChannelState::Closed);
+        assert!(matches!(
+            group.receive(channel),
+            Err(WorkerError::ChannelClosed)
+        ));
+    }
+
+    #[test]
+    fn recursive_mutex() {
+        let mut group = WorkerGroup::new();
+        let worker = group.create_worker(PRIMORDIAL_WORKER, SwfMovie::empty(32));
+        let mutex = group.create_mutex();
+
+        assert!(group.try_lock(mutex, PRIMORDIAL_WORKER));
+        assert!(group.try_lock(mutex, PRIMORDIAL_WORKER));
+        assert!(!group.try_lock(mutex, worker));
+        assert_eq!(group.unlock(mutex, worker), Err(WorkerError::MutexNotOwned));
+
+        group.unlock(mutex, PRIMORDIAL_WORKER).unwrap();
+        assert!(group.owns_mutex(mutex, PRIMORDIAL_WORKER));
+        group.unlock(mutex, PRIMORDIAL_WORKER).unwrap();
+        assert!(!group.owns_mutex(mutex, PRIMORDIAL_WORKER));
+        assert!(group.try_lock(mutex, worker));
+    }
+
+    #[test]
+    fn condition_handshake() {
+        let mut group = WorkerGroup::new();
+        let worker = group.create_worker(PRIMORDIAL_WORKER, SwfMovie::empty(32));
+        let mutex = group.create_mutex();
+        let condition = group.create_condition(mutex);
+
+        assert_eq!(
+            group.start_wait(condition, worker),
+            Err(WorkerError::MutexNotOwned)
+        );
+        assert!(group.try_lock(mutex, PRIMORDIAL_WORKER));
+        assert!(group.try_lock(mutex, PRIMORDIAL_WORKER));
+        assert_eq!(group.start_wait(condition, PRIMORDIAL_WORKER), Ok(2));
+
+        // The mutex is released while waiting, so another worker can notify.
+        assert!(group.try_lock(mutex, worker));
+        assert!(!group.is_notified(condition, PRIMORDIAL_WORKER));
+        group.notify(condition, worker, false).unwrap();
+        assert!(group.is_notified(condition, PRIMORDIAL_WORKER));
+
+        assert!(!group.try_relock(mutex, PRIMORDIAL_WORKER, 2));
+        group.unlock(mutex, worker).unwrap();
+        assert!(group.try_relock(mutex, PRIMORDIAL_WORKER, 2));
+        assert!(group.stop_wait(condition, PRIMORDIAL_WORKER));
+        assert!(!group.stop_wait(condition, PRIMORDIAL_WORKER));
+
+        group.unlock(mutex, PRIMORDIAL_WORKER).unwrap();
+        group.unlock(mutex, PRIMORDIAL_WORKER).unwrap();
+        assert!(!group.owns_mutex(mutex, PRIMORDIAL_WORKER));
+    }
+
+    #[test]
+    fn notify_waiting_workers_in_order() {
+        let mut group = WorkerGroup::new();
+        let first = group.create_worker(PRIMORDIAL_WORKER, SwfMovie::empty(32));
+        let second = group.create_worker(PRIMORDIAL_WORKER, SwfMovie::empty(32));
+        let mutex = group.create_mutex();
+        let condition = group.create_condition(mutex);
+
+        for worker in [first, second] {
+            assert!(group.try_lock(mutex, worker));
+            assert_eq!(group.start_wait(condition, worker), Ok(1));
+        }
+
+        assert!(group.try_lock(mutex, PRIMORDIAL_WORKER));
+        group.notify(condition, PRIMORDIAL_WORKER, false).unwrap();
+        assert!(group.is_notified(condition, first));
+        assert!(!group.is_notified(condition, second));
+        group.notify(condition, PRIMORDIAL_WORKER, true).unwrap();
+        assert!(group.is_notified(condition, second));
+    }
+
+    #[test]
+    fn shared_bytes_versions() {
+        let mut group = WorkerGroup::new();
+        let id = group.share_bytes(vec![1, 2, 3]);
+        assert_eq!(group.update_shared_bytes(id, &[1, 2, 3]), 0);
+        assert_eq!(group.update_shared_bytes(id, &[4, 5]), 1);
+        assert_eq!(group.shared_bytes(id), (&[4, 5][..], 1));
+    }
+}
@@ -0,0 +1,330 @@
+//! Running the players of background workers
+//!
+//! On native platforms, the player of every background worker lives on its
+//! own thread, but only one worker runs at a time: the primordial worker
+//! hands each background worker a turn through its `Baton`, and waits until
+//! the worker hands it back. A worker that is blocked on a `Mutex` or
+//! `Condition` hands back its turn in the middle of its tick, and is parked
+//! until it's given another turn.
+//!
+//! Threads aren't available on the web, so there the players of background
+//! workers are ticked on the stack of the worker that runs them, and can't
+//! be parked.
+
+use super::WorkerId;
+use crate::player::{Player, PlayerBuilder};
+use std::sync::{Arc, Mutex};
+
+#[cfg(not(target_family = "wasm"))]
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    sync::{Condvar, PoisonError},
+    thread::JoinHandle,
+};
+
+/// What a background worker does with its turn.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) enum Turn {
+    /// Run a new tick.
+    Tick(f64),
+
+    /// Continue the tick the worker was parked in.
+    Resume,
+
+    /// Stop the worker, which was terminated.
+    Stop,
+}
+
+/// How a background worker ended its turn.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum TurnEnd {
+    Finished,
+    Parked,
+}
+
+#[cfg(not(target_family = "wasm"))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum BatonState {
+    /// The scheduler is running, and the worker waits for its turn.
+    Scheduler,
+
+    /// The worker is running its turn.
+    Worker(Turn),
+
+    /// The worker handed its turn back.
+    Ended(TurnEnd),
+
+    /// The thread of the worker exited.
+    Exited,
+}
+
+/// Hands turns back and forth between the scheduler and the thread of a
+/// background worker, so that only one of them runs at a time.
+#[cfg(not(target_family = "wasm"))]
+pub(super) struct Baton {
+    state: Mutex<BatonState>,
+    changed: Condvar,
+
+    /// Whether the worker was given a `Stop` turn.
+    stopped: AtomicBool,
+}
+
+#[cfg(not(target_family = "wasm"))]
+impl Baton {
+    fn new() -> Self {
+        Self {
+            state: Mutex::new(BatonState::Scheduler),
+            changed: Condvar::new(),
+            stopped: AtomicBool::new(false),
+        }
+    }
+
+    fn set_state(&self, state: BatonState) {
+        *self.state.lock().unwrap_or_else(PoisonError::into_inner) = state;
+        self.changed.notify_all();
+    }
+
+    /// Give the worker a turn, and wait until it's handed back.
+    fn run(&self, turn: Turn) -> TurnEnd {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        if *state == BatonState::Exited {
+            return TurnEnd::Finished;
+        }
+        *state = BatonState::Worker(turn);
+        self.changed.notify_all();
+        loop {
+            match *state {
+                BatonState::Ended(end) => {
+                    *state = BatonState::Scheduler;
+                    return end;
+                }
+                BatonState::Exited => return TurnEnd::Finished,
+                _ => {
+                    state = self
+                        .changed
+                        .wait(state)
+                        .unwrap_or_else(PoisonError::into_inner)
+                }
+            }
+        }
+    }
+
+    /// Wait until the worker is given a turn.
+    pub(super) fn wait_for_turn(&self) -> Turn {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        loop {
+            if let BatonState::Worker(turn) = *state {
+                if turn == Turn::Stop {
+                    self.stopped.store(true, Ordering::Relaxed);
+                }
+                return turn;
+            }
+            state = self
+                .changed
+                .wait(state)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+    }
+
+    /// Hand the turn of the worker back to the scheduler.
+    pub(super) fn end_turn(&self, end: TurnEnd) {
+        self.set_state(BatonState::Ended(end));
+    }
+
+    /// Whether the worker was stopped, after which it can't be parked anymore.
+    pub(super) fn is_stopped(&self) -> bool {
+        self.stopped.load(Ordering::Relaxed)
+    }
+}
+
+/// Moves the state of a background worker, which isn't `Send`, to its thread.
+#[cfg(not(target_family = "wasm"))]
+struct SendToWorker<T>(T);
+
+// SAFETY: The state of a worker is shared with the other workers through
+// `Rc`s, but it's only ever accessed by the thread that holds the baton.
+// Threads hand the baton to each other through the mutex of a `Baton`, which
+// orders their accesses.
+#[cfg(not(target_family = "wasm"))]
+unsafe impl<T> Send for SendToWorker<T> {}
+
+#[cfg(not(target_family = "wasm"))]
+impl<T> SendToWorker<T> {
+    fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+/// Marks the thread of a worker as exited once everything on it was dropped,
+/// even if it panicked.
+#[cfg(not(target_family = "wasm"))]
+struct ExitGuard(Arc<Baton>);
+
+#[cfg(not(target_family = "wasm"))]
+impl Drop for ExitGuard {
+    fn drop(&mut self) {
+        self.0.set_state(BatonState::Exited);
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn run_worker_thread(baton: &Baton, builder: PlayerBuilder) {
+    let mut builder = Some(builder);
+    let mut player: Option<Arc<Mutex<Player>>> = None;
+    loop {
+        match baton.wait_for_turn() {
+            Turn::Tick(dt) => {
+                let player = player.get_or_insert_with(|| {
+                    builder
+                        .take()
+                        .expect("The player is built on the first turn")
+                        .build()
+                });
+                player.lock().unwrap().tick(dt);
+            }
+            Turn::Resume => {}
+            Turn::Stop => break,
+        }
+        if baton.is_stopped() {
+            // The worker was stopped while it was parked.
+            break;
+        }
+        baton.end_turn(TurnEnd::Finished);
+    }
+}
+
+/// Gives turns to a background worker.
+#[derive(Clone)]
+pub(super) struct Turns {
+    #[cfg(not(target_family = "wasm"))]
+    baton: Arc<Baton>,
+
+    #[cfg(target_family = "wasm")]
+    player: Arc<Mutex<Player>>,
+}
+
+impl Turns {
+    /// Give the worker a turn, returning how it ended, or `None` if the
+    /// worker couldn't run because it's further up the stack.
+    pub(super) fn run(&self, turn: Turn) -> Option<TurnEnd> {
+        #[cfg(not(target_family = "wasm"))]
+        {
+            Some(self.baton.run(turn))
+        }
+
+        #[cfg(target_family = "wasm")]
+        {
+            let mut player = self.player.try_lock().ok()?;
+            if let Turn::Tick(dt) = turn {
+                player.tick(dt);
+            }
+            Some(TurnEnd::Finished)
+        }
+    }
+}
+
+/// Runs the player of a background worker.
+pub(super) struct WorkerRunner {
+    turns: Turns,
+
+    #[cfg(not(target_family = "wasm"))]
+    thread: JoinHandle<()>,
+}
+
+impl WorkerRunner {
+    /// Spawn a background worker, whose player is built by `builder`.
+    ///
+    /// On native platforms, the player is built on the thread of the worker
+    /// at the start of its first turn.
+    pub(super) fn spawn(id: WorkerId, builder: PlayerBuilder) -> Self {
+        #[cfg(not(target_family = "wasm"))]
+        {
+            let baton = Arc::new(Baton::new());
+            let guard = ExitGuard(baton.clone());
+            let builder = SendToWorker(builder);
+            let thread = std::thread::Builder::new()
+                .name(format!("Worker {id}"))
+                .spawn(move || {
+                    let guard = guard;
+                    run_worker_thread(&guard.0, builder.into_inner());
+                })
+                .expect("Failed to spawn a worker thread");
+            Self {
+                turns: Turns { baton },
+                thread,
+            }
+        }
+
+        #[cfg(target_family = "wasm")]
+        {
+            let _ = id;
+            Self {
+                turns: Turns {
+                    player: builder.build(),
+                },
+            }
+        }
+    }
+
+    pub(super) fn turns(&self) -> Turns {
+        self.turns.clone()
+    }
+
+    /// The baton of the worker, if the current thread is the worker's.
+    ///
+    /// The worker also runs code on other threads, when the futures of its
+    /// player are polled.
+    #[cfg(not(target_family = "wasm"))]
+    pub(super) fn baton_on_current_thread(&self) -> Option<Arc<Baton>> {
+        (self.thread.thread().id() == std::thread::current().id()).then(|| self.turns.baton.clone())
+    }
+
+    /// Stop the worker, and wait until it's done.
+    pub(super) fn stop(self) {
+        #[cfg(not(target_family = "wasm"))]
+        {
+            self.turns.run(Turn::Stop);
+            if self.thread.join().is_err() {
+                tracing::error!("A worker thread panicked");
+            }
+        }
+    }
+}
+
+#[cfg(all(test, not(target_family = "wasm")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn baton_hands_turns_back_and_forth() {
+        let baton = Arc::new(Baton::new());
+        let worker_baton = baton.clone();
+        let thread = std::thread::spawn(move || {
+            let mut turns = vec![];
+            loop {
+                let turn = worker_baton.wait_for_turn();
+                turns.push(turn);
+                match turn {
+                    Turn::Tick(_) if turns.len() == 1 => worker_baton.end_turn(TurnEnd::Parked),
+                    Turn::Stop => break,
+                    _ => worker_baton.end_turn(TurnEnd::Finished),
+                }
+            }
+            worker_baton.set_state(BatonState::Exited);
+            turns
+        });
+
+        assert_eq!(baton.run(Turn::Tick(1.0)), TurnEnd::Parked);
+        assert_eq!(baton.run(Turn::Resume), TurnEnd::Finished);
+        assert_eq!(baton.run(Turn::Tick(2.0)), TurnEnd::Finished);
+        assert_eq!(baton.run(Turn::Stop), TurnEnd::Finished);
+        assert!(baton.is_stopped());
+        assert_eq!(
+            thread.join().unwrap(),
+            vec![Turn::Tick(1.0), Turn::Resume, Turn::Tick(2.0), Turn::Stop]
+        );
+
+        // The thread exited, so it can't be given any more turns.
+        assert_eq!(baton.run(Turn::Tick(3.0)), TurnEnd::Finished);
+    }
+}
@@ -64,6 +64,10 @@ pub struct SoundStreamInfo {
     pub stream_format: swf::SoundFormat,
     pub num_samples_per_block: u16,
     pub latency_seek: i16,
+
+    /// Codec-specific data needed to set up the decoder, such as the
+    /// `AudioSpecificConfig` of an AAC stream.
+    pub decoder_config: Option<Vec<u8>>,
 }
 
 impl From<swf::SoundStreamHead> for SoundStreamInfo {
@@ -73,6 +77,7 @@ impl From<swf::SoundStreamHead> for SoundStreamInfo {
             stream_format: swfhead.stream_format,
             num_samples_per_block: swfhead.num_samples_per_block,
             latency_seek: swfhead.latency_seek,
+            decoder_config: None,
         }
     }
 }
@@ -1,5 +1,7 @@
 //! Audio decoders.
 
+#[cfg(feature = "aac")]
+mod aac;
 mod adpcm;
 #[cfg(feature = "mp3")]
 mod mp3;
@@ -7,6 +9,8 @@ mod mp3;
 mod nellymoser;
 mod pcm;
 
+#[cfg(feature = "aac")]
+pub use aac::AacDecoder;
 pub use adpcm::AdpcmDecoder;
 #[cfg(feature = "mp3")]
 pub use mp3::{mp3_metadata, Mp3Decoder};
@@ -27,6 +31,10 @@ pub enum Error {
     #[error("Couldn't decode MP3: {0}")]
     InvalidMp3(#[from] mp3::Error),
 
+    #[cfg(feature = "aac")]
+    #[error("Couldn't decode AAC: {0}")]
+    InvalidAac(#[from] aac::Error),
+
     #[error("Couldn't decode ADPCM: {0}")]
     InvalidAdpcm(#[from] adpcm::Error),
 
@@ -491,12 +499,20 @@ pub fn make_substream_decoder(
     stream_info: &SoundStreamInfo,
     data_stream: Substream,
 ) -> Result<Box<dyn Decoder + Send>, Error> {
-    let decoder: Box<dyn Decoder + Send> =
-        if stream_info.stream_format.compression == AudioCompression::Adpcm {
-            Box::new(AdpcmSubstreamDecoder::new(stream_info, data_stream)?)
-        } else {
-            Box::new(StandardSubstreamDecoder::new(stream_info, data_stream)?)
-        };
+    let decoder: Box<dyn Decoder + Send> = match stream_info.stream_format.compression {
+        AudioCompression::Adpcm => Box::new(AdpcmSubstreamDecoder::new(stream_info, data_stream)?),
+        // AAC frames aren't self-delimiting, so each chunk is handed to the
+        // decoder as one frame.
+        #[cfg(feature = "aac")]
+        AudioCompression::Aac => Box::new(AacDecoder::new(
+            stream_info
+                .decoder_config
+                .as_deref()
+                .ok_or(aac::Error::MissingConfig)?,
+            SubstreamTagReader::new(stream_info, data_stream),
+        )?),
+        _ => Box::new(StandardSubstreamDecoder::new(stream_info, data_stream)?),
+    };
     Ok(decoder)
 }
 
@@ -0,0 +1,243 @@
+use crate::backend::audio::decoders::Decoder;
+use crate::buffer::Slice;
+use symphonia::core::{
+    audio,
+    codecs::{self, Decoder as _},
+    errors,
+    formats::Packet,
+};
+use symphonia::default::codecs::AacDecoder as SymphoniaAacDecoder;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Couldn't decode AAC frame: {0}")]
+    FrameDecode(#[from] errors::Error),
+
+    #[error("Missing AudioSpecificConfig")]
+    MissingConfig,
+
+    #[error("Invalid AudioSpecificConfig")]
+    InvalidConfig,
+
+    #[error("Unsupported sample rate {0}")]
+    UnsupportedSampleRate(u32),
+}
+
+/// Decodes a stream of raw AAC frames.
+///
+/// Unlike MP3, raw AAC frames are not self-delimiting, so the container has
+/// to hand them to us one at a time. The stream parameters are taken from
+/// the `AudioSpecificConfig` that FLV and MP4 files store out-of-band.
+///
+/// SBR and PS aren't supported, so HE-AAC streams are decoded without them:
+/// only the AAC-LC core is played, at its (halved) sample rate and channel
+/// count.
+pub struct AacDecoder<I> {
+    frames: I,
+    decoder: SymphoniaAacDecoder,
+    sample_buf: audio::SampleBuffer<i16>,
+    cur_sample: usize,
+    /// The number of channels of the frame in `sample_buf`.
+    frame_channels: usize,
+    sample_rate: u16,
+    num_channels: u8,
+}
+
+impl<I: Iterator<Item = Slice>> AacDecoder<I> {
+    /// Create a decoder for the given `AudioSpecificConfig`, which will play
+    /// each frame yielded by `frames`.
+    pub fn new(config: &[u8], frames: I) -> Result<Self, Error> {
+        let AudioSpecificConfig {
+            sample_rate,
+            num_channels,
+            object_type,
+        } = parse_audio_specific_config(config).ok_or(Error::InvalidConfig)?;
+        if matches!(object_type, OBJECT_TYPE_SBR | OBJECT_TYPE_PS) {
+            tracing::warn!(
+                "HE-AAC (object type {object_type}) is not supported; playing the AAC-LC core only"
+            );
+        }
+
+        let mut codec_params = codecs::CodecParameters::new();
+        codec_params
+            .for_codec(codecs::CODEC_TYPE_AAC)
+            .with_extra_data(config.into());
+        let decoder = SymphoniaAacDecoder::try_new(&codec_params, &Default::default())?;
+
+        Ok(Self {
+            frames,
+            decoder,
+            sample_buf: audio::SampleBuffer::new(
+                0,
+                audio::SignalSpec::new(sample_rate, audio::Channels::FRONT_LEFT),
+            ),
+            cur_sample: 0,
+            frame_channels: num_channels.into(),
+            sample_rate: sample_rate
+                .try_into()
+                .map_err(|_| Error::UnsupportedSampleRate(sample_rate))?,
+            num_channels,
+        })
+    }
+
+    /// Decode frames until we get some samples, returning `false` once we
+    /// run out of frames.
+    fn next_frame(&mut self) -> bool {
+        self.cur_sample = 0;
+        for frame in self.frames.by_ref() {
+            let packet = Packet::new_from_slice(0, 0, 0, &frame.data());
+            match self.decoder.decode(&packet) {
+                Ok(decoded) => {
+                    // The channel layout may differ from the config, and
+                    // change between frames.
+                    let channels = decoded.spec().channels.count();
+                    if self.sample_buf.capacity() < decoded.capacity() * channels {
+                        // Ensure our buffer has enough space for the decoded samples.
+                        self.sample_buf = audio::SampleBuffer::new(
+                            decoded.capacity() as symphonia::core::units::Duration,
+                            *decoded.spec(),
+                        );
+                    }
+                    self.frame_channels = channels.max(1);
+                    self.sample_buf.copy_interleaved_ref(decoded);
+                    if !self.sample_buf.is_empty() {
+                        return true;
+                    }
+                }
+                // Decode errors are not fatal.
+                Err(errors::Error::DecodeError(e)) => {
+                    tracing::warn!("Skipping invalid AAC frame: {}", e);
+                }
+                Err(_) => break,
+            }
+        }
+        self.sample_buf.clear();
+        false
+    }
+}
+
+impl<I: Iterator<Item = Slice>> Iterator for AacDecoder<I> {
+    type Item = [i16; 2];
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let end = self.cur_sample + self.frame_channels;
+            if let Some(frame) = self.sample_buf.samples().get(self.cur_sample..end) {
+                self.cur_sample = end;
+                // Anything beyond the front left and right channels is dropped.
+                return Some(match *frame {
+                    [left, right, ..] => [left, right],
+                    [sample] => [sample, sample],
+                    [] => [0, 0],
+                });
+            }
+
+            if !self.next_frame() {
+                return None;
+            }
+        }
+    }
+}
+
+impl<I: Iterator<Item = Slice> + Send + Sync> Decoder for AacDecoder<I> {
+    #[inline]
+    fn num_channels(&self) -> u8 {
+        self.num_channels
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> u16 {
+        self.sample_rate
+    }
+}
+
+/// Audio object types of HE-AAC (v1 and v2) with explicit signaling.
+const OBJECT_TYPE_SBR: u8 = 5;
+const OBJECT_TYPE_PS: u8 = 29;
+
+/// The fields of an `AudioSpecificConfig` that describe the AAC core.
+#[derive(Debug, PartialEq)]
+struct AudioSpecificConfig {
+    sample_rate: u32,
+    num_channels: u8,
+    /// The audio object type, which is SBR or PS for HE-AAC.
+    object_type: u8,
+}
+
+/// Read the sample rate and channel count of the AAC core from an
+/// `AudioSpecificConfig`, as defined in ISO/IEC 14496-3.
+fn parse_audio_specific_config(config: &[u8]) -> Option<AudioSpecificConfig> {
+    const SAMPLE_RATES: [u32; 13] = [
+        96000, 88200, 64000, 48000, 44100, 32000, 24000, 22050, 16000, 12000, 11025, 8000, 7350,
+    ];
+
+    // The fields we need fit in the first 43 bits.
+    let len = config.len().min(8);
+    let mut bytes = [0; 8];
+    bytes[..len].copy_from_slice(&config[..len]);
+    let bits = u64::from_be_bytes(bytes);
+    let mut pos = 0;
+    let mut read = |n: u32| {
+        pos += n;
+        (pos as usize <= len * 8).then(|| (bits >> (64 - pos)) & ((1 << n) - 1))
+    };
+
+    let object_type = match read(5)? {
+        31 => 32 + read(6)? as u8,
+        object_type => object_type as u8,
+    };
+    let sample_rate = match read(4)? {
+        15 => read(24)? as u32,
+        index => *SAMPLE_RATES.get(index as usize)?,
+    };
+    let num_channels = match read(4)? {
+        channels @ 1..=2 => channels as u8,
+        _ => return None,
+    };
+
+    Some(AudioSpecificConfig {
+        sample_rate,
+        num_channels,
+        object_type,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(sample_rate: u32, num_channels: u8, object_type: u8) -> Option<AudioSpecificConfig> {
+        Some(AudioSpecificConfig {
+            sample_rate,
+            num_channels,
+            object_type,
+        })
+    }
+
+    #[test]
+    fn audio_specific_config() {
+        // AAC-LC, 44.1kHz, stereo
+        assert_eq!(
+            parse_audio_specific_config(&[0x12, 0x10]),
+            config(44100, 2, 2)
+        );
+        // HE-AAC (explicit SBR signaling), 24kHz mono core
+        assert_eq!(
+            parse_audio_specific_config(&[0x2B, 0x0A, 0x08, 0x00]),
+            config(24000, 1, OBJECT_TYPE_SBR)
+        );
+        // Explicit sample rate
+        assert_eq!(
+            parse_audio_specific_config(&[0x17, 0x80, 0x3E, 0x80, 0x10]),
+            config(32000, 2, 2)
+        );
+        // Extended object type (ER AAC ELD)
+        assert_eq!(
+            parse_audio_specific_config(&[0xF8, 0xE6, 0x20]),
+            config(48000, 1, 39)
+        );
+        assert_eq!(parse_audio_specific_config(&[0x12]), None);
+    }
+}
@@ -0,0 +1,458 @@
+//! Microphone and camera capture
+
+use downcast_rs::{impl_downcast, Downcast};
+use image::imageops::FilterType;
+use image::RgbaImage;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum CaptureError {
+    #[error("There is no capture device with index {0}")]
+    NoSuchDevice(usize),
+
+    #[error("Couldn't decode capture file: {0}")]
+    InvalidFile(String),
+
+    #[error("Couldn't open capture device: {0}")]
+    DeviceUnavailable(String),
+}
+
+/// A single frame captured from a camera.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CameraFrame {
+    pub width: u32,
+    pub height: u32,
+
+    /// The pixels of the frame, as RGBA with every pixel opaque.
+    pub rgba: Vec<u8>,
+}
+
+/// A backend that captures audio from microphones and video from cameras.
+///
+/// Devices are identified by their index in the lists of names returned by
+/// `microphone_names` and `camera_names`.
+/// Captured data is polled by the player every tick.
+pub trait CaptureBackend: Downcast {
+    /// The names of the available microphones.
+    fn microphone_names(&self) -> Vec<String>;
+
+    /// The names of the available cameras.
+    fn camera_names(&self) -> Vec<String>;
+
+    /// Start capturing mono audio from a microphone, at the given sample rate in Hz.
+    ///
+    /// Opening a microphone that is already open changes its sample rate.
+    fn open_microphone(&mut self, index: usize, sample_rate: u32) -> Result<(), CaptureError>;
+
+    /// Stop capturing audio from a microphone.
+    fn close_microphone(&mut self, index: usize);
+
+    /// Take the samples captured from an open microphone since the last call,
+    /// as values between -1.0 and 1.0.
+    fn read_microphone(&mut self, index: usize) -> Vec<f32>;
+
+    /// Start capturing video from a camera, at the given frame size and frames per second.
+    ///
+    /// Opening a camera that is already open changes its capture mode.
+    fn open_camera(
+        &mut self,
+        index: usize,
+        width: u32,
+        height: u32,
+        fps: f64,
+    ) -> Result<(), CaptureError>;
+
+    /// Stop capturing video from a camera.
+    fn close_camera(&mut self, index: usize);
+
+    /// Take the newest frame captured from an open camera,
+    /// if a new one has been captured since the last call.
+    fn read_camera(&mut self, index: usize) -> Option<CameraFrame>;
+
+    /// Advance the clock of a backend that doesn't capture in real time, by `dt` milliseconds.
+    fn tick(&mut self, _dt: f64) {}
+}
+impl_downcast!(CaptureBackend);
+
+/// A capture backend without any devices.
+#[derive(Default)]
+pub struct NullCaptureBackend;
+
+impl NullCaptureBackend {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl CaptureBackend for NullCaptureBackend {
+    fn microphone_names(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    fn camera_names(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    fn open_microphone(&mut self, index: usize, _sample_rate: u32) -> Result<(), CaptureError> {
+        Err(CaptureError::NoSuchDevice(index))
+    }
+
+    fn close_microphone(&mut self, _index: usize) {}
+
+    fn read_microphone(&mut self, _index: usize) -> Vec<f32> {
+        Vec::new()
+    }
+
+    fn open_camera(
+        &mut self,
+        index: usize,
+        _width: u32,
+        _height: u32,
+        _fps: f64,
+    ) -> Result<(), CaptureError> {
+        Err(CaptureError::NoSuchDevice(index))
+    }
+
+    fn close_camera(&mut self, _index: usize) {}
+
+    fn read_camera(&mut self, _index: usize) -> Option<CameraFrame> {
+        None
+    }
+}
+
+struct FileMicrophone {
+    name: String,
+    sample_rate: u32,
+    samples: Vec<f32>,
+    capture: Option<MicrophoneCapture>,
+}
+
+struct MicrophoneCapture {
+    sample_rate: u32,
+
+    /// Milliseconds since the microphone was opened.
+    elapsed: f64,
+
+    /// The number of samples produced since the microphone was opened.
+    produced: u64,
+
+    pending: Vec<f32>,
+}
+
+struct FileCamera {
+    name: String,
+    frames: Vec<RgbaImage>,
+    capture: Option<CameraCapture>,
+}
+
+struct CameraCapture {
+    fps: f64,
+
+    /// Milliseconds since the camera was opened.
+    elapsed: f64,
+
+    /// The frames of the camera, resized to the capture size.
+    frames: Vec<CameraFrame>,
+
+    last_read: Option<u64>,
+}
+
+/// A capture backend whose devices play back files in a loop, in step with the player.
+///
+/// Microphones play WAV files and cameras play sequences of images,
+/// which makes capture deterministic enough for headless tests.
+#[derive(Default)]
+pub struct FileCaptureBackend {
+    microphones: Vec<FileMicrophone>,
+    cameras: Vec<FileCamera>,
+}
+
+impl FileCaptureBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a microphone that plays back the given WAV file.
+    pub fn add_microphone(&mut self, name: String, wav: &[u8]) -> Result<(), CaptureError> {
+        let (sample_rate, samples) = decode_wav(wav)?;
+        self.microphones.push(FileMicrophone {
+            name,
+            sample_rate,
+            samples,
+            capture: None,
+        });
+        Ok(())
+    }
+
+    /// Add a camera that plays back the given encoded images, one per frame.
+    pub fn add_camera(&mut self, name: String, images: &[Vec<u8>]) -> Result<(), CaptureError> {
+        let frames = images
+            .iter()
+            .map(|image| {
+                image::load_from_memory(image)
+                    .map(|image| image.to_rgba8())
+                    .map_err(|e| CaptureError::InvalidFile(e.to_string()))
+            })
+            .collect::<Result<_, _>>()?;
+        self.cameras.push(FileCamera {
+            name,
+            frames,
+            capture: None,
+        });
+        Ok(())
+    }
+}
+
+impl CaptureBackend for FileCaptureBackend {
+    fn microphone_names(&self) -> Vec<String> {
+        self.microphones.iter().map(|m| m.name.clone()).collect()
+    }
+
+    fn camera_names(&self) -> Vec<String> {
+        self.cameras.iter().map(|c| c.name.clone()).collect()
+    }
+
+    fn open_microphone(&mut self, index: usize, sample_rate: u32) -> Result<(), CaptureError> {
+        let microphone = self
+            .microphones
+            .get_mut(index)
+            .ok_or(CaptureError::NoSuchDevice(index))?;
+        microphone.capture = Some(MicrophoneCapture {
+            sample_rate,
+            elapsed: 0.0,
+            produced: 0,
+            pending: Vec::new(),
+        });
+        Ok(())
+    }
+
+    fn close_microphone(&mut self, index: usize) {
+        if let Some(microphone) = self.microphones.get_mut(index) {
+            microphone.capture = None;
+        }
+    }
+
+    fn read_microphone(&mut self, index: usize) -> Vec<f32> {
+        self.microphones
+            .get_mut(index)
+            .and_then(|m| m.capture.as_mut())
+            .map(|capture| std::mem::take(&mut capture.pending))
+            .unwrap_or_default()
+    }
+
+    fn open_camera(
+        &mut self,
+        index: usize,
+        width: u32,
+        height: u32,
+        fps: f64,
+    ) -> Result<(), CaptureError> {
+        let camera = self
+            .cameras
+            .get_mut(index)
+            .ok_or(CaptureError::NoSuchDevice(index))?;
+        let frames = camera
+            .frames
+            .iter()
+            .map(|image| CameraFrame {
+                width,
+                height,
+                rgba: image::imageops::resize(image, width, height, FilterType::Triangle)
+                    .into_raw(),
+            })
+            .collect();
+        camera.capture = Some(CameraCapture {
+            fps,
+            elapsed: 0.0,
+            frames,
+            last_read: None,
+        });
+        Ok(())
+    }
+
+    fn close_camera(&mut self, index: usize) {
+        if let Some(camera) = self.cameras.get_mut(index) {
+            camera.capture = None;
+        }
+    }
+
+    fn read_camera(&mut self, index: usize) -> Option<CameraFrame> {
+        let capture = self.cameras.get_mut(index)?.capture.as_mut()?;
+        if capture.frames.is_empty() {
+            return None;
+        }
+
+        let frame_number = (capture.elapsed * capture.fps / 1000.0) as u64;
+        if capture.last_read == Some(frame_number) {
+            return None;
+        }
+        capture.last_read = Some(frame_number);
+        let frame = frame_number % capture.frames.len() as u64;
+        Some(capture.frames[frame as usize].clone())
+    }
+
+    fn tick(&mut self, dt: f64) {
+        for microphone in &mut self.microphones {
+            let Some(capture) = &mut microphone.capture else {
+                continue;
+            };
+            capture.elapsed += dt;
+            let target = (capture.elapsed * capture.sample_rate as f64 / 1000.0) as u64;
+            for position in capture.produced..target {
+                let sample = if microphone.samples.is_empty() {
+                    0.0
+                } else {
+                    // Nearest-neighbour resampling is good enough for a stand-in device.
+                    let source = position * microphone.sample_rate as u64
+                        / capture.sample_rate.max(1) as u64;
+                    microphone.samples[(source % microphone.samples.len() as u64) as usize]
+                };
+                capture.pending.push(sample);
+            }
+            capture.produced = target.max(capture.produced);
+        }
+
+        for camera in &mut self.cameras {
+            if let Some(capture) = &mut camera.capture {
+                capture.elapsed += dt;
+            }
+        }
+    }
+}
+
+/// Decode a PCM or floating point WAV file into its sample rate and mono samples.
+fn decode_wav(data: &[u8]) -> Result<(u32, Vec<f32>), CaptureError> {
+    let invalid = |message: &str| CaptureError::InvalidFile(message.to_string());
+    if data.len() < 12 || &data[0..4] != b"RIFF" || &data[8..12] != b"WAVE" {
+        return Err(invalid("not a WAV file"));
+    }
+
+    let mut format = None;
+    let mut samples = None;
+    let mut chunks = &data[12..];
+    while chunks.len() >= 8 {
+        let id = &chunks[0..4];
+        let len = u32::from_le_bytes([chunks[4], chunks[5], chunks[6], chunks[7]]) as usize;
+        let body = chunks
+            .get(8..8 + len)
+            .ok_or_else(|| invalid("truncated chunk"))?;
+        match id {
+            b"fmt " if body.len() >= 16 => {
+                let tag = u16::from_le_bytes([body[0], body[1]]);
+                let channels = u16::from_le_bytes([body[2], body[3]]);
+                let sample_rate = u32::from_le_bytes([body[4], body[5], body[6], body[7]]);
+                let bits = u16::from_le_bytes([body[14], body[15]]);
+                format = Some((tag, channels, sample_rate, bits));
+            }
+            b"data" => samples = Some(body),
+            _ => {}
+        }
+        // Chunks are padded to an even length.
+        let next = (8 + len + (len & 1)).min(chunks.len());
+        chunks = &chunks[next..];
+    }
+
+    let (tag, channels, sample_rate, bits) = format.ok_or_else(|| invalid("missing format"))?;
+    let data = samples.ok_or_else(|| invalid("missing data"))?;
+    if channels == 0 || sample_rate == 0 {
+        return Err(invalid("invalid format"));
+    }
+
+    let decode: fn(&[u8]) -> f32 = match (tag, bits) {
+        (1, 8) => |b| (b[0] as f32 - 128.0) / 128.0,
+        (1, 16) => |b| i16::from_le_bytes([b[0], b[1]]) as f32 / 32768.0,
+        (1, 24) => |b| i32::from_le_bytes([0, b[0], b[1], b[2]]) as f32 / 2147483648.0,
+        (1, 32) => |b| i32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f32 / 2147483648.0,
+        (3, 32) => |b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]),
+        _ => return Err(invalid("unsupported sample format")),
+    };
+
+    let sample_size = bits as usize / 8;
+    let frame_size = sample_size * channels as usize;
+    let samples = data
+        .chunks_exact(frame_size)
+        .map(|frame| {
+            let sum: f32 = frame.chunks_exact(sample_size).map(decode).sum();
+            sum / channels as f32
+        })
+        .collect();
+
+    Ok((sample_rate, samples))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wav(channels: u16, sample_rate: u32, samples: &[i16]) -> Vec<u8> {
+        let data: Vec<u8> = samples.iter().flat_map(|s| s.to_le_bytes()).collect();
+        let mut wav = Vec::new();
+        wav.extend_from_slice(b"RIFF");
+        wav.extend_from_slice(&(36 + data.len() as u32).to_le_bytes());
+        wav.extend_from_slice(b"WAVEfmt ");
+        wav.extend_from_slice(&16u32.to_le_bytes());
+        wav.extend_from_slice(&1u16.to_le_bytes());
+        wav.extend_from_slice(&channels.to_le_bytes());
+        wav.extend_from_slice(&sample_rate.to_le_bytes());
+        wav.extend_from_slice(&(sample_rate * channels as u32 * 2).to_le_bytes());
+        wav.extend_from_slice(&(channels * 2).to_le_bytes());
+        wav.extend_from_slice(&16u16.to_le_bytes());
+        wav.extend_from_slice(b"data");
+        wav.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        wav.extend_from_slice(&data);
+        wav
+    }
+
+    #[test]
+    fn decode_stereo_wav_as_mono() {
+        let (rate, samples) = decode_wav(&wav(2, 8000, &[16384, 0, -32768, -32768])).unwrap();
+        assert_eq!(rate, 8000);
+        assert_eq!(samples, vec![0.25, -1.0]);
+        assert!(decode_wav(b"RIFF\0\0\0\0AVI ").is_err());
+    }
+
+    #[test]
+    fn file_microphone_loops_in_step_with_ticks() {
+        let mut backend = FileCaptureBackend::new();
+        backend
+            .add_microphone("Test".to_string(), &wav(1, 4000, &[0, 16384]))
+            .unwrap();
+        assert_eq!(backend.microphone_names(), vec!["Test".to_string()]);
+
+        backend.open_microphone(0, 8000).unwrap();
+        backend.tick(0.5);
+        assert_eq!(backend.read_microphone(0), vec![0.0, 0.0, 0.5, 0.5]);
+        backend.tick(0.375);
+        assert_eq!(backend.read_microphone(0), vec![0.0, 0.0, 0.5]);
+        assert!(backend.read_microphone(0).is_empty());
+
+        backend.close_microphone(0);
+        backend.tick(10.0);
+        assert!(backend.read_microphone(0).is_empty());
+        assert!(backend.open_microphone(1, 8000).is_err());
+    }
+
+    #[test]
+    fn file_camera_advances_frames() {
+        let mut backend = FileCaptureBackend::new();
+        backend.cameras.push(FileCamera {
+            name: "Test".to_string(),
+            frames: vec![
+                RgbaImage::from_pixel(4, 4, image::Rgba([255, 0, 0, 255])),
+                RgbaImage::from_pixel(4, 4, image::Rgba([0, 0, 255, 255])),
+            ],
+            capture: None,
+        });
+
+        backend.open_camera(0, 2, 1, 10.0).unwrap();
+        let frame = backend.read_camera(0).unwrap();
+        assert_eq!((frame.width, frame.height), (2, 1));
+        assert_eq!(frame.rgba, vec![255, 0, 0, 255, 255, 0, 0, 255]);
+        assert!(backend.read_camera(0).is_none());
+
+        backend.tick(100.0);
+        assert_eq!(backend.read_camera(0).unwrap().rgba[0..4], [0, 0, 255, 255]);
+        backend.tick(100.0);
+        assert_eq!(backend.read_camera(0).unwrap().rgba[0..4], [255, 0, 0, 255]);
+    }
+}
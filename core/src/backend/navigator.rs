@@ -702,3 +702,89 @@ pub fn get_encoding(content_type: &str) -> Option<&'static Encoding> {
         None
     }
 }
+
+/// The boundary between the parts of `multipart/form-data` request bodies, as used by Flash Player.
+const MULTIPART_BOUNDARY: &str = "----------Ij5GI3GI3ei4Ij5gL6ae0KM7KM7GI3";
+
+/// Escapes a name or file name quoted in a `Content-Disposition` header, in the
+/// same way as browsers encode form data.
+fn escape_disposition_value(value: &str) -> String {
+    value
+        .replace('"', "%22")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Formats a file upload as a `multipart/form-data` request body, in the same
+/// way as Flash Player.
+///
+/// The `fields` are sent along with the file, whose contents are sent as the
+/// `file_field` field. Returns the body and its content type.
+pub fn multipart_form_data(
+    fields: &[(String, String)],
+    file_field: &str,
+    file_name: &str,
+    data: &[u8],
+) -> (Vec<u8>, String) {
+    let contains = |bytes: &[u8], boundary: &[u8]| {
+        bytes
+            .windows(boundary.len())
+            .any(|window| window == boundary)
+    };
+    let parts_contain = |boundary: &str| {
+        let boundary = boundary.as_bytes();
+        contains(data, boundary)
+            || contains(file_name.as_bytes(), boundary)
+            || contains(file_field.as_bytes(), boundary)
+            || fields.iter().any(|(name, value)| {
+                contains(name.as_bytes(), boundary) || contains(value.as_bytes(), boundary)
+            })
+    };
+
+    // In the unlikely case that the boundary appears in a part, extend it until it doesn't.
+    let mut boundary = MULTIPART_BOUNDARY.to_string();
+    let mut suffix = 0;
+    while parts_contain(&boundary) {
+        boundary = format!("{MULTIPART_BOUNDARY}{suffix}");
+        suffix += 1;
+    }
+
+    let mut body = Vec::new();
+    let mut add_part = |disposition: &str, content_type: Option<&str>, content: &[u8]| {
+        body.extend_from_slice(b"--");
+        body.extend_from_slice(boundary.as_bytes());
+        body.extend_from_slice(b"\r\nContent-Disposition: form-data; ");
+        body.extend_from_slice(disposition.as_bytes());
+        body.extend_from_slice(b"\r\n");
+        if let Some(content_type) = content_type {
+            body.extend_from_slice(b"Content-Type: ");
+            body.extend_from_slice(content_type.as_bytes());
+            body.extend_from_slice(b"\r\n");
+        }
+        body.extend_from_slice(b"\r\n");
+        body.extend_from_slice(content);
+        body.extend_from_slice(b"\r\n");
+    };
+
+    add_part("name=\"Filename\"", None, file_name.as_bytes());
+    for (name, value) in fields {
+        let name = escape_disposition_value(name);
+        add_part(&format!("name=\"{name}\""), None, value.as_bytes());
+    }
+    add_part(
+        &format!(
+            "name=\"{}\"; filename=\"{}\"",
+            escape_disposition_value(file_field),
+            escape_disposition_value(file_name)
+        ),
+        Some("application/octet-stream"),
+        data,
+    );
+    add_part("name=\"Upload\"", None, b"Submit Query");
+
+    body.extend_from_slice(b"--");
+    body.extend_from_slice(boundary.as_bytes());
+    body.extend_from_slice(b"--\r\n");
+
+    (body, format!("multipart/form-data; boundary={boundary}"))
+}
@@ -7,6 +7,7 @@ use fluent_templates::loader::langid;
 pub use fluent_templates::LanguageIdentifier;
 use std::borrow::Cow;
 use std::collections::HashSet;
+use std::path::PathBuf;
 use url::Url;
 
 pub type FullscreenError = Cow<'static, str>;
@@ -39,6 +40,107 @@ pub struct FileFilter {
     pub mac_type: Option<String>,
 }
 
+/// A format of data on the clipboard.
+///
+/// Equivalent to the AS3 `ClipboardFormats` supported by the system clipboard.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClipboardFormat {
+    Text,
+    Html,
+    RichText,
+    Url,
+    Bitmap,
+    FileList,
+}
+
+/// An image on the clipboard.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClipboardBitmap {
+    pub width: u32,
+    pub height: u32,
+
+    /// The pixels of the image, as non-premultiplied RGBA.
+    pub rgba: Vec<u8>,
+}
+
+/// A page of a print job, rendered to an image.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PrintPage {
+    /// The size of the paper this page should be printed on, in points.
+    pub paper_width: f64,
+    pub paper_height: f64,
+
+    /// The offset of the image from the top-left corner of the paper, in points.
+    pub x: f64,
+    pub y: f64,
+
+    /// The size of the image on the paper, in points.
+    pub width: f64,
+    pub height: f64,
+
+    pub image_width: u32,
+    pub image_height: u32,
+
+    /// The pixels of the image, as non-premultiplied RGBA.
+    pub rgba: Vec<u8>,
+}
+
+/// The contents of the clipboard, in each format they are available in.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ClipboardContent {
+    pub text: Option<String>,
+    pub html: Option<String>,
+
+    /// A document in the Rich Text Format.
+    pub rich_text: Option<Vec<u8>>,
+    pub url: Option<String>,
+    pub bitmap: Option<ClipboardBitmap>,
+    pub file_list: Option<Vec<PathBuf>>,
+}
+
+impl ClipboardContent {
+    /// The formats that the contents are available in.
+    pub fn formats(&self) -> Vec<ClipboardFormat> {
+        [
+            (ClipboardFormat::Text, self.text.is_some()),
+            (ClipboardFormat::Html, self.html.is_some()),
+            (ClipboardFormat::RichText, self.rich_text.is_some()),
+            (ClipboardFormat::Url, self.url.is_some()),
+            (ClipboardFormat::Bitmap, self.bitmap.is_some()),
+            (ClipboardFormat::FileList, self.file_list.is_some()),
+        ]
+        .into_iter()
+        .filter_map(|(format, available)| available.then_some(format))
+        .collect()
+    }
+
+    pub fn has_format(&self, format: ClipboardFormat) -> bool {
+        self.formats().contains(&format)
+    }
+
+    /// Remove the contents in the given format.
+    pub fn clear_format(&mut self, format: ClipboardFormat) {
+        match format {
+            ClipboardFormat::Text => self.text = None,
+            ClipboardFormat::Html => self.html = None,
+            ClipboardFormat::RichText => self.rich_text = None,
+            ClipboardFormat::Url => self.url = None,
+            ClipboardFormat::Bitmap => self.bitmap = None,
+            ClipboardFormat::FileList => self.file_list = None,
+        }
+    }
+
+    /// The contents as plain text, for backends that only support text.
+    pub fn plain_text(&self) -> Option<String> {
+        self.text.clone().or_else(|| self.url.clone()).or_else(|| {
+            self.file_list.as_ref().map(|files| {
+                let paths: Vec<_> = files.iter().map(|path| path.to_string_lossy()).collect();
+                paths.join("\n")
+            })
+        })
+    }
+}
+
 /// A result of a file selection
 pub trait FileDialogResult: Downcast {
     /// Was the file selection canceled by the user
@@ -62,6 +164,12 @@ impl_downcast!(FileDialogResult);
 /// Future representing a file selection in process
 pub type DialogResultFuture = OwnedFuture<Box<dyn FileDialogResult>, DialogLoaderError>;
 
+/// Future representing a selection of multiple files in process
+///
+/// An empty list of files means that the selection was canceled.
+pub type MultipleDialogResultFuture =
+    OwnedFuture<Vec<Box<dyn FileDialogResult>>, DialogLoaderError>;
+
 pub trait UiBackend: Downcast {
     fn mouse_visible(&self) -> bool;
 
@@ -70,6 +178,16 @@ pub trait UiBackend: Downcast {
     /// Changes the mouse cursor image.
     fn set_mouse_cursor(&mut self, cursor: MouseCursor);
 
+    /// Whether this backend can display a `CustomCursor`, which is reported as
+    /// `Mouse.supportsNativeCursor`.
+    fn supports_custom_mouse_cursor(&self) -> bool {
+        false
+    }
+
+    /// Shows a custom cursor image in place of the cursor set by `set_mouse_cursor`,
+    /// or goes back to that cursor if `cursor` is `None`.
+    fn set_custom_mouse_cursor(&mut self, _cursor: Option<CustomCursor>) {}
+
     /// Get the clipboard content
     fn clipboard_content(&mut self) -> String;
 
@@ -81,6 +199,22 @@ pub trait UiBackend: Downcast {
     /// Sets the clipboard to the given content.
     fn set_clipboard_content(&mut self, content: String);
 
+    /// Get the clipboard contents in every format supported by this backend.
+    ///
+    /// By default, only plain text is supported.
+    fn clipboard_contents(&mut self) -> ClipboardContent {
+        let text = self.clipboard_content();
+        ClipboardContent {
+            text: (!text.is_empty()).then_some(text),
+            ..Default::default()
+        }
+    }
+
+    /// Replace the clipboard contents, in all formats.
+    fn set_clipboard_contents(&mut self, contents: ClipboardContent) {
+        self.set_clipboard_content(contents.plain_text().unwrap_or_default());
+    }
+
     fn set_fullscreen(&mut self, is_full: bool) -> Result<(), FullscreenError>;
 
     /// Displays a message about an error during root movie download.
@@ -118,6 +252,26 @@ pub trait UiBackend: Downcast {
     /// * `filters` represents a list of filters to the possible file types that can be selected
     fn display_file_open_dialog(&mut self, filters: Vec<FileFilter>) -> Option<DialogResultFuture>;
 
+    /// Displays a file selection dialog allowing the user to select multiple files,
+    /// returning None if the dialog cannot be displayed (e.g because it is already open)
+    /// * `filters` represents a list of filters to the possible file types that can be selected
+    ///
+    /// By default, this only allows selecting a single file.
+    fn display_file_open_multiple_dialog(
+        &mut self,
+        filters: Vec<FileFilter>,
+    ) -> Option<MultipleDialogResultFuture> {
+        let dialog = self.display_file_open_dialog(filters)?;
+        Some(Box::pin(async move {
+            let result = dialog.await?;
+            if result.is_cancelled() {
+                Ok(vec![])
+            } else {
+                Ok(vec![result])
+            }
+        }))
+    }
+
     /// Display a dialog allowing a user to select a destination to save a file to
     ///
     /// * `file_name` is a suggestion for the file name to save the file as
@@ -130,6 +284,16 @@ pub trait UiBackend: Downcast {
 
     /// Mark that any previously open dialog has been closed
     fn close_file_dialog(&mut self);
+
+    /// Whether this backend is able to print, which allows a `PrintJob` to be started.
+    fn can_print(&self) -> bool {
+        false
+    }
+
+    /// Print the pages of a finished print job.
+    ///
+    /// This is only called if `can_print` returned `true` when the job was started.
+    fn print(&mut self, _pages: Vec<PrintPage>) {}
 }
 impl_downcast!(UiBackend);
 
@@ -154,6 +318,30 @@ pub enum MouseCursor {
     Grab,
 }
 
+/// A mouse cursor made of images, registered with AS3 `Mouse.registerCursor`.
+/// Communicated from the core to the UI backend via `UiBackend::set_custom_mouse_cursor`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CustomCursor {
+    /// The images of the cursor, which are shown in turn if there is more than one.
+    pub frames: Vec<CustomCursorFrame>,
+
+    /// The position within the images that points at the mouse position, in pixels.
+    pub hotspot: (u32, u32),
+
+    /// The number of frames shown per second.
+    pub frame_rate: f64,
+}
+
+/// A single image of a `CustomCursor`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CustomCursorFrame {
+    pub width: u32,
+    pub height: u32,
+
+    /// The pixels of the image as unpremultiplied RGBA.
+    pub rgba: Vec<u8>,
+}
+
 struct ClickEventData {
     x: f64,
     y: f64,
@@ -1,4 +1,5 @@
 pub mod bitmap_data;
+pub mod encoder;
 pub mod operations;
 pub mod turbulence;
 
@@ -0,0 +1,129 @@
+//! Encoding of bitmap data into image files, as done by `BitmapData.encode`
+
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::png::{CompressionType, FilterType, PngEncoder};
+use image::{ExtendedColorType, ImageEncoder, ImageError};
+use thiserror::Error;
+
+/// An image format that bitmap data can be encoded into, along with its options.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImageEncoding {
+    /// PNG, as requested with `PNGEncoderOptions`.
+    Png { fast_compression: bool },
+
+    /// JPEG, as requested with `JPEGEncoderOptions`.
+    Jpeg { quality: u32 },
+}
+
+#[derive(Debug, Error)]
+pub enum EncodeError {
+    #[error("Couldn't encode image: {0}")]
+    Image(#[from] ImageError),
+}
+
+/// Encode non-premultiplied RGBA pixels into an image file.
+///
+/// The alpha channel is only kept if `transparent` is set and the format supports it.
+pub fn encode(
+    width: u32,
+    height: u32,
+    rgba: &[u8],
+    transparent: bool,
+    encoding: ImageEncoding,
+) -> Result<Vec<u8>, EncodeError> {
+    let mut output = Vec::new();
+    match encoding {
+        ImageEncoding::Png { fast_compression } => {
+            let compression = if fast_compression {
+                CompressionType::Fast
+            } else {
+                CompressionType::Default
+            };
+            let encoder =
+                PngEncoder::new_with_quality(&mut output, compression, FilterType::Adaptive);
+            if transparent {
+                encoder.write_image(rgba, width, height, ExtendedColorType::Rgba8)?;
+            } else {
+                encoder.write_image(&strip_alpha(rgba), width, height, ExtendedColorType::Rgb8)?;
+            }
+        }
+        ImageEncoding::Jpeg { quality } => {
+            let encoder = JpegEncoder::new_with_quality(&mut output, quality.clamp(1, 100) as u8);
+            encoder.write_image(&strip_alpha(rgba), width, height, ExtendedColorType::Rgb8)?;
+        }
+    }
+    Ok(output)
+}
+
+fn strip_alpha(rgba: &[u8]) -> Vec<u8> {
+    rgba.chunks_exact(4)
+        .flat_map(|pixel| [pixel[0], pixel[1], pixel[2]])
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PIXELS: [u8; 16] = [
+        255, 0, 0, 255, // red
+        0, 255, 0, 128, // half-transparent green
+        0, 0, 255, 0, // transparent blue
+        255, 255, 255, 255, // white
+    ];
+
+    #[test]
+    fn png_keeps_alpha_of_transparent_bitmaps() {
+        let png = encode(
+            2,
+            2,
+            &PIXELS,
+            true,
+            ImageEncoding::Png {
+                fast_compression: false,
+            },
+        )
+        .expect("PNG encoding succeeds");
+        let image = image::load_from_memory(&png).expect("PNG is valid");
+        assert_eq!(image.to_rgba8().into_raw(), PIXELS);
+    }
+
+    #[test]
+    fn png_drops_alpha_of_opaque_bitmaps() {
+        let png = encode(
+            2,
+            2,
+            &PIXELS,
+            false,
+            ImageEncoding::Png {
+                fast_compression: true,
+            },
+        )
+        .expect("PNG encoding succeeds");
+        let image = image::load_from_memory(&png).expect("PNG is valid");
+        assert_eq!(image.color(), image::ColorType::Rgb8);
+        assert_eq!(image.to_rgb8().into_raw(), strip_alpha(&PIXELS));
+    }
+
+    #[test]
+    fn jpeg_quality_affects_size() {
+        let pixels: Vec<u8> = (0..64 * 64)
+            .flat_map(|i| {
+                [
+                    (i % 64 * 4) as u8,
+                    (i / 64 * 4) as u8,
+                    (i % 7 * 36) as u8,
+                    255,
+                ]
+            })
+            .collect();
+        let low = encode(64, 64, &pixels, false, ImageEncoding::Jpeg { quality: 10 })
+            .expect("JPEG encoding succeeds");
+        let high = encode(64, 64, &pixels, false, ImageEncoding::Jpeg { quality: 100 })
+            .expect("JPEG encoding succeeds");
+        assert!(low.len() < high.len());
+
+        let image = image::load_from_memory(&high).expect("JPEG is valid");
+        assert_eq!((image.width(), image.height()), (64, 64));
+    }
+}
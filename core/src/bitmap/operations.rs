@@ -1691,6 +1691,59 @@ pub fn get_pixels_as_byte_array<'gc>(
     Ok(())
 }
 
+/// Get the pixels of a region as non-premultiplied RGBA bytes, along with the
+/// size of the region once clamped to the bitmap.
+pub fn get_pixels_as_rgba(
+    renderer: &mut dyn RenderBackend,
+    target: BitmapDataWrapper,
+    x: i32,
+    y: i32,
+    width: i32,
+    height: i32,
+) -> (u32, u32, Vec<u8>) {
+    let mut region = PixelRegion::for_region_i32(x, y, width, height);
+    region.clamp(target.width(), target.height());
+
+    let read = target.read_area(region, renderer);
+    let mut result = Vec::with_capacity(region.width() as usize * region.height() as usize * 4);
+    for y in region.y_min..region.y_max {
+        for x in region.x_min..region.x_max {
+            let color = read.get_pixel32_raw(x, y).to_un_multiplied_alpha();
+            result.extend_from_slice(&[color.red(), color.green(), color.blue(), color.alpha()]);
+        }
+    }
+
+    (region.width(), region.height(), result)
+}
+
+/// Count how many pixels of a region have each value of each channel,
+/// in the order red, green, blue and alpha.
+pub fn histogram(
+    renderer: &mut dyn RenderBackend,
+    target: BitmapDataWrapper,
+    x: i32,
+    y: i32,
+    width: i32,
+    height: i32,
+) -> [[u32; 256]; 4] {
+    let mut region = PixelRegion::for_region_i32(x, y, width, height);
+    region.clamp(target.width(), target.height());
+
+    let read = target.read_area(region, renderer);
+    let mut histogram = [[0; 256]; 4];
+    for y in region.y_min..region.y_max {
+        for x in region.x_min..region.x_max {
+            let color = read.get_pixel32_raw(x, y).to_un_multiplied_alpha();
+            histogram[0][color.red() as usize] += 1;
+            histogram[1][color.green() as usize] += 1;
+            histogram[2][color.blue() as usize] += 1;
+            histogram[3][color.alpha() as usize] += 1;
+        }
+    }
+
+    histogram
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn set_pixels_from_byte_array<'gc>(
     mc: &Mutation<'gc>,
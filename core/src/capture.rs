@@ -0,0 +1,517 @@
+//! Microphone and camera capture, shared by every object using a device.
+
+use crate::avm2::bytearray::ByteArrayStorage;
+use crate::avm2::object::ByteArrayObject;
+use crate::avm2::{
+    Activation as Avm2Activation, Avm2, Error as Avm2Error, Object as Avm2Object, TObject as _,
+};
+use crate::backend::capture::CameraFrame;
+use crate::context::UpdateContext;
+use gc_arena::{Collect, GcCell};
+use ruffle_render::bitmap::{Bitmap, BitmapFormat, BitmapInfo, PixelRegion};
+use std::cell::Ref;
+
+/// The sample rates that a microphone can capture at,
+/// in kHz as used by `Microphone.rate` and in Hz.
+const MICROPHONE_RATES: [(u32, u32); 6] = [
+    (5, 5512),
+    (8, 8000),
+    (11, 11025),
+    (16, 16000),
+    (22, 22050),
+    (44, 44100),
+];
+
+/// Get the sample rate in Hz of a `Microphone.rate`, if it is supported.
+pub fn microphone_sample_rate(rate: u32) -> Option<u32> {
+    MICROPHONE_RATES
+        .iter()
+        .find(|(khz, _)| *khz == rate)
+        .map(|(_, hz)| *hz)
+}
+
+/// Manages the microphones and cameras that are in use.
+///
+/// ActionScript gets the same object every time it asks for the same device,
+/// so there is at most one `Microphone` or `Camera` per device index.
+#[derive(Collect, Default)]
+#[collect(no_drop)]
+pub struct CaptureManager<'gc> {
+    microphones: Vec<Microphone<'gc>>,
+    cameras: Vec<Camera<'gc>>,
+}
+
+impl<'gc> CaptureManager<'gc> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn microphone_by_index(&self, index: usize) -> Option<Microphone<'gc>> {
+        self.microphones
+            .iter()
+            .find(|m| m.0.read().index == index)
+            .copied()
+    }
+
+    pub fn microphone_by_object(&self, object: Avm2Object<'gc>) -> Option<Microphone<'gc>> {
+        self.microphones
+            .iter()
+            .find(|m| Avm2Object::ptr_eq(m.0.read().object, object))
+            .copied()
+    }
+
+    pub fn camera_by_index(&self, index: usize) -> Option<Camera<'gc>> {
+        self.cameras
+            .iter()
+            .find(|c| c.0.read().index == index)
+            .copied()
+    }
+
+    pub fn camera_by_object(&self, object: Avm2Object<'gc>) -> Option<Camera<'gc>> {
+        self.cameras
+            .iter()
+            .find(|c| Avm2Object::ptr_eq(c.0.read().object, object))
+            .copied()
+    }
+
+    /// Deliver everything captured since the last tick.
+    pub fn tick(context: &mut UpdateContext<'gc>, dt: f64) {
+        if context.capture_manager.microphones.is_empty()
+            && context.capture_manager.cameras.is_empty()
+        {
+            return;
+        }
+
+        context.capture.tick(dt);
+
+        let microphones = context.capture_manager.microphones.clone();
+        for microphone in microphones {
+            if let Err(e) = microphone.tick(context, dt) {
+                tracing::error!("Error delivering microphone data: {e:?}");
+            }
+        }
+
+        let cameras = context.capture_manager.cameras.clone();
+        for camera in cameras {
+            camera.tick(context, dt);
+        }
+    }
+}
+
+/// A microphone that ActionScript is capturing audio from.
+#[derive(Clone, Copy, Collect)]
+#[collect(no_drop)]
+pub struct Microphone<'gc>(GcCell<'gc, MicrophoneData<'gc>>);
+
+#[derive(Collect)]
+#[collect(no_drop)]
+struct MicrophoneData<'gc> {
+    index: usize,
+
+    /// The AVM2 `Microphone` representing this device.
+    object: Avm2Object<'gc>,
+
+    /// Whether the device was opened, which is false if the backend refused access.
+    open: bool,
+
+    /// The sample rate, in kHz.
+    rate: u32,
+
+    gain: f64,
+    silence_level: f64,
+    silence_timeout: i32,
+    activity_level: f64,
+
+    /// Whether the activity level was last above the silence level.
+    active: bool,
+
+    /// How long the activity level has been below the silence level, in milliseconds.
+    silent_for: f64,
+
+    /// The number of samples delivered so far.
+    position: u64,
+}
+
+impl<'gc> Microphone<'gc> {
+    /// Start capturing from a microphone, with `object` representing it to ActionScript.
+    pub fn open(context: &mut UpdateContext<'gc>, index: usize, object: Avm2Object<'gc>) -> Self {
+        let rate = 8;
+        let open = context
+            .capture
+            .open_microphone(index, microphone_sample_rate(rate).unwrap_or(8000))
+            .inspect_err(|e| tracing::warn!("Couldn't open microphone {index}: {e}"))
+            .is_ok();
+
+        let microphone = Microphone(GcCell::new(
+            context.gc_context,
+            MicrophoneData {
+                index,
+                object,
+                open,
+                rate,
+                gain: 50.0,
+                silence_level: 10.0,
+                silence_timeout: 2000,
+                activity_level: -1.0,
+                active: false,
+                silent_for: 0.0,
+                position: 0,
+            },
+        ));
+        context.capture_manager.microphones.push(microphone);
+        microphone
+    }
+
+    pub fn index(self) -> usize {
+        self.0.read().index
+    }
+
+    /// The ActionScript object representing this device.
+    pub fn object(self) -> Avm2Object<'gc> {
+        self.0.read().object
+    }
+
+    pub fn muted(self) -> bool {
+        !self.0.read().open
+    }
+
+    pub fn activity_level(self) -> f64 {
+        self.0.read().activity_level
+    }
+
+    pub fn rate(self) -> u32 {
+        self.0.read().rate
+    }
+
+    /// Change the sample rate, in kHz. Unsupported rates are ignored.
+    pub fn set_rate(self, context: &mut UpdateContext<'gc>, rate: u32) {
+        let Some(sample_rate) = microphone_sample_rate(rate) else {
+            return;
+        };
+        let mut write = self.0.write(context.gc_context);
+        write.rate = rate;
+        if write.open {
+            write.open = context
+                .capture
+                .open_microphone(write.index, sample_rate)
+                .is_ok();
+        }
+    }
+
+    pub fn gain(self) -> f64 {
+        self.0.read().gain
+    }
+
+    pub fn set_gain(self, context: &mut UpdateContext<'gc>, gain: f64) {
+        self.0.write(context.gc_context).gain = gain.clamp(0.0, 100.0);
+    }
+
+    pub fn silence_level(self) -> f64 {
+        self.0.read().silence_level
+    }
+
+    pub fn silence_timeout(self) -> i32 {
+        self.0.read().silence_timeout
+    }
+
+    pub fn set_silence_level(self, context: &mut UpdateContext<'gc>, level: f64, timeout: i32) {
+        let mut write = self.0.write(context.gc_context);
+        write.silence_level = level.clamp(0.0, 100.0);
+        if timeout >= 0 {
+            write.silence_timeout = timeout;
+        }
+    }
+
+    fn tick(self, context: &mut UpdateContext<'gc>, dt: f64) -> Result<(), Avm2Error<'gc>> {
+        let (index, open, gain) = {
+            let read = self.0.read();
+            (read.index, read.open, read.gain)
+        };
+        if !open {
+            return Ok(());
+        }
+
+        let mut samples = context.capture.read_microphone(index);
+        let gain = (gain / 50.0) as f32;
+        for sample in &mut samples {
+            *sample = (*sample * gain).clamp(-1.0, 1.0);
+        }
+
+        let (object, position, activating) = {
+            let mut write = self.0.write(context.gc_context);
+            if !samples.is_empty() {
+                let peak = samples.iter().fold(0.0f32, |peak, s| peak.max(s.abs()));
+                write.activity_level = (peak as f64 * 100.0).round();
+            }
+
+            let activating = if write.activity_level >= write.silence_level {
+                write.silent_for = 0.0;
+                (!write.active).then_some(true)
+            } else {
+                write.silent_for += dt;
+                let timed_out =
+                    write.silence_timeout >= 0 && write.silent_for >= write.silence_timeout as f64;
+                (write.active && timed_out).then_some(false)
+            };
+            if let Some(activating) = activating {
+                write.active = activating;
+            }
+
+            let position = write.position;
+            write.position += samples.len() as u64;
+            (write.object, position, activating)
+        };
+
+        let mut activation = Avm2Activation::from_nothing(context);
+        if let Some(activating) = activating {
+            let class = activation.avm2().classes().activityevent;
+            let event = class.construct(
+                &mut activation,
+                &[
+                    "activity".into(),
+                    false.into(),
+                    false.into(),
+                    activating.into(),
+                ],
+            )?;
+            Avm2::dispatch_event(activation.context, event, object);
+        }
+
+        if !samples.is_empty() {
+            let mut storage = ByteArrayStorage::new();
+            for sample in samples {
+                storage
+                    .write_float(sample)
+                    .map_err(|e| e.to_avm(&mut activation))?;
+            }
+            storage.set_position(0);
+            let data = ByteArrayObject::from_storage(&mut activation, storage)?;
+
+            let class = activation.avm2().classes().sampledataevent;
+            let event = class.construct(
+                &mut activation,
+                &[
+                    "sampleData".into(),
+                    false.into(),
+                    false.into(),
+                    (position as f64).into(),
+                    data.into(),
+                ],
+            )?;
+            Avm2::dispatch_event(activation.context, event, object);
+        }
+
+        Ok(())
+    }
+}
+
+/// A camera that ActionScript is capturing video from.
+#[derive(Clone, Copy, Collect)]
+#[collect(no_drop)]
+pub struct Camera<'gc>(GcCell<'gc, CameraData<'gc>>);
+
+impl std::fmt::Debug for Camera<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Camera")
+            .field("ptr", &self.0.as_ptr())
+            .finish()
+    }
+}
+
+#[derive(Collect)]
+#[collect(no_drop)]
+struct CameraData<'gc> {
+    index: usize,
+
+    /// The AVM2 `Camera` representing this device.
+    object: Avm2Object<'gc>,
+
+    /// Whether the device was opened, which is false if the backend refused access.
+    open: bool,
+
+    width: u32,
+    height: u32,
+    fps: f64,
+
+    /// The most recently captured frame.
+    #[collect(require_static)]
+    frame: Option<CameraFrame>,
+
+    /// The most recently captured frame, uploaded to the renderer for `Video` to display.
+    #[collect(require_static)]
+    bitmap: Option<BitmapInfo>,
+
+    activity_level: f64,
+    current_fps: f64,
+
+    /// The frames captured, and milliseconds elapsed, since `current_fps` was last updated.
+    recent_frames: u32,
+    recent_time: f64,
+}
+
+impl<'gc> Camera<'gc> {
+    /// Start capturing from a camera, with `object` representing it to ActionScript.
+    pub fn open(context: &mut UpdateContext<'gc>, index: usize, object: Avm2Object<'gc>) -> Self {
+        // This is the default capture mode of Flash Player.
+        let (width, height, fps) = (160, 120, 15.0);
+        let open = context
+            .capture
+            .open_camera(index, width, height, fps)
+            .inspect_err(|e| tracing::warn!("Couldn't open camera {index}: {e}"))
+            .is_ok();
+
+        let camera = Camera(GcCell::new(
+            context.gc_context,
+            CameraData {
+                index,
+                object,
+                open,
+                width,
+                height,
+                fps,
+                frame: None,
+                bitmap: None,
+                activity_level: -1.0,
+                current_fps: 0.0,
+                recent_frames: 0,
+                recent_time: 0.0,
+            },
+        ));
+        context.capture_manager.cameras.push(camera);
+        camera
+    }
+
+    pub fn index(self) -> usize {
+        self.0.read().index
+    }
+
+    /// The ActionScript object representing this device.
+    pub fn object(self) -> Avm2Object<'gc> {
+        self.0.read().object
+    }
+
+    pub fn muted(self) -> bool {
+        !self.0.read().open
+    }
+
+    pub fn width(self) -> u32 {
+        self.0.read().width
+    }
+
+    pub fn height(self) -> u32 {
+        self.0.read().height
+    }
+
+    pub fn fps(self) -> f64 {
+        self.0.read().fps
+    }
+
+    pub fn current_fps(self) -> f64 {
+        self.0.read().current_fps
+    }
+
+    pub fn activity_level(self) -> f64 {
+        self.0.read().activity_level
+    }
+
+    /// Change the capture mode of the camera.
+    pub fn set_mode(self, context: &mut UpdateContext<'gc>, width: u32, height: u32, fps: f64) {
+        let mut write = self.0.write(context.gc_context);
+        write.width = width.max(1);
+        write.height = height.max(1);
+        write.fps = if fps > 0.0 { fps } else { write.fps };
+        if write.open {
+            write.open = context
+                .capture
+                .open_camera(write.index, write.width, write.height, write.fps)
+                .is_ok();
+        }
+    }
+
+    /// The most recently captured frame.
+    pub fn frame(&self) -> Ref<'_, Option<CameraFrame>> {
+        Ref::map(self.0.read(), |r| &r.frame)
+    }
+
+    /// The most recently captured frame, as a bitmap that can be rendered.
+    pub fn bitmap(self) -> Option<BitmapInfo> {
+        self.0.read().bitmap.clone()
+    }
+
+    fn tick(self, context: &mut UpdateContext<'gc>, dt: f64) {
+        let mut write = self.0.write(context.gc_context);
+        if !write.open {
+            return;
+        }
+
+        write.recent_time += dt;
+        if write.recent_time >= 1000.0 {
+            write.current_fps = write.recent_frames as f64 * 1000.0 / write.recent_time;
+            write.recent_frames = 0;
+            write.recent_time = 0.0;
+        }
+
+        let Some(frame) = context.capture.read_camera(write.index) else {
+            return;
+        };
+        write.recent_frames += 1;
+        write.activity_level = write
+            .frame
+            .as_ref()
+            .map_or(0.0, |previous| motion_level(previous, &frame));
+
+        let bitmap = Bitmap::new(
+            frame.width,
+            frame.height,
+            BitmapFormat::Rgba,
+            frame.rgba.clone(),
+        );
+        let reusable = write
+            .bitmap
+            .as_ref()
+            .filter(|b| b.width as u32 == frame.width && b.height as u32 == frame.height);
+        if let Some(existing) = reusable {
+            let region = PixelRegion::for_whole_size(frame.width, frame.height);
+            if let Err(e) = context
+                .renderer
+                .update_texture(&existing.handle, bitmap, region)
+            {
+                tracing::error!("Couldn't update camera frame: {e}");
+            }
+        } else {
+            match context.renderer.register_bitmap(bitmap) {
+                Ok(handle) => {
+                    write.bitmap = Some(BitmapInfo {
+                        handle,
+                        width: frame.width as u16,
+                        height: frame.height as u16,
+                    });
+                }
+                Err(e) => tracing::error!("Couldn't register camera frame: {e}"),
+            }
+        }
+
+        write.frame = Some(frame);
+        *context.needs_render = true;
+    }
+}
+
+/// How much the image changed between two frames, from 0 to 100.
+fn motion_level(previous: &CameraFrame, current: &CameraFrame) -> f64 {
+    if previous.width != current.width || previous.height != current.height {
+        return 100.0;
+    }
+
+    let luma = |p: &[u8]| 0.299 * p[0] as f64 + 0.587 * p[1] as f64 + 0.114 * p[2] as f64;
+    let pixels = previous
+        .rgba
+        .chunks_exact(4)
+        .zip(current.rgba.chunks_exact(4));
+    let (total, count) = pixels.fold((0.0, 0usize), |(total, count), (a, b)| {
+        (total + (luma(a) - luma(b)).abs(), count + 1)
+    });
+    if count == 0 {
+        return 0.0;
+    }
+    (total / count as f64 * 100.0 / 255.0).round()
+}
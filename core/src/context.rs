@@ -9,17 +9,21 @@ use crate::avm1::SystemProperties;
 use crate::avm1::TObject;
 use crate::avm1::{Object as Avm1Object, Value as Avm1Value};
 use crate::avm2::api_version::ApiVersion;
+use crate::avm2::debugger::Avm2Debugger;
 use crate::avm2::object::LoaderInfoObject;
+use crate::avm2::worker::{WorkerGroupHandle, WorkerId};
 use crate::avm2::Activation as Avm2Activation;
 use crate::avm2::TObject as _;
 use crate::avm2::{Avm2, Object as Avm2Object, SoundChannelObject};
 use crate::backend::{
     audio::{AudioBackend, AudioManager, SoundHandle, SoundInstanceHandle},
+    capture::CaptureBackend,
     log::LogBackend,
     navigator::NavigatorBackend,
     storage::StorageBackend,
     ui::{InputManager, UiBackend},
 };
+use crate::capture::CaptureManager;
 use crate::context_menu::ContextMenuState;
 use crate::display_object::{EditText, MovieClip, SoundTransform, Stage};
 use crate::external::ExternalInterface;
@@ -31,6 +35,7 @@ use crate::local_connection::LocalConnections;
 use crate::net_connection::NetConnections;
 use crate::player::PostFrameCallback;
 use crate::player::{MouseData, Player};
+use crate::policy_file::PolicyFiles;
 use crate::prelude::*;
 use crate::socket::Sockets;
 use crate::streams::StreamManager;
@@ -133,6 +138,9 @@ pub struct UpdateContext<'gc> {
     /// The storage backend, used for storing persistent state
     pub storage: &'gc mut dyn StorageBackend,
 
+    /// The capture backend, used to record from microphones and cameras.
+    pub capture: &'gc mut dyn CaptureBackend,
+
     /// The logging backend, used for trace output capturing.
     ///
     /// **DO NOT** use this field directly, use the `avm_trace` method instead.
@@ -164,6 +172,12 @@ pub struct UpdateContext<'gc> {
     /// that the player lives across I/O boundaries.
     pub player: Weak<Mutex<Player>>,
 
+    /// The state shared with the other ActionScript workers of this SWF.
+    pub worker_group: &'gc WorkerGroupHandle,
+
+    /// The worker this player is running.
+    pub worker_id: WorkerId,
+
     /// The player's load manager.
     ///
     /// This is required for asynchronous behavior, such as fetching data from
@@ -237,8 +251,17 @@ pub struct UpdateContext<'gc> {
     /// Manager of in-progress media streams.
     pub stream_manager: &'gc mut StreamManager<'gc>,
 
+    /// Manager of the microphones and cameras in use.
+    pub capture_manager: &'gc mut CaptureManager<'gc>,
+
     pub sockets: &'gc mut Sockets<'gc>,
 
+    /// The cross-domain policy files that grant the movie access to other domains.
+    pub policy_files: &'gc mut PolicyFiles,
+
+    /// The AVM2 debugger, following breakpoints and steps.
+    pub avm2_debugger: &'gc mut Avm2Debugger,
+
     /// List of active NetConnection instances.
     pub net_connections: &'gc mut NetConnections<'gc>,
 
@@ -345,7 +368,8 @@ impl<'gc> UpdateContext<'gc> {
     /// This should only be called once, as it makes no attempt at removing
     /// previous stage contents. If you need to load a new root movie, you
     /// should use `replace_root_movie`.
-    pub fn set_root_movie(&mut self, movie: SwfMovie) {
+    pub fn set_root_movie(&mut self, movie: impl Into<Arc<SwfMovie>>) {
+        let movie = movie.into();
         if !self.forced_frame_rate {
             *self.frame_rate = movie.frame_rate().into();
         }
@@ -358,7 +382,7 @@ impl<'gc> UpdateContext<'gc> {
             self.frame_rate,
         );
 
-        *self.swf = Arc::new(movie);
+        *self.swf = movie;
         *self.instance_counter = 0;
 
         if self.swf.is_action_script_3() {
@@ -448,7 +472,7 @@ impl<'gc> UpdateContext<'gc> {
         self.audio.set_frame_rate(*self.frame_rate);
     }
 
-    pub fn replace_root_movie(&mut self, movie: SwfMovie) {
+    pub fn replace_root_movie(&mut self, movie: impl Into<Arc<SwfMovie>>) {
         // FIXME Use RAII here, e.g. destroy and recreate
         //       the player instance instead of cleaning up.
 
@@ -479,8 +503,10 @@ impl<'a, 'gc> UpdateContext<'gc> {
         }
     }
 
-    pub fn avm_trace(&self, message: &str) {
-        self.log.avm_trace(&message.replace('\r', "\n"));
+    pub fn avm_trace(&mut self, message: &str) {
+        let message = message.replace('\r', "\n");
+        self.avm2_debugger.trace(&message);
+        self.log.avm_trace(&message);
     }
 }
 
@@ -146,6 +146,48 @@ impl<'gc> ContextMenuState<'gc> {
         }
     }
 
+    /// Build the items of a `ContextMenu` with `clipboardMenu` enabled.
+    ///
+    /// These aren't shown when the menu already has the items of a text field.
+    pub fn build_clipboard_items(
+        &mut self,
+        item_flags: ClipboardItemFlags,
+        context: &mut UpdateContext<'gc>,
+    ) {
+        let has_text_items = self
+            .callbacks
+            .iter()
+            .any(|callback| matches!(callback, ContextMenuCallback::TextControl { .. }));
+        if has_text_items {
+            return;
+        }
+
+        let language = &context.ui.language();
+        let items = [
+            (item_flags.cut, true, "context-menu-cut", "cut"),
+            (item_flags.copy, false, "context-menu-copy", "copy"),
+            (item_flags.paste, false, "context-menu-paste", "paste"),
+            (item_flags.clear, false, "context-menu-delete", "clear"),
+            (
+                item_flags.select_all,
+                true,
+                "context-menu-select-all",
+                "selectAll",
+            ),
+        ];
+        for (enabled, separator_before, caption, event) in items {
+            self.push(
+                ContextMenuItem {
+                    enabled,
+                    separator_before,
+                    caption: core_text(language, caption),
+                    checked: false,
+                },
+                ContextMenuCallback::Clipboard { event },
+            );
+        }
+    }
+
     fn build_text_items(&mut self, text: EditText<'gc>, context: &mut UpdateContext<'gc>) {
         let language = &context.ui.language();
         self.push(
@@ -246,6 +288,11 @@ pub enum ContextMenuCallback<'gc> {
         code: TextControlCode,
         text: EditText<'gc>,
     },
+    /// Dispatches a clipboard event, such as `copy`, to the focused object.
+    Clipboard {
+        #[collect(require_static)]
+        event: &'static str,
+    },
 }
 
 pub struct BuiltInItemFlags {
@@ -259,6 +306,17 @@ pub struct BuiltInItemFlags {
     pub zoom: bool,
 }
 
+/// The items of a `ContextMenu` with `clipboardMenu` enabled that can be selected,
+/// as set in its `clipboardItems`.
+#[derive(Default)]
+pub struct ClipboardItemFlags {
+    pub clear: bool,
+    pub copy: bool,
+    pub cut: bool,
+    pub paste: bool,
+    pub select_all: bool,
+}
+
 impl BuiltInItemFlags {
     pub fn for_stage(stage: Stage<'_>) -> Self {
         let root_mc = stage.root_clip().and_then(|c| c.as_movie_clip());
@@ -1,5 +1,6 @@
 mod avm1;
 mod avm2;
+mod avm2_debugger;
 mod display_object;
 mod domain;
 mod handle;
@@ -8,6 +9,7 @@ mod movie;
 use crate::context::{RenderContext, UpdateContext};
 use crate::debug_ui::avm1::Avm1ObjectWindow;
 use crate::debug_ui::avm2::Avm2ObjectWindow;
+use crate::debug_ui::avm2_debugger::Avm2DebuggerWindow;
 use crate::debug_ui::display_object::{DisplayObjectSearchWindow, DisplayObjectWindow};
 use crate::debug_ui::domain::DomainListWindow;
 use crate::debug_ui::handle::{
@@ -35,6 +37,8 @@ pub struct DebugUi {
     movie_list: Option<MovieListWindow>,
     domain_list: Option<DomainListWindow>,
     display_object_search: Option<DisplayObjectSearchWindow>,
+    avm2_debugger: Option<Avm2DebuggerWindow>,
+    avm2_debugger_suspended: bool,
 }
 
 #[derive(Debug)]
@@ -50,6 +54,7 @@ pub enum Message {
     ShowDomains,
     SaveFile(ItemToSave),
     SearchForDisplayObject,
+    ShowAvm2Debugger,
 }
 
 impl DebugUi {
@@ -97,6 +102,18 @@ impl DebugUi {
             }
         }
 
+        // Execution stopped without a remote client, so show where.
+        if context.avm2_debugger.is_suspended() && self.avm2_debugger.is_none() {
+            self.avm2_debugger = Some(Default::default());
+        }
+
+        if let Some(mut debugger) = self.avm2_debugger.take() {
+            if debugger.show(egui_ctx, context) {
+                self.avm2_debugger = Some(debugger);
+            }
+        }
+        self.avm2_debugger_suspended = context.avm2_debugger.is_suspended();
+
         for message in messages {
             match message {
                 Message::TrackDisplayObject(object) => {
@@ -132,12 +149,15 @@ impl DebugUi {
                 Message::SearchForDisplayObject => {
                     self.display_object_search = Some(Default::default());
                 }
+                Message::ShowAvm2Debugger => {
+                    self.avm2_debugger = Some(Default::default());
+                }
             }
         }
     }
 
     pub fn should_suspend_player(&self) -> bool {
-        self.display_object_search.is_some()
+        self.display_object_search.is_some() || self.avm2_debugger_suspended
     }
 
     pub fn items_to_save(&mut self) -> Vec<ItemToSave> {
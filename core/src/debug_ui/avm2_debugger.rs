@@ -0,0 +1,185 @@
+use crate::avm2::debugger::{Avm2Debugger, Breakpoint, StepMode, StopInfo, StopReason, Variable};
+use crate::context::UpdateContext;
+use egui::{CollapsingHeader, Grid, TextEdit, Ui, Window};
+
+#[derive(Debug, Default)]
+pub struct Avm2DebuggerWindow {
+    breakpoint_file: String,
+    breakpoint_line: String,
+}
+
+impl Avm2DebuggerWindow {
+    pub fn show(&mut self, egui_ctx: &egui::Context, context: &mut UpdateContext) -> bool {
+        let mut keep_open = true;
+        let debugger = &mut *context.avm2_debugger;
+
+        Window::new("AVM2 Debugger")
+            .open(&mut keep_open)
+            .scroll([true, true])
+            .show(egui_ctx, |ui| {
+                let mut enabled = debugger.is_enabled();
+                if ui.checkbox(&mut enabled, "Enabled").changed() {
+                    debugger.set_enabled(enabled);
+                }
+
+                let mut break_on_throw = debugger.break_on_throw();
+                if ui
+                    .checkbox(&mut break_on_throw, "Break when an exception is thrown")
+                    .changed()
+                {
+                    debugger.set_break_on_throw(break_on_throw);
+                }
+
+                if debugger.is_enabled()
+                    && !debugger.is_suspended()
+                    && ui.button("Pause on Next Line").clicked()
+                {
+                    debugger.request_pause();
+                }
+
+                if debugger.is_client_connected() {
+                    ui.label("A remote debugger client is connected and handles stops.");
+                }
+
+                ui.separator();
+                self.show_breakpoints(ui, debugger);
+                ui.separator();
+                show_last_stop(ui, debugger);
+            });
+
+        if !keep_open && debugger.is_suspended() {
+            debugger.resume(None);
+        }
+        keep_open
+    }
+
+    fn show_breakpoints(&mut self, ui: &mut Ui, debugger: &mut Avm2Debugger) {
+        ui.strong("Breakpoints");
+
+        let mut removed = None;
+        Grid::new(ui.id().with("breakpoints"))
+            .num_columns(2)
+            .show(ui, |ui| {
+                for breakpoint in debugger.breakpoints() {
+                    ui.label(breakpoint.to_string());
+                    if ui.button("Remove").clicked() {
+                        removed = Some(breakpoint.clone());
+                    }
+                    ui.end_row();
+                }
+            });
+        if let Some(breakpoint) = removed {
+            debugger.clear_breakpoint(&breakpoint);
+        }
+
+        ui.horizontal(|ui| {
+            TextEdit::singleline(&mut self.breakpoint_file)
+                .hint_text("File")
+                .show(ui);
+            TextEdit::singleline(&mut self.breakpoint_line)
+                .hint_text("Line")
+                .desired_width(50.0)
+                .show(ui);
+
+            let line = self.breakpoint_line.trim().parse().ok();
+            let can_add = line.is_some() && !self.breakpoint_file.trim().is_empty();
+            if ui.add_enabled(can_add, egui::Button::new("Add")).clicked() {
+                if let Some(line) = line {
+                    debugger.set_breakpoint(Breakpoint::new(self.breakpoint_file.trim(), line));
+                    self.breakpoint_line.clear();
+                }
+            }
+        });
+    }
+}
+
+fn show_last_stop(ui: &mut Ui, debugger: &mut Avm2Debugger) {
+    let Some(stop) = debugger.last_stop() else {
+        ui.label("Execution hasn't stopped yet.");
+        return;
+    };
+
+    let location = stop
+        .position()
+        .map_or_else(|| "an unknown location".to_string(), ToString::to_string);
+    if debugger.is_suspended() {
+        ui.strong(format!("Stopped at {location}: {}", describe_reason(stop)));
+        ui.label("The player is suspended; stepping continues once it runs again.");
+    } else {
+        ui.strong(format!(
+            "Last stopped at {location}: {}",
+            describe_reason(stop)
+        ));
+    }
+
+    let mut resume = None;
+    if debugger.is_suspended() {
+        ui.horizontal(|ui| {
+            if ui.button("Continue").clicked() {
+                resume = Some(None);
+            }
+            if ui.button("Step Into").clicked() {
+                resume = Some(Some(StepMode::Into));
+            }
+            if ui.button("Step Over").clicked() {
+                resume = Some(Some(StepMode::Over));
+            }
+            if ui.button("Step Out").clicked() {
+                resume = Some(Some(StepMode::Out));
+            }
+        });
+    }
+
+    CollapsingHeader::new("Call Stack")
+        .id_source(ui.id().with("backtrace"))
+        .default_open(true)
+        .show(ui, |ui| {
+            Grid::new(ui.id().with("backtrace"))
+                .num_columns(2)
+                .show(ui, |ui| {
+                    for frame in &stop.backtrace {
+                        ui.label(&frame.name);
+                        match &frame.position {
+                            Some(position) => ui.label(position.to_string()),
+                            None => ui.weak("?"),
+                        };
+                        ui.end_row();
+                    }
+                });
+        });
+    show_variables(ui, "Locals", &stop.locals, true);
+    show_variables(ui, "Registers", &stop.registers, false);
+    show_variables(ui, "Scope", &stop.scope, false);
+    show_variables(ui, "Stack", &stop.stack, false);
+
+    if let Some(step) = resume {
+        debugger.resume(step);
+    }
+}
+
+fn show_variables(ui: &mut Ui, title: &str, variables: &[Variable], default_open: bool) {
+    CollapsingHeader::new(title)
+        .id_source(ui.id().with(title))
+        .default_open(default_open)
+        .show(ui, |ui| {
+            Grid::new(ui.id().with(title))
+                .num_columns(2)
+                .show(ui, |ui| {
+                    for variable in variables {
+                        ui.label(&variable.name);
+                        ui.label(variable.value.to_string());
+                        ui.end_row();
+                    }
+                });
+        });
+}
+
+fn describe_reason(stop: &StopInfo) -> String {
+    match &stop.reason {
+        StopReason::Breakpoint => "breakpoint".to_string(),
+        StopReason::BreakpointOpcode => "bkpt opcode".to_string(),
+        StopReason::Step => "step".to_string(),
+        StopReason::Pause => "paused".to_string(),
+        StopReason::Exception(value) => format!("exception thrown: {value}"),
+    }
+}
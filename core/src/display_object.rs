@@ -58,8 +58,9 @@ use ruffle_render::bitmap::{BitmapHandle, BitmapInfo, PixelSnapping};
 use ruffle_render::blend::ExtendedBlendMode;
 use ruffle_render::commands::{CommandHandler, CommandList, RenderBlendMode};
 use ruffle_render::filters::Filter;
+use ruffle_render::matrix3d::Matrix3D;
 pub use stage::{Stage, StageAlign, StageDisplayState, StageScaleMode, WindowMode};
-pub use text::Text;
+pub use text::{StaticTextChar, Text};
 pub use video::Video;
 
 use self::loader_display::LoaderDisplayWeak;
@@ -210,6 +211,21 @@ pub struct DisplayObjectBase<'gc> {
 
     skew: f64,
 
+    // 3D transform properties `z`, `rotationX`, `rotationY` and `scaleZ`.
+    // These are only used once this object has a 3D matrix (`transform.matrix3d`),
+    // in which case they are kept in sync with it alongside the cached 2D properties above.
+    z: f64,
+    #[collect(require_static)]
+    rotation_x: Degrees,
+    #[collect(require_static)]
+    rotation_y: Degrees,
+    scale_z: f64,
+
+    /// The perspective projection applied to the 3D children of this display object.
+    /// Set by `transform.perspectiveProjection`.
+    #[collect(require_static)]
+    perspective_projection: Option<PerspectiveProjection>,
+
     /// The next display object in order of execution.
     ///
     /// `None` in an AVM2 movie.
@@ -281,6 +297,11 @@ impl<'gc> Default for DisplayObjectBase<'gc> {
             scale_x: Percent::from_unit(1.0),
             scale_y: Percent::from_unit(1.0),
             skew: 0.0,
+            z: 0.0,
+            rotation_x: Degrees::from_radians(0.0),
+            rotation_y: Degrees::from_radians(0.0),
+            scale_z: 1.0,
+            perspective_projection: None,
             next_avm1_clip: None,
             masker: None,
             maskee: None,
@@ -335,9 +356,84 @@ impl<'gc> DisplayObjectBase<'gc> {
 
     pub fn set_matrix(&mut self, matrix: Matrix) {
         self.transform.matrix = matrix;
+        self.clear_matrix3d();
         self.set_scale_rotation_cached(false);
     }
 
+    /// The 3D matrix of this object, if it has been given a 3D transform.
+    pub fn matrix3d(&self) -> Option<&Matrix3D> {
+        self.transform.matrix3d.as_ref()
+    }
+
+    /// Sets the 3D matrix of this object, as done by `transform.matrix3D`.
+    ///
+    /// The 3D properties are decomposed from the new matrix, and the 2D matrix
+    /// becomes its affine part. Setting `None` turns this back into a 2D object.
+    pub fn set_matrix3d(&mut self, matrix3d: Option<Matrix3D>) {
+        let Some(matrix3d) = matrix3d else {
+            self.clear_matrix3d();
+            return;
+        };
+        let ([_, _, z], [rotation_x, rotation_y, rotation_z], [scale_x, scale_y, scale_z]) =
+            matrix3d.decompose();
+        self.transform.matrix = matrix3d.to_matrix();
+        self.transform.matrix3d = Some(matrix3d);
+        self.z = z / Twips::TWIPS_PER_PIXEL as f64;
+        self.rotation_x = Degrees::from_radians(rotation_x);
+        self.rotation_y = Degrees::from_radians(rotation_y);
+        self.rotation = Degrees::from_radians(rotation_z);
+        self.scale_x = Percent::from_unit(scale_x);
+        self.scale_y = Percent::from_unit(scale_y);
+        self.scale_z = scale_z;
+        self.skew = 0.0;
+        self.set_scale_rotation_cached(true);
+    }
+
+    fn clear_matrix3d(&mut self) {
+        self.transform.matrix3d = None;
+        self.z = 0.0;
+        self.rotation_x = Degrees::from_radians(0.0);
+        self.rotation_y = Degrees::from_radians(0.0);
+        self.scale_z = 1.0;
+    }
+
+    /// Rebuilds the 3D matrix from the individual transform properties,
+    /// turning this into a 3D object if it wasn't one already.
+    fn recompose_matrix3d(&mut self) {
+        self.cache_scale_rotation();
+        // As with the 2D properties, `NaN`s are treated as 0 when building the matrix.
+        let radians = |degrees: Degrees| {
+            let radians = degrees.into_radians();
+            if radians.is_nan() {
+                0.0
+            } else {
+                radians
+            }
+        };
+        let unit = |unit: f64| if unit.is_nan() { 0.0 } else { unit };
+        let matrix3d = Matrix3D::recompose(
+            [
+                self.transform.matrix.tx.get().into(),
+                self.transform.matrix.ty.get().into(),
+                unit(self.z) * Twips::TWIPS_PER_PIXEL as f64,
+            ],
+            [
+                radians(self.rotation_x),
+                radians(self.rotation_y),
+                radians(self.rotation),
+            ],
+            [
+                unit(self.scale_x.unit()),
+                unit(self.scale_y.unit()),
+                unit(self.scale_z),
+            ],
+        );
+        self.transform.matrix = matrix3d.to_matrix();
+        self.transform.matrix3d = Some(matrix3d);
+        self.skew = 0.0;
+        self.set_scale_rotation_cached(true);
+    }
+
     pub fn color_transform(&self) -> &ColorTransform {
         &self.transform.color_transform
     }
@@ -358,6 +454,9 @@ impl<'gc> DisplayObjectBase<'gc> {
         let changed = self.transform.matrix.tx != x;
         self.set_transformed_by_script(true);
         self.transform.matrix.tx = x;
+        if let Some(matrix3d) = &mut self.transform.matrix3d {
+            matrix3d.raw_data[12] = x.get().into();
+        }
         changed
     }
 
@@ -369,6 +468,21 @@ impl<'gc> DisplayObjectBase<'gc> {
         let changed = self.transform.matrix.ty != y;
         self.set_transformed_by_script(true);
         self.transform.matrix.ty = y;
+        if let Some(matrix3d) = &mut self.transform.matrix3d {
+            matrix3d.raw_data[13] = y.get().into();
+        }
+        changed
+    }
+
+    fn z(&self) -> f64 {
+        self.z
+    }
+
+    fn set_z(&mut self, z: f64) -> bool {
+        let changed = self.z != z;
+        self.set_transformed_by_script(true);
+        self.z = z;
+        self.recompose_matrix3d();
         changed
     }
 
@@ -441,7 +555,34 @@ impl<'gc> DisplayObjectBase<'gc> {
         matrix.b = (self.scale_x.unit() * sin_x) as f32;
         matrix.c = (self.scale_y.unit() * -sin_y) as f32;
         matrix.d = (self.scale_y.unit() * cos_y) as f32;
+        if self.transform.matrix3d.is_some() {
+            self.recompose_matrix3d();
+        }
+
+        changed
+    }
+
+    fn rotation_x(&self) -> Degrees {
+        self.rotation_x
+    }
+
+    fn set_rotation_x(&mut self, degrees: Degrees) -> bool {
+        let changed = self.rotation_x != degrees;
+        self.set_transformed_by_script(true);
+        self.rotation_x = degrees;
+        self.recompose_matrix3d();
+        changed
+    }
+
+    fn rotation_y(&self) -> Degrees {
+        self.rotation_y
+    }
 
+    fn set_rotation_y(&mut self, degrees: Degrees) -> bool {
+        let changed = self.rotation_y != degrees;
+        self.set_transformed_by_script(true);
+        self.rotation_y = degrees;
+        self.recompose_matrix3d();
         changed
     }
 
@@ -475,6 +616,9 @@ impl<'gc> DisplayObjectBase<'gc> {
         let matrix = &mut self.transform.matrix;
         matrix.a = (cos * value.unit()) as f32;
         matrix.b = (sin * value.unit()) as f32;
+        if self.transform.matrix3d.is_some() {
+            self.recompose_matrix3d();
+        }
 
         changed
     }
@@ -509,10 +653,33 @@ impl<'gc> DisplayObjectBase<'gc> {
         let matrix = &mut self.transform.matrix;
         matrix.c = (-sin * value.unit()) as f32;
         matrix.d = (cos * value.unit()) as f32;
+        if self.transform.matrix3d.is_some() {
+            self.recompose_matrix3d();
+        }
+
+        changed
+    }
+
+    fn scale_z(&self) -> f64 {
+        self.scale_z
+    }
 
+    fn set_scale_z(&mut self, value: f64) -> bool {
+        let changed = self.scale_z != value;
+        self.set_transformed_by_script(true);
+        self.scale_z = value;
+        self.recompose_matrix3d();
         changed
     }
 
+    pub fn perspective_projection(&self) -> Option<PerspectiveProjection> {
+        self.perspective_projection
+    }
+
+    pub fn set_perspective_projection(&mut self, projection: Option<PerspectiveProjection>) {
+        self.perspective_projection = projection;
+    }
+
     fn name(&self) -> Option<AvmString<'gc>> {
         self.name
     }
@@ -796,6 +963,37 @@ impl<'gc> DisplayObjectBase<'gc> {
     }
 }
 
+/// The perspective projection used to draw the 3D children of a display object.
+/// Set by `transform.perspectiveProjection`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PerspectiveProjection {
+    /// The field of view, in degrees. This is always between 0 and 180, exclusive.
+    pub field_of_view: f64,
+
+    /// The vanishing point of the projection, in the coordinate space of its owner.
+    pub center: Point<Twips>,
+}
+
+impl PerspectiveProjection {
+    pub const DEFAULT_FIELD_OF_VIEW: f64 = 55.0;
+
+    /// The default projection, which converges to the center of the stage.
+    pub fn centered(stage_width: Twips, stage_height: Twips) -> Self {
+        Self {
+            field_of_view: Self::DEFAULT_FIELD_OF_VIEW,
+            center: Point::new(stage_width / 2, stage_height / 2),
+        }
+    }
+
+    /// The distance from the viewer to the Z = 0 plane.
+    ///
+    /// Like in Flash, this is derived from the field of view and the width of the stage.
+    pub fn focal_length(&self, stage_width: Twips) -> f64 {
+        let half_width = f64::from(stage_width.get()) / 2.0;
+        half_width / (self.field_of_view.to_radians() / 2.0).tan()
+    }
+}
+
 struct DrawCacheInfo {
     handle: BitmapHandle,
     dirty: bool,
@@ -809,7 +1007,25 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
     if this.maskee().is_some() {
         return;
     }
-    context.transform_stack.push(this.base().transform());
+    let mut transform = this.base().transform().clone();
+    if let Some(matrix3d) = transform.matrix3d {
+        if context.transform_stack.transform().matrix3d.is_none() {
+            // This is the outermost 3D object, so it applies the perspective projection.
+            // The projection is defined in stage space, so bring it into our parent's space.
+            let parent_matrix = this
+                .parent()
+                .map(|parent| parent.local_to_global_matrix())
+                .unwrap_or_default();
+            let inverse_parent_matrix = parent_matrix.inverse().unwrap_or_default();
+            transform.matrix3d = Some(
+                Matrix3D::from(inverse_parent_matrix)
+                    * this.perspective_matrix()
+                    * Matrix3D::from(parent_matrix)
+                    * matrix3d,
+            );
+        }
+    }
+    context.transform_stack.push(&transform);
     let blend_mode = this.blend_mode();
     let original_commands = if blend_mode != ExtendedBlendMode::Normal {
         Some(std::mem::take(&mut context.commands))
@@ -914,12 +1130,12 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
         if cache_info.dirty {
             let mut transform_stack = TransformStack::new();
             transform_stack.push(&Transform {
-                color_transform: Default::default(),
                 matrix: Matrix {
                     tx: -offset_x,
                     ty: -offset_y,
                     ..cache_info.base_transform.matrix
                 },
+                ..Default::default()
             });
             let mut offscreen_context = RenderContext {
                 renderer: context.renderer,
@@ -952,6 +1168,7 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
                         ..Default::default()
                     },
                     color_transform: cache_info.base_transform.color_transform,
+                    matrix3d: None,
                 },
                 true,
                 PixelSnapping::Always, // cacheAsBitmap forces pixel snapping
@@ -1026,7 +1243,7 @@ pub fn apply_standard_mask_and_scroll<'gc, F>(
         // Translate everything that we render (including DisplayObject.mask)
         context.transform_stack.push(&Transform {
             matrix: Matrix::translate(-rect.x_min, -rect.y_min),
-            color_transform: Default::default(),
+            ..Default::default()
         });
     }
 
@@ -1145,7 +1362,28 @@ pub trait TDisplayObject<'gc>:
 
     /// The world bounding box of this object including children, relative to the stage.
     fn world_bounds(&self) -> Rectangle<Twips> {
-        self.bounds_with_transform(&self.local_to_global_matrix())
+        let Some(matrix3d) = self.local_to_global_matrix3d() else {
+            return self.bounds_with_transform(&self.local_to_global_matrix());
+        };
+
+        // Project the corners of the local bounds.
+        let bounds = self.bounds();
+        if !bounds.is_valid() {
+            return Default::default();
+        }
+        let mut world_bounds = Rectangle::default();
+        for (x, y) in [
+            (bounds.x_min, bounds.y_min),
+            (bounds.x_max, bounds.y_min),
+            (bounds.x_min, bounds.y_max),
+            (bounds.x_max, bounds.y_max),
+        ] {
+            if let Some([x, y]) = matrix3d.project_point(x.get().into(), y.get().into()) {
+                let point = Point::new(Twips::new(x.round() as i32), Twips::new(y.round() as i32));
+                world_bounds = world_bounds.encompass(point);
+            }
+        }
+        world_bounds
     }
 
     /// Bounds used for drawing debug rects and picking objects.
@@ -1275,14 +1513,139 @@ pub trait TDisplayObject<'gc>:
         self.local_to_global_matrix().inverse()
     }
 
+    /// Returns the 3D matrix for transforming from this object's local space to global stage space,
+    /// without any perspective projection.
+    /// Used by `Transform.getRelativeMatrix3D`.
+    fn concatenated_matrix3d(&self) -> Matrix3D {
+        let mut matrix = Matrix3D::IDENTITY;
+        let mut node = Some((*self).into());
+        while let Some(display_object) = node {
+            if display_object.as_stage().is_some() {
+                break;
+            }
+            if let Some(rect) = display_object.scroll_rect() {
+                matrix = Matrix3D::from(Matrix::translate(-rect.x_min, -rect.y_min)) * matrix;
+            }
+            matrix = display_object.local_matrix3d() * matrix;
+            node = display_object.parent();
+        }
+        matrix
+    }
+
+    /// Returns the matrix for transforming from this object's local space to global stage space,
+    /// including the perspective projection of any 3D transforms.
+    /// `None` is returned if neither this object nor its ancestors have a 3D transform,
+    /// in which case `local_to_global_matrix` should be used.
+    fn local_to_global_matrix3d(&self) -> Option<Matrix3D> {
+        // The projection is applied once, by the outermost 3D object.
+        let mut outermost_3d = None;
+        let mut node = Some((*self).into());
+        while let Some(display_object) = node {
+            if display_object.as_stage().is_some() {
+                break;
+            }
+            if display_object.base().matrix3d().is_some() {
+                outermost_3d = Some(display_object);
+            }
+            node = display_object.parent();
+        }
+        let outermost_3d = outermost_3d?;
+
+        let mut matrix = Matrix3D::IDENTITY;
+        let mut node = Some((*self).into());
+        while let Some(display_object) = node {
+            if let Some(rect) = display_object.scroll_rect() {
+                matrix = Matrix3D::from(Matrix::translate(-rect.x_min, -rect.y_min)) * matrix;
+            }
+            matrix = display_object.local_matrix3d() * matrix;
+            if DisplayObject::ptr_eq(display_object, outermost_3d) {
+                break;
+            }
+            node = display_object.parent();
+        }
+
+        let parent_matrix = outermost_3d
+            .parent()
+            .map(|parent| parent.local_to_global_matrix())
+            .unwrap_or_default();
+        Some(outermost_3d.perspective_matrix() * Matrix3D::from(parent_matrix) * matrix)
+    }
+
+    /// This object's transform in its parent's coordinate space, as a 3D matrix.
+    fn local_matrix3d(&self) -> Matrix3D {
+        let base = self.base();
+        base.matrix3d()
+            .copied()
+            .unwrap_or_else(|| (*base.matrix()).into())
+    }
+
+    /// The perspective projection applied to this object's 3D transform, in global stage space.
+    ///
+    /// This comes from the `perspectiveProjection` of the nearest ancestor that has one,
+    /// or from the default projection centered on the stage.
+    fn perspective_matrix(&self) -> Matrix3D {
+        let mut owner = None;
+        let mut stage_size = None;
+        let mut node = self.parent();
+        while let Some(display_object) = node {
+            if let Some(stage) = display_object.as_stage() {
+                stage_size = Some(stage.stage_size());
+                break;
+            }
+            if owner.is_none() {
+                if let Some(projection) = display_object.base().perspective_projection() {
+                    owner = Some((display_object, projection));
+                }
+            }
+            node = display_object.parent();
+        }
+
+        let stage_width = match stage_size {
+            Some((width, _)) => Twips::from_pixels_i32(width as i32),
+            None => self.movie().width(),
+        };
+        let (projection, center) = match owner {
+            Some((owner, projection)) => {
+                let center = owner.local_to_global_matrix() * projection.center;
+                (projection, center)
+            }
+            None => {
+                let stage_height = match stage_size {
+                    Some((_, height)) => Twips::from_pixels_i32(height as i32),
+                    None => self.movie().height(),
+                };
+                let projection = PerspectiveProjection::centered(stage_width, stage_height);
+                (projection, projection.center)
+            }
+        };
+        Matrix3D::perspective(
+            projection.focal_length(stage_width),
+            center.x.get().into(),
+            center.y.get().into(),
+        )
+    }
+
     /// Converts a local position to a global stage position
     fn local_to_global(&self, local: Point<Twips>) -> Point<Twips> {
+        if let Some(matrix3d) = self.local_to_global_matrix3d() {
+            if let Some([x, y]) = matrix3d.project_point(local.x.get().into(), local.y.get().into())
+            {
+                return Point::new(Twips::new(x.round() as i32), Twips::new(y.round() as i32));
+            }
+        }
         self.local_to_global_matrix() * local
     }
 
     /// Converts a local position on the stage to a local position on this display object
     /// Returns `None` if the object has zero scale.
     fn global_to_local(&self, global: Point<Twips>) -> Option<Point<Twips>> {
+        if let Some(matrix3d) = self.local_to_global_matrix3d() {
+            let [x, y] = matrix3d.unproject_point(global.x.get().into(), global.y.get().into())?;
+            return Some(Point::new(
+                Twips::new(x.round() as i32),
+                Twips::new(y.round() as i32),
+            ));
+        }
         self.global_to_local_matrix().map(|matrix| matrix * global)
     }
 
@@ -1416,6 +1779,84 @@ pub trait TDisplayObject<'gc>:
         }
     }
 
+    /// The `z` position in pixels of this display object in local space.
+    /// Returned by the `z` ActionScript property.
+    fn z(&self) -> f64 {
+        self.base().z()
+    }
+
+    /// Sets the `z` position in pixels of this display object in local space.
+    /// Set by the `z` ActionScript property, which gives this object a 3D transform.
+    /// This invalidates any ancestors cacheAsBitmap automatically.
+    fn set_z(&self, gc_context: &Mutation<'gc>, z: f64) {
+        if self.base_mut(gc_context).set_z(z) {
+            if let Some(parent) = self.parent() {
+                parent.invalidate_cached_bitmap(gc_context);
+            }
+        }
+    }
+
+    /// The rotation in degrees around the X axis of this display object in local space.
+    /// Returned by the `rotationX` ActionScript property.
+    fn rotation_x(&self) -> Degrees {
+        self.base().rotation_x()
+    }
+
+    /// Sets the rotation in degrees around the X axis of this display object in local space.
+    /// Set by the `rotationX` ActionScript property, which gives this object a 3D transform.
+    /// This invalidates any ancestors cacheAsBitmap automatically.
+    fn set_rotation_x(&self, gc_context: &Mutation<'gc>, degrees: Degrees) {
+        if self.base_mut(gc_context).set_rotation_x(degrees) {
+            if let Some(parent) = self.parent() {
+                parent.invalidate_cached_bitmap(gc_context);
+            }
+        }
+    }
+
+    /// The rotation in degrees around the Y axis of this display object in local space.
+    /// Returned by the `rotationY` ActionScript property.
+    fn rotation_y(&self) -> Degrees {
+        self.base().rotation_y()
+    }
+
+    /// Sets the rotation in degrees around the Y axis of this display object in local space.
+    /// Set by the `rotationY` ActionScript property, which gives this object a 3D transform.
+    /// This invalidates any ancestors cacheAsBitmap automatically.
+    fn set_rotation_y(&self, gc_context: &Mutation<'gc>, degrees: Degrees) {
+        if self.base_mut(gc_context).set_rotation_y(degrees) {
+            if let Some(parent) = self.parent() {
+                parent.invalidate_cached_bitmap(gc_context);
+            }
+        }
+    }
+
+    /// The Z axis scale for this display object in local space.
+    /// Returned by the `scaleZ` ActionScript property.
+    fn scale_z(&self) -> f64 {
+        self.base().scale_z()
+    }
+
+    /// Sets the Z axis scale for this display object in local space.
+    /// Set by the `scaleZ` ActionScript property, which gives this object a 3D transform.
+    /// This invalidates any ancestors cacheAsBitmap automatically.
+    fn set_scale_z(&self, gc_context: &Mutation<'gc>, value: f64) {
+        if self.base_mut(gc_context).set_scale_z(value) {
+            if let Some(parent) = self.parent() {
+                parent.invalidate_cached_bitmap(gc_context);
+            }
+        }
+    }
+
+    /// Sets the 3D matrix of this display object, or turns it back into a 2D object.
+    /// Set by the `transform.matrix3D` ActionScript property.
+    /// This invalidates any ancestors cacheAsBitmap automatically.
+    fn set_matrix3d(&self, gc_context: &Mutation<'gc>, matrix3d: Option<Matrix3D>) {
+        self.base_mut(gc_context).set_matrix3d(matrix3d);
+        if let Some(parent) = self.parent() {
+            parent.invalidate_cached_bitmap(gc_context);
+        }
+    }
+
     /// Gets the pixel width of the AABB containing this display object in local space.
     /// Returned by the ActionScript `_width`/`width` properties.
     fn width(&self) -> f64 {
@@ -12,7 +12,7 @@ use crate::avm2::{
     Activation as Avm2Activation, EventObject as Avm2EventObject, Object as Avm2Object,
     StageObject as Avm2StageObject, TObject as _,
 };
-use crate::backend::ui::MouseCursor;
+use crate::backend::ui::{ClipboardContent, MouseCursor};
 use crate::context::{RenderContext, UpdateContext};
 use crate::display_object::interactive::{
     InteractiveObject, InteractiveObjectBase, TInteractiveObject,
@@ -165,6 +165,12 @@ pub struct EditTextData<'gc> {
     /// Whether this EditText represents an AVM2 TextLine.
     is_tlf: bool,
 
+    /// Display objects laid out inline with the text, from the
+    /// `GraphicElement`s of a TextLine, along with their position in the text.
+    ///
+    /// These are rendered along with the text, but aren't children of it.
+    inline_graphics: Vec<(usize, DisplayObject<'gc>)>,
+
     /// Restrict what characters the user may input.
     #[collect(require_static)]
     restrict: EditTextRestrict,
@@ -302,6 +308,7 @@ impl<'gc> EditText<'gc> {
                 max_chars: swf_tag.max_length().unwrap_or_default() as i32,
                 mouse_wheel_enabled: true,
                 is_tlf: false,
+                inline_graphics: Vec::new(),
                 restrict: EditTextRestrict::allow_all(),
                 last_click: None,
             },
@@ -594,6 +601,20 @@ impl<'gc> EditText<'gc> {
             .set(EditTextFlag::ALWAYS_SHOW_SELECTION, value);
     }
 
+    pub fn use_rich_text_clipboard(self) -> bool {
+        self.0
+            .read()
+            .flags
+            .contains(EditTextFlag::USE_RICH_TEXT_CLIPBOARD)
+    }
+
+    pub fn set_use_rich_text_clipboard(self, context: &mut UpdateContext<'gc>, value: bool) {
+        self.0
+            .write(context.gc())
+            .flags
+            .set(EditTextFlag::USE_RICH_TEXT_CLIPBOARD, value);
+    }
+
     pub fn is_device_font(self) -> bool {
         !self.0.read().flags.contains(EditTextFlag::USE_OUTLINES)
     }
@@ -625,6 +646,37 @@ impl<'gc> EditText<'gc> {
         self.0.write(gc_context).is_tlf = is_tlf;
     }
 
+    pub fn set_inline_graphics(
+        self,
+        gc_context: &Mutation<'gc>,
+        inline_graphics: Vec<(usize, DisplayObject<'gc>)>,
+    ) {
+        self.0.write(gc_context).inline_graphics = inline_graphics;
+    }
+
+    pub fn has_inline_graphics(self) -> bool {
+        !self.0.read().inline_graphics.is_empty()
+    }
+
+    pub fn inline_graphics(self) -> Vec<DisplayObject<'gc>> {
+        self.0
+            .read()
+            .inline_graphics
+            .iter()
+            .map(|(_, graphic)| *graphic)
+            .collect()
+    }
+
+    /// The inline graphic displayed at the given position, if any.
+    pub fn inline_graphic_at(self, position: usize) -> Option<DisplayObject<'gc>> {
+        self.0
+            .read()
+            .inline_graphics
+            .iter()
+            .find(|(graphic_position, _)| *graphic_position == position)
+            .map(|(_, graphic)| *graphic)
+    }
+
     pub fn draw_layout_boxes(self) -> bool {
         self.0
             .read()
@@ -734,7 +786,7 @@ impl<'gc> EditText<'gc> {
 
     /// Internal padding between the bounds of the EditText and the text.
     /// Applies to each side.
-    const INTERNAL_PADDING: f64 = 2.0;
+    pub const INTERNAL_PADDING: f64 = 2.0;
 
     /// Relayout the `EditText`.
     ///
@@ -968,6 +1020,8 @@ impl<'gc> EditText<'gc> {
             let baseline_adjustment = baseline - params.height();
             let caret_height = baseline + descent;
             let mut caret_x = Twips::ZERO;
+            // In right-to-left text, a character starts at its right edge.
+            let rtl = params.is_rtl();
             font.evaluate(
                 text,
                 self.text_transform(color, baseline_adjustment),
@@ -983,6 +1037,7 @@ impl<'gc> EditText<'gc> {
                             context.transform_stack.push(&Transform {
                                 matrix: transform.matrix,
                                 color_transform: ColorTransform::IDENTITY,
+                                matrix3d: None,
                             });
                         } else {
                             context.transform_stack.push(transform);
@@ -998,10 +1053,10 @@ impl<'gc> EditText<'gc> {
                     // Update caret position
                     if let Some(caret) = caret {
                         if pos == caret {
-                            caret_x = x;
+                            caret_x = if rtl { x + advance } else { x };
                         } else if caret > 0 && pos == caret - 1 {
                             // The caret may be rendered at the end, after all glyphs.
-                            caret_x = x + advance;
+                            caret_x = if rtl { x } else { x + advance };
                         }
                     }
                 },
@@ -1303,19 +1358,20 @@ impl<'gc> EditText<'gc> {
         }
 
         // ...then find the box within that line that is the closest match to the X position.
-        let mut closest_layout_box: Option<&LayoutBox<'gc>> = None;
-        if let Some(line) = closest_line {
-            for layout_box in line.boxes_iter() {
-                if layout_box.is_text_box() {
-                    if position.x >= layout_box.bounds().offset_x() || closest_layout_box.is_none()
-                    {
-                        closest_layout_box = Some(layout_box);
+        // That is the rightmost box starting before the position, or the leftmost box.
+        // Boxes are in text order, which differs from their visual order in bidirectional text.
+        let closest_layout_box: Option<&LayoutBox<'gc>> = closest_line.and_then(|line| {
+            line.boxes_iter()
+                .filter(|layout_box| layout_box.is_text_box())
+                .max_by_key(|layout_box| {
+                    let offset_x = layout_box.bounds().offset_x();
+                    if position.x >= offset_x {
+                        (true, offset_x)
                     } else {
-                        break;
+                        (false, -offset_x)
                     }
-                }
-            }
-        }
+                })
+        });
 
         if let Some(layout_box) = closest_layout_box {
             let origin = layout_box.bounds().origin();
@@ -1326,7 +1382,10 @@ impl<'gc> EditText<'gc> {
             if let Some((text, _tf, font, params, color)) =
                 layout_box.as_renderable_text(text.text_spans.text())
             {
-                let mut result = 0;
+                // Glyphs are evaluated from left to right, so in right-to-left text
+                // the leftmost position is the end of the text.
+                let rtl = params.is_rtl();
+                let mut result = if rtl { text.len() } else { 0 };
                 let baseline_adjustment =
                     font.get_baseline_for_height(params.height()) - params.height();
                 font.evaluate(
@@ -1335,7 +1394,7 @@ impl<'gc> EditText<'gc> {
                     params,
                     |pos, _transform, _glyph: &Glyph, advance, x| {
                         if local_position.x >= x {
-                            if local_position.x > x + (advance / 2) {
+                            if (local_position.x > x + (advance / 2)) != rtl {
                                 result = string_utils::next_char_boundary(text, pos);
                             } else {
                                 result = pos;
@@ -1402,6 +1461,66 @@ impl<'gc> EditText<'gc> {
         }
     }
 
+    /// Put the text in the range [from, to) on the clipboard, along with its
+    /// formatting when `useRichTextClipboard` is enabled.
+    fn copy_to_clipboard(self, from: usize, to: usize, context: &mut UpdateContext<'gc>) {
+        let text = self.text()[from..to].to_string();
+        if self.use_rich_text_clipboard() {
+            let html = self.0.read().text_spans.slice(from, to).to_html();
+            context.ui.set_clipboard_contents(ClipboardContent {
+                text: Some(text),
+                html: Some(html.to_string()),
+                ..Default::default()
+            });
+        } else {
+            context.ui.set_clipboard_content(text);
+        }
+    }
+
+    /// Paste the HTML on the clipboard over the selection, keeping its
+    /// formatting, when `useRichTextClipboard` is enabled.
+    ///
+    /// Returns the length of the pasted text, or `None` when the text has to
+    /// be pasted as plain text instead.
+    fn paste_rich_text(
+        self,
+        selection: TextSelection,
+        context: &mut UpdateContext<'gc>,
+    ) -> Option<usize> {
+        if !self.use_rich_text_clipboard() {
+            return None;
+        }
+        let html = context.ui.clipboard_contents().html?;
+
+        let spans = {
+            let read = self.0.read();
+            FormatSpans::from_html(
+                &WString::from_utf8(&html),
+                read.text_spans.default_format().clone(),
+                read.flags.contains(EditTextFlag::MULTILINE),
+                read.flags.contains(EditTextFlag::CONDENSE_WHITE),
+                read.static_data.swf.version(),
+            )
+        };
+
+        // Text that would be restricted or truncated is pasted as plain text.
+        let text = spans.text().to_string();
+        if spans.text().is_empty()
+            || self.0.read().restrict.filter_allowed(&text) != text
+            || spans.text().len() > self.available_chars()
+        {
+            return None;
+        }
+
+        self.0.write(context.gc()).text_spans.replace_with_spans(
+            selection.start(),
+            selection.end(),
+            &spans,
+        );
+        self.relayout(context);
+        Some(spans.text().len())
+    }
+
     pub fn text_control_input(
         self,
         control_code: TextControlCode,
@@ -1439,7 +1558,7 @@ impl<'gc> EditText<'gc> {
             | TextControlCode::MoveRightWord
             | TextControlCode::MoveRightLine
             | TextControlCode::MoveRightDocument => {
-                let new_pos = if selection.is_caret() && selection.to < self.text().len() {
+                let new_pos = if selection.is_caret() {
                     self.find_new_position(control_code, selection.to)
                 } else {
                     selection.end()
@@ -1453,8 +1572,8 @@ impl<'gc> EditText<'gc> {
             | TextControlCode::SelectLeftWord
             | TextControlCode::SelectLeftLine
             | TextControlCode::SelectLeftDocument => {
-                if selection.to > 0 {
-                    let new_pos = self.find_new_position(control_code, selection.to);
+                let new_pos = self.find_new_position(control_code, selection.to);
+                if new_pos != selection.to {
                     self.set_selection(
                         Some(TextSelection::for_range(selection.from, new_pos)),
                         context.gc_context,
@@ -1465,8 +1584,8 @@ impl<'gc> EditText<'gc> {
             | TextControlCode::SelectRightWord
             | TextControlCode::SelectRightLine
             | TextControlCode::SelectRightDocument => {
-                if selection.to < self.text().len() {
-                    let new_pos = self.find_new_position(control_code, selection.to);
+                let new_pos = self.find_new_position(control_code, selection.to);
+                if new_pos != selection.to {
                     self.set_selection(
                         Some(TextSelection::for_range(selection.from, new_pos)),
                         context.gc_context,
@@ -1480,50 +1599,56 @@ impl<'gc> EditText<'gc> {
                 );
             }
             TextControlCode::Copy => {
-                let text = &self.text()[selection.start()..selection.end()];
-                context.ui.set_clipboard_content(text.to_string());
+                self.copy_to_clipboard(selection.start(), selection.end(), context);
             }
             TextControlCode::Paste => 'paste: {
-                let text = context.ui.clipboard_content();
-                if text.is_empty() {
-                    // When the clipboard is empty, nothing is pasted
-                    // and the already selected text is not removed.
-                    // Note that if the clipboard is not empty, but does not have
-                    // any allowed characters, the selected text is removed.
-                    break 'paste;
-                }
+                let pasted_len = if let Some(len) = self.paste_rich_text(selection, context) {
+                    len
+                } else {
+                    let text = context.ui.clipboard_content();
+                    if text.is_empty() {
+                        // When the clipboard is empty, nothing is pasted
+                        // and the already selected text is not removed.
+                        // Note that if the clipboard is not empty, but does not have
+                        // any allowed characters, the selected text is removed.
+                        break 'paste;
+                    }
 
-                let mut text = self.0.read().restrict.filter_allowed(&text);
+                    let mut text = self.0.read().restrict.filter_allowed(&text);
 
-                if text.len() > self.available_chars() && self.available_chars() > 0 {
-                    text = text[0..self.available_chars()].to_owned();
-                }
+                    if text.len() > self.available_chars() && self.available_chars() > 0 {
+                        text = text[0..self.available_chars()].to_owned();
+                    }
+
+                    if text.len() > self.available_chars() {
+                        break 'paste;
+                    }
 
-                if text.len() <= self.available_chars() {
                     self.replace_text(
                         selection.start(),
                         selection.end(),
                         &WString::from_utf8(&text),
                         context,
                     );
-                    let new_pos = selection.start() + text.len();
-                    if is_selectable {
-                        self.set_selection(
-                            Some(TextSelection::for_position(new_pos)),
-                            context.gc_context,
-                        );
-                    } else {
-                        self.set_selection(
-                            Some(TextSelection::for_position(self.text().len())),
-                            context.gc_context,
-                        );
-                    }
-                    changed = true;
+                    text.len()
+                };
+
+                let new_pos = selection.start() + pasted_len;
+                if is_selectable {
+                    self.set_selection(
+                        Some(TextSelection::for_position(new_pos)),
+                        context.gc_context,
+                    );
+                } else {
+                    self.set_selection(
+                        Some(TextSelection::for_position(self.text().len())),
+                        context.gc_context,
+                    );
                 }
+                changed = true;
             }
             TextControlCode::Cut => {
-                let text = &self.text()[selection.start()..selection.end()];
-                context.ui.set_clipboard_content(text.to_string());
+                self.copy_to_clipboard(selection.start(), selection.end(), context);
 
                 self.replace_text(selection.start(), selection.end(), WStr::empty(), context);
                 if is_selectable {
@@ -1595,7 +1720,22 @@ impl<'gc> EditText<'gc> {
     /// * For left/right moves it will represent the final caret position.
     /// * For backspace/delete it will represent the position to which the text should be deleted.
     fn find_new_position(self, control_code: TextControlCode, current_pos: usize) -> usize {
+        // Arrow keys move the caret visually, which is against the order of
+        // the text when it runs from right to left.
+        let rtl = self.is_rtl_at(current_pos);
         match control_code {
+            TextControlCode::SelectRight | TextControlCode::MoveRight if rtl => {
+                string_utils::prev_char_boundary(&self.text(), current_pos)
+            }
+            TextControlCode::SelectLeft | TextControlCode::MoveLeft if rtl => {
+                string_utils::next_char_boundary(&self.text(), current_pos)
+            }
+            TextControlCode::SelectRightWord | TextControlCode::MoveRightWord if rtl => {
+                self.find_prev_word_boundary(current_pos, false)
+            }
+            TextControlCode::SelectLeftWord | TextControlCode::MoveLeftWord if rtl => {
+                self.find_next_word_boundary(current_pos, false)
+            }
             TextControlCode::SelectRight | TextControlCode::MoveRight | TextControlCode::Delete => {
                 string_utils::next_char_boundary(&self.text(), current_pos)
             }
@@ -1624,6 +1764,19 @@ impl<'gc> EditText<'gc> {
         }
     }
 
+    /// Whether the text at `pos` runs from right to left.
+    ///
+    /// At the end of the text, the last character is checked instead.
+    fn is_rtl_at(self, pos: usize) -> bool {
+        let text_len = self.text().len();
+        let pos = if pos >= text_len {
+            string_utils::prev_char_boundary(&self.text(), text_len)
+        } else {
+            pos
+        };
+        self.0.read().layout.is_rtl_at(pos)
+    }
+
     /// Find the nearest word boundary before (or exceptionally at) `pos`,
     /// which is applicable for selection.
     ///
@@ -1920,6 +2073,47 @@ impl<'gc> EditText<'gc> {
         })
     }
 
+    /// The bounds of the character at the given position, in local coordinates.
+    ///
+    /// Returns `None` for characters that are not rendered, such as newlines.
+    pub fn char_bounds(self, position: usize) -> Option<Rectangle<Twips>> {
+        let edit_text = self.0.read();
+        let layout_box = edit_text.layout.boxes_iter().find(|layout_box| {
+            layout_box.is_text_box()
+                && layout_box.start() <= position
+                && position < layout_box.end()
+        })?;
+        let (text, _tf, font, params, _color) =
+            layout_box.as_renderable_text(edit_text.text_spans.displayed_text())?;
+
+        let index = position - layout_box.start();
+        let mut glyph_bounds = None;
+        font.evaluate(
+            text,
+            Default::default(),
+            params,
+            |pos, _transform, _glyph: &Glyph, advance, x| {
+                if pos == index {
+                    glyph_bounds = Some((x, advance));
+                }
+            },
+        );
+        let (x, advance) = glyph_bounds?;
+
+        let origin = layout_box.bounds().origin();
+        let padding = Twips::from_pixels(Self::INTERNAL_PADDING);
+        let x_min = edit_text.bounds.x_min + padding + origin.x() + x
+            - Twips::from_pixels(edit_text.hscroll);
+        let y_min =
+            edit_text.bounds.y_min + padding + origin.y() - edit_text.vertical_scroll_offset();
+        Some(Rectangle {
+            x_min,
+            x_max: x_min + advance,
+            y_min,
+            y_max: y_min + layout_box.bounds().height(),
+        })
+    }
+
     pub fn line_length(self, line: usize) -> Option<usize> {
         Some(self.0.read().layout.lines().get(line)?.len())
     }
@@ -2344,6 +2538,10 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
         context.commands.pop_mask();
 
         context.transform_stack.pop();
+
+        for (_, graphic) in &edit_text.inline_graphics {
+            graphic.render(context);
+        }
     }
 
     fn allow_as_mask(&self) -> bool {
@@ -2714,6 +2912,7 @@ bitflags::bitflags! {
         const DRAW_LAYOUT_BOXES = 1 << 2;
         const CONDENSE_WHITE = 1 << 13;
         const ALWAYS_SHOW_SELECTION = 1 << 14;
+        const USE_RICH_TEXT_CLIPBOARD = 1 << 15;
 
         // The following bits need to match `swf::EditTextFlag`.
         const READ_ONLY = 1 << 3;
@@ -522,8 +522,10 @@ impl<'gc> MovieClip<'gc> {
     ) -> bool {
         {
             let read = self.0.read();
-            if read.static_data.preload_progress.read().next_preload_chunk
-                >= read.static_data.swf.len() as u64
+            let next_preload_chunk = read.static_data.preload_progress.read().next_preload_chunk;
+            if next_preload_chunk == u64::MAX
+                || (next_preload_chunk >= read.static_data.swf.len() as u64
+                    && read.static_data.swf.is_loaded())
             {
                 return true;
             }
@@ -532,7 +534,10 @@ impl<'gc> MovieClip<'gc> {
         // TODO: Re-creating static data because preload step occurs after construction.
         // Should be able to hoist this up somewhere, or use MaybeUninit.
         let mut static_data = (*self.0.read().static_data).clone();
-        let data = self.0.read().static_data.swf.clone();
+        // A movie may turn out to be longer than its header says, in which
+        // case the rest of it is preloaded as well.
+        static_data.swf.extend_to_movie_data();
+        let data = static_data.swf.clone();
         let (mut cur_frame, mut start_pos, next_preload_chunk, preload_symbol) = {
             let read = static_data.preload_progress.read();
             (
@@ -542,7 +547,13 @@ impl<'gc> MovieClip<'gc> {
                 read.cur_preload_symbol,
             )
         };
+        // While the movie is still loading, only the tags that have fully
+        // arrived can be preloaded.
+        let is_loading = !data.is_loaded();
         let mut reader = data.read_from(next_preload_chunk);
+        if is_loading {
+            tag_utils::truncate_to_complete_tags(&mut reader);
+        }
 
         if let Some(cur_preload_symbol) = preload_symbol {
             match context
@@ -796,7 +807,10 @@ impl<'gc> MovieClip<'gc> {
         } else {
             Ok(true)
         };
-        let is_finished = end_tag_found || result.is_err() || !result.unwrap_or_default();
+        // Running out of data isn't the end of the movie if more is on its way.
+        let is_waiting_for_data = is_loading && reader.get_ref().is_empty();
+        let is_finished = !is_waiting_for_data
+            && (end_tag_found || result.is_err() || !result.unwrap_or_default());
 
         self.0
             .write(context.gc_context)
@@ -1298,7 +1312,8 @@ impl<'gc> MovieClip<'gc> {
             return max(self.total_bytes(), 0) as u32;
         }
 
-        let swf_header_size = max(self.total_bytes(), 0) as u32 - self.tag_stream_len() as u32;
+        let swf_header_size =
+            (max(self.total_bytes(), 0) as u32).saturating_sub(self.tag_stream_len() as u32);
 
         swf_header_size + progress_read.next_preload_chunk as u32
     }
@@ -1316,7 +1331,7 @@ impl<'gc> MovieClip<'gc> {
         if self.is_root() {
             compressed_movie_size as u32
         } else {
-            let uncompressed_movie_size = movie.data().len();
+            let uncompressed_movie_size = movie.total_data_len();
             let uncompressed_clip_size = self.tag_stream_len() as u32;
 
             (uncompressed_clip_size as f64 * compressed_movie_size as f64
@@ -2802,7 +2817,9 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
                 }
             }
 
-            let point = local_matrix * point;
+            let Some(point) = self.global_to_local(point) else {
+                return false;
+            };
             if self.0.read().drawing.hit_test(point, &local_matrix) {
                 return true;
             }
@@ -3148,7 +3165,7 @@ impl<'gc> TInteractiveObject<'gc> for MovieClip<'gc> {
 
             // Check drawing, because this selects the current clip, it must have mouse enabled
             if self.mouse_enabled() && check_non_interactive {
-                let point = local_matrix * point;
+                let point = self.global_to_local(point)?;
                 if self.0.read().drawing.hit_test(point, &local_matrix) {
                     return Some(this);
                 }
@@ -3288,7 +3305,9 @@ impl<'gc> TInteractiveObject<'gc> for MovieClip<'gc> {
 
             // Check drawing, because this selects the current clip, it must have mouse enabled
             if self.world_bounds().contains(point) {
-                let point = local_matrix * point;
+                let Some(point) = self.global_to_local(point) else {
+                    return Avm2MousePick::Miss;
+                };
 
                 if self.0.read().drawing.hit_test(point, &local_matrix) {
                     return if self.mouse_enabled() {
@@ -4,6 +4,7 @@ use crate::avm2::{
 use crate::context::{RenderContext, UpdateContext};
 use crate::display_object::{DisplayObjectBase, DisplayObjectPtr};
 use crate::font::TextRenderSettings;
+use crate::library::Library;
 use crate::prelude::*;
 use crate::tag_utils::SwfMovie;
 use crate::vminterface::Instantiator;
@@ -34,6 +35,30 @@ pub struct TextData<'gc> {
     #[collect(require_static)]
     render_settings: TextRenderSettings,
     avm2_object: Option<Avm2Object<'gc>>,
+
+    /// Which characters are selected by a `TextSnapshot`, by index.
+    #[collect(require_static)]
+    selection: Vec<bool>,
+
+    /// The color that selected characters are highlighted with.
+    #[collect(require_static)]
+    select_color: swf::Color,
+}
+
+/// A character of static text, in the coordinates of the text.
+#[derive(Clone, Debug)]
+pub struct StaticTextChar {
+    pub character: char,
+    pub font_name: String,
+    pub color: swf::Color,
+    pub height: Twips,
+
+    /// The position of the character on its baseline.
+    pub position: Point<Twips>,
+
+    pub advance: Twips,
+    pub ascent: Twips,
+    pub descent: Twips,
 }
 
 impl<'gc> Text<'gc> {
@@ -58,6 +83,8 @@ impl<'gc> Text<'gc> {
                 ),
                 render_settings: Default::default(),
                 avm2_object: None,
+                selection: Vec::new(),
+                select_color: swf::Color::from_rgb(0xFFFF00, 255),
             },
         ))
     }
@@ -66,6 +93,83 @@ impl<'gc> Text<'gc> {
         self.0.write(gc_context).render_settings = settings;
         self.invalidate_cached_bitmap(gc_context);
     }
+
+    /// The matrix that the characters of this text are positioned with.
+    pub fn text_transform(self) -> Matrix {
+        self.0.read().static_data.text_transform
+    }
+
+    /// The characters of this text, in the order that they are defined.
+    ///
+    /// Glyphs of fonts without a code table are read as the replacement character.
+    pub fn chars(self, library: &Library<'gc>) -> Vec<StaticTextChar> {
+        let tf = self.0.read();
+        let Some(movie_library) = library.library_for_movie(self.movie()) else {
+            return Vec::new();
+        };
+
+        let mut chars = Vec::new();
+        let mut color = swf::Color::BLACK;
+        let mut font_id = 0;
+        let mut height = Twips::ZERO;
+        let mut position = Point::new(Twips::ZERO, Twips::ZERO);
+        for block in &tf.static_data.text_blocks {
+            if let Some(x) = block.x_offset {
+                position.x = x;
+            }
+            if let Some(y) = block.y_offset {
+                position.y = y;
+            }
+            color = block.color.unwrap_or(color);
+            font_id = block.font_id.unwrap_or(font_id);
+            height = block.height.unwrap_or(height);
+            if let Some(font) = movie_library.get_font(font_id) {
+                for c in &block.glyphs {
+                    if font.get_glyph(c.index as usize).is_some() {
+                        chars.push(StaticTextChar {
+                            character: font
+                                .get_char_for_glyph(c.index as usize)
+                                .unwrap_or(char::REPLACEMENT_CHARACTER),
+                            font_name: font.descriptor().name().to_owned(),
+                            color,
+                            height,
+                            position,
+                            advance: Twips::new(c.advance),
+                            ascent: font.get_baseline_for_height(height),
+                            descent: font.get_descent_for_height(height),
+                        });
+                        position.x += Twips::new(c.advance);
+                    }
+                }
+            }
+        }
+        chars
+    }
+
+    pub fn is_char_selected(self, index: usize) -> bool {
+        self.0.read().selection.get(index).copied().unwrap_or(false)
+    }
+
+    /// Select or deselect the characters in `range`.
+    pub fn set_chars_selected(
+        self,
+        gc_context: &Mutation<'gc>,
+        range: std::ops::Range<usize>,
+        selected: bool,
+    ) {
+        let mut write = self.0.write(gc_context);
+        if write.selection.len() < range.end {
+            write.selection.resize(range.end, false);
+        }
+        write.selection[range].fill(selected);
+        drop(write);
+        self.invalidate_cached_bitmap(gc_context);
+    }
+
+    pub fn set_select_color(self, gc_context: &Mutation<'gc>, color: swf::Color) {
+        self.0.write(gc_context).select_color = color;
+        self.invalidate_cached_bitmap(gc_context);
+    }
 }
 
 impl<'gc> TDisplayObject<'gc> for Text<'gc> {
@@ -126,6 +230,7 @@ impl<'gc> TDisplayObject<'gc> for Text<'gc> {
         let mut font_id = 0;
         let mut height = Twips::ZERO;
         let mut transform: Transform = Default::default();
+        let mut char_index = 0;
         for block in &tf.static_data.text_blocks {
             if let Some(x) = block.x_offset {
                 transform.matrix.tx = x;
@@ -148,6 +253,20 @@ impl<'gc> TDisplayObject<'gc> for Text<'gc> {
                 transform.color_transform.set_mult_color(&color);
                 for c in &block.glyphs {
                     if let Some(glyph) = font.get_glyph(c.index as usize) {
+                        if tf.selection.get(char_index).copied().unwrap_or(false) {
+                            let ascent = font.get_baseline_for_height(height);
+                            let descent = font.get_descent_for_height(height);
+                            let selection_box = context.transform_stack.transform().matrix
+                                * Matrix::create_box(
+                                    Twips::new(c.advance).to_pixels() as f32,
+                                    (ascent + descent).to_pixels() as f32,
+                                    transform.matrix.tx,
+                                    transform.matrix.ty - ascent,
+                                );
+                            context.commands.draw_rect(tf.select_color, selection_box);
+                        }
+                        char_index += 1;
+
                         if let Some(glyph_shape_handle) = glyph.shape_handle(context.renderer) {
                             context.transform_stack.push(&transform);
                             context.commands.render_shape(
@@ -184,14 +303,16 @@ impl<'gc> TDisplayObject<'gc> for Text<'gc> {
             }
 
             // Transform the point into the text's local space.
-            let Some(local_matrix) = self.global_to_local_matrix() else {
+            let (Some(local_matrix), Some(local_point)) =
+                (self.global_to_local_matrix(), self.global_to_local(point))
+            else {
                 return false;
             };
             let tf = self.0.read();
             let Some(text_matrix) = tf.static_data.text_transform.inverse() else {
                 return false;
             };
-            point = text_matrix * local_matrix * point;
+            point = text_matrix * local_point;
 
             let mut font_id = 0;
             let mut height = Twips::ZERO;
@@ -5,6 +5,7 @@ use crate::avm2::{
     Activation as Avm2Activation, Object as Avm2Object, StageObject as Avm2StageObject,
     Value as Avm2Value,
 };
+use crate::capture::Camera;
 use crate::context::{RenderContext, UpdateContext};
 use crate::display_object::{DisplayObjectBase, DisplayObjectPtr};
 use crate::prelude::*;
@@ -101,8 +102,8 @@ pub enum VideoSource<'gc> {
     /// particular character. If you need to mutate the video source, consider
     /// reallocating a new source for your specific video instead.
     ///
-    /// This warning does not apply to `NetStream` or `Unconnected` videos,
-    /// which are never aliased.
+    /// This warning does not apply to `NetStream`, `Camera` or `Unconnected`
+    /// videos, which are never aliased.
     Swf {
         /// The video stream definition.
         #[collect(require_static)]
@@ -119,6 +120,11 @@ pub enum VideoSource<'gc> {
         /// The stream the video is downloaded from.
         stream: NetStream<'gc>,
     },
+    /// An attached camera.
+    Camera {
+        /// The camera whose latest frame is displayed.
+        camera: Camera<'gc>,
+    },
     Unconnected,
 }
 
@@ -192,6 +198,23 @@ impl<'gc> Video<'gc> {
         video.keyframes = BTreeSet::new();
     }
 
+    /// Convert this Video into a camera sourced video, or disconnect it if
+    /// `camera` is `None`.
+    ///
+    /// Existing video state related to the old video stream will be dropped.
+    pub fn attach_camera(self, context: &mut UpdateContext<'gc>, camera: Option<Camera<'gc>>) {
+        let mut video = self.0.write(context.gc_context);
+
+        let source = match camera {
+            Some(camera) => VideoSource::Camera { camera },
+            None => VideoSource::Unconnected,
+        };
+        video.source = GcCell::new(context.gc_context, source);
+        video.stream = VideoStream::Uninstantiated(0);
+        video.keyframes = BTreeSet::new();
+        video.decoded_frame = None;
+    }
+
     /// Preload frame data from an SWF.
     ///
     /// This function yields an error if this video player is not playing an
@@ -216,6 +239,7 @@ impl<'gc> Video<'gc> {
                 frames.insert(tag.frame_num.into(), (subslice.start, subslice.end));
             }
             VideoSource::NetStream { .. } => {}
+            VideoSource::Camera { .. } => {}
             VideoSource::Unconnected { .. } => {}
         }
     }
@@ -244,6 +268,7 @@ impl<'gc> Video<'gc> {
         let num_frames = match &*read.source.read() {
             VideoSource::Swf { streamdef, .. } => streamdef.num_frames as usize,
             VideoSource::NetStream { .. } => return,
+            VideoSource::Camera { .. } => return,
             VideoSource::Unconnected { .. } => return,
         };
 
@@ -332,6 +357,7 @@ impl<'gc> Video<'gc> {
                 }
             },
             VideoSource::NetStream { .. } => return,
+            VideoSource::Camera { .. } => return,
             VideoSource::Unconnected { .. } => return,
         };
 
@@ -435,6 +461,7 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
                 }
             }
             VideoSource::NetStream { .. } => return,
+            VideoSource::Camera { .. } => return,
             VideoSource::Unconnected { .. } => return,
         };
 
@@ -496,6 +523,7 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
         match &*self.0.read().source.read() {
             VideoSource::Swf { streamdef, .. } => streamdef.id,
             VideoSource::NetStream { .. } => 0,
+            VideoSource::Camera { .. } => 0,
             VideoSource::Unconnected { .. } => 0,
         }
     }
@@ -541,6 +569,9 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
                 stream.last_decoded_bitmap(),
                 None,
             ),
+            VideoSource::Camera { camera } => {
+                (false, None, read.movie.version(), camera.bitmap(), None)
+            }
             VideoSource::Unconnected { .. } => return context.transform_stack.pop(),
         };
 
@@ -2,6 +2,7 @@ use crate::drawing::Drawing;
 use crate::html::TextSpan;
 use crate::prelude::*;
 use crate::string::WStr;
+use either::Either;
 use gc_arena::{Collect, Gc, Mutation};
 use ruffle_render::backend::null::NullBitmapSource;
 use ruffle_render::backend::{RenderBackend, ShapeHandle};
@@ -40,6 +41,25 @@ fn round_to_pixel(t: Twips) -> Twips {
     Twips::from_pixels(t.to_pixels().round())
 }
 
+/// Whether a string contains characters that can't be rendered correctly
+/// without shaping, such as combining marks or characters of Arabic and
+/// Indic scripts.
+fn requires_shaping(text: &WStr) -> bool {
+    text.iter().any(|unit| {
+        matches!(unit,
+            0x0300..=0x036F // Combining Diacritical Marks
+            | 0x0590..=0x08FF // Hebrew, Arabic, Syriac, Thaana, NKo, ...
+            | 0x0900..=0x0DFF // Indic scripts, from Devanagari to Sinhala
+            | 0x0E00..=0x0FFF // Thai, Lao, Tibetan
+            | 0x1000..=0x109F // Myanmar
+            | 0x1780..=0x18AF // Khmer, Mongolian
+            | 0x200C..=0x200D // Zero-width (non-)joiner
+            | 0xFB1D..=0xFDFF // Hebrew and Arabic presentation forms
+            | 0xFE70..=0xFEFF // Arabic presentation forms
+        )
+    })
+}
+
 /// Parameters necessary to evaluate a font.
 #[derive(Copy, Clone, Debug)]
 pub struct EvalParameters {
@@ -56,6 +76,12 @@ pub struct EvalParameters {
     /// pairs of letters, separate from the ordinary width between glyphs. This
     /// parameter allows enabling or disabling that feature.
     kerning: bool,
+
+    /// Whether the text runs from right to left.
+    ///
+    /// Glyphs are still produced from left to right, so right-to-left text
+    /// is evaluated starting from its last character.
+    rtl: bool,
 }
 
 impl EvalParameters {
@@ -66,6 +92,7 @@ impl EvalParameters {
             height,
             letter_spacing,
             kerning,
+            rtl: false,
         }
     }
 
@@ -76,13 +103,25 @@ impl EvalParameters {
             height: Twips::from_pixels(span.font.size),
             letter_spacing: Twips::from_pixels(span.font.letter_spacing),
             kerning: span.font.kerning,
+            rtl: false,
         }
     }
 
+    /// Set the direction of the text to evaluate.
+    pub fn with_rtl(mut self, rtl: bool) -> Self {
+        self.rtl = rtl;
+        self
+    }
+
     /// Get the height that the font would be evaluated at.
     pub fn height(&self) -> Twips {
         self.height
     }
+
+    /// Whether the text is evaluated from right to left.
+    pub fn is_rtl(&self) -> bool {
+        self.rtl
+    }
 }
 
 struct GlyphToDrawing<'a>(&'a mut Drawing);
@@ -188,41 +227,94 @@ impl FontFace {
     pub fn get_glyph(&self, character: char) -> Option<&Glyph> {
         let face = ttf_parser::Face::parse(&self.bytes, self.font_index)
             .expect("Font was already checked to be valid");
-        if let Some(glyph_id) = face.glyph_index(character) {
-            return self.glyphs[glyph_id.0 as usize]
-                .get_or_init(|| {
-                    let mut drawing = Drawing::new();
-                    // TTF uses NonZero
-                    drawing.new_fill(
-                        Some(FillStyle::Color(Color::WHITE)),
-                        Some(FillRule::NonZero),
-                    );
-                    if face
-                        .outline_glyph(glyph_id, &mut GlyphToDrawing(&mut drawing))
-                        .is_some()
-                    {
-                        let advance = face.glyph_hor_advance(glyph_id).map_or_else(
-                            || drawing.self_bounds().width(),
-                            |a| Twips::new(a as i32),
-                        );
-                        Some(Glyph {
-                            shape_handle: Default::default(),
-                            shape: GlyphShape::Drawing(drawing),
-                            advance,
-                        })
-                    } else {
-                        let advance = Twips::new(face.glyph_hor_advance(glyph_id)? as i32);
-                        // If we have advance, then this is either an image, SVG or simply missing (ie whitespace)
-                        Some(Glyph {
-                            shape_handle: Default::default(),
-                            shape: GlyphShape::None,
-                            advance,
-                        })
-                    }
-                })
-                .as_ref();
+        self.get_glyph_by_id(&face, face.glyph_index(character)?)
+    }
+
+    fn get_glyph_by_id(
+        &self,
+        face: &ttf_parser::Face<'_>,
+        glyph_id: ttf_parser::GlyphId,
+    ) -> Option<&Glyph> {
+        self.glyphs
+            .get(glyph_id.0 as usize)?
+            .get_or_init(|| {
+                let mut drawing = Drawing::new();
+                // TTF uses NonZero
+                drawing.new_fill(
+                    Some(FillStyle::Color(Color::WHITE)),
+                    Some(FillRule::NonZero),
+                );
+                if face
+                    .outline_glyph(glyph_id, &mut GlyphToDrawing(&mut drawing))
+                    .is_some()
+                {
+                    let advance = face
+                        .glyph_hor_advance(glyph_id)
+                        .map_or_else(|| drawing.self_bounds().width(), |a| Twips::new(a as i32));
+                    Some(Glyph {
+                        shape_handle: Default::default(),
+                        shape: GlyphShape::Drawing(drawing),
+                        advance,
+                    })
+                } else {
+                    let advance = Twips::new(face.glyph_hor_advance(glyph_id)? as i32);
+                    // If we have advance, then this is either an image, SVG or simply missing (ie whitespace)
+                    Some(Glyph {
+                        shape_handle: Default::default(),
+                        shape: GlyphShape::None,
+                        advance,
+                    })
+                }
+            })
+            .as_ref()
+    }
+
+    /// Shape a run of text using the OpenType layout tables of this font.
+    ///
+    /// This applies ligatures, contextual forms and mark positioning, which
+    /// are required to render scripts such as Arabic or Devanagari.
+    /// The resulting glyphs are in visual order, from left to right.
+    fn shape(&self, text: &WStr, rtl: bool, kerning: bool) -> Vec<ShapedGlyph<'_>> {
+        let face = ttf_parser::Face::parse(&self.bytes, self.font_index)
+            .expect("Font was already checked to be valid");
+
+        let mut buffer = rustybuzz::UnicodeBuffer::new();
+        for (pos, c) in text.char_indices() {
+            buffer.add(c.unwrap_or(char::REPLACEMENT_CHARACTER), pos as u32);
         }
-        None
+        buffer.set_direction(if rtl {
+            rustybuzz::Direction::RightToLeft
+        } else {
+            rustybuzz::Direction::LeftToRight
+        });
+        buffer.guess_segment_properties();
+
+        let features = if kerning {
+            vec![]
+        } else {
+            vec![rustybuzz::Feature::new(
+                ttf_parser::Tag::from_bytes(b"kern"),
+                0,
+                ..,
+            )]
+        };
+        let output = rustybuzz::shape(&rustybuzz::Face::from_face(face.clone()), &features, buffer);
+
+        output
+            .glyph_infos()
+            .iter()
+            .zip(output.glyph_positions())
+            .filter_map(|(info, position)| {
+                let glyph_id = ttf_parser::GlyphId(info.glyph_id as u16);
+                Some(ShapedGlyph {
+                    glyph: self.get_glyph_by_id(&face, glyph_id)?,
+                    cluster: info.cluster as usize,
+                    x_advance: Twips::new(position.x_advance),
+                    x_offset: Twips::new(position.x_offset),
+                    y_offset: Twips::new(position.y_offset),
+                })
+            })
+            .collect()
     }
 
     pub fn has_kerning_info(&self) -> bool {
@@ -251,6 +343,21 @@ impl FontFace {
     }
 }
 
+/// A glyph positioned by [`FontFace::shape`].
+///
+/// All metrics are in font units.
+struct ShapedGlyph<'a> {
+    glyph: &'a Glyph,
+
+    /// The position of the first character in the text that this glyph
+    /// represents. Ligatures represent several characters.
+    cluster: usize,
+
+    x_advance: Twips,
+    x_offset: Twips,
+    y_offset: Twips,
+}
+
 #[derive(Debug)]
 pub enum GlyphSource {
     Memory {
@@ -262,6 +369,10 @@ pub enum GlyphSource {
         /// Used by `DefineEditText` tags.
         code_point_to_glyph: fnv::FnvHashMap<u16, usize>,
 
+        /// The Unicode code point of each glyph in the `glyphs` array.
+        /// Used to read back the text of `DefineText` tags.
+        glyph_code_points: Vec<u16>,
+
         /// Kerning information.
         /// Maps from a pair of unicode code points to horizontal offset value.
         kerning_pairs: fnv::FnvHashMap<(u16, u16), Twips>,
@@ -279,6 +390,18 @@ impl GlyphSource {
         }
     }
 
+    pub fn get_code_point_by_index(&self, index: usize) -> Option<char> {
+        match self {
+            GlyphSource::Memory {
+                glyph_code_points, ..
+            } => glyph_code_points
+                .get(index)
+                .and_then(|code| char::from_u32((*code).into())),
+            GlyphSource::FontFace(_) => None, // Unsupported.
+            GlyphSource::Empty => None,
+        }
+    }
+
     pub fn get_by_code_point(&self, code_point: char) -> Option<&Glyph> {
         match self {
             GlyphSource::Memory {
@@ -396,6 +519,7 @@ impl<'gc> Font<'gc> {
         font_type: FontType,
     ) -> Font<'gc> {
         let mut code_point_to_glyph = fnv::FnvHashMap::default();
+        let mut glyph_code_points = Vec::with_capacity(tag.glyphs.len());
 
         let descriptor = FontDescriptor::from_swf_tag(&tag, encoding);
         let (ascent, descent, leading) = if let Some(layout) = &tag.layout {
@@ -411,6 +535,7 @@ impl<'gc> Font<'gc> {
             .map(|(index, swf_glyph)| {
                 let code = swf_glyph.code;
                 code_point_to_glyph.insert(code, index);
+                glyph_code_points.push(code);
 
                 let glyph = Glyph {
                     shape_handle: None.into(),
@@ -446,6 +571,7 @@ impl<'gc> Font<'gc> {
                     GlyphSource::Memory {
                         glyphs,
                         code_point_to_glyph,
+                        glyph_code_points,
                         kerning_pairs,
                     }
                 },
@@ -506,6 +632,12 @@ impl<'gc> Font<'gc> {
         self.0.glyphs.get_by_index(i)
     }
 
+    /// Returns the character of a glyph entry by index.
+    /// Used to read the text of `Text` display objects.
+    pub fn get_char_for_glyph(&self, i: usize) -> Option<char> {
+        self.0.glyphs.get_code_point_by_index(i)
+    }
+
     /// Returns a glyph entry by character.
     /// Used by `EditText` display objects.
     pub fn get_glyph_for_char(&self, c: char) -> Option<&Glyph> {
@@ -583,31 +715,60 @@ impl<'gc> Font<'gc> {
 
         transform.matrix.a = scale;
         transform.matrix.d = scale;
-        let mut char_indices = text.char_indices().peekable();
+
+        if let GlyphSource::FontFace(face) = &self.0.glyphs {
+            if params.rtl || requires_shaping(text) {
+                let mut x = Twips::ZERO;
+                for shaped in face.shape(text, params.rtl, params.kerning) {
+                    // Combining marks have no advance, and letter spacing
+                    // would detach them from their base glyph.
+                    let twips_advance = if shaped.x_advance == Twips::ZERO {
+                        Twips::ZERO
+                    } else {
+                        self.scale_advance(shaped.x_advance, scale, params)
+                    };
+
+                    let mut glyph_transform = transform.clone();
+                    glyph_transform.matrix.tx +=
+                        Twips::new((shaped.x_offset.get() as f32 * scale) as i32);
+                    glyph_transform.matrix.ty -=
+                        Twips::new((shaped.y_offset.get() as f32 * scale) as i32);
+                    glyph_func(
+                        shaped.cluster,
+                        &glyph_transform,
+                        shaped.glyph,
+                        twips_advance,
+                        x,
+                    );
+
+                    // Step horizontally.
+                    transform.matrix.tx += twips_advance;
+                    x += twips_advance;
+                }
+                return;
+            }
+        }
+
+        let chars = text
+            .char_indices()
+            .map(|(pos, c)| (pos, c.unwrap_or(char::REPLACEMENT_CHARACTER)));
+        // Right-to-left text is laid out starting from its last character.
+        let mut chars = if params.rtl {
+            Either::Left(chars.collect::<Vec<_>>().into_iter().rev())
+        } else {
+            Either::Right(chars)
+        }
+        .peekable();
         let has_kerning_info = self.has_kerning_info();
         let mut x = Twips::ZERO;
-        while let Some((pos, c)) = char_indices.next() {
-            let c = c.unwrap_or(char::REPLACEMENT_CHARACTER);
+        while let Some((pos, c)) = chars.next() {
             if let Some(glyph) = self.get_glyph_for_char(c) {
                 let mut advance = glyph.advance();
                 if has_kerning_info && params.kerning {
-                    let next_char = char_indices.peek().cloned().unwrap_or((0, Ok('\0'))).1;
-                    let next_char = next_char.unwrap_or(char::REPLACEMENT_CHARACTER);
+                    let next_char = chars.peek().map_or('\0', |(_, c)| *c);
                     advance += self.get_kerning_offset(c, next_char);
                 }
-                let twips_advance = if self.font_type() == FontType::Device {
-                    let unspaced_advance =
-                        round_to_pixel(Twips::new((advance.get() as f32 * scale) as i32));
-                    let spaced_advance =
-                        unspaced_advance + params.letter_spacing.round_to_pixel_ties_even();
-                    if spaced_advance > Twips::ZERO {
-                        spaced_advance
-                    } else {
-                        unspaced_advance
-                    }
-                } else {
-                    Twips::new((advance.get() as f32 * scale) as i32) + params.letter_spacing
-                };
+                let twips_advance = self.scale_advance(advance, scale, params);
 
                 glyph_func(pos, &transform, glyph, twips_advance, x);
 
@@ -618,6 +779,24 @@ impl<'gc> Font<'gc> {
         }
     }
 
+    /// Scale a glyph advance from font units to the evaluated height,
+    /// applying letter spacing.
+    fn scale_advance(&self, advance: Twips, scale: f32, params: EvalParameters) -> Twips {
+        if self.font_type() == FontType::Device {
+            let unspaced_advance =
+                round_to_pixel(Twips::new((advance.get() as f32 * scale) as i32));
+            let spaced_advance =
+                unspaced_advance + params.letter_spacing.round_to_pixel_ties_even();
+            if spaced_advance > Twips::ZERO {
+                spaced_advance
+            } else {
+                unspaced_advance
+            }
+        } else {
+            Twips::new((advance.get() as f32 * scale) as i32) + params.letter_spacing
+        }
+    }
+
     /// Measure a particular string's metrics (width and height).
     ///
     /// The `round` flag causes the returned coordinates to be rounded down to
@@ -0,0 +1,65 @@
+//! Bidirectional text utilities
+//!
+//! Embedding levels are resolved with the Unicode Bidirectional Algorithm
+//! ([UAX #9](https://unicode.org/reports/tr9/)). An even level is
+//! left-to-right, an odd level is right-to-left.
+
+use crate::string::{Units, WStr};
+use unicode_bidi::utf16::BidiInfo;
+
+/// Returns whether text at the given embedding level runs from right to left.
+pub fn is_rtl(level: u8) -> bool {
+    level % 2 == 1
+}
+
+/// Resolve the embedding level of every code unit of `text`.
+///
+/// The base direction of every paragraph is determined by its first strong
+/// character. This returns `None` when all of the text is left-to-right,
+/// so that the common case doesn't need any reordering.
+pub fn resolve_levels(text: &WStr) -> Option<Vec<u8>> {
+    let units = match text.units() {
+        // LATIN1 strings don't contain any right-to-left characters.
+        Units::Bytes(_) => return None,
+        Units::Wide(units) => units,
+    };
+
+    let info = BidiInfo::new(units, None);
+    if info.levels.iter().all(|level| level.number() == 0) {
+        return None;
+    }
+
+    Some(info.levels.iter().map(|level| level.number()).collect())
+}
+
+/// Compute the visual order of a line of runs from their embedding levels,
+/// following rule L2 of the Unicode Bidirectional Algorithm.
+///
+/// The returned vector holds indices into `levels`, from left to right.
+pub fn visual_order(levels: &[u8]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..levels.len()).collect();
+    let (Some(&max_level), Some(&min_level)) = (levels.iter().max(), levels.iter().min()) else {
+        return order;
+    };
+    let lowest_odd_level = min_level | 1;
+
+    // From the highest level down to the lowest odd level, reverse every
+    // contiguous sequence of runs at that level or higher.
+    for level in (lowest_odd_level..=max_level).rev() {
+        let mut index = 0;
+        while index < order.len() {
+            if levels[order[index]] < level {
+                index += 1;
+                continue;
+            }
+
+            let start = index;
+            while index < order.len() && levels[order[index]] >= level {
+                index += 1;
+            }
+            order[start..index].reverse();
+        }
+    }
+
+    order
+}
@@ -3,6 +3,7 @@
 use crate::context::UpdateContext;
 use crate::drawing::Drawing;
 use crate::font::{EvalParameters, Font, FontType};
+use crate::html::bidi;
 use crate::html::dimensions::{BoxBounds, Position, Size};
 use crate::html::text_format::{FormatSpans, TextFormat, TextSpan};
 use crate::string::{utils as string_utils, WStr};
@@ -66,6 +67,11 @@ pub struct LayoutContext<'a, 'gc> {
     /// The underlying bundle of text being formatted.
     text: &'a WStr,
 
+    /// The bidi embedding level of every code unit of the text.
+    ///
+    /// `None` indicates that all of the text is left-to-right.
+    levels: Option<Vec<u8>>,
+
     /// The highest font size observed within the current line.
     max_font_size: Twips,
 
@@ -119,6 +125,7 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
             cursor: Default::default(),
             font: None,
             text,
+            levels: bidi::resolve_levels(text),
             max_font_size: Default::default(),
             lines: Vec::new(),
             current_line_index: 0,
@@ -171,7 +178,12 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
         let mut line_drawing = Drawing::new();
         let mut has_underline: bool = false;
 
-        for linebox in self.boxes.iter() {
+        // Bidirectional text may have been reordered, so go through the boxes
+        // from left to right instead of in text order.
+        let mut boxes: Vec<_> = self.boxes.iter().collect();
+        boxes.sort_by_key(|linebox| linebox.bounds().offset_x());
+
+        for linebox in boxes {
             if linebox.is_text_box() {
                 if let Some((_t, tf, font, params, color)) = linebox.as_renderable_text(self.text) {
                     let underline_baseline =
@@ -261,6 +273,8 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
         span: &TextSpan,
         font_type: FontType,
     ) {
+        self.reorder_line();
+
         let mut line_bounds = None;
         let mut box_count: i32 = 0;
         for linebox in self.boxes.iter_mut() {
@@ -354,6 +368,34 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
         }
     }
 
+    /// Move the boxes of the current line from their logical order into
+    /// their visual order, according to their bidi levels.
+    ///
+    /// Only the positions of the boxes change, they are kept in text order.
+    fn reorder_line(&mut self) {
+        if self.boxes.iter().all(|linebox| linebox.bidi_level() == 0) {
+            return;
+        }
+
+        // Each box takes the space up to the next one along with it, which
+        // keeps the gaps left by tabs.
+        let widths: Vec<Twips> = (0..self.boxes.len())
+            .map(|index| match self.boxes.get(index + 1) {
+                Some(next) => next.bounds.offset_x() - self.boxes[index].bounds.offset_x(),
+                None => self.boxes[index].bounds.width(),
+            })
+            .collect();
+        let levels: Vec<u8> = self.boxes.iter().map(LayoutBox::bidi_level).collect();
+
+        let mut x = self.boxes[0].bounds.offset_x();
+        for index in bidi::visual_order(&levels) {
+            let linebox = &mut self.boxes[index];
+            let shift = x - linebox.bounds.offset_x();
+            linebox.bounds += Position::from((shift, Twips::ZERO));
+            x += widths[index];
+        }
+    }
+
     fn flush_line(&mut self, end: usize) {
         if self.boxes.is_empty() {
             return;
@@ -609,12 +651,58 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
     ///
     /// This function bypasses the text fragmentation necessary for justify to
     /// work, and it should only be called internally.
+    ///
+    /// Bidirectional text is further split into runs of a single direction.
     fn append_text_fragment(&mut self, text: &'a WStr, start: usize, end: usize, span: &TextSpan) {
+        let Some(levels) = self
+            .levels
+            .as_ref()
+            .and_then(|levels| levels.get(start..end))
+        else {
+            self.append_text_run(text, start, end, span, 0);
+            return;
+        };
+
+        let mut runs = Vec::new();
+        let mut run_start = 0;
+        while run_start < levels.len() {
+            let level = levels[run_start];
+            let run_end = levels[run_start..]
+                .iter()
+                .position(|l| *l != level)
+                .map_or(levels.len(), |length| run_start + length);
+            runs.push((run_start, run_end, level));
+            run_start = run_end;
+        }
+
+        if runs.is_empty() {
+            self.append_text_run(text, start, end, span, 0);
+        }
+        for (run_start, run_end, level) in runs {
+            self.append_text_run(
+                &text[run_start..run_end],
+                start + run_start,
+                start + run_end,
+                span,
+                level,
+            );
+        }
+    }
+
+    /// Append a run of text with a single bidi level to the current line.
+    fn append_text_run(
+        &mut self,
+        text: &'a WStr,
+        start: usize,
+        end: usize,
+        span: &TextSpan,
+        bidi_level: u8,
+    ) {
         if let Some(font) = self.font {
-            let params = EvalParameters::from_span(span);
+            let params = EvalParameters::from_span(span).with_rtl(bidi::is_rtl(bidi_level));
             let text_size = Size::from(font.measure(text, params, false));
             let text_bounds = BoxBounds::from_position_and_size(self.cursor, text_size);
-            let mut new_text = LayoutBox::from_text(start, end, font, span);
+            let mut new_text = LayoutBox::from_text(start, end, font, span, bidi_level);
 
             new_text.bounds = text_bounds;
 
@@ -777,6 +865,15 @@ impl<'gc> Layout<'gc> {
         }
     }
 
+    /// Whether the text at the given position runs from right to left.
+    pub fn is_rtl_at(&self, position: usize) -> bool {
+        self.boxes_iter()
+            .find(|linebox| {
+                linebox.is_text_box() && linebox.start() <= position && position < linebox.end()
+            })
+            .is_some_and(|linebox| bidi::is_rtl(linebox.bidi_level()))
+    }
+
     pub fn find_line_index_by_position(&self, position: usize) -> Option<usize> {
         let result = self.lines.binary_search_by(|probe| {
             if probe.end <= position {
@@ -903,6 +1000,11 @@ pub enum LayoutContent<'gc> {
         /// The color to render the font with.
         #[collect(require_static)]
         color: swf::Color,
+
+        /// The bidi embedding level of the text.
+        ///
+        /// Odd levels are right-to-left.
+        bidi_level: u8,
     },
 
     /// A layout box containing a bullet.
@@ -966,8 +1068,14 @@ impl<'gc> Debug for LayoutContent<'gc> {
 
 impl<'gc> LayoutBox<'gc> {
     /// Construct a text box for a text node.
-    pub fn from_text(start: usize, end: usize, font: Font<'gc>, span: &TextSpan) -> Self {
-        let params = EvalParameters::from_span(span);
+    pub fn from_text(
+        start: usize,
+        end: usize,
+        font: Font<'gc>,
+        span: &TextSpan,
+        bidi_level: u8,
+    ) -> Self {
+        let params = EvalParameters::from_span(span).with_rtl(bidi::is_rtl(bidi_level));
 
         Self {
             bounds: Default::default(),
@@ -978,6 +1086,7 @@ impl<'gc> LayoutBox<'gc> {
                 font,
                 params,
                 color: span.font.color,
+                bidi_level,
             },
         }
     }
@@ -1166,6 +1275,7 @@ impl<'gc> LayoutBox<'gc> {
                 font,
                 params,
                 color,
+                ..
             } => Some((
                 text.slice(*start..*end)?,
                 text_format,
@@ -1207,6 +1317,14 @@ impl<'gc> LayoutBox<'gc> {
         matches!(&self.content, LayoutContent::Bullet { .. })
     }
 
+    /// The bidi embedding level of this box, which is 0 for anything but text.
+    pub fn bidi_level(&self) -> u8 {
+        match &self.content {
+            LayoutContent::Text { bidi_level, .. } => *bidi_level,
+            _ => 0,
+        }
+    }
+
     pub fn start(&self) -> usize {
         match &self.content {
             LayoutContent::Text { start, .. } => *start,
@@ -1,5 +1,6 @@
 //! Tests for HTML module
 
+use crate::html::bidi;
 use crate::html::dimensions::{BoxBounds, Position, Size};
 use crate::html::text_format::{FormatSpans, TextFormat, TextSpan};
 use crate::string::{WStr, WString};
@@ -868,3 +869,90 @@ fn formatspans_replace_text_degenerate() {
     assert_eq!((0, 1), fs.get_span_boundaries(0, 5));
     assert_eq!((1, 2), fs.get_span_boundaries(5, 9));
 }
+
+#[test]
+fn bidi_levels_ltr() {
+    assert_eq!(None, bidi::resolve_levels(WStr::from_units(b"hello world")));
+    assert_eq!(
+        None,
+        bidi::resolve_levels(&WString::from_utf8("\u{65e5}\u{672c}\u{8a9e}"))
+    );
+}
+
+#[test]
+fn bidi_levels_mixed() {
+    // A Hebrew word inside a left-to-right paragraph.
+    let text = WString::from_utf8("abc \u{5d0}\u{5d1}\u{5d2} def");
+    assert_eq!(
+        Some(vec![0, 0, 0, 0, 1, 1, 1, 0, 0, 0, 0]),
+        bidi::resolve_levels(&text)
+    );
+
+    // A number inside a right-to-left paragraph.
+    let text = WString::from_utf8("\u{5d0} 12");
+    assert_eq!(Some(vec![1, 1, 2, 2]), bidi::resolve_levels(&text));
+}
+
+#[test]
+fn bidi_visual_order() {
+    assert_eq!(vec![0, 1, 2], bidi::visual_order(&[0, 0, 0]));
+    assert_eq!(vec![2, 1, 0], bidi::visual_order(&[1, 1, 1]));
+    assert_eq!(vec![0, 3, 2, 1, 4], bidi::visual_order(&[0, 1, 1, 1, 0]));
+    assert_eq!(vec![3, 1, 2, 0], bidi::visual_order(&[1, 2, 2, 1]));
+    assert_eq!(Vec::<usize>::new(), bidi::visual_order(&[]));
+}
+
+#[test]
+fn formatspans_slice() {
+    let bold = TextFormat {
+        bold: Some(true),
+        ..Default::default()
+    };
+    let italic = TextFormat {
+        italic: Some(true),
+        ..Default::default()
+    };
+    let fs = FormatSpans::from_str_and_spans(
+        WStr::from_units(b"abcdef"),
+        &[
+            TextSpan::with_length_and_format(3, &bold),
+            TextSpan::with_length_and_format(3, &italic),
+        ],
+    );
+
+    let slice = fs.slice(2, 5);
+    assert_eq!(slice.text(), WStr::from_units(b"cde"));
+    let spans: Vec<_> = slice
+        .iter_spans()
+        .map(|(start, end, _, span)| (start, end, span.style.bold, span.style.italic))
+        .collect();
+    assert_eq!(spans, vec![(0, 1, true, false), (1, 3, false, true)]);
+
+    let empty = fs.slice(4, 4);
+    assert_eq!(empty.text(), WStr::empty());
+}
+
+#[test]
+fn formatspans_replace_with_spans() {
+    let tf = Default::default();
+    let bold = TextFormat {
+        bold: Some(true),
+        ..Default::default()
+    };
+    let mut fs = FormatSpans::from_str_and_spans(
+        WStr::from_units(b"abcdef"),
+        &[TextSpan::with_length_and_format(6, &tf)],
+    );
+    let with = FormatSpans::from_str_and_spans(
+        WStr::from_units(b"XY"),
+        &[TextSpan::with_length_and_format(2, &bold)],
+    );
+
+    fs.replace_with_spans(2, 4, &with);
+    assert_eq!(fs.text(), WStr::from_units(b"abXYef"));
+    let spans: Vec<_> = fs
+        .iter_spans()
+        .map(|(start, end, _, span)| (start, end, span.style.bold))
+        .collect();
+    assert_eq!(spans, vec![(0, 2, false), (2, 4, true), (4, 6, false)]);
+}
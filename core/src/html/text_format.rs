@@ -1353,6 +1353,41 @@ impl FormatSpans {
         self.normalize();
     }
 
+    /// Copy the text and formatting in the range [from, to) into new format
+    /// spans.
+    pub fn slice(&self, from: usize, to: usize) -> Self {
+        let text = self.text.slice(from..to).unwrap_or_default();
+        let mut spans: Vec<TextSpan> = self
+            .iter_spans()
+            .filter_map(|(start, end, _, span)| {
+                let length = end.min(to).saturating_sub(start.max(from));
+                (length > 0).then(|| TextSpan {
+                    span_length: length,
+                    ..span.clone()
+                })
+            })
+            .collect();
+        if spans.is_empty() {
+            spans.push(TextSpan::with_length_and_format(0, &self.default_format));
+        }
+
+        Self {
+            text: text.into(),
+            displayed_text: WString::new(),
+            spans,
+            default_format: self.default_format.clone(),
+        }
+    }
+
+    /// Replace the text in the range [from, to) with the text of `with`,
+    /// keeping its formatting.
+    pub fn replace_with_spans(&mut self, from: usize, to: usize, with: &FormatSpans) {
+        self.replace_text(from, to, with.text(), None);
+        for (start, end, _, span) in with.iter_spans() {
+            self.set_text_format(from + start, from + end, &span.get_text_format());
+        }
+    }
+
     /// Iterate over all text spans in the current list of format spans.
     ///
     /// The iterator returned by this function yields a tuple for each span,
@@ -24,6 +24,7 @@ mod avm2;
 mod binary_data;
 pub mod bitmap;
 pub mod buffer;
+mod capture;
 mod character;
 pub mod context;
 pub mod context_menu;
@@ -42,11 +43,14 @@ mod locale;
 mod net_connection;
 pub mod pixel_bender;
 mod player;
+mod policy_file;
 mod prelude;
+mod printing;
 pub mod socket;
 mod streams;
 pub mod string;
 pub mod tag_utils;
+mod text_snapshot;
 pub mod timer;
 mod types;
 mod vminterface;
@@ -5,16 +5,19 @@ use crate::avm1::{Attribute, Avm1};
 use crate::avm1::{ExecutionReason, NativeObject};
 use crate::avm1::{Object, SoundObject, TObject, Value};
 use crate::avm2::bytearray::ByteArrayStorage;
+use crate::avm2::globals::flash::net::url_stream;
 use crate::avm2::object::{
-    ByteArrayObject, EventObject as Avm2EventObject, FileReferenceObject, LoaderStream,
-    TObject as _,
+    ArrayObject, ByteArrayObject, EventObject as Avm2EventObject, FileReference,
+    FileReferenceObject, LoaderStream, TObject as _,
 };
 use crate::avm2::{
-    Activation as Avm2Activation, Avm2, BitmapDataObject, Domain as Avm2Domain,
-    Object as Avm2Object, Value as Avm2Value,
+    Activation as Avm2Activation, ArrayStorage, Avm2, BitmapDataObject, Domain as Avm2Domain,
+    Multiname, Object as Avm2Object, Value as Avm2Value,
 };
 use crate::backend::navigator::{ErrorResponse, OwnedFuture, Request, SuccessResponse};
-use crate::backend::ui::DialogResultFuture;
+use crate::backend::ui::{
+    DialogLoaderError, DialogResultFuture, FileDialogResult, MultipleDialogResultFuture,
+};
 use crate::bitmap::bitmap_data::Color;
 use crate::bitmap::bitmap_data::{BitmapData, BitmapDataWrapper};
 use crate::context::{ActionQueue, ActionType, UpdateContext};
@@ -25,6 +28,7 @@ use crate::events::ClipEvent;
 use crate::frame_lifecycle::catchup_display_object_to_frame;
 use crate::limits::ExecutionLimit;
 use crate::player::{Player, PostFrameCallback};
+use crate::policy_file::PolicyFiles;
 use crate::streams::NetStream;
 use crate::string::AvmString;
 use crate::tag_utils::SwfMovie;
@@ -41,7 +45,7 @@ use std::fmt;
 use std::str::FromStr;
 use std::sync::{Arc, Mutex, Weak};
 use std::time::Duration;
-use swf::read::{extract_swz, read_compression_type};
+use swf::read::{extract_swz, read_compression_type, SwfDecompressor};
 use thiserror::Error;
 use url::{form_urlencoded, ParseError, Url};
 
@@ -147,6 +151,109 @@ impl ContentType {
     }
 }
 
+/// The response body of a SWF that is played while it is being downloaded.
+///
+/// The body is decompressed as it arrives, and a `SwfMovie` is created as soon
+/// as the SWF header is available. Any other content is collected in full.
+struct MovieStream {
+    url: String,
+    loader_url: Option<String>,
+    parameters: Vec<(String, String)>,
+
+    /// The length of the response body, if it is known in advance.
+    expected_length: Option<usize>,
+
+    /// The number of bytes of the response body received so far.
+    loaded_length: usize,
+
+    /// The response body received before a movie could be created from it.
+    body: Vec<u8>,
+
+    /// The decompressor, unless the content turned out not to be a SWF.
+    decompressor: Option<SwfDecompressor>,
+
+    movie: Option<Arc<SwfMovie>>,
+}
+
+impl MovieStream {
+    fn new(
+        url: String,
+        loader_url: Option<String>,
+        parameters: Vec<(String, String)>,
+        expected_length: Option<u64>,
+    ) -> Self {
+        Self {
+            url,
+            loader_url,
+            parameters,
+            expected_length: expected_length.map(|length| length as usize),
+            loaded_length: 0,
+            body: Vec::new(),
+            decompressor: Some(SwfDecompressor::new()),
+            movie: None,
+        }
+    }
+
+    /// Add the next chunk of the response body.
+    ///
+    /// Returns the movie once enough of it has arrived for it to be created.
+    fn write(&mut self, chunk: &[u8]) -> Option<Arc<SwfMovie>> {
+        self.loaded_length += chunk.len();
+        if self.movie.is_none() {
+            self.body.extend_from_slice(chunk);
+        }
+
+        let decompressor = self.decompressor.as_mut()?;
+        if decompressor.write(chunk).is_err() {
+            // This isn't a SWF; it will be handled once the entire body has arrived.
+            self.decompressor = None;
+            return None;
+        }
+
+        if let Some(movie) = &self.movie {
+            movie.append_data(&decompressor.take_data());
+            if self.expected_length.is_none() {
+                movie.set_compressed_len(self.loaded_length);
+            }
+            return None;
+        }
+
+        let header = decompressor.header()?.clone();
+        let tag_stream_len = decompressor.tag_stream_len()?;
+        let mut movie = SwfMovie::from_loading_data(
+            header,
+            tag_stream_len,
+            self.url.clone(),
+            self.loader_url.clone(),
+            self.expected_length.unwrap_or(self.loaded_length),
+        );
+        movie.append_parameters(std::mem::take(&mut self.parameters));
+        movie.append_data(&decompressor.take_data());
+
+        let movie = Arc::new(movie);
+        self.movie = Some(movie.clone());
+        self.body = Vec::new();
+        Some(movie)
+    }
+
+    /// Signal the end of the response body.
+    ///
+    /// If no movie was created while the body was arriving, the entire body
+    /// is returned instead, to be handled like any other loaded content.
+    fn finish(self) -> Option<Vec<u8>> {
+        let (Some(movie), Some(mut decompressor)) = (self.movie, self.decompressor) else {
+            return Some(self.body);
+        };
+
+        if let Err(e) = decompressor.finish() {
+            tracing::error!("Error decompressing SWF: {}", e);
+        }
+        movie.append_data(&decompressor.take_data());
+        movie.finish_loading(self.loaded_length);
+        None
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum Error {
     #[error("Load cancelled")]
@@ -173,6 +280,9 @@ pub enum Error {
     #[error("Non-NetStream loader spawned as NetStream loader")]
     NotNetStreamLoader,
 
+    #[error("Non-URLStream loader spawned as URLStream loader")]
+    NotURLStreamLoader,
+
     #[error("Other Loader spawned as Movie unloader")]
     NotMovieUnloader,
 
@@ -273,11 +383,15 @@ impl<'gc> LoadManager<'gc> {
             | Loader::SoundAvm1 { self_handle, .. }
             | Loader::SoundAvm2 { self_handle, .. }
             | Loader::NetStream { self_handle, .. }
+            | Loader::URLStream { self_handle, .. }
             | Loader::FileDialog { self_handle, .. }
             | Loader::FileDialogAvm2 { self_handle, .. }
             | Loader::SaveFileDialog { self_handle, .. }
             | Loader::DownloadFileDialog { self_handle, .. }
             | Loader::UploadFile { self_handle, .. }
+            | Loader::DownloadFileDialogAvm2 { self_handle, .. }
+            | Loader::UploadFileAvm2 { self_handle, .. }
+            | Loader::FileListDialogAvm2 { self_handle, .. }
             | Loader::StyleSheet { self_handle, .. }
             | Loader::MovieUnloader { self_handle, .. } => *self_handle = Some(handle),
         }
@@ -575,6 +689,37 @@ impl<'gc> LoadManager<'gc> {
         loader.stream_loader(player, request)
     }
 
+    /// Kick off a download into a `URLStream`, appending data to its buffer
+    /// as it arrives.
+    ///
+    /// Returns the loader's async process, which you will need to spawn.
+    pub fn load_url_stream(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        target_object: Avm2Object<'gc>,
+        request: Request,
+    ) -> OwnedFuture<(), Error> {
+        self.cancel_url_stream(target_object);
+
+        let loader = Loader::URLStream {
+            self_handle: None,
+            target_object,
+        };
+        let handle = self.add_loader(loader);
+        let loader = self.get_loader_mut(handle).unwrap();
+        loader.url_stream_loader(player, request)
+    }
+
+    /// Stop the download into a `URLStream`, if there is one.
+    ///
+    /// The download's async process will end when it next receives data.
+    pub fn cancel_url_stream(&mut self, target: Avm2Object<'gc>) {
+        self.0.retain(|_, loader| match loader {
+            Loader::URLStream { target_object, .. } => !Avm2Object::ptr_eq(*target_object, target),
+            _ => true,
+        });
+    }
+
     /// Process tags on all loaders in the Parsing phase.
     ///
     /// Returns true if *all* loaders finished preloading.
@@ -680,9 +825,8 @@ impl<'gc> LoadManager<'gc> {
         &mut self,
         player: Weak<Mutex<Player>>,
         target_object: Object<'gc>,
-        url: String,
-        data: Vec<u8>,
-        file_name: String,
+        request: Request,
+        file_size: u64,
     ) -> OwnedFuture<(), Error> {
         let loader = Loader::UploadFile {
             self_handle: None,
@@ -690,7 +834,68 @@ impl<'gc> LoadManager<'gc> {
         };
         let handle = self.add_loader(loader);
         let loader = self.get_loader_mut(handle).unwrap();
-        loader.file_upload_loader(player, url, data, file_name)
+        loader.file_upload_loader(player, request, file_size)
+    }
+
+    /// Display a dialog allowing a user to download a file from an AVM2 scope
+    ///
+    /// Returns a future that will be resolved when a file is selected and the download has completed
+    #[must_use]
+    pub fn download_file_dialog_avm2(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        target_object: FileReferenceObject<'gc>,
+        dialog: DialogResultFuture,
+        request: Request,
+    ) -> OwnedFuture<(), Error> {
+        let loader = Loader::DownloadFileDialogAvm2 {
+            self_handle: None,
+            target_object,
+        };
+        let handle = self.add_loader(loader);
+        target_object.set_loader(Some(handle));
+        let loader = self.get_loader_mut(handle).unwrap();
+        loader.file_download_dialog_loader_avm2(player, dialog, request)
+    }
+
+    /// Upload a file from an AVM2 scope
+    ///
+    /// Returns a future that will be resolved when the file upload has completed
+    #[must_use]
+    pub fn upload_file_avm2(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        target_object: FileReferenceObject<'gc>,
+        request: Request,
+        file_size: u64,
+    ) -> OwnedFuture<(), Error> {
+        let loader = Loader::UploadFileAvm2 {
+            self_handle: None,
+            target_object,
+        };
+        let handle = self.add_loader(loader);
+        target_object.set_loader(Some(handle));
+        let loader = self.get_loader_mut(handle).unwrap();
+        loader.file_upload_loader_avm2(player, request, file_size)
+    }
+
+    /// Display a dialog allowing a user to select multiple files from an AVM2 scope
+    ///
+    /// Returns a future that will be resolved when the files are selected
+    #[must_use]
+    pub fn select_multiple_files_dialog_avm2(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        target_object: Avm2Object<'gc>,
+        dialog: MultipleDialogResultFuture,
+    ) -> OwnedFuture<(), Error> {
+        let loader = Loader::FileListDialogAvm2 {
+            self_handle: None,
+            target_object,
+        };
+        let handle = self.add_loader(loader);
+        let loader = self.get_loader_mut(handle).unwrap();
+        loader.file_list_dialog_loader_avm2(player, dialog)
     }
 }
 
@@ -836,6 +1041,16 @@ pub enum Loader<'gc> {
         target_stream: NetStream<'gc>,
     },
 
+    /// Loader that is streaming data into a `URLStream`.
+    URLStream {
+        /// The handle to refer to this loader instance.
+        #[collect(require_static)]
+        self_handle: Option<LoaderHandle>,
+
+        /// The target `URLStream` to append data to.
+        target_object: Avm2Object<'gc>,
+    },
+
     /// Loader that is unloading a MovieClip.
     MovieUnloader {
         /// The handle to refer to this loader instance.
@@ -896,6 +1111,36 @@ pub enum Loader<'gc> {
         target_object: Object<'gc>,
     },
 
+    /// Loader that is downloading a file from an AVM2 scope.
+    DownloadFileDialogAvm2 {
+        /// The handle to refer to this loader instance.
+        #[collect(require_static)]
+        self_handle: Option<LoaderHandle>,
+
+        /// The target AVM2 object to save the downloaded file for.
+        target_object: FileReferenceObject<'gc>,
+    },
+
+    /// Loader that is uploading a file from an AVM2 scope.
+    UploadFileAvm2 {
+        /// The handle to refer to this loader instance.
+        #[collect(require_static)]
+        self_handle: Option<LoaderHandle>,
+
+        /// The target AVM2 object whose file is uploaded.
+        target_object: FileReferenceObject<'gc>,
+    },
+
+    /// Loader that is choosing multiple files from an AVM2 scope.
+    FileListDialogAvm2 {
+        /// The handle to refer to this loader instance.
+        #[collect(require_static)]
+        self_handle: Option<LoaderHandle>,
+
+        /// The target `FileReferenceList` to set the selected files of.
+        target_object: Avm2Object<'gc>,
+    },
+
     /// Loader that is downloading a stylesheet
     StyleSheet {
         /// The handle to refer to this loader instance.
@@ -987,6 +1232,26 @@ impl<'gc> Loader<'gc> {
         }
     }
 
+    /// Waits for a file dialog to be closed, after which new dialogs may be opened.
+    async fn wait_for_file_dialog(
+        player: &Mutex<Player>,
+        dialog: DialogResultFuture,
+    ) -> Result<Box<dyn FileDialogResult>, DialogLoaderError> {
+        let dialog_result = dialog.await;
+        player.lock().unwrap().ui_mut().close_file_dialog();
+        dialog_result
+    }
+
+    /// Sends the request of a file download or upload, and waits for the whole response.
+    async fn transfer_file(
+        player: &Mutex<Player>,
+        request: Request,
+    ) -> Result<(Vec<u8>, String, u16, bool), ErrorResponse> {
+        // Doing this in two steps to prevent holding the player lock during fetch
+        let future = player.lock().unwrap().navigator().fetch(request);
+        Self::wait_for_full_response(future).await
+    }
+
     /// Construct a future for the root movie loader.
     fn root_movie_loader(
         &mut self,
@@ -1008,7 +1273,7 @@ impl<'gc> Loader<'gc> {
 
         Box::pin(async move {
             let fetch = player.lock().unwrap().navigator().fetch(request);
-            let response = fetch.await.map_err(|error| {
+            let mut response = fetch.await.map_err(|error| {
                 player
                     .lock()
                     .unwrap()
@@ -1017,13 +1282,6 @@ impl<'gc> Loader<'gc> {
                 error.error
             })?;
             let url = response.url().into_owned();
-            let body = response.body().await.inspect_err(|_error| {
-                player
-                    .lock()
-                    .unwrap()
-                    .ui()
-                    .display_root_movie_download_failed_message(true);
-            })?;
 
             // The spoofed root movie URL takes precedence over the actual URL.
             let swf_url = player
@@ -1038,6 +1296,36 @@ impl<'gc> Loader<'gc> {
                 .map(|u| u.to_string())
                 .unwrap_or(swf_url);
 
+            // The movie starts playing as soon as its header has arrived,
+            // and is then preloaded as the rest of it arrives.
+            let expected_length = response.expected_length().ok().flatten();
+            let mut stream = MovieStream::new(
+                spoofed_or_swf_url.clone(),
+                None,
+                parameters.clone(),
+                expected_length,
+            );
+            let mut on_metadata = Some(on_metadata);
+            while let Some(chunk) = response.next_chunk().await.inspect_err(|_error| {
+                player
+                    .lock()
+                    .unwrap()
+                    .ui()
+                    .display_root_movie_download_failed_message(true);
+            })? {
+                if let Some(movie) = stream.write(&chunk) {
+                    if let Some(on_metadata) = on_metadata.take() {
+                        on_metadata(movie.header());
+                    }
+                    player.lock().unwrap().mutate_with_update_context(|uc| {
+                        uc.set_root_movie(movie);
+                    });
+                }
+            }
+
+            let Some(body) = stream.finish() else {
+                return Ok(());
+            };
             let mut movie =
                 SwfMovie::from_data(&body, spoofed_or_swf_url, None).inspect_err(|_error| {
                     player
@@ -1046,7 +1334,9 @@ impl<'gc> Loader<'gc> {
                         .ui()
                         .display_root_movie_download_failed_message(true);
                 })?;
-            on_metadata(movie.header());
+            if let Some(on_metadata) = on_metadata {
+                on_metadata(movie.header());
+            }
             movie.append_parameters(parameters);
             player.lock().unwrap().mutate_with_update_context(|uc| {
                 uc.set_root_movie(movie);
@@ -1124,89 +1414,91 @@ impl<'gc> Loader<'gc> {
                 Loader::movie_loader_start(handle, uc)
             })?;
 
-            match Self::wait_for_full_response(fetch).await {
-                Ok((body, url, _status, _redirected)) if replacing_root_movie => {
-                    ContentType::sniff(&body).expect(ContentType::Swf)?;
+            let mut response = match fetch.await {
+                Ok(response) => response,
+                Err(response) => {
+                    return Loader::movie_loader_fetch_error(&player, handle, response)
+                }
+            };
+            let url = response.url().to_string();
+            let status = response.status();
+            let redirected = response.redirected();
+            let expected_length = response.expected_length().ok().flatten();
+
+            // A SWF is handed over to its target as soon as its header has
+            // arrived, and is then preloaded as the rest of it arrives.
+            let mut stream =
+                MovieStream::new(url.clone(), loader_url.clone(), Vec::new(), expected_length);
+            loop {
+                let chunk = match response.next_chunk().await {
+                    Ok(Some(chunk)) => chunk,
+                    Ok(None) => break,
+                    Err(error) => {
+                        let response = ErrorResponse { url, error };
+                        return Loader::movie_loader_fetch_error(&player, handle, response);
+                    }
+                };
 
-                    let movie = SwfMovie::from_data(&body, url.to_string(), loader_url)?;
+                if let Some(movie) = stream.write(&chunk) {
                     player.lock().unwrap().mutate_with_update_context(|uc| {
-                        // Make a copy of the properties on the root, so we can put them back after replacing it
-                        let mut root_properties: IndexMap<AvmString, Value> = IndexMap::new();
-                        if let Some(root) = uc.stage.root_clip() {
-                            let root_val = root.object();
-                            if let Value::Object(root_object) = root_val {
-                                let mut activation = Activation::from_nothing(
-                                    uc,
-                                    ActivationIdentifier::root("unknown"),
-                                    root,
-                                );
-                                for key in root_object.get_keys(&mut activation, true) {
-                                    let val = root_object
-                                        .get_stored(key, &mut activation)
-                                        .unwrap_or(Value::Undefined);
-                                    root_properties.insert(key, val);
-                                }
-                            }
+                        if replacing_root_movie {
+                            Loader::movie_loader_replace_root(uc, movie);
+                            Ok(())
+                        } else {
+                            Loader::movie_loader_content(
+                                handle,
+                                uc,
+                                ContentType::Swf,
+                                movie,
+                                &[],
+                                status,
+                                redirected,
+                            )
                         }
+                    })?;
+                }
+            }
 
-                        uc.replace_root_movie(movie);
+            match stream.finish() {
+                Some(body) if replacing_root_movie => {
+                    ContentType::sniff(&body).expect(ContentType::Swf)?;
 
-                        // Add the copied properties back onto the new root
-                        if !root_properties.is_empty() {
-                            if let Some(root) = uc.stage.root_clip() {
-                                let val = root.object();
-                                if let Value::Object(clip_object) = val {
-                                    let mut activation = Activation::from_nothing(
-                                        uc,
-                                        ActivationIdentifier::root("unknown"),
-                                        root,
-                                    );
-                                    for (key, val) in root_properties {
-                                        let _ = clip_object.set(key, val, &mut activation);
-                                    }
-                                }
-                            }
-                        }
+                    let movie = SwfMovie::from_data(&body, url, loader_url)?;
+                    player.lock().unwrap().mutate_with_update_context(|uc| {
+                        Loader::movie_loader_replace_root(uc, Arc::new(movie));
                     });
-                    return Ok(());
                 }
-                Ok((body, url, status, redirected)) => {
+                Some(body) => {
                     player.lock().unwrap().mutate_with_update_context(|uc| {
                         Loader::movie_loader_data(
-                            handle,
-                            uc,
-                            &body,
-                            url.to_string(),
-                            status,
-                            redirected,
-                            loader_url,
+                            handle, uc, &body, url, status, redirected, loader_url,
                         )
                     })?;
                 }
-                Err(response) => {
-                    tracing::error!(
-                        "Error during movie loading of {:?}: {:?}",
-                        response.url,
-                        response.error
-                    );
-                    player.lock().unwrap().update(|uc| -> Result<(), Error> {
-                        // FIXME - match Flash's error message
+                None if replacing_root_movie => {}
+                None => {
+                    player.lock().unwrap().mutate_with_update_context(|uc| {
+                        // The movie may have already finished preloading while its data arrived.
+                        match uc.load_manager.get_loader(handle) {
+                            Some(Loader::Movie {
+                                loader_status: LoaderStatus::Parsing,
+                                ..
+                            }) => {}
+                            _ => return Ok(()),
+                        }
 
-                        let (status_code, redirected) =
-                            if let Error::HttpNotOk(_, status_code, redirected, _) = response.error
-                            {
-                                (status_code, redirected)
-                            } else {
-                                (0, false)
-                            };
-                        Loader::movie_loader_error(
+                        // NOTE: Certain tests specifically expect small files to preload immediately
+                        Loader::preload_tick(
                             handle,
                             uc,
-                            "Movie loader error".into(),
-                            status_code,
+                            &mut ExecutionLimit::with_max_ops_and_time(
+                                10000,
+                                Duration::from_millis(1),
+                            ),
+                            status,
                             redirected,
-                            response.url,
                         )
+                        .map(|_| ())
                     })?;
                 }
             }
@@ -1215,6 +1507,72 @@ impl<'gc> Loader<'gc> {
         })
     }
 
+    /// Report a failed fetch of a movie loader to script code.
+    fn movie_loader_fetch_error(
+        player: &Arc<Mutex<Player>>,
+        handle: LoaderHandle,
+        response: ErrorResponse,
+    ) -> Result<(), Error> {
+        tracing::error!(
+            "Error during movie loading of {:?}: {:?}",
+            response.url,
+            response.error
+        );
+        player.lock().unwrap().update(|uc| -> Result<(), Error> {
+            // FIXME - match Flash's error message
+
+            let (status_code, redirected) =
+                if let Error::HttpNotOk(_, status_code, redirected, _) = response.error {
+                    (status_code, redirected)
+                } else {
+                    (0, false)
+                };
+            Loader::movie_loader_error(
+                handle,
+                uc,
+                "Movie loader error".into(),
+                status_code,
+                redirected,
+                response.url,
+            )
+        })
+    }
+
+    /// Replace the root movie with a movie loaded into the root clip.
+    fn movie_loader_replace_root(uc: &mut UpdateContext<'gc>, movie: Arc<SwfMovie>) {
+        // Make a copy of the properties on the root, so we can put them back after replacing it
+        let mut root_properties: IndexMap<AvmString, Value> = IndexMap::new();
+        if let Some(root) = uc.stage.root_clip() {
+            let root_val = root.object();
+            if let Value::Object(root_object) = root_val {
+                let mut activation =
+                    Activation::from_nothing(uc, ActivationIdentifier::root("unknown"), root);
+                for key in root_object.get_keys(&mut activation, true) {
+                    let val = root_object
+                        .get_stored(key, &mut activation)
+                        .unwrap_or(Value::Undefined);
+                    root_properties.insert(key, val);
+                }
+            }
+        }
+
+        uc.replace_root_movie(movie);
+
+        // Add the copied properties back onto the new root
+        if !root_properties.is_empty() {
+            if let Some(root) = uc.stage.root_clip() {
+                let val = root.object();
+                if let Value::Object(clip_object) = val {
+                    let mut activation =
+                        Activation::from_nothing(uc, ActivationIdentifier::root("unknown"), root);
+                    for (key, val) in root_properties {
+                        let _ = clip_object.set(key, val, &mut activation);
+                    }
+                }
+            }
+        }
+    }
+
     pub fn movie_loader_bytes(
         handle: LoaderHandle,
         uc: &mut UpdateContext<'gc>,
@@ -1527,6 +1885,50 @@ impl<'gc> Loader<'gc> {
             .expect("Could not upgrade weak reference to player");
 
         Box::pin(async move {
+            let access_check = player
+                .lock()
+                .unwrap()
+                .mutate_with_update_context(|uc| PolicyFiles::url_access_check(uc, request.url()));
+            if let Some(access_check) = access_check {
+                if !access_check.await {
+                    return player.lock().unwrap().update(|uc| {
+                        let target = match uc.load_manager.get_loader(handle) {
+                            Some(&Loader::LoadURLLoader { target_object, .. }) => target_object,
+                            None => return Err(Error::Cancelled),
+                            _ => unreachable!(),
+                        };
+
+                        let mut activation = Avm2Activation::from_nothing(uc);
+                        let message = AvmString::new_utf8(
+                            activation.gc(),
+                            format!(
+                                "Error #2048: Security sandbox violation: {} cannot load data from {}.",
+                                activation.context.swf.url(),
+                                request.url()
+                            ),
+                        );
+                        let security_error_evt = activation
+                            .avm2()
+                            .classes()
+                            .securityerrorevent
+                            .construct(
+                                &mut activation,
+                                &[
+                                    "securityError".into(),
+                                    false.into(),
+                                    false.into(),
+                                    message.into(),
+                                    2048.into(),
+                                ],
+                            )
+                            .map_err(|e| Error::Avm2Error(e.to_string()))?;
+
+                        Avm2::dispatch_event(uc, security_error_evt, target);
+                        Ok(())
+                    });
+                }
+            }
+
             let fetch = player.lock().unwrap().navigator().fetch(request);
             let response = Self::wait_for_full_response(fetch).await;
 
@@ -1957,19 +2359,183 @@ impl<'gc> Loader<'gc> {
         })
     }
 
-    /// Report a movie loader start event to script code.
-    fn movie_loader_start(handle: LoaderHandle, uc: &mut UpdateContext<'gc>) -> Result<(), Error> {
-        let me = uc.load_manager.get_loader_mut(handle);
-        if me.is_none() {
-            return Err(Error::Cancelled);
-        }
-
-        let me = me.unwrap();
-
-        let (clip, vm_data) = match me {
-            Loader::Movie {
-                target_clip,
-                vm_data,
+    fn url_stream_loader(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        request: Request,
+    ) -> OwnedFuture<(), Error> {
+        let handle = match self {
+            Loader::URLStream { self_handle, .. } => {
+                self_handle.expect("Loader not self-introduced")
+            }
+            _ => return Box::pin(async { Err(Error::NotURLStreamLoader) }),
+        };
+
+        let player = player
+            .upgrade()
+            .expect("Could not upgrade weak reference to player");
+
+        fn url_stream_target<'gc>(
+            uc: &UpdateContext<'gc>,
+            handle: LoaderHandle,
+        ) -> Result<Avm2Object<'gc>, Error> {
+            match uc.load_manager.get_loader(handle) {
+                Some(&Loader::URLStream { target_object, .. }) => Ok(target_object),
+                None => Err(Error::Cancelled),
+                _ => Err(Error::NotURLStreamLoader),
+            }
+        }
+
+        fn dispatch_http_status<'gc>(
+            activation: &mut Avm2Activation<'_, 'gc>,
+            target: Avm2Object<'gc>,
+            status: u16,
+            redirected: bool,
+        ) -> Result<(), Error> {
+            let http_status_evt = activation
+                .avm2()
+                .classes()
+                .httpstatusevent
+                .construct(
+                    activation,
+                    &[
+                        "httpStatus".into(),
+                        false.into(),
+                        false.into(),
+                        status.into(),
+                        redirected.into(),
+                    ],
+                )
+                .map_err(|e| Error::Avm2Error(e.to_string()))?;
+            Avm2::dispatch_event(activation.context, http_status_evt, target);
+            Ok(())
+        }
+
+        fn dispatch_io_error<'gc>(
+            activation: &mut Avm2Activation<'_, 'gc>,
+            target: Avm2Object<'gc>,
+        ) -> Result<(), Error> {
+            // FIXME - Match the exact error message generated by Flash
+            let io_error_evt = activation
+                .avm2()
+                .classes()
+                .ioerrorevent
+                .construct(
+                    activation,
+                    &[
+                        "ioError".into(),
+                        false.into(),
+                        false.into(),
+                        "Error #2032: Stream Error".into(),
+                        2032.into(),
+                    ],
+                )
+                .map_err(|e| Error::Avm2Error(e.to_string()))?;
+            Avm2::dispatch_event(activation.context, io_error_evt, target);
+            Ok(())
+        }
+
+        Box::pin(async move {
+            let fetch = player.lock().unwrap().navigator().fetch(request);
+            let mut response = match fetch.await {
+                Ok(response) => response,
+                Err(response) => {
+                    return player.lock().unwrap().update(|uc| {
+                        let target = url_stream_target(uc, handle)?;
+                        uc.load_manager.remove_loader(handle);
+
+                        tracing::error!(
+                            "Error during URLStream load of {:?}: {:?}",
+                            response.url,
+                            response.error
+                        );
+
+                        let mut activation = Avm2Activation::from_nothing(uc);
+                        if let Error::HttpNotOk(_, status, redirected, _) = response.error {
+                            dispatch_http_status(&mut activation, target, status, redirected)?;
+                        }
+                        dispatch_io_error(&mut activation, target)
+                    });
+                }
+            };
+
+            let status = response.status();
+            let redirected = response.redirected();
+            let bytes_total = response.expected_length().ok().flatten();
+
+            player.lock().unwrap().update(|uc| {
+                let target = url_stream_target(uc, handle)?;
+                let open_evt = Avm2EventObject::bare_default_event(uc, "open");
+                Avm2::dispatch_event(uc, open_evt, target);
+                Ok(())
+            })?;
+
+            let mut bytes_loaded = 0;
+            loop {
+                let chunk = response.next_chunk().await;
+                let is_end = player.lock().unwrap().update(|uc| {
+                    // The stream may have been closed while we were waiting for data.
+                    let target = url_stream_target(uc, handle)?;
+                    let mut activation = Avm2Activation::from_nothing(uc);
+
+                    match chunk {
+                        Ok(Some(data)) => {
+                            bytes_loaded += data.len() as u64;
+                            url_stream::append_data(&mut activation, target, &data)
+                                .map_err(|e| Error::Avm2Error(e.to_string()))?;
+
+                            let progress_evt = Avm2EventObject::progress_event(
+                                &mut activation,
+                                "progress",
+                                bytes_loaded,
+                                bytes_total.unwrap_or(bytes_loaded),
+                                false,
+                                false,
+                            );
+                            Avm2::dispatch_event(activation.context, progress_evt, target);
+                            Ok(false)
+                        }
+                        Ok(None) => {
+                            activation.context.load_manager.remove_loader(handle);
+
+                            dispatch_http_status(&mut activation, target, status, redirected)?;
+                            let complete_evt =
+                                Avm2EventObject::bare_default_event(activation.context, "complete");
+                            Avm2::dispatch_event(activation.context, complete_evt, target);
+                            Ok(true)
+                        }
+                        Err(err) => {
+                            activation.context.load_manager.remove_loader(handle);
+
+                            tracing::error!("Error during URLStream load: {:?}", err);
+                            dispatch_io_error(&mut activation, target)?;
+                            Ok(true)
+                        }
+                    }
+                })?;
+
+                if is_end {
+                    break;
+                }
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Report a movie loader start event to script code.
+    fn movie_loader_start(handle: LoaderHandle, uc: &mut UpdateContext<'gc>) -> Result<(), Error> {
+        let me = uc.load_manager.get_loader_mut(handle);
+        if me.is_none() {
+            return Err(Error::Cancelled);
+        }
+
+        let me = me.unwrap();
+
+        let (clip, vm_data) = match me {
+            Loader::Movie {
+                target_clip,
+                vm_data,
                 ..
             } => (*target_clip, *vm_data),
             _ => unreachable!(),
@@ -2009,7 +2575,6 @@ impl<'gc> Loader<'gc> {
         loader_url: Option<String>,
     ) -> Result<(), Error> {
         let sniffed_type = ContentType::sniff(data);
-        let length = data.len();
 
         if sniffed_type == ContentType::Unknown {
             if let Ok(data) = extract_swz(data) {
@@ -2018,6 +2583,37 @@ impl<'gc> Loader<'gc> {
                 );
             }
         }
+
+        let movie = match sniffed_type {
+            ContentType::Swf => Arc::new(SwfMovie::from_data(data, url, loader_url)?),
+            ContentType::Gif | ContentType::Jpeg | ContentType::Png => {
+                Arc::new(SwfMovie::from_loaded_image(url, data.len()))
+            }
+            ContentType::Unknown => Arc::new(SwfMovie::error_movie(url)),
+        };
+
+        Self::movie_loader_content(handle, uc, sniffed_type, movie, data, status, redirected)
+    }
+
+    /// Hand the content of a movie loader over to its target.
+    ///
+    /// A SWF may still be loading, in which case `data` is empty, and the
+    /// movie will be preloaded as its data arrives.
+    fn movie_loader_content(
+        handle: LoaderHandle,
+        uc: &mut UpdateContext<'gc>,
+        sniffed_type: ContentType,
+        movie: Arc<SwfMovie>,
+        data: &[u8],
+        status: u16,
+        redirected: bool,
+    ) -> Result<(), Error> {
+        let length = match sniffed_type {
+            ContentType::Unknown => data.len(),
+            // For a SWF that is still loading, this is the expected length.
+            _ => movie.compressed_len(),
+        };
+
         let (clip, vm_data, from_bytes) = match uc.load_manager.get_loader(handle) {
             Some(Loader::Movie {
                 target_clip,
@@ -2055,16 +2651,6 @@ impl<'gc> Loader<'gc> {
             activation.context.avm2.stage_domain()
         };
 
-        let movie = match sniffed_type {
-            ContentType::Swf => {
-                Arc::new(SwfMovie::from_data(data, url.clone(), loader_url.clone())?)
-            }
-            ContentType::Gif | ContentType::Jpeg | ContentType::Png => {
-                Arc::new(SwfMovie::from_loaded_image(url.clone(), length))
-            }
-            ContentType::Unknown => Arc::new(SwfMovie::error_movie(url.clone())),
-        };
-
         match activation.context.load_manager.get_loader_mut(handle) {
             Some(Loader::Movie {
                 movie: old,
@@ -2084,7 +2670,7 @@ impl<'gc> Loader<'gc> {
                 .set_content_type(sniffed_type);
             let fake_movie = Arc::new(SwfMovie::fake_with_compressed_len(
                 activation.context.swf.version(),
-                data.len(),
+                length,
             ));
 
             // Expose 'bytesTotal' (via the fake movie) during the first 'progress' event,
@@ -2172,7 +2758,8 @@ impl<'gc> Loader<'gc> {
                 }
 
                 // NOTE: Certain tests specifically expect small files to preload immediately
-                if !from_bytes {
+                // A SWF that is still loading is preloaded once its data has arrived.
+                if !from_bytes && movie.is_loaded() {
                     Loader::preload_tick(
                         handle,
                         uc,
@@ -2224,7 +2811,7 @@ impl<'gc> Loader<'gc> {
                 if let MovieLoaderVMData::Avm2 { loader_info, .. } = vm_data {
                     let fake_movie = Arc::new(SwfMovie::fake_with_compressed_len(
                         activation.context.swf.version(),
-                        data.len(),
+                        length,
                     ));
 
                     loader_info
@@ -2283,6 +2870,7 @@ impl<'gc> Loader<'gc> {
                 }
             }
             ContentType::Unknown => {
+                let url = movie.url().to_string();
                 match vm_data {
                     MovieLoaderVMData::Avm1 { .. } => {
                         // If the file is no valid supported file, the MovieClip enters the error state
@@ -2303,7 +2891,7 @@ impl<'gc> Loader<'gc> {
                     MovieLoaderVMData::Avm2 { loader_info, .. } => {
                         let fake_movie = Arc::new(SwfMovie::fake_with_compressed_len(
                             activation.context.swf.version(),
-                            data.len(),
+                            length,
                         ));
 
                         let loader_info = loader_info.as_loader_info_object().unwrap();
@@ -3002,16 +3590,10 @@ impl<'gc> Loader<'gc> {
             .expect("Could not upgrade weak reference to player");
 
         Box::pin(async move {
-            let dialog_result = dialog.await;
-
-            // Dialog is done, allow opening new dialogs
-            player.lock().unwrap().ui_mut().close_file_dialog();
+            let dialog_result = Self::wait_for_file_dialog(&player, dialog).await;
 
             // Download the data
-            let req = Request::get(url.clone());
-            // Doing this in two steps to prevent holding the player lock during fetch
-            let future = player.lock().unwrap().navigator().fetch(req);
-            let download_res = Self::wait_for_full_response(future).await;
+            let download_res = Self::transfer_file(&player, Request::get(url.clone())).await;
 
             // Fire the load handler.
             player.lock().unwrap().update(|uc| -> Result<(), Error> {
@@ -3185,14 +3767,13 @@ impl<'gc> Loader<'gc> {
 
     /// Loader to handle a file upload task
     ///
-    /// Uploads the given `data` to the provided `url`.
-    /// `file_name` is sent along with the data, as part of the multipart/form-data body
+    /// Sends `request`, whose body contains the file, and processes callbacks.
+    /// `file_size` is the size of the uploaded file, which is reported to `onProgress`.
     pub fn file_upload_loader(
         &mut self,
         player: Weak<Mutex<Player>>,
-        url: String,
-        data: Vec<u8>,
-        file_name: String,
+        request: Request,
+        file_size: u64,
     ) -> OwnedFuture<(), Error> {
         let handle = match self {
             Loader::UploadFile { self_handle, .. } => {
@@ -3206,47 +3787,7 @@ impl<'gc> Loader<'gc> {
             .expect("Could not upgrade weak reference to player");
 
         Box::pin(async move {
-            let total_size_bytes = data.len();
-
-            //FIXME: The code below won't work if the payload contains the boundary separator
-            if file_name.contains("------------BOUNDARY")
-                || data.windows(20).any(|b| b == b"------------BOUNDARY")
-            {
-                tracing::error!(
-                    "File upload data contains boundary separator, request cannot be sent"
-                );
-                return Err(Error::Cancelled);
-            }
-
-            // Format the data into multipart/form-data
-            let mut out_data = Vec::new();
-            out_data.extend_from_slice(b"------------BOUNDARY\n");
-            out_data.extend_from_slice(b"Content-Disposition: form-data; name=\"Filename\"\n\n");
-            out_data.extend_from_slice(file_name.as_bytes());
-            out_data.extend_from_slice(b"\n------------BOUNDARY\n");
-            out_data.extend_from_slice(
-                b"Content-Disposition: form-data; name=\"Filedata\"; filename=\"",
-            );
-            out_data.extend_from_slice(file_name.as_bytes());
-            out_data.extend_from_slice(b"\"\n");
-            out_data.extend_from_slice(b"Content-Type: application/octet-stream\n\n");
-            out_data.extend_from_slice(&data);
-            out_data.extend_from_slice(b"\n------------BOUNDARY\n");
-            out_data.extend_from_slice(b"Content-Disposition: form-data; name=\"Upload\"\n\n");
-            out_data.extend_from_slice(b"Submit Query");
-            out_data.extend_from_slice(b"\n------------BOUNDARY\n");
-
-            // Upload the data
-            let req = Request::post(
-                url,
-                Some((
-                    out_data,
-                    "multipart/form-data; boundary=------------BOUNDARY".to_string(),
-                )),
-            );
-            // Doing this in two steps to prevent holding the player lock during fetch
-            let future = player.lock().unwrap().navigator().fetch(req);
-            let result = future.await;
+            let result = Self::transfer_file(&player, request).await;
 
             // Fire the load handler.
             player.lock().unwrap().update(|uc| -> Result<(), Error> {
@@ -3275,11 +3816,7 @@ impl<'gc> Loader<'gc> {
                         as_broadcaster::broadcast_internal(
                             &mut activation,
                             target_object,
-                            &[
-                                target_object.into(),
-                                total_size_bytes.into(),
-                                total_size_bytes.into(),
-                            ],
+                            &[target_object.into(), file_size.into(), file_size.into()],
                             "onProgress".into(),
                         )?;
 
@@ -3310,11 +3847,7 @@ impl<'gc> Loader<'gc> {
                                 as_broadcaster::broadcast_internal(
                                     &mut activation,
                                     target_object,
-                                    &[
-                                        target_object.into(),
-                                        total_size_bytes.into(),
-                                        total_size_bytes.into(),
-                                    ],
+                                    &[target_object.into(), file_size.into(), file_size.into()],
                                     "onProgress".into(),
                                 )?;
 
@@ -3350,4 +3883,540 @@ impl<'gc> Loader<'gc> {
             })
         })
     }
+
+    /// Loader to handle a file download dialog from an AVM2 scope
+    ///
+    /// Fetches the data of `request`, saves the data to the selected destination and dispatches events
+    pub fn file_download_dialog_loader_avm2(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        dialog: DialogResultFuture,
+        request: Request,
+    ) -> OwnedFuture<(), Error> {
+        let handle = match self {
+            Loader::DownloadFileDialogAvm2 { self_handle, .. } => {
+                self_handle.expect("Loader not self-introduced")
+            }
+            _ => return Box::pin(async { Err(Error::NotFileDownloadDialogLoader) }),
+        };
+
+        let player = player
+            .upgrade()
+            .expect("Could not upgrade weak reference to player");
+
+        Box::pin(async move {
+            let dialog_result = Self::wait_for_file_dialog(&player, dialog).await;
+
+            // The `select` and `open` events are dispatched before the download begins.
+            let selected = player.lock().unwrap().update(|uc| -> Result<bool, Error> {
+                let target_object = match uc.load_manager.get_loader(handle) {
+                    Some(&Loader::DownloadFileDialogAvm2 { target_object, .. }) => target_object,
+                    None => return Err(Error::Cancelled),
+                    _ => return Err(Error::NotFileDownloadDialogLoader),
+                };
+
+                let dialog_result = match dialog_result {
+                    Ok(dialog_result) if !dialog_result.is_cancelled() => dialog_result,
+                    Ok(_) => {
+                        uc.load_manager.remove_loader(handle);
+                        target_object.set_loader(None);
+
+                        let cancel_event = Avm2EventObject::bare_default_event(uc, "cancel");
+                        Avm2::dispatch_event(uc, cancel_event, target_object.into());
+                        return Ok(false);
+                    }
+                    Err(err) => {
+                        uc.load_manager.remove_loader(handle);
+                        target_object.set_loader(None);
+
+                        tracing::warn!("Download dialog had an error {:?}", err);
+                        return Ok(false);
+                    }
+                };
+
+                target_object.init_from_dialog_result(dialog_result);
+                target_object.set_loaded(false);
+
+                let select_event = Avm2EventObject::bare_default_event(uc, "select");
+                Avm2::dispatch_event(uc, select_event, target_object.into());
+
+                let open_event = Avm2EventObject::bare_default_event(uc, "open");
+                Avm2::dispatch_event(uc, open_event, target_object.into());
+
+                Ok(true)
+            })?;
+
+            if !selected {
+                return Ok(());
+            }
+
+            fn download_target<'gc>(
+                uc: &UpdateContext<'gc>,
+                handle: LoaderHandle,
+            ) -> Result<FileReferenceObject<'gc>, Error> {
+                match uc.load_manager.get_loader(handle) {
+                    Some(&Loader::DownloadFileDialogAvm2 { target_object, .. }) => {
+                        Ok(target_object)
+                    }
+                    None => Err(Error::Cancelled),
+                    _ => Err(Error::NotFileDownloadDialogLoader),
+                }
+            }
+
+            fn dispatch_io_error<'gc>(
+                activation: &mut Avm2Activation<'_, 'gc>,
+                target_object: FileReferenceObject<'gc>,
+            ) -> Result<(), Error> {
+                let io_error_evt = activation
+                    .avm2()
+                    .classes()
+                    .ioerrorevent
+                    .construct(
+                        activation,
+                        &[
+                            "ioError".into(),
+                            false.into(),
+                            false.into(),
+                            "Error #2038: File I/O Error.".into(),
+                            2038.into(),
+                        ],
+                    )
+                    .map_err(|e| Error::Avm2Error(e.to_string()))?;
+                Avm2::dispatch_event(activation.context, io_error_evt, target_object.into());
+                Ok(())
+            }
+
+            let fetch = player.lock().unwrap().navigator().fetch(request);
+            let mut response = match fetch.await {
+                Ok(response) => response,
+                Err(response) => {
+                    return player.lock().unwrap().update(|uc| {
+                        let target_object = download_target(uc, handle)?;
+                        uc.load_manager.remove_loader(handle);
+                        target_object.set_loader(None);
+
+                        tracing::error!(
+                            "Error during FileReference download of {:?}: {:?}",
+                            response.url,
+                            response.error
+                        );
+
+                        let mut activation = Avm2Activation::from_nothing(uc);
+                        dispatch_io_error(&mut activation, target_object)
+                    });
+                }
+            };
+
+            let bytes_total = response.expected_length().ok().flatten();
+            let mut body = Vec::new();
+            loop {
+                let chunk = response.next_chunk().await;
+                let is_end = player.lock().unwrap().update(|uc| -> Result<bool, Error> {
+                    // The download may have been cancelled while we were waiting for data.
+                    let target_object = download_target(uc, handle)?;
+                    let mut activation = Avm2Activation::from_nothing(uc);
+
+                    match chunk {
+                        Ok(Some(data)) => {
+                            body.extend_from_slice(&data);
+
+                            let bytes_loaded = body.len() as u64;
+                            let progress_evt = Avm2EventObject::progress_event(
+                                &mut activation,
+                                "progress",
+                                bytes_loaded,
+                                bytes_total.unwrap_or(bytes_loaded),
+                                false,
+                                false,
+                            );
+                            Avm2::dispatch_event(
+                                activation.context,
+                                progress_evt,
+                                target_object.into(),
+                            );
+                            Ok(false)
+                        }
+                        Ok(None) => {
+                            activation.context.load_manager.remove_loader(handle);
+                            target_object.set_loader(None);
+
+                            if let FileReference::FileDialogResult(ref mut dialog_result) =
+                                *target_object.file_reference_mut()
+                            {
+                                dialog_result.write_and_refresh(&body);
+                            }
+
+                            let complete_evt =
+                                Avm2EventObject::bare_default_event(activation.context, "complete");
+                            Avm2::dispatch_event(
+                                activation.context,
+                                complete_evt,
+                                target_object.into(),
+                            );
+                            Ok(true)
+                        }
+                        Err(error) => {
+                            activation.context.load_manager.remove_loader(handle);
+                            target_object.set_loader(None);
+
+                            tracing::error!(
+                                "Error during FileReference download of {:?}: {:?}",
+                                response.url(),
+                                error
+                            );
+                            dispatch_io_error(&mut activation, target_object)?;
+                            Ok(true)
+                        }
+                    }
+                })?;
+
+                if is_end {
+                    return Ok(());
+                }
+            }
+        })
+    }
+
+    /// Loader to handle a file upload task from an AVM2 scope
+    ///
+    /// Sends `request`, whose body contains the file, and dispatches events.
+    /// `file_size` is the size of the uploaded file, which is reported in progress events.
+    pub fn file_upload_loader_avm2(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        request: Request,
+        file_size: u64,
+    ) -> OwnedFuture<(), Error> {
+        let handle = match self {
+            Loader::UploadFileAvm2 { self_handle, .. } => {
+                self_handle.expect("Loader not self-introduced")
+            }
+            _ => return Box::pin(async { Err(Error::NotFileUploadLoader) }),
+        };
+
+        let player = player
+            .upgrade()
+            .expect("Could not upgrade weak reference to player");
+
+        Box::pin(async move {
+            let response = Self::transfer_file(&player, request).await;
+
+            // Fire the load handler.
+            player.lock().unwrap().update(|uc| -> Result<(), Error> {
+                let target_object = match uc.load_manager.get_loader(handle) {
+                    Some(&Loader::UploadFileAvm2 { target_object, .. }) => target_object,
+                    None => return Err(Error::Cancelled),
+                    _ => return Err(Error::NotFileUploadLoader),
+                };
+                uc.load_manager.remove_loader(handle);
+                target_object.set_loader(None);
+
+                let mut activation = Avm2Activation::from_nothing(uc);
+
+                let (body, status, redirected) = match response {
+                    Ok((body, _, status, redirected)) => (Ok(body), status, redirected),
+                    Err(response) => {
+                        tracing::error!(
+                            "Error during FileReference upload of {:?}: {:?}",
+                            response.url,
+                            response.error
+                        );
+
+                        match response.error {
+                            Error::HttpNotOk(_, status, redirected, _) => {
+                                (Err(Some(status)), status, redirected)
+                            }
+                            // The connection could not be opened, so only `ioError` is dispatched.
+                            _ => (Err(None), 0, false),
+                        }
+                    }
+                };
+
+                if status != 0 {
+                    let open_evt = Avm2EventObject::bare_default_event(activation.context, "open");
+                    Avm2::dispatch_event(activation.context, open_evt, target_object.into());
+
+                    // The navigator sends the whole request body before the response arrives,
+                    // without reporting how much of it has been sent so far.
+                    let progress_evt = Avm2EventObject::progress_event(
+                        &mut activation,
+                        "progress",
+                        file_size,
+                        file_size,
+                        false,
+                        false,
+                    );
+                    Avm2::dispatch_event(activation.context, progress_evt, target_object.into());
+                }
+
+                match body {
+                    Ok(body) => {
+                        let complete_evt =
+                            Avm2EventObject::bare_default_event(activation.context, "complete");
+                        Avm2::dispatch_event(
+                            activation.context,
+                            complete_evt,
+                            target_object.into(),
+                        );
+
+                        // The response of the server is only available through this event.
+                        let data = AvmString::new_utf8_bytes(activation.context.gc_context, &body);
+                        let upload_complete_data_evt = activation
+                            .avm2()
+                            .classes()
+                            .dataevent
+                            .construct(
+                                &mut activation,
+                                &[
+                                    "uploadCompleteData".into(),
+                                    false.into(),
+                                    false.into(),
+                                    data.into(),
+                                ],
+                            )
+                            .map_err(|e| Error::Avm2Error(e.to_string()))?;
+                        Avm2::dispatch_event(uc, upload_complete_data_evt, target_object.into());
+                    }
+                    Err(http_status) => {
+                        // Unlike `URLLoader`, `httpStatus` is only dispatched when an upload fails.
+                        if let Some(status) = http_status {
+                            let http_status_evt = activation
+                                .avm2()
+                                .classes()
+                                .httpstatusevent
+                                .construct(
+                                    &mut activation,
+                                    &[
+                                        "httpStatus".into(),
+                                        false.into(),
+                                        false.into(),
+                                        status.into(),
+                                        redirected.into(),
+                                    ],
+                                )
+                                .map_err(|e| Error::Avm2Error(e.to_string()))?;
+                            Avm2::dispatch_event(
+                                activation.context,
+                                http_status_evt,
+                                target_object.into(),
+                            );
+                        }
+
+                        let io_error_evt_cls = activation.avm2().classes().ioerrorevent;
+                        let io_error_evt = io_error_evt_cls
+                            .construct(
+                                &mut activation,
+                                &[
+                                    "ioError".into(),
+                                    false.into(),
+                                    false.into(),
+                                    "Error #2038: File I/O Error.".into(),
+                                    2038.into(),
+                                ],
+                            )
+                            .map_err(|e| Error::Avm2Error(e.to_string()))?;
+
+                        Avm2::dispatch_event(uc, io_error_evt, target_object.into());
+                    }
+                }
+
+                Ok(())
+            })
+        })
+    }
+
+    /// Loader to process callbacks for a dialog selecting multiple files from an AVM2 scope
+    pub fn file_list_dialog_loader_avm2(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        dialog: MultipleDialogResultFuture,
+    ) -> OwnedFuture<(), Error> {
+        let handle = match self {
+            Loader::FileListDialogAvm2 { self_handle, .. } => {
+                self_handle.expect("Loader not self-introduced")
+            }
+            _ => return Box::pin(async { Err(Error::NotFileDialogLoader) }),
+        };
+
+        let player = player
+            .upgrade()
+            .expect("Could not upgrade weak reference to player");
+
+        Box::pin(async move {
+            let dialog_result = dialog.await;
+
+            // Dialog is done, allow opening new dialogs
+            player.lock().unwrap().ui_mut().close_file_dialog();
+
+            // Fire the load handler.
+            player.lock().unwrap().update(|uc| -> Result<(), Error> {
+                let target_object = match uc.load_manager.get_loader(handle) {
+                    Some(&Loader::FileListDialogAvm2 { target_object, .. }) => target_object,
+                    None => return Err(Error::Cancelled),
+                    _ => return Err(Error::NotFileDialogLoader),
+                };
+                uc.load_manager.remove_loader(handle);
+
+                let mut activation = Avm2Activation::from_nothing(uc);
+
+                match dialog_result {
+                    Ok(dialog_results) if !dialog_results.is_empty() => {
+                        let mut files = Vec::with_capacity(dialog_results.len());
+                        for dialog_result in dialog_results {
+                            let file = activation
+                                .avm2()
+                                .classes()
+                                .filereference
+                                .construct(&mut activation, &[])
+                                .map_err(|e| Error::Avm2Error(e.to_string()))?;
+                            file.as_file_reference()
+                                .expect("FileReference objects are FileReferenceObjects")
+                                .init_from_dialog_result(dialog_result);
+                            files.push(Some(file.into()));
+                        }
+
+                        let file_list = ArrayObject::from_storage(
+                            &mut activation,
+                            ArrayStorage::from_storage(files),
+                        )
+                        .map_err(|e| Error::Avm2Error(e.to_string()))?;
+                        target_object
+                            .set_property(
+                                &Multiname::new(activation.avm2().flash_net_internal, "_fileList"),
+                                file_list.into(),
+                                &mut activation,
+                            )
+                            .map_err(|e| Error::Avm2Error(e.to_string()))?;
+
+                        let select_event =
+                            Avm2EventObject::bare_default_event(activation.context, "select");
+                        Avm2::dispatch_event(activation.context, select_event, target_object);
+                    }
+                    Ok(_) => {
+                        let cancel_event =
+                            Avm2EventObject::bare_default_event(activation.context, "cancel");
+                        Avm2::dispatch_event(activation.context, cancel_event, target_object);
+                    }
+                    Err(err) => {
+                        tracing::warn!("Error on file dialog: {:?}", err);
+                    }
+                }
+
+                Ok(())
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tag_utils::{self, SwfStream};
+    use swf::{Compression, DefineBinaryData, Header, Tag, TagCode};
+
+    const NUM_FRAMES: u16 = 8;
+
+    /// A SWF with a different 2 KiB binary data tag in each frame.
+    fn write_swf(compression: Compression) -> Vec<u8> {
+        // Data that doesn't compress well, so that the frames arrive over several chunks.
+        let mut seed = 1u32;
+        let data: Vec<Vec<u8>> = (0..NUM_FRAMES)
+            .map(|_| {
+                (0..2048)
+                    .map(|_| {
+                        seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+                        (seed >> 16) as u8
+                    })
+                    .collect()
+            })
+            .collect();
+        let mut tags = Vec::new();
+        for (id, data) in (1..).zip(&data) {
+            tags.push(Tag::DefineBinaryData(DefineBinaryData { id, data }));
+            tags.push(Tag::ShowFrame);
+        }
+        let header = Header {
+            compression,
+            num_frames: NUM_FRAMES,
+            ..Header::default_with_swf_version(10)
+        };
+        let mut swf = Vec::new();
+        swf::write_swf(&header, &tags, &mut swf).unwrap();
+        swf
+    }
+
+    /// The number of frames whose tags have entirely arrived.
+    fn frames_loaded(movie: &SwfMovie) -> usize {
+        let mut reader = SwfStream::new(movie.data(), movie.version());
+        if !movie.is_loaded() {
+            tag_utils::truncate_to_complete_tags(&mut reader);
+        }
+        let mut frames = 0;
+        while let Ok((tag_code, tag_len)) = reader.read_tag_code_and_length() {
+            if tag_code == TagCode::ShowFrame as u16 {
+                frames += 1;
+            }
+            *reader.get_mut() = &reader.get_ref()[tag_len..];
+        }
+        frames
+    }
+
+    /// Stream a SWF in 1 KiB chunks, checking that it loads progressively.
+    fn load_in_chunks(swf: &[u8]) {
+        let url = "file:///movie.swf".to_string();
+        let mut stream = MovieStream::new(url.clone(), None, Vec::new(), Some(swf.len() as u64));
+        let mut movie = None;
+        let mut progress = Vec::new();
+        for chunk in swf.chunks(1024) {
+            if let Some(new_movie) = stream.write(chunk) {
+                assert!(movie.is_none(), "movie should only be created once");
+                movie = Some(new_movie);
+            }
+            if let Some(movie) = &movie {
+                assert!(!movie.is_loaded());
+                assert_eq!(movie.compressed_len(), swf.len());
+                progress.push((movie.data().len(), frames_loaded(movie)));
+            }
+        }
+        assert!(stream.finish().is_none(), "movie should load progressively");
+        let movie = movie.unwrap();
+        assert!(movie.is_loaded());
+        progress.push((movie.data().len(), frames_loaded(&movie)));
+
+        // The bytes and frames loaded only ever grow, and some frames are
+        // available before the entire movie is.
+        assert!(progress
+            .windows(2)
+            .all(|pair| pair[0].0 <= pair[1].0 && pair[0].1 <= pair[1].1));
+        assert!(progress
+            .iter()
+            .any(|&(_, frames)| frames > 0 && frames < NUM_FRAMES as usize));
+
+        let expected = SwfMovie::from_data(swf, url, None).unwrap();
+        assert_eq!(movie.data(), expected.data());
+        assert_eq!(movie.total_data_len(), expected.data().len());
+        assert_eq!(
+            progress.last(),
+            Some(&(expected.data().len(), NUM_FRAMES as usize))
+        );
+    }
+
+    #[test]
+    fn load_zlib_swf_in_chunks() {
+        load_in_chunks(&write_swf(Compression::Zlib));
+    }
+
+    #[test]
+    fn load_uncompressed_swf_in_chunks() {
+        load_in_chunks(&write_swf(Compression::None));
+    }
+
+    #[test]
+    fn load_swf_longer_than_header_in_chunks() {
+        let mut swf = write_swf(Compression::Zlib);
+        // Claim that the SWF is half as long as it actually is.
+        let uncompressed_len = u32::from_le_bytes(swf[4..8].try_into().unwrap());
+        swf[4..8].copy_from_slice(&(uncompressed_len / 2).to_le_bytes());
+        load_in_chunks(&swf);
+    }
 }
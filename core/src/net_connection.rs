@@ -1,18 +1,33 @@
+mod rtmp;
+
 use crate::avm1::globals::netconnection::NetConnection as Avm1NetConnectionObject;
-use crate::avm1::Object as Avm1Object;
+use crate::avm1::globals::shared_object::deserialize_value as deserialize_avm1_value;
+use crate::avm1::{
+    Activation as Avm1Activation, ActivationIdentifier as Avm1ActivationIdentifier,
+    ExecutionReason as Avm1ExecutionReason, Object as Avm1Object, TObject as Avm1TObject,
+};
+use crate::avm2::amf::deserialize_value as deserialize_avm2_value;
 use crate::avm2::object::{
     NetConnectionObject as Avm2NetConnectionObject, ResponderObject as Avm2ResponderObject,
+    TObject as Avm2TObject,
+};
+use crate::avm2::{
+    Activation as Avm2Activation, Avm2, EventObject as Avm2EventObject, Value as Avm2Value,
 };
-use crate::avm2::{Activation as Avm2Activation, Avm2, EventObject as Avm2EventObject};
 use crate::backend::navigator::{ErrorResponse, NavigatorBackend, OwnedFuture, Request};
 use crate::context::UpdateContext;
 use crate::loader::Error;
+use crate::streams::NetStream;
 use crate::string::AvmString;
 use crate::Player;
 use flash_lso::packet::{Header, Message, Packet};
 use flash_lso::types::{AMFVersion, Value as AmfValue};
 use gc_arena::{Collect, DynamicRoot, Rootable};
+use rtmp::{Rtmp, RtmpEvent};
+
+pub use rtmp::FLV_HEADER;
 use slotmap::{new_key_type, SlotMap};
+use std::collections::BTreeMap;
 use std::fmt::{Debug, Formatter};
 use std::rc::Rc;
 use std::sync::{Mutex, Weak};
@@ -68,7 +83,7 @@ impl ResponderHandle {
     }
 }
 
-#[derive(Copy, Clone, Collect)]
+#[derive(Copy, Clone, Collect, Debug)]
 #[collect(no_drop)]
 pub enum NetConnectionObject<'gc> {
     Avm2(Avm2NetConnectionObject<'gc>),
@@ -76,6 +91,15 @@ pub enum NetConnectionObject<'gc> {
 }
 
 impl<'gc> NetConnectionObject<'gc> {
+    pub fn handle(&self) -> Option<NetConnectionHandle> {
+        match self {
+            NetConnectionObject::Avm2(object) => object.handle(),
+            NetConnectionObject::Avm1(object) => {
+                Avm1NetConnectionObject::cast((*object).into()).and_then(|nc| nc.handle())
+            }
+        }
+    }
+
     pub fn set_handle(&self, handle: Option<NetConnectionHandle>) -> Option<NetConnectionHandle> {
         match self {
             NetConnectionObject::Avm2(object) => object.set_handle(handle),
@@ -132,6 +156,7 @@ impl<'gc> NetConnections<'gc> {
         let connection = NetConnection {
             object: target,
             protocol: NetConnectionProtocol::Local,
+            streams: Vec::new(),
         };
         let handle = context.net_connections.connections.insert(connection);
 
@@ -177,6 +202,7 @@ impl<'gc> NetConnections<'gc> {
                 headers: vec![],
                 outgoing_queue: vec![],
             }),
+            streams: Vec::new(),
         };
         let handle = context.net_connections.connections.insert(connection);
 
@@ -187,11 +213,59 @@ impl<'gc> NetConnections<'gc> {
         // No open event here
     }
 
+    pub fn connect_to_rtmp<O: Into<NetConnectionObject<'gc>>>(
+        context: &mut UpdateContext<'gc>,
+        target: O,
+        url: String,
+        object_encoding: AMFVersion,
+        arguments: Vec<AmfValue>,
+    ) {
+        let target = target.into();
+        let rtmp = match Rtmp::connect(
+            context.navigator,
+            &url,
+            context.swf.url(),
+            object_encoding,
+            arguments,
+        ) {
+            Ok(rtmp) => rtmp,
+            Err(e) => {
+                tracing::error!("Couldn't connect to {url}: {e}");
+                Self::dispatch_status(
+                    context,
+                    target,
+                    "NetConnection.Connect.Failed",
+                    "error",
+                    None,
+                );
+                return;
+            }
+        };
+
+        let connection = NetConnection {
+            object: target,
+            protocol: NetConnectionProtocol::Rtmp(rtmp),
+            streams: Vec::new(),
+        };
+        let handle = context.net_connections.connections.insert(connection);
+
+        if let Some(existing_handle) = target.set_handle(Some(handle)) {
+            NetConnections::close(context, existing_handle, false);
+        }
+
+        // The status event is sent once the server has accepted the connection.
+    }
+
     pub fn close(context: &mut UpdateContext<'gc>, handle: NetConnectionHandle, is_explicit: bool) {
         let Some(connection) = context.net_connections.connections.remove(handle) else {
             return;
         };
 
+        // The object may already have moved on to a newer connection.
+        if connection.object.handle() == Some(handle) {
+            connection.object.set_handle(None);
+        }
+
         match connection.object {
             NetConnectionObject::Avm2(object) => {
                 let mut activation = Avm2Activation::from_nothing(context);
@@ -243,8 +317,443 @@ impl<'gc> NetConnections<'gc> {
     }
 
     pub fn update_connections(context: &mut UpdateContext<'gc>) {
+        let mut rtmp_events = Vec::new();
+
         for (handle, connection) in context.net_connections.connections.iter_mut() {
             connection.update(handle, context.navigator, context.player.clone());
+
+            if let NetConnectionProtocol::Rtmp(rtmp) = &mut connection.protocol {
+                rtmp_events.extend(rtmp.poll().into_iter().map(|event| (handle, event)));
+            }
+        }
+
+        for (handle, event) in rtmp_events {
+            Self::handle_rtmp_event(context, handle, event);
+        }
+    }
+
+    fn handle_rtmp_event(
+        context: &mut UpdateContext<'gc>,
+        handle: NetConnectionHandle,
+        event: RtmpEvent,
+    ) {
+        let Some(connection) = context.net_connections.connections.get_mut(handle) else {
+            // The connection was closed while handling an earlier event.
+            return;
+        };
+        let object = connection.object;
+
+        match event {
+            RtmpEvent::ConnectFailed => {
+                context.net_connections.connections.remove(handle);
+                if object.handle() == Some(handle) {
+                    object.set_handle(None);
+                }
+                Self::dispatch_status(
+                    context,
+                    object,
+                    "NetConnection.Connect.Failed",
+                    "error",
+                    None,
+                );
+            }
+            RtmpEvent::ConnectResult { success, info } => {
+                if success {
+                    Self::dispatch_status(
+                        context,
+                        object,
+                        "NetConnection.Connect.Success",
+                        "status",
+                        info.as_deref(),
+                    );
+                } else {
+                    Self::dispatch_status(
+                        context,
+                        object,
+                        "NetConnection.Connect.Rejected",
+                        "error",
+                        info.as_deref(),
+                    );
+                    NetConnections::close(context, handle, false);
+                }
+            }
+            RtmpEvent::Closed => {
+                NetConnections::close(context, handle, false);
+            }
+            RtmpEvent::CallResult {
+                responder,
+                callback,
+                value,
+            } => {
+                responder.call(context, callback, value);
+            }
+            RtmpEvent::StreamCreated {
+                transaction_id,
+                stream_id,
+            } => {
+                let Some(index) = connection.streams.iter().position(|s| {
+                    s.stream_id.is_none() && s.transaction_id == Some(transaction_id)
+                }) else {
+                    return;
+                };
+
+                let Some(stream_id) = stream_id else {
+                    let stream = connection.streams.remove(index).stream;
+                    stream.trigger_status_event(
+                        context,
+                        vec![("code", "NetStream.Play.Failed"), ("level", "error")],
+                    );
+                    return;
+                };
+
+                let buffer_time_ms =
+                    (connection.streams[index].stream.buffer_time() * 1000.0) as u32;
+                let entry = &mut connection.streams[index];
+                entry.stream_id = Some(stream_id);
+                entry.transaction_id = None;
+                let action = entry.pending_action.take();
+
+                if let (NetConnectionProtocol::Rtmp(rtmp), Some(action)) =
+                    (&mut connection.protocol, action)
+                {
+                    match action {
+                        RtmpStreamAction::Play(name) => rtmp.play(stream_id, &name, buffer_time_ms),
+                        RtmpStreamAction::Publish(name, publish_type) => {
+                            rtmp.publish(stream_id, &name, &publish_type)
+                        }
+                    }
+                }
+            }
+            RtmpEvent::Command {
+                stream_id: 0,
+                name,
+                arguments,
+            } => {
+                if name == "close" {
+                    NetConnections::close(context, handle, false);
+                } else {
+                    Self::call_client_method(context, object, &name, &arguments);
+                }
+            }
+            RtmpEvent::Command {
+                stream_id,
+                name,
+                arguments,
+            } => {
+                let Some(entry) = connection
+                    .streams
+                    .iter()
+                    .find(|s| s.stream_id == Some(stream_id))
+                else {
+                    return;
+                };
+                let stream = entry.stream;
+
+                if name == "onStatus" {
+                    let info: Vec<_> = arguments
+                        .first()
+                        .map(|info| rtmp::amf_string_properties(info))
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(|(key, value)| {
+                            (
+                                AvmString::new_utf8(context.gc_context, key),
+                                AvmString::new_utf8(context.gc_context, value),
+                            )
+                        })
+                        .collect();
+                    stream.trigger_status_event(context, info);
+                } else {
+                    tracing::warn!("Unhandled RTMP stream command {name}");
+                }
+            }
+            RtmpEvent::Media {
+                stream_id,
+                tag_type,
+                timestamp,
+                payload,
+            } => {
+                let Some(entry) = connection
+                    .streams
+                    .iter_mut()
+                    .find(|s| s.stream_id == Some(stream_id))
+                else {
+                    return;
+                };
+
+                let mut tag = rtmp::flv_tag(entry.previous_tag_size, tag_type, timestamp, &payload);
+                entry.previous_tag_size = (tag.len() - 4) as u32;
+                let stream = entry.stream;
+                let is_first_media = !std::mem::replace(&mut entry.has_media, true);
+
+                if is_first_media {
+                    // Streams don't necessarily start at 0, e.g. when joining a
+                    // live stream or after seeking.
+                    stream.set_stream_time(context, timestamp as f64);
+                }
+                stream.append_stream_data(context, &mut tag);
+            }
+        }
+    }
+
+    /// Send a status event to a `NetConnection`.
+    ///
+    /// `info` is the status object as sent by a server, if any. It takes
+    /// precedence over the default `code` and `level`.
+    fn dispatch_status(
+        context: &mut UpdateContext<'gc>,
+        object: NetConnectionObject<'gc>,
+        code: &'static str,
+        level: &'static str,
+        info: Option<&AmfValue>,
+    ) {
+        match object {
+            NetConnectionObject::Avm2(object) => {
+                let mut activation = Avm2Activation::from_nothing(context);
+                let info_object = match info
+                    .map(|info| deserialize_avm2_value(&mut activation, info))
+                {
+                    Some(Ok(Avm2Value::Object(info_object))) => Some(info_object),
+                    Some(Err(e)) => {
+                        tracing::error!("Couldn't deserialize NetConnection status info: {e:?}");
+                        None
+                    }
+                    _ => None,
+                }
+                .unwrap_or_else(|| {
+                    activation
+                        .avm2()
+                        .classes()
+                        .object
+                        .construct(&mut activation, &[])
+                        .expect("Object is constructible")
+                });
+                for (key, value) in [("code", code), ("level", level)] {
+                    if !info_object.has_public_property(key, &mut activation) {
+                        if let Err(e) =
+                            info_object.set_public_property(key, value.into(), &mut activation)
+                        {
+                            tracing::error!("Couldn't set NetConnection status {key}: {e:?}");
+                        }
+                    }
+                }
+                let event = Avm2EventObject::net_status_event_with_info(
+                    &mut activation,
+                    "netStatus",
+                    info_object,
+                );
+                Avm2::dispatch_event(activation.context, event, object.into());
+            }
+            NetConnectionObject::Avm1(object) => {
+                if let Err(e) = Avm1NetConnectionObject::on_server_status_event(
+                    context, object, code, level, info,
+                ) {
+                    tracing::error!("Unhandled error sending connection callback: {e}");
+                }
+            }
+        }
+    }
+
+    /// Invoke a method the server called on the `NetConnection`'s client.
+    fn call_client_method(
+        context: &mut UpdateContext<'gc>,
+        object: NetConnectionObject<'gc>,
+        name: &str,
+        arguments: &[Rc<AmfValue>],
+    ) {
+        match object {
+            NetConnectionObject::Avm2(object) => {
+                let mut activation = Avm2Activation::from_nothing(context);
+                let name = AvmString::new_utf8(activation.context.gc_context, name);
+                let result = (|| {
+                    let mut args = Vec::with_capacity(arguments.len());
+                    for argument in arguments {
+                        args.push(deserialize_avm2_value(&mut activation, argument)?);
+                    }
+                    let client = object
+                        .get_public_property("client", &mut activation)?
+                        .as_object()
+                        .unwrap_or(object.into());
+                    client.call_public_property(name, &args, &mut activation)
+                })();
+
+                if let Err(e) = result {
+                    tracing::error!(
+                        "Unhandled error calling NetConnection client method {name}: {e:?}"
+                    );
+                }
+            }
+            NetConnectionObject::Avm1(object) => {
+                let Some(root_clip) = context.stage.root_clip() else {
+                    tracing::warn!("Ignored NetConnection call as there's no root movie");
+                    return;
+                };
+                let mut activation = Avm1Activation::from_nothing(
+                    context,
+                    Avm1ActivationIdentifier::root("[NetConnection call]"),
+                    root_clip,
+                );
+                let reader = flash_lso::read::Reader::default();
+                let mut reference_cache = BTreeMap::default();
+                let args: Vec<_> = arguments
+                    .iter()
+                    .map(|argument| {
+                        deserialize_avm1_value(
+                            &mut activation,
+                            argument,
+                            &reader.amf0_decoder,
+                            &mut reference_cache,
+                        )
+                    })
+                    .collect();
+                let name = AvmString::new_utf8(activation.context.gc_context, name);
+
+                if let Err(e) =
+                    object.call_method(name, &args, &mut activation, Avm1ExecutionReason::Special)
+                {
+                    tracing::error!("Unhandled error calling NetConnection method {name}: {e}");
+                }
+            }
+        }
+    }
+
+    /// Start playing a named stream from an RTMP connection on a `NetStream`.
+    pub fn play_stream(
+        context: &mut UpdateContext<'gc>,
+        handle: NetConnectionHandle,
+        stream: NetStream<'gc>,
+        name: String,
+    ) {
+        Self::start_stream(context, handle, stream, RtmpStreamAction::Play(name));
+    }
+
+    /// Start publishing a `NetStream` to an RTMP connection.
+    pub fn publish_stream(
+        context: &mut UpdateContext<'gc>,
+        handle: NetConnectionHandle,
+        stream: NetStream<'gc>,
+        name: String,
+        publish_type: String,
+    ) {
+        Self::start_stream(
+            context,
+            handle,
+            stream,
+            RtmpStreamAction::Publish(name, publish_type),
+        );
+    }
+
+    fn start_stream(
+        context: &mut UpdateContext<'gc>,
+        handle: NetConnectionHandle,
+        stream: NetStream<'gc>,
+        action: RtmpStreamAction,
+    ) {
+        let Some(connection) = context.net_connections.connections.get_mut(handle) else {
+            return;
+        };
+        let NetConnectionProtocol::Rtmp(rtmp) = &mut connection.protocol else {
+            return;
+        };
+
+        match connection.streams.iter_mut().find(|s| s.stream == stream) {
+            Some(entry) => {
+                entry.has_media = false;
+                entry.previous_tag_size = 0;
+                match (entry.stream_id, action) {
+                    (Some(stream_id), RtmpStreamAction::Play(name)) => {
+                        rtmp.play(stream_id, &name, (stream.buffer_time() * 1000.0) as u32)
+                    }
+                    (Some(stream_id), RtmpStreamAction::Publish(name, publish_type)) => {
+                        rtmp.publish(stream_id, &name, &publish_type)
+                    }
+                    (None, action) => entry.pending_action = Some(action),
+                }
+            }
+            None => {
+                let transaction_id = rtmp.create_stream();
+                connection.streams.push(RtmpStream {
+                    stream,
+                    stream_id: None,
+                    transaction_id: Some(transaction_id),
+                    pending_action: Some(action),
+                    previous_tag_size: 0,
+                    has_media: false,
+                });
+            }
+        }
+    }
+
+    /// Pause or resume an RTMP stream on the server.
+    pub fn pause_stream(
+        context: &mut UpdateContext<'gc>,
+        handle: NetConnectionHandle,
+        stream: NetStream<'gc>,
+        paused: bool,
+    ) {
+        if let Some((rtmp, stream_id)) = context.net_connections.rtmp_stream(handle, stream) {
+            rtmp.pause(stream_id, paused, stream.time());
+        }
+    }
+
+    /// Ask the server to continue an RTMP stream from a different time.
+    ///
+    /// `offset` is in milliseconds.
+    pub fn seek_stream(
+        context: &mut UpdateContext<'gc>,
+        handle: NetConnectionHandle,
+        stream: NetStream<'gc>,
+        offset: f64,
+    ) {
+        if let Some((rtmp, stream_id)) = context.net_connections.rtmp_stream(handle, stream) {
+            rtmp.seek(stream_id, offset);
+        }
+        if let Some(entry) = context
+            .net_connections
+            .connections
+            .get_mut(handle)
+            .and_then(|c| c.streams.iter_mut().find(|s| s.stream == stream))
+        {
+            entry.has_media = false;
+            entry.previous_tag_size = 0;
+        }
+    }
+
+    /// Stop an RTMP stream and release it on the server.
+    pub fn close_stream(
+        context: &mut UpdateContext<'gc>,
+        handle: NetConnectionHandle,
+        stream: NetStream<'gc>,
+    ) {
+        let Some(connection) = context.net_connections.connections.get_mut(handle) else {
+            return;
+        };
+        let Some(index) = connection.streams.iter().position(|s| s.stream == stream) else {
+            return;
+        };
+
+        let entry = connection.streams.remove(index);
+        if let (NetConnectionProtocol::Rtmp(rtmp), Some(stream_id)) =
+            (&mut connection.protocol, entry.stream_id)
+        {
+            rtmp.delete_stream(stream_id);
+        }
+    }
+
+    fn rtmp_stream(
+        &mut self,
+        handle: NetConnectionHandle,
+        stream: NetStream<'gc>,
+    ) -> Option<(&mut Rtmp, u32)> {
+        let connection = self.connections.get_mut(handle)?;
+        let stream_id = connection
+            .streams
+            .iter()
+            .find(|s| s.stream == stream)?
+            .stream_id?;
+        match &mut connection.protocol {
+            NetConnectionProtocol::Rtmp(rtmp) => Some((rtmp, stream_id)),
+            _ => None,
         }
     }
 
@@ -293,6 +802,13 @@ impl<'gc> NetConnections<'gc> {
         }
     }
 
+    pub fn is_rtmp(&self, handle: NetConnectionHandle) -> bool {
+        self.connections
+            .get(handle)
+            .map(|c| matches!(c.protocol, NetConnectionProtocol::Rtmp(_)))
+            .unwrap_or_default()
+    }
+
     pub fn is_connected(&self, handle: NetConnectionHandle) -> bool {
         self.connections
             .get(handle)
@@ -342,6 +858,39 @@ pub struct NetConnection<'gc> {
 
     #[collect(require_static)]
     protocol: NetConnectionProtocol,
+
+    /// The `NetStream`s playing or publishing over this connection.
+    streams: Vec<RtmpStream<'gc>>,
+}
+
+/// What a `NetStream` should do once the server has created its stream.
+#[derive(Debug)]
+enum RtmpStreamAction {
+    Play(String),
+    Publish(String, String),
+}
+
+/// A `NetStream` attached to an RTMP connection.
+#[derive(Collect)]
+#[collect(no_drop)]
+struct RtmpStream<'gc> {
+    stream: NetStream<'gc>,
+
+    /// The message stream ID assigned by the server.
+    stream_id: Option<u32>,
+
+    /// The `createStream` transaction that will provide `stream_id`.
+    transaction_id: Option<u32>,
+
+    /// The action to take once the stream is created.
+    #[collect(require_static)]
+    pending_action: Option<RtmpStreamAction>,
+
+    /// The size of the last FLV tag written to the stream's buffer.
+    previous_tag_size: u32,
+
+    /// Whether any media was received since the stream was last started.
+    has_media: bool,
 }
 
 impl<'gc> NetConnection<'gc> {
@@ -349,6 +898,7 @@ impl<'gc> NetConnection<'gc> {
         match self.protocol {
             NetConnectionProtocol::Local => true,
             NetConnectionProtocol::FlashRemoting(_) => false,
+            NetConnectionProtocol::Rtmp(ref rtmp) => rtmp.is_connected(),
         }
     }
 
@@ -356,6 +906,7 @@ impl<'gc> NetConnection<'gc> {
         match self.protocol {
             NetConnectionProtocol::Local => Some("none"),
             NetConnectionProtocol::FlashRemoting(_) => None,
+            NetConnectionProtocol::Rtmp(ref rtmp) => rtmp.is_connected().then_some("none"),
         }
     }
 
@@ -363,6 +914,7 @@ impl<'gc> NetConnection<'gc> {
         match self.protocol {
             NetConnectionProtocol::Local => Some(""),
             NetConnectionProtocol::FlashRemoting(_) => None,
+            NetConnectionProtocol::Rtmp(ref rtmp) => rtmp.is_connected().then_some(""),
         }
     }
 
@@ -372,6 +924,9 @@ impl<'gc> NetConnection<'gc> {
                 Some("0000000000000000000000000000000000000000000000000000000000000000")
             }
             NetConnectionProtocol::FlashRemoting(_) => None,
+            NetConnectionProtocol::Rtmp(ref rtmp) => rtmp
+                .is_connected()
+                .then_some("0000000000000000000000000000000000000000000000000000000000000000"),
         }
     }
 
@@ -379,6 +934,7 @@ impl<'gc> NetConnection<'gc> {
         match self.protocol {
             NetConnectionProtocol::Local => Some(""),
             NetConnectionProtocol::FlashRemoting(_) => None,
+            NetConnectionProtocol::Rtmp(ref rtmp) => rtmp.is_connected().then_some(""),
         }
     }
 
@@ -388,6 +944,9 @@ impl<'gc> NetConnection<'gc> {
                 Some("0000000000000000000000000000000000000000000000000000000000000000")
             }
             NetConnectionProtocol::FlashRemoting(_) => None,
+            NetConnectionProtocol::Rtmp(ref rtmp) => rtmp
+                .is_connected()
+                .then_some("0000000000000000000000000000000000000000000000000000000000000000"),
         }
     }
 
@@ -395,6 +954,7 @@ impl<'gc> NetConnection<'gc> {
         match self.protocol {
             NetConnectionProtocol::Local => Some("rtmp"),
             NetConnectionProtocol::FlashRemoting(_) => None,
+            NetConnectionProtocol::Rtmp(ref rtmp) => rtmp.is_connected().then_some("rtmp"),
         }
     }
 
@@ -402,6 +962,7 @@ impl<'gc> NetConnection<'gc> {
         match &self.protocol {
             NetConnectionProtocol::Local => Some("null".to_string()), // Yes, it's a string "null", not a real null.
             NetConnectionProtocol::FlashRemoting(remoting) => Some(remoting.url.to_string()),
+            NetConnectionProtocol::Rtmp(rtmp) => Some(rtmp.url().to_string()),
         }
     }
 
@@ -409,6 +970,7 @@ impl<'gc> NetConnection<'gc> {
         match &self.protocol {
             NetConnectionProtocol::Local => Some(false),
             NetConnectionProtocol::FlashRemoting(_) => None,
+            NetConnectionProtocol::Rtmp(rtmp) => rtmp.is_connected().then_some(false),
         }
     }
 
@@ -423,6 +985,7 @@ impl<'gc> NetConnection<'gc> {
             NetConnectionProtocol::FlashRemoting(remoting) => {
                 remoting.send(command, responder_handle, message)
             }
+            NetConnectionProtocol::Rtmp(rtmp) => rtmp.call(command, responder_handle, message),
         }
     }
 
@@ -439,6 +1002,8 @@ impl<'gc> NetConnection<'gc> {
                     navigator.spawn_future(remoting.flush_queue(self_handle, player));
                 }
             }
+            // RTMP connections are polled by `NetConnections::update_connections`.
+            NetConnectionProtocol::Rtmp(_) => {}
         }
     }
 
@@ -448,6 +1013,7 @@ impl<'gc> NetConnection<'gc> {
             NetConnectionProtocol::FlashRemoting(remoting) => {
                 remoting.set_header(header);
             }
+            NetConnectionProtocol::Rtmp(_) => {}
         }
     }
 }
@@ -459,6 +1025,9 @@ pub enum NetConnectionProtocol {
 
     /// Flash Remoting protocol, caused by connecting to a `http://` address.
     FlashRemoting(FlashRemoting),
+
+    /// A persistent RTMP connection, caused by connecting to a `rtmp://` address.
+    Rtmp(Rtmp),
 }
 
 #[derive(Debug)]
@@ -6,17 +6,24 @@ use crate::avm1::SystemProperties;
 use crate::avm1::VariableDumper;
 use crate::avm1::{Activation, ActivationIdentifier};
 use crate::avm1::{TObject, Value};
+use crate::avm2::debugger::Avm2Debugger;
+use crate::avm2::worker::{
+    self, SharedBackends, WorkerGroup, WorkerGroupHandle, WorkerId, PRIMORDIAL_WORKER,
+};
 use crate::avm2::{
-    object::TObject as _, Activation as Avm2Activation, Avm2, CallStack, Object as Avm2Object,
+    object::TObject as _, Activation as Avm2Activation, Avm2, CallStack,
+    EventObject as Avm2EventObject, Object as Avm2Object,
 };
 use crate::backend::ui::FontDefinition;
 use crate::backend::{
     audio::{AudioBackend, AudioManager},
+    capture::CaptureBackend,
     log::LogBackend,
     navigator::{NavigatorBackend, Request},
     storage::StorageBackend,
-    ui::{InputManager, MouseCursor, UiBackend},
+    ui::{CustomCursor, InputManager, MouseCursor, UiBackend},
 };
+use crate::capture::CaptureManager;
 use crate::compatibility_rules::CompatibilityRules;
 use crate::config::Letterbox;
 use crate::context::GcContext;
@@ -41,6 +48,7 @@ use crate::loader::{LoadBehavior, LoadManager};
 use crate::local_connection::LocalConnections;
 use crate::locale::get_current_date_time;
 use crate::net_connection::NetConnections;
+use crate::policy_file::PolicyFiles;
 use crate::prelude::*;
 use crate::socket::Sockets;
 use crate::streams::StreamManager;
@@ -60,6 +68,7 @@ use ruffle_render::transform::TransformStack;
 use ruffle_video::backend::VideoBackend;
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet, VecDeque};
+use std::net::SocketAddr;
 use std::ops::{Deref, DerefMut};
 use std::rc::{Rc, Weak as RcWeak};
 use std::str::FromStr;
@@ -112,6 +121,27 @@ pub struct MouseData<'gc> {
     pub pressed: Option<InteractiveObject<'gc>>,
     pub right_pressed: Option<InteractiveObject<'gc>>,
     pub middle_pressed: Option<InteractiveObject<'gc>>,
+
+    /// The cursor of the object that the mouse is over.
+    #[collect(require_static)]
+    pub cursor: MouseCursor,
+
+    /// The cursor chosen with `Mouse.cursor`, which is shown instead of `cursor`.
+    #[collect(require_static)]
+    pub cursor_override: Option<CursorOverride>,
+
+    /// The cursors registered with `Mouse.registerCursor`, by name.
+    #[collect(require_static)]
+    pub custom_cursors: HashMap<String, CustomCursor>,
+}
+
+/// A cursor chosen with `Mouse.cursor`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CursorOverride {
+    Builtin(MouseCursor),
+
+    /// A cursor registered with `Mouse.registerCursor`, by name.
+    Custom(String),
 }
 
 impl<'gc> MouseData<'gc> {
@@ -132,6 +162,39 @@ impl<'gc> MouseData<'gc> {
             MouseButton::Middle => self.middle_pressed = value,
         }
     }
+
+    /// Change the cursor of the object that the mouse is over.
+    pub fn set_cursor(&mut self, ui: &mut dyn UiBackend, cursor: MouseCursor) {
+        if cursor != self.cursor {
+            self.cursor = cursor;
+            if self.cursor_override.is_none() {
+                ui.set_mouse_cursor(cursor);
+            }
+        }
+    }
+
+    /// Change the cursor chosen with `Mouse.cursor`, with `None` going back to
+    /// the cursor of the object that the mouse is over.
+    pub fn set_cursor_override(
+        &mut self,
+        ui: &mut dyn UiBackend,
+        cursor_override: Option<CursorOverride>,
+    ) {
+        self.cursor_override = cursor_override;
+        match &self.cursor_override {
+            None => {
+                ui.set_custom_mouse_cursor(None);
+                ui.set_mouse_cursor(self.cursor);
+            }
+            Some(CursorOverride::Builtin(cursor)) => {
+                ui.set_custom_mouse_cursor(None);
+                ui.set_mouse_cursor(*cursor);
+            }
+            Some(CursorOverride::Custom(name)) => {
+                ui.set_custom_mouse_cursor(self.custom_cursors.get(name).cloned());
+            }
+        }
+    }
 }
 
 #[derive(Collect)]
@@ -184,6 +247,9 @@ struct GcRootData<'gc> {
     /// List of actively playing streams to decode.
     stream_manager: StreamManager<'gc>,
 
+    /// Microphones and cameras being captured from.
+    capture_manager: CaptureManager<'gc>,
+
     sockets: Sockets<'gc>,
 
     /// List of active NetConnection objects.
@@ -229,6 +295,7 @@ impl<'gc> GcRootData<'gc> {
         &mut ExternalInterface<'gc>,
         &mut AudioManager<'gc>,
         &mut StreamManager<'gc>,
+        &mut CaptureManager<'gc>,
         &mut Sockets<'gc>,
         &mut NetConnections<'gc>,
         &mut LocalConnections<'gc>,
@@ -253,6 +320,7 @@ impl<'gc> GcRootData<'gc> {
             &mut self.external_interface,
             &mut self.audio_manager,
             &mut self.stream_manager,
+            &mut self.capture_manager,
             &mut self.sockets,
             &mut self.net_connections,
             &mut self.local_connections,
@@ -266,6 +334,7 @@ impl<'gc> GcRootData<'gc> {
 type GcArena = gc_arena::Arena<Rootable![GcRoot<'_>]>;
 
 type Audio = Box<dyn AudioBackend>;
+type Capture = Box<dyn CaptureBackend>;
 type Navigator = Box<dyn NavigatorBackend>;
 type Renderer = Box<dyn RenderBackend>;
 type Storage = Box<dyn StorageBackend>;
@@ -288,7 +357,6 @@ pub struct Player {
 
     /// The runtime we're emulating (Flash Player or Adobe AIR).
     /// In Adobe AIR mode, additional classes are available
-    #[allow(unused)]
     player_runtime: PlayerRuntime,
 
     swf: Arc<SwfMovie>,
@@ -300,6 +368,7 @@ pub struct Player {
     audio: Audio,
     navigator: Navigator,
     storage: Storage,
+    capture: Capture,
     log: Log,
     ui: Ui,
     video: Video,
@@ -333,8 +402,6 @@ pub struct Player {
     mouse_in_stage: bool,
     mouse_position: Point<Twips>,
 
-    /// The current mouse cursor icon.
-    mouse_cursor: MouseCursor,
     mouse_cursor_needs_check: bool,
 
     system: SystemProperties,
@@ -361,6 +428,12 @@ pub struct Player {
     /// player lives across `await` calls in async code.
     self_reference: Weak<Mutex<Self>>,
 
+    /// The state shared with the other ActionScript workers of this SWF.
+    worker_group: WorkerGroupHandle,
+
+    /// The worker this player is running.
+    worker_id: WorkerId,
+
     /// The current frame of the main timeline, if available.
     /// The first frame is frame 1.
     current_frame: Option<u16>,
@@ -372,6 +445,12 @@ pub struct Player {
     /// the actual loaded url will be used
     spoofed_url: Option<String>,
 
+    /// The cross-domain policy files that grant this movie access to other domains.
+    policy_files: PolicyFiles,
+
+    /// The AVM2 debugger.
+    avm2_debugger: Avm2Debugger,
+
     /// Any compatibility rules to apply for this movie.
     compatibility_rules: CompatibilityRules,
 
@@ -494,6 +573,14 @@ impl Player {
 
     pub fn tick(&mut self, dt: f64) {
         if self.is_playing() {
+            let has_workers = self.worker_group.borrow().has_background_workers();
+            if has_workers {
+                self.update(|context| {
+                    worker::receive_shared_bytes(context);
+                    worker::dispatch_worker_events(context);
+                });
+            }
+
             self.frame_accumulator += dt;
             let frame_rate = self.frame_rate;
             let frame_time = 1000.0 / frame_rate;
@@ -548,10 +635,73 @@ impl Player {
             self.update_timers(dt);
             self.update(|context| {
                 StreamManager::tick(context, dt);
+                CaptureManager::tick(context, dt);
             });
             self.audio.tick();
+
+            if has_workers {
+                self.mutate_with_update_context(worker::publish_shared_bytes);
+                if self.worker_id == PRIMORDIAL_WORKER {
+                    self.tick_workers(dt);
+                }
+            }
         }
     }
+
+    /// Run the background workers of this SWF, spawning a player for each
+    /// worker that was started since the last tick.
+    ///
+    /// Only the primordial worker's player does this. Workers are scheduled
+    /// cooperatively, so each worker runs to the end of its tick before the
+    /// next one starts, unless it's blocked by another worker: it's then
+    /// parked, and continues its tick on a later turn.
+    ///
+    /// Once a worker is spawned, the log and navigator of this player are
+    /// shared with the players of all workers.
+    fn tick_workers(&mut self, dt: f64) {
+        let workers_to_spawn = self.worker_group.borrow_mut().workers_to_spawn();
+        if !workers_to_spawn.is_empty() && self.worker_group.borrow().shared_backends().is_none() {
+            // Background workers trace to and load through the backends of this player.
+            let log = std::mem::replace(&mut self.log, Box::new(log::NullLogBackend::new()));
+            let navigator = std::mem::replace(
+                &mut self.navigator,
+                Box::new(navigator::NullNavigatorBackend::new()),
+            );
+            let backends = SharedBackends::new(log, navigator);
+            self.log = Box::new(backends.log());
+            self.navigator = Box::new(backends.navigator());
+            self.worker_group.borrow_mut().set_shared_backends(backends);
+        }
+
+        for (id, movie) in workers_to_spawn {
+            let (log, navigator) = {
+                let group = self.worker_group.borrow();
+                let backends = group
+                    .shared_backends()
+                    .expect("Backends are shared before spawning workers");
+                (
+                    backends.log(),
+                    backends.worker_navigator(&self.worker_group, id),
+                )
+            };
+            let builder = PlayerBuilder::new()
+                .with_log(log)
+                .with_navigator(navigator)
+                .with_movie(movie)
+                .with_autoplay(true)
+                .with_player_version(Some(self.player_version))
+                .with_player_runtime(self.player_runtime)
+                .with_sandbox_type(self.system.sandbox_type)
+                .with_policy_files(self.policy_files.is_enabled())
+                .with_max_execution_duration(self.max_execution_duration)
+                .with_compatibility_rules(self.compatibility_rules.clone())
+                .with_worker(self.worker_group.clone(), id);
+            self.worker_group.borrow_mut().spawn_worker(id, builder);
+        }
+
+        worker::run_background_workers(&self.worker_group, dt);
+    }
+
     pub fn time_til_next_timer(&self) -> Option<f64> {
         self.time_til_next_timer
     }
@@ -732,6 +882,21 @@ impl Player {
                     ContextMenuCallback::TextControl { code, text } => {
                         text.text_control_input(*code, context)
                     }
+                    ContextMenuCallback::Clipboard { event } => {
+                        let event = *event;
+                        let target = context
+                            .focus_tracker
+                            .get()
+                            .map(|focus| focus.as_displayobject())
+                            .or_else(|| menu.get_display_object());
+                        if let Some(Avm2Value::Object(target)) = target.map(|obj| obj.object2()) {
+                            let is_paste = event == "paste";
+                            let event = Avm2EventObject::bare_default_event(context, event);
+                            context.avm2.dispatching_paste = is_paste;
+                            Avm2::dispatch_event(context, event, target);
+                            context.avm2.dispatching_paste = false;
+                        }
+                    }
                     _ => {}
                 }
                 Self::run_actions(context);
@@ -1488,13 +1653,14 @@ impl Player {
         is_mouse_moved: bool,
         player_event_handled: &mut bool,
     ) -> bool {
-        let mut new_cursor = self.mouse_cursor;
         let mut mouse_cursor_needs_check = self.mouse_cursor_needs_check;
         let mouse_in_stage = self.mouse_in_stage();
 
         // Determine the display object the mouse is hovering over.
         // Search through levels from top-to-bottom, returning the first display object that is under the mouse.
         let needs_render = self.mutate_with_update_context(|context| {
+            let mut new_cursor = context.mouse_data.cursor;
+
             // Objects may be hovered using Tab,
             // skip mouse hover when it's not necessary.
             let mut skip_mouse_hover = !is_mouse_moved
@@ -1789,14 +1955,12 @@ impl Player {
                 refresh
             };
             Self::run_actions(context);
+
+            // Update mouse cursor if it has changed.
+            context.mouse_data.set_cursor(context.ui, new_cursor);
             needs_render
         });
 
-        // Update mouse cursor if it has changed.
-        if new_cursor != self.mouse_cursor {
-            self.mouse_cursor = new_cursor;
-            self.ui.set_mouse_cursor(new_cursor)
-        }
         self.mouse_cursor_needs_check = mouse_cursor_needs_check;
 
         needs_render
@@ -1937,6 +2101,9 @@ impl Player {
         }
 
         self.update(|context| {
+            // Breakpoints may have been set while the movie wasn't running any code.
+            context.avm2_debugger.poll_commands();
+
             // TODO: Is this order correct?
             run_all_phases_avm2(context);
             Avm1::run_frame(context);
@@ -2051,6 +2218,14 @@ impl Player {
         &mut self.storage
     }
 
+    pub fn capture(&self) -> &Capture {
+        &self.capture
+    }
+
+    pub fn capture_mut(&mut self) -> &mut Capture {
+        &mut self.capture
+    }
+
     pub fn destroy(self) -> Renderer {
         self.renderer
     }
@@ -2183,6 +2358,7 @@ impl Player {
                 external_interface,
                 audio_manager,
                 stream_manager,
+                capture_manager,
                 sockets,
                 net_connections,
                 local_connections,
@@ -2209,11 +2385,14 @@ impl Player {
                 mouse_position: &this.mouse_position,
                 drag_object,
                 player: this.self_reference.clone(),
+                worker_group: &this.worker_group,
+                worker_id: this.worker_id,
                 load_manager,
                 system: &mut this.system,
                 page_url: &mut this.page_url,
                 instance_counter: &mut this.instance_counter,
                 storage: this.storage.deref_mut(),
+                capture: this.capture.deref_mut(),
                 log: this.log.deref_mut(),
                 video: this.video.deref_mut(),
                 avm1_shared_objects,
@@ -2237,7 +2416,10 @@ impl Player {
                 actions_since_timeout_check: &mut this.actions_since_timeout_check,
                 frame_phase: &mut this.frame_phase,
                 stub_tracker: &mut this.stub_tracker,
+                policy_files: &mut this.policy_files,
+                avm2_debugger: &mut this.avm2_debugger,
                 stream_manager,
+                capture_manager,
                 sockets,
                 net_connections,
                 local_connections,
@@ -2312,6 +2494,11 @@ impl Player {
         // GC
         self.gc_arena.borrow_mut().collect_debt();
 
+        // Record the collection of any objects tracked by `flash.sampler` as soon as it happens.
+        self.enter_arena_mut(|gc_context, gc_root, _| {
+            gc_root.avm2.sampler_mut().sweep(gc_context);
+        });
+
         rval
     }
 
@@ -2397,6 +2584,10 @@ impl Player {
         &self.compatibility_rules
     }
 
+    pub(crate) fn policy_files_mut(&mut self) -> &mut PolicyFiles {
+        &mut self.policy_files
+    }
+
     pub fn log_backend(&self) -> &Log {
         &self.log
     }
@@ -2433,12 +2624,22 @@ impl Player {
     }
 }
 
+impl Drop for Player {
+    fn drop(&mut self) {
+        // The players of background workers live on their own threads.
+        if self.worker_id == PRIMORDIAL_WORKER {
+            worker::stop_background_workers(&self.worker_group);
+        }
+    }
+}
+
 /// Player factory, which can be used to configure the aspects of a Ruffle player.
 pub struct PlayerBuilder {
     movie: Option<SwfMovie>,
 
     // Backends
     audio: Option<Audio>,
+    capture: Option<Capture>,
     log: Option<Log>,
     navigator: Option<Navigator>,
     renderer: Option<Renderer>,
@@ -2461,6 +2662,9 @@ pub struct PlayerBuilder {
     viewport_scale_factor: f64,
     load_behavior: LoadBehavior,
     spoofed_url: Option<String>,
+    policy_files: bool,
+    avm2_debug_server: Option<SocketAddr>,
+    flash_debugger: Option<SocketAddr>,
     compatibility_rules: CompatibilityRules,
     gamepad_button_mapping: HashMap<GamepadButton, KeyCode>,
     player_version: Option<u8>,
@@ -2474,6 +2678,7 @@ pub struct PlayerBuilder {
     #[cfg(feature = "known_stubs")]
     stub_report_output: Option<std::path::PathBuf>,
     avm2_optimizer_enabled: bool,
+    worker: Option<(WorkerGroupHandle, WorkerId)>,
 }
 
 impl PlayerBuilder {
@@ -2487,6 +2692,7 @@ impl PlayerBuilder {
             movie: None,
 
             audio: None,
+            capture: None,
             log: None,
             navigator: None,
             renderer: None,
@@ -2513,6 +2719,9 @@ impl PlayerBuilder {
             viewport_scale_factor: 1.0,
             load_behavior: LoadBehavior::Streaming,
             spoofed_url: None,
+            policy_files: false,
+            avm2_debug_server: None,
+            flash_debugger: None,
             compatibility_rules: CompatibilityRules::default(),
             gamepad_button_mapping: HashMap::new(),
             player_version: None,
@@ -2526,6 +2735,7 @@ impl PlayerBuilder {
             #[cfg(feature = "known_stubs")]
             stub_report_output: None,
             avm2_optimizer_enabled: true,
+            worker: None,
         }
     }
 
@@ -2550,6 +2760,13 @@ impl PlayerBuilder {
         self
     }
 
+    /// Sets the capture backend of the player, used for microphones and cameras.
+    #[inline]
+    pub fn with_capture(mut self, capture: impl 'static + CaptureBackend) -> Self {
+        self.capture = Some(Box::new(capture));
+        self
+    }
+
     /// Sets the logging backend of the player.
     #[inline]
     pub fn with_log(mut self, log: impl 'static + LogBackend) -> Self {
@@ -2674,6 +2891,36 @@ impl PlayerBuilder {
         self
     }
 
+    /// Sets whether cross-domain policy files are enforced (default is `false`).
+    ///
+    /// When enabled, movies that aren't trusted need a policy file to read data
+    /// from other domains, or to connect sockets.
+    pub fn with_policy_files(mut self, enabled: bool) -> Self {
+        self.policy_files = enabled;
+        self
+    }
+
+    /// Sets the address to listen on for AVM2 debugger clients.
+    ///
+    /// When set, the AVM2 debugger is enabled, and clients can set breakpoints
+    /// and step through the movie over a line-based TCP protocol. This isn't
+    /// supported on the web.
+    pub fn with_avm2_debug_server(mut self, addr: Option<SocketAddr>) -> Self {
+        self.avm2_debug_server = addr;
+        self
+    }
+
+    /// Sets the address of a Flash debugger to connect to, such as `fdb` or an
+    /// IDE, which usually listens on `127.0.0.1:7935`.
+    ///
+    /// When set, the player connects to the debugger when it's built, and the
+    /// AVM2 debugger is controlled by it. This takes precedence over
+    /// [`Self::with_avm2_debug_server`], and isn't supported on the web.
+    pub fn with_flash_debugger(mut self, addr: Option<SocketAddr>) -> Self {
+        self.flash_debugger = addr;
+        self
+    }
+
     /// Sets the compatibility rules to use with this movie.
     pub fn with_compatibility_rules(mut self, compatibility_rules: CompatibilityRules) -> Self {
         self.compatibility_rules = compatibility_rules;
@@ -2740,6 +2987,45 @@ impl PlayerBuilder {
         self
     }
 
+    /// Runs a background worker of another player's SWF, rather than the
+    /// primordial worker of a new SWF.
+    pub(crate) fn with_worker(mut self, group: WorkerGroupHandle, id: WorkerId) -> Self {
+        self.worker = Some((group, id));
+        self
+    }
+
+    fn create_avm2_debugger(
+        debug_server: Option<SocketAddr>,
+        flash_debugger: Option<SocketAddr>,
+    ) -> Avm2Debugger {
+        #[cfg(not(target_family = "wasm"))]
+        if let Some(addr) = flash_debugger {
+            use crate::avm2::debugger::fdb::FdbConnection;
+
+            if debug_server.is_some() {
+                tracing::warn!("Using the Flash debugger instead of the AVM2 debug server");
+            }
+            match FdbConnection::connect(addr) {
+                Ok(connection) => return Avm2Debugger::new(Some(Box::new(connection))),
+                Err(e) => tracing::error!("Couldn't connect to Flash debugger at {addr}: {e}"),
+            }
+        } else if let Some(addr) = debug_server {
+            use crate::avm2::debugger::server::DebugServer;
+
+            match DebugServer::bind(addr) {
+                Ok(server) => return Avm2Debugger::new(Some(Box::new(server))),
+                Err(e) => tracing::error!("Couldn't start AVM2 debugger on {addr}: {e}"),
+            }
+        }
+
+        #[cfg(target_family = "wasm")]
+        if debug_server.is_some() || flash_debugger.is_some() {
+            tracing::warn!("AVM2 debuggers aren't supported on this platform");
+        }
+
+        Avm2Debugger::new(None)
+    }
+
     fn create_gc_root<'gc>(
         gc_context: &'gc Mutation<'gc>,
         player_version: u8,
@@ -2774,6 +3060,9 @@ impl PlayerBuilder {
                 pressed: None,
                 right_pressed: None,
                 middle_pressed: None,
+                cursor: MouseCursor::Arrow,
+                cursor_override: None,
+                custom_cursors: HashMap::new(),
             },
             avm1_shared_objects: HashMap::new(),
             avm2_shared_objects: HashMap::new(),
@@ -2781,6 +3070,7 @@ impl PlayerBuilder {
             timers: Timers::new(),
             unbound_text_fields: Vec::new(),
             stream_manager: StreamManager::new(),
+            capture_manager: CaptureManager::new(),
             sockets: Sockets::empty(),
             net_connections: NetConnections::default(),
             local_connections: LocalConnections::empty(),
@@ -2801,6 +3091,9 @@ impl PlayerBuilder {
         let audio = self
             .audio
             .unwrap_or_else(|| Box::new(audio::NullAudioBackend::new()));
+        let capture = self
+            .capture
+            .unwrap_or_else(|| Box::new(capture::NullCaptureBackend::new()));
         let log = self
             .log
             .unwrap_or_else(|| Box::new(log::NullLogBackend::new()));
@@ -2825,6 +3118,9 @@ impl PlayerBuilder {
             .unwrap_or_else(|| Box::new(null::NullVideoBackend::new()));
 
         let player_version = self.player_version.unwrap_or(NEWEST_PLAYER_VERSION);
+        let (worker_group, worker_id) = self
+            .worker
+            .unwrap_or_else(|| (WorkerGroup::new_handle(), PRIMORDIAL_WORKER));
 
         // Instantiate the player.
         let fake_movie = Arc::new(SwfMovie::empty(player_version));
@@ -2838,6 +3134,7 @@ impl PlayerBuilder {
                 navigator,
                 renderer,
                 storage,
+                capture,
                 ui,
                 video,
 
@@ -2861,7 +3158,6 @@ impl PlayerBuilder {
                 input: Default::default(),
                 mouse_in_stage: true,
                 mouse_position: Point::ZERO,
-                mouse_cursor: MouseCursor::Arrow,
                 mouse_cursor_needs_check: false,
 
                 // Misc. state
@@ -2875,8 +3171,15 @@ impl PlayerBuilder {
                 is_playing: self.autoplay,
                 needs_render: true,
                 self_reference: self_ref.clone(),
+                worker_group,
+                worker_id,
                 load_behavior: self.load_behavior,
                 spoofed_url: self.spoofed_url.clone(),
+                policy_files: PolicyFiles::new(self.policy_files),
+                avm2_debugger: Self::create_avm2_debugger(
+                    self.avm2_debug_server,
+                    self.flash_debugger,
+                ),
                 compatibility_rules: self.compatibility_rules.clone(),
                 gamepad_button_mapping: self.gamepad_button_mapping,
                 stub_tracker: StubCollection::new(),
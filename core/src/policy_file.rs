@@ -0,0 +1,734 @@
+//! Cross-domain policy files
+//!
+//! Flash Player only lets a movie read data from its own domain, unless the
+//! server hosting the data grants access with a policy file: a
+//! `crossdomain.xml` file for HTTP, or a policy served over a socket for
+//! `Socket` and `XMLSocket` connections.
+//!
+//! Enforcing policy files is opt-in, see `PlayerBuilder::with_policy_files`.
+
+use crate::avm1::globals::system::SandboxType;
+use crate::backend::navigator::Request;
+use crate::context::UpdateContext;
+use crate::display_object::{DisplayObject, TDisplayObject, TDisplayObjectContainer};
+use crate::player::Player;
+use crate::socket::{ConnectionState, SocketAction, SocketHandle};
+use async_channel::{unbounded, Sender};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::ops::RangeInclusive;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use thiserror::Error;
+use url::Url;
+
+/// The port on which a server provides its master socket policy file.
+const MASTER_SOCKET_POLICY_PORT: u16 = 843;
+
+/// The message sent to a socket to ask for its policy file.
+const SOCKET_POLICY_REQUEST: &[u8] = b"<policy-file-request/>\0";
+
+/// How long to wait for a socket policy file before giving up on it.
+const SOCKET_POLICY_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// A pending check of whether a movie may access some content.
+///
+/// Resolves to `true` if a policy file grants access.
+pub type AccessCheck = Pin<Box<dyn Future<Output = bool> + 'static>>;
+
+#[derive(Debug, Error)]
+pub enum PolicyFileError {
+    #[error("Invalid XML: {0}")]
+    InvalidXml(#[from] quick_xml::Error),
+
+    #[error("The root element is not <cross-domain-policy>")]
+    InvalidRoot,
+}
+
+/// The meta-policy of a server, which decides which of its policy files
+/// besides the master policy file may be used.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MetaPolicy {
+    /// No policy file may be used, not even the master policy file.
+    None,
+
+    /// Only the master policy file may be used.
+    MasterOnly,
+
+    /// Only policy files served with the `text/x-cross-domain-policy`
+    /// content type may be used.
+    ByContentType,
+
+    /// Only policy files named `crossdomain.xml` may be used (FTP only).
+    ByFtpFilename,
+
+    /// Any policy file may be used.
+    All,
+}
+
+impl MetaPolicy {
+    fn from_attribute(value: &str) -> Option<Self> {
+        match value {
+            "none" => Some(Self::None),
+            "master-only" => Some(Self::MasterOnly),
+            "by-content-type" => Some(Self::ByContentType),
+            "by-ftp-filename" => Some(Self::ByFtpFilename),
+            "all" => Some(Self::All),
+            _ => None,
+        }
+    }
+
+    /// Whether policy files other than the master policy file may be used.
+    fn allows_other_policies(self) -> bool {
+        // Navigator backends don't expose the content type of responses, so
+        // `by-content-type` can't be verified and is treated like `all`.
+        matches!(self, Self::All | Self::ByContentType)
+    }
+}
+
+/// An `<allow-access-from>` entry of a policy file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct AllowAccessFrom {
+    /// The domain that is granted access: either a host name, a wildcard
+    /// like `*.example.com`, or `*` for any domain.
+    domain: String,
+
+    /// The ports that may be connected to, for socket policy files.
+    to_ports: Vec<RangeInclusive<u16>>,
+
+    /// Whether only movies loaded over HTTPS are granted access, if the
+    /// policy file itself was loaded over HTTPS.
+    secure: bool,
+}
+
+impl AllowAccessFrom {
+    fn from_element(element: &BytesStart) -> Result<Self, PolicyFileError> {
+        let mut entry = Self {
+            domain: String::new(),
+            to_ports: Vec::new(),
+            secure: true,
+        };
+        for attribute in element.attributes() {
+            let attribute = attribute.map_err(quick_xml::Error::from)?;
+            let value = attribute.unescape_value()?;
+            match attribute.key.as_ref() {
+                b"domain" => entry.domain = value.trim().to_ascii_lowercase(),
+                b"to-ports" => entry.to_ports = parse_ports(&value),
+                b"secure" => entry.secure = value.trim() != "false",
+                _ => {}
+            }
+        }
+        Ok(entry)
+    }
+
+    fn matches_domain(&self, source: &Url) -> bool {
+        if self.domain == "*" {
+            return true;
+        }
+
+        // Movies that weren't loaded from a server only match `*`.
+        let Some(host) = source.host_str().filter(|_| source.scheme() != "file") else {
+            return false;
+        };
+        match self.domain.strip_prefix("*.") {
+            Some(suffix) => {
+                host == suffix
+                    || host
+                        .strip_suffix(suffix)
+                        .is_some_and(|subdomain| subdomain.ends_with('.'))
+            }
+            None => host == self.domain,
+        }
+    }
+
+    fn matches_port(&self, port: u16) -> bool {
+        self.to_ports.iter().any(|ports| ports.contains(&port))
+    }
+}
+
+/// Parse the `to-ports` attribute of an `<allow-access-from>` entry.
+///
+/// This is a comma-separated list of ports and port ranges, or `*` for any
+/// port. Invalid entries are ignored.
+fn parse_ports(value: &str) -> Vec<RangeInclusive<u16>> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter_map(|ports| {
+            if ports == "*" {
+                return Some(0..=u16::MAX);
+            }
+            match ports.split_once('-') {
+                Some((start, end)) => Some(start.trim().parse().ok()?..=end.trim().parse().ok()?),
+                None => {
+                    let port = ports.parse().ok()?;
+                    Some(port..=port)
+                }
+            }
+        })
+        .collect()
+}
+
+/// A parsed cross-domain policy file.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PolicyFile {
+    /// The meta-policy given by `<site-control>`, which only matters in a
+    /// master policy file.
+    meta_policy: Option<MetaPolicy>,
+
+    allow_access_from: Vec<AllowAccessFrom>,
+}
+
+impl PolicyFile {
+    pub fn parse(data: &[u8]) -> Result<Self, PolicyFileError> {
+        // Socket policy files are terminated by a null byte.
+        let data = data.split(|&b| b == 0).next().unwrap_or_default();
+
+        let mut reader = Reader::from_reader(data);
+        let mut policy = Self::default();
+        let mut has_root = false;
+        let mut depth = 0;
+        loop {
+            let (element, is_empty) = match reader.read_event()? {
+                Event::Start(element) => (element, false),
+                Event::Empty(element) => (element, true),
+                Event::End(_) => {
+                    depth -= 1;
+                    continue;
+                }
+                Event::Eof => break,
+                _ => continue,
+            };
+
+            if depth == 0 {
+                if element.name().as_ref() != b"cross-domain-policy" {
+                    return Err(PolicyFileError::InvalidRoot);
+                }
+                has_root = true;
+            }
+
+            // Only the direct children of the root element are meaningful.
+            if depth == 1 {
+                match element.name().as_ref() {
+                    b"allow-access-from" => policy
+                        .allow_access_from
+                        .push(AllowAccessFrom::from_element(&element)?),
+                    b"site-control" => {
+                        if let Some(attribute) =
+                            element.try_get_attribute("permitted-cross-domain-policies")?
+                        {
+                            let value = attribute.unescape_value()?;
+                            policy.meta_policy = MetaPolicy::from_attribute(value.trim());
+                        }
+                    }
+                    _ => {}
+                }
+            }
+
+            if !is_empty {
+                depth += 1;
+            }
+        }
+
+        if !has_root {
+            return Err(PolicyFileError::InvalidRoot);
+        }
+        Ok(policy)
+    }
+
+    /// The meta-policy of the server, if this is its master policy file.
+    pub fn meta_policy(&self) -> Option<MetaPolicy> {
+        self.meta_policy
+    }
+
+    /// Whether this policy file grants a movie loaded from `source` access to
+    /// data on its server.
+    ///
+    /// `secure_policy` is whether the policy file was loaded over HTTPS.
+    pub fn allows_url_access(&self, source: &Url, secure_policy: bool) -> bool {
+        self.allow_access_from.iter().any(|entry| {
+            entry.matches_domain(source)
+                && (!secure_policy || !entry.secure || source.scheme() == "https")
+        })
+    }
+
+    /// Whether this socket policy file grants a movie loaded from `source`
+    /// access to `port` on its server.
+    pub fn allows_socket_access(&self, source: &Url, port: u16) -> bool {
+        self.allow_access_from
+            .iter()
+            .any(|entry| entry.matches_domain(source) && entry.matches_port(port))
+    }
+}
+
+/// Where a policy file is loaded from.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum PolicySource {
+    /// A policy file fetched over HTTP.
+    Url(Url),
+
+    /// A policy file served over a socket.
+    Socket { host: String, port: u16 },
+}
+
+enum PolicyState {
+    /// The policy file is being loaded. The senders are notified once it has
+    /// been loaded.
+    Loading(Vec<Sender<Option<Arc<PolicyFile>>>>),
+
+    /// The policy file has been loaded, or couldn't be loaded (`None`).
+    Loaded(Option<Arc<PolicyFile>>),
+}
+
+/// The cross-domain policy files known to a player.
+pub struct PolicyFiles {
+    /// Whether policy files are enforced at all.
+    enabled: bool,
+
+    /// The policy files that have been loaded, or are being loaded.
+    policies: HashMap<PolicySource, PolicyState>,
+
+    /// Policy files registered with `Security.loadPolicyFile`, in the order in
+    /// which they were registered.
+    registered: Vec<PolicySource>,
+
+    /// URLs of content from other domains that a policy file allowed the root
+    /// movie to read, e.g. with `BitmapData.draw`.
+    readable_content: HashSet<String>,
+}
+
+impl PolicyFiles {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            policies: HashMap::new(),
+            registered: Vec::new(),
+            readable_content: HashSet::new(),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Register a policy file to be consulted in addition to the master policy
+    /// file of a server, as done by `Security.loadPolicyFile`.
+    ///
+    /// Socket policy files are given with `xmlsocket://host:port` URLs.
+    pub fn load_policy_file(&mut self, url: Url) {
+        let source = match url.scheme() {
+            "http" | "https" => PolicySource::Url(url),
+            "xmlsocket" => match (url.host_str(), url.port()) {
+                (Some(host), Some(port)) => PolicySource::Socket {
+                    host: host.to_owned(),
+                    port,
+                },
+                _ => {
+                    tracing::warn!("Invalid socket policy file URL: {url}");
+                    return;
+                }
+            },
+            _ => {
+                tracing::warn!("Unsupported policy file URL: {url}");
+                return;
+            }
+        };
+        if !self.registered.contains(&source) {
+            self.registered.push(source);
+        }
+    }
+
+    /// The registered HTTP policy files that may apply to `target`.
+    ///
+    /// A policy file only applies to URLs within its own directory.
+    fn registered_url_policies(&self, target: &Url) -> Vec<Url> {
+        self.registered
+            .iter()
+            .filter_map(|source| match source {
+                PolicySource::Url(url) => Some(url),
+                PolicySource::Socket { .. } => None,
+            })
+            .filter(|url| {
+                let directory = &url.path()[..url.path().rfind('/').map_or(0, |i| i + 1)];
+                url.origin() == target.origin() && target.path().starts_with(directory)
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// The ports of the registered socket policy files of `host`.
+    fn registered_socket_policies(&self, host: &str) -> Vec<u16> {
+        self.registered
+            .iter()
+            .filter_map(|source| match source {
+                PolicySource::Socket {
+                    host: policy_host,
+                    port,
+                } if policy_host == host => Some(*port),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Whether the root movie is exempt from policy files, either because they
+    /// aren't enforced or because the movie is trusted.
+    fn is_exempt(context: &UpdateContext<'_>) -> bool {
+        !context.policy_files.enabled || context.system.sandbox_type == SandboxType::LocalTrusted
+    }
+
+    /// Prepare a check of whether the root movie may read data from `url`.
+    ///
+    /// Returns `None` if no policy file is needed to read the data.
+    pub fn url_access_check(context: &mut UpdateContext<'_>, url: &str) -> Option<AccessCheck> {
+        if Self::is_exempt(context) {
+            return None;
+        }
+
+        let source = Url::parse(context.swf.url()).ok()?;
+        let target = context.navigator.resolve_url(url).ok()?;
+        if !matches!(target.scheme(), "http" | "https") || source.origin() == target.origin() {
+            return None;
+        }
+
+        let player = context.player.upgrade()?;
+        Some(Box::pin(async move {
+            Self::has_url_access(&player, &source, &target).await
+        }))
+    }
+
+    /// Prepare a check of whether the root movie may read the pixels of
+    /// content loaded from `url`, as done when loading it with
+    /// `LoaderContext.checkPolicyFile`.
+    ///
+    /// If access is granted, it is recorded so that `BitmapData.draw` can use
+    /// the content. Returns `None` if no policy file is needed.
+    pub fn content_access_check(context: &mut UpdateContext<'_>, url: &str) -> Option<AccessCheck> {
+        let check = Self::url_access_check(context, url)?;
+        let player = context.player.clone();
+        let url = context.navigator.resolve_url(url).ok()?.to_string();
+        Some(Box::pin(async move {
+            let allowed = check.await;
+            if allowed {
+                if let Some(player) = player.upgrade() {
+                    let mut player = player.lock().unwrap();
+                    player.policy_files_mut().readable_content.insert(url);
+                }
+            }
+            allowed
+        }))
+    }
+
+    /// Prepare a check of whether the root movie may connect a socket to
+    /// `host` on `port`.
+    ///
+    /// Unlike HTTP requests, socket connections need a policy file even when
+    /// connecting to the domain of the movie. Returns `None` if no policy file
+    /// is needed.
+    pub fn socket_access_check(
+        context: &mut UpdateContext<'_>,
+        host: &str,
+        port: u16,
+    ) -> Option<AccessCheck> {
+        if Self::is_exempt(context) {
+            return None;
+        }
+
+        let source = Url::parse(context.swf.url()).ok()?;
+        let player = context.player.upgrade()?;
+        let host = host.to_owned();
+        Some(Box::pin(async move {
+            Self::has_socket_access(&player, &source, &host, port).await
+        }))
+    }
+
+    /// Find content within `object` whose pixels the root movie may not read.
+    ///
+    /// Returns the URL of the content, if any.
+    pub fn find_unreadable_content<'gc>(
+        context: &UpdateContext<'gc>,
+        object: DisplayObject<'gc>,
+    ) -> Option<String> {
+        if Self::is_exempt(context) {
+            return None;
+        }
+        let source = Url::parse(context.swf.url()).ok()?;
+        context
+            .policy_files
+            .find_unreadable_content_in(&source, object)
+    }
+
+    fn find_unreadable_content_in(
+        &self,
+        source: &Url,
+        object: DisplayObject<'_>,
+    ) -> Option<String> {
+        let movie = object.movie();
+        let readable = match Url::parse(movie.url()) {
+            Ok(url) if matches!(url.scheme(), "http" | "https") => {
+                url.origin() == source.origin() || self.readable_content.contains(movie.url())
+            }
+            _ => true,
+        };
+        if !readable {
+            return Some(movie.url().to_owned());
+        }
+
+        object.as_container().and_then(|container| {
+            container
+                .iter_render_list()
+                .find_map(|child| self.find_unreadable_content_in(source, child))
+        })
+    }
+
+    async fn has_url_access(player: &Arc<Mutex<Player>>, source: &Url, target: &Url) -> bool {
+        let Ok(master_url) = target.join("/crossdomain.xml") else {
+            return false;
+        };
+        let master = Self::policy(player, PolicySource::Url(master_url)).await;
+
+        // Without a master policy file, only the master policy file may be used.
+        let meta_policy = master
+            .as_ref()
+            .and_then(|master| master.meta_policy())
+            .unwrap_or(MetaPolicy::MasterOnly);
+        if meta_policy == MetaPolicy::None {
+            return false;
+        }
+
+        let secure = target.scheme() == "https";
+        if master.is_some_and(|master| master.allows_url_access(source, secure)) {
+            return true;
+        }
+        if !meta_policy.allows_other_policies() {
+            return false;
+        }
+
+        let urls = player
+            .lock()
+            .unwrap()
+            .policy_files_mut()
+            .registered_url_policies(target);
+        for url in urls {
+            let secure = url.scheme() == "https";
+            let policy = Self::policy(player, PolicySource::Url(url)).await;
+            if policy.is_some_and(|policy| policy.allows_url_access(source, secure)) {
+                return true;
+            }
+        }
+        false
+    }
+
+    async fn has_socket_access(
+        player: &Arc<Mutex<Player>>,
+        source: &Url,
+        host: &str,
+        port: u16,
+    ) -> bool {
+        let master = Self::policy(
+            player,
+            PolicySource::Socket {
+                host: host.to_owned(),
+                port: MASTER_SOCKET_POLICY_PORT,
+            },
+        )
+        .await;
+
+        // Without a master socket policy file, any socket policy file may be used.
+        let meta_policy = master
+            .as_ref()
+            .and_then(|master| master.meta_policy())
+            .unwrap_or(MetaPolicy::All);
+        if meta_policy == MetaPolicy::None {
+            return false;
+        }
+
+        if master.is_some_and(|master| master.allows_socket_access(source, port)) {
+            return true;
+        }
+        if !meta_policy.allows_other_policies() {
+            return false;
+        }
+
+        // Registered policy files are tried first, then the port being connected to.
+        let mut policy_ports = player
+            .lock()
+            .unwrap()
+            .policy_files_mut()
+            .registered_socket_policies(host);
+        policy_ports.retain(|&policy_port| policy_port != MASTER_SOCKET_POLICY_PORT);
+        if !policy_ports.contains(&port) {
+            policy_ports.push(port);
+        }
+        for policy_port in policy_ports {
+            // Policy files served on unprivileged ports can't grant access to privileged ports.
+            if policy_port >= 1024 && port < 1024 {
+                continue;
+            }
+            let policy = Self::policy(
+                player,
+                PolicySource::Socket {
+                    host: host.to_owned(),
+                    port: policy_port,
+                },
+            )
+            .await;
+            if policy.is_some_and(|policy| policy.allows_socket_access(source, port)) {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Get a policy file, loading it if it hasn't been loaded yet.
+    async fn policy(player: &Arc<Mutex<Player>>, source: PolicySource) -> Option<Arc<PolicyFile>> {
+        let waiter = {
+            let mut player = player.lock().unwrap();
+            let policies = &mut player.policy_files_mut().policies;
+            match policies.get_mut(&source) {
+                Some(PolicyState::Loaded(policy)) => return policy.clone(),
+                Some(PolicyState::Loading(waiters)) => {
+                    let (sender, receiver) = unbounded();
+                    waiters.push(sender);
+                    Some(receiver)
+                }
+                None => {
+                    policies.insert(source.clone(), PolicyState::Loading(Vec::new()));
+                    None
+                }
+            }
+        };
+        if let Some(receiver) = waiter {
+            return receiver.recv().await.ok().flatten();
+        }
+
+        let data = match &source {
+            PolicySource::Url(url) => Self::fetch_url_policy(player, url).await,
+            PolicySource::Socket { host, port } => {
+                Self::fetch_socket_policy(player, host, *port).await
+            }
+        };
+        let policy = data.and_then(|data| match PolicyFile::parse(&data) {
+            Ok(policy) => Some(Arc::new(policy)),
+            Err(e) => {
+                tracing::warn!("Ignoring invalid policy file from {source:?}: {e}");
+                None
+            }
+        });
+
+        let mut player = player.lock().unwrap();
+        let policies = &mut player.policy_files_mut().policies;
+        if let Some(PolicyState::Loading(waiters)) =
+            policies.insert(source, PolicyState::Loaded(policy.clone()))
+        {
+            for waiter in waiters {
+                let _ = waiter.try_send(policy.clone());
+            }
+        }
+        policy
+    }
+
+    async fn fetch_url_policy(player: &Arc<Mutex<Player>>, url: &Url) -> Option<Vec<u8>> {
+        let fetch = player
+            .lock()
+            .unwrap()
+            .navigator()
+            .fetch(Request::get(url.to_string()));
+        let response = fetch.await.ok()?;
+        response.body().await.ok()
+    }
+
+    async fn fetch_socket_policy(
+        player: &Arc<Mutex<Player>>,
+        host: &str,
+        port: u16,
+    ) -> Option<Vec<u8>> {
+        let (data_sender, data_receiver) = unbounded();
+        let (action_sender, action_receiver) = unbounded();
+        player.lock().unwrap().navigator_mut().connect_socket(
+            host.to_owned(),
+            port,
+            SOCKET_POLICY_TIMEOUT,
+            SocketHandle::default(),
+            data_receiver,
+            action_sender,
+        );
+
+        let mut data = Vec::new();
+        while let Ok(action) = action_receiver.recv().await {
+            match action {
+                SocketAction::Connect(_, ConnectionState::Connected) => {
+                    let _ = data_sender.try_send(SOCKET_POLICY_REQUEST.to_vec());
+                }
+                SocketAction::Connect(..) | SocketAction::Close(_) => break,
+                SocketAction::Data(_, chunk) => {
+                    data.extend(chunk);
+                    if data.contains(&0) {
+                        break;
+                    }
+                }
+            }
+        }
+        // NOTE: Dropping the sender closes the connection.
+        drop(data_sender);
+
+        (!data.is_empty()).then_some(data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn url(url: &str) -> Url {
+        Url::parse(url).unwrap()
+    }
+
+    #[test]
+    fn parse_policy_file() {
+        let policy = PolicyFile::parse(
+            br#"<?xml version="1.0"?>
+<!DOCTYPE cross-domain-policy SYSTEM "http://www.adobe.com/xml/dtds/cross-domain-policy.dtd">
+<cross-domain-policy>
+    <site-control permitted-cross-domain-policies="all"/>
+    <allow-access-from domain="*.example.com" />
+    <allow-access-from domain="ruffle.rs" to-ports="80,8000-8100" secure="false"/>
+</cross-domain-policy>"#,
+        )
+        .unwrap();
+
+        assert_eq!(policy.meta_policy(), Some(MetaPolicy::All));
+        assert!(policy.allows_url_access(&url("http://example.com/a.swf"), false));
+        assert!(policy.allows_url_access(&url("http://www.example.com/a.swf"), false));
+        assert!(!policy.allows_url_access(&url("http://badexample.com/a.swf"), false));
+        assert!(!policy.allows_url_access(&url("file:///a.swf"), false));
+
+        // Secure policy files only grant access to movies loaded over HTTPS.
+        assert!(!policy.allows_url_access(&url("http://example.com/a.swf"), true));
+        assert!(policy.allows_url_access(&url("https://example.com/a.swf"), true));
+        assert!(policy.allows_url_access(&url("http://ruffle.rs/a.swf"), true));
+
+        assert!(policy.allows_socket_access(&url("http://ruffle.rs/a.swf"), 8050));
+        assert!(!policy.allows_socket_access(&url("http://ruffle.rs/a.swf"), 443));
+        assert!(!policy.allows_socket_access(&url("http://example.com/a.swf"), 80));
+    }
+
+    #[test]
+    fn parse_socket_policy_file() {
+        let policy = PolicyFile::parse(
+            b"<cross-domain-policy><allow-access-from domain=\"*\" to-ports=\"*\"/></cross-domain-policy>\0",
+        )
+        .unwrap();
+
+        assert_eq!(policy.meta_policy(), None);
+        assert!(policy.allows_socket_access(&url("file:///a.swf"), 8001));
+    }
+
+    #[test]
+    fn parse_invalid_policy_file() {
+        assert!(PolicyFile::parse(b"<html><body>Not found</body></html>").is_err());
+        assert!(PolicyFile::parse(b"").is_err());
+    }
+}
@@ -0,0 +1,106 @@
+//! Rendering of print job pages, shared by the AVM1 and AVM2 `PrintJob` classes.
+
+use crate::backend::ui::PrintPage;
+use crate::bitmap::bitmap_data::{
+    BitmapData, BitmapDataDrawError, BitmapDataWrapper, IBitmapDrawable,
+};
+use crate::bitmap::operations;
+use crate::context::UpdateContext;
+use crate::display_object::{DisplayObject, TDisplayObject};
+use gc_arena::GcCell;
+use ruffle_render::backend::RenderBackend;
+use ruffle_render::matrix::Matrix;
+use ruffle_render::transform::Transform;
+use swf::{BlendMode, Rectangle, Twips};
+
+/// The size of the paper used for every print job, in points.
+/// This is US Letter, which is what Flash Player reports without a printer configured.
+pub const PAPER_WIDTH: f64 = 612.0;
+pub const PAPER_HEIGHT: f64 = 792.0;
+
+/// The unprintable margin on each side of the paper, in points.
+pub const PAGE_MARGIN: f64 = 18.0;
+
+/// The size of the printable area of the paper, in points.
+pub const PAGE_WIDTH: f64 = PAPER_WIDTH - 2.0 * PAGE_MARGIN;
+pub const PAGE_HEIGHT: f64 = PAPER_HEIGHT - 2.0 * PAGE_MARGIN;
+
+/// How many pixels are rendered for each point on the page.
+///
+/// Content is printed at one point per pixel, like in Flash Player,
+/// but is rendered at a higher resolution so that it stays sharp on paper.
+const PRINT_SCALE: f64 = 2.0;
+
+/// Render an area of a display object onto a new page.
+///
+/// `print_area` is in the coordinate space of `target`, and defaults to its bounds.
+/// Anything outside of the printable area of the paper is cut off.
+///
+/// Returns `None` if there is nothing to print.
+pub fn render_page<'gc>(
+    context: &mut UpdateContext<'gc>,
+    target: DisplayObject<'gc>,
+    print_area: Option<Rectangle<Twips>>,
+) -> Option<BitmapDataWrapper<'gc>> {
+    let area = print_area.unwrap_or_else(|| target.bounds());
+    if !area.is_valid() {
+        return None;
+    }
+
+    let width = area.width().to_pixels().min(PAGE_WIDTH);
+    let height = area.height().to_pixels().min(PAGE_HEIGHT);
+    let image_width = (width * PRINT_SCALE).ceil() as u32;
+    let image_height = (height * PRINT_SCALE).ceil() as u32;
+    if image_width == 0 || image_height == 0 {
+        return None;
+    }
+
+    let page = BitmapDataWrapper::new(GcCell::new(
+        context.gc_context,
+        BitmapData::new(image_width, image_height, false, 0xFFFFFFFF),
+    ));
+    let transform = Transform {
+        matrix: Matrix::scale(PRINT_SCALE as f32, PRINT_SCALE as f32)
+            * Matrix::translate(-area.x_min, -area.y_min),
+        ..Default::default()
+    };
+    let quality = context.stage.quality();
+    if let Err(BitmapDataDrawError::Unimplemented) = operations::draw(
+        context,
+        page,
+        IBitmapDrawable::DisplayObject(target),
+        transform,
+        true,
+        BlendMode::Normal,
+        None,
+        quality,
+    ) {
+        tracing::warn!("Render backend does not support printing, page will be blank");
+    }
+
+    Some(page)
+}
+
+/// Convert a page rendered by `render_page` into the form passed to the UI backend.
+pub fn to_print_page(renderer: &mut dyn RenderBackend, page: BitmapDataWrapper) -> PrintPage {
+    let (image_width, image_height, rgba) = operations::get_pixels_as_rgba(
+        renderer,
+        page,
+        0,
+        0,
+        page.width() as i32,
+        page.height() as i32,
+    );
+
+    PrintPage {
+        paper_width: PAPER_WIDTH,
+        paper_height: PAPER_HEIGHT,
+        x: PAGE_MARGIN,
+        y: PAGE_MARGIN,
+        width: image_width as f64 / PRINT_SCALE,
+        height: image_height as f64 / PRINT_SCALE,
+        image_width,
+        image_height,
+        rgba,
+    }
+}
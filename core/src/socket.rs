@@ -7,8 +7,8 @@ use crate::{
         object::SocketObject, Activation as Avm2Activation, Avm2, EventObject,
         TObject as Avm2TObject,
     },
-    backend::navigator::NavigatorBackend,
     context::UpdateContext,
+    policy_file::PolicyFiles,
     string::AvmString,
 };
 use async_channel::{unbounded, Receiver, Sender as AsyncSender, Sender};
@@ -36,14 +36,18 @@ struct Socket<'gc> {
     target: SocketKind<'gc>,
     sender: RefCell<AsyncSender<Vec<u8>>>,
     connected: Cell<bool>,
+    /// The `host:port` this socket connects to.
+    #[collect(require_static)]
+    address: String,
 }
 
 impl<'gc> Socket<'gc> {
-    fn new(target: SocketKind<'gc>, sender: AsyncSender<Vec<u8>>) -> Self {
+    fn new(target: SocketKind<'gc>, sender: AsyncSender<Vec<u8>>, address: String) -> Self {
         Self {
             target,
             sender: RefCell::new(sender),
             connected: Cell::new(false),
+            address,
         }
     }
 }
@@ -53,6 +57,11 @@ pub enum ConnectionState {
     Connected,
     Failed,
     TimedOut,
+    /// The connection was not allowed by a socket policy file.
+    ///
+    /// This is only reported by the player itself, before asking the
+    /// navigator to connect.
+    Denied,
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -90,37 +99,28 @@ impl<'gc> Sockets<'gc> {
     }
 
     pub fn connect_avm2(
-        &mut self,
-        backend: &mut dyn NavigatorBackend,
+        context: &mut UpdateContext<'gc>,
         target: SocketObject<'gc>,
         host: String,
         port: u16,
     ) {
         let (sender, receiver) = unbounded();
 
-        let socket = Socket::new(SocketKind::Avm2(target), sender);
-        let handle = self.sockets.insert(socket);
+        let socket = Socket::new(SocketKind::Avm2(target), sender, format!("{host}:{port}"));
+        let handle = context.sockets.sockets.insert(socket);
 
-        // NOTE: This call will send SocketAction::Connect to sender with connection status.
-        backend.connect_socket(
-            host,
-            port,
-            Duration::from_millis(target.timeout().into()),
-            handle,
-            receiver,
-            self.sender.clone(),
-        );
+        let timeout = Duration::from_millis(target.timeout().into());
+        Self::open(context, host, port, timeout, handle, receiver);
 
         if let Some(existing_handle) = target.set_handle(handle) {
             // As written in the AS3 docs, we are supposed to close the existing connection,
             // when a new one is created.
-            self.close(existing_handle)
+            context.sockets.close(existing_handle)
         }
     }
 
     pub fn connect_avm1(
-        &mut self,
-        backend: &mut dyn NavigatorBackend,
+        context: &mut UpdateContext<'gc>,
         target: Avm1Object<'gc>,
         host: String,
         port: u16,
@@ -132,23 +132,52 @@ impl<'gc> Sockets<'gc> {
             None => return,
         };
 
-        let socket = Socket::new(SocketKind::Avm1(target), sender);
-        let handle = self.sockets.insert(socket);
+        let socket = Socket::new(SocketKind::Avm1(target), sender, format!("{host}:{port}"));
+        let handle = context.sockets.sockets.insert(socket);
 
-        // NOTE: This call will send SocketAction::Connect to sender with connection status.
-        backend.connect_socket(
-            host,
-            port,
-            Duration::from_millis(xml_socket.timeout().into()),
-            handle,
-            receiver,
-            self.sender.clone(),
-        );
+        let timeout = Duration::from_millis(xml_socket.timeout().into());
+        Self::open(context, host, port, timeout, handle, receiver);
 
         if let Some(existing_handle) = xml_socket.set_handle(handle) {
             // NOTE: AS2 docs don't specify what happens when connect is called with open connection,
             //       but we will close the existing connection anyway.
-            self.close(existing_handle)
+            context.sockets.close(existing_handle)
+        }
+    }
+
+    /// Ask the navigator to open the connection of a socket, once a socket
+    /// policy file allows it if policy files are enforced.
+    fn open(
+        context: &mut UpdateContext<'gc>,
+        host: String,
+        port: u16,
+        timeout: Duration,
+        handle: SocketHandle,
+        receiver: Receiver<Vec<u8>>,
+    ) {
+        let sender = context.sockets.sender.clone();
+
+        // NOTE: `connect_socket` will send SocketAction::Connect to sender with connection status.
+        match PolicyFiles::socket_access_check(context, &host, port) {
+            None => context
+                .navigator
+                .connect_socket(host, port, timeout, handle, receiver, sender),
+            Some(access_check) => {
+                let player = context.player.clone();
+                context.navigator.spawn_future(Box::pin(async move {
+                    if !access_check.await {
+                        let _ =
+                            sender.try_send(SocketAction::Connect(handle, ConnectionState::Denied));
+                    } else if let Some(player) = player.upgrade() {
+                        player
+                            .lock()
+                            .unwrap()
+                            .navigator_mut()
+                            .connect_socket(host, port, timeout, handle, receiver, sender);
+                    }
+                    Ok(())
+                }));
+            }
         }
     }
 
@@ -187,6 +216,7 @@ impl<'gc> Sockets<'gc> {
             sender,
             target,
             connected: _,
+            address: _,
         } = socket;
 
         drop(sender); // NOTE: By dropping the sender, the reading task will close automatically.
@@ -295,6 +325,62 @@ impl<'gc> Sockets<'gc> {
                         }
                     }
                 }
+                SocketAction::Connect(handle, ConnectionState::Denied) => {
+                    let (target, address) = match context.sockets.sockets.get(handle) {
+                        Some(socket) => (socket.target, socket.address.clone()),
+                        // Socket must have been closed before we could send event.
+                        None => continue,
+                    };
+
+                    match target {
+                        SocketKind::Avm2(target) => {
+                            let mut activation = Avm2Activation::from_nothing(context);
+
+                            let message = AvmString::new_utf8(
+                                activation.gc(),
+                                format!(
+                                    "Error #2048: Security sandbox violation: {} cannot load data from {}.",
+                                    activation.context.swf.url(),
+                                    address
+                                ),
+                            );
+                            let security_error_evt = activation
+                                .avm2()
+                                .classes()
+                                .securityerrorevent
+                                .construct(
+                                    &mut activation,
+                                    &[
+                                        "securityError".into(),
+                                        false.into(),
+                                        false.into(),
+                                        message.into(),
+                                        2048.into(),
+                                    ],
+                                )
+                                .expect("SecurityErrorEvent should be constructed");
+
+                            Avm2::dispatch_event(
+                                activation.context,
+                                security_error_evt,
+                                target.into(),
+                            );
+                        }
+                        SocketKind::Avm1(target) => {
+                            let mut activation = Avm1Activation::from_stub(
+                                context,
+                                ActivationIdentifier::root("[XMLSocket]"),
+                            );
+
+                            let _ = target.call_method(
+                                "onConnect".into(),
+                                &[false.into()],
+                                &mut activation,
+                                ExecutionReason::Special,
+                            );
+                        }
+                    }
+                }
                 SocketAction::Data(handle, mut data) => {
                     let target = match context.sockets.sockets.get(handle) {
                         Some(socket) => socket.target,
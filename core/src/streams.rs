@@ -1,5 +1,7 @@
 //! NetStream implementation
 
+mod mp4;
+
 use crate::avm1::{
     Activation as Avm1Activation, ActivationIdentifier as Avm1ActivationIdentifier,
     ExecutionReason as Avm1ExecutionReason, FlvValueAvm1Ext, ScriptObject as Avm1ScriptObject,
@@ -18,6 +20,7 @@ use crate::buffer::{Buffer, Slice, Substream, SubstreamError};
 use crate::context::UpdateContext;
 use crate::display_object::{MovieClip, TDisplayObject};
 use crate::loader::Error;
+use crate::net_connection::{NetConnectionHandle, NetConnectionObject, NetConnections, FLV_HEADER};
 use crate::string::AvmString;
 use crate::vminterface::AvmObject;
 use flv_rs::{
@@ -25,7 +28,7 @@ use flv_rs::{
     FrameType as FlvFrameType, Header as FlvHeader, ScriptData as FlvScriptData,
     SoundFormat as FlvSoundFormat, SoundRate as FlvSoundRate, SoundSize as FlvSoundSize,
     SoundType as FlvSoundType, Tag as FlvTag, TagData as FlvTagData, Value as FlvValue,
-    VideoData as FlvVideoData, VideoPacket as FlvVideoPacket,
+    Variable as FlvVariable, VideoData as FlvVideoData, VideoPacket as FlvVideoPacket,
 };
 use gc_arena::{Collect, GcCell, Mutation};
 use ruffle_render::bitmap::BitmapInfo;
@@ -33,6 +36,7 @@ use ruffle_video::frame::EncodedFrame;
 use ruffle_video::VideoStreamHandle;
 use std::cmp::max;
 use std::io::{Seek, SeekFrom};
+use std::rc::Rc;
 use swf::{AudioCompression, SoundFormat, VideoCodec, VideoDeblocking};
 use thiserror::Error;
 use url::Url;
@@ -176,6 +180,27 @@ pub enum NetStreamType {
         /// onto a table of data buffers like `Video` does, so we must maintain
         /// frame IDs ourselves for various API related purposes.
         frame_id: u32,
+
+        /// The `AudioSpecificConfig` of the last AAC sequence header.
+        ///
+        /// Seeking never goes back to the sequence header, so we hold onto
+        /// it for every audio stream we start afterwards.
+        aac_config: Option<Vec<u8>>,
+    },
+
+    /// The stream is an MP4, F4V, or any other ISO base media file.
+    Mp4 {
+        /// The parsed `moov` box, once it has been downloaded.
+        movie: Option<Rc<mp4::Movie>>,
+
+        /// The currently playing video track's stream instance.
+        video_stream: Option<VideoStreamHandle>,
+
+        /// The index of the last processed frame.
+        frame_id: u32,
+
+        /// The index of the next sample to play, for each track of the movie.
+        next_samples: Vec<usize>,
     },
 }
 
@@ -252,6 +277,12 @@ pub struct NetStreamData<'gc> {
 
     /// True if the stream should play when ticked.
     playing: bool,
+
+    /// The `NetConnection` this stream was created with.
+    ///
+    /// Only RTMP connections actually carry stream data; any other connection
+    /// makes `play` download the named file instead.
+    connection: Option<NetConnectionObject<'gc>>,
 }
 
 impl<'gc> NetStream<'gc> {
@@ -277,6 +308,7 @@ impl<'gc> NetStream<'gc> {
                 attached_to: None,
                 playing: false,
                 expected_length: Some(0),
+                connection: None,
             },
         ))
     }
@@ -293,6 +325,20 @@ impl<'gc> NetStream<'gc> {
         self.0.write(gc_context).avm_object = Some(avm_object);
     }
 
+    pub fn set_connection(
+        self,
+        gc_context: &Mutation<'gc>,
+        connection: Option<NetConnectionObject<'gc>>,
+    ) {
+        self.0.write(gc_context).connection = connection;
+    }
+
+    /// The handle of the RTMP connection this stream plays from, if any.
+    fn rtmp_connection(self, context: &UpdateContext<'gc>) -> Option<NetConnectionHandle> {
+        let handle = self.0.read().connection?.handle()?;
+        context.net_connections.is_rtmp(handle).then_some(handle)
+    }
+
     /// Reset the `NetStream` buffer to accept new source data.
     ///
     /// This must be done once per source change and should ideally be done
@@ -358,6 +404,24 @@ impl<'gc> NetStream<'gc> {
         );
     }
 
+    /// Append data received from a live connection to the buffer.
+    ///
+    /// Unlike `load_buffer`, this doesn't report the buffer as full, as a
+    /// connection delivers a stream a few bytes at a time.
+    pub fn append_stream_data(self, context: &mut UpdateContext<'gc>, data: &mut Vec<u8>) {
+        self.0.write(context.gc_context).buffer.append(data);
+
+        StreamManager::activate(context, self);
+    }
+
+    /// Set the current stream time without seeking in the buffer.
+    ///
+    /// This is used when a server starts sending a stream from somewhere other
+    /// than the beginning. `time` is in milliseconds.
+    pub fn set_stream_time(self, context: &mut UpdateContext<'gc>, time: f64) {
+        self.0.write(context.gc_context).stream_time = time;
+    }
+
     /// Indicate that the buffer has finished loading and that no further data
     /// is expected to be downloaded to it.
     pub fn finish_buffer(self, context: &mut UpdateContext<'gc>) {
@@ -395,7 +459,18 @@ impl<'gc> NetStream<'gc> {
     ///
     /// `offset` is in milliseconds.
     pub fn seek(self, context: &mut UpdateContext<'gc>, offset: f64, notify: bool) {
-        self.0.write(context.gc_context).queued_seek_time = Some(offset);
+        if let Some(handle) = self.rtmp_connection(context) {
+            // The server only sends what's after the new position, so the
+            // old data in the buffer is useless.
+            self.reset_buffer(context);
+            self.0
+                .write(context.gc_context)
+                .buffer
+                .append(&mut FLV_HEADER.to_vec());
+            NetConnections::seek_stream(context, handle, self, offset);
+        } else {
+            self.0.write(context.gc_context).queued_seek_time = Some(offset);
+        }
         StreamManager::activate(context, self);
 
         if notify {
@@ -528,6 +603,40 @@ impl<'gc> NetStream<'gc> {
                 .expect("FLV reader stream position") as usize;
         }
 
+        let mp4_seek_time = if let Some(NetStreamType::Mp4 {
+            movie: Some(movie),
+            next_samples,
+            ..
+        }) = &mut write.stream_type
+        {
+            // Video can only restart from a keyframe, so snap to the one
+            // before the requested time and let the other tracks follow.
+            let time = movie
+                .tracks
+                .iter()
+                .find(|t| matches!(t.kind, mp4::TrackKind::Video { .. }))
+                .and_then(|t| t.samples.get(t.seek_sample(offset)))
+                .map(|s| s.time)
+                .unwrap_or(offset);
+
+            for (track, next_sample) in movie.tracks.iter().zip(next_samples.iter_mut()) {
+                *next_sample = match track.kind {
+                    mp4::TrackKind::Video { .. } => track.seek_sample(offset),
+                    mp4::TrackKind::Audio { .. } => {
+                        track.samples.partition_point(|s| s.time < time)
+                    }
+                };
+            }
+
+            Some(time)
+        } else {
+            None
+        };
+
+        if let Some(time) = mp4_seek_time {
+            write.stream_time = time;
+        }
+
         drop(write);
 
         if let Some(AvmObject::Avm2(_)) = self.0.read().avm_object {
@@ -548,6 +657,27 @@ impl<'gc> NetStream<'gc> {
     /// the given resource. Otherwise, the stream will play whatever data is
     /// available in the buffer.
     pub fn play(self, context: &mut UpdateContext<'gc>, name: Option<AvmString<'gc>>) {
+        if let Some(handle) = self.rtmp_connection(context) {
+            let Some(name) = name else {
+                return;
+            };
+
+            // The server sends us raw audio, video and data messages, which
+            // we repackage as FLV tags so they can be played like any other
+            // FLV.
+            self.reset_buffer(context);
+            self.0
+                .write(context.gc_context)
+                .buffer
+                .append(&mut FLV_HEADER.to_vec());
+            NetConnections::play_stream(context, handle, self, name.to_string());
+
+            // The server reports `NetStream.Play.Start` itself.
+            self.0.write(context.gc_context).playing = true;
+            StreamManager::activate(context, self);
+            return;
+        }
+
         if let Some(name) = name {
             let request = if let Ok(stream_url) =
                 Url::parse(context.swf.url()).and_then(|url| url.join(name.to_string().as_str()))
@@ -575,12 +705,38 @@ impl<'gc> NetStream<'gc> {
         );
     }
 
+    /// Publish this stream's audio and video to an RTMP server.
+    ///
+    /// Capturing local media isn't supported, so this only announces the
+    /// stream to the server.
+    pub fn publish(self, context: &mut UpdateContext<'gc>, name: String, publish_type: String) {
+        if let Some(handle) = self.rtmp_connection(context) {
+            NetConnections::publish_stream(context, handle, self, name, publish_type);
+        } else {
+            tracing::warn!("NetStream.publish requires an RTMP connection");
+        }
+    }
+
+    /// Stop playing or publishing and release the stream on the server.
+    pub fn close(self, context: &mut UpdateContext<'gc>) {
+        if let Some(handle) = self.rtmp_connection(context) {
+            NetConnections::close_stream(context, handle, self);
+        }
+
+        self.0.write(context.gc_context).playing = false;
+        self.reset_buffer(context);
+    }
+
     /// Pause stream playback.
     pub fn pause(self, context: &mut UpdateContext<'gc>, notify: bool) {
         // NOTE: We do not deactivate the stream here as there may be other
         // work to be done at tick time.
         self.0.write(context.gc_context).playing = false;
 
+        if let Some(handle) = self.rtmp_connection(context) {
+            NetConnections::pause_stream(context, handle, self, true);
+        }
+
         if notify {
             self.trigger_status_event(
                 context,
@@ -597,16 +753,26 @@ impl<'gc> NetStream<'gc> {
     pub fn resume(self, context: &mut UpdateContext<'gc>) {
         self.0.write(context.gc_context).playing = true;
         StreamManager::activate(context, self);
+
+        if let Some(handle) = self.rtmp_connection(context) {
+            NetConnections::pause_stream(context, handle, self, false);
+        }
     }
 
     /// Resume stream playback if paused, pause otherwise.
     pub fn toggle_paused(self, context: &mut UpdateContext<'gc>) {
         let mut write = self.0.write(context.gc_context);
         write.playing = !write.playing;
+        let playing = write.playing;
+        drop(write);
 
-        if write.playing {
+        if playing {
             StreamManager::activate(context, self);
         }
+
+        if let Some(handle) = self.rtmp_connection(context) {
+            NetConnections::pause_stream(context, handle, self, !playing);
+        }
     }
 
     /// Indicates that this `NetStream`'s audio was detached from a `MovieClip` (AVM1)
@@ -652,10 +818,116 @@ impl<'gc> NetStream<'gc> {
         audio_data: FlvAudioData<'_>,
     ) -> Result<(), NetstreamError> {
         let data = match audio_data.data {
-            FlvAudioDataType::Raw(data)
-            | FlvAudioDataType::AacSequenceHeader(data)
-            | FlvAudioDataType::AacRaw(data) => slice.to_subslice(data),
+            FlvAudioDataType::AacSequenceHeader(config) => {
+                // The sequence header configures the decoder rather than
+                // carrying any audio of its own.
+                if let Some(NetStreamType::Flv { aac_config, .. }) = &mut write.stream_type {
+                    *aac_config = Some(config.to_vec());
+                }
+                return Ok(());
+            }
+            FlvAudioDataType::Raw(data) | FlvAudioDataType::AacRaw(data) => slice.to_subslice(data),
+        };
+        let decoder_config = match &write.stream_type {
+            Some(NetStreamType::Flv { aac_config, .. }) => aac_config.clone(),
+            _ => None,
+        };
+
+        self.append_audio_data(write, slice, data, decoder_config, || {
+            Ok(SoundFormat {
+                compression: match audio_data.format {
+                    FlvSoundFormat::LinearPCMPlatformEndian => {
+                        AudioCompression::UncompressedUnknownEndian
+                    }
+                    FlvSoundFormat::Adpcm => AudioCompression::Adpcm,
+                    FlvSoundFormat::MP3 => AudioCompression::Mp3,
+                    FlvSoundFormat::LinearPCMLittleEndian => AudioCompression::Uncompressed,
+                    FlvSoundFormat::Nellymoser16kHz => AudioCompression::Nellymoser16Khz,
+                    FlvSoundFormat::Nellymoser8kHz => AudioCompression::Nellymoser8Khz,
+                    FlvSoundFormat::Nellymoser => AudioCompression::Nellymoser,
+                    FlvSoundFormat::G711ALawPCM => return Err(NetstreamError::UnknownCodec),
+                    FlvSoundFormat::G711MuLawPCM => return Err(NetstreamError::UnknownCodec),
+                    FlvSoundFormat::Aac => AudioCompression::Aac,
+                    FlvSoundFormat::Speex => AudioCompression::Speex,
+                    FlvSoundFormat::MP38kHz => AudioCompression::Mp3,
+                    FlvSoundFormat::DeviceSpecific => return Err(NetstreamError::UnknownCodec),
+                },
+                sample_rate: match (audio_data.format, audio_data.rate) {
+                    (FlvSoundFormat::MP38kHz, _) => 8_000,
+                    (_, FlvSoundRate::R5_500) => 5_500,
+                    (_, FlvSoundRate::R11_000) => 11_000,
+                    (_, FlvSoundRate::R22_000) => 22_000,
+                    (_, FlvSoundRate::R44_000) => 44_000,
+                },
+                is_stereo: match audio_data.sound_type {
+                    FlvSoundType::Mono => false,
+                    FlvSoundType::Stereo => true,
+                },
+                is_16_bit: match audio_data.size {
+                    FlvSoundSize::Bits8 => false,
+                    FlvSoundSize::Bits16 => true,
+                },
+            })
+        })
+    }
+
+    /// Process an audio sample from an MP4 track.
+    ///
+    /// `write` must be an active borrow of the current `NetStream`. `slice`
+    /// must reference the underlying backing buffer.
+    ///
+    /// Samples in codecs we can't play are silently dropped; the codec was
+    /// already reported when the movie was loaded.
+    fn mp4_audio_sample(
+        self,
+        write: &mut NetStreamData<'gc>,
+        slice: &Slice,
+        track: &mp4::Track,
+        sample: &mp4::Sample,
+    ) -> Result<(), NetstreamError> {
+        let mp4::TrackKind::Audio {
+            codec,
+            sample_rate,
+            channels,
+            ..
+        } = &track.kind
+        else {
+            unreachable!()
+        };
+        let (compression, decoder_config) = match codec {
+            mp4::AudioCodec::Mp3 => (AudioCompression::Mp3, None),
+            mp4::AudioCodec::Aac { configuration } => {
+                (AudioCompression::Aac, Some(configuration.clone()))
+            }
+            mp4::AudioCodec::Unsupported(_) => return Ok(()),
         };
+
+        let Some(data) = sample.end().and_then(|end| slice.get(sample.offset..end)) else {
+            return Ok(());
+        };
+        self.append_audio_data(write, slice, data, decoder_config, || {
+            Ok(SoundFormat {
+                compression,
+                sample_rate: *sample_rate as u16,
+                is_stereo: *channels > 1,
+                is_16_bit: true,
+            })
+        })
+    }
+
+    /// Append a block of audio data to the current sound stream.
+    ///
+    /// If there is no sound stream yet, a new one is started, using
+    /// `sound_format` to determine the format of the stream and
+    /// `decoder_config` to set up its decoder.
+    fn append_audio_data(
+        self,
+        write: &mut NetStreamData<'gc>,
+        slice: &Slice,
+        data: Slice,
+        decoder_config: Option<Vec<u8>>,
+        sound_format: impl FnOnce() -> Result<SoundFormat, NetstreamError>,
+    ) -> Result<(), NetstreamError> {
         let substream = match &mut write.audio_stream {
             Some((substream, _sound_stream_info)) => {
                 if substream
@@ -678,46 +950,12 @@ impl<'gc> NetStream<'gc> {
             audio_stream => {
                 // None
                 let substream = Substream::new(slice.buffer().clone());
-                let swf_format = SoundFormat {
-                    compression: match audio_data.format {
-                        FlvSoundFormat::LinearPCMPlatformEndian => {
-                            AudioCompression::UncompressedUnknownEndian
-                        }
-                        FlvSoundFormat::Adpcm => AudioCompression::Adpcm,
-                        FlvSoundFormat::MP3 => AudioCompression::Mp3,
-                        FlvSoundFormat::LinearPCMLittleEndian => AudioCompression::Uncompressed,
-                        FlvSoundFormat::Nellymoser16kHz => AudioCompression::Nellymoser16Khz,
-                        FlvSoundFormat::Nellymoser8kHz => AudioCompression::Nellymoser8Khz,
-                        FlvSoundFormat::Nellymoser => AudioCompression::Nellymoser,
-                        FlvSoundFormat::G711ALawPCM => return Err(NetstreamError::UnknownCodec),
-                        FlvSoundFormat::G711MuLawPCM => return Err(NetstreamError::UnknownCodec),
-                        FlvSoundFormat::Aac => return Err(NetstreamError::UnknownCodec),
-                        FlvSoundFormat::Speex => AudioCompression::Speex,
-                        FlvSoundFormat::MP38kHz => AudioCompression::Mp3,
-                        FlvSoundFormat::DeviceSpecific => return Err(NetstreamError::UnknownCodec),
-                    },
-                    sample_rate: match (audio_data.format, audio_data.rate) {
-                        (FlvSoundFormat::MP38kHz, _) => 8_000,
-                        (_, FlvSoundRate::R5_500) => 5_500,
-                        (_, FlvSoundRate::R11_000) => 11_000,
-                        (_, FlvSoundRate::R22_000) => 22_000,
-                        (_, FlvSoundRate::R44_000) => 44_000,
-                    },
-                    is_stereo: match audio_data.sound_type {
-                        FlvSoundType::Mono => false,
-                        FlvSoundType::Stereo => true,
-                    },
-                    is_16_bit: match audio_data.size {
-                        FlvSoundSize::Bits8 => false,
-                        FlvSoundSize::Bits16 => true,
-                    },
-                };
-
                 let sound_stream_head = SoundStreamInfo {
                     wrapping: SoundStreamWrapping::Unwrapped,
-                    stream_format: swf_format,
+                    stream_format: sound_format()?,
                     num_samples_per_block: 0,
                     latency_seek: 0,
+                    decoder_config,
                 };
 
                 *audio_stream = Some((substream, sound_stream_head));
@@ -823,6 +1061,7 @@ impl<'gc> NetStream<'gc> {
                             header,
                             video_stream: None,
                             frame_id: 0,
+                            aac_config: None,
                         });
                         true
                     }
@@ -835,6 +1074,17 @@ impl<'gc> NetStream<'gc> {
                     }
                 }
             }
+            _ if mp4::is_mp4(&buffer) => {
+                write.stream_type = Some(NetStreamType::Mp4 {
+                    movie: None,
+                    video_stream: None,
+                    frame_id: 0,
+                    next_samples: Vec::new(),
+                });
+                true
+            }
+            // MP4 files can only be recognized from their 8th byte on.
+            Some(_) if buffer.len() < 8 => false,
             Some(magic) => {
                 //Unrecognized signature
                 //TODO: Fire an error event to AS & stop playing too
@@ -1134,6 +1384,272 @@ impl<'gc> NetStream<'gc> {
         }
     }
 
+    /// Load the sample tables of an MP4 stream, if they aren't already.
+    ///
+    /// Returns false if the stream can't be played (yet), either because the
+    /// `moov` box hasn't been downloaded or because it is invalid. Streams of
+    /// any other type always return true.
+    fn load_mp4_movie(self, context: &mut UpdateContext<'gc>) -> bool {
+        let mut write = self.0.write(context.gc_context);
+        if !matches!(
+            write.stream_type,
+            Some(NetStreamType::Mp4 { movie: None, .. })
+        ) {
+            return true;
+        }
+
+        let slice = write.buffer.to_full_slice();
+        let buffer = slice.data();
+        let movie = match mp4::find_movie_box(&buffer) {
+            Some((position, moov)) => mp4::Movie::parse(position, moov),
+            None if write.expected_length.is_none() => Err(mp4::Mp4Error::Invalid("moov")),
+            None => return false,
+        };
+        drop(buffer);
+
+        let movie = match movie {
+            Ok(movie) => Rc::new(movie),
+            Err(e) => {
+                //TODO: Fire an error event to AS & stop playing too
+                tracing::error!("MP4 parsing failed: {}", e);
+                write.playing = false;
+                return false;
+            }
+        };
+
+        let mut video_stream = None;
+        for track in &movie.tracks {
+            match &track.kind {
+                mp4::TrackKind::Video {
+                    codec: mp4::VideoCodec::H264 { configuration },
+                    width,
+                    height,
+                } if video_stream.is_none() => {
+                    match context.video.register_video_stream(
+                        track.samples.len() as u32,
+                        (*width, *height),
+                        VideoCodec::H264,
+                        VideoDeblocking::UseVideoPacketValue,
+                    ) {
+                        Ok(handle) => {
+                            if let Err(e) = context
+                                .video
+                                .configure_video_stream_decoder(handle, configuration)
+                            {
+                                tracing::error!("Configuring MP4 video decoder failed: {}", e);
+                            }
+                            video_stream = Some(handle);
+                        }
+                        Err(e) => {
+                            tracing::error!("Got error when registering MP4 video stream: {}", e)
+                        }
+                    }
+                }
+                mp4::TrackKind::Video {
+                    codec: mp4::VideoCodec::Unsupported(codec),
+                    ..
+                } => {
+                    tracing::error!(
+                        "MP4 video codec {} is not supported",
+                        String::from_utf8_lossy(codec)
+                    );
+                }
+                mp4::TrackKind::Audio {
+                    codec: mp4::AudioCodec::Unsupported(codec),
+                    ..
+                } => {
+                    tracing::error!(
+                        "MP4 audio codec {} is not supported",
+                        String::from_utf8_lossy(codec)
+                    );
+                }
+                _ => {}
+            }
+        }
+
+        write.stream_type = Some(NetStreamType::Mp4 {
+            movie: Some(movie.clone()),
+            video_stream,
+            frame_id: 0,
+            next_samples: vec![0; movie.tracks.len()],
+        });
+        let avm_object = write.avm_object;
+        drop(write);
+
+        // MP4 files have no script data, but Flash synthesizes an
+        // `onMetaData` event from the movie's header.
+        let metadata = Self::mp4_metadata(&movie);
+        let _ = self.handle_script_data(avm_object, context, b"onMetaData", metadata); // Any errors while trying to lookup or call AVM2 properties are silently swallowed.
+
+        true
+    }
+
+    /// Build the `onMetaData` object of an MP4 stream.
+    fn mp4_metadata(movie: &mp4::Movie) -> FlvValue<'_> {
+        let mut metadata = vec![
+            FlvVariable {
+                name: b"duration",
+                data: FlvValue::Number(movie.duration),
+            },
+            FlvVariable {
+                name: b"moovposition",
+                data: FlvValue::Number(movie.position as f64),
+            },
+        ];
+
+        for track in &movie.tracks {
+            match &track.kind {
+                mp4::TrackKind::Video {
+                    codec,
+                    width,
+                    height,
+                } => {
+                    let codec_id: &[u8] = match codec {
+                        mp4::VideoCodec::H264 { configuration } => {
+                            if let [_, profile, _, level, ..] = configuration.as_slice() {
+                                metadata.push(FlvVariable {
+                                    name: b"avcprofile",
+                                    data: FlvValue::Number(*profile as f64),
+                                });
+                                metadata.push(FlvVariable {
+                                    name: b"avclevel",
+                                    data: FlvValue::Number(*level as f64),
+                                });
+                            }
+                            b"avc1"
+                        }
+                        mp4::VideoCodec::Unsupported(codec) => codec,
+                    };
+                    metadata.push(FlvVariable {
+                        name: b"videocodecid",
+                        data: FlvValue::String(codec_id),
+                    });
+                    metadata.push(FlvVariable {
+                        name: b"width",
+                        data: FlvValue::Number(*width as f64),
+                    });
+                    metadata.push(FlvVariable {
+                        name: b"height",
+                        data: FlvValue::Number(*height as f64),
+                    });
+                    if let Some(frame_rate) = track.frame_rate() {
+                        metadata.push(FlvVariable {
+                            name: b"videoframerate",
+                            data: FlvValue::Number(frame_rate),
+                        });
+                    }
+                }
+                mp4::TrackKind::Audio {
+                    codec,
+                    sample_rate,
+                    channels,
+                    ..
+                } => {
+                    let codec_id: &[u8] = match codec {
+                        mp4::AudioCodec::Aac { configuration } => {
+                            if let Some(object_type) = configuration.first() {
+                                metadata.push(FlvVariable {
+                                    name: b"aacaot",
+                                    data: FlvValue::Number((object_type >> 3) as f64),
+                                });
+                            }
+                            b"mp4a"
+                        }
+                        mp4::AudioCodec::Mp3 => b".mp3",
+                        mp4::AudioCodec::Unsupported(codec) => codec,
+                    };
+                    metadata.push(FlvVariable {
+                        name: b"audiocodecid",
+                        data: FlvValue::String(codec_id),
+                    });
+                    metadata.push(FlvVariable {
+                        name: b"audiosamplerate",
+                        data: FlvValue::Number(*sample_rate as f64),
+                    });
+                    metadata.push(FlvVariable {
+                        name: b"audiochannels",
+                        data: FlvValue::Number(*channels as f64),
+                    });
+                }
+            }
+        }
+
+        let seek_points = movie
+            .seek_points()
+            .map(|sample| {
+                FlvValue::Object(vec![
+                    FlvVariable {
+                        name: b"time",
+                        data: FlvValue::Number(sample.time / 1000.0),
+                    },
+                    FlvVariable {
+                        name: b"offset",
+                        data: FlvValue::Number(sample.offset as f64),
+                    },
+                ])
+            })
+            .collect();
+        metadata.push(FlvVariable {
+            name: b"seekpoints",
+            data: FlvValue::StrictArray(seek_points),
+        });
+
+        FlvValue::Object(metadata)
+    }
+
+    /// Process a video sample from an MP4 track.
+    ///
+    /// `write` must be an active borrow of the current `NetStream`.
+    fn mp4_video_sample(
+        self,
+        context: &mut UpdateContext<'gc>,
+        write: &mut NetStreamData<'gc>,
+        data: &[u8],
+    ) {
+        let (video_handle, frame_id) = match write.stream_type {
+            Some(NetStreamType::Mp4 {
+                video_stream,
+                frame_id,
+                ..
+            }) => (video_stream, frame_id),
+            _ => unreachable!(),
+        };
+
+        // Tracks in unsupported codecs have no video stream.
+        let Some(video_handle) = video_handle else {
+            return;
+        };
+
+        let encoded_frame = EncodedFrame {
+            codec: VideoCodec::H264,
+            data,
+            frame_id,
+        };
+
+        match context
+            .video
+            .decode_video_stream_frame(video_handle, encoded_frame, context.renderer)
+        {
+            Ok(bitmap_info) => {
+                write.last_decoded_bitmap = Some(bitmap_info);
+                if let Some(mc) = write.attached_to {
+                    mc.invalidate_cached_bitmap(context.gc_context);
+                    *context.needs_render = true;
+                }
+            }
+            Err(e) => {
+                tracing::error!("Decoding video frame {} failed: {}", frame_id, e);
+            }
+        }
+
+        if let Some(NetStreamType::Mp4 {
+            ref mut frame_id, ..
+        }) = write.stream_type
+        {
+            *frame_id += 1;
+        }
+    }
+
     /// Process stream data.
     ///
     /// `dt` is in milliseconds.
@@ -1154,6 +1670,11 @@ impl<'gc> NetStream<'gc> {
             return;
         }
 
+        // MP4 files can't be played before their sample tables are loaded.
+        if !self.load_mp4_movie(context) {
+            return;
+        }
+
         let mut write = self.0.write(context.gc_context);
 
         self.cleanup_sound_stream(context, &mut write);
@@ -1235,6 +1756,75 @@ impl<'gc> NetStream<'gc> {
                     write.preload_offset = max(write.offset, write.preload_offset);
                 }
             }
+        } else if let Some(NetStreamType::Mp4 {
+            movie: Some(movie),
+            next_samples,
+            ..
+        }) = &write.stream_type
+        {
+            let movie = movie.clone();
+            let mut cursors = next_samples.clone();
+            let mut committed_cursors = cursors.clone();
+
+            loop {
+                // Samples of all tracks are played in decoding order.
+                let next = movie
+                    .tracks
+                    .iter()
+                    .zip(&cursors)
+                    .enumerate()
+                    .filter_map(|(index, (track, &cursor))| {
+                        Some((index, track, track.samples.get(cursor)?))
+                    })
+                    .min_by(|(_, _, a), (_, _, b)| a.time.total_cmp(&b.time));
+
+                let Some((index, track, sample)) = next else {
+                    if !is_lookahead_tag {
+                        buffer_underrun = true;
+                    }
+                    break;
+                };
+
+                let Some(end) = sample.end().filter(|&end| end <= buffer.len()) else {
+                    // The sample hasn't been downloaded yet.
+                    if !is_lookahead_tag {
+                        buffer_underrun = true;
+                    }
+                    break;
+                };
+
+                is_lookahead_tag = sample.time >= max_time;
+                if is_lookahead_tag && max_lookahead_audio_tags == 0 {
+                    break;
+                }
+
+                cursors[index] += 1;
+
+                match track.kind {
+                    mp4::TrackKind::Audio { .. } => {
+                        if is_lookahead_tag {
+                            max_lookahead_audio_tags -= 1;
+                        }
+
+                        if let Err(e) = self.mp4_audio_sample(&mut write, &slice, track, sample) {
+                            //TODO: Fire an error event at AS.
+                            tracing::error!("Error committing sound stream: {}", e);
+                        }
+                    }
+                    mp4::TrackKind::Video { .. } if !is_lookahead_tag => {
+                        self.mp4_video_sample(context, &mut write, &buffer[sample.offset..end])
+                    }
+                    mp4::TrackKind::Video { .. } => {}
+                }
+
+                if !is_lookahead_tag {
+                    committed_cursors.clone_from(&cursors);
+                }
+            }
+
+            if let Some(NetStreamType::Mp4 { next_samples, .. }) = &mut write.stream_type {
+                *next_samples = committed_cursors;
+            }
         }
 
         write.stream_time = max_time;
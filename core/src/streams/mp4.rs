@@ -0,0 +1,957 @@
+//! ISO base media file format (MP4, F4V, M4A) demuxing.
+//!
+//! Unlike FLV, an MP4 file doesn't interleave its metadata with the media:
+//! everything needed to locate a sample lives in the `moov` box, and the
+//! samples themselves are just byte ranges in the file. We therefore parse
+//! the whole `moov` box up front into per-track sample tables, and then play
+//! the file by walking those tables.
+
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum Mp4Error {
+    #[error("MP4 box {0} is truncated")]
+    Truncated(&'static str),
+
+    #[error("MP4 box {0} is invalid")]
+    Invalid(&'static str),
+
+    #[error("MP4 file has no playable tracks")]
+    NoTracks,
+}
+
+/// A fourcc box type.
+type BoxType = [u8; 4];
+
+/// A cursor over big-endian box data.
+struct Reader<'a> {
+    data: &'a [u8],
+    name: &'static str,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8], name: &'static str) -> Self {
+        Self { data, name }
+    }
+
+    fn bytes(&mut self, length: usize) -> Result<&'a [u8], Mp4Error> {
+        if self.data.len() < length {
+            return Err(Mp4Error::Truncated(self.name));
+        }
+        let (head, tail) = self.data.split_at(length);
+        self.data = tail;
+        Ok(head)
+    }
+
+    fn skip(&mut self, length: usize) -> Result<(), Mp4Error> {
+        self.bytes(length).map(|_| ())
+    }
+
+    fn u8(&mut self) -> Result<u8, Mp4Error> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, Mp4Error> {
+        let bytes = self.bytes(2)?;
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn u32(&mut self) -> Result<u32, Mp4Error> {
+        let bytes = self.bytes(4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn u64(&mut self) -> Result<u64, Mp4Error> {
+        Ok(((self.u32()? as u64) << 32) | self.u32()? as u64)
+    }
+
+    /// Read the number of entries of a table whose entries are `entry_size`
+    /// bytes each, making sure that they're all actually there.
+    fn entry_count(&mut self, entry_size: usize) -> Result<usize, Mp4Error> {
+        let count = self.u32()? as usize;
+        if count > self.data.len() / entry_size {
+            return Err(Mp4Error::Truncated(self.name));
+        }
+        Ok(count)
+    }
+
+    /// Read the version and flags of a "full box".
+    fn version(&mut self) -> Result<u8, Mp4Error> {
+        let version = self.u8()?;
+        self.skip(3)?;
+        Ok(version)
+    }
+
+    /// Read a u32 or a u64 depending on a full box version.
+    fn u32_or_u64(&mut self, version: u8) -> Result<u64, Mp4Error> {
+        if version == 1 {
+            self.u64()
+        } else {
+            self.u32().map(|v| v as u64)
+        }
+    }
+
+    /// Read the length of an MPEG-4 descriptor.
+    fn descriptor_length(&mut self) -> Result<usize, Mp4Error> {
+        let mut length = 0;
+        for _ in 0..4 {
+            let byte = self.u8()?;
+            length = (length << 7) | (byte & 0x7F) as usize;
+            if byte & 0x80 == 0 {
+                break;
+            }
+        }
+        Ok(length)
+    }
+}
+
+/// Iterate over the boxes contained in `data`.
+///
+/// Iteration stops at the first box that isn't entirely contained in `data`.
+fn boxes(data: &[u8]) -> impl Iterator<Item = (BoxType, &[u8])> {
+    let mut data = data;
+    std::iter::from_fn(move || {
+        let (box_type, header_size, size) = box_header(data)?;
+        let payload = data.get(header_size..size)?;
+        data = &data[size..];
+        Some((box_type, payload))
+    })
+}
+
+/// Parse a box header, returning the type, the header size and the total box
+/// size.
+fn box_header(data: &[u8]) -> Option<(BoxType, usize, usize)> {
+    let mut reader = Reader::new(data, "header");
+    let size = reader.u32().ok()? as u64;
+    let box_type: BoxType = reader.bytes(4).ok()?.try_into().ok()?;
+    let (header_size, size) = match size {
+        // The box extends to the end of the file.
+        0 => (8, data.len() as u64),
+        1 => (16, reader.u64().ok()?),
+        size => (8, size),
+    };
+
+    if size < header_size as u64 {
+        return None;
+    }
+    Some((box_type, header_size, usize::try_from(size).ok()?))
+}
+
+fn find_box<'a>(data: &'a [u8], box_type: &BoxType) -> Option<&'a [u8]> {
+    boxes(data).find(|(t, _)| t == box_type).map(|(_, b)| b)
+}
+
+/// Check whether the data looks like the start of an MP4 file.
+pub fn is_mp4(data: &[u8]) -> bool {
+    matches!(
+        data.get(4..8),
+        Some(b"ftyp") | Some(b"moov") | Some(b"mdat") | Some(b"free") | Some(b"skip")
+    )
+}
+
+/// Locate the `moov` box in a (possibly partially downloaded) file.
+///
+/// Returns the position and contents of the box, or `None` if it isn't
+/// completely loaded yet.
+pub fn find_movie_box(data: &[u8]) -> Option<(usize, &[u8])> {
+    let mut position = 0;
+    loop {
+        let (box_type, header_size, size) = box_header(data.get(position..)?)?;
+        if &box_type == b"moov" {
+            let payload = data.get(position + header_size..position + size)?;
+            return Some((position, payload));
+        }
+
+        // Other boxes (mostly `mdat`) can be skipped before they're loaded.
+        position = position.checked_add(size.max(header_size))?;
+    }
+}
+
+/// A single sample (video frame or block of audio) in a track.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sample {
+    /// The byte offset of the sample in the file.
+    pub offset: usize,
+
+    /// The size of the sample in bytes.
+    pub size: usize,
+
+    /// The decoding time of the sample, in milliseconds.
+    pub time: f64,
+
+    /// Whether the sample can be decoded without any previous samples.
+    pub is_keyframe: bool,
+}
+
+impl Sample {
+    /// The position just after the sample in the file, unless that doesn't
+    /// fit in memory.
+    pub fn end(&self) -> Option<usize> {
+        self.offset.checked_add(self.size)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum VideoCodec {
+    /// H.264, configured with an `AVCDecoderConfigurationRecord`.
+    H264 { configuration: Vec<u8> },
+
+    /// Any other codec, identified by its sample entry type.
+    Unsupported(BoxType),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum AudioCodec {
+    /// AAC, configured with an `AudioSpecificConfig`.
+    Aac {
+        configuration: Vec<u8>,
+    },
+
+    Mp3,
+
+    /// Any other codec, identified by its sample entry type.
+    Unsupported(BoxType),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TrackKind {
+    Video {
+        codec: VideoCodec,
+        width: u16,
+        height: u16,
+    },
+    Audio {
+        codec: AudioCodec,
+        sample_rate: u32,
+        channels: u16,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Track {
+    pub kind: TrackKind,
+
+    /// The duration of the track in seconds.
+    pub duration: f64,
+
+    /// All samples of the track, in decoding order.
+    pub samples: Vec<Sample>,
+}
+
+impl Track {
+    /// Find the sample to continue playing from at the given time.
+    ///
+    /// For tracks with keyframes, this is the last keyframe at or before
+    /// `time`; otherwise, it's the first sample at or after `time`.
+    pub fn seek_sample(&self, time: f64) -> usize {
+        if self.samples.iter().all(|s| s.is_keyframe) {
+            self.samples.partition_point(|s| s.time < time)
+        } else {
+            self.samples
+                .iter()
+                .rposition(|s| s.is_keyframe && s.time <= time)
+                .unwrap_or(0)
+        }
+    }
+
+    /// The video frame rate, if this is a video track.
+    pub fn frame_rate(&self) -> Option<f64> {
+        match self.kind {
+            TrackKind::Video { .. } if self.duration > 0.0 => {
+                Some(self.samples.len() as f64 / self.duration)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The parsed contents of a `moov` box.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Movie {
+    /// The duration of the movie in seconds.
+    pub duration: f64,
+
+    /// The position of the `moov` box in the file.
+    pub position: usize,
+
+    pub tracks: Vec<Track>,
+}
+
+impl Movie {
+    /// Parse the contents of a `moov` box found at `position` in the file.
+    pub fn parse(position: usize, moov: &[u8]) -> Result<Self, Mp4Error> {
+        let mut duration = 0.0;
+        let mut tracks = Vec::new();
+
+        for (box_type, data) in boxes(moov) {
+            match &box_type {
+                b"mvhd" => {
+                    let mut reader = Reader::new(data, "mvhd");
+                    let version = reader.version()?;
+                    reader.u32_or_u64(version)?; // Creation time
+                    reader.u32_or_u64(version)?; // Modification time
+                    let timescale = reader.u32()?;
+                    let length = reader.u32_or_u64(version)?;
+                    if timescale != 0 {
+                        duration = length as f64 / timescale as f64;
+                    }
+                }
+                b"trak" => match parse_track(data) {
+                    Ok(Some(track)) => tracks.push(track),
+                    Ok(None) => {}
+                    Err(e) => tracing::warn!("Skipping invalid MP4 track: {}", e),
+                },
+                _ => {}
+            }
+        }
+
+        if tracks.is_empty() {
+            return Err(Mp4Error::NoTracks);
+        }
+
+        Ok(Self {
+            duration,
+            position,
+            tracks,
+        })
+    }
+
+    /// The positions and times of all video keyframes.
+    pub fn seek_points(&self) -> impl Iterator<Item = &Sample> {
+        self.tracks
+            .iter()
+            .find(|t| matches!(t.kind, TrackKind::Video { .. }))
+            .into_iter()
+            .flat_map(|t| t.samples.iter().filter(|s| s.is_keyframe))
+    }
+}
+
+/// Parse a `trak` box.
+///
+/// Tracks that are neither audio nor video (e.g. hint or text tracks) are
+/// ignored.
+fn parse_track(trak: &[u8]) -> Result<Option<Track>, Mp4Error> {
+    let mdia = find_box(trak, b"mdia").ok_or(Mp4Error::Invalid("trak"))?;
+
+    let mdhd = find_box(mdia, b"mdhd").ok_or(Mp4Error::Invalid("mdia"))?;
+    let mut reader = Reader::new(mdhd, "mdhd");
+    let version = reader.version()?;
+    reader.u32_or_u64(version)?; // Creation time
+    reader.u32_or_u64(version)?; // Modification time
+    let timescale = reader.u32()?;
+    let length = reader.u32_or_u64(version)?;
+    if timescale == 0 {
+        return Err(Mp4Error::Invalid("mdhd"));
+    }
+
+    let hdlr = find_box(mdia, b"hdlr").ok_or(Mp4Error::Invalid("mdia"))?;
+    let mut reader = Reader::new(hdlr, "hdlr");
+    reader.version()?;
+    reader.u32()?; // Pre-defined
+    let handler: BoxType = reader.bytes(4)?.try_into().expect("4 bytes");
+    if &handler != b"vide" && &handler != b"soun" {
+        return Ok(None);
+    }
+
+    let stbl = find_box(mdia, b"minf")
+        .and_then(|minf| find_box(minf, b"stbl"))
+        .ok_or(Mp4Error::Invalid("mdia"))?;
+    let stsd = find_box(stbl, b"stsd").ok_or(Mp4Error::Invalid("stbl"))?;
+    let mut reader = Reader::new(stsd, "stsd");
+    reader.version()?;
+    reader.u32()?; // Entry count
+    let (entry_type, entry) = boxes(reader.data).next().ok_or(Mp4Error::Invalid("stsd"))?;
+
+    let kind = if &handler == b"vide" {
+        parse_visual_sample_entry(entry_type, entry)?
+    } else {
+        parse_audio_sample_entry(entry_type, entry)?
+    };
+
+    Ok(Some(Track {
+        kind,
+        duration: length as f64 / timescale as f64,
+        samples: parse_sample_table(stbl, timescale)?,
+    }))
+}
+
+fn parse_visual_sample_entry(entry_type: BoxType, entry: &[u8]) -> Result<TrackKind, Mp4Error> {
+    let mut reader = Reader::new(entry, "stsd");
+    reader.skip(8)?; // Reserved and data reference index
+    reader.skip(16)?; // Pre-defined and reserved
+    let width = reader.u16()?;
+    let height = reader.u16()?;
+    reader.skip(50)?; // Resolution, frame count, compressor name and depth
+
+    let codec = match &entry_type {
+        b"avc1" | b"avc3" => VideoCodec::H264 {
+            configuration: find_box(reader.data, b"avcC")
+                .ok_or(Mp4Error::Invalid("avc1"))?
+                .to_vec(),
+        },
+        _ => VideoCodec::Unsupported(entry_type),
+    };
+
+    Ok(TrackKind::Video {
+        codec,
+        width,
+        height,
+    })
+}
+
+fn parse_audio_sample_entry(entry_type: BoxType, entry: &[u8]) -> Result<TrackKind, Mp4Error> {
+    let mut reader = Reader::new(entry, "stsd");
+    reader.skip(8)?; // Reserved and data reference index
+    let quicktime_version = reader.u16()?;
+    reader.skip(6)?; // Reserved
+    let mut channels = reader.u16()?;
+    reader.skip(6)?; // Sample size, pre-defined and reserved
+    let mut sample_rate = reader.u32()? >> 16;
+
+    // QuickTime sound descriptions carry extra fields.
+    match quicktime_version {
+        1 => reader.skip(16)?,
+        2 => {
+            reader.skip(4)?;
+            sample_rate = f64::from_bits(reader.u64()?) as u32;
+            channels = reader.u32()? as u16;
+            reader.skip(20)?;
+        }
+        _ => {}
+    }
+
+    let codec = match &entry_type {
+        b"mp4a" => match find_box(reader.data, b"esds") {
+            Some(esds) => parse_elementary_stream_descriptor(esds)?,
+            None => AudioCodec::Unsupported(entry_type),
+        },
+        b".mp3" => AudioCodec::Mp3,
+        _ => AudioCodec::Unsupported(entry_type),
+    };
+
+    Ok(TrackKind::Audio {
+        codec,
+        sample_rate,
+        channels,
+    })
+}
+
+/// Determine the codec of an `mp4a` track from its `esds` box.
+fn parse_elementary_stream_descriptor(esds: &[u8]) -> Result<AudioCodec, Mp4Error> {
+    const ES_DESCRIPTOR: u8 = 3;
+    const DECODER_CONFIG_DESCRIPTOR: u8 = 4;
+    const DECODER_SPECIFIC_INFO: u8 = 5;
+
+    let mut reader = Reader::new(esds, "esds");
+    reader.version()?;
+
+    if reader.u8()? != ES_DESCRIPTOR {
+        return Err(Mp4Error::Invalid("esds"));
+    }
+    reader.descriptor_length()?;
+    reader.u16()?; // ES ID
+    let flags = reader.u8()?;
+    if flags & 0x80 != 0 {
+        reader.u16()?; // Depends on ES ID
+    }
+    if flags & 0x40 != 0 {
+        let url_length = reader.u8()?;
+        reader.skip(url_length as usize)?;
+    }
+    if flags & 0x20 != 0 {
+        reader.u16()?; // OCR ES ID
+    }
+
+    if reader.u8()? != DECODER_CONFIG_DESCRIPTOR {
+        return Err(Mp4Error::Invalid("esds"));
+    }
+    reader.descriptor_length()?;
+    let object_type = reader.u8()?;
+    reader.skip(12)?; // Stream type, buffer size and bitrates
+
+    let mut configuration = Vec::new();
+    if reader.u8().ok() == Some(DECODER_SPECIFIC_INFO) {
+        let length = reader.descriptor_length()?;
+        configuration = reader.bytes(length)?.to_vec();
+    }
+
+    Ok(match object_type {
+        // MPEG-4 AAC and MPEG-2 AAC (Main, LC, SSR)
+        0x40 | 0x66 | 0x67 | 0x68 => AudioCodec::Aac { configuration },
+        // MPEG-2 and MPEG-1 audio
+        0x69 | 0x6B => AudioCodec::Mp3,
+        _ => AudioCodec::Unsupported(*b"mp4a"),
+    })
+}
+
+/// The most samples we accept in a track whose samples all have the same size.
+///
+/// Other sample counts are limited by the size of the tables describing them,
+/// but a constant size is only described by a count.
+const MAX_CONSTANT_SIZE_SAMPLES: usize = 1 << 24;
+
+/// Build the list of samples of a track from its `stbl` box.
+fn parse_sample_table(stbl: &[u8], timescale: u32) -> Result<Vec<Sample>, Mp4Error> {
+    // Sample sizes
+    let sizes: Vec<usize> = if let Some(stsz) = find_box(stbl, b"stsz") {
+        let mut reader = Reader::new(stsz, "stsz");
+        reader.version()?;
+        let sample_size = reader.u32()?;
+        if sample_size != 0 {
+            // There's no table to check this count against.
+            let count = reader.u32()? as usize;
+            if count > MAX_CONSTANT_SIZE_SAMPLES {
+                return Err(Mp4Error::Invalid("stsz"));
+            }
+            vec![sample_size as usize; count]
+        } else {
+            let count = reader.entry_count(4)?;
+            (0..count)
+                .map(|_| reader.u32().map(|s| s as usize))
+                .collect::<Result<_, _>>()?
+        }
+    } else if let Some(stz2) = find_box(stbl, b"stz2") {
+        let mut reader = Reader::new(stz2, "stz2");
+        reader.version()?;
+        reader.skip(3)?;
+        let field_size = reader.u8()?;
+        let count = reader.u32()? as usize;
+        if !matches!(field_size, 4 | 8 | 16) {
+            return Err(Mp4Error::Invalid("stz2"));
+        }
+        if count > reader.data.len() * 8 / field_size as usize {
+            return Err(Mp4Error::Truncated("stz2"));
+        }
+        let mut sizes = Vec::with_capacity(count);
+        while sizes.len() < count {
+            match field_size {
+                4 => {
+                    let byte = reader.u8()?;
+                    sizes.push((byte >> 4) as usize);
+                    if sizes.len() < count {
+                        sizes.push((byte & 0xF) as usize);
+                    }
+                }
+                8 => sizes.push(reader.u8()? as usize),
+                16 => sizes.push(reader.u16()? as usize),
+                _ => return Err(Mp4Error::Invalid("stz2")),
+            }
+        }
+        sizes
+    } else {
+        return Err(Mp4Error::Invalid("stbl"));
+    };
+
+    // Chunk offsets
+    let chunk_offsets: Vec<u64> = if let Some(stco) = find_box(stbl, b"stco") {
+        let mut reader = Reader::new(stco, "stco");
+        reader.version()?;
+        let count = reader.entry_count(4)?;
+        (0..count)
+            .map(|_| reader.u32().map(|o| o as u64))
+            .collect::<Result<_, _>>()?
+    } else if let Some(co64) = find_box(stbl, b"co64") {
+        let mut reader = Reader::new(co64, "co64");
+        reader.version()?;
+        let count = reader.entry_count(8)?;
+        (0..count).map(|_| reader.u64()).collect::<Result<_, _>>()?
+    } else {
+        return Err(Mp4Error::Invalid("stbl"));
+    };
+
+    // Sample to chunk mapping, as (first chunk, samples per chunk)
+    let stsc = find_box(stbl, b"stsc").ok_or(Mp4Error::Invalid("stbl"))?;
+    let mut reader = Reader::new(stsc, "stsc");
+    reader.version()?;
+    let count = reader.entry_count(12)?;
+    let mut sample_to_chunk = Vec::with_capacity(count);
+    for _ in 0..count {
+        let first_chunk = reader.u32()?;
+        let samples_per_chunk = reader.u32()?;
+        reader.u32()?; // Sample description index
+        sample_to_chunk.push((first_chunk.max(1) as usize - 1, samples_per_chunk as usize));
+    }
+
+    let mut offsets = Vec::with_capacity(sizes.len());
+    for (index, &(first_chunk, samples_per_chunk)) in sample_to_chunk.iter().enumerate() {
+        let last_chunk = sample_to_chunk
+            .get(index + 1)
+            .map(|&(next, _)| next)
+            .unwrap_or(chunk_offsets.len())
+            .min(chunk_offsets.len());
+        for chunk_offset in chunk_offsets
+            .get(first_chunk..last_chunk)
+            .unwrap_or_default()
+        {
+            let mut offset =
+                usize::try_from(*chunk_offset).map_err(|_| Mp4Error::Invalid("stbl"))?;
+            for _ in 0..samples_per_chunk {
+                let Some(size) = sizes.get(offsets.len()) else {
+                    break;
+                };
+                offsets.push(offset);
+                offset = offset.checked_add(*size).ok_or(Mp4Error::Invalid("stbl"))?;
+            }
+        }
+    }
+
+    // Decoding times
+    let stts = find_box(stbl, b"stts").ok_or(Mp4Error::Invalid("stbl"))?;
+    let mut reader = Reader::new(stts, "stts");
+    reader.version()?;
+    let count = reader.entry_count(8)?;
+    let mut times = Vec::with_capacity(sizes.len());
+    let mut time = 0u64;
+    for _ in 0..count {
+        let sample_count = reader.u32()?;
+        let sample_delta = reader.u32()?;
+        for _ in 0..sample_count {
+            if times.len() >= sizes.len() {
+                break;
+            }
+            times.push(time as f64 * 1000.0 / timescale as f64);
+            time += sample_delta as u64;
+        }
+    }
+
+    // Keyframes; without a sync sample table, every sample is a keyframe.
+    let keyframes = match find_box(stbl, b"stss") {
+        Some(stss) => {
+            let mut reader = Reader::new(stss, "stss");
+            reader.version()?;
+            let count = reader.entry_count(4)?;
+            let mut keyframes = vec![false; sizes.len()];
+            for _ in 0..count {
+                let sample = reader.u32()? as usize;
+                if let Some(keyframe) = sample.checked_sub(1).and_then(|i| keyframes.get_mut(i)) {
+                    *keyframe = true;
+                }
+            }
+            keyframes
+        }
+        None => vec![true; sizes.len()],
+    };
+
+    Ok(offsets
+        .into_iter()
+        .zip(sizes)
+        .zip(times)
+        .zip(keyframes)
+        .map(|(((offset, size), time), is_keyframe)| Sample {
+            offset,
+            size,
+            time,
+            is_keyframe,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mp4_box(box_type: &[u8; 4], payload: &[u8]) -> Vec<u8> {
+        let mut out = ((payload.len() + 8) as u32).to_be_bytes().to_vec();
+        out.extend_from_slice(box_type);
+        out.extend_from_slice(payload);
+        out
+    }
+
+    fn full_box(box_type: &[u8; 4], fields: &[u32]) -> Vec<u8> {
+        let mut payload = vec![0; 4];
+        for field in fields {
+            payload.extend_from_slice(&field.to_be_bytes());
+        }
+        mp4_box(box_type, &payload)
+    }
+
+    fn video_track() -> Vec<u8> {
+        let mut avc1 = vec![0; 24];
+        avc1.extend_from_slice(&320u16.to_be_bytes());
+        avc1.extend_from_slice(&240u16.to_be_bytes());
+        avc1.extend_from_slice(&[0; 50]);
+        avc1.extend(mp4_box(b"avcC", &[1, 2, 3]));
+        let mut stsd = vec![0, 0, 0, 0, 0, 0, 0, 1];
+        stsd.extend(mp4_box(b"avc1", &avc1));
+
+        let stbl = [
+            mp4_box(b"stsd", &stsd),
+            // 4 samples of 1000 units each
+            full_box(b"stts", &[1, 4, 1000]),
+            full_box(b"stss", &[2, 1, 3]),
+            full_box(b"stsz", &[0, 4, 10, 20, 30, 40]),
+            // 2 samples per chunk
+            full_box(b"stsc", &[1, 1, 2, 1]),
+            full_box(b"stco", &[2, 100, 500]),
+        ]
+        .concat();
+
+        let mdia = [
+            full_box(b"mdhd", &[0, 0, 1000, 4000, 0]),
+            full_box(b"hdlr", &[0, u32::from_be_bytes(*b"vide"), 0, 0, 0]),
+            mp4_box(b"minf", &mp4_box(b"stbl", &stbl)),
+        ]
+        .concat();
+
+        mp4_box(b"trak", &mp4_box(b"mdia", &mdia))
+    }
+
+    fn movie() -> Vec<u8> {
+        [full_box(b"mvhd", &[0, 0, 1000, 4000]), video_track()].concat()
+    }
+
+    #[test]
+    fn sample_table() {
+        let movie = Movie::parse(0, &movie()).unwrap();
+        assert_eq!(movie.duration, 4.0);
+        assert_eq!(movie.tracks.len(), 1);
+
+        let track = &movie.tracks[0];
+        assert_eq!(
+            track.kind,
+            TrackKind::Video {
+                codec: VideoCodec::H264 {
+                    configuration: vec![1, 2, 3]
+                },
+                width: 320,
+                height: 240,
+            }
+        );
+        assert_eq!(track.frame_rate(), Some(1.0));
+
+        let samples: Vec<_> = track
+            .samples
+            .iter()
+            .map(|s| (s.offset, s.size, s.time, s.is_keyframe))
+            .collect();
+        assert_eq!(
+            samples,
+            vec![
+                (100, 10, 0.0, true),
+                (110, 20, 1000.0, false),
+                (500, 30, 2000.0, true),
+                (530, 40, 3000.0, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn seek_to_keyframe() {
+        let movie = Movie::parse(0, &movie()).unwrap();
+        let track = &movie.tracks[0];
+        assert_eq!(track.seek_sample(0.0), 0);
+        assert_eq!(track.seek_sample(1500.0), 0);
+        assert_eq!(track.seek_sample(2000.0), 2);
+        assert_eq!(track.seek_sample(3500.0), 2);
+        assert_eq!(
+            movie.seek_points().map(|s| s.time).collect::<Vec<_>>(),
+            vec![0.0, 2000.0]
+        );
+    }
+
+    #[test]
+    fn movie_box_after_media() {
+        let file = [
+            mp4_box(b"ftyp", b"isom\0\0\0\0"),
+            mp4_box(b"mdat", &[0; 64]),
+            mp4_box(b"moov", &movie()),
+        ]
+        .concat();
+
+        assert!(is_mp4(&file));
+        assert_eq!(find_movie_box(&file[..100]), None);
+        let (position, moov) = find_movie_box(&file).unwrap();
+        assert_eq!(position, 88);
+        assert_eq!(moov, &movie()[..]);
+    }
+
+    #[test]
+    fn invalid_box_headers() {
+        // Sizes smaller than the header would never advance.
+        assert_eq!(box_header(&[0, 0, 0, 4, b'm', b'o', b'o', b'v']), None);
+        assert_eq!(find_movie_box(&[0, 0, 0, 4, b'f', b't', b'y', b'p']), None);
+
+        // 64-bit sizes that are too small or don't fit in the data.
+        let mut large = vec![0, 0, 0, 1];
+        large.extend_from_slice(b"moov");
+        large.extend_from_slice(&8u64.to_be_bytes());
+        assert_eq!(box_header(&large), None);
+        large[8..16].copy_from_slice(&u64::MAX.to_be_bytes());
+        assert_eq!(find_movie_box(&large), None);
+
+        // A box running past the end of its parent ends the iteration.
+        let truncated = [mp4_box(b"free", &[]), mp4_box(b"moov", &[0; 16])].concat();
+        assert_eq!(boxes(&truncated[..20]).count(), 1);
+        assert_eq!(find_movie_box(&truncated[..20]), None);
+    }
+
+    #[test]
+    fn invalid_movie_header() {
+        let mvhd = full_box(b"mvhd", &[0, 0]);
+        assert_eq!(
+            Movie::parse(0, &[mvhd, video_track()].concat()),
+            Err(Mp4Error::Truncated("mvhd"))
+        );
+    }
+
+    #[test]
+    fn invalid_tracks_are_skipped() {
+        let truncated_mdhd = mp4_box(b"trak", &mp4_box(b"mdia", &full_box(b"mdhd", &[0, 0])));
+        let no_media = mp4_box(b"trak", &[]);
+        let mvhd = full_box(b"mvhd", &[0, 0, 1000, 4000]);
+
+        let movie = Movie::parse(
+            0,
+            &[
+                mvhd.clone(),
+                truncated_mdhd.clone(),
+                video_track(),
+                no_media.clone(),
+            ]
+            .concat(),
+        )
+        .unwrap();
+        assert_eq!(movie.tracks.len(), 1);
+
+        assert_eq!(
+            Movie::parse(0, &[mvhd.clone(), truncated_mdhd, no_media].concat()),
+            Err(Mp4Error::NoTracks)
+        );
+        assert_eq!(Movie::parse(0, &mvhd), Err(Mp4Error::NoTracks));
+        assert_eq!(Movie::parse(0, &[]), Err(Mp4Error::NoTracks));
+    }
+
+    #[test]
+    fn invalid_sample_entries() {
+        // An `avc1` entry without its decoder configuration.
+        assert_eq!(
+            parse_visual_sample_entry(*b"avc1", &[0; 78]),
+            Err(Mp4Error::Invalid("avc1"))
+        );
+        assert_eq!(
+            parse_visual_sample_entry(*b"avc1", &[0; 20]),
+            Err(Mp4Error::Truncated("stsd"))
+        );
+        assert_eq!(
+            parse_audio_sample_entry(*b"mp4a", &[0; 10]),
+            Err(Mp4Error::Truncated("stsd"))
+        );
+
+        // Elementary stream descriptors with the wrong tags or cut short.
+        assert_eq!(
+            parse_elementary_stream_descriptor(&[0, 0, 0, 0, 4]),
+            Err(Mp4Error::Invalid("esds"))
+        );
+        assert_eq!(
+            parse_elementary_stream_descriptor(&[0, 0, 0, 0, 3, 3, 0, 1, 0, 3]),
+            Err(Mp4Error::Invalid("esds"))
+        );
+        assert_eq!(
+            parse_elementary_stream_descriptor(&[0, 0, 0, 0, 3, 20, 0, 1, 0, 4, 17, 0x40]),
+            Err(Mp4Error::Truncated("esds"))
+        );
+        let mut esds = vec![0, 0, 0, 0, 3, 25, 0, 1, 0, 4, 17, 0x40];
+        esds.extend_from_slice(&[0; 12]);
+        // The decoder specific info claims more bytes than there are.
+        esds.extend_from_slice(&[5, 10, 0x12, 0x10]);
+        assert_eq!(
+            parse_elementary_stream_descriptor(&esds),
+            Err(Mp4Error::Truncated("esds"))
+        );
+    }
+
+    #[test]
+    fn missing_sample_tables() {
+        let stsz = full_box(b"stsz", &[10, 2]);
+        let stco = full_box(b"stco", &[1, 100]);
+        let stsc = full_box(b"stsc", &[1, 1, 2, 1]);
+        let stts = full_box(b"stts", &[1, 2, 1000]);
+
+        let parse = |boxes: &[&Vec<u8>]| {
+            let stbl: Vec<u8> = boxes.iter().flat_map(|b| b.iter().copied()).collect();
+            parse_sample_table(&stbl, 1000)
+        };
+        assert_eq!(
+            parse(&[&stco, &stsc, &stts]),
+            Err(Mp4Error::Invalid("stbl"))
+        );
+        assert_eq!(
+            parse(&[&stsz, &stsc, &stts]),
+            Err(Mp4Error::Invalid("stbl"))
+        );
+        assert_eq!(
+            parse(&[&stsz, &stco, &stts]),
+            Err(Mp4Error::Invalid("stbl"))
+        );
+        assert_eq!(
+            parse(&[&stsz, &stco, &stsc]),
+            Err(Mp4Error::Invalid("stbl"))
+        );
+        assert_eq!(
+            parse(&[&full_box(b"stz2", &[5, 2, 0]), &stco, &stsc, &stts]),
+            Err(Mp4Error::Invalid("stz2"))
+        );
+
+        // Samples without a decoding time are dropped.
+        let short_stts = full_box(b"stts", &[1, 1, 1000]);
+        assert_eq!(
+            parse(&[&stsz, &stco, &stsc, &short_stts]).map(|s| s.len()),
+            Ok(1)
+        );
+    }
+
+    fn parse_stbl(boxes: &[Vec<u8>]) -> Result<Vec<Sample>, Mp4Error> {
+        let stbl = [
+            &[
+                full_box(b"stts", &[1, 2, 1000]),
+                full_box(b"stsc", &[1, 1, 2, 1]),
+            ][..],
+            boxes,
+        ]
+        .concat()
+        .concat();
+        parse_sample_table(&stbl, 1000)
+    }
+
+    #[test]
+    fn oversized_counts() {
+        let stco = full_box(b"stco", &[1, 100]);
+        assert_eq!(
+            parse_stbl(&[full_box(b"stsz", &[0, u32::MAX, 10]), stco.clone()]),
+            Err(Mp4Error::Truncated("stsz"))
+        );
+        assert_eq!(
+            parse_stbl(&[full_box(b"stsz", &[10, u32::MAX]), stco.clone()]),
+            Err(Mp4Error::Invalid("stsz"))
+        );
+        assert_eq!(
+            parse_stbl(&[full_box(b"stsz", &[10, 2]), full_box(b"stco", &[u32::MAX])]),
+            Err(Mp4Error::Truncated("stco"))
+        );
+        assert_eq!(
+            parse_stbl(&[full_box(b"stsz", &[10, 2]), stco]).map(|s| s.len()),
+            Ok(2)
+        );
+    }
+
+    #[test]
+    fn overflowing_offsets() {
+        // Two samples in a chunk starting just before the end of the address
+        // space.
+        let co64 = full_box(b"co64", &[1, u32::MAX, u32::MAX - 15]);
+        let result = parse_stbl(&[full_box(b"stsz", &[10, 2]), co64]);
+        if usize::BITS == 64 {
+            assert_eq!(result, Err(Mp4Error::Invalid("stbl")));
+        } else {
+            assert!(result.is_err());
+        }
+
+        let sample = Sample {
+            offset: usize::MAX - 5,
+            size: 10,
+            time: 0.0,
+            is_keyframe: true,
+        };
+        assert_eq!(sample.end(), None);
+    }
+}
@@ -1,5 +1,9 @@
 use gc_arena::Collect;
-use std::sync::Arc;
+use std::fmt;
+use std::ptr::{self, NonNull};
+use std::slice;
+use std::sync::atomic::{AtomicBool, AtomicPtr, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
 use swf::{CharacterId, Fixed8, HeaderExt, Rectangle, TagCode, Twips};
 use thiserror::Error;
 use url::Url;
@@ -49,7 +53,7 @@ pub struct SwfMovie {
     header: HeaderExt,
 
     /// Uncompressed SWF data.
-    data: Vec<u8>,
+    data: SwfData,
 
     /// The URL the SWF was downloaded from.
     url: String,
@@ -64,9 +68,6 @@ pub struct SwfMovie {
     /// The suggest encoding for this SWF.
     encoding: &'static swf::Encoding,
 
-    /// The compressed length of the entire datastream
-    compressed_len: usize,
-
     /// Whether this SwfMovie actually represents a loaded movie or fills in for
     /// something else, like an loaded image, filler movie, or error state.
     is_movie: bool,
@@ -77,12 +78,11 @@ impl SwfMovie {
     pub fn empty(swf_version: u8) -> Self {
         Self {
             header: HeaderExt::default_with_swf_version(swf_version),
-            data: vec![],
+            data: SwfData::new(vec![], 0),
             url: "file:///".into(),
             loader_url: None,
             parameters: Vec::new(),
             encoding: swf::UTF_8,
-            compressed_len: 0,
             is_movie: false,
         }
     }
@@ -94,8 +94,7 @@ impl SwfMovie {
     pub fn fake_with_compressed_len(swf_version: u8, compressed_len: usize) -> Self {
         Self {
             header: HeaderExt::default_with_swf_version(swf_version),
-            compressed_len,
-            data: Vec::new(),
+            data: SwfData::new(Vec::new(), compressed_len),
             url: "file:///".into(),
             loader_url: None,
             parameters: Vec::new(),
@@ -107,10 +106,10 @@ impl SwfMovie {
     /// Like `fake_with_compressed_len`, but uses actual data.
     /// This is used when loading a Bitmap to expose the underlying content
     pub fn fake_with_compressed_data(swf_version: u8, compressed_data: Vec<u8>) -> Self {
+        let compressed_len = compressed_data.len();
         Self {
             header: HeaderExt::default_with_swf_version(swf_version),
-            compressed_len: compressed_data.len(),
-            data: compressed_data,
+            data: SwfData::new(compressed_data, compressed_len),
             url: "file:///".into(),
             loader_url: None,
             parameters: Vec::new(),
@@ -127,12 +126,11 @@ impl SwfMovie {
     pub fn error_movie(movie_url: String) -> Self {
         Self {
             header: HeaderExt::default_error_header(),
-            data: vec![],
+            data: SwfData::new(vec![], 0),
             url: movie_url,
             loader_url: None,
             parameters: Vec::new(),
             encoding: swf::UTF_8,
-            compressed_len: 0,
             is_movie: false,
         }
     }
@@ -162,28 +160,56 @@ impl SwfMovie {
         let encoding = swf::SwfStr::encoding_for_version(swf_buf.header.version());
         let mut movie = Self {
             header: swf_buf.header,
-            data: swf_buf.data,
+            data: SwfData::new(swf_buf.data, compressed_len),
             url,
             loader_url,
             parameters: Vec::new(),
             encoding,
-            compressed_len,
             is_movie: true,
         };
         movie.append_parameters_from_url();
         Ok(movie)
     }
 
+    /// Construct a movie that is still being downloaded.
+    ///
+    /// `tag_stream_len` is the length of the uncompressed tag stream, as given by
+    /// the SWF header. No data is available at first: it has to be provided with
+    /// `append_data` as it arrives, followed by a call to `finish_loading`.
+    ///
+    /// `compressed_len` is the expected length of the entire datastream. If it
+    /// isn't known, the length downloaded so far should be given and updated with
+    /// `set_compressed_len`.
+    pub fn from_loading_data(
+        header: HeaderExt,
+        tag_stream_len: usize,
+        url: String,
+        loader_url: Option<String>,
+        compressed_len: usize,
+    ) -> Self {
+        let encoding = swf::SwfStr::encoding_for_version(header.version());
+        let mut movie = Self {
+            header,
+            data: SwfData::loading(tag_stream_len, compressed_len),
+            url,
+            loader_url,
+            parameters: Vec::new(),
+            encoding,
+            is_movie: true,
+        };
+        movie.append_parameters_from_url();
+        movie
+    }
+
     /// Construct a movie based on a loaded image (JPEG, GIF or PNG).
     pub fn from_loaded_image(url: String, length: usize) -> Self {
         let mut movie = Self {
             header: HeaderExt::default_with_uncompressed_len(length as i32),
-            data: vec![],
+            data: SwfData::new(vec![], length),
             url,
             loader_url: None,
             parameters: Vec::new(),
             encoding: swf::UTF_8,
-            compressed_len: length,
             is_movie: false,
         };
         movie.append_parameters_from_url();
@@ -215,8 +241,53 @@ impl SwfMovie {
         self.header.version()
     }
 
+    /// The uncompressed data of the SWF that is available so far.
     pub fn data(&self) -> &[u8] {
-        &self.data
+        self.data.get()
+    }
+
+    /// The length of the uncompressed data of the SWF, once it has fully loaded.
+    ///
+    /// While the movie is loading, this is the length given by the SWF header,
+    /// unless more data than that has already arrived.
+    pub fn total_data_len(&self) -> usize {
+        self.data.expected_len.max(self.data.get().len())
+    }
+
+    /// Whether all of the data of this movie is available.
+    pub fn is_loaded(&self) -> bool {
+        self.data.is_loaded()
+    }
+
+    /// Append newly arrived data to a movie created by `from_loading_data`.
+    ///
+    /// Data that is already available doesn't change, so it can still be
+    /// borrowed from `data`.
+    pub fn append_data(&self, data: &[u8]) {
+        if self.is_loaded() {
+            tracing::warn!("Attempted to append data to a movie that has already loaded");
+            return;
+        }
+        self.data.append(data);
+    }
+
+    /// Update the compressed length of a movie whose total length isn't known yet.
+    pub fn set_compressed_len(&self, compressed_len: usize) {
+        self.data
+            .compressed_len
+            .store(compressed_len, Ordering::Relaxed);
+    }
+
+    /// Mark all of the data of this movie as available.
+    pub fn finish_loading(&self, compressed_len: usize) {
+        if self.is_loaded() {
+            return;
+        }
+        self.data.finish();
+        if self.data.get().len() != self.data.expected_len {
+            tracing::warn!("SWF length doesn't match header, may be corrupt");
+        }
+        self.set_compressed_len(compressed_len);
     }
 
     /// Returns the suggested string encoding for the given SWF version.
@@ -260,7 +331,7 @@ impl SwfMovie {
     }
 
     pub fn compressed_len(&self) -> usize {
-        self.compressed_len
+        self.data.compressed_len.load(Ordering::Relaxed)
     }
 
     pub fn uncompressed_len(&self) -> i32 {
@@ -288,6 +359,171 @@ impl SwfMovie {
     }
 }
 
+/// The uncompressed data of a `SwfMovie`, which may still be arriving over the network.
+///
+/// The data is written to a single buffer, which is allocated up front with the
+/// length given by the SWF header. Data is only ever appended, so data that has
+/// already arrived never changes, and can be borrowed while more data is written
+/// after it. A movie that turns out to be longer than its header says is moved to
+/// a larger buffer, and the previous buffer is kept until the movie is dropped,
+/// as its data may still be borrowed.
+struct SwfData {
+    /// The start of the current buffer.
+    buffer: AtomicPtr<u8>,
+
+    /// The number of bytes at the start of the current buffer that have been
+    /// written. These bytes are never written again.
+    len: AtomicUsize,
+
+    /// All of the buffers, the last of which is the current one.
+    /// Held while appending data, so that there is only ever one writer.
+    buffers: Mutex<Vec<Buffer>>,
+
+    /// Whether all of the data has arrived.
+    is_loaded: AtomicBool,
+
+    /// The length of the data given by the SWF header.
+    expected_len: usize,
+
+    /// The compressed length of the entire datastream.
+    compressed_len: AtomicUsize,
+}
+
+impl SwfData {
+    /// Data that is entirely available.
+    fn new(data: Vec<u8>, compressed_len: usize) -> Self {
+        let len = data.len();
+        Self::from_buffer(
+            Buffer::new(data.into_boxed_slice()),
+            len,
+            true,
+            len,
+            compressed_len,
+        )
+    }
+
+    /// Data that will arrive later, expected to be `expected_len` bytes long.
+    fn loading(expected_len: usize, compressed_len: usize) -> Self {
+        let buffer = Buffer::new(vec![0; expected_len].into_boxed_slice());
+        Self::from_buffer(buffer, 0, false, expected_len, compressed_len)
+    }
+
+    fn from_buffer(
+        buffer: Buffer,
+        len: usize,
+        is_loaded: bool,
+        expected_len: usize,
+        compressed_len: usize,
+    ) -> Self {
+        Self {
+            buffer: AtomicPtr::new(buffer.start()),
+            len: AtomicUsize::new(len),
+            buffers: Mutex::new(vec![buffer]),
+            is_loaded: AtomicBool::new(is_loaded),
+            expected_len,
+            compressed_len: AtomicUsize::new(compressed_len),
+        }
+    }
+
+    fn get(&self) -> &[u8] {
+        // The length is loaded first: a buffer is always published before the
+        // length that it holds, and every buffer holds all of the data that was
+        // written before it was published.
+        let len = self.len.load(Ordering::Acquire);
+        let buffer = self.buffer.load(Ordering::Acquire);
+        // SAFETY: The first `len` bytes of the buffer have been written, and are
+        // never written again. The buffer lives as long as `self`.
+        unsafe { slice::from_raw_parts(buffer, len) }
+    }
+
+    fn is_loaded(&self) -> bool {
+        self.is_loaded.load(Ordering::Acquire)
+    }
+
+    /// Append newly arrived data.
+    fn append(&self, data: &[u8]) {
+        let mut buffers = self.buffers.lock().unwrap_or_else(PoisonError::into_inner);
+        let len = self.len.load(Ordering::Relaxed);
+        let new_len = len + data.len();
+        let current = buffers.last().expect("There is always a buffer");
+        if new_len > current.capacity() {
+            // The movie is longer than its header says.
+            let mut larger = vec![0; new_len.max(current.capacity() * 2)];
+            larger[..len].copy_from_slice(self.get());
+            larger[len..new_len].copy_from_slice(data);
+            let larger = Buffer::new(larger.into_boxed_slice());
+            self.buffer.store(larger.start(), Ordering::Release);
+            buffers.push(larger);
+        } else {
+            // SAFETY: The bytes past `len` aren't borrowed, and the buffer has
+            // room for `data`. Writers are serialized by `buffers`.
+            unsafe {
+                ptr::copy_nonoverlapping(data.as_ptr(), current.start().add(len), data.len());
+            }
+        }
+        self.len.store(new_len, Ordering::Release);
+    }
+
+    /// Mark all of the data that has arrived as complete.
+    fn finish(&self) {
+        self.is_loaded.store(true, Ordering::Release);
+    }
+}
+
+impl Clone for SwfData {
+    fn clone(&self) -> Self {
+        let _buffers = self.buffers.lock().unwrap_or_else(PoisonError::into_inner);
+        let data = self.get();
+        let mut buffer = vec![0; self.expected_len.max(data.len())];
+        buffer[..data.len()].copy_from_slice(data);
+        Self::from_buffer(
+            Buffer::new(buffer.into_boxed_slice()),
+            data.len(),
+            self.is_loaded(),
+            self.expected_len,
+            self.compressed_len.load(Ordering::Relaxed),
+        )
+    }
+}
+
+impl fmt::Debug for SwfData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SwfData")
+            .field("len", &self.get().len())
+            .field("expected_len", &self.expected_len)
+            .field("is_loaded", &self.is_loaded())
+            .finish()
+    }
+}
+
+/// A buffer of `SwfData`, which is freed when the data is dropped.
+struct Buffer(NonNull<[u8]>);
+
+// SAFETY: The buffer is owned, like a `Box<[u8]>`.
+unsafe impl Send for Buffer {}
+
+impl Buffer {
+    fn new(buffer: Box<[u8]>) -> Self {
+        Self(NonNull::from(Box::leak(buffer)))
+    }
+
+    fn start(&self) -> *mut u8 {
+        self.0.as_ptr().cast()
+    }
+
+    fn capacity(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl Drop for Buffer {
+    fn drop(&mut self) {
+        // SAFETY: The buffer was leaked from a box in `new`, and the data that
+        // borrowed it is being dropped.
+        drop(unsafe { Box::from_raw(self.0.as_ptr()) });
+    }
+}
+
 /// A shared-ownership reference to some portion of an SWF datastream.
 #[derive(Debug, Clone, Collect)]
 #[collect(no_drop)]
@@ -299,7 +535,7 @@ pub struct SwfSlice {
 
 impl From<Arc<SwfMovie>> for SwfSlice {
     fn from(movie: Arc<SwfMovie>) -> Self {
-        let end = movie.data().len();
+        let end = movie.total_data_len();
 
         Self {
             movie,
@@ -430,8 +666,31 @@ impl SwfSlice {
     }
 
     /// Convert the SwfSlice into a standard data slice.
+    ///
+    /// If the movie is still loading, this only includes the data that is available so far.
     pub fn data(&self) -> &[u8] {
-        &self.movie.data()[self.start..self.end]
+        let data = self.movie.data();
+        &data[self.start.min(data.len())..self.end.min(data.len())]
+    }
+
+    /// Whether all of the data of this slice is available.
+    ///
+    /// A slice of an entire movie that is still loading may grow with
+    /// `extend_to_movie_data`, so it isn't loaded until the movie is.
+    pub fn is_loaded(&self) -> bool {
+        self.movie.is_loaded() || (!self.is_entire_movie() && self.end <= self.movie.data().len())
+    }
+
+    /// Extend a slice of an entire movie to include any data that has arrived
+    /// past the length given by the SWF header.
+    pub fn extend_to_movie_data(&mut self) {
+        if self.is_entire_movie() {
+            self.end = self.end.max(self.movie.total_data_len());
+        }
+    }
+
+    fn is_entire_movie(&self) -> bool {
+        self.start == 0 && self.end >= self.movie.data.expected_len
     }
 
     /// Get the version of the SWF this data comes from.
@@ -457,6 +716,24 @@ impl SwfSlice {
     }
 }
 
+/// Shorten a SWF stream reader to the tags that are complete.
+///
+/// This allows decoding the tags of a movie that is still loading, without
+/// running into a tag that has only partially arrived.
+pub fn truncate_to_complete_tags(reader: &mut SwfStream<'_>) {
+    let data = reader.get_ref();
+    let mut tags = SwfStream::new(data, reader.version());
+    let mut complete_len = 0;
+    while let Ok((_, tag_len)) = tags.read_tag_code_and_length() {
+        let Some(rest) = tags.get_ref().get(tag_len..) else {
+            break;
+        };
+        *tags.get_mut() = rest;
+        complete_len = data.len() - rest.len();
+    }
+    *reader.get_mut() = &data[..complete_len];
+}
+
 /// Decode tags from a SWF stream reader.
 ///
 /// The given `tag_callback` will be called for each decoded tag. It will be
@@ -0,0 +1,200 @@
+//! Text snapshots, which read and select the static text of a movie clip.
+
+use crate::display_object::{
+    DisplayObject, StaticTextChar, TDisplayObject, TDisplayObjectContainer, Text,
+};
+use crate::library::Library;
+use gc_arena::Mutation;
+use ruffle_render::matrix::Matrix;
+use std::ops::Range;
+use swf::{Color, Point, Twips};
+
+/// A character of static text in a snapshot.
+#[derive(Clone, Debug)]
+pub struct SnapshotChar<'gc> {
+    /// The static text that the character belongs to.
+    pub text: Text<'gc>,
+
+    /// The index of the character within `text`.
+    pub index: usize,
+
+    pub info: StaticTextChar,
+
+    /// The transform from the coordinates of the character to the coordinates of
+    /// the container of the snapshot, positioned at the character's origin.
+    pub matrix: Matrix,
+
+    /// Whether the character is the first of a line.
+    pub starts_line: bool,
+}
+
+impl SnapshotChar<'_> {
+    pub fn is_selected(&self) -> bool {
+        self.text.is_char_selected(self.index)
+    }
+
+    /// The corners of the box around the character, in the coordinates of the container:
+    /// bottom-left, bottom-right, top-right and top-left.
+    pub fn corners(&self) -> [Point<Twips>; 4] {
+        let (left, right) = (Twips::ZERO, self.info.advance);
+        let (top, bottom) = (-self.info.ascent, self.info.descent);
+        [
+            self.matrix * Point::new(left, bottom),
+            self.matrix * Point::new(right, bottom),
+            self.matrix * Point::new(right, top),
+            self.matrix * Point::new(left, top),
+        ]
+    }
+}
+
+/// The static text of a movie clip, as used by `TextSnapshot`.
+///
+/// Only the static text that is directly on the timeline of the clip is included,
+/// in depth order.
+pub struct TextSnapshot<'gc> {
+    chars: Vec<SnapshotChar<'gc>>,
+}
+
+impl<'gc> TextSnapshot<'gc> {
+    pub fn new(library: &Library<'gc>, container: DisplayObject<'gc>) -> Self {
+        let mut chars = Vec::new();
+        let Some(container) = container.as_container() else {
+            return Self { chars };
+        };
+
+        for child in container.iter_render_list() {
+            let DisplayObject::Text(text) = child else {
+                continue;
+            };
+            let text_matrix = *child.base().matrix() * text.text_transform();
+
+            let mut previous_y = None;
+            for (index, info) in text.chars(library).into_iter().enumerate() {
+                let starts_line = previous_y.is_some_and(|y| y != info.position.y)
+                    || (previous_y.is_none() && !chars.is_empty());
+                previous_y = Some(info.position.y);
+
+                let matrix = text_matrix * Matrix::translate(info.position.x, info.position.y);
+                chars.push(SnapshotChar {
+                    text,
+                    index,
+                    info,
+                    matrix,
+                    starts_line,
+                });
+            }
+        }
+
+        Self { chars }
+    }
+
+    pub fn chars(&self) -> &[SnapshotChar<'gc>] {
+        &self.chars
+    }
+
+    pub fn char_count(&self) -> usize {
+        self.chars.len()
+    }
+
+    /// Clamp a range of character indices given to ActionScript to this snapshot.
+    pub fn clamp_range(&self, begin: i32, end: i32) -> Range<usize> {
+        let len = self.chars.len();
+        let begin = begin.clamp(0, len as i32) as usize;
+        let end = end.clamp(0, len as i32) as usize;
+        begin..end.max(begin)
+    }
+
+    fn collect_text<'a>(
+        chars: impl Iterator<Item = &'a SnapshotChar<'gc>>,
+        include_line_endings: bool,
+    ) -> String
+    where
+        'gc: 'a,
+    {
+        let mut text = String::new();
+        for (i, c) in chars.enumerate() {
+            if include_line_endings && i > 0 && c.starts_line {
+                text.push('\n');
+            }
+            text.push(c.info.character);
+        }
+        text
+    }
+
+    pub fn text(&self, range: Range<usize>, include_line_endings: bool) -> String {
+        Self::collect_text(self.chars[range].iter(), include_line_endings)
+    }
+
+    pub fn selected_text(&self, include_line_endings: bool) -> String {
+        Self::collect_text(
+            self.chars.iter().filter(|c| c.is_selected()),
+            include_line_endings,
+        )
+    }
+
+    /// Find the first occurrence of `needle` at or after `begin`.
+    pub fn find_text(&self, begin: usize, needle: &str, case_sensitive: bool) -> Option<usize> {
+        let fold = |c: char| {
+            if case_sensitive {
+                c
+            } else {
+                c.to_lowercase().next().unwrap_or(c)
+            }
+        };
+        let needle: Vec<char> = needle.chars().map(fold).collect();
+        if needle.is_empty() || begin >= self.chars.len() {
+            return None;
+        }
+
+        let haystack: Vec<char> = self.chars.iter().map(|c| fold(c.info.character)).collect();
+        haystack[begin..]
+            .windows(needle.len())
+            .position(|window| window == needle.as_slice())
+            .map(|i| i + begin)
+    }
+
+    /// Whether any of the characters in `range` are selected.
+    pub fn is_selected(&self, range: Range<usize>) -> bool {
+        self.chars[range].iter().any(|c| c.is_selected())
+    }
+
+    pub fn set_selected(&self, gc_context: &Mutation<'gc>, range: Range<usize>, selected: bool) {
+        for c in &self.chars[range] {
+            c.text
+                .set_chars_selected(gc_context, c.index..c.index + 1, selected);
+        }
+    }
+
+    /// Set the highlight color of every static text in this snapshot.
+    pub fn set_select_color(&self, gc_context: &Mutation<'gc>, color: Color) {
+        let mut previous: Option<Text<'gc>> = None;
+        for c in &self.chars {
+            if previous.map_or(true, |p| !DisplayObject::ptr_eq(p.into(), c.text.into())) {
+                c.text.set_select_color(gc_context, color);
+                previous = Some(c.text);
+            }
+        }
+    }
+
+    /// Find the character closest to `point`, if it's no further than `max_distance` away.
+    ///
+    /// A point inside the box of a character is at a distance of zero.
+    pub fn hit_test_near_pos(&self, point: Point<Twips>, max_distance: f64) -> Option<usize> {
+        let mut closest = None;
+        for (i, c) in self.chars.iter().enumerate() {
+            let corners = c.corners();
+            let x_min = corners.iter().map(|p| p.x).min().unwrap_or_default();
+            let x_max = corners.iter().map(|p| p.x).max().unwrap_or_default();
+            let y_min = corners.iter().map(|p| p.y).min().unwrap_or_default();
+            let y_max = corners.iter().map(|p| p.y).max().unwrap_or_default();
+
+            let dx = (x_min - point.x).max(point.x - x_max).max(Twips::ZERO);
+            let dy = (y_min - point.y).max(point.y - y_max).max(Twips::ZERO);
+            let distance = dx.to_pixels().hypot(dy.to_pixels());
+            if distance <= max_distance && closest.map_or(true, |(_, d)| distance < d) {
+                closest = Some((i, distance));
+            }
+        }
+        closest.map(|(i, _)| i)
+    }
+}
@@ -39,6 +39,7 @@ debug-menu-open-movie = View Movie
 debug-menu-open-movie-list = Show Known Movies
 debug-menu-open-domain-list = Show Domains
 debug-menu-search-display-objects = Search Display Objects...
+debug-menu-open-avm2-debugger = Show AVM2 Debugger
 
 view-menu = View
 view-menu-fullscreen = Full Screen
@@ -2,6 +2,7 @@ mod audio;
 mod external_interface;
 mod fscommand;
 mod navigator;
+mod print;
 mod ui;
 
 pub use audio::CpalAudioBackend;
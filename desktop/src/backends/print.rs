@@ -0,0 +1,204 @@
+//! Saving of printed pages, as PNG images or a PDF document.
+
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use ruffle_core::backend::ui::PrintPage;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Save the printed pages to `path`, as a PDF if it has a `.pdf` extension
+/// and as PNG images otherwise.
+pub fn save_pages(path: &Path, pages: &[PrintPage]) -> anyhow::Result<()> {
+    let is_pdf = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("pdf"));
+    if is_pdf {
+        std::fs::write(path, write_pdf(pages)?)?;
+        return Ok(());
+    }
+
+    for (index, page) in pages.iter().enumerate() {
+        let image =
+            image::RgbaImage::from_raw(page.image_width, page.image_height, page.rgba.clone())
+                .ok_or_else(|| anyhow::anyhow!("Printed page has an invalid size"))?;
+        let page_path = if pages.len() == 1 {
+            path.to_owned()
+        } else {
+            numbered_path(path, index + 1)
+        };
+        image.save_with_format(page_path, image::ImageFormat::Png)?;
+    }
+    Ok(())
+}
+
+/// Turn `print.png` into `print-2.png`, for saving every page as a separate image.
+fn numbered_path(path: &Path, number: usize) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let mut file_name = format!("{stem}-{number}");
+    if let Some(extension) = path.extension() {
+        file_name.push('.');
+        file_name.push_str(&extension.to_string_lossy());
+    }
+    path.with_file_name(file_name)
+}
+
+/// A PDF document being written, which keeps track of where each object starts.
+struct PdfWriter {
+    output: Vec<u8>,
+    offsets: Vec<usize>,
+}
+
+impl PdfWriter {
+    fn new() -> Self {
+        Self {
+            output: b"%PDF-1.4\n".to_vec(),
+            offsets: Vec::new(),
+        }
+    }
+
+    /// Write the next object, which must have the id `self.offsets.len() + 1`.
+    fn object(&mut self, dictionary: &str, stream: Option<&[u8]>) {
+        self.offsets.push(self.output.len());
+        let id = self.offsets.len();
+        self.output
+            .extend_from_slice(format!("{id} 0 obj\n{dictionary}\n").as_bytes());
+        if let Some(stream) = stream {
+            self.output.extend_from_slice(b"stream\n");
+            self.output.extend_from_slice(stream);
+            self.output.extend_from_slice(b"\nendstream\n");
+        }
+        self.output.extend_from_slice(b"endobj\n");
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        let xref_offset = self.output.len();
+        let size = self.offsets.len() + 1;
+        let mut xref = format!("xref\n0 {size}\n0000000000 65535 f \n");
+        for offset in &self.offsets {
+            xref.push_str(&format!("{offset:010} 00000 n \n"));
+        }
+        xref.push_str(&format!(
+            "trailer\n<< /Size {size} /Root 1 0 R >>\nstartxref\n{xref_offset}\n%%EOF\n"
+        ));
+        self.output.extend_from_slice(xref.as_bytes());
+        self.output
+    }
+}
+
+/// Write the pages as a PDF document, with each page holding its image.
+fn write_pdf(pages: &[PrintPage]) -> io::Result<Vec<u8>> {
+    let mut pdf = PdfWriter::new();
+
+    // Objects 1 and 2 are the catalog and page tree,
+    // followed by a page, its contents and its image for every page.
+    let page_id = |index: usize| 3 + index * 3;
+    let kids: Vec<String> = (0..pages.len())
+        .map(|index| format!("{} 0 R", page_id(index)))
+        .collect();
+    pdf.object("<< /Type /Catalog /Pages 2 0 R >>", None);
+    pdf.object(
+        &format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            kids.join(" "),
+            pages.len()
+        ),
+        None,
+    );
+
+    for (index, page) in pages.iter().enumerate() {
+        let contents_id = page_id(index) + 1;
+        let image_id = page_id(index) + 2;
+
+        pdf.object(
+            &format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] \
+                 /Resources << /XObject << /Page {image_id} 0 R >> >> \
+                 /Contents {contents_id} 0 R >>",
+                page.paper_width, page.paper_height
+            ),
+            None,
+        );
+
+        // PDF coordinates start from the bottom-left corner of the paper.
+        let contents = format!(
+            "q {} 0 0 {} {} {} cm /Page Do Q",
+            page.width,
+            page.height,
+            page.x,
+            page.paper_height - page.y - page.height
+        );
+        pdf.object(
+            &format!("<< /Length {} >>", contents.len()),
+            Some(contents.as_bytes()),
+        );
+
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        for pixel in page.rgba.chunks_exact(4) {
+            encoder.write_all(&pixel[..3])?;
+        }
+        let image = encoder.finish()?;
+        pdf.object(
+            &format!(
+                "<< /Type /XObject /Subtype /Image /Width {} /Height {} \
+                 /ColorSpace /DeviceRGB /BitsPerComponent 8 /Filter /FlateDecode /Length {} >>",
+                page.image_width,
+                page.image_height,
+                image.len()
+            ),
+            Some(&image),
+        );
+    }
+
+    Ok(pdf.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page() -> PrintPage {
+        PrintPage {
+            paper_width: 612.0,
+            paper_height: 792.0,
+            x: 18.0,
+            y: 18.0,
+            width: 1.0,
+            height: 1.0,
+            image_width: 2,
+            image_height: 2,
+            rgba: vec![255; 16],
+        }
+    }
+
+    #[test]
+    fn pdf_xref_points_at_objects() {
+        let pdf = write_pdf(&[page(), page()]).unwrap();
+        assert!(pdf.starts_with(b"%PDF-1.4\n"));
+        assert!(pdf.ends_with(b"%%EOF\n"));
+
+        let start_xref = pdf
+            .windows(10)
+            .rposition(|window| window == b"startxref\n")
+            .unwrap();
+        let tail = std::str::from_utf8(&pdf[start_xref..]).unwrap();
+        let xref: usize = tail.lines().nth(1).unwrap().parse().unwrap();
+        let xref = std::str::from_utf8(&pdf[xref..]).unwrap();
+        assert!(xref.starts_with("xref\n0 9\n"));
+
+        let entries: Vec<&str> = xref.lines().skip(3).take(8).collect();
+        for (index, entry) in entries.iter().enumerate() {
+            let offset: usize = entry[..10].parse().unwrap();
+            assert!(pdf[offset..].starts_with(format!("{} 0 obj\n", index + 1).as_bytes()));
+        }
+        assert!(xref.contains("/Size 9 /Root 1 0 R"));
+    }
+
+    #[test]
+    fn numbered_paths() {
+        assert_eq!(
+            numbered_path(Path::new("/tmp/print.png"), 2),
+            Path::new("/tmp/print-2.png")
+        );
+        assert_eq!(numbered_path(Path::new("print"), 1), Path::new("print-1"));
+    }
+}
@@ -4,15 +4,19 @@ use chrono::{DateTime, Utc};
 use egui_winit::clipboard::Clipboard;
 use fontdb::Family;
 use rfd::{
-    AsyncFileDialog, FileHandle, MessageButtons, MessageDialog, MessageDialogResult, MessageLevel,
+    AsyncFileDialog, FileDialog, FileHandle, MessageButtons, MessageDialog, MessageDialogResult,
+    MessageLevel,
 };
 use ruffle_core::backend::navigator::OpenURLMode;
 use ruffle_core::backend::ui::{
-    DialogLoaderError, DialogResultFuture, FileDialogResult, FileFilter, FontDefinition,
-    FullscreenError, LanguageIdentifier, MouseCursor, UiBackend,
+    ClipboardBitmap, ClipboardContent, CustomCursor, DialogLoaderError, DialogResultFuture,
+    FileDialogResult, FileFilter, FontDefinition, FullscreenError, LanguageIdentifier, MouseCursor,
+    MultipleDialogResultFuture, PrintPage, UiBackend,
 };
+use std::borrow::Cow;
 use std::rc::Rc;
 use std::sync::Arc;
+use std::time::Instant;
 use tracing::error;
 use url::Url;
 use winit::raw_window_handle::HasDisplayHandle;
@@ -112,12 +116,44 @@ impl FileDialogResult for DesktopFileDialogResult {
     }
 }
 
+/// Create a file dialog that only allows selecting files matching `filters`
+fn file_dialog_with_filters(filters: Vec<FileFilter>) -> AsyncFileDialog {
+    let mut dialog = AsyncFileDialog::new();
+
+    for filter in filters {
+        if cfg!(target_os = "macos") && filter.mac_type.is_some() {
+            let mac_type = filter.mac_type.expect("Checked above");
+            let extensions: Vec<&str> = mac_type.split(';').collect();
+            dialog = dialog.add_filter(&filter.description, &extensions);
+        } else {
+            let extensions: Vec<&str> = filter
+                .extensions
+                .split(';')
+                .map(|x| x.trim_start_matches("*."))
+                .collect();
+            dialog = dialog.add_filter(&filter.description, &extensions);
+        }
+    }
+
+    dialog
+}
+
 pub struct DesktopUiBackend {
     window: Arc<Window>,
     cursor_visible: bool,
     clipboard: Clipboard,
+    /// Access to the formats of the system clipboard other than text.
+    image_clipboard: Option<arboard::Clipboard>,
+    /// The contents last put on the clipboard by the movie.
+    ///
+    /// Only text, HTML and images can be written to the system clipboard,
+    /// and only text and images can be read back, so the other formats
+    /// are kept here as long as the clipboard isn't changed by another application.
+    clipboard_contents: Option<ClipboardContent>,
     preferences: GlobalPreferences,
     preferred_cursor: MouseCursor,
+    /// The custom cursor shown instead of `preferred_cursor`, and when it was set.
+    custom_cursor: Option<(Arc<CustomCursor>, Instant)>,
     open_url_mode: OpenURLMode,
     font_database: Rc<fontdb::Database>,
     /// Is a dialog currently open
@@ -140,12 +176,18 @@ impl DesktopUiBackend {
                 .ok()
                 .map(|handle| handle.as_raw()),
         );
+        let image_clipboard = arboard::Clipboard::new()
+            .inspect_err(|e| tracing::warn!("Couldn't access the system clipboard: {e}"))
+            .ok();
         Ok(Self {
             window,
             cursor_visible: true,
             clipboard,
+            image_clipboard,
+            clipboard_contents: None,
             preferences,
             preferred_cursor: MouseCursor::Arrow,
+            custom_cursor: None,
             open_url_mode,
             dialog_open: false,
             font_database,
@@ -153,7 +195,8 @@ impl DesktopUiBackend {
     }
 
     pub fn cursor(&self) -> egui::CursorIcon {
-        if self.cursor_visible {
+        // Custom cursors are drawn by the GUI in place of the system cursor.
+        if self.cursor_visible && self.custom_cursor.is_none() {
             match self.preferred_cursor {
                 MouseCursor::Arrow => egui::CursorIcon::Default,
                 MouseCursor::Hand => egui::CursorIcon::PointingHand,
@@ -164,6 +207,21 @@ impl DesktopUiBackend {
             egui::CursorIcon::None
         }
     }
+
+    /// The custom cursor to draw, if any, and the index of its current frame.
+    pub fn custom_cursor(&self) -> Option<(&Arc<CustomCursor>, usize)> {
+        if !self.cursor_visible {
+            return None;
+        }
+        let (cursor, set_at) = self.custom_cursor.as_ref()?;
+        let frame = if cursor.frame_rate > 0.0 {
+            let elapsed = set_at.elapsed().as_secs_f64();
+            (elapsed * cursor.frame_rate) as usize % cursor.frames.len()
+        } else {
+            0
+        };
+        Some((cursor, frame))
+    }
 }
 
 const DOWNLOAD_FAILED_MESSAGE: &str = "Ruffle failed to open or download this file.";
@@ -181,14 +239,73 @@ impl UiBackend for DesktopUiBackend {
         self.preferred_cursor = cursor;
     }
 
+    fn supports_custom_mouse_cursor(&self) -> bool {
+        true
+    }
+
+    fn set_custom_mouse_cursor(&mut self, cursor: Option<CustomCursor>) {
+        self.custom_cursor = cursor
+            .filter(|cursor| !cursor.frames.is_empty())
+            .map(|cursor| (Arc::new(cursor), Instant::now()));
+    }
+
     fn clipboard_content(&mut self) -> String {
         self.clipboard.get().unwrap_or_default()
     }
 
     fn set_clipboard_content(&mut self, content: String) {
+        self.clipboard_contents = None;
         self.clipboard.set(content);
     }
 
+    fn clipboard_contents(&mut self) -> ClipboardContent {
+        let text = self.clipboard.get().unwrap_or_default();
+        let bitmap = self
+            .image_clipboard
+            .as_mut()
+            .and_then(|clipboard| clipboard.get_image().ok())
+            .map(|image| ClipboardBitmap {
+                width: image.width as u32,
+                height: image.height as u32,
+                rgba: image.bytes.into_owned(),
+            });
+
+        if let Some(contents) = &self.clipboard_contents {
+            let text_unchanged = contents.plain_text().unwrap_or_default() == text;
+            let bitmap_unchanged = contents.bitmap.is_none() || contents.bitmap == bitmap;
+            if text_unchanged && bitmap_unchanged {
+                return contents.clone();
+            }
+        }
+
+        ClipboardContent {
+            text: (!text.is_empty()).then_some(text),
+            bitmap,
+            ..Default::default()
+        }
+    }
+
+    fn set_clipboard_contents(&mut self, contents: ClipboardContent) {
+        let plain_text = contents.plain_text();
+        let image_clipboard = self.image_clipboard.as_mut();
+        let result = match (image_clipboard, &contents.html, &contents.bitmap) {
+            (Some(clipboard), Some(html), _) => clipboard.set_html(html, plain_text.as_deref()),
+            (Some(clipboard), None, Some(bitmap)) => clipboard.set_image(arboard::ImageData {
+                width: bitmap.width as usize,
+                height: bitmap.height as usize,
+                bytes: Cow::Borrowed(&bitmap.rgba),
+            }),
+            _ => {
+                self.clipboard.set(plain_text.unwrap_or_default());
+                Ok(())
+            }
+        };
+        if let Err(e) = result {
+            tracing::error!("Couldn't write to the system clipboard: {e}");
+        }
+        self.clipboard_contents = Some(contents);
+    }
+
     fn set_fullscreen(&mut self, is_full: bool) -> Result<(), FullscreenError> {
         self.window.set_fullscreen(if is_full {
             Some(Fullscreen::Borderless(None))
@@ -326,22 +443,7 @@ impl UiBackend for DesktopUiBackend {
 
         // Create the dialog future
         Some(Box::pin(async move {
-            let mut dialog = AsyncFileDialog::new();
-
-            for filter in filters {
-                if cfg!(target_os = "macos") && filter.mac_type.is_some() {
-                    let mac_type = filter.mac_type.expect("Checked above");
-                    let extensions: Vec<&str> = mac_type.split(';').collect();
-                    dialog = dialog.add_filter(&filter.description, &extensions);
-                } else {
-                    let extensions: Vec<&str> = filter
-                        .extensions
-                        .split(';')
-                        .map(|x| x.trim_start_matches("*."))
-                        .collect();
-                    dialog = dialog.add_filter(&filter.description, &extensions);
-                }
-            }
+            let dialog = file_dialog_with_filters(filters);
 
             let result: Result<Box<dyn FileDialogResult>, DialogLoaderError> = Ok(Box::new(
                 DesktopFileDialogResult::new(dialog.pick_file().await),
@@ -350,6 +452,33 @@ impl UiBackend for DesktopUiBackend {
         }))
     }
 
+    fn display_file_open_multiple_dialog(
+        &mut self,
+        filters: Vec<FileFilter>,
+    ) -> Option<MultipleDialogResultFuture> {
+        // Prevent opening multiple dialogs at the same time
+        if self.dialog_open {
+            return None;
+        }
+        self.dialog_open = true;
+
+        // Create the dialog future
+        Some(Box::pin(async move {
+            let dialog = file_dialog_with_filters(filters);
+            let results = dialog
+                .pick_files()
+                .await
+                .unwrap_or_default()
+                .into_iter()
+                .map(|handle| {
+                    Box::new(DesktopFileDialogResult::new(Some(handle)))
+                        as Box<dyn FileDialogResult>
+                })
+                .collect();
+            Ok(results)
+        }))
+    }
+
     fn display_file_save_dialog(
         &mut self,
         file_name: String,
@@ -378,4 +507,30 @@ impl UiBackend for DesktopUiBackend {
     fn close_file_dialog(&mut self) {
         self.dialog_open = false;
     }
+
+    fn can_print(&self) -> bool {
+        true
+    }
+
+    fn print(&mut self, pages: Vec<PrintPage>) {
+        if pages.is_empty() {
+            return;
+        }
+
+        // There's no access to system printers, so pages are saved to a file instead.
+        let Some(path) = FileDialog::new()
+            .set_title("Save printed pages")
+            .set_file_name("print.pdf")
+            .add_filter("PDF document", &["pdf"])
+            .add_filter("PNG image", &["png"])
+            .save_file()
+        else {
+            return;
+        };
+
+        if let Err(e) = super::print::save_pages(&path, &pages) {
+            error!("Couldn't save printed pages to {}: {e}", path.display());
+            self.message(&format!("Couldn't save printed pages: {e}"));
+        }
+    }
 }
@@ -8,6 +8,7 @@ use ruffle_core::events::{GamepadButton, KeyCode};
 use ruffle_core::{LoadBehavior, PlayerRuntime, StageAlign, StageScaleMode};
 use ruffle_render::quality::StageQuality;
 use ruffle_render_wgpu::clap::{GraphicsBackend, PowerPreference};
+use std::net::SocketAddr;
 use std::path::Path;
 use std::time::Duration;
 use url::Url;
@@ -213,6 +214,16 @@ pub struct Opt {
     /// (like inlining constant pool entries) can't be disabled.
     #[clap(long)]
     pub no_avm2_optimizer: bool,
+
+    /// Listen for AVM2 debugger clients on this address (`[host]:[port]`).
+    /// Clients can set breakpoints and step through movies compiled with debugging information.
+    #[clap(long, value_name = "ADDRESS")]
+    pub avm2_debugger: Option<SocketAddr>,
+
+    /// Connect to a Flash debugger such as fdb on this address (`[host]:[port]`).
+    /// Debuggers listen on `127.0.0.1:7935` by default. This takes precedence over `--avm2-debugger`.
+    #[clap(long, value_name = "ADDRESS")]
+    pub fdb: Option<SocketAddr>,
 }
 
 fn parse_movie_file_or_url(path: &str) -> Result<Url, Error> {
@@ -6,8 +6,9 @@ use crate::gui::{RuffleGui, MENU_HEIGHT};
 use crate::player::{LaunchOptions, PlayerController};
 use crate::preferences::GlobalPreferences;
 use anyhow::anyhow;
-use egui::{Context, ViewportId};
+use egui::{Context, TextureHandle, ViewportId};
 use fontdb::{Database, Family, Query, Source};
+use ruffle_core::backend::ui::CustomCursor;
 use ruffle_core::{Player, PlayerEvent};
 use ruffle_render_wgpu::backend::{request_adapter_and_device, WgpuRenderBackend};
 use ruffle_render_wgpu::descriptors::Descriptors;
@@ -41,6 +42,8 @@ pub struct GuiController {
     /// If this is set, we should not render the main menu.
     no_gui: bool,
     theme_controller: ThemeController,
+    /// The textures of the custom cursor of the movie, one for each of its frames.
+    cursor_textures: Option<(Arc<CustomCursor>, Vec<TextureHandle>)>,
 }
 
 impl GuiController {
@@ -138,6 +141,7 @@ impl GuiController {
             size,
             no_gui,
             theme_controller,
+            cursor_textures: None,
         })
     }
 
@@ -273,6 +277,9 @@ impl GuiController {
                     0.0
                 },
             );
+            if let Some(player) = player.as_deref() {
+                paint_custom_cursor(context, &mut self.cursor_textures, player);
+            }
         });
         self.repaint_after = full_output
             .viewport_output
@@ -509,3 +516,68 @@ fn load_system_fonts(
 
     Ok(fd)
 }
+
+/// Draw the custom cursor of the movie at the mouse position, if it has one.
+///
+/// winit can't change the system cursor to an image, so the system cursor is hidden instead
+/// and the custom cursor is drawn on top of everything else.
+fn paint_custom_cursor(
+    context: &Context,
+    textures: &mut Option<(Arc<CustomCursor>, Vec<TextureHandle>)>,
+    player: &Player,
+) {
+    let ui = player
+        .ui()
+        .downcast_ref::<DesktopUiBackend>()
+        .unwrap_or_else(|| panic!("UI Backend should be DesktopUiBackend"));
+    let Some((cursor, frame)) = ui.custom_cursor() else {
+        *textures = None;
+        return;
+    };
+    if context.wants_pointer_input() {
+        return;
+    }
+    let Some(position) = context.pointer_hover_pos() else {
+        return;
+    };
+
+    if !matches!(textures, Some((uploaded, _)) if Arc::ptr_eq(uploaded, cursor)) {
+        let frames = cursor
+            .frames
+            .iter()
+            .enumerate()
+            .map(|(index, frame)| {
+                let image = egui::ColorImage::from_rgba_unmultiplied(
+                    [frame.width as usize, frame.height as usize],
+                    &frame.rgba,
+                );
+                context.load_texture(
+                    format!("custom_cursor_{index}"),
+                    image,
+                    egui::TextureOptions::NEAREST,
+                )
+            })
+            .collect();
+        *textures = Some((cursor.clone(), frames));
+    }
+    let Some(texture) = textures.as_ref().and_then(|(_, frames)| frames.get(frame)) else {
+        return;
+    };
+
+    // Cursor images are shown at their size in physical pixels, like system cursors.
+    let pixels_per_point = context.pixels_per_point();
+    let hotspot = egui::vec2(cursor.hotspot.0 as f32, cursor.hotspot.1 as f32) / pixels_per_point;
+    let size = texture.size_vec2() / pixels_per_point;
+    let rect = egui::Rect::from_min_size(position - hotspot, size);
+    let uv = egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0));
+    context
+        .layer_painter(egui::LayerId::new(
+            egui::Order::Tooltip,
+            egui::Id::new("custom_cursor"),
+        ))
+        .image(texture.id(), rect, uv, egui::Color32::WHITE);
+
+    if cursor.frames.len() > 1 && cursor.frame_rate > 0.0 {
+        context.request_repaint_after(Duration::from_secs_f64(1.0 / cursor.frame_rate));
+    }
+}
@@ -157,6 +157,12 @@ impl MenuBar {
                                 player.debug_ui().queue_message(DebugMessage::SearchForDisplayObject);
                             }
                         }
+                        if Button::new(text(locale, "debug-menu-open-avm2-debugger")).ui(ui).clicked() {
+                            ui.close_menu();
+                            if let Some(player) = &mut player {
+                                player.debug_ui().queue_message(DebugMessage::ShowAvm2Debugger);
+                            }
+                        }
                     });
                 });
                 menu::menu_button(ui, text(locale, "help-menu"), |ui| {
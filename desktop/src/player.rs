@@ -25,6 +25,7 @@ use ruffle_render_wgpu::descriptors::Descriptors;
 use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
+use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::rc::Rc;
 use std::sync::{Arc, Mutex, MutexGuard};
@@ -46,6 +47,8 @@ pub struct LaunchOptions {
     pub open_url_mode: OpenURLMode,
     pub gamepad_button_mapping: HashMap<GamepadButton, KeyCode>,
     pub avm2_optimizer_enabled: bool,
+    pub avm2_debug_server: Option<SocketAddr>,
+    pub flash_debugger: Option<SocketAddr>,
 }
 
 impl From<&GlobalPreferences> for LaunchOptions {
@@ -95,6 +98,8 @@ impl From<&GlobalPreferences> for LaunchOptions {
             tcp_connections: value.cli.tcp_connections,
             gamepad_button_mapping: HashMap::from_iter(value.cli.gamepad_button.iter().cloned()),
             avm2_optimizer_enabled: !value.cli.no_avm2_optimizer,
+            avm2_debug_server: value.cli.avm2_debugger,
+            flash_debugger: value.cli.fdb,
         }
     }
 }
@@ -196,6 +201,8 @@ impl ActivePlayer {
                     open_url_mode: opt.open_url_mode,
                     gamepad_button_mapping: opt.gamepad_button_mapping.clone(),
                     avm2_optimizer_enabled: opt.avm2_optimizer_enabled,
+                    avm2_debug_server: opt.avm2_debug_server,
+                    flash_debugger: opt.flash_debugger,
                 })
             }
         };
@@ -294,7 +301,9 @@ impl ActivePlayer {
             .with_player_version(opt.player.player_version)
             .with_player_runtime(opt.player.player_runtime.unwrap_or_default())
             .with_frame_rate(opt.player.frame_rate)
-            .with_avm2_optimizer_enabled(opt.avm2_optimizer_enabled);
+            .with_avm2_optimizer_enabled(opt.avm2_optimizer_enabled)
+            .with_avm2_debug_server(opt.avm2_debug_server)
+            .with_flash_debugger(opt.flash_debugger);
         let player = builder.build();
 
         window.set_title(&format!("Ruffle - {readable_name}"));
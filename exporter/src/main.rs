@@ -6,6 +6,7 @@ use rayon::prelude::*;
 use ruffle_core::limits::ExecutionLimit;
 use ruffle_core::tag_utils::SwfMovie;
 use ruffle_core::PlayerBuilder;
+use ruffle_render_software::SoftwareRenderBackend;
 use ruffle_render_wgpu::backend::{request_adapter_and_device, WgpuRenderBackend};
 use ruffle_render_wgpu::clap::{GraphicsBackend, PowerPreference};
 use ruffle_render_wgpu::descriptors::Descriptors;
@@ -76,11 +77,16 @@ struct Opt {
     /// Skip unsupported movie types (currently AVM 2)
     #[clap(long, action)]
     skip_unsupported: bool,
+
+    /// Render on the CPU instead of using a graphics device.
+    /// This is much slower, but works on machines without a GPU.
+    #[clap(long, action)]
+    software: bool,
 }
 
 /// Captures a screenshot. The resulting image uses straight alpha
 fn take_screenshot(
-    descriptors: Arc<Descriptors>,
+    descriptors: Option<Arc<Descriptors>>,
     swf_path: &Path,
     frames: u32,
     skipframes: u32,
@@ -106,12 +112,16 @@ fn take_screenshot(
         .unwrap_or_else(|| movie.height().to_pixels());
     let height = (height * size.scale).round() as u32;
 
-    let target = TextureTarget::new(&descriptors.device, (width, height))
-        .map_err(|e| anyhow!(e.to_string()))?;
-    let player = PlayerBuilder::new()
-        .with_renderer(
+    let builder = if let Some(descriptors) = descriptors {
+        let target = TextureTarget::new(&descriptors.device, (width, height))
+            .map_err(|e| anyhow!(e.to_string()))?;
+        PlayerBuilder::new().with_renderer(
             WgpuRenderBackend::new(descriptors, target).map_err(|e| anyhow!(e.to_string()))?,
         )
+    } else {
+        PlayerBuilder::new().with_renderer(SoftwareRenderBackend::new(width, height))
+    };
+    let player = builder
         .with_movie(movie)
         .with_viewport_dimensions(width, height, size.scale)
         .build();
@@ -135,11 +145,15 @@ fn take_screenshot(
             let image = || {
                 player.lock().unwrap().render();
                 let mut player = player.lock().unwrap();
-                let renderer = player
-                    .renderer_mut()
-                    .downcast_mut::<WgpuRenderBackend<TextureTarget>>()
-                    .unwrap();
-                renderer.capture_frame()
+                let renderer = player.renderer_mut();
+                if let Some(renderer) = renderer.downcast_mut::<WgpuRenderBackend<TextureTarget>>()
+                {
+                    renderer.capture_frame()
+                } else {
+                    renderer
+                        .downcast_mut::<SoftwareRenderBackend>()
+                        .map(|renderer| renderer.capture_frame())
+                }
             };
             match catch_unwind(image) {
                 Ok(Some(image)) => result.push(image),
@@ -192,7 +206,7 @@ fn find_files(root: &Path, with_progress: bool) -> Vec<DirEntry> {
     results
 }
 
-fn capture_single_swf(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
+fn capture_single_swf(descriptors: Option<Arc<Descriptors>>, opt: &Opt) -> Result<()> {
     let output = opt.output_path.clone().unwrap_or_else(|| {
         let mut result = PathBuf::new();
         result.set_file_name(opt.swf.file_stem().unwrap());
@@ -286,7 +300,7 @@ fn capture_single_swf(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
 }
 
 #[allow(clippy::branches_sharing_code)]
-fn capture_multiple_swfs(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
+fn capture_multiple_swfs(descriptors: Option<Arc<Descriptors>>, opt: &Opt) -> Result<()> {
     let output = opt.output_path.clone().unwrap();
     let files = find_files(&opt.swf, !opt.silent);
 
@@ -383,20 +397,24 @@ fn trace_path(_opt: &Opt) -> Option<&Path> {
 
 fn main() -> Result<()> {
     let opt: Opt = Opt::parse();
-    let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
-        backends: opt.graphics.into(),
-        ..Default::default()
-    });
-    let (adapter, device, queue) = futures::executor::block_on(request_adapter_and_device(
-        opt.graphics.into(),
-        &instance,
-        None,
-        opt.power.into(),
-        trace_path(&opt),
-    ))
-    .map_err(|e| anyhow!(e.to_string()))?;
-
-    let descriptors = Arc::new(Descriptors::new(instance, adapter, device, queue));
+    let descriptors = if opt.software {
+        None
+    } else {
+        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
+            backends: opt.graphics.into(),
+            ..Default::default()
+        });
+        let (adapter, device, queue) = futures::executor::block_on(request_adapter_and_device(
+            opt.graphics.into(),
+            &instance,
+            None,
+            opt.power.into(),
+            trace_path(&opt),
+        ))
+        .map_err(|e| anyhow!(e.to_string()))?;
+
+        Some(Arc::new(Descriptors::new(instance, adapter, device, queue)))
+    };
 
     if opt.swf.is_file() {
         capture_single_swf(descriptors, &opt)?;
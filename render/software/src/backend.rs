@@ -0,0 +1,402 @@
+use crate::bitmap::{as_texture, SoftwareSyncHandle, Texture};
+use crate::filters::{self, FilterImage};
+use crate::mesh::Mesh;
+use crate::raster::CommandRenderer;
+use crate::surface::Surface;
+use ruffle_render::backend::{
+    BitmapCacheEntry, Context3D, Context3DProfile, PixelBenderOutput, PixelBenderTarget,
+    RenderBackend, ShapeHandle, ViewportDimensions,
+};
+use ruffle_render::bitmap::{
+    Bitmap, BitmapHandle, BitmapSource, PixelRegion, RgbaBufRead, SyncHandle,
+};
+use ruffle_render::commands::CommandList;
+use ruffle_render::error::Error;
+use ruffle_render::filters::Filter;
+use ruffle_render::pixel_bender::{
+    PixelBenderShader, PixelBenderShaderArgument, PixelBenderShaderHandle,
+};
+use ruffle_render::quality::StageQuality;
+use ruffle_render::shape_utils::DistilledShape;
+use ruffle_render::tessellator::ShapeTessellator;
+use std::borrow::Cow;
+use std::sync::Arc;
+use swf::Color;
+
+/// The largest bitmap we're willing to allocate, on either dimension.
+const MAX_TEXTURE_SIZE: u32 = 8192;
+
+pub struct SoftwareRenderBackend {
+    viewport_dimensions: ViewportDimensions,
+    quality: StageQuality,
+    shape_tessellator: ShapeTessellator,
+
+    /// The last frame that was rendered, as premultiplied RGBA.
+    frame: Vec<u8>,
+}
+
+impl SoftwareRenderBackend {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            viewport_dimensions: ViewportDimensions {
+                width,
+                height,
+                scale_factor: 1.0,
+            },
+            quality: StageQuality::High,
+            shape_tessellator: ShapeTessellator::new(),
+            frame: vec![0; width as usize * height as usize * 4],
+        }
+    }
+
+    /// Returns the last rendered frame. The resulting image uses straight alpha.
+    pub fn capture_frame(&self) -> image::RgbaImage {
+        let mut pixels = self.frame.clone();
+        ruffle_render::utils::unmultiply_alpha_rgba(&mut pixels);
+        image::RgbaImage::from_raw(
+            self.viewport_dimensions.width,
+            self.viewport_dimensions.height,
+            pixels,
+        )
+        .expect("Frame buffer must be a valid RgbaImage")
+    }
+
+    /// Renders commands into a bitmap, on top of its existing contents.
+    fn render_to_texture(&self, texture: &Texture, commands: CommandList, quality: StageQuality) {
+        let mut surface = Surface::from_pixels(
+            texture.width(),
+            texture.height(),
+            quality,
+            &texture.pixels(),
+        );
+        commands.execute(&mut CommandRenderer::new(&mut surface, quality));
+        *texture.pixels_mut() = surface.resolve();
+    }
+}
+
+fn clear_color(color: Color) -> [f32; 4] {
+    let alpha = f32::from(color.a) / 255.0;
+    [
+        f32::from(color.r) / 255.0 * alpha,
+        f32::from(color.g) / 255.0 * alpha,
+        f32::from(color.b) / 255.0 * alpha,
+        alpha,
+    ]
+}
+
+impl RenderBackend for SoftwareRenderBackend {
+    fn viewport_dimensions(&self) -> ViewportDimensions {
+        self.viewport_dimensions
+    }
+
+    fn set_viewport_dimensions(&mut self, dimensions: ViewportDimensions) {
+        self.viewport_dimensions = dimensions;
+        self.frame = vec![0; dimensions.width as usize * dimensions.height as usize * 4];
+    }
+
+    fn register_shape(
+        &mut self,
+        shape: DistilledShape,
+        bitmap_source: &dyn BitmapSource,
+    ) -> ShapeHandle {
+        let mesh = self
+            .shape_tessellator
+            .tessellate_shape(shape, bitmap_source);
+        ShapeHandle(Arc::new(Mesh::new(mesh, bitmap_source, self)))
+    }
+
+    fn render_offscreen(
+        &mut self,
+        handle: BitmapHandle,
+        commands: CommandList,
+        quality: StageQuality,
+        bounds: PixelRegion,
+    ) -> Option<Box<dyn SyncHandle>> {
+        self.render_to_texture(as_texture(&handle), commands, quality);
+        Some(Box::new(SoftwareSyncHandle {
+            handle,
+            area: bounds,
+        }))
+    }
+
+    fn apply_filter(
+        &mut self,
+        source: BitmapHandle,
+        source_point: (u32, u32),
+        source_size: (u32, u32),
+        destination: BitmapHandle,
+        dest_point: (u32, u32),
+        filter: Filter,
+    ) -> Option<Box<dyn SyncHandle>> {
+        if !filters::is_filter_supported(&filter) {
+            return None;
+        }
+
+        // Read the source before writing anything, as it may be the same bitmap as the destination.
+        let source_texture = as_texture(&source);
+        let mut image = FilterImage::from_area(
+            &source_texture.pixels(),
+            source_texture.width(),
+            source_texture.height(),
+            source_point,
+            source_size,
+        );
+        filters::apply_filter(&mut image, &filter);
+
+        let dest_texture = as_texture(&destination);
+        image.write_to(
+            &mut dest_texture.pixels_mut(),
+            dest_texture.width(),
+            dest_texture.height(),
+            dest_point,
+        );
+
+        let mut area =
+            PixelRegion::for_region(dest_point.0, dest_point.1, source_size.0, source_size.1);
+        area.clamp(dest_texture.width(), dest_texture.height());
+        Some(Box::new(SoftwareSyncHandle {
+            handle: destination,
+            area,
+        }))
+    }
+
+    fn is_filter_supported(&self, filter: &Filter) -> bool {
+        filters::is_filter_supported(filter)
+    }
+
+    fn is_offscreen_supported(&self) -> bool {
+        true
+    }
+
+    fn submit_frame(
+        &mut self,
+        clear: Color,
+        commands: CommandList,
+        cache_entries: Vec<BitmapCacheEntry>,
+    ) {
+        for entry in cache_entries {
+            let texture = as_texture(&entry.handle);
+            let mut surface = Surface::new(
+                texture.width(),
+                texture.height(),
+                self.quality,
+                clear_color(entry.clear),
+            );
+            entry
+                .commands
+                .execute(&mut CommandRenderer::new(&mut surface, self.quality));
+            let mut pixels = surface.resolve();
+
+            if !entry.filters.is_empty() {
+                let size = (texture.width(), texture.height());
+                let mut image = FilterImage::from_area(&pixels, size.0, size.1, (0, 0), size);
+                for filter in &entry.filters {
+                    if !filters::apply_filter(&mut image, filter) {
+                        tracing::warn!("Unsupported filter {filter:?}");
+                    }
+                }
+                image.write_to(&mut pixels, size.0, size.1, (0, 0));
+            }
+
+            *texture.pixels_mut() = pixels;
+        }
+
+        let mut surface = Surface::new(
+            self.viewport_dimensions.width,
+            self.viewport_dimensions.height,
+            self.quality,
+            clear_color(clear),
+        );
+        commands.execute(&mut CommandRenderer::new(&mut surface, self.quality));
+        self.frame = surface.resolve();
+    }
+
+    fn create_empty_texture(&mut self, width: u32, height: u32) -> Result<BitmapHandle, Error> {
+        if width == 0 || height == 0 {
+            return Err(Error::InvalidSize);
+        }
+        if width > MAX_TEXTURE_SIZE || height > MAX_TEXTURE_SIZE {
+            return Err(Error::TooLarge);
+        }
+        let pixels = vec![0; width as usize * height as usize * 4];
+        Ok(BitmapHandle(Arc::new(Texture::new(width, height, pixels))))
+    }
+
+    fn register_bitmap(&mut self, bitmap: Bitmap) -> Result<BitmapHandle, Error> {
+        if bitmap.width() == 0 || bitmap.height() == 0 {
+            return Err(Error::InvalidSize);
+        }
+        if bitmap.width() > MAX_TEXTURE_SIZE || bitmap.height() > MAX_TEXTURE_SIZE {
+            return Err(Error::TooLarge);
+        }
+        let bitmap = bitmap.to_rgba();
+        Ok(BitmapHandle(Arc::new(Texture::new(
+            bitmap.width(),
+            bitmap.height(),
+            bitmap.data().to_vec(),
+        ))))
+    }
+
+    fn update_texture(
+        &mut self,
+        handle: &BitmapHandle,
+        bitmap: Bitmap,
+        region: PixelRegion,
+    ) -> Result<(), Error> {
+        let texture = as_texture(handle);
+        let bitmap = bitmap.to_rgba();
+        if bitmap.width() != texture.width() || bitmap.height() != texture.height() {
+            return Err(Error::InvalidSize);
+        }
+
+        let mut pixels = texture.pixels_mut();
+        let data = bitmap.data();
+        for y in region.y_min..region.y_max {
+            let start = (y * texture.width() + region.x_min) as usize * 4;
+            let end = (y * texture.width() + region.x_max) as usize * 4;
+            pixels[start..end].copy_from_slice(&data[start..end]);
+        }
+        Ok(())
+    }
+
+    fn create_context3d(
+        &mut self,
+        _profile: Context3DProfile,
+    ) -> Result<Box<dyn Context3D>, Error> {
+        Err(Error::Unimplemented("createContext3D".into()))
+    }
+
+    fn context3d_present(&mut self, _context: &mut dyn Context3D) -> Result<(), Error> {
+        Err(Error::Unimplemented("Context3D.present".into()))
+    }
+
+    fn debug_info(&self) -> Cow<'static, str> {
+        Cow::Owned(format!(
+            "Renderer: Software\nViewport: {}x{}\nQuality: {}\n",
+            self.viewport_dimensions.width, self.viewport_dimensions.height, self.quality
+        ))
+    }
+
+    fn name(&self) -> &'static str {
+        "software"
+    }
+
+    fn set_quality(&mut self, quality: StageQuality) {
+        self.quality = quality;
+    }
+
+    fn compile_pixelbender_shader(
+        &mut self,
+        _shader: PixelBenderShader,
+    ) -> Result<PixelBenderShaderHandle, Error> {
+        Err(Error::Unimplemented(
+            "Pixel bender shader compilation".into(),
+        ))
+    }
+
+    fn run_pixelbender_shader(
+        &mut self,
+        _shader: PixelBenderShaderHandle,
+        _arguments: &[PixelBenderShaderArgument],
+        _target: &PixelBenderTarget,
+    ) -> Result<PixelBenderOutput, Error> {
+        Err(Error::Unimplemented("Pixel bender shader".into()))
+    }
+
+    fn resolve_sync_handle(
+        &mut self,
+        handle: Box<dyn SyncHandle>,
+        with_rgba: RgbaBufRead,
+    ) -> Result<(), Error> {
+        let handle = handle
+            .downcast::<SoftwareSyncHandle>()
+            .map_err(|_| Error::Unimplemented("Foreign sync handle".into()))?;
+        let pixels = as_texture(&handle.handle).read_area(handle.area);
+        with_rgba(&pixels, handle.area.width() * 4);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ruffle_render::commands::{CommandHandler, RenderBlendMode};
+    use ruffle_render::matrix::Matrix;
+    use swf::{BlendMode, Twips};
+
+    fn pixel(backend: &SoftwareRenderBackend, x: u32, y: u32) -> [u8; 4] {
+        let i = (y * backend.viewport_dimensions.width + x) as usize * 4;
+        backend.frame[i..i + 4].try_into().unwrap()
+    }
+
+    #[test]
+    fn draw_rect() {
+        let mut backend = SoftwareRenderBackend::new(4, 4);
+        let mut commands = CommandList::new();
+        commands.draw_rect(
+            Color::RED,
+            Matrix::create_box(2.0, 2.0, Twips::from_pixels(1.0), Twips::from_pixels(1.0)),
+        );
+        backend.submit_frame(Color::WHITE, commands, vec![]);
+
+        assert_eq!(pixel(&backend, 0, 0), [255, 255, 255, 255]);
+        assert_eq!(pixel(&backend, 1, 1), [255, 0, 0, 255]);
+        assert_eq!(pixel(&backend, 2, 2), [255, 0, 0, 255]);
+        assert_eq!(pixel(&backend, 3, 3), [255, 255, 255, 255]);
+    }
+
+    #[test]
+    fn antialiased_edges() {
+        let mut backend = SoftwareRenderBackend::new(2, 1);
+        let mut commands = CommandList::new();
+        commands.draw_rect(
+            Color::BLACK,
+            Matrix::create_box(1.5, 1.0, Twips::ZERO, Twips::ZERO),
+        );
+        backend.submit_frame(Color::WHITE, commands, vec![]);
+
+        assert_eq!(pixel(&backend, 0, 0), [0, 0, 0, 255]);
+        assert_eq!(pixel(&backend, 1, 0), [128, 128, 128, 255]);
+    }
+
+    #[test]
+    fn masks() {
+        let mut backend = SoftwareRenderBackend::new(4, 1);
+        let mut commands = CommandList::new();
+        commands.push_mask();
+        commands.draw_rect(
+            Color::WHITE,
+            Matrix::create_box(2.0, 1.0, Twips::ZERO, Twips::ZERO),
+        );
+        commands.activate_mask();
+        commands.draw_rect(
+            Color::BLUE,
+            Matrix::create_box(4.0, 1.0, Twips::ZERO, Twips::ZERO),
+        );
+        commands.deactivate_mask();
+        commands.draw_rect(
+            Color::WHITE,
+            Matrix::create_box(2.0, 1.0, Twips::ZERO, Twips::ZERO),
+        );
+        commands.pop_mask();
+        backend.submit_frame(Color::BLACK, commands, vec![]);
+
+        assert_eq!(pixel(&backend, 1, 0), [0, 0, 255, 255]);
+        assert_eq!(pixel(&backend, 2, 0), [0, 0, 0, 255]);
+    }
+
+    #[test]
+    fn blend_modes() {
+        let mut backend = SoftwareRenderBackend::new(1, 1);
+        let mut layer = CommandList::new();
+        layer.draw_rect(
+            Color::from_rgb(0x0080FF, 255),
+            Matrix::create_box(1.0, 1.0, Twips::ZERO, Twips::ZERO),
+        );
+        let mut commands = CommandList::new();
+        commands.blend(layer, RenderBlendMode::Builtin(BlendMode::Multiply));
+        backend.submit_frame(Color::from_rgb(0xFF8000, 255), commands, vec![]);
+
+        assert_eq!(pixel(&backend, 0, 0), [0, 64, 0, 255]);
+    }
+}
@@ -0,0 +1,131 @@
+use ruffle_render::bitmap::{BitmapHandle, BitmapHandleImpl, PixelRegion, SyncHandle};
+use std::cell::{Ref, RefCell, RefMut};
+
+/// A bitmap living in main memory, stored as premultiplied RGBA.
+#[derive(Debug)]
+pub struct Texture {
+    width: u32,
+    height: u32,
+    pixels: RefCell<Vec<u8>>,
+}
+
+impl BitmapHandleImpl for Texture {}
+
+impl Texture {
+    pub fn new(width: u32, height: u32, pixels: Vec<u8>) -> Self {
+        debug_assert_eq!(pixels.len(), width as usize * height as usize * 4);
+        Self {
+            width,
+            height,
+            pixels: RefCell::new(pixels),
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn pixels(&self) -> Ref<'_, Vec<u8>> {
+        self.pixels.borrow()
+    }
+
+    pub fn pixels_mut(&self) -> RefMut<'_, Vec<u8>> {
+        self.pixels.borrow_mut()
+    }
+
+    /// Copies the given area out of this texture, as tightly packed rows.
+    pub fn read_area(&self, area: PixelRegion) -> Vec<u8> {
+        let pixels = self.pixels();
+        let row_len = area.width() as usize * 4;
+        let mut out = Vec::with_capacity(row_len * area.height() as usize);
+        for y in area.y_min..area.y_max {
+            let start = (y * self.width + area.x_min) as usize * 4;
+            out.extend_from_slice(&pixels[start..start + row_len]);
+        }
+        out
+    }
+}
+
+pub fn as_texture(handle: &BitmapHandle) -> &Texture {
+    <dyn BitmapHandleImpl>::downcast_ref(&*handle.0).unwrap()
+}
+
+/// Samples a texel from premultiplied RGBA pixels, normalized to `[0.0, 1.0]`.
+#[inline]
+pub fn texel(pixels: &[u8], width: u32, x: u32, y: u32) -> [f32; 4] {
+    let i = (y * width + x) as usize * 4;
+    [
+        f32::from(pixels[i]) / 255.0,
+        f32::from(pixels[i + 1]) / 255.0,
+        f32::from(pixels[i + 2]) / 255.0,
+        f32::from(pixels[i + 3]) / 255.0,
+    ]
+}
+
+/// Samples a bitmap at the given texture coordinates, where `(1.0, 1.0)`
+/// is the bottom-right corner of the bitmap.
+pub fn sample(
+    pixels: &[u8],
+    width: u32,
+    height: u32,
+    u: f32,
+    v: f32,
+    smoothed: bool,
+    repeating: bool,
+) -> [f32; 4] {
+    let wrap = |coord: i64, size: u32| -> u32 {
+        if repeating {
+            coord.rem_euclid(i64::from(size)) as u32
+        } else {
+            coord.clamp(0, i64::from(size) - 1) as u32
+        }
+    };
+
+    let x = u * width as f32;
+    let y = v * height as f32;
+    if !x.is_finite() || !y.is_finite() {
+        return [0.0; 4];
+    }
+
+    if !smoothed {
+        let x = wrap(x.floor() as i64, width);
+        let y = wrap(y.floor() as i64, height);
+        return texel(pixels, width, x, y);
+    }
+
+    let x = x - 0.5;
+    let y = y - 0.5;
+    let x0 = x.floor();
+    let y0 = y.floor();
+    let fx = x - x0;
+    let fy = y - y0;
+    let (x0, y0) = (x0 as i64, y0 as i64);
+    let (x1, y1) = (wrap(x0 + 1, width), wrap(y0 + 1, height));
+    let (x0, y0) = (wrap(x0, width), wrap(y0, height));
+
+    let c00 = texel(pixels, width, x0, y0);
+    let c10 = texel(pixels, width, x1, y0);
+    let c01 = texel(pixels, width, x0, y1);
+    let c11 = texel(pixels, width, x1, y1);
+    std::array::from_fn(|i| {
+        let top = c00[i] + (c10[i] - c00[i]) * fx;
+        let bottom = c01[i] + (c11[i] - c01[i]) * fx;
+        top + (bottom - top) * fy
+    })
+}
+
+/// A pending read of a texture area.
+///
+/// Rendering happens synchronously, so the pixels are simply read from the
+/// texture once the handle is resolved.
+#[derive(Debug, Clone)]
+pub struct SoftwareSyncHandle {
+    pub handle: BitmapHandle,
+    pub area: PixelRegion,
+}
+
+impl SyncHandle for SoftwareSyncHandle {}
@@ -0,0 +1,77 @@
+use swf::BlendMode;
+
+/// Blends a premultiplied source color onto a premultiplied destination color.
+///
+/// These mirror the blend states and shaders used by the wgpu backend.
+pub fn blend(blend_mode: BlendMode, src: [f32; 4], dst: [f32; 4]) -> [f32; 4] {
+    let [sr, sg, sb, sa] = src;
+    let [dr, dg, db, da] = dst;
+    let over_alpha = sa + da * (1.0 - sa);
+    match blend_mode {
+        BlendMode::Normal | BlendMode::Layer => [
+            sr + dr * (1.0 - sa),
+            sg + dg * (1.0 - sa),
+            sb + db * (1.0 - sa),
+            over_alpha,
+        ],
+        BlendMode::Add => [
+            (sr + dr).min(1.0),
+            (sg + dg).min(1.0),
+            (sb + db).min(1.0),
+            over_alpha,
+        ],
+        BlendMode::Subtract => [
+            (dr - sr).max(0.0),
+            (dg - sg).max(0.0),
+            (db - sb).max(0.0),
+            over_alpha,
+        ],
+        BlendMode::Screen => [
+            sr + dr * (1.0 - sr),
+            sg + dg * (1.0 - sg),
+            sb + db * (1.0 - sb),
+            over_alpha,
+        ],
+        // Flash doesn't blend with fully transparent pixels, it replaces them.
+        BlendMode::Multiply if da <= 0.0 => src,
+        BlendMode::Multiply => complex(src, dst, |s, d| s * d),
+        BlendMode::Lighten => complex(src, dst, f32::max),
+        BlendMode::Darken => complex(src, dst, f32::min),
+        BlendMode::Difference => complex(src, dst, |s, d| (d - s).abs()),
+        BlendMode::Overlay => complex(src, dst, |s, d| hard_light(d, s)),
+        BlendMode::HardLight => complex(src, dst, hard_light),
+        BlendMode::Invert => complex(src, dst, |_, d| 1.0 - d),
+        BlendMode::Alpha => [dr * sa, dg * sa, db * sa, da * sa],
+        BlendMode::Erase => [
+            dr * (1.0 - sa),
+            dg * (1.0 - sa),
+            db * (1.0 - sa),
+            da * (1.0 - sa),
+        ],
+    }
+}
+
+/// A separable blend mode, where `f` blends the straight source and destination colors.
+fn complex(src: [f32; 4], dst: [f32; 4], f: impl Fn(f32, f32) -> f32) -> [f32; 4] {
+    let sa = src[3];
+    let da = dst[3];
+    if sa <= 0.0 {
+        return dst;
+    }
+    let mut out = [0.0; 4];
+    for c in 0..3 {
+        let s = src[c] / sa;
+        let d = if da > 0.0 { dst[c] / da } else { 0.0 };
+        out[c] = (src[c] * (1.0 - da) + dst[c] * (1.0 - sa) + sa * da * f(s, d)).clamp(0.0, 1.0);
+    }
+    out[3] = sa + da * (1.0 - sa);
+    out
+}
+
+fn hard_light(s: f32, d: f32) -> f32 {
+    if s <= 0.5 {
+        2.0 * s * d
+    } else {
+        1.0 - 2.0 * (1.0 - d) * (1.0 - s)
+    }
+}
@@ -0,0 +1,227 @@
+use ruffle_render::filters::Filter;
+use swf::{BlurFilter, ColorMatrixFilter, GlowFilter};
+
+/// A premultiplied RGBA image, normalized to `[0.0, 1.0]`, that filters operate on.
+pub struct FilterImage {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<[f32; 4]>,
+}
+
+impl FilterImage {
+    /// Reads an area of premultiplied RGBA pixels. Anything outside of the
+    /// source is treated as transparent.
+    pub fn from_area(
+        pixels: &[u8],
+        stride: u32,
+        source_height: u32,
+        (x, y): (u32, u32),
+        (width, height): (u32, u32),
+    ) -> Self {
+        let mut image = Self {
+            width: width as usize,
+            height: height as usize,
+            pixels: vec![[0.0; 4]; width as usize * height as usize],
+        };
+        for dy in 0..height {
+            for dx in 0..width {
+                let (sx, sy) = (x + dx, y + dy);
+                if sx < stride && sy < source_height {
+                    let i = (sy * stride + sx) as usize * 4;
+                    image.pixels[(dy * width + dx) as usize] =
+                        std::array::from_fn(|c| f32::from(pixels[i + c]) / 255.0);
+                }
+            }
+        }
+        image
+    }
+
+    /// Writes this image into premultiplied RGBA pixels at the given position,
+    /// clipping anything that doesn't fit.
+    pub fn write_to(&self, pixels: &mut [u8], stride: u32, dest_height: u32, (x, y): (u32, u32)) {
+        for dy in 0..self.height as u32 {
+            for dx in 0..self.width as u32 {
+                let (tx, ty) = (x + dx, y + dy);
+                if tx < stride && ty < dest_height {
+                    let i = (ty * stride + tx) as usize * 4;
+                    let color = self.pixels[dy as usize * self.width + dx as usize];
+                    for c in 0..4 {
+                        pixels[i + c] = (color[c] * 255.0).round().clamp(0.0, 255.0) as u8;
+                    }
+                }
+            }
+        }
+    }
+
+    fn get(&self, x: isize, y: isize) -> [f32; 4] {
+        if x < 0 || y < 0 || x >= self.width as isize || y >= self.height as isize {
+            [0.0; 4]
+        } else {
+            self.pixels[y as usize * self.width + x as usize]
+        }
+    }
+}
+
+pub fn is_filter_supported(filter: &Filter) -> bool {
+    matches!(
+        filter,
+        Filter::BlurFilter(_)
+            | Filter::ColorMatrixFilter(_)
+            | Filter::DropShadowFilter(_)
+            | Filter::GlowFilter(_)
+    )
+}
+
+/// Applies a filter to an image, returning `false` if the filter isn't supported.
+pub fn apply_filter(image: &mut FilterImage, filter: &Filter) -> bool {
+    match filter {
+        Filter::BlurFilter(filter) => blur(image, filter),
+        Filter::ColorMatrixFilter(filter) => color_matrix(image, filter),
+        Filter::GlowFilter(filter) => glow(image, filter, (0, 0)),
+        Filter::DropShadowFilter(filter) => {
+            let distance = filter.distance.to_f32();
+            let angle = filter.angle.to_f32();
+            let offset = (
+                (angle.cos() * distance).round() as isize,
+                (angle.sin() * distance).round() as isize,
+            );
+            glow(image, &filter.inner_glow_filter(), offset)
+        }
+        _ => return false,
+    }
+    true
+}
+
+/// A multi-pass box blur, with fractional kernel sizes.
+///
+/// This follows the same algorithm as the wgpu backend, see
+/// <https://fgiesen.wordpress.com/2012/08/01/fast-blurs-2/>.
+fn blur(image: &mut FilterImage, filter: &BlurFilter) {
+    for _ in 0..filter.num_passes() {
+        blur_pass(image, filter.blur_x.to_f32(), true);
+        blur_pass(image, filter.blur_y.to_f32(), false);
+    }
+}
+
+fn blur_pass(image: &mut FilterImage, strength: f32, horizontal: bool) {
+    // Full width of the kernel (left edge to right edge)
+    let full_size = strength.min(255.0);
+    if full_size <= 1.0 {
+        // A width of 1 or less is a noop
+        return;
+    }
+
+    // The kernel extends this far past the center pixel to either side. The
+    // outermost pixels on each side get a fractional weight of `alpha`,
+    // rounded like the fixed-point calculations in Flash Player.
+    let radius = (full_size - 1.0) / 2.0;
+    let m = radius.ceil() - 1.0;
+    let alpha = ((radius - m) * 255.0).floor() / 255.0;
+    let m = m as isize;
+
+    let (outer, inner) = if horizontal {
+        (image.height, image.width)
+    } else {
+        (image.width, image.height)
+    };
+    let mut line = vec![[0.0; 4]; inner];
+    for o in 0..outer as isize {
+        let get = |i: isize| {
+            if horizontal {
+                image.get(i, o)
+            } else {
+                image.get(o, i)
+            }
+        };
+        for (i, out) in line.iter_mut().enumerate() {
+            let i = i as isize;
+            let mut total = [0.0; 4];
+            let mut add = |color: [f32; 4], weight: f32| {
+                for c in 0..4 {
+                    total[c] += color[c] * weight;
+                }
+            };
+            add(get(i - m - 1), alpha);
+            add(get(i + m + 1), alpha);
+            for j in i - m..=i + m {
+                add(get(j), 1.0);
+            }
+            // This rounding imitates the fixed-point computations of Flash Player.
+            *out = total.map(|c| (c / full_size * 255.0).floor() / 255.0);
+        }
+        for (i, color) in line.iter().enumerate() {
+            let index = if horizontal {
+                o as usize * image.width + i
+            } else {
+                i * image.width + o as usize
+            };
+            image.pixels[index] = *color;
+        }
+    }
+}
+
+fn color_matrix(image: &mut FilterImage, filter: &ColorMatrixFilter) {
+    let m = &filter.matrix;
+    for pixel in &mut image.pixels {
+        let [r, g, b, a] = *pixel;
+        let (r, g, b) = if a > 0.0 {
+            (r / a, g / a, b / a)
+        } else {
+            (0.0, 0.0, 0.0)
+        };
+        let row = |i: usize| {
+            (m[i] * r + m[i + 1] * g + m[i + 2] * b + m[i + 3] * a + m[i + 4] / 255.0)
+                .clamp(0.0, 1.0)
+        };
+        let alpha = row(15);
+        *pixel = [row(0) * alpha, row(5) * alpha, row(10) * alpha, alpha];
+    }
+}
+
+/// Applies a glow, using the alpha of the image blurred and moved by `offset`.
+fn glow(image: &mut FilterImage, filter: &GlowFilter, offset: (isize, isize)) {
+    let mut blurred = FilterImage {
+        width: image.width,
+        height: image.height,
+        pixels: image.pixels.clone(),
+    };
+    blur(&mut blurred, &filter.inner_blur_filter());
+
+    let color = [
+        f32::from(filter.color.r) / 255.0,
+        f32::from(filter.color.g) / 255.0,
+        f32::from(filter.color.b) / 255.0,
+    ];
+    let color_alpha = f32::from(filter.color.a) / 255.0;
+    let strength = filter.strength.to_f32();
+    let inner = filter.is_inner();
+    let knockout = filter.is_knockout();
+    let composite_source = filter.composite_source();
+
+    for y in 0..image.height {
+        for x in 0..image.width {
+            let blur = blurred.get(x as isize - offset.0, y as isize - offset.1)[3];
+            let dest = &mut image.pixels[y * image.width + x];
+            let glow = |alpha: f32| [color[0] * alpha, color[1] * alpha, color[2] * alpha, alpha];
+            *dest = if inner {
+                let alpha = color_alpha * ((1.0 - blur) * strength).clamp(0.0, 1.0);
+                let shaded = glow(alpha * dest[3]);
+                if composite_source && !knockout {
+                    std::array::from_fn(|c| shaded[c] + dest[c] * (1.0 - alpha))
+                } else {
+                    shaded
+                }
+            } else {
+                let alpha = color_alpha * (blur * strength).clamp(0.0, 1.0);
+                if knockout {
+                    glow(alpha * (1.0 - dest[3]))
+                } else if composite_source {
+                    let shaded = glow(alpha * (1.0 - dest[3]));
+                    std::array::from_fn(|c| shaded[c] + dest[c])
+                } else {
+                    glow(alpha)
+                }
+            };
+        }
+    }
+}
@@ -0,0 +1,19 @@
+//! A render backend that draws everything on the CPU.
+//!
+//! This is much slower than the GPU backends, but it has no dependencies on
+//! any graphics device, which makes it useful for exporting frames and running
+//! image tests on headless machines.
+
+// Remove this when we decide on how to handle multithreaded rendering
+#![allow(clippy::arc_with_non_send_sync)]
+
+pub mod backend;
+
+mod bitmap;
+mod blend;
+mod filters;
+mod mesh;
+mod raster;
+mod surface;
+
+pub use backend::SoftwareRenderBackend;
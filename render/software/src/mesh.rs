@@ -0,0 +1,228 @@
+use ruffle_render::backend::{RenderBackend, ShapeHandle, ShapeHandleImpl};
+use ruffle_render::bitmap::{BitmapHandle, BitmapSource};
+use ruffle_render::shape_utils::GradientType;
+use ruffle_render::tessellator::{
+    DrawType as TessDrawType, Gradient as TessGradient, Mesh as TessMesh,
+};
+use swf::{GradientInterpolation, GradientSpread};
+
+/// The number of colors in a gradient ramp, matching Flash Player.
+const GRADIENT_RAMP_SIZE: usize = 256;
+
+#[derive(Debug)]
+pub struct Mesh {
+    pub draws: Vec<Draw>,
+}
+
+impl ShapeHandleImpl for Mesh {}
+
+pub fn as_mesh(handle: &ShapeHandle) -> &Mesh {
+    <dyn ShapeHandleImpl>::downcast_ref(&*handle.0).unwrap()
+}
+
+impl Mesh {
+    pub fn new(
+        mesh: TessMesh,
+        bitmap_source: &dyn BitmapSource,
+        backend: &mut dyn RenderBackend,
+    ) -> Self {
+        let draws = mesh
+            .draws
+            .into_iter()
+            .map(|draw| Draw {
+                draw_type: match draw.draw_type {
+                    TessDrawType::Color => DrawType::Color,
+                    TessDrawType::Gradient { matrix, gradient } => DrawType::Gradient {
+                        matrix,
+                        gradient: Box::new(Gradient::new(&mesh.gradients[gradient])),
+                    },
+                    TessDrawType::Bitmap(bitmap) => DrawType::Bitmap {
+                        matrix: bitmap.matrix,
+                        handle: bitmap_source.bitmap_handle(bitmap.bitmap_id, backend),
+                        is_smoothed: bitmap.is_smoothed,
+                        is_repeating: bitmap.is_repeating,
+                    },
+                },
+                vertices: draw
+                    .vertices
+                    .into_iter()
+                    .map(|vertex| Vertex {
+                        position: [vertex.x, vertex.y],
+                        color: [
+                            f32::from(vertex.color.r) / 255.0,
+                            f32::from(vertex.color.g) / 255.0,
+                            f32::from(vertex.color.b) / 255.0,
+                            f32::from(vertex.color.a) / 255.0,
+                        ],
+                    })
+                    .collect(),
+                indices: draw.indices,
+                mask_index_count: draw.mask_index_count as usize,
+            })
+            .collect();
+        Self { draws }
+    }
+}
+
+#[derive(Debug)]
+pub struct Draw {
+    pub draw_type: DrawType,
+    pub vertices: Vec<Vertex>,
+    pub indices: Vec<u32>,
+    pub mask_index_count: usize,
+}
+
+#[derive(Debug)]
+pub enum DrawType {
+    Color,
+    Gradient {
+        matrix: [[f32; 3]; 3],
+        gradient: Box<Gradient>,
+    },
+    Bitmap {
+        matrix: [[f32; 3]; 3],
+        handle: Option<BitmapHandle>,
+        is_smoothed: bool,
+        is_repeating: bool,
+    },
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Vertex {
+    pub position: [f32; 2],
+    /// The straight RGBA color of this vertex, normalized to `[0.0, 1.0]`.
+    pub color: [f32; 4],
+}
+
+#[derive(Debug)]
+pub struct Gradient {
+    gradient_type: GradientType,
+    repeat_mode: GradientSpread,
+    focal_point: f32,
+    interpolation: GradientInterpolation,
+    ratios: Vec<f32>,
+    /// Straight RGBA colors, converted to linear RGB for `LinearRgb` gradients.
+    colors: Vec<[f32; 4]>,
+}
+
+impl Gradient {
+    fn new(gradient: &TessGradient) -> Self {
+        let colors = gradient
+            .records
+            .iter()
+            .map(|record| {
+                let mut color = [
+                    f32::from(record.color.r) / 255.0,
+                    f32::from(record.color.g) / 255.0,
+                    f32::from(record.color.b) / 255.0,
+                    f32::from(record.color.a) / 255.0,
+                ];
+                if gradient.interpolation == GradientInterpolation::LinearRgb {
+                    for c in &mut color[..3] {
+                        *c = srgb_to_linear(*c);
+                    }
+                }
+                color
+            })
+            .collect();
+
+        Self {
+            gradient_type: gradient.gradient_type,
+            repeat_mode: gradient.repeat_mode,
+            focal_point: gradient.focal_point.to_f32().clamp(-0.98, 0.98),
+            interpolation: gradient.interpolation,
+            ratios: gradient
+                .records
+                .iter()
+                .map(|record| f32::from(record.ratio) / 255.0)
+                .collect(),
+            colors,
+        }
+    }
+
+    /// Builds the premultiplied color ramp of this gradient, after applying the given color transform.
+    pub fn ramp(&self, mult: [f32; 4], add: [f32; 4]) -> Vec<[f32; 4]> {
+        let (Some(&first), Some(&last)) = (self.colors.first(), self.colors.last()) else {
+            return vec![[0.0; 4]];
+        };
+        let transform = |color: [f32; 4]| -> [f32; 4] {
+            std::array::from_fn(|c| (color[c] * mult[c] + add[c]).clamp(0.0, 1.0))
+        };
+
+        (0..GRADIENT_RAMP_SIZE)
+            .map(|i| {
+                let t = i as f32 / (GRADIENT_RAMP_SIZE - 1) as f32;
+                let mut color = match self.ratios.iter().position(|&ratio| t <= ratio) {
+                    Some(0) => transform(first),
+                    Some(i) => {
+                        let a = (t - self.ratios[i - 1]) / (self.ratios[i] - self.ratios[i - 1]);
+                        let from = transform(self.colors[i - 1]);
+                        let to = transform(self.colors[i]);
+                        std::array::from_fn(|c| from[c] + (to[c] - from[c]) * a)
+                    }
+                    None => transform(last),
+                };
+
+                if self.interpolation == GradientInterpolation::LinearRgb {
+                    for c in &mut color[..3] {
+                        *c = linear_to_srgb(*c);
+                    }
+                }
+
+                let alpha = color[3];
+                [color[0] * alpha, color[1] * alpha, color[2] * alpha, alpha]
+            })
+            .collect()
+    }
+
+    /// Looks up the color at the given gradient coordinates in a ramp built by [`Gradient::ramp`].
+    pub fn color_at(&self, ramp: &[[f32; 4]], u: f32, v: f32) -> [f32; 4] {
+        let t = match self.gradient_type {
+            GradientType::Linear => u,
+            GradientType::Radial => {
+                let (x, y) = (u * 2.0 - 1.0, v * 2.0 - 1.0);
+                (x * x + y * y).sqrt()
+            }
+            GradientType::Focal => {
+                let focal_point = self.focal_point;
+                let (x, y) = (focal_point - (u * 2.0 - 1.0), -(v * 2.0 - 1.0));
+                let l = (x * x + y * y).sqrt();
+                let (dx, dy) = (x / l, y / l);
+                l / ((1.0 - focal_point * focal_point * dy * dy).sqrt() + focal_point * dx)
+            }
+        };
+
+        let t = match self.repeat_mode {
+            GradientSpread::Pad => t.clamp(0.0, 1.0),
+            GradientSpread::Repeat => t.rem_euclid(1.0),
+            GradientSpread::Reflect => {
+                let t = t.abs().rem_euclid(2.0);
+                if t > 1.0 {
+                    2.0 - t
+                } else {
+                    t
+                }
+            }
+        };
+
+        // NaN (at the focal point itself) ends up as the first color.
+        let index = (t * (ramp.len() - 1) as f32).round() as usize;
+        ramp[index.min(ramp.len() - 1)]
+    }
+}
+
+fn srgb_to_linear(c: f32) -> f32 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        f32::powf((c + 0.055) / 1.055, 2.4)
+    }
+}
+
+fn linear_to_srgb(c: f32) -> f32 {
+    if c < 0.0031308 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+}
@@ -0,0 +1,406 @@
+use crate::bitmap::{as_texture, sample};
+use crate::mesh::{as_mesh, DrawType};
+use crate::surface::{MaskState, Surface};
+use ruffle_render::backend::ShapeHandle;
+use ruffle_render::bitmap::{BitmapHandle, PixelSnapping};
+use ruffle_render::commands::{CommandHandler, CommandList, RenderBlendMode};
+use ruffle_render::matrix::Matrix;
+use ruffle_render::quality::StageQuality;
+use ruffle_render::transform::Transform;
+use swf::{BlendMode, Color, Twips};
+
+/// A 2D affine transform in pixels, laid out like `ruffle_render::matrix::Matrix`.
+#[derive(Copy, Clone, Debug)]
+struct Affine {
+    a: f32,
+    b: f32,
+    c: f32,
+    d: f32,
+    tx: f32,
+    ty: f32,
+}
+
+impl Affine {
+    fn transform(&self, [x, y]: [f32; 2]) -> [f32; 2] {
+        [
+            self.a * x + self.c * y + self.tx,
+            self.b * x + self.d * y + self.ty,
+        ]
+    }
+
+    fn inverse(&self) -> Option<Self> {
+        let det = self.a * self.d - self.b * self.c;
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+        Some(Self {
+            a: self.d / det,
+            b: -self.b / det,
+            c: -self.c / det,
+            d: self.a / det,
+            tx: (self.c * self.ty - self.d * self.tx) / det,
+            ty: (self.b * self.tx - self.a * self.ty) / det,
+        })
+    }
+
+    /// Builds the transform that maps pixels to texture or gradient coordinates,
+    /// given the object's transform and a texture matrix as produced by the tessellator.
+    fn texture_space(&self, texture_matrix: &[[f32; 3]; 3]) -> Option<Self> {
+        let inverse = self.inverse()?;
+        let texture = Self {
+            a: texture_matrix[0][0],
+            b: texture_matrix[0][1],
+            c: texture_matrix[1][0],
+            d: texture_matrix[1][1],
+            tx: texture_matrix[2][0],
+            ty: texture_matrix[2][1],
+        };
+        Some(texture.then(&inverse))
+    }
+
+    /// Returns the transform equivalent to applying `first`, then `self`.
+    fn then(&self, first: &Self) -> Self {
+        Self {
+            a: self.a * first.a + self.c * first.b,
+            b: self.b * first.a + self.d * first.b,
+            c: self.a * first.c + self.c * first.d,
+            d: self.b * first.c + self.d * first.d,
+            tx: self.a * first.tx + self.c * first.ty + self.tx,
+            ty: self.b * first.tx + self.d * first.ty + self.ty,
+        }
+    }
+}
+
+impl From<Matrix> for Affine {
+    fn from(matrix: Matrix) -> Self {
+        Self {
+            a: matrix.a,
+            b: matrix.b,
+            c: matrix.c,
+            d: matrix.d,
+            tx: matrix.tx.to_pixels() as f32,
+            ty: matrix.ty.to_pixels() as f32,
+        }
+    }
+}
+
+/// Applies a color transform to a straight color, returning a premultiplied color.
+#[inline]
+fn apply_color_transform(color: [f32; 4], mult: &[f32; 4], add: &[f32; 4]) -> [f32; 4] {
+    let [r, g, b, a] = std::array::from_fn(|c| (color[c] * mult[c] + add[c]).clamp(0.0, 1.0));
+    [r * a, g * a, b * a, a]
+}
+
+/// Applies a color transform to a premultiplied color.
+#[inline]
+fn apply_color_transform_premultiplied(
+    color: [f32; 4],
+    mult: &[f32; 4],
+    add: &[f32; 4],
+) -> [f32; 4] {
+    let alpha = color[3];
+    if alpha <= 0.0 {
+        return color;
+    }
+    apply_color_transform(
+        [color[0] / alpha, color[1] / alpha, color[2] / alpha, alpha],
+        mult,
+        add,
+    )
+}
+
+fn premultiplied(color: Color) -> [f32; 4] {
+    let alpha = f32::from(color.a) / 255.0;
+    [
+        f32::from(color.r) / 255.0 * alpha,
+        f32::from(color.g) / 255.0 * alpha,
+        f32::from(color.b) / 255.0 * alpha,
+        alpha,
+    ]
+}
+
+/// Executes render commands onto a [`Surface`].
+pub struct CommandRenderer<'a> {
+    surface: &'a mut Surface,
+    quality: StageQuality,
+}
+
+impl<'a> CommandRenderer<'a> {
+    pub fn new(surface: &'a mut Surface, quality: StageQuality) -> Self {
+        Self { surface, quality }
+    }
+
+    fn fill_quad(
+        &mut self,
+        corners: [[f32; 2]; 4],
+        shader: &mut impl FnMut([f32; 2], [f32; 3]) -> [f32; 4],
+    ) {
+        self.surface
+            .fill_triangle([corners[0], corners[1], corners[2]], shader);
+        self.surface
+            .fill_triangle([corners[0], corners[2], corners[3]], shader);
+    }
+
+    fn draw_bitmap(
+        &mut self,
+        bitmap: &BitmapHandle,
+        transform: &Transform,
+        smoothing: bool,
+        pixel_snapping: PixelSnapping,
+    ) {
+        let texture = as_texture(bitmap);
+        let (width, height) = (texture.width() as f32, texture.height() as f32);
+
+        let mut matrix = transform.matrix;
+        pixel_snapping.apply(&mut matrix);
+        let matrix = Affine::from(matrix);
+        let Some(inverse) = matrix.inverse() else {
+            return;
+        };
+
+        let corners = [[0.0, 0.0], [width, 0.0], [width, height], [0.0, height]]
+            .map(|corner| matrix.transform(corner));
+
+        let mult = transform.color_transform.mult_rgba_normalized();
+        let add = transform.color_transform.add_rgba_normalized();
+        let smoothed = smoothing && self.quality != StageQuality::Low;
+        let pixels = texture.pixels();
+        self.fill_quad(corners, &mut |position, _| {
+            let [x, y] = inverse.transform(position);
+            let color = sample(
+                &pixels,
+                texture.width(),
+                texture.height(),
+                x / width,
+                y / height,
+                smoothed,
+                false,
+            );
+            apply_color_transform_premultiplied(color, &mult, &add)
+        });
+    }
+
+    /// Draws a one pixel wide line between two points given in pixels.
+    fn draw_hairline(&mut self, from: [f32; 2], to: [f32; 2], color: [f32; 4]) {
+        let (dx, dy) = (to[0] - from[0], to[1] - from[1]);
+        let length = (dx * dx + dy * dy).sqrt();
+        if length == 0.0 {
+            return;
+        }
+        let (nx, ny) = (-dy / length * 0.5, dx / length * 0.5);
+        self.fill_quad(
+            [
+                [from[0] + nx, from[1] + ny],
+                [to[0] + nx, to[1] + ny],
+                [to[0] - nx, to[1] - ny],
+                [from[0] - nx, from[1] - ny],
+            ],
+            &mut |_, _| color,
+        );
+    }
+}
+
+impl<'a> CommandHandler for CommandRenderer<'a> {
+    fn render_bitmap(
+        &mut self,
+        bitmap: BitmapHandle,
+        transform: Transform,
+        smoothing: bool,
+        pixel_snapping: PixelSnapping,
+    ) {
+        self.draw_bitmap(&bitmap, &transform, smoothing, pixel_snapping);
+    }
+
+    fn render_stage3d(&mut self, bitmap: BitmapHandle, transform: Transform) {
+        self.draw_bitmap(&bitmap, &transform, false, PixelSnapping::Never);
+    }
+
+    fn render_shape(&mut self, shape: ShapeHandle, transform: Transform) {
+        let mesh = as_mesh(&shape);
+        let matrix = Affine::from(transform.matrix);
+        let mult = transform.color_transform.mult_rgba_normalized();
+        let add = transform.color_transform.add_rgba_normalized();
+        let drawing_mask = self.surface.is_drawing_mask();
+
+        for draw in &mesh.draws {
+            // Strokes are not drawn when a shape is used as a mask.
+            let indices = if drawing_mask {
+                &draw.indices[..draw.mask_index_count]
+            } else {
+                &draw.indices[..]
+            };
+            let triangles = indices
+                .chunks_exact(3)
+                .map(|triangle| triangle.iter().map(|&i| &draw.vertices[i as usize]));
+
+            match &draw.draw_type {
+                DrawType::Color => {
+                    for triangle in triangles {
+                        let mut points = [[0.0; 2]; 3];
+                        let mut colors = [[0.0; 4]; 3];
+                        for (i, vertex) in triangle.enumerate() {
+                            points[i] = matrix.transform(vertex.position);
+                            colors[i] = apply_color_transform(vertex.color, &mult, &add);
+                        }
+                        if colors[0] == colors[1] && colors[1] == colors[2] {
+                            let color = colors[0];
+                            self.surface.fill_triangle(points, &mut |_, _| color);
+                        } else {
+                            self.surface.fill_triangle(points, &mut |_, weights| {
+                                std::array::from_fn(|c| {
+                                    colors[0][c] * weights[0]
+                                        + colors[1][c] * weights[1]
+                                        + colors[2][c] * weights[2]
+                                })
+                            });
+                        }
+                    }
+                }
+                DrawType::Gradient {
+                    matrix: gradient_matrix,
+                    gradient,
+                } => {
+                    let Some(to_gradient) = matrix.texture_space(gradient_matrix) else {
+                        continue;
+                    };
+                    let ramp = if drawing_mask {
+                        vec![[0.0; 4]]
+                    } else {
+                        gradient.ramp(mult, add)
+                    };
+                    for triangle in triangles {
+                        let mut points = [[0.0; 2]; 3];
+                        for (i, vertex) in triangle.enumerate() {
+                            points[i] = matrix.transform(vertex.position);
+                        }
+                        self.surface.fill_triangle(points, &mut |position, _| {
+                            let [u, v] = to_gradient.transform(position);
+                            gradient.color_at(&ramp, u, v)
+                        });
+                    }
+                }
+                DrawType::Bitmap {
+                    matrix: bitmap_matrix,
+                    handle,
+                    is_smoothed,
+                    is_repeating,
+                } => {
+                    let Some(handle) = handle else {
+                        continue;
+                    };
+                    let Some(to_texture) = matrix.texture_space(bitmap_matrix) else {
+                        continue;
+                    };
+                    let texture = as_texture(handle);
+                    let pixels = texture.pixels();
+                    let smoothed = *is_smoothed && self.quality != StageQuality::Low;
+                    for triangle in triangles {
+                        let mut points = [[0.0; 2]; 3];
+                        for (i, vertex) in triangle.enumerate() {
+                            points[i] = matrix.transform(vertex.position);
+                        }
+                        self.surface.fill_triangle(points, &mut |position, _| {
+                            let [u, v] = to_texture.transform(position);
+                            let color = sample(
+                                &pixels,
+                                texture.width(),
+                                texture.height(),
+                                u,
+                                v,
+                                smoothed,
+                                *is_repeating,
+                            );
+                            apply_color_transform_premultiplied(color, &mult, &add)
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    fn draw_rect(&mut self, color: Color, matrix: Matrix) {
+        let matrix = Affine::from(matrix);
+        let corners =
+            [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]].map(|corner| matrix.transform(corner));
+        let color = premultiplied(color);
+        self.fill_quad(corners, &mut |_, _| color);
+    }
+
+    fn draw_line(&mut self, color: Color, mut matrix: Matrix) {
+        matrix.tx += Twips::HALF;
+        matrix.ty += Twips::HALF;
+        let matrix = Affine::from(matrix);
+        self.draw_hairline(
+            matrix.transform([0.0, 0.0]),
+            matrix.transform([1.0, 0.0]),
+            premultiplied(color),
+        );
+    }
+
+    fn draw_line_rect(&mut self, color: Color, mut matrix: Matrix) {
+        matrix.tx += Twips::HALF;
+        matrix.ty += Twips::HALF;
+        let matrix = Affine::from(matrix);
+        let corners =
+            [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]].map(|corner| matrix.transform(corner));
+        let color = premultiplied(color);
+        for i in 0..4 {
+            self.draw_hairline(corners[i], corners[(i + 1) % 4], color);
+        }
+    }
+
+    fn push_mask(&mut self) {
+        debug_assert!(
+            self.surface.mask_state() == MaskState::NoMask
+                || self.surface.mask_state() == MaskState::DrawMaskedContent
+        );
+        let num_masks = self.surface.num_masks() + 1;
+        self.surface
+            .set_mask_state(MaskState::DrawMaskStencil, num_masks);
+    }
+
+    fn activate_mask(&mut self) {
+        debug_assert!(
+            self.surface.num_masks() > 0 && self.surface.mask_state() == MaskState::DrawMaskStencil
+        );
+        self.surface
+            .set_mask_state(MaskState::DrawMaskedContent, self.surface.num_masks());
+    }
+
+    fn deactivate_mask(&mut self) {
+        debug_assert!(
+            self.surface.num_masks() > 0
+                && self.surface.mask_state() == MaskState::DrawMaskedContent
+        );
+        self.surface
+            .set_mask_state(MaskState::ClearMaskStencil, self.surface.num_masks());
+    }
+
+    fn pop_mask(&mut self) {
+        debug_assert!(
+            self.surface.num_masks() > 0
+                && self.surface.mask_state() == MaskState::ClearMaskStencil
+        );
+        let num_masks = self.surface.num_masks() - 1;
+        let mask_state = if num_masks == 0 {
+            MaskState::NoMask
+        } else {
+            MaskState::DrawMaskedContent
+        };
+        self.surface.set_mask_state(mask_state, num_masks);
+    }
+
+    fn blend(&mut self, commands: CommandList, blend_mode: RenderBlendMode) {
+        let blend_mode = match blend_mode {
+            RenderBlendMode::Builtin(blend_mode) => blend_mode,
+            RenderBlendMode::Shader(_) => {
+                tracing::warn!("Shader blend modes are not supported by the software renderer");
+                BlendMode::Normal
+            }
+        };
+
+        let mut layer = self.surface.new_layer();
+        commands.execute(&mut CommandRenderer::new(&mut layer, self.quality));
+        self.surface.composite(&layer, blend_mode);
+    }
+}
@@ -0,0 +1,270 @@
+use crate::blend::blend;
+use ruffle_render::quality::StageQuality;
+use swf::BlendMode;
+
+/// Vertex coordinates are snapped to this many subdivisions of a sample,
+/// so that edges shared between triangles are rasterized exactly once.
+const SUBSAMPLE_BITS: u32 = 8;
+const SUBSAMPLE_SCALE: f32 = (1 << SUBSAMPLE_BITS) as f32;
+
+/// Coordinates are clamped to this many samples away from the origin, to keep
+/// the edge functions from overflowing.
+const MAX_COORDINATE: f32 = (1 << 21) as f32;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum MaskState {
+    NoMask,
+    DrawMaskStencil,
+    DrawMaskedContent,
+    ClearMaskStencil,
+}
+
+/// A supersampled render target.
+///
+/// Every pixel is made up of a square grid of samples, each storing a
+/// premultiplied color and a stencil value. Shapes are rasterized per sample
+/// (much like MSAA on the GPU), and the samples are averaged when the surface
+/// is resolved back into pixels.
+pub struct Surface {
+    width: u32,
+    height: u32,
+    samples: u32,
+    colors: Vec<[f32; 4]>,
+    stencil: Vec<u8>,
+    mask_state: MaskState,
+    num_masks: u8,
+}
+
+impl Surface {
+    /// Creates a surface filled with the given premultiplied color.
+    pub fn new(width: u32, height: u32, quality: StageQuality, clear: [f32; 4]) -> Self {
+        Self::with_samples(width, height, samples_per_axis(quality), clear)
+    }
+
+    /// Creates a surface containing the given premultiplied RGBA pixels.
+    pub fn from_pixels(width: u32, height: u32, quality: StageQuality, pixels: &[u8]) -> Self {
+        let mut surface = Self::new(width, height, quality, [0.0; 4]);
+        let samples = surface.samples as usize;
+        let sample_width = surface.sample_width() as usize;
+        for (i, color) in surface.colors.iter_mut().enumerate() {
+            let x = (i % sample_width) / samples;
+            let y = (i / sample_width) / samples;
+            let p = (y * width as usize + x) * 4;
+            *color = std::array::from_fn(|c| f32::from(pixels[p + c]) / 255.0);
+        }
+        surface
+    }
+
+    /// Creates an empty surface with the same dimensions and sample count as this one.
+    pub fn new_layer(&self) -> Self {
+        Self::with_samples(self.width, self.height, self.samples, [0.0; 4])
+    }
+
+    fn with_samples(width: u32, height: u32, samples: u32, clear: [f32; 4]) -> Self {
+        let len = (width * samples) as usize * (height * samples) as usize;
+        Self {
+            width,
+            height,
+            samples,
+            colors: vec![clear; len],
+            stencil: vec![0; len],
+            mask_state: MaskState::NoMask,
+            num_masks: 0,
+        }
+    }
+
+    fn sample_width(&self) -> u32 {
+        self.width * self.samples
+    }
+
+    fn sample_height(&self) -> u32 {
+        self.height * self.samples
+    }
+
+    /// Averages the samples of each pixel, producing premultiplied RGBA pixels.
+    pub fn resolve(&self) -> Vec<u8> {
+        let samples = self.samples as usize;
+        let sample_width = self.sample_width() as usize;
+        let scale = 255.0 / (samples * samples) as f32;
+        let mut pixels = Vec::with_capacity(self.width as usize * self.height as usize * 4);
+        for y in 0..self.height as usize {
+            for x in 0..self.width as usize {
+                let mut sum = [0.0; 4];
+                for sy in y * samples..(y + 1) * samples {
+                    let row = sy * sample_width;
+                    for color in &self.colors[row + x * samples..row + (x + 1) * samples] {
+                        for c in 0..4 {
+                            sum[c] += color[c];
+                        }
+                    }
+                }
+                pixels.extend(sum.map(|c| (c * scale).round().clamp(0.0, 255.0) as u8));
+            }
+        }
+        pixels
+    }
+
+    pub fn mask_state(&self) -> MaskState {
+        self.mask_state
+    }
+
+    pub fn num_masks(&self) -> u8 {
+        self.num_masks
+    }
+
+    pub fn set_mask_state(&mut self, mask_state: MaskState, num_masks: u8) {
+        self.mask_state = mask_state;
+        self.num_masks = num_masks;
+    }
+
+    /// Whether drawing only affects the stencil, rather than the color of the surface.
+    pub fn is_drawing_mask(&self) -> bool {
+        matches!(
+            self.mask_state,
+            MaskState::DrawMaskStencil | MaskState::ClearMaskStencil
+        )
+    }
+
+    /// Updates the stencil for a covered sample when drawing or clearing a mask.
+    ///
+    /// Returns `true` if the sample should be drawn to instead.
+    #[inline]
+    fn apply_mask(&mut self, i: usize) -> bool {
+        match self.mask_state {
+            MaskState::NoMask => true,
+            MaskState::DrawMaskedContent => self.stencil[i] == self.num_masks,
+            MaskState::DrawMaskStencil => {
+                if self.stencil[i] == self.num_masks - 1 {
+                    self.stencil[i] = self.num_masks;
+                }
+                false
+            }
+            MaskState::ClearMaskStencil => {
+                if self.stencil[i] == self.num_masks {
+                    self.stencil[i] = self.num_masks - 1;
+                }
+                false
+            }
+        }
+    }
+
+    /// Rasterizes a triangle given in pixel coordinates, compositing the colors
+    /// returned by `shader` over every sample it covers.
+    ///
+    /// The shader is called with the position of the sample in pixels and its
+    /// barycentric coordinates, and must return a premultiplied color.
+    pub fn fill_triangle(
+        &mut self,
+        points: [[f32; 2]; 3],
+        shader: &mut impl FnMut([f32; 2], [f32; 3]) -> [f32; 4],
+    ) {
+        let samples = self.samples as f32;
+        let to_fixed = |p: [f32; 2]| -> Option<[i64; 2]> {
+            if !p[0].is_finite() || !p[1].is_finite() {
+                return None;
+            }
+            Some(p.map(|c| {
+                ((c * samples).clamp(-MAX_COORDINATE, MAX_COORDINATE) * SUBSAMPLE_SCALE).round()
+                    as i64
+            }))
+        };
+        let (Some(v0), Some(mut v1), Some(mut v2)) = (
+            to_fixed(points[0]),
+            to_fixed(points[1]),
+            to_fixed(points[2]),
+        ) else {
+            return;
+        };
+
+        let edge = |a: [i64; 2], b: [i64; 2], p: [i64; 2]| {
+            (b[0] - a[0]) * (p[1] - a[1]) - (b[1] - a[1]) * (p[0] - a[0])
+        };
+        let mut area = edge(v0, v1, v2);
+        if area == 0 {
+            return;
+        }
+        let mut swapped = false;
+        if area < 0 {
+            std::mem::swap(&mut v1, &mut v2);
+            area = -area;
+            swapped = true;
+        }
+
+        // Top-left fill rule: a sample lying exactly on an edge belongs to only
+        // one of the two triangles sharing that edge.
+        let bias = |a: [i64; 2], b: [i64; 2]| {
+            let (dx, dy) = (b[0] - a[0], b[1] - a[1]);
+            if dy < 0 || (dy == 0 && dx > 0) {
+                0
+            } else {
+                -1
+            }
+        };
+        let bias0 = bias(v1, v2);
+        let bias1 = bias(v2, v0);
+        let bias2 = bias(v0, v1);
+
+        let half = 1 << (SUBSAMPLE_BITS - 1);
+        let to_sample = |c: i64| (c - half) >> SUBSAMPLE_BITS;
+        let min_x = to_sample(v0[0].min(v1[0]).min(v2[0])).max(0);
+        let min_y = to_sample(v0[1].min(v1[1]).min(v2[1])).max(0);
+        let max_x =
+            (to_sample(v0[0].max(v1[0]).max(v2[0])) + 1).min(i64::from(self.sample_width()) - 1);
+        let max_y =
+            (to_sample(v0[1].max(v1[1]).max(v2[1])) + 1).min(i64::from(self.sample_height()) - 1);
+
+        let sample_width = self.sample_width() as usize;
+        let inv_area = 1.0 / area as f32;
+        for sy in min_y..=max_y {
+            let py = (sy << SUBSAMPLE_BITS) + half;
+            for sx in min_x..=max_x {
+                let p = [(sx << SUBSAMPLE_BITS) + half, py];
+                let w0 = edge(v1, v2, p) + bias0;
+                let w1 = edge(v2, v0, p) + bias1;
+                let w2 = edge(v0, v1, p) + bias2;
+                if w0 < 0 || w1 < 0 || w2 < 0 {
+                    continue;
+                }
+
+                let i = sy as usize * sample_width + sx as usize;
+                if !self.apply_mask(i) {
+                    continue;
+                }
+
+                let (w0, w1, w2) = (
+                    (w0 - bias0) as f32 * inv_area,
+                    (w1 - bias1) as f32 * inv_area,
+                    (w2 - bias2) as f32 * inv_area,
+                );
+                let barycentric = if swapped { [w0, w2, w1] } else { [w0, w1, w2] };
+                let position = [(sx as f32 + 0.5) / samples, (sy as f32 + 0.5) / samples];
+                let src = shader(position, barycentric);
+                self.colors[i] = blend(BlendMode::Normal, src, self.colors[i]);
+            }
+        }
+    }
+
+    /// Composites a layer on top of this surface with the given blend mode.
+    pub fn composite(&mut self, layer: &Surface, blend_mode: BlendMode) {
+        debug_assert_eq!(self.colors.len(), layer.colors.len());
+        for (i, &src) in layer.colors.iter().enumerate() {
+            // Every blend mode leaves the destination untouched where nothing was drawn.
+            if src == [0.0; 4] {
+                continue;
+            }
+            if self.apply_mask(i) {
+                self.colors[i] = blend(blend_mode, src, self.colors[i]);
+            }
+        }
+    }
+}
+
+/// The number of samples taken along each axis of a pixel for the given quality.
+fn samples_per_axis(quality: StageQuality) -> u32 {
+    match quality.sample_count() {
+        0..=1 => 1,
+        2..=4 => 2,
+        5..=9 => 3,
+        _ => 4,
+    }
+}
@@ -0,0 +1,460 @@
+use crate::matrix::Matrix;
+use swf::Twips;
+
+/// A 4x4 transformation matrix, used by display objects with a 3D transform.
+///
+/// The elements are stored in column-major order, matching the layout of
+/// `flash.geom.Matrix3D.rawData`: `raw_data[12..15]` holds the translation.
+/// Like [`Matrix`], the translation is usually expressed in twips.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Matrix3D {
+    pub raw_data: [f64; 16],
+}
+
+impl Matrix3D {
+    pub const IDENTITY: Self = Self {
+        raw_data: [
+            1.0, 0.0, 0.0, 0.0, //
+            0.0, 1.0, 0.0, 0.0, //
+            0.0, 0.0, 1.0, 0.0, //
+            0.0, 0.0, 0.0, 1.0, //
+        ],
+    };
+
+    pub const TWIPS_TO_PIXELS: Self = Self::scale(
+        1.0 / Twips::TWIPS_PER_PIXEL as f64,
+        1.0 / Twips::TWIPS_PER_PIXEL as f64,
+        1.0 / Twips::TWIPS_PER_PIXEL as f64,
+    );
+
+    pub const PIXELS_TO_TWIPS: Self = Self::scale(
+        Twips::TWIPS_PER_PIXEL as f64,
+        Twips::TWIPS_PER_PIXEL as f64,
+        Twips::TWIPS_PER_PIXEL as f64,
+    );
+
+    /// Creates a matrix from its rows, which is easier to read than the column-major storage.
+    pub const fn from_rows(rows: [[f64; 4]; 4]) -> Self {
+        let [r0, r1, r2, r3] = rows;
+        Self {
+            raw_data: [
+                r0[0], r1[0], r2[0], r3[0], //
+                r0[1], r1[1], r2[1], r3[1], //
+                r0[2], r1[2], r2[2], r3[2], //
+                r0[3], r1[3], r2[3], r3[3], //
+            ],
+        }
+    }
+
+    pub const fn scale(scale_x: f64, scale_y: f64, scale_z: f64) -> Self {
+        Self::from_rows([
+            [scale_x, 0.0, 0.0, 0.0],
+            [0.0, scale_y, 0.0, 0.0],
+            [0.0, 0.0, scale_z, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    pub const fn translate(x: f64, y: f64, z: f64) -> Self {
+        Self::from_rows([
+            [1.0, 0.0, 0.0, x],
+            [0.0, 1.0, 0.0, y],
+            [0.0, 0.0, 1.0, z],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    /// A rotation around the X axis, in radians.
+    pub fn rotate_x(angle: f64) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Self::from_rows([
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, cos, -sin, 0.0],
+            [0.0, sin, cos, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    /// A rotation around the Y axis, in radians.
+    pub fn rotate_y(angle: f64) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Self::from_rows([
+            [cos, 0.0, sin, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [-sin, 0.0, cos, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    /// A rotation around the Z axis, in radians.
+    ///
+    /// This is the same rotation as [`Matrix::rotate`].
+    pub fn rotate_z(angle: f64) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Self::from_rows([
+            [cos, -sin, 0.0, 0.0],
+            [sin, cos, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    /// A perspective projection looking down the Z axis, with the eye placed
+    /// `focal_length` units in front of the `(center_x, center_y)` point of the Z = 0 plane.
+    ///
+    /// Points on the Z = 0 plane are left untouched, while points further away
+    /// converge towards the projection center.
+    pub fn perspective(focal_length: f64, center_x: f64, center_y: f64) -> Self {
+        Self::from_rows([
+            [1.0, 0.0, center_x / focal_length, 0.0],
+            [0.0, 1.0, center_y / focal_length, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 1.0 / focal_length, 1.0],
+        ])
+    }
+
+    /// Builds a matrix that scales, then rotates around the X, Y and Z axes (in radians),
+    /// then translates.
+    ///
+    /// This is the Euler angles form of `flash.geom.Matrix3D.recompose`.
+    pub fn recompose(translation: [f64; 3], rotation: [f64; 3], scale: [f64; 3]) -> Self {
+        let [x, y, z] = translation;
+        let [rotation_x, rotation_y, rotation_z] = rotation;
+        let [scale_x, scale_y, scale_z] = scale;
+        Self::translate(x, y, z)
+            * Self::rotate_z(rotation_z)
+            * Self::rotate_y(rotation_y)
+            * Self::rotate_x(rotation_x)
+            * Self::scale(scale_x, scale_y, scale_z)
+    }
+
+    /// Splits this matrix into the translation, Euler angles and scale that
+    /// [`Matrix3D::recompose`] would build it from.
+    ///
+    /// Any skew or perspective in the matrix is lost.
+    pub fn decompose(&self) -> ([f64; 3], [f64; 3], [f64; 3]) {
+        let m = &self.raw_data;
+        let translation = [m[12], m[13], m[14]];
+
+        let length = |v: [f64; 3]| (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt();
+        let x_axis = [m[0], m[1], m[2]];
+        let y_axis = [m[4], m[5], m[6]];
+        let z_axis = [m[8], m[9], m[10]];
+        let mut scale = [length(x_axis), length(y_axis), length(z_axis)];
+        let det = m[0] * (m[5] * m[10] - m[6] * m[9]) - m[1] * (m[4] * m[10] - m[6] * m[8])
+            + m[2] * (m[4] * m[9] - m[5] * m[8]);
+        if det < 0.0 {
+            scale[2] = -scale[2];
+        }
+
+        let normalize = |v: [f64; 3], scale: f64| {
+            if scale == 0.0 {
+                v
+            } else {
+                v.map(|c| c / scale)
+            }
+        };
+        let x_axis = normalize(x_axis, scale[0]);
+        let y_axis = normalize(y_axis, scale[1]);
+        let z_axis = normalize(z_axis, scale[2]);
+
+        let rotation_y = (-x_axis[2]).clamp(-1.0, 1.0).asin();
+        let (rotation_x, rotation_z) = if x_axis[2].abs() != 1.0 {
+            (y_axis[2].atan2(z_axis[2]), x_axis[1].atan2(x_axis[0]))
+        } else {
+            (y_axis[0].atan2(y_axis[1]), 0.0)
+        };
+
+        (translation, [rotation_x, rotation_y, rotation_z], scale)
+    }
+
+    #[inline]
+    pub fn get(&self, row: usize, column: usize) -> f64 {
+        self.raw_data[column * 4 + row]
+    }
+
+    /// Whether this matrix only contains a 2D affine transform, and can be
+    /// represented by a [`Matrix`] without any loss.
+    pub fn is_2d(&self) -> bool {
+        let m = &self.raw_data;
+        m[2] == 0.0
+            && m[3] == 0.0
+            && m[6] == 0.0
+            && m[7] == 0.0
+            && m[8] == 0.0
+            && m[9] == 0.0
+            && m[10] == 1.0
+            && m[11] == 0.0
+            && m[14] == 0.0
+            && m[15] == 1.0
+    }
+
+    /// Returns the 2D affine part of this matrix, as seen when looking straight down the Z axis
+    /// without any perspective.
+    pub fn to_matrix(&self) -> Matrix {
+        let m = &self.raw_data;
+        Matrix {
+            a: m[0] as f32,
+            b: m[1] as f32,
+            c: m[4] as f32,
+            d: m[5] as f32,
+            tx: Twips::new(m[12].round() as i32),
+            ty: Twips::new(m[13].round() as i32),
+        }
+    }
+
+    pub fn determinant(&self) -> f64 {
+        let m = |row, column| self.get(row, column);
+        let s0 = m(0, 0) * m(1, 1) - m(1, 0) * m(0, 1);
+        let s1 = m(0, 0) * m(1, 2) - m(1, 0) * m(0, 2);
+        let s2 = m(0, 0) * m(1, 3) - m(1, 0) * m(0, 3);
+        let s3 = m(0, 1) * m(1, 2) - m(1, 1) * m(0, 2);
+        let s4 = m(0, 1) * m(1, 3) - m(1, 1) * m(0, 3);
+        let s5 = m(0, 2) * m(1, 3) - m(1, 2) * m(0, 3);
+        let c5 = m(2, 2) * m(3, 3) - m(3, 2) * m(2, 3);
+        let c4 = m(2, 1) * m(3, 3) - m(3, 1) * m(2, 3);
+        let c3 = m(2, 1) * m(3, 2) - m(3, 1) * m(2, 2);
+        let c2 = m(2, 0) * m(3, 3) - m(3, 0) * m(2, 3);
+        let c1 = m(2, 0) * m(3, 2) - m(3, 0) * m(2, 2);
+        let c0 = m(2, 0) * m(3, 1) - m(3, 0) * m(2, 1);
+        s0 * c5 - s1 * c4 + s2 * c3 + s3 * c2 - s4 * c1 + s5 * c0
+    }
+
+    pub fn inverse(&self) -> Option<Self> {
+        let m = |row, column| self.get(row, column);
+        let s0 = m(0, 0) * m(1, 1) - m(1, 0) * m(0, 1);
+        let s1 = m(0, 0) * m(1, 2) - m(1, 0) * m(0, 2);
+        let s2 = m(0, 0) * m(1, 3) - m(1, 0) * m(0, 3);
+        let s3 = m(0, 1) * m(1, 2) - m(1, 1) * m(0, 2);
+        let s4 = m(0, 1) * m(1, 3) - m(1, 1) * m(0, 3);
+        let s5 = m(0, 2) * m(1, 3) - m(1, 2) * m(0, 3);
+        let c5 = m(2, 2) * m(3, 3) - m(3, 2) * m(2, 3);
+        let c4 = m(2, 1) * m(3, 3) - m(3, 1) * m(2, 3);
+        let c3 = m(2, 1) * m(3, 2) - m(3, 1) * m(2, 2);
+        let c2 = m(2, 0) * m(3, 3) - m(3, 0) * m(2, 3);
+        let c1 = m(2, 0) * m(3, 2) - m(3, 0) * m(2, 2);
+        let c0 = m(2, 0) * m(3, 1) - m(3, 0) * m(2, 1);
+
+        let det = s0 * c5 - s1 * c4 + s2 * c3 + s3 * c2 - s4 * c1 + s5 * c0;
+        if det.abs() <= f64::EPSILON || !det.is_finite() {
+            return None;
+        }
+        let inv = 1.0 / det;
+        Some(Self::from_rows([
+            [
+                (m(1, 1) * c5 - m(1, 2) * c4 + m(1, 3) * c3) * inv,
+                (-m(0, 1) * c5 + m(0, 2) * c4 - m(0, 3) * c3) * inv,
+                (m(3, 1) * s5 - m(3, 2) * s4 + m(3, 3) * s3) * inv,
+                (-m(2, 1) * s5 + m(2, 2) * s4 - m(2, 3) * s3) * inv,
+            ],
+            [
+                (-m(1, 0) * c5 + m(1, 2) * c2 - m(1, 3) * c1) * inv,
+                (m(0, 0) * c5 - m(0, 2) * c2 + m(0, 3) * c1) * inv,
+                (-m(3, 0) * s5 + m(3, 2) * s2 - m(3, 3) * s1) * inv,
+                (m(2, 0) * s5 - m(2, 2) * s2 + m(2, 3) * s1) * inv,
+            ],
+            [
+                (m(1, 0) * c4 - m(1, 1) * c2 + m(1, 3) * c0) * inv,
+                (-m(0, 0) * c4 + m(0, 1) * c2 - m(0, 3) * c0) * inv,
+                (m(3, 0) * s4 - m(3, 1) * s2 + m(3, 3) * s0) * inv,
+                (-m(2, 0) * s4 + m(2, 1) * s2 - m(2, 3) * s0) * inv,
+            ],
+            [
+                (-m(1, 0) * c3 + m(1, 1) * c1 - m(1, 2) * c0) * inv,
+                (m(0, 0) * c3 - m(0, 1) * c1 + m(0, 2) * c0) * inv,
+                (-m(3, 0) * s3 + m(3, 1) * s1 - m(3, 2) * s0) * inv,
+                (m(2, 0) * s3 - m(2, 1) * s1 + m(2, 2) * s0) * inv,
+            ],
+        ]))
+    }
+
+    /// Transforms a point, returning its homogeneous coordinates `[x, y, z, w]`.
+    pub fn transform_point(&self, [x, y, z]: [f64; 3]) -> [f64; 4] {
+        std::array::from_fn(|row| {
+            self.get(row, 0) * x + self.get(row, 1) * y + self.get(row, 2) * z + self.get(row, 3)
+        })
+    }
+
+    /// Projects a point of the Z = 0 plane, dividing by the resulting `w`.
+    ///
+    /// Returns `None` if the point ends up behind the viewer.
+    pub fn project_point(&self, x: f64, y: f64) -> Option<[f64; 2]> {
+        let [out_x, out_y, _, w] = self.transform_point([x, y, 0.0]);
+        (w > 0.0).then(|| [out_x / w, out_y / w])
+    }
+
+    /// Finds the point of the Z = 0 plane that [`Matrix3D::project_point`] would project onto `(x, y)`.
+    ///
+    /// This is used to map mouse positions back into a display object's own coordinate space.
+    pub fn unproject_point(&self, x: f64, y: f64) -> Option<[f64; 2]> {
+        // Dropping the Z row and column leaves the homography between the Z = 0 plane and the screen.
+        let h = |row: usize, column: usize| self.get([0, 1, 3][row], [0, 1, 3][column]);
+        let (a, b, c) = (h(0, 0), h(0, 1), h(0, 2));
+        let (d, e, f) = (h(1, 0), h(1, 1), h(1, 2));
+        let (g, i, j) = (h(2, 0), h(2, 1), h(2, 2));
+
+        // Solve the homography using its adjugate; the determinant cancels out in the divide.
+        let out_x = (e * j - f * i) * x + (c * i - b * j) * y + (b * f - c * e);
+        let out_y = (f * g - d * j) * x + (a * j - c * g) * y + (c * d - a * f);
+        let out_w = (d * i - e * g) * x + (b * g - a * i) * y + (a * e - b * d);
+        if out_w.abs() <= f64::EPSILON {
+            return None;
+        }
+        let point = [out_x / out_w, out_y / out_w];
+        point.iter().all(|c| c.is_finite()).then_some(point)
+    }
+
+    /// Converts this matrix into a column-major world matrix for a vertex shader,
+    /// mapping vertices in pixels to pixels.
+    ///
+    /// The Z output is flattened to 0, as display objects are drawn in order rather than
+    /// depth-tested, and content close to the viewer must not get clipped.
+    pub fn to_world_matrix(&self) -> [[f32; 4]; 4] {
+        let m = Self::TWIPS_TO_PIXELS * *self * Self::PIXELS_TO_TWIPS;
+        std::array::from_fn(|column| {
+            std::array::from_fn(|row| {
+                if row == 2 {
+                    0.0
+                } else {
+                    m.get(row, column) as f32
+                }
+            })
+        })
+    }
+}
+
+impl Default for Matrix3D {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl std::ops::Mul for Matrix3D {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        let mut raw_data = [0.0; 16];
+        for column in 0..4 {
+            for row in 0..4 {
+                raw_data[column * 4 + row] =
+                    (0..4).map(|k| self.get(row, k) * rhs.get(k, column)).sum();
+            }
+        }
+        Self { raw_data }
+    }
+}
+
+impl From<Matrix> for Matrix3D {
+    fn from(matrix: Matrix) -> Self {
+        Self::from_rows([
+            [
+                matrix.a.into(),
+                matrix.c.into(),
+                0.0,
+                matrix.tx.get().into(),
+            ],
+            [
+                matrix.b.into(),
+                matrix.d.into(),
+                0.0,
+                matrix.ty.get().into(),
+            ],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    fn assert_matrix_eq(left: Matrix3D, right: Matrix3D) {
+        for (l, r) in left.raw_data.iter().zip(right.raw_data.iter()) {
+            assert_relative_eq!(l, r, epsilon = 1e-9);
+        }
+    }
+
+    #[test]
+    fn from_matrix_round_trips() {
+        let matrix = Matrix {
+            a: 2.0,
+            b: 0.5,
+            c: -0.25,
+            d: 3.0,
+            tx: Twips::new(100),
+            ty: Twips::new(-40),
+        };
+        let matrix3d = Matrix3D::from(matrix);
+        assert!(matrix3d.is_2d());
+        assert_eq!(matrix3d.to_matrix(), matrix);
+        assert_eq!(matrix3d.raw_data[12], 100.0);
+        assert_eq!(matrix3d.raw_data[13], -40.0);
+    }
+
+    #[test]
+    fn multiply_matches_2d() {
+        let a = Matrix::create_box(2.0, 3.0, Twips::new(20), Twips::new(40));
+        let b = Matrix::rotate(0.5);
+        let product = Matrix3D::from(a) * Matrix3D::from(b);
+        assert_matrix_eq(product, Matrix3D::from(a * b));
+    }
+
+    #[test]
+    fn inverse() {
+        let matrix = Matrix3D::translate(10.0, -20.0, 30.0)
+            * Matrix3D::rotate_x(0.3)
+            * Matrix3D::rotate_y(-1.2)
+            * Matrix3D::scale(2.0, 3.0, 4.0);
+        let inverse = matrix.inverse().expect("matrix is invertible");
+        assert_matrix_eq(matrix * inverse, Matrix3D::IDENTITY);
+        assert_relative_eq!(matrix.determinant(), 24.0, epsilon = 1e-9);
+        assert_eq!(Matrix3D::scale(1.0, 0.0, 1.0).inverse(), None);
+    }
+
+    #[test]
+    fn decompose_round_trips() {
+        let translation = [10.0, -20.0, 30.0];
+        let rotation = [0.3, -1.2, 2.5];
+        let scale = [2.0, 3.0, 4.0];
+        let matrix = Matrix3D::recompose(translation, rotation, scale);
+        let (out_translation, out_rotation, out_scale) = matrix.decompose();
+        for (out, expected) in out_translation
+            .iter()
+            .chain(&out_rotation)
+            .chain(&out_scale)
+            .zip(translation.iter().chain(&rotation).chain(&scale))
+        {
+            assert_relative_eq!(out, expected, epsilon = 1e-9);
+        }
+        assert_matrix_eq(
+            Matrix3D::recompose(out_translation, out_rotation, out_scale),
+            matrix,
+        );
+    }
+
+    #[test]
+    fn perspective() {
+        let projection = Matrix3D::perspective(500.0, 100.0, 100.0);
+        // Points on the Z = 0 plane are untouched.
+        assert_eq!(projection.project_point(20.0, 30.0), Some([20.0, 30.0]));
+        // Points at the focal distance are halfway to the projection center.
+        let [x, y, z, w] = projection.transform_point([0.0, 300.0, 500.0]);
+        assert_relative_eq!(x / w, 50.0);
+        assert_relative_eq!(y / w, 200.0);
+        assert_relative_eq!(z / w, 250.0);
+    }
+
+    #[test]
+    fn unproject_point() {
+        let matrix = Matrix3D::perspective(400.0, 50.0, 80.0)
+            * Matrix3D::translate(30.0, 10.0, 100.0)
+            * Matrix3D::rotate_y(0.7)
+            * Matrix3D::rotate_x(-0.4);
+        let [x, y] = matrix.project_point(12.0, -7.0).expect("point is visible");
+        let [local_x, local_y] = matrix.unproject_point(x, y).expect("plane is not edge-on");
+        assert_relative_eq!(local_x, 12.0, epsilon = 1e-9);
+        assert_relative_eq!(local_y, -7.0, epsilon = 1e-9);
+    }
+}
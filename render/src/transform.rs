@@ -1,4 +1,5 @@
 use crate::matrix::Matrix;
+use crate::matrix3d::Matrix3D;
 use swf::ColorTransform;
 
 /// Represents the transform for a DisplayObject.
@@ -7,6 +8,12 @@ use swf::ColorTransform;
 pub struct Transform {
     pub matrix: Matrix,
     pub color_transform: ColorTransform,
+
+    /// The full 3D transform, if this object or one of its ancestors has one.
+    ///
+    /// When this is set, `matrix` only holds its 2D approximation, which is
+    /// used by renderers that can't draw projected content.
+    pub matrix3d: Option<Matrix3D>,
 }
 
 pub struct TransformStack(Vec<Transform>);
@@ -20,9 +27,17 @@ impl TransformStack {
         let cur_transform = self.transform();
         let matrix = cur_transform.matrix * transform.matrix;
         let color_transform = cur_transform.color_transform * transform.color_transform;
+        let matrix3d = match (cur_transform.matrix3d, transform.matrix3d) {
+            (None, None) => None,
+            (cur_matrix3d, matrix3d) => Some(
+                cur_matrix3d.unwrap_or_else(|| cur_transform.matrix.into())
+                    * matrix3d.unwrap_or_else(|| transform.matrix.into()),
+            ),
+        };
         self.0.push(Transform {
             matrix,
             color_transform,
+            matrix3d,
         });
     }
 
@@ -0,0 +1,62 @@
+// NOTE: The `shader_filter_common.wgsl` source is prepended to this before compilation.
+
+struct Filter {
+    /// The weights of the matrix, in row-major order, packed four to an element.
+    weights: array<vec4<f32>, 64>,
+    default_color: vec4<f32>,
+    bias: f32,
+    divisor: f32,
+    matrix_x: u32,
+    matrix_y: u32,
+    /// The area of the texture that's being filtered, in pixels. Anything outside of it is out of bounds.
+    source_min: vec2<i32>,
+    source_max: vec2<i32>,
+    clamp_edges: u32,
+    preserve_alpha: u32,
+}
+
+@group(0) @binding(0) var texture: texture_2d<f32>;
+@group(0) @binding(1) var<uniform> filter_args: Filter;
+
+@vertex
+fn main_vertex(in: filter__VertexInput) -> filter__VertexOutput {
+    return filter__main_vertex(in);
+}
+
+fn unmultiply(color: vec4<f32>) -> vec4<f32> {
+    if (color.a == 0.0) {
+        return vec4<f32>(0.0);
+    }
+    return vec4<f32>(color.rgb / color.a, color.a);
+}
+
+fn load(pos: vec2<i32>) -> vec4<f32> {
+    if (any(pos < filter_args.source_min) || any(pos >= filter_args.source_max)) {
+        if (filter_args.clamp_edges == 0u) {
+            return filter_args.default_color;
+        }
+        return unmultiply(textureLoad(texture, clamp(pos, filter_args.source_min, filter_args.source_max - 1), 0));
+    }
+    return unmultiply(textureLoad(texture, pos, 0));
+}
+
+@fragment
+fn main_fragment(in: filter__VertexOutput) -> @location(0) vec4<f32> {
+    let pos = vec2<i32>(floor(in.uv * vec2<f32>(textureDimensions(texture))));
+    let center = vec2<i32>(i32(filter_args.matrix_x / 2u), i32(filter_args.matrix_y / 2u));
+
+    var total = vec4<f32>(0.0);
+    for (var y = 0u; y < filter_args.matrix_y; y++) {
+        for (var x = 0u; x < filter_args.matrix_x; x++) {
+            let index = y * filter_args.matrix_x + x;
+            let weight = filter_args.weights[index / 4u][index % 4u];
+            total += load(pos + vec2<i32>(i32(x), i32(y)) - center) * weight;
+        }
+    }
+
+    var color = saturate(total / filter_args.divisor + filter_args.bias / 255.0);
+    if (filter_args.preserve_alpha != 0u) {
+        color.a = load(pos).a;
+    }
+    return vec4<f32>(color.rgb * color.a, color.a);
+}
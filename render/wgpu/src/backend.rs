@@ -772,6 +772,9 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
                 | Filter::ShaderFilter(_)
                 | Filter::BevelFilter(_)
                 | Filter::DisplacementMapFilter(_)
+                | Filter::ConvolutionFilter(_)
+                | Filter::GradientGlowFilter(_)
+                | Filter::GradientBevelFilter(_)
         )
     }
 
@@ -1,29 +1,36 @@
 mod bevel;
 mod blur;
 mod color_matrix;
+mod convolution;
 mod displacement_map;
 mod drop_shadow;
 mod glow;
+mod gradient_bevel;
+mod gradient_glow;
 mod shader;
 
-use std::collections::HashSet;
-use std::sync::{Mutex, OnceLock};
-
 use crate::buffer_pool::TexturePool;
 use crate::descriptors::Descriptors;
 use crate::filters::bevel::BevelFilter;
 use crate::filters::blur::BlurFilter;
 use crate::filters::color_matrix::ColorMatrixFilter;
+use crate::filters::convolution::ConvolutionFilter;
 use crate::filters::displacement_map::DisplacementMapFilter;
 use crate::filters::drop_shadow::DropShadowFilter;
 use crate::filters::glow::GlowFilter;
+use crate::filters::gradient_bevel::GradientBevelFilter;
+use crate::filters::gradient_glow::GradientGlowFilter;
 use crate::filters::shader::ShaderFilter;
 use crate::surface::target::CommandTarget;
 use bytemuck::{Pod, Zeroable};
 use ruffle_render::filters::Filter;
-use wgpu::util::StagingBelt;
+use swf::GradientRecord;
+use wgpu::util::{DeviceExt, StagingBelt};
 use wgpu::vertex_attr_array;
 
+/// The width of the color ramp used by the gradient filters.
+const GRADIENT_RAMP_SIZE: usize = 256;
+
 #[derive(Debug)]
 pub struct FilterSource<'a> {
     pub texture: &'a wgpu::Texture,
@@ -185,6 +192,56 @@ impl<'a> FilterSource<'a> {
     }
 }
 
+/// Creates a `GRADIENT_RAMP_SIZE`x1 texture of premultiplied colors, used by the gradient filters
+/// to look up the color for a given glow or bevel strength.
+pub fn create_gradient_ramp(
+    descriptors: &Descriptors,
+    records: &[GradientRecord],
+) -> wgpu::TextureView {
+    let mut colors = [0; GRADIENT_RAMP_SIZE * 4];
+    if let (Some(first), Some(last)) = (records.first(), records.last()) {
+        for (t, color) in colors.chunks_exact_mut(4).enumerate() {
+            let t = t as f32;
+            let (from, to, a) = match records.iter().position(|r| t <= f32::from(r.ratio)) {
+                Some(0) => (first, first, 0.0),
+                Some(i) => {
+                    let (from, to) = (&records[i - 1], &records[i]);
+                    let (from_ratio, to_ratio) = (f32::from(from.ratio), f32::from(to.ratio));
+                    (from, to, (t - from_ratio) / (to_ratio - from_ratio))
+                }
+                None => (last, last, 0.0),
+            };
+            let lerp = |from: u8, to: u8| f32::from(from) + (f32::from(to) - f32::from(from)) * a;
+            let alpha = lerp(from.color.a, to.color.a);
+            color[0] = (lerp(from.color.r, to.color.r) * alpha / 255.0).round() as u8;
+            color[1] = (lerp(from.color.g, to.color.g) * alpha / 255.0).round() as u8;
+            color[2] = (lerp(from.color.b, to.color.b) * alpha / 255.0).round() as u8;
+            color[3] = alpha.round() as u8;
+        }
+    }
+
+    let texture = descriptors.device.create_texture_with_data(
+        &descriptors.queue,
+        &wgpu::TextureDescriptor {
+            label: create_debug_label!("Gradient filter ramp").as_deref(),
+            size: wgpu::Extent3d {
+                width: GRADIENT_RAMP_SIZE as u32,
+                height: 1,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        },
+        wgpu::util::TextureDataOrder::LayerMajor,
+        &colors[..],
+    );
+    texture.create_view(&Default::default())
+}
+
 pub struct Filters {
     pub blur: BlurFilter,
     pub color_matrix: ColorMatrixFilter,
@@ -192,6 +249,9 @@ pub struct Filters {
     pub glow: GlowFilter,
     pub bevel: BevelFilter,
     pub displacement_map: DisplacementMapFilter,
+    pub convolution: ConvolutionFilter,
+    pub gradient_glow: GradientGlowFilter,
+    pub gradient_bevel: GradientBevelFilter,
 }
 
 impl Filters {
@@ -203,6 +263,9 @@ impl Filters {
             glow: GlowFilter::new(device),
             bevel: BevelFilter::new(device),
             displacement_map: DisplacementMapFilter::new(device),
+            convolution: ConvolutionFilter::new(device),
+            gradient_glow: GradientGlowFilter::new(device),
+            gradient_bevel: GradientBevelFilter::new(device),
         }
     }
 
@@ -276,31 +339,32 @@ impl Filters {
                 &source,
                 &filter,
             ),
-            filter => {
-                static WARNED_FILTERS: OnceLock<Mutex<HashSet<&'static str>>> = OnceLock::new();
-                let name = match filter {
-                    Filter::GradientGlowFilter(_) => "GradientGlowFilter",
-                    Filter::GradientBevelFilter(_) => "GradientBevelFilter",
-                    Filter::ConvolutionFilter(_) => "ConvolutionFilter",
-                    Filter::ColorMatrixFilter(_)
-                    | Filter::BlurFilter(_)
-                    | Filter::GlowFilter(_)
-                    | Filter::DropShadowFilter(_)
-                    | Filter::BevelFilter(_)
-                    | Filter::DisplacementMapFilter(_)
-                    | Filter::ShaderFilter(_) => unreachable!(),
-                };
-                // Only warn once per filter type
-                if WARNED_FILTERS
-                    .get_or_init(Default::default)
-                    .lock()
-                    .unwrap()
-                    .insert(name)
-                {
-                    tracing::warn!("Unsupported filter {filter:?}");
-                }
-                None
-            }
+            Filter::ConvolutionFilter(filter) => descriptors.filters.convolution.apply(
+                descriptors,
+                texture_pool,
+                draw_encoder,
+                staging_belt,
+                &source,
+                &filter,
+            ),
+            Filter::GradientGlowFilter(filter) => Some(descriptors.filters.gradient_glow.apply(
+                descriptors,
+                texture_pool,
+                draw_encoder,
+                staging_belt,
+                &source,
+                &filter,
+                &self.blur,
+            )),
+            Filter::GradientBevelFilter(filter) => Some(descriptors.filters.gradient_bevel.apply(
+                descriptors,
+                texture_pool,
+                draw_encoder,
+                staging_belt,
+                &source,
+                &filter,
+                &self.blur,
+            )),
         };
 
         let target = target.unwrap_or_else(|| {
@@ -0,0 +1,270 @@
+use crate::backend::RenderTargetMode;
+use crate::buffer_pool::TexturePool;
+use crate::descriptors::Descriptors;
+use crate::filters::{FilterSource, FilterVertex, VERTEX_BUFFERS_DESCRIPTION_FILTERS};
+use crate::surface::target::CommandTarget;
+use crate::utils::SampleCountMap;
+use bytemuck::{Pod, Zeroable};
+use std::sync::OnceLock;
+use swf::ConvolutionFilter as ConvolutionFilterArgs;
+use wgpu::util::StagingBelt;
+
+/// Flash Player doesn't allow matrices with more than 15 rows or columns.
+const MAX_MATRIX_SIZE: usize = 15;
+
+/// The weights are packed four to an element, as arrays in uniforms need 16 byte strides.
+/// This is rounded up to a size that `bytemuck` supports.
+const WEIGHTS_LEN: usize = 64;
+const _: () = assert!(WEIGHTS_LEN * 4 >= MAX_MATRIX_SIZE * MAX_MATRIX_SIZE);
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable, PartialEq)]
+struct ConvolutionUniform {
+    weights: [[f32; 4]; WEIGHTS_LEN],
+    default_color: [f32; 4],
+    bias: f32,
+    divisor: f32,
+    matrix_x: u32,
+    matrix_y: u32,
+    source_min: [i32; 2],
+    source_max: [i32; 2],
+    clamp_edges: u32,    // a wasteful bool, but we need to be aligned anyway
+    preserve_alpha: u32, // a wasteful bool, but we need to be aligned anyway
+    _padding: [u32; 2],
+}
+
+pub struct ConvolutionFilter {
+    bind_group_layout: wgpu::BindGroupLayout,
+    pipeline_layout: wgpu::PipelineLayout,
+    vertex_buffer: wgpu::Buffer,
+    uniform_buffer: wgpu::Buffer,
+    vertices_size: wgpu::BufferSize,
+    uniform_size: wgpu::BufferSize,
+    pipelines: SampleCountMap<OnceLock<wgpu::RenderPipeline>>,
+}
+
+impl ConvolutionFilter {
+    pub fn new(device: &wgpu::Device) -> Self {
+        let uniform_size = std::mem::size_of::<ConvolutionUniform>() as u64;
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        multisampled: false,
+                        sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: wgpu::BufferSize::new(uniform_size),
+                    },
+                    count: None,
+                },
+            ],
+            label: create_debug_label!("Convolution filter binds").as_deref(),
+        });
+
+        let vertices_size = std::mem::size_of::<[FilterVertex; 4]>() as u64;
+        let vertex_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: vertices_size,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: None,
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: uniform_size,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        Self {
+            pipelines: Default::default(),
+            pipeline_layout,
+            vertex_buffer,
+            uniform_buffer,
+            bind_group_layout,
+            vertices_size: wgpu::BufferSize::new(vertices_size).expect("Definitely not zero."),
+            uniform_size: wgpu::BufferSize::new(uniform_size).expect("Definitely not zero."),
+        }
+    }
+
+    fn pipeline(&self, descriptors: &Descriptors, msaa_sample_count: u32) -> &wgpu::RenderPipeline {
+        self.pipelines.get_or_init(msaa_sample_count, || {
+            let label = create_debug_label!("Convolution Filter ({} msaa)", msaa_sample_count);
+            descriptors
+                .device
+                .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                    label: label.as_deref(),
+                    layout: Some(&self.pipeline_layout),
+                    vertex: wgpu::VertexState {
+                        module: &descriptors.shaders.convolution_filter,
+                        entry_point: "main_vertex",
+                        buffers: &VERTEX_BUFFERS_DESCRIPTION_FILTERS,
+                        compilation_options: Default::default(),
+                    },
+                    primitive: wgpu::PrimitiveState {
+                        topology: wgpu::PrimitiveTopology::TriangleList,
+                        strip_index_format: None,
+                        front_face: wgpu::FrontFace::Ccw,
+                        cull_mode: None,
+                        polygon_mode: wgpu::PolygonMode::default(),
+                        unclipped_depth: false,
+                        conservative: false,
+                    },
+                    depth_stencil: None,
+                    multisample: wgpu::MultisampleState {
+                        count: msaa_sample_count,
+                        mask: !0,
+                        alpha_to_coverage_enabled: false,
+                    },
+                    fragment: Some(wgpu::FragmentState {
+                        module: &descriptors.shaders.convolution_filter,
+                        entry_point: "main_fragment",
+                        targets: &[Some(wgpu::TextureFormat::Rgba8Unorm.into())],
+                        compilation_options: Default::default(),
+                    }),
+                    multiview: None,
+                    cache: None,
+                })
+        })
+    }
+
+    pub fn apply(
+        &self,
+        descriptors: &Descriptors,
+        texture_pool: &mut TexturePool,
+        draw_encoder: &mut wgpu::CommandEncoder,
+        staging_belt: &mut StagingBelt,
+        source: &FilterSource,
+        filter: &ConvolutionFilterArgs,
+    ) -> Option<CommandTarget> {
+        let matrix_x = usize::from(filter.num_matrix_cols);
+        let matrix_y = usize::from(filter.num_matrix_rows);
+        if matrix_x == 0
+            || matrix_y == 0
+            || matrix_x > MAX_MATRIX_SIZE
+            || matrix_y > MAX_MATRIX_SIZE
+            || filter.matrix.len() < matrix_x * matrix_y
+        {
+            // Flash Player ignores invalid matrices, leaving the source as it was.
+            return None;
+        }
+
+        let sample_count = source.texture.sample_count();
+        let format = source.texture.format();
+        let pipeline = self.pipeline(descriptors, sample_count);
+
+        let target = CommandTarget::new(
+            descriptors,
+            texture_pool,
+            wgpu::Extent3d {
+                width: source.size.0,
+                height: source.size.1,
+                depth_or_array_layers: 1,
+            },
+            format,
+            sample_count,
+            RenderTargetMode::FreshWithColor(wgpu::Color::TRANSPARENT),
+            draw_encoder,
+        );
+        let source_view = source.texture.create_view(&Default::default());
+
+        let mut weights = [[0.0; 4]; WEIGHTS_LEN];
+        for (i, weight) in filter.matrix[..matrix_x * matrix_y].iter().enumerate() {
+            weights[i / 4][i % 4] = *weight;
+        }
+        staging_belt
+            .write_buffer(
+                draw_encoder,
+                &self.uniform_buffer,
+                0,
+                self.uniform_size,
+                &descriptors.device,
+            )
+            .copy_from_slice(bytemuck::cast_slice(&[ConvolutionUniform {
+                weights,
+                default_color: [
+                    f32::from(filter.default_color.r) / 255.0,
+                    f32::from(filter.default_color.g) / 255.0,
+                    f32::from(filter.default_color.b) / 255.0,
+                    f32::from(filter.default_color.a) / 255.0,
+                ],
+                bias: filter.bias,
+                // A divisor of 0 is treated as 1 by Flash Player.
+                divisor: if filter.divisor == 0.0 {
+                    1.0
+                } else {
+                    filter.divisor
+                },
+                matrix_x: matrix_x as u32,
+                matrix_y: matrix_y as u32,
+                source_min: [source.point.0 as i32, source.point.1 as i32],
+                source_max: [
+                    (source.point.0 + source.size.0) as i32,
+                    (source.point.1 + source.size.1) as i32,
+                ],
+                clamp_edges: if filter.is_clamped() { 1 } else { 0 },
+                preserve_alpha: if filter.is_preserve_alpha() { 1 } else { 0 },
+                _padding: [0; 2],
+            }]));
+        staging_belt
+            .write_buffer(
+                draw_encoder,
+                &self.vertex_buffer,
+                0,
+                self.vertices_size,
+                &descriptors.device,
+            )
+            .copy_from_slice(bytemuck::cast_slice(&[source.vertices()]));
+        let filter_group = descriptors
+            .device
+            .create_bind_group(&wgpu::BindGroupDescriptor {
+                label: create_debug_label!("Filter group").as_deref(),
+                layout: &self.bind_group_layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::TextureView(&source_view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: self.uniform_buffer.as_entire_binding(),
+                    },
+                ],
+            });
+        let mut render_pass = draw_encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: create_debug_label!("Convolution filter").as_deref(),
+            color_attachments: &[target.color_attachments()],
+            ..Default::default()
+        });
+        render_pass.set_pipeline(pipeline);
+
+        render_pass.set_bind_group(0, &filter_group, &[]);
+
+        render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+        render_pass.set_index_buffer(
+            descriptors.quad.indices.slice(..),
+            wgpu::IndexFormat::Uint32,
+        );
+        render_pass.draw_indexed(0..6, 0, 0..1);
+        drop(render_pass);
+        Some(target)
+    }
+}
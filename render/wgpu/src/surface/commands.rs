@@ -13,6 +13,7 @@ use ruffle_render::bitmap::{BitmapHandle, PixelSnapping};
 use ruffle_render::commands::{CommandHandler, CommandList, RenderBlendMode};
 use ruffle_render::lines::{emulate_line, emulate_line_rect};
 use ruffle_render::matrix::Matrix;
+use ruffle_render::matrix3d::Matrix3D;
 use ruffle_render::pixel_bender::PixelBenderShaderHandle;
 use ruffle_render::quality::StageQuality;
 use ruffle_render::transform::Transform;
@@ -563,22 +564,12 @@ impl<'a> WgpuCommandHandler<'a> {
 
     fn add_to_current(
         &mut self,
-        matrix: Matrix,
+        world_matrix: [[f32; 4]; 4],
         color_transform: ColorTransform,
         command_builder: impl FnOnce(wgpu::DynamicOffset) -> DrawCommand,
     ) {
         let transform = Transforms {
-            world_matrix: [
-                [matrix.a, matrix.b, 0.0, 0.0],
-                [matrix.c, matrix.d, 0.0, 0.0],
-                [0.0, 0.0, 1.0, 0.0],
-                [
-                    matrix.tx.to_pixels() as f32,
-                    matrix.ty.to_pixels() as f32,
-                    0.0,
-                    1.0,
-                ],
-            ],
+            world_matrix,
             mult_color: color_transform.mult_rgba_normalized(),
             add_color: color_transform.add_rgba_normalized(),
         };
@@ -641,7 +632,7 @@ impl<'a> CommandHandler for WgpuCommandHandler<'a> {
             BlendType::Trivial(blend_mode) => {
                 let transform = Transform {
                     matrix: Matrix::scale(target.width() as f32, target.height() as f32),
-                    color_transform: Default::default(),
+                    ..Default::default()
                 };
                 let texture = target.take_color_texture();
                 let bind_group =
@@ -672,7 +663,7 @@ impl<'a> CommandHandler for WgpuCommandHandler<'a> {
                             label: None,
                         });
                 self.add_to_current(
-                    transform.matrix,
+                    world_matrix(transform.matrix),
                     transform.color_transform,
                     |transform_buffer| DrawCommand::RenderTexture {
                         _texture: texture,
@@ -717,48 +708,57 @@ impl<'a> CommandHandler for WgpuCommandHandler<'a> {
         smoothing: bool,
         pixel_snapping: PixelSnapping,
     ) {
-        let mut matrix = transform.matrix;
-        {
+        let world_matrix = {
             let texture = as_texture(&bitmap);
-            pixel_snapping.apply(&mut matrix);
-            matrix *= Matrix::scale(
-                texture.texture.width() as f32,
-                texture.texture.height() as f32,
-            );
-        }
-        self.add_to_current(matrix, transform.color_transform, |transform_buffer| {
-            DrawCommand::RenderBitmap {
+            bitmap_world_matrix(
+                &transform,
+                texture.texture.width(),
+                texture.texture.height(),
+                pixel_snapping,
+            )
+        };
+        self.add_to_current(
+            world_matrix,
+            transform.color_transform,
+            |transform_buffer| DrawCommand::RenderBitmap {
                 bitmap,
                 transform_buffer,
                 smoothing,
                 blend_mode: TrivialBlend::Normal,
                 render_stage3d: false,
-            }
-        });
+            },
+        );
     }
     fn render_stage3d(&mut self, bitmap: BitmapHandle, transform: Transform) {
-        let mut matrix = transform.matrix;
-        {
+        let world_matrix = {
             let texture = as_texture(&bitmap);
-            matrix *= Matrix::scale(
-                texture.texture.width() as f32,
-                texture.texture.height() as f32,
-            );
-        }
-        self.add_to_current(matrix, transform.color_transform, |transform_buffer| {
-            DrawCommand::RenderBitmap {
+            bitmap_world_matrix(
+                &transform,
+                texture.texture.width(),
+                texture.texture.height(),
+                PixelSnapping::Never,
+            )
+        };
+        self.add_to_current(
+            world_matrix,
+            transform.color_transform,
+            |transform_buffer| DrawCommand::RenderBitmap {
                 bitmap,
                 transform_buffer,
                 smoothing: false,
                 blend_mode: TrivialBlend::Normal,
                 render_stage3d: true,
-            }
-        });
+            },
+        );
     }
 
     fn render_shape(&mut self, shape: ShapeHandle, transform: Transform) {
+        let world_matrix = match transform.matrix3d {
+            Some(matrix3d) => matrix3d.to_world_matrix(),
+            None => world_matrix(transform.matrix),
+        };
         self.add_to_current(
-            transform.matrix,
+            world_matrix,
             transform.color_transform,
             |transform_buffer| DrawCommand::RenderShape {
                 shape,
@@ -769,7 +769,7 @@ impl<'a> CommandHandler for WgpuCommandHandler<'a> {
 
     fn draw_rect(&mut self, color: Color, matrix: Matrix) {
         self.add_to_current(
-            matrix,
+            world_matrix(matrix),
             ColorTransform::multiply_from(color),
             |transform_buffer| DrawCommand::DrawRect { transform_buffer },
         );
@@ -784,7 +784,7 @@ impl<'a> CommandHandler for WgpuCommandHandler<'a> {
             matrix.tx += Twips::HALF;
             matrix.ty += Twips::HALF;
             self.add_to_current(
-                matrix,
+                world_matrix(matrix),
                 ColorTransform::multiply_from(color),
                 |transform_buffer| DrawCommand::DrawLine { transform_buffer },
             );
@@ -800,7 +800,7 @@ impl<'a> CommandHandler for WgpuCommandHandler<'a> {
             matrix.tx += Twips::HALF;
             matrix.ty += Twips::HALF;
             self.add_to_current(
-                matrix,
+                world_matrix(matrix),
                 ColorTransform::multiply_from(color),
                 |transform_buffer| DrawCommand::DrawLineRect { transform_buffer },
             );
@@ -829,3 +829,35 @@ impl<'a> CommandHandler for WgpuCommandHandler<'a> {
         self.current.push(DrawCommand::PopMask);
     }
 }
+
+/// Converts a 2D transform into the 4x4 matrix used by the vertex shaders.
+fn world_matrix(matrix: Matrix) -> [[f32; 4]; 4] {
+    [
+        [matrix.a, matrix.b, 0.0, 0.0],
+        [matrix.c, matrix.d, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+        [
+            matrix.tx.to_pixels() as f32,
+            matrix.ty.to_pixels() as f32,
+            0.0,
+            1.0,
+        ],
+    ]
+}
+
+/// The world matrix of a bitmap, which is drawn as a unit quad scaled to its dimensions.
+fn bitmap_world_matrix(
+    transform: &Transform,
+    width: u32,
+    height: u32,
+    pixel_snapping: PixelSnapping,
+) -> [[f32; 4]; 4] {
+    if let Some(matrix3d) = transform.matrix3d {
+        // Snapping has no meaning once the bitmap is projected.
+        (matrix3d * Matrix3D::scale(width.into(), height.into(), 1.0)).to_world_matrix()
+    } else {
+        let mut matrix = transform.matrix;
+        pixel_snapping.apply(&mut matrix);
+        world_matrix(matrix * Matrix::scale(width as f32, height as f32))
+    }
+}
@@ -74,6 +74,41 @@ pub fn decompress_swf<'a, R: Read + 'a>(mut input: R) -> Result<SwfBuf> {
     let version = input.read_u8()?;
     let uncompressed_len = input.read_u32::<LittleEndian>()?;
 
+    validate_version(compression, version)?;
+
+    // Now the SWF switches to a compressed stream.
+    let mut decompress_stream: Box<dyn Read> = match compression {
+        Compression::None => Box::new(input),
+        Compression::Zlib => make_zlib_reader(input)?,
+        // Uncompressed length includes the 4-byte header and 4-byte uncompressed length itself,
+        // subtract it here.
+        Compression::Lzma => make_lzma_reader(input, uncompressed_len - 8)?,
+    };
+
+    // Decompress the entire SWF.
+    let mut data = Vec::with_capacity(uncompressed_len as usize);
+    if let Err(e) = decompress_stream.read_to_end(&mut data) {
+        log::error!("Error decompressing SWF: {}", e);
+    }
+
+    // Some SWF streams may not be compressed correctly,
+    // (e.g. incorrect data length in the stream), so decompressing
+    // may throw an error even though the data otherwise comes
+    // through the stream.
+    // We'll still try to parse what we get if the full decompression fails.
+    // (+ 8 for header size)
+    if data.len() as u64 + 8 != uncompressed_len as u64 {
+        log::warn!("SWF length doesn't match header, may be corrupt");
+    }
+
+    let (header, header_len) = read_header_ext(compression, version, uncompressed_len, &data)?;
+    data.drain(..header_len);
+
+    Ok(SwfBuf { header, data })
+}
+
+/// Checks the SWF version read from the file header against the compression format.
+fn validate_version(compression: Compression, version: u8) -> Result<()> {
     // Check whether the SWF version is 0.
     // Note that the behavior should actually vary, depending on the player version:
     // - Flash Player 9 and later bail out (the behavior we implement).
@@ -84,9 +119,8 @@ pub fn decompress_swf<'a, R: Read + 'a>(mut input: R) -> Result<SwfBuf> {
         return Err(Error::invalid_data("Invalid SWF version"));
     }
 
-    // Now the SWF switches to a compressed stream.
-    let mut decompress_stream: Box<dyn Read> = match compression {
-        Compression::None => Box::new(input),
+    match compression {
+        Compression::None => (),
         Compression::Zlib => {
             if version < 6 {
                 log::warn!(
@@ -94,7 +128,6 @@ pub fn decompress_swf<'a, R: Read + 'a>(mut input: R) -> Result<SwfBuf> {
                     version
                 );
             }
-            make_zlib_reader(input)?
         }
         Compression::Lzma => {
             if version < 13 {
@@ -103,29 +136,22 @@ pub fn decompress_swf<'a, R: Read + 'a>(mut input: R) -> Result<SwfBuf> {
                     version
                 );
             }
-            // Uncompressed length includes the 4-byte header and 4-byte uncompressed length itself,
-            // subtract it here.
-            make_lzma_reader(input, uncompressed_len - 8)?
         }
-    };
-
-    // Decompress the entire SWF.
-    let mut data = Vec::with_capacity(uncompressed_len as usize);
-    if let Err(e) = decompress_stream.read_to_end(&mut data) {
-        log::error!("Error decompressing SWF: {}", e);
-    }
-
-    // Some SWF streams may not be compressed correctly,
-    // (e.g. incorrect data length in the stream), so decompressing
-    // may throw an error even though the data otherwise comes
-    // through the stream.
-    // We'll still try to parse what we get if the full decompression fails.
-    // (+ 8 for header size)
-    if data.len() as u64 + 8 != uncompressed_len as u64 {
-        log::warn!("SWF length doesn't match header, may be corrupt");
     }
+    Ok(())
+}
 
-    let mut reader = Reader::new(&data, version);
+/// Parses the part of the SWF header stored at the start of the decompressed stream.
+///
+/// Returns the extended header, along with the length of the header data that
+/// precedes the tag stream.
+fn read_header_ext(
+    compression: Compression,
+    version: u8,
+    uncompressed_len: u32,
+    data: &[u8],
+) -> Result<(HeaderExt, usize)> {
+    let mut reader = Reader::new(data, version);
     let stage_size = reader.read_rectangle()?;
     let frame_rate = reader.read_fixed8()?;
     let num_frames = reader.read_u16()?;
@@ -136,7 +162,7 @@ pub fn decompress_swf<'a, R: Read + 'a>(mut input: R) -> Result<SwfBuf> {
         frame_rate,
         num_frames,
     };
-    let data = reader.get_ref().to_vec();
+    let header_len = data.len() - reader.get_ref().len();
 
     // Parse the first two tags, searching for the FileAttributes and SetBackgroundColor tags.
     // This metadata is useful, so we want to return it along with the header.
@@ -162,15 +188,306 @@ pub fn decompress_swf<'a, R: Read + 'a>(mut input: R) -> Result<SwfBuf> {
         tag = reader.read_tag();
     }
 
-    Ok(SwfBuf {
-        header: HeaderExt {
+    Ok((
+        HeaderExt {
             header,
             file_attributes,
             background_color,
             uncompressed_len: uncompressed_len as i32,
         },
-        data,
-    })
+        header_len,
+    ))
+}
+
+/// Returns whether `data` holds enough of the decompressed stream for `read_header_ext`
+/// to return the same result as it would with the entire stream.
+fn is_header_ext_available(version: u8, data: &[u8]) -> bool {
+    let mut reader = Reader::new(data, version);
+    if reader.read_rectangle().is_err()
+        || reader.read_fixed8().is_err()
+        || reader.read_u16().is_err()
+    {
+        return false;
+    }
+
+    // `read_header_ext` looks at no more than the first three tags.
+    for _ in 0..3 {
+        let Ok((_, tag_len)) = reader.read_tag_code_and_length() else {
+            return false;
+        };
+        let Some(rest) = reader.get_ref().get(tag_len..) else {
+            return false;
+        };
+        *reader.get_mut() = rest;
+    }
+    true
+}
+
+/// Decompresses an SWF incrementally, as its data arrives.
+///
+/// This is the streaming counterpart of `decompress_swf`, used to play a SWF
+/// while it is still being downloaded. Compressed data is pushed in with `write`,
+/// and the decompressed tag stream can be pulled out with `take_data` as soon as
+/// the extended header is available.
+///
+/// LZMA data is only decompressed in blocks of the LZMA dictionary size, so LZMA
+/// compressed SWFs will usually arrive in fewer, larger pieces than other SWFs.
+///
+/// # Example
+/// ```
+/// # std::env::set_current_dir(env!("CARGO_MANIFEST_DIR"));
+/// let data = std::fs::read("tests/swfs/DefineSprite.swf").unwrap();
+/// let mut decompressor = swf::read::SwfDecompressor::new();
+/// for chunk in data.chunks(16) {
+///     decompressor.write(chunk).unwrap();
+/// }
+/// decompressor.finish().unwrap();
+/// println!("FPS: {}", decompressor.header().unwrap().frame_rate());
+/// ```
+pub struct SwfDecompressor {
+    decoder: Decoder,
+
+    /// The extended header, once enough data has been decompressed to read it.
+    header: Option<HeaderExt>,
+
+    /// Decompressed data that hasn't been returned by `take_data` yet.
+    ///
+    /// Until the extended header is available, this also holds the header data.
+    data: Vec<u8>,
+
+    /// The compression type, version and uncompressed length from the file header.
+    file_header: Option<(Compression, u8, u32)>,
+
+    /// The length of the header data at the start of the decompressed stream.
+    header_len: usize,
+}
+
+enum Decoder {
+    /// Waiting for the rest of the 8-byte file header.
+    FileHeader(Vec<u8>),
+
+    Uncompressed,
+
+    #[cfg(feature = "flate2")]
+    Zlib(flate2::write::ZlibDecoder<Vec<u8>>),
+
+    #[cfg(feature = "lzma")]
+    Lzma {
+        /// The number of bytes of the (ignored) compressed length left to skip.
+        skip: usize,
+        stream: Box<lzma_rs::decompress::Stream<Vec<u8>>>,
+    },
+
+    /// The end of the stream was reached, or decompression failed.
+    Finished,
+}
+
+impl Default for SwfDecompressor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SwfDecompressor {
+    pub fn new() -> Self {
+        Self {
+            decoder: Decoder::FileHeader(Vec::with_capacity(8)),
+            header: None,
+            data: Vec::new(),
+            file_header: None,
+            header_len: 0,
+        }
+    }
+
+    /// The extended header of the SWF, if enough of the SWF has been decompressed to read it.
+    pub fn header(&self) -> Option<&HeaderExt> {
+        self.header.as_ref()
+    }
+
+    /// The length of the decompressed tag stream, according to the file header.
+    ///
+    /// This is available along with the extended header.
+    pub fn tag_stream_len(&self) -> Option<usize> {
+        let header = self.header.as_ref()?;
+        Some((header.uncompressed_len() as u32 as usize).saturating_sub(8 + self.header_len))
+    }
+
+    /// Decompresses the next chunk of the SWF.
+    ///
+    /// Returns an `Error` if this is not a valid SWF file. Errors in the compressed
+    /// stream itself are logged and end decompression, like in `decompress_swf`.
+    pub fn write(&mut self, mut input: &[u8]) -> Result<()> {
+        if let Decoder::FileHeader(file_header) = &mut self.decoder {
+            let len = input.len().min(8 - file_header.len());
+            file_header.extend_from_slice(&input[..len]);
+            input = &input[len..];
+            if file_header.len() < 8 {
+                return Ok(());
+            }
+
+            let mut file_header = &file_header[..];
+            let compression = read_compression_type(&mut file_header)?;
+            let version = file_header.read_u8()?;
+            let uncompressed_len = file_header.read_u32::<LittleEndian>()?;
+            validate_version(compression, version)?;
+            self.decoder = Decoder::new(compression, uncompressed_len)?;
+            self.file_header = Some((compression, version, uncompressed_len));
+        }
+
+        match &mut self.decoder {
+            Decoder::FileHeader(_) | Decoder::Finished => (),
+            Decoder::Uncompressed => self.data.extend_from_slice(input),
+            #[cfg(feature = "flate2")]
+            Decoder::Zlib(decoder) => {
+                use std::io::Write;
+                let mut result = Ok(());
+                while !input.is_empty() && result.is_ok() {
+                    match decoder.write(input) {
+                        // The zlib stream ended; ignore any trailing data.
+                        Ok(0) => break,
+                        Ok(len) => input = &input[len..],
+                        Err(e) => result = Err(e),
+                    }
+                }
+                let result = result.and_then(|_| decoder.flush());
+                self.data.append(decoder.get_mut());
+                if let Err(e) = result {
+                    log::error!("Error decompressing SWF: {}", e);
+                    self.decoder = Decoder::Finished;
+                }
+            }
+            #[cfg(feature = "lzma")]
+            Decoder::Lzma { skip, stream } => {
+                use std::io::Write;
+                let len = input.len().min(*skip);
+                *skip -= len;
+                input = &input[len..];
+                let mut ended = false;
+                while !input.is_empty() && !ended {
+                    match stream.write(input) {
+                        // The LZMA stream ended; ignore any trailing data.
+                        Ok(0) => ended = true,
+                        Ok(len) => input = &input[len..],
+                        Err(e) => {
+                            log::error!("Error decompressing SWF: {}", e);
+                            ended = true;
+                        }
+                    }
+                }
+                if ended {
+                    self.finish_decoder();
+                } else if let Some(output) = stream.get_output_mut() {
+                    self.data.append(output);
+                }
+            }
+        }
+
+        self.read_header_if_available();
+        Ok(())
+    }
+
+    /// Signals that all of the SWF has been written, decompressing any remaining data.
+    ///
+    /// Returns an `Error` if the SWF ended before its header could be read.
+    pub fn finish(&mut self) -> Result<()> {
+        if let Decoder::FileHeader(_) = self.decoder {
+            return Err(Error::invalid_data("Invalid SWF"));
+        }
+        self.finish_decoder();
+
+        if self.header.is_none() {
+            let Some((compression, version, uncompressed_len)) = self.file_header else {
+                return Err(Error::invalid_data("Invalid SWF"));
+            };
+            let (header, header_len) =
+                read_header_ext(compression, version, uncompressed_len, &self.data)?;
+            self.data.drain(..header_len);
+            self.header = Some(header);
+            self.header_len = header_len;
+        }
+        Ok(())
+    }
+
+    /// Returns the part of the tag stream decompressed since the last call.
+    ///
+    /// This is always empty until the extended header is available.
+    pub fn take_data(&mut self) -> Vec<u8> {
+        if self.header.is_some() {
+            std::mem::take(&mut self.data)
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Ends decompression, keeping any data still buffered by the decoder.
+    fn finish_decoder(&mut self) {
+        match std::mem::replace(&mut self.decoder, Decoder::Finished) {
+            Decoder::FileHeader(_) | Decoder::Uncompressed | Decoder::Finished => (),
+            #[cfg(feature = "flate2")]
+            Decoder::Zlib(decoder) => match decoder.finish() {
+                Ok(mut output) => self.data.append(&mut output),
+                Err(e) => log::error!("Error decompressing SWF: {}", e),
+            },
+            #[cfg(feature = "lzma")]
+            Decoder::Lzma { stream, .. } => match stream.finish() {
+                Ok(mut output) => self.data.append(&mut output),
+                Err(_) => log::error!("Unable to decompress LZMA SWF."),
+            },
+        }
+    }
+
+    fn read_header_if_available(&mut self) {
+        let Some((compression, version, uncompressed_len)) = self.file_header else {
+            return;
+        };
+        if self.header.is_some() || !is_header_ext_available(version, &self.data) {
+            return;
+        }
+        if let Ok((header, header_len)) =
+            read_header_ext(compression, version, uncompressed_len, &self.data)
+        {
+            self.data.drain(..header_len);
+            self.header = Some(header);
+            self.header_len = header_len;
+        }
+    }
+}
+
+impl Decoder {
+    #[cfg_attr(not(feature = "lzma"), allow(unused_variables))]
+    fn new(compression: Compression, uncompressed_len: u32) -> Result<Self> {
+        match compression {
+            Compression::None => Ok(Self::Uncompressed),
+            #[cfg(feature = "flate2")]
+            Compression::Zlib => Ok(Self::Zlib(flate2::write::ZlibDecoder::new(Vec::new()))),
+            #[cfg(not(feature = "flate2"))]
+            Compression::Zlib => Err(Error::unsupported(
+                "Support for Zlib compressed SWFs is not enabled.",
+            )),
+            #[cfg(feature = "lzma")]
+            Compression::Lzma => {
+                use lzma_rs::decompress::{Options, Stream, UnpackedSize};
+                // See `make_lzma_reader` for the layout of the LZMA SWF header.
+                // Uncompressed length includes the 4-byte header and 4-byte uncompressed length itself,
+                // subtract it here.
+                let options = Options {
+                    unpacked_size: UnpackedSize::UseProvided(Some(
+                        uncompressed_len.saturating_sub(8).into(),
+                    )),
+                    allow_incomplete: true,
+                    memlimit: None,
+                };
+                Ok(Self::Lzma {
+                    skip: 4,
+                    stream: Box::new(Stream::new_with_options(&options, Vec::new())),
+                })
+            }
+            #[cfg(not(feature = "lzma"))]
+            Compression::Lzma => Err(Error::unsupported(
+                "Support for LZMA compressed SWFs is not enabled.",
+            )),
+        }
+    }
 }
 
 #[cfg(feature = "flate2")]
@@ -2653,6 +2970,46 @@ pub mod tests {
         }
     }
 
+    #[test]
+    fn decompress_swf_in_chunks() {
+        let mut paths = vec![
+            "tests/swfs/uncompressed.swf",
+            "tests/swfs/zlib.swf",
+            "tests/swfs/DefineSprite.swf",
+        ];
+        if cfg!(feature = "lzma") {
+            paths.push("tests/swfs/lzma.swf");
+        }
+        for path in paths {
+            let data = std::fs::read(path).unwrap();
+            let expected = decompress_swf(&data[..]).unwrap();
+            for chunk_size in [1, 7, 4096] {
+                let mut decompressor = SwfDecompressor::new();
+                let mut tags = Vec::new();
+                for chunk in data.chunks(chunk_size) {
+                    decompressor.write(chunk).unwrap();
+                    tags.extend(decompressor.take_data());
+                }
+                decompressor.finish().unwrap();
+                tags.extend(decompressor.take_data());
+                let header = decompressor.header().unwrap();
+                assert_eq!(decompressor.tag_stream_len(), Some(tags.len()), "{path}");
+                assert_eq!(header.swf_header(), expected.header.swf_header(), "{path}");
+                assert_eq!(
+                    header.background_color(),
+                    expected.header.background_color(),
+                    "{path}"
+                );
+                assert_eq!(
+                    header.uncompressed_len(),
+                    expected.header.uncompressed_len(),
+                    "{path}"
+                );
+                assert_eq!(tags, expected.data, "{path}");
+            }
+        }
+    }
+
     #[test]
     fn read_invalid_swf() {
         let junk = [0u8; 128];
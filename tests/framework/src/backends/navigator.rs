@@ -1,6 +1,7 @@
 use crate::backends::TestLogBackend;
 use crate::util::read_bytes;
 use async_channel::{Receiver, Sender};
+use mockrtmp::Server as RtmpServer;
 use percent_encoding::percent_decode_str;
 use ruffle_core::backend::log::LogBackend;
 use ruffle_core::backend::navigator::{
@@ -13,16 +14,81 @@ use ruffle_core::socket::{ConnectionState, SocketAction, SocketHandle};
 use ruffle_core::swf::Encoding;
 use ruffle_socket_format::SocketEvent;
 use std::borrow::Cow;
+use std::sync::{Arc, Mutex};
+use std::task::{Poll, Waker};
 use std::time::Duration;
 use url::{ParseError, Url};
 use vfs::VfsPath;
 
+/// Counts the frames of a test, so that responses can be slowed down to
+/// arrive over several frames.
+#[derive(Clone, Default)]
+pub struct FrameClock(Arc<Mutex<FrameClockState>>);
+
+#[derive(Default)]
+struct FrameClockState {
+    frame: u64,
+    wakers: Vec<Waker>,
+}
+
+impl FrameClock {
+    /// Move on to the next frame, waking everything that waits for it.
+    pub fn advance(&self) {
+        let mut state = self.0.lock().unwrap();
+        state.frame += 1;
+        for waker in state.wakers.drain(..) {
+            waker.wake();
+        }
+    }
+
+    /// Wait until the next frame.
+    async fn next_frame(self) {
+        let frame = self.0.lock().unwrap().frame + 1;
+        std::future::poll_fn(|cx| {
+            let mut state = self.0.lock().unwrap();
+            if state.frame >= frame {
+                Poll::Ready(())
+            } else {
+                state.wakers.push(cx.waker().clone());
+                Poll::Pending
+            }
+        })
+        .await
+    }
+}
+
 struct TestResponse {
     url: String,
     body: Vec<u8>,
-    chunk_gotten: bool,
     status: u16,
     redirected: bool,
+
+    /// The size of the chunks the body is received in.
+    chunk_size: usize,
+
+    /// If set, each chunk of the body is only received on the next frame.
+    frame_clock: Option<FrameClock>,
+
+    /// How much of the body has been received so far.
+    position: usize,
+
+    /// Whether the connection fails after the whole body has been received.
+    fail_at_end: bool,
+}
+
+impl TestResponse {
+    fn new(url: String, body: Vec<u8>, status: u16) -> Self {
+        Self {
+            url,
+            chunk_size: body.len().max(1),
+            body,
+            status,
+            redirected: false,
+            frame_clock: None,
+            position: 0,
+            fail_at_end: false,
+        }
+    }
 }
 
 impl SuccessResponse for TestResponse {
@@ -31,6 +97,9 @@ impl SuccessResponse for TestResponse {
     }
 
     fn body(self: Box<Self>) -> OwnedFuture<Vec<u8>, Error> {
+        if self.fail_at_end {
+            return Box::pin(async move { Err(Error::FetchError("Connection reset".to_string())) });
+        }
         Box::pin(async move { Ok(self.body) })
     }
 
@@ -47,10 +116,19 @@ impl SuccessResponse for TestResponse {
     }
 
     fn next_chunk(&mut self) -> OwnedFuture<Option<Vec<u8>>, Error> {
-        if !self.chunk_gotten {
-            self.chunk_gotten = true;
-            let body = self.body.clone();
-            Box::pin(async move { Ok(Some(body)) })
+        if self.position < self.body.len() {
+            let end = (self.position + self.chunk_size).min(self.body.len());
+            let chunk = self.body[self.position..end].to_vec();
+            self.position = end;
+            let frame_clock = self.frame_clock.clone();
+            Box::pin(async move {
+                if let Some(frame_clock) = frame_clock {
+                    frame_clock.next_frame().await;
+                }
+                Ok(Some(chunk))
+            })
+        } else if self.fail_at_end {
+            Box::pin(async move { Err(Error::FetchError("Connection reset".to_string())) })
         } else {
             Box::pin(async move { Ok(None) })
         }
@@ -67,15 +145,23 @@ impl SuccessResponse for TestResponse {
 ///
 /// Attempting to fetch URLs containing the following "hints" will cause a simulated response:
 /// * "?debug-success" -> Simulates a successful fetch, with body "Hello, World!"
+/// * "?debug-chunked" -> Like "?debug-success", but the body arrives in chunks of 5 bytes
+/// * "?debug-error-midstream" -> Like "?debug-chunked", but the connection fails after the body
 /// * "?debug-error-statuscode" -> Simulates a failed fetch due to a unsuccessful status
 /// * "?debug-error-dns" -> Simulates a failed fetch due to a dns resolution error
+/// * "?debug-slow" -> Loads a local file in chunks of 1 KiB, one of which arrives each frame
 ///
 /// These are formatted as query params, rather than domains/whole URLs, so that real/real-invalid
 /// URLs can be used in Flash Player when writing tests
+///
+/// Socket connections are answered by replaying `socket_events` or, failing that, by
+/// `rtmp_server`.
 pub struct TestNavigatorBackend {
     spawner: NullSpawner,
     relative_base_path: VfsPath,
     socket_events: Option<Vec<SocketEvent>>,
+    rtmp_server: Option<RtmpServer>,
+    frame_clock: FrameClock,
     log: Option<TestLogBackend>,
 }
 
@@ -84,12 +170,16 @@ impl TestNavigatorBackend {
         path: VfsPath,
         executor: &NullExecutor,
         socket_events: Option<Vec<SocketEvent>>,
+        rtmp_server: Option<RtmpServer>,
+        frame_clock: FrameClock,
         log: Option<TestLogBackend>,
     ) -> Result<Self, std::io::Error> {
         Ok(Self {
             spawner: executor.spawner(),
             relative_base_path: path,
             socket_events,
+            rtmp_server,
+            frame_clock,
             log,
         })
     }
@@ -117,38 +207,6 @@ impl NavigatorBackend for TestNavigatorBackend {
     }
 
     fn fetch(&self, request: Request) -> OwnedFuture<Box<dyn SuccessResponse>, ErrorResponse> {
-        if request.url().contains("?debug-success") {
-            return Box::pin(async move {
-                let response: Box<dyn SuccessResponse> = Box::new(TestResponse {
-                    url: request.url().to_string(),
-                    body: b"Hello, World!".to_vec(),
-                    chunk_gotten: false,
-                    status: 200,
-                    redirected: false,
-                });
-
-                Ok(response)
-            });
-        }
-
-        if request.url().contains("?debug-error-statuscode") {
-            return Box::pin(async move {
-                Err(ErrorResponse {
-                    url: request.url().to_string(),
-                    error: Error::HttpNotOk(request.url().to_string(), 0, false, 0),
-                })
-            });
-        }
-
-        if request.url().contains("?debug-error-dns") {
-            return Box::pin(async move {
-                Err(ErrorResponse {
-                    url: request.url().to_string(),
-                    error: Error::InvalidDomain(request.url().to_string()),
-                })
-            });
-        }
-
         // Log request.
         if let Some(log) = &self.log {
             log.avm_trace("Navigator::fetch:");
@@ -169,18 +227,74 @@ impl NavigatorBackend for TestNavigatorBackend {
                 log.avm_trace(&format!("  Mime-Type: {}", mime_type));
                 if mime_type == "application/x-www-form-urlencoded" {
                     log.avm_trace(&format!("  Body: {}", String::from_utf8_lossy(body)));
+                } else if mime_type.starts_with("multipart/form-data") {
+                    // Show the line breaks of the parts, which are all CRLF.
+                    log.avm_trace(&format!(
+                        "  Body:\n{}",
+                        String::from_utf8_lossy(body)
+                            .replace("\r\n", "\\r\\n\n")
+                            .trim_end()
+                    ));
                 } else {
                     log.avm_trace(&format!("  Body: {:02X?}", body));
                 }
             }
         }
 
+        if request.url().contains("?debug-success") {
+            return Box::pin(async move {
+                let response: Box<dyn SuccessResponse> = Box::new(TestResponse::new(
+                    request.url().to_string(),
+                    b"Hello, World!".to_vec(),
+                    200,
+                ));
+
+                Ok(response)
+            });
+        }
+
+        if request.url().contains("?debug-chunked")
+            || request.url().contains("?debug-error-midstream")
+        {
+            return Box::pin(async move {
+                let mut response =
+                    TestResponse::new(request.url().to_string(), b"Hello, World!".to_vec(), 200);
+                response.chunk_size = 5;
+                response.fail_at_end = request.url().contains("?debug-error-midstream");
+
+                let response: Box<dyn SuccessResponse> = Box::new(response);
+                Ok(response)
+            });
+        }
+
+        if request.url().contains("?debug-error-statuscode") {
+            return Box::pin(async move {
+                Err(ErrorResponse {
+                    url: request.url().to_string(),
+                    error: Error::HttpNotOk(request.url().to_string(), 0, false, 0),
+                })
+            });
+        }
+
+        if request.url().contains("?debug-error-dns") {
+            return Box::pin(async move {
+                Err(ErrorResponse {
+                    url: request.url().to_string(),
+                    error: Error::InvalidDomain(request.url().to_string()),
+                })
+            });
+        }
+
         let url = match self.resolve_url(request.url()) {
             Ok(url) => url,
             Err(e) => return async_return(create_fetch_error(request.url(), e)),
         };
 
         let base_path = self.relative_base_path.clone();
+        let frame_clock = request
+            .url()
+            .contains("?debug-slow")
+            .then(|| self.frame_clock.clone());
 
         Box::pin(async move {
             let path = if url.scheme() == "file" {
@@ -233,13 +347,13 @@ impl NavigatorBackend for TestNavigatorBackend {
                 error: Error::FetchError(error.to_string()),
             })?;
 
-            let response: Box<dyn SuccessResponse> = Box::new(TestResponse {
-                url: url.to_string(),
-                body,
-                chunk_gotten: false,
-                status: 0,
-                redirected: false,
-            });
+            let mut response = TestResponse::new(url.to_string(), body, 0);
+            if let Some(frame_clock) = frame_clock {
+                response.chunk_size = 1024;
+                response.frame_clock = Some(frame_clock);
+            }
+
+            let response: Box<dyn SuccessResponse> = Box::new(response);
 
             Ok(response)
         })
@@ -318,6 +432,34 @@ impl NavigatorBackend for TestNavigatorBackend {
                     }
                 }
 
+                Ok(())
+            }));
+        } else if let Some(mut server) = self.rtmp_server.clone() {
+            let log = self.log.clone();
+            self.spawn_future(Box::pin(async move {
+                sender
+                    .try_send(SocketAction::Connect(handle, ConnectionState::Connected))
+                    .expect("working channel send");
+
+                // Serve the client until it disconnects.
+                while let Ok(data) = receiver.recv().await {
+                    let response = server
+                        .receive(&data)
+                        .unwrap_or_else(|e| panic!("Invalid RTMP data from client: {e}"));
+
+                    for line in server.take_log() {
+                        if let Some(log) = &log {
+                            log.avm_trace(&format!("MockRtmp: {line}"));
+                        }
+                    }
+
+                    if !response.is_empty() {
+                        sender
+                            .try_send(SocketAction::Data(handle, response))
+                            .expect("working channel send");
+                    }
+                }
+
                 Ok(())
             }));
         }
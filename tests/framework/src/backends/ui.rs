@@ -1,8 +1,10 @@
+use crate::backends::TestLogBackend;
 use crate::test::Font;
 use chrono::{DateTime, Utc};
+use ruffle_core::backend::log::LogBackend;
 use ruffle_core::backend::ui::{
-    DialogLoaderError, DialogResultFuture, FileDialogResult, FileFilter, FontDefinition,
-    FullscreenError, LanguageIdentifier, MouseCursor, UiBackend, US_ENGLISH,
+    CustomCursor, DialogLoaderError, DialogResultFuture, FileDialogResult, FileFilter,
+    FontDefinition, FullscreenError, LanguageIdentifier, MouseCursor, UiBackend, US_ENGLISH,
 };
 use url::Url;
 
@@ -76,16 +78,19 @@ impl FileDialogResult for TestFileDialogResult {
 /// * Attempting to display a file save dialog with a file name hint of "debug-success.txt" will simulate successfully selecting a destination
 ///   otherwise a user cancellation will be simulated
 /// * Simulated in-memory clipboard
+/// * Custom mouse cursors are supported, and cursor changes can be logged
 pub struct TestUiBackend {
     fonts: Vec<Font>,
     clipboard: String,
+    cursor_log: Option<TestLogBackend>,
 }
 
 impl TestUiBackend {
-    pub fn new(fonts: Vec<Font>) -> Self {
+    pub fn new(fonts: Vec<Font>, cursor_log: Option<TestLogBackend>) -> Self {
         Self {
             fonts,
             clipboard: "".to_string(),
+            cursor_log,
         }
     }
 }
@@ -97,7 +102,35 @@ impl UiBackend for TestUiBackend {
 
     fn set_mouse_visible(&mut self, _visible: bool) {}
 
-    fn set_mouse_cursor(&mut self, _cursor: MouseCursor) {}
+    fn set_mouse_cursor(&mut self, cursor: MouseCursor) {
+        if let Some(log) = &self.cursor_log {
+            log.avm_trace(&format!("UiBackend::set_mouse_cursor: {cursor:?}"));
+        }
+    }
+
+    fn supports_custom_mouse_cursor(&self) -> bool {
+        true
+    }
+
+    fn set_custom_mouse_cursor(&mut self, cursor: Option<CustomCursor>) {
+        let Some(log) = &self.cursor_log else {
+            return;
+        };
+        let Some(cursor) = cursor else {
+            log.avm_trace("UiBackend::set_custom_mouse_cursor: None");
+            return;
+        };
+        log.avm_trace("UiBackend::set_custom_mouse_cursor:");
+        log.avm_trace(&format!("  Hotspot: {:?}", cursor.hotspot));
+        log.avm_trace(&format!("  Frame rate: {}", cursor.frame_rate));
+        for frame in &cursor.frames {
+            let first_pixel = frame.rgba.get(..4).unwrap_or_default();
+            log.avm_trace(&format!(
+                "  Frame: {}x{}, first pixel {first_pixel:?}",
+                frame.width, frame.height
+            ));
+        }
+    }
 
     fn clipboard_content(&mut self) -> String {
         self.clipboard.clone()
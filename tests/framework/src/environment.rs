@@ -43,4 +43,12 @@ pub trait RenderInterface {
     ///
     /// The provided backend is guaranteed to be the same one paired with this interface.
     fn capture(&self, renderer: &mut Box<dyn RenderBackend>) -> image::RgbaImage;
+
+    /// Gets the name of this renderer, if it isn't the one that the expected images of tests are
+    /// made with.
+    ///
+    /// Tests may provide their own expected images and tolerances for such renderers.
+    fn alternative_renderer(&self) -> Option<&'static str> {
+        None
+    }
 }
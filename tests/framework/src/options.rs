@@ -29,8 +29,11 @@ pub struct TestOptions {
     pub approximations: Option<Approximations>,
     pub player_options: PlayerOptions,
     pub log_fetch: bool,
+    pub log_cursor: bool,
     pub required_features: RequiredFeatures,
     pub fonts: HashMap<String, FontOptions>,
+    pub capture: CaptureOptions,
+    pub rtmp: Option<RtmpOptions>,
 }
 
 impl Default for TestOptions {
@@ -47,8 +50,11 @@ impl Default for TestOptions {
             approximations: None,
             player_options: PlayerOptions::default(),
             log_fetch: false,
+            log_cursor: false,
             required_features: RequiredFeatures::default(),
             fonts: Default::default(),
+            capture: CaptureOptions::default(),
+            rtmp: None,
         }
     }
 }
@@ -237,6 +243,15 @@ pub struct ImageComparison {
     tolerance: u8,
     max_outliers: usize,
     pub trigger: ImageTrigger,
+    renderers: HashMap<String, RendererTolerance>,
+}
+
+/// The tolerances of an image comparison when using an alternative renderer.
+#[derive(Deserialize, Default, Clone, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct RendererTolerance {
+    tolerance: Option<u8>,
+    max_outliers: Option<usize>,
 }
 
 fn calc_difference(lhs: u8, rhs: u8) -> u8 {
@@ -244,6 +259,15 @@ fn calc_difference(lhs: u8, rhs: u8) -> u8 {
 }
 
 impl ImageComparison {
+    /// Applies the tolerances this comparison has for the given alternative renderer, if any.
+    pub fn for_renderer(mut self, renderer: Option<&str>) -> Self {
+        if let Some(overrides) = renderer.and_then(|renderer| self.renderers.get(renderer)) {
+            self.tolerance = overrides.tolerance.unwrap_or(self.tolerance);
+            self.max_outliers = overrides.max_outliers.unwrap_or(self.max_outliers);
+        }
+        self
+    }
+
     pub fn test(
         &self,
         name: &str,
@@ -407,3 +431,22 @@ pub struct FontOptions {
     pub bold: bool,
     pub italic: bool,
 }
+
+/// The capture devices available to the test, backed by files in the test directory.
+#[derive(Deserialize, Default, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct CaptureOptions {
+    /// A WAV file for each microphone, which is played in a loop.
+    pub microphones: Vec<String>,
+
+    /// A sequence of images for each camera, which is played in a loop.
+    pub cameras: Vec<Vec<String>>,
+}
+
+/// A mock RTMP server that answers the test's socket connections.
+#[derive(Deserialize, Default, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct RtmpOptions {
+    /// An FLV file whose tags are streamed to clients that play a stream.
+    pub flv: Option<String>,
+}
@@ -1,4 +1,4 @@
-use crate::backends::{TestLogBackend, TestNavigatorBackend, TestUiBackend};
+use crate::backends::{FrameClock, TestLogBackend, TestNavigatorBackend, TestUiBackend};
 use crate::environment::RenderInterface;
 use crate::fs_commands::{FsCommand, TestFsCommandProvider};
 use crate::image_trigger::ImageTrigger;
@@ -39,6 +39,7 @@ pub struct TestRunner {
     player: Arc<Mutex<Player>>,
     injector: InputInjector,
     executor: NullExecutor,
+    frame_clock: FrameClock,
     frame_time: f64,
     frame_time_duration: Duration,
     log: TestLogBackend,
@@ -75,10 +76,13 @@ impl TestRunner {
 
         let log = TestLogBackend::default();
         let (fs_command_provider, fs_commands) = TestFsCommandProvider::new();
+        let frame_clock = FrameClock::default();
         let navigator = TestNavigatorBackend::new(
             test.root_path.clone(),
             &executor,
             socket_events,
+            test.rtmp_server()?,
+            frame_clock.clone(),
             test.options.log_fetch.then(|| log.clone()),
         )?;
 
@@ -87,7 +91,11 @@ impl TestRunner {
             .with_navigator(navigator)
             .with_max_execution_duration(Duration::from_secs(300))
             .with_fs_commands(Box::new(fs_command_provider))
-            .with_ui(TestUiBackend::new(test.fonts()?))
+            .with_ui(TestUiBackend::new(
+                test.fonts()?,
+                test.options.log_cursor.then(|| log.clone()),
+            ))
+            .with_capture(test.capture()?)
             .with_viewport_dimensions(
                 viewport_dimensions.width,
                 viewport_dimensions.height,
@@ -125,6 +133,7 @@ impl TestRunner {
             injector,
             render_interface,
             executor,
+            frame_clock,
             frame_time,
             frame_time_duration,
             log,
@@ -166,6 +175,7 @@ impl TestRunner {
         }
         self.remaining_iterations -= 1;
         self.current_iteration += 1;
+        self.frame_clock.advance();
         self.executor.run();
     }
 
@@ -497,13 +507,23 @@ fn capture_and_compare_image(
 
         let actual_image = render_interface.capture(player_lock.renderer_mut());
 
-        let expected_image_path = base_path.join(format!("{name}.expected.png"))?;
+        // Alternative renderers may have their own expected image, but fall back to the
+        // common one if they don't.
+        let renderer = render_interface.alternative_renderer();
+        let renderer_image_path = renderer
+            .map(|renderer| base_path.join(format!("{name}.expected-{renderer}.png")))
+            .transpose()?;
+        let common_image_path = base_path.join(format!("{name}.expected.png"))?;
+        let expected_image_path = match &renderer_image_path {
+            Some(path) if path.is_file()? => path,
+            _ => &common_image_path,
+        };
         if expected_image_path.is_file()? {
-            let expected_image = image::load_from_memory(&read_bytes(&expected_image_path)?)
+            let expected_image = image::load_from_memory(&read_bytes(expected_image_path)?)
                 .context("Failed to open expected image")?
                 .into_rgba8();
 
-            image_comparison.test(
+            image_comparison.for_renderer(renderer).test(
                 name,
                 actual_image,
                 expected_image,
@@ -517,7 +537,8 @@ fn capture_and_compare_image(
             ));
         } else {
             // If we're expecting this to be wrong, don't save a likely wrong image
-            write_image(&expected_image_path, &actual_image, ImageFormat::Png)?;
+            let new_image_path = renderer_image_path.as_ref().unwrap_or(&common_image_path);
+            write_image(new_image_path, &actual_image, ImageFormat::Png)?;
         }
     } else if known_failure {
         // It's possible that the trace output matched but the image might not.
@@ -3,6 +3,8 @@ use crate::options::TestOptions;
 use crate::runner::TestRunner;
 use crate::util::read_bytes;
 use anyhow::{anyhow, Result};
+use mockrtmp::Server as RtmpServer;
+use ruffle_core::backend::capture::FileCaptureBackend;
 use ruffle_core::tag_utils::SwfMovie;
 use ruffle_input_format::InputInjector;
 use ruffle_socket_format::SocketEvent;
@@ -104,6 +106,33 @@ impl Test {
             .collect()
     }
 
+    pub fn capture(&self) -> Result<FileCaptureBackend> {
+        let mut capture = FileCaptureBackend::new();
+        for path in &self.options.capture.microphones {
+            let wav = read_bytes(&self.root_path.join(path)?)?;
+            capture.add_microphone(path.to_owned(), &wav)?;
+        }
+        for paths in &self.options.capture.cameras {
+            let images = paths
+                .iter()
+                .map(|path| Ok(read_bytes(&self.root_path.join(path)?)?))
+                .collect::<Result<Vec<_>>>()?;
+            let name = paths.first().cloned().unwrap_or_default();
+            capture.add_camera(name, &images)?;
+        }
+        Ok(capture)
+    }
+
+    pub fn rtmp_server(&self) -> Result<Option<RtmpServer>> {
+        let Some(rtmp) = &self.options.rtmp else {
+            return Ok(None);
+        };
+        Ok(Some(match &rtmp.flv {
+            Some(path) => RtmpServer::with_flv(&read_bytes(&self.root_path.join(path)?)?)?,
+            None => RtmpServer::new(),
+        }))
+    }
+
     pub fn should_run(&self, check_renderer: bool, environment: &impl Environment) -> bool {
         if self.options.ignore {
             return false;
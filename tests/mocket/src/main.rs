@@ -19,6 +19,10 @@ struct Opt {
     /// Path to a `socket.json` file.
     #[clap(name = "FILE")]
     file_path: PathBuf,
+
+    /// Path to a socket policy file to serve, instead of one allowing any connection.
+    #[clap(long)]
+    policy: Option<PathBuf>,
 }
 
 fn main() -> Result<(), Error> {
@@ -35,6 +39,17 @@ fn main() -> Result<(), Error> {
     let _ = tracing::subscriber::set_global_default(subscriber);
 
     let events = SocketEvent::from_file(opt.file_path)?;
+    let policy = match opt.policy {
+        Some(path) => {
+            let mut policy = std::fs::read(path)?;
+            // Socket policy files are terminated by a null byte.
+            if policy.last() != Some(&0) {
+                policy.push(0);
+            }
+            policy
+        }
+        None => POLICY.to_vec(),
+    };
     let event_count = events.len();
 
     let listener = TcpListener::bind("0.0.0.0:8001")?;
@@ -45,7 +60,7 @@ fn main() -> Result<(), Error> {
     // Handle socket policy stuff. (Required as Flash Player wont want to connect otherwise.)
     let mut buffer = [0; 4096];
     let _ = stream.read(&mut buffer);
-    stream.write_all(POLICY)?;
+    stream.write_all(&policy)?;
     tracing::info!("Policy sent successfully!");
 
     // Now we listen again as flash reopens socket connection.
@@ -0,0 +1,701 @@
+//! A tiny RTMP server, good enough to test Ruffle's RTMP client against.
+//!
+//! [`Server`] doesn't do any I/O itself: it's fed whatever the client sent and
+//! returns what should be sent back. This lets the same server run behind a
+//! real socket (see the `mockrtmp` binary) and inside the test framework.
+//!
+//! It answers `connect`, `createStream`, `play` and `publish`, and echoes the
+//! first argument of any other call back as its result. When FLV data is
+//! given, its tags are streamed to whoever plays a stream.
+
+use anyhow::{anyhow, Error};
+use std::collections::HashMap;
+use std::fmt;
+
+const HANDSHAKE_SIZE: usize = 1536;
+const CHUNK_SIZE: usize = 128;
+
+const SET_CHUNK_SIZE: u8 = 1;
+const WINDOW_ACK_SIZE: u8 = 5;
+const SET_PEER_BANDWIDTH: u8 = 6;
+const AUDIO: u8 = 8;
+const VIDEO: u8 = 9;
+const COMMAND_AMF3: u8 = 17;
+const DATA_AMF0: u8 = 18;
+const COMMAND_AMF0: u8 = 20;
+
+/// The only stream ID this server ever hands out.
+const STREAM_ID: u32 = 1;
+
+/// The subset of AMF0 values that RTMP commands use.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Amf {
+    Number(f64),
+    Bool(bool),
+    String(String),
+    Object(Vec<(String, Amf)>),
+    Null,
+    Undefined,
+    Array(Vec<Amf>),
+}
+
+impl Amf {
+    fn object(properties: &[(&str, Amf)]) -> Self {
+        Amf::Object(
+            properties
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.clone()))
+                .collect(),
+        )
+    }
+
+    fn string(value: &str) -> Self {
+        Amf::String(value.to_string())
+    }
+
+    fn get(&self, name: &str) -> Option<&Amf> {
+        match self {
+            Amf::Object(properties) => properties
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    fn write(&self, out: &mut Vec<u8>) {
+        match self {
+            Amf::Number(value) => {
+                out.push(0);
+                out.extend_from_slice(&value.to_be_bytes());
+            }
+            Amf::Bool(value) => {
+                out.push(1);
+                out.push(*value as u8);
+            }
+            Amf::String(value) => {
+                out.push(2);
+                write_utf8(out, value);
+            }
+            Amf::Object(properties) => {
+                out.push(3);
+                for (name, value) in properties {
+                    write_utf8(out, name);
+                    value.write(out);
+                }
+                out.extend_from_slice(&[0, 0, 9]);
+            }
+            Amf::Null => out.push(5),
+            Amf::Undefined => out.push(6),
+            Amf::Array(values) => {
+                out.push(10);
+                out.extend_from_slice(&(values.len() as u32).to_be_bytes());
+                for value in values {
+                    value.write(out);
+                }
+            }
+        }
+    }
+
+    /// Write this value as AMF3, wrapped in the AMF0 "AVM+" switch marker.
+    ///
+    /// Only the scalar values that calls get echoed with are supported;
+    /// anything else is written as AMF0.
+    fn write_amf3(&self, out: &mut Vec<u8>) {
+        match self {
+            Amf::Undefined => out.extend_from_slice(&[0x11, 0x00]),
+            Amf::Null => out.extend_from_slice(&[0x11, 0x01]),
+            Amf::Bool(false) => out.extend_from_slice(&[0x11, 0x02]),
+            Amf::Bool(true) => out.extend_from_slice(&[0x11, 0x03]),
+            Amf::Number(value) => {
+                out.extend_from_slice(&[0x11, 0x05]);
+                out.extend_from_slice(&value.to_be_bytes());
+            }
+            Amf::String(value) if value.len() < 1 << 13 => {
+                out.extend_from_slice(&[0x11, 0x06]);
+                write_u29(out, (value.len() as u32) << 1 | 1);
+                out.extend_from_slice(value.as_bytes());
+            }
+            _ => self.write(out),
+        }
+    }
+
+    fn read(data: &mut &[u8]) -> Result<Self, Error> {
+        let marker = take(data, 1)?[0];
+        Ok(match marker {
+            0 => Amf::Number(f64::from_be_bytes(take(data, 8)?.try_into()?)),
+            1 => Amf::Bool(take(data, 1)?[0] != 0),
+            2 => Amf::String(read_utf8(data)?),
+            3 => Amf::Object(read_properties(data)?),
+            5 => Amf::Null,
+            6 => Amf::Undefined,
+            8 => {
+                take(data, 4)?;
+                Amf::Object(read_properties(data)?)
+            }
+            10 => {
+                let length = u32::from_be_bytes(take(data, 4)?.try_into()?);
+                let mut values = Vec::new();
+                for _ in 0..length {
+                    values.push(Amf::read(data)?);
+                }
+                Amf::Array(values)
+            }
+            _ => return Err(anyhow!("Unsupported AMF0 marker {marker}")),
+        })
+    }
+}
+
+impl fmt::Display for Amf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Amf::Number(value) => write!(f, "{value}"),
+            Amf::Bool(value) => write!(f, "{value}"),
+            Amf::String(value) => write!(f, "{value:?}"),
+            Amf::Object(properties) => {
+                write!(f, "{{")?;
+                for (index, (name, value)) in properties.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{name}: {value}")?;
+                }
+                write!(f, "}}")
+            }
+            Amf::Null => write!(f, "null"),
+            Amf::Undefined => write!(f, "undefined"),
+            Amf::Array(values) => {
+                write!(f, "[")?;
+                for (index, value) in values.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{value}")?;
+                }
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+fn take<'a>(data: &mut &'a [u8], length: usize) -> Result<&'a [u8], Error> {
+    if data.len() < length {
+        return Err(anyhow!("Unexpected end of AMF0 data"));
+    }
+    let (head, tail) = data.split_at(length);
+    *data = tail;
+    Ok(head)
+}
+
+fn write_utf8(out: &mut Vec<u8>, value: &str) {
+    out.extend_from_slice(&(value.len() as u16).to_be_bytes());
+    out.extend_from_slice(value.as_bytes());
+}
+
+/// Write an AMF3 variable length integer, for values below 2^14.
+fn write_u29(out: &mut Vec<u8>, value: u32) {
+    if value >= 0x80 {
+        out.push((value >> 7) as u8 | 0x80);
+    }
+    out.push(value as u8 & 0x7F);
+}
+
+fn read_utf8(data: &mut &[u8]) -> Result<String, Error> {
+    let length = u16::from_be_bytes(take(data, 2)?.try_into()?) as usize;
+    Ok(String::from_utf8_lossy(take(data, length)?).into_owned())
+}
+
+fn read_properties(data: &mut &[u8]) -> Result<Vec<(String, Amf)>, Error> {
+    let mut properties = Vec::new();
+    loop {
+        let name = read_utf8(data)?;
+        if name.is_empty() && data.first() == Some(&9) {
+            take(data, 1)?;
+            return Ok(properties);
+        }
+        properties.push((name, Amf::read(data)?));
+    }
+}
+
+/// A tag of an FLV file that gets streamed to players.
+#[derive(Clone)]
+struct FlvTag {
+    tag_type: u8,
+    timestamp: u32,
+    payload: Vec<u8>,
+}
+
+/// Split an FLV file into its tags.
+fn read_flv_tags(data: &[u8]) -> Result<Vec<FlvTag>, Error> {
+    if data.len() < 9 || &data[0..3] != b"FLV" {
+        return Err(anyhow!("Not an FLV file"));
+    }
+
+    let mut tags = vec![];
+    let mut position = u32::from_be_bytes(data[5..9].try_into()?) as usize + 4;
+    while position + 11 <= data.len() {
+        let header = &data[position..position + 11];
+        let length = u32::from_be_bytes([0, header[1], header[2], header[3]]) as usize;
+        let timestamp = u32::from_be_bytes([header[7], header[4], header[5], header[6]]);
+        let Some(payload) = data.get(position + 11..position + 11 + length) else {
+            break;
+        };
+
+        tags.push(FlvTag {
+            tag_type: header[0],
+            timestamp,
+            payload: payload.to_vec(),
+        });
+        position += 11 + length + 4;
+    }
+
+    Ok(tags)
+}
+
+/// A message reassembled from incoming chunks.
+struct Message {
+    message_type: u8,
+    stream_id: u32,
+    payload: Vec<u8>,
+}
+
+/// The last header seen on a chunk stream, for decoding compressed headers.
+#[derive(Clone, Default)]
+struct ChunkHeader {
+    length: usize,
+    message_type: u8,
+    stream_id: u32,
+    payload: Vec<u8>,
+}
+
+/// Reads from the start of the buffered input, failing if it runs out.
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl Reader<'_> {
+    fn bytes(&mut self, length: usize) -> Option<&[u8]> {
+        let (head, tail) = self.data.split_at_checked(length)?;
+        self.data = tail;
+        Some(head)
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        self.bytes(1).map(|bytes| bytes[0])
+    }
+
+    fn uint(&mut self, length: usize) -> Option<u32> {
+        let mut value = 0;
+        for &byte in self.bytes(length)? {
+            value = (value << 8) | byte as u32;
+        }
+        Some(value)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum State {
+    /// Waiting for C0 and C1.
+    Handshake,
+    /// Waiting for C2.
+    HandshakeAck,
+    Connected,
+}
+
+/// A scripted RTMP server for a single client.
+#[derive(Clone)]
+pub struct Server {
+    tags: Vec<FlvTag>,
+    state: State,
+    input: Vec<u8>,
+    chunk_size: usize,
+    headers: HashMap<u32, ChunkHeader>,
+    object_encoding: f64,
+    log: Vec<String>,
+}
+
+impl Server {
+    /// Create a server that streams nothing when a client plays a stream.
+    pub fn new() -> Self {
+        Self {
+            tags: vec![],
+            state: State::Handshake,
+            input: vec![],
+            chunk_size: CHUNK_SIZE,
+            headers: HashMap::new(),
+            object_encoding: 0.0,
+            log: vec![],
+        }
+    }
+
+    /// Create a server that streams the tags of an FLV file when a client
+    /// plays a stream.
+    pub fn with_flv(flv: &[u8]) -> Result<Self, Error> {
+        Ok(Self {
+            tags: read_flv_tags(flv)?,
+            ..Self::new()
+        })
+    }
+
+    /// Take the descriptions of the commands received since the last call.
+    pub fn take_log(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.log)
+    }
+
+    /// Process data sent by the client, returning the data to send back.
+    pub fn receive(&mut self, data: &[u8]) -> Result<Vec<u8>, Error> {
+        self.input.extend_from_slice(data);
+        let mut out = vec![];
+
+        if self.state == State::Handshake {
+            if self.input.len() < 1 + HANDSHAKE_SIZE {
+                return Ok(out);
+            }
+            if self.input[0] != 3 {
+                return Err(anyhow!("Unsupported RTMP version {}", self.input[0]));
+            }
+
+            // S0 and S1, which is all zeroes as we don't care about
+            // timestamps, and S2, echoing C1.
+            out.push(3);
+            out.extend_from_slice(&[0; HANDSHAKE_SIZE]);
+            out.extend_from_slice(&self.input[1..1 + HANDSHAKE_SIZE]);
+            self.input.drain(..1 + HANDSHAKE_SIZE);
+            self.state = State::HandshakeAck;
+        }
+
+        if self.state == State::HandshakeAck {
+            if self.input.len() < HANDSHAKE_SIZE {
+                return Ok(out);
+            }
+            self.input.drain(..HANDSHAKE_SIZE);
+            self.state = State::Connected;
+        }
+
+        while let Some(message) = self.read_message() {
+            self.handle_message(message, &mut out)?;
+        }
+
+        Ok(out)
+    }
+
+    /// Reassemble the next complete message from the buffered input, leaving
+    /// any incomplete chunk to be read once the rest of it arrives.
+    fn read_message(&mut self) -> Option<Message> {
+        loop {
+            let mut reader = Reader { data: &self.input };
+
+            let first = reader.u8()?;
+            let format = first >> 6;
+            let chunk_stream_id = match first & 0x3F {
+                0 => reader.u8()? as u32 + 64,
+                1 => reader.u8()? as u32 + reader.u8()? as u32 * 256 + 64,
+                id => id as u32,
+            };
+
+            let mut header = self
+                .headers
+                .get(&chunk_stream_id)
+                .cloned()
+                .unwrap_or_default();
+            if format < 3 {
+                let timestamp = reader.uint(3)?;
+                if format < 2 {
+                    header.length = reader.uint(3)? as usize;
+                    header.message_type = reader.u8()?;
+                    if format == 0 {
+                        header.stream_id = reader.uint(4)?.swap_bytes();
+                    }
+                }
+                if timestamp == 0xFFFFFF {
+                    reader.uint(4)?;
+                }
+            }
+
+            let remaining = header.length.saturating_sub(header.payload.len());
+            header
+                .payload
+                .extend_from_slice(reader.bytes(remaining.min(self.chunk_size))?);
+
+            let consumed = self.input.len() - reader.data.len();
+            self.input.drain(..consumed);
+
+            if header.payload.len() >= header.length {
+                let message = Message {
+                    message_type: header.message_type,
+                    stream_id: header.stream_id,
+                    payload: std::mem::take(&mut header.payload),
+                };
+                self.headers.insert(chunk_stream_id, header);
+                return Some(message);
+            }
+            self.headers.insert(chunk_stream_id, header);
+        }
+    }
+
+    fn handle_message(&mut self, message: Message, out: &mut Vec<u8>) -> Result<(), Error> {
+        match message.message_type {
+            SET_CHUNK_SIZE => {
+                let size = u32::from_be_bytes(message.payload[..4].try_into()?);
+                self.chunk_size = size as usize;
+            }
+            COMMAND_AMF0 | COMMAND_AMF3 => {
+                let mut data = &message.payload[..];
+                if message.message_type == COMMAND_AMF3 {
+                    data = &data[1..];
+                }
+
+                let mut values = vec![];
+                while !data.is_empty() {
+                    values.push(Amf::read(&mut data)?);
+                }
+                self.handle_command(message.stream_id, values, out);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn handle_command(&mut self, stream_id: u32, values: Vec<Amf>, out: &mut Vec<u8>) {
+        let (Some(Amf::String(name)), Some(Amf::Number(transaction_id))) =
+            (values.first(), values.get(1))
+        else {
+            self.log.push(format!("Malformed command {values:?}"));
+            return;
+        };
+        let transaction_id = Amf::Number(*transaction_id);
+
+        // The command object is left out, as `connect`'s contains the URL of
+        // the movie.
+        let arguments: Vec<_> = values.iter().skip(3).map(ToString::to_string).collect();
+        self.log
+            .push(format!("Received command {name}({})", arguments.join(", ")));
+
+        match name.as_str() {
+            "connect" => {
+                if let Some(Amf::Number(encoding)) = values
+                    .get(2)
+                    .and_then(|object| object.get("objectEncoding"))
+                {
+                    self.object_encoding = *encoding;
+                }
+
+                send(out, 2, WINDOW_ACK_SIZE, 0, 0, &2500000u32.to_be_bytes());
+                send(
+                    out,
+                    2,
+                    SET_PEER_BANDWIDTH,
+                    0,
+                    0,
+                    &[0x00, 0x26, 0x25, 0xA0, 2],
+                );
+                send_command(
+                    out,
+                    0,
+                    &[
+                        Amf::string("_result"),
+                        transaction_id,
+                        Amf::object(&[
+                            ("fmsVer", Amf::string("FMS/3,5,7,7009")),
+                            ("capabilities", Amf::Number(31.0)),
+                        ]),
+                        Amf::object(&[
+                            ("level", Amf::string("status")),
+                            ("code", Amf::string("NetConnection.Connect.Success")),
+                            ("description", Amf::string("Connection succeeded.")),
+                            ("objectEncoding", Amf::Number(self.object_encoding)),
+                        ]),
+                    ],
+                );
+            }
+            "createStream" => {
+                send_command(
+                    out,
+                    0,
+                    &[
+                        Amf::string("_result"),
+                        transaction_id,
+                        Amf::Null,
+                        Amf::Number(STREAM_ID as f64),
+                    ],
+                );
+            }
+            "play" => {
+                send_status(out, "NetStream.Play.Reset", "Playing and resetting.");
+                send_status(out, "NetStream.Play.Start", "Started playing.");
+
+                for tag in &self.tags {
+                    if matches!(tag.tag_type, AUDIO | VIDEO | DATA_AMF0) {
+                        send(out, 4, tag.tag_type, STREAM_ID, tag.timestamp, &tag.payload);
+                    }
+                }
+
+                send_status(out, "NetStream.Play.Stop", "Stopped playing.");
+            }
+            "publish" => {
+                send_status(out, "NetStream.Publish.Start", "Started publishing.");
+            }
+            "closeStream" | "deleteStream" | "FCUnpublish" => {}
+            _ if transaction_id != Amf::Number(0.0) => {
+                let result = values.get(3).cloned().unwrap_or(Amf::Undefined);
+                if self.object_encoding == 3.0 {
+                    let mut payload = vec![0];
+                    Amf::string("_result").write(&mut payload);
+                    transaction_id.write(&mut payload);
+                    Amf::Null.write(&mut payload);
+                    result.write_amf3(&mut payload);
+                    send(out, 3, COMMAND_AMF3, stream_id, 0, &payload);
+                } else {
+                    send_command(
+                        out,
+                        stream_id,
+                        &[Amf::string("_result"), transaction_id, Amf::Null, result],
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Default for Server {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn send(
+    out: &mut Vec<u8>,
+    chunk_stream_id: u8,
+    message_type: u8,
+    stream_id: u32,
+    timestamp: u32,
+    payload: &[u8],
+) {
+    out.push(chunk_stream_id);
+    out.extend_from_slice(&timestamp.min(0xFFFFFE).to_be_bytes()[1..]);
+    out.extend_from_slice(&(payload.len() as u32).to_be_bytes()[1..]);
+    out.push(message_type);
+    out.extend_from_slice(&stream_id.to_le_bytes());
+
+    for (index, chunk) in payload.chunks(CHUNK_SIZE).enumerate() {
+        if index > 0 {
+            out.push(0xC0 | chunk_stream_id);
+        }
+        out.extend_from_slice(chunk);
+    }
+}
+
+fn send_command(out: &mut Vec<u8>, stream_id: u32, values: &[Amf]) {
+    let mut payload = vec![];
+    for value in values {
+        value.write(&mut payload);
+    }
+    send(out, 3, COMMAND_AMF0, stream_id, 0, &payload);
+}
+
+fn send_status(out: &mut Vec<u8>, code: &str, description: &str) {
+    send_command(
+        out,
+        STREAM_ID,
+        &[
+            Amf::string("onStatus"),
+            Amf::Number(0.0),
+            Amf::Null,
+            Amf::object(&[
+                ("level", Amf::string("status")),
+                ("code", Amf::string(code)),
+                ("description", Amf::string(description)),
+            ]),
+        ],
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Split a client message into chunks, like the client does.
+    fn client_chunks(chunk_stream_id: u8, message_type: u8, payload: &[u8]) -> Vec<u8> {
+        let mut out = vec![];
+        send(&mut out, chunk_stream_id, message_type, 0, 0, payload);
+        out
+    }
+
+    fn command(values: &[Amf]) -> Vec<u8> {
+        let mut payload = vec![];
+        for value in values {
+            value.write(&mut payload);
+        }
+        client_chunks(3, COMMAND_AMF0, &payload)
+    }
+
+    fn connected_server() -> Server {
+        let mut server = Server::new();
+        let mut handshake = vec![3];
+        handshake.extend_from_slice(&[7; HANDSHAKE_SIZE]);
+        let response = server.receive(&handshake).unwrap();
+        assert_eq!(response.len(), 1 + 2 * HANDSHAKE_SIZE);
+        assert_eq!(&response[1 + HANDSHAKE_SIZE..], &[7; HANDSHAKE_SIZE]);
+        assert!(server.receive(&[0; HANDSHAKE_SIZE]).unwrap().is_empty());
+        server
+    }
+
+    #[test]
+    fn messages_split_across_receives() {
+        let mut server = connected_server();
+        let data = command(&[
+            Amf::string("echo"),
+            Amf::Number(2.0),
+            Amf::Null,
+            Amf::string(&"a".repeat(200)),
+        ]);
+
+        // The message spans two chunks; feed it a byte at a time.
+        let mut response = vec![];
+        for byte in &data {
+            response.extend(server.receive(&[*byte]).unwrap());
+        }
+
+        assert_eq!(
+            server.take_log(),
+            vec![format!("Received command echo(\"{}\")", "a".repeat(200))]
+        );
+        let mut expected = vec![];
+        send_command(
+            &mut expected,
+            0,
+            &[
+                Amf::string("_result"),
+                Amf::Number(2.0),
+                Amf::Null,
+                Amf::string(&"a".repeat(200)),
+            ],
+        );
+        assert_eq!(response, expected);
+    }
+
+    #[test]
+    fn amf3_results_after_amf3_connect() {
+        let mut server = connected_server();
+        server
+            .receive(&command(&[
+                Amf::string("connect"),
+                Amf::Number(1.0),
+                Amf::object(&[("objectEncoding", Amf::Number(3.0))]),
+            ]))
+            .unwrap();
+
+        let response = server
+            .receive(&command(&[
+                Amf::string("echo"),
+                Amf::Number(2.0),
+                Amf::Null,
+                Amf::string("Hello!"),
+            ]))
+            .unwrap();
+
+        assert_eq!(response[7], COMMAND_AMF3);
+        assert!(response.ends_with(&[0x05, 0x11, 0x06, 0x0D, b'H', b'e', b'l', b'l', b'o', b'!']));
+    }
+}
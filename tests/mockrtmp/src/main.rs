@@ -0,0 +1,63 @@
+//! Runs the mock RTMP server on a real socket, for testing Ruffle by hand.
+//!
+//! It accepts a single connection and serves it until the client disconnects.
+
+use anyhow::Error;
+use clap::Parser;
+use mockrtmp::Server;
+use std::{
+    io::{Read, Write},
+    net::TcpListener,
+    path::PathBuf,
+};
+use tracing_subscriber::filter::{EnvFilter, LevelFilter};
+
+#[derive(Parser, Debug)]
+struct Opt {
+    /// Path to an FLV file to stream when a client plays a stream.
+    #[clap(name = "FILE")]
+    flv_path: Option<PathBuf>,
+
+    /// The port to listen on.
+    #[clap(long, short, default_value_t = 1935)]
+    port: u16,
+}
+
+fn main() -> Result<(), Error> {
+    let opt = Opt::parse();
+
+    let subscriber = tracing_subscriber::fmt::Subscriber::builder()
+        .with_env_filter(
+            EnvFilter::builder()
+                .with_default_directive(LevelFilter::INFO.into())
+                .from_env_lossy(),
+        )
+        .finish();
+    // Ignore error if it's already been set
+    let _ = tracing::subscriber::set_global_default(subscriber);
+
+    let mut server = match &opt.flv_path {
+        Some(path) => Server::with_flv(&std::fs::read(path)?)?,
+        None => Server::new(),
+    };
+
+    let listener = TcpListener::bind(("0.0.0.0", opt.port))?;
+    tracing::info!("Listening on {}", listener.local_addr()?);
+    let (mut stream, addr) = listener.accept()?;
+    tracing::info!("Incoming connection from {}", addr);
+
+    let mut buffer = [0; 4096];
+    loop {
+        let length = stream.read(&mut buffer)?;
+        if length == 0 {
+            tracing::info!("Client has closed the connection!");
+            return Ok(());
+        }
+
+        let response = server.receive(&buffer[..length])?;
+        for line in server.take_log() {
+            tracing::info!("{line}");
+        }
+        stream.write_all(&response)?;
+    }
+}
@@ -27,6 +27,7 @@ impl Environment for NativeEnvironment {
 #[cfg(feature = "imgtests")]
 mod renderer {
     use image::RgbaImage;
+    use ruffle_render_software::SoftwareRenderBackend;
     use ruffle_render_wgpu::backend::{request_adapter_and_device, WgpuRenderBackend};
     use ruffle_render_wgpu::descriptors::Descriptors;
     use ruffle_render_wgpu::target::TextureTarget;
@@ -51,6 +52,11 @@ mod renderer {
                     WgpuRenderBackend::new(descriptors.clone(), target)
                         .expect("WGPU Render backend creation must not fail, everything was checked ahead of time"),
                 )))
+            } else if software_fallback_enabled() {
+                Some((
+                    Box::new(Self),
+                    Box::new(SoftwareRenderBackend::new(width, height)),
+                ))
             } else {
                 None
             }
@@ -63,21 +69,39 @@ mod renderer {
                 let adapter_info = descriptors.adapter.get_info();
                 format!("{}-{:?}", std::env::consts::OS, adapter_info.backend)
             } else {
-                std::env::consts::OS.to_string()
+                format!("{}-Software", std::env::consts::OS)
             }
         }
 
         fn capture(&self, backend: &mut Box<dyn RenderBackend>) -> RgbaImage {
-            let renderer = backend
-                .downcast_mut::<WgpuRenderBackend<TextureTarget>>()
-                .unwrap();
+            if let Some(renderer) = backend.downcast_mut::<WgpuRenderBackend<TextureTarget>>() {
+                renderer.capture_frame().expect("Failed to capture image")
+            } else {
+                let renderer = backend.downcast_mut::<SoftwareRenderBackend>().unwrap();
+                renderer.capture_frame()
+            }
+        }
 
-            renderer.capture_frame().expect("Failed to capture image")
+        fn alternative_renderer(&self) -> Option<&'static str> {
+            if descriptors().is_some() {
+                None
+            } else {
+                Some("software")
+            }
         }
     }
 
     pub fn is_supported(_requirements: &RenderOptions) -> bool {
-        descriptors().is_some()
+        descriptors().is_some() || software_fallback_enabled()
+    }
+
+    /// Whether to render on the CPU when there's no graphics device, which is opted into by
+    /// setting `RUFFLE_TEST_SOFTWARE_RENDERER`.
+    ///
+    /// Its output isn't pixel perfect with wgpu, so tests may need to provide their own
+    /// expected images or tolerances for it.
+    fn software_fallback_enabled() -> bool {
+        std::env::var_os("RUFFLE_TEST_SOFTWARE_RENDERER").is_some()
     }
 
     static WGPU: OnceLock<Option<Arc<Descriptors>>> = OnceLock::new();
@@ -0,0 +1,284 @@
+#!/usr/bin/env python3
+"""Builds test.swf, as static text can't be authored without Flash.
+
+The movie has two static texts on its root timeline, using a font whose
+glyphs have no outlines, as the test only reads the text:
+
+* depth 1, at (10, 20): "Hello World" in black, with "Second line" in red
+  on the line below it.
+* depth 2, at (10, 100), scaled twice: "Quiz" in blue.
+
+The glyphs are 10 pixels wide and 20 pixels high, with an ascent of 15 pixels.
+"""
+
+import struct
+
+LINES = [
+    # (text, color, y offset of the baseline in twips)
+    ("Hello World", 0x000000, 300),
+    ("Second line", 0xFF0000, 700),
+]
+QUIZ = ("Quiz", 0x0000FF, 300)
+CHARS = sorted(set("".join(text for text, _, _ in LINES + [QUIZ])))
+
+FONT_ID = 1
+FONT_SIZE = 400
+ADVANCE = 200
+
+
+class Bits:
+    def __init__(self):
+        self.bits = []
+
+    def ubits(self, value, n):
+        self.bits += [(value >> i) & 1 for i in reversed(range(n))]
+
+    def sbits(self, value, n):
+        self.ubits(value & ((1 << n) - 1), n)
+
+    def bytes(self):
+        bits = self.bits + [0] * (-len(self.bits) % 8)
+        return bytes(
+            int("".join(map(str, bits[i : i + 8])), 2) for i in range(0, len(bits), 8)
+        )
+
+
+def tag(code, body):
+    if len(body) < 0x3F:
+        return struct.pack("<H", code << 6 | len(body)) + body
+    return struct.pack("<HI", code << 6 | 0x3F, len(body)) + body
+
+
+def rect(x_min, x_max, y_min, y_max):
+    bits = Bits()
+    bits.ubits(16, 5)
+    for value in (x_min, x_max, y_min, y_max):
+        bits.sbits(value, 16)
+    return bits.bytes()
+
+
+def matrix(scale, tx, ty):
+    bits = Bits()
+    if scale != 1:
+        bits.ubits(1, 1)
+        bits.ubits(20, 5)
+        bits.sbits(int(scale * 65536), 20)
+        bits.sbits(int(scale * 65536), 20)
+    else:
+        bits.ubits(0, 1)
+    bits.ubits(0, 1)
+    bits.ubits(16, 5)
+    bits.sbits(tx, 16)
+    bits.sbits(ty, 16)
+    return bits.bytes()
+
+
+def define_font_2():
+    # Every glyph is an empty shape: one fill bit, no line bits, and an end record.
+    shapes = [bytes([0x10, 0x00])] * len(CHARS)
+    offsets_size = 2 * (len(CHARS) + 1)
+    offsets, offset = [], offsets_size
+    for shape in shapes:
+        offsets.append(offset)
+        offset += len(shape)
+
+    name = b"TestFont"
+    body = struct.pack("<HBBB", FONT_ID, 0x80 | 0x04, 1, len(name)) + name
+    body += struct.pack("<H", len(CHARS))
+    body += b"".join(struct.pack("<H", o) for o in offsets)
+    body += struct.pack("<H", offset)
+    body += b"".join(shapes)
+    body += b"".join(struct.pack("<H", ord(c)) for c in CHARS)
+    # Layout: an ascent of 768 and a descent of 256 in the 1024 EM square.
+    body += struct.pack("<hhh", 768, 256, 0)
+    body += b"".join(struct.pack("<h", ADVANCE) for _ in CHARS)
+    body += b"".join(rect(0, 0, 0, 0) for _ in CHARS)
+    body += struct.pack("<H", 0)
+    return tag(48, body)
+
+
+def define_text(id, lines):
+    body = struct.pack("<H", id)
+    body += rect(0, ADVANCE * 11, 0, 800)
+    body += matrix(1, 0, 0)
+    body += bytes([8, 16])
+    for i, (text, color, y) in enumerate(lines):
+        flags = 0x80 | 0x04 | 0x02 | 0x01
+        if i == 0:
+            flags |= 0x08
+        body += bytes([flags])
+        if i == 0:
+            body += struct.pack("<H", FONT_ID)
+        body += bytes([color >> 16, (color >> 8) & 0xFF, color & 0xFF])
+        body += struct.pack("<hh", 0, y)
+        if i == 0:
+            body += struct.pack("<H", FONT_SIZE)
+        body += bytes([len(text)])
+        bits = Bits()
+        for c in text:
+            bits.ubits(CHARS.index(c), 8)
+            bits.sbits(ADVANCE, 16)
+        body += bits.bytes()
+    body += bytes([0])
+    return tag(11, body)
+
+
+def place_object_2(id, depth, scale, tx, ty):
+    body = bytes([0x02 | 0x04]) + struct.pack("<HH", depth, id) + matrix(scale, tx, ty)
+    return tag(26, body)
+
+
+def static_text_tags():
+    """The tags that define and place the static texts, on the first frame."""
+    return (
+        define_font_2()
+        + define_text(2, LINES)
+        + place_object_2(2, 1, 1, 200, 400)
+        + define_text(3, [QUIZ])
+        + place_object_2(3, 2, 2, 200, 2000)
+    )
+
+
+# AVM1 bytecode for the script of the first frame.
+
+
+def push(*values):
+    body = b""
+    for value in values:
+        if isinstance(value, bool):
+            body += bytes([5, value])
+        elif isinstance(value, int):
+            body += bytes([7]) + struct.pack("<i", value)
+        else:
+            body += bytes([0]) + value.encode() + b"\0"
+    return bytes([0x96]) + struct.pack("<H", len(body)) + body
+
+
+GET_VARIABLE = bytes([0x1C])
+SET_VARIABLE = bytes([0x1D])
+GET_MEMBER = bytes([0x4E])
+CALL_METHOD = bytes([0x52])
+ADD = bytes([0x47])
+TRACE = bytes([0x26])
+POP = bytes([0x17])
+
+
+def var(name):
+    return push(name) + GET_VARIABLE
+
+
+def member(obj, name):
+    return obj + push(name) + GET_MEMBER
+
+
+def call(obj, method, *args):
+    code = b""
+    for arg in reversed(args):
+        code += arg if isinstance(arg, bytes) else push(arg)
+    return code + push(len(args)) + obj + push(method) + CALL_METHOD
+
+
+def trace(label, value):
+    return push(label) + value + ADD + TRACE
+
+
+def comment(text):
+    return push("// " + text) + TRACE
+
+
+def set_var(name, value):
+    return push(name) + value + SET_VARIABLE
+
+
+def run_info(index):
+    code = set_var("run", member(call(var("ts"), "getTextRunInfo", index, index + 1), "0"))
+    for name in [
+        "indexInRun",
+        "selected",
+        "font",
+        "color",
+        "height",
+        "matrix_a",
+        "matrix_b",
+        "matrix_c",
+        "matrix_d",
+        "matrix_tx",
+        "matrix_ty",
+    ] + [f"corner{i}{axis}" for i in range(4) for axis in "xy"]:
+        code += trace(f"{name}: ", member(var("run"), name))
+    return code
+
+
+def script():
+    ts = var("ts")
+    code = set_var("ts", call(var("_root"), "getTextSnapshot"))
+    code += trace("getCount(): ", call(ts, "getCount"))
+
+    code += comment("getText")
+    code += trace("getText(0, 100): ", call(ts, "getText", 0, 100))
+    code += trace("getText(0, 100, true): ", call(ts, "getText", 0, 100, True))
+    code += trace("getText(6, 11): ", call(ts, "getText", 6, 11))
+    code += trace("getText(5, 2): ", call(ts, "getText", 5, 2))
+    code += trace("getText(-5, 3): ", call(ts, "getText", -5, 3))
+
+    code += comment("findText")
+    code += trace('findText(0, "world", false): ', call(ts, "findText", 0, "world", False))
+    code += trace('findText(0, "world", true): ', call(ts, "findText", 0, "world", True))
+    code += trace('findText(0, "World", true): ', call(ts, "findText", 0, "World", True))
+    code += trace('findText(0, "o", false): ', call(ts, "findText", 0, "o", False))
+    code += trace('findText(5, "o", false): ', call(ts, "findText", 5, "o", False))
+    code += trace('findText(0, "quiz", false): ', call(ts, "findText", 0, "quiz", False))
+    code += trace('findText(0, "", false): ', call(ts, "findText", 0, "", False))
+    code += trace('findText(30, "Quiz", false): ', call(ts, "findText", 30, "Quiz", False))
+
+    code += comment("hitTestTextNearPos")
+    code += trace("hitTestTextNearPos(15, 30): ", call(ts, "hitTestTextNearPos", 15, 30))
+    code += trace("hitTestTextNearPos(25, 50): ", call(ts, "hitTestTextNearPos", 25, 50))
+    code += trace("hitTestTextNearPos(300, 300): ", call(ts, "hitTestTextNearPos", 300, 300))
+    code += trace(
+        "hitTestTextNearPos(135, 30, 10): ", call(ts, "hitTestTextNearPos", 135, 30, 10)
+    )
+    code += trace(
+        "hitTestTextNearPos(135, 30, 20): ", call(ts, "hitTestTextNearPos", 135, 30, 20)
+    )
+    code += trace("hitTestTextNearPos(50, 120): ", call(ts, "hitTestTextNearPos", 50, 120))
+
+    code += comment("setSelected")
+    code += call(ts, "setSelected", 6, 11, True) + POP
+    code += trace("getSelected(0, 5): ", call(ts, "getSelected", 0, 5))
+    code += trace("getSelected(4, 7): ", call(ts, "getSelected", 4, 7))
+    code += trace("getSelectedText(): ", call(ts, "getSelectedText"))
+    code += call(ts, "setSelected", 22, 26, True) + POP
+    code += trace("getSelectedText(false): ", call(ts, "getSelectedText", False))
+    code += trace("getSelectedText(true): ", call(ts, "getSelectedText", True))
+    code += call(ts, "setSelectColor", 0xFF0000) + POP
+
+    code += comment("getTextRunInfo(11, 12)")
+    code += run_info(11)
+    code += comment("getTextRunInfo(23, 24)")
+    code += run_info(23)
+    code += trace("getTextRunInfo(0, 100).length: ", member(call(ts, "getTextRunInfo", 0, 100), "length"))
+    code += trace("getTextRunInfo(5, 2).length: ", member(call(ts, "getTextRunInfo", 5, 2), "length"))
+
+    code += call(ts, "setSelected", 0, 100, False) + POP
+    code += trace("getSelectedText() after deselecting: ", call(ts, "getSelectedText"))
+    code += trace("getSelected(0, 100) after deselecting: ", call(ts, "getSelected", 0, 100))
+
+    code += comment("A clip without static text")
+    code += set_var("empty", call(var("_root"), "createEmptyMovieClip", "empty", 10))
+    code += trace("getCount(): ", call(call(var("empty"), "getTextSnapshot"), "getCount"))
+    code += trace("getText(0, 10): ", call(call(var("empty"), "getTextSnapshot"), "getText", 0, 10))
+    return code + bytes([0])
+
+
+def main():
+    tags = static_text_tags() + tag(12, script()) + tag(1, b"") + tag(0, b"")
+    header = rect(0, 11000, 0, 8000) + struct.pack("<BBH", 0, 24, 1)
+    body = header + tag(69, struct.pack("<I", 0)) + tags
+    swf = b"FWS" + bytes([8]) + struct.pack("<I", 8 + len(body)) + body
+    with open("test.swf", "wb") as f:
+        f.write(swf)
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,50 @@
+package
+{
+    import flash.display.Sprite;
+
+    public class Test extends Sprite
+    {
+    }
+}
+
+import flash.display.BitmapData;
+import flash.filters.BitmapFilter;
+import flash.filters.ConvolutionFilter;
+import flash.geom.Point;
+
+function hex(value:uint):String
+{
+    var digits:String = value.toString(16).toUpperCase();
+    while (digits.length < 8) {
+        digits = "0" + digits;
+    }
+    return "0x" + digits;
+}
+
+// Opaque 5x5 bitmaps, with channels that divide evenly by the divisors below.
+var dot:BitmapData = new BitmapData(5, 5, false, 0x000000);
+dot.setPixel(2, 2, 0x906C48);
+var solid:BitmapData = new BitmapData(5, 5, false, 0x906C48);
+
+function test(name:String, source:BitmapData, filter:BitmapFilter, points:Array):void
+{
+    var dest:BitmapData = new BitmapData(5, 5, false, 0x00FF00);
+    dest.applyFilter(source, source.rect, new Point(0, 0), filter);
+    trace("// " + name);
+    for each (var point:Point in points) {
+        trace("(" + point.x + ", " + point.y + "): " + hex(dest.getPixel32(point.x, point.y)));
+    }
+    trace("");
+}
+
+var center:Point = new Point(2, 2);
+var neighbor:Point = new Point(1, 1);
+var corner:Point = new Point(0, 0);
+
+test("Identity", dot, new ConvolutionFilter(3, 3, [0, 0, 0, 0, 1, 0, 0, 0, 0]), [center, neighbor, corner]);
+test("Box blur", dot, new ConvolutionFilter(3, 3, [1, 1, 1, 1, 1, 1, 1, 1, 1], 9), [center, neighbor, corner]);
+test("Bias", dot, new ConvolutionFilter(3, 3, [0, 0, 0, 0, 1, 0, 0, 0, 0], 1, 16), [center, neighbor, corner]);
+test("Top left neighbor", dot, new ConvolutionFilter(3, 3, [1, 0, 0, 0, 0, 0, 0, 0, 0]), [center, new Point(3, 3), corner]);
+test("Divisor", dot, new ConvolutionFilter(1, 1, [1], 2), [center, corner]);
+test("Clamped edges", solid, new ConvolutionFilter(3, 3, [1, 0, 0, 0, 0, 0, 0, 0, 0], 1, 0, true, true), [corner, new Point(0, 4)]);
+test("Edge color", solid, new ConvolutionFilter(3, 3, [1, 0, 0, 0, 0, 0, 0, 0, 0], 1, 0, true, false, 0xFF0000, 1), [corner, new Point(0, 4), new Point(4, 0), center]);
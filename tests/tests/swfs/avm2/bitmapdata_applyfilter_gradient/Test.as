@@ -0,0 +1,67 @@
+package
+{
+    import flash.display.Sprite;
+
+    public class Test extends Sprite
+    {
+    }
+}
+
+import flash.display.BitmapData;
+import flash.filters.BitmapFilter;
+import flash.filters.BitmapFilterType;
+import flash.filters.GradientBevelFilter;
+import flash.filters.GradientGlowFilter;
+import flash.geom.Point;
+import flash.geom.Rectangle;
+
+function hex(value:uint):String
+{
+    var digits:String = value.toString(16).toUpperCase();
+    while (digits.length < 8) {
+        digits = "0" + digits;
+    }
+    return "0x" + digits;
+}
+
+// A transparent 24x4 bitmap, with an opaque white rectangle from x = 6 to x = 15.
+var source:BitmapData = new BitmapData(24, 4, true, 0);
+source.fillRect(new Rectangle(6, 0, 10, 4), 0xFFFFFFFF);
+
+function test(name:String, filter:BitmapFilter, xs:Array):void
+{
+    var dest:BitmapData = new BitmapData(24, 4, true, 0);
+    dest.applyFilter(source, source.rect, new Point(0, 0), filter);
+    trace("// " + name);
+    for each (var x:int in xs) {
+        trace("x = " + x + ": " + hex(dest.getPixel32(x, 1)));
+    }
+    trace("");
+}
+
+// Blurs of 1 leave the shape sharp, so that each pixel picks an end of the gradient.
+var glowColors:Array = [0x0000FF, 0x00FF00];
+var glowAlphas:Array = [0, 1];
+var glowRatios:Array = [0, 255];
+
+test("Outer glow",
+    new GradientGlowFilter(2, 0, glowColors, glowAlphas, glowRatios, 1, 1, 1, 1, BitmapFilterType.OUTER),
+    [2, 6, 12, 16, 17, 18, 20]);
+test("Outer glow, knockout",
+    new GradientGlowFilter(2, 0, glowColors, glowAlphas, glowRatios, 1, 1, 1, 1, BitmapFilterType.OUTER, true),
+    [2, 6, 12, 16, 17, 18, 20]);
+test("Full glow, knockout",
+    new GradientGlowFilter(2, 0, glowColors, glowAlphas, glowRatios, 1, 1, 1, 1, BitmapFilterType.FULL, true),
+    [2, 6, 7, 8, 12, 17, 18, 20]);
+
+// The highlight is at ratio 0 and the shadow at ratio 255, with the transparent flat surface in between.
+var bevelColors:Array = [0xFF0000, 0x00FF00, 0x0000FF];
+var bevelAlphas:Array = [1, 0, 1];
+var bevelRatios:Array = [0, 128, 255];
+
+test("Full bevel, knockout",
+    new GradientBevelFilter(2, 0, bevelColors, bevelAlphas, bevelRatios, 1, 1, 1, 1, BitmapFilterType.FULL, true),
+    [2, 6, 10, 15, 20]);
+test("Inner bevel",
+    new GradientBevelFilter(2, 0, bevelColors, bevelAlphas, bevelRatios, 1, 1, 1, 1, BitmapFilterType.INNER),
+    [2, 6, 10, 15, 20]);
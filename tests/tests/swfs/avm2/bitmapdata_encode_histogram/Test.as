@@ -0,0 +1,189 @@
+package {
+  import flash.display.Sprite;
+  public class Test extends Sprite { }
+}
+
+import flash.display.Bitmap;
+import flash.display.BitmapData;
+import flash.display.JPEGEncoderOptions;
+import flash.display.JPEGXREncoderOptions;
+import flash.display.Loader;
+import flash.display.PNGEncoderOptions;
+import flash.events.Event;
+import flash.geom.Rectangle;
+import flash.utils.ByteArray;
+
+function hex(value) {
+    var str = uint(value).toString(16).toUpperCase();
+    while (str.length < 8) {
+        str = "0" + str;
+    }
+    return str;
+}
+
+function dumpBytes(bytes, start, count) {
+    var out = [];
+    for (var i = start; i < start + count; i++) {
+        var str = bytes[i].toString(16).toUpperCase();
+        out.push(str.length == 1 ? "0" + str : str);
+    }
+    return out.join(" ");
+}
+
+function dumpPngHeader(bytes, start) {
+    trace("signature: " + dumpBytes(bytes, start, 8));
+    bytes.position = start + 16;
+    var width = bytes.readUnsignedInt();
+    var height = bytes.readUnsignedInt();
+    trace("size: " + width + "x" + height);
+    trace("bit depth: " + bytes.readUnsignedByte() + ", color type: " + bytes.readUnsignedByte());
+}
+
+function dumpHistogram(histogram) {
+    var names = ["red", "green", "blue", "alpha"];
+    for (var channel = 0; channel < histogram.length; channel++) {
+        var counts = [];
+        for (var value = 0; value < histogram[channel].length; value++) {
+            if (histogram[channel][value] != 0) {
+                counts.push(value + ":" + histogram[channel][value]);
+            }
+        }
+        trace(names[channel] + ": " + counts.join(" "));
+    }
+}
+
+var transparent = new BitmapData(4, 2, true, 0);
+transparent.setPixel32(0, 0, 0xFFFF0000);
+transparent.setPixel32(1, 0, 0x8000FF00);
+transparent.setPixel32(2, 0, 0x00000000);
+transparent.setPixel32(3, 0, 0xFFFFFFFF);
+transparent.setPixel32(0, 1, 0xFF336699);
+transparent.setPixel32(1, 1, 0xFF336699);
+transparent.setPixel32(2, 1, 0xFF000000);
+transparent.setPixel32(3, 1, 0x80FFFFFF);
+
+var opaque = new BitmapData(2, 2, false, 0x112233);
+
+trace("// histogram()");
+var histogram = transparent.histogram();
+trace("length: " + histogram.length + ", channel length: " + histogram[0].length);
+dumpHistogram(histogram);
+
+trace("// histogram(new Rectangle(1, 0, 2, 2))");
+dumpHistogram(transparent.histogram(new Rectangle(1, 0, 2, 2)));
+
+trace("// histogram(new Rectangle(-1, -1, 10, 10))");
+dumpHistogram(transparent.histogram(new Rectangle(-1, -1, 10, 10)));
+
+trace("// opaque.histogram()");
+dumpHistogram(opaque.histogram());
+
+trace("// encode(rect, new PNGEncoderOptions())");
+var png = transparent.encode(transparent.rect, new PNGEncoderOptions());
+dumpPngHeader(png, 0);
+
+trace("// encode(rect, new PNGEncoderOptions(true))");
+var fastPng = transparent.encode(transparent.rect, new PNGEncoderOptions(true));
+dumpPngHeader(fastPng, 0);
+
+trace("// opaque.encode(rect, new PNGEncoderOptions())");
+dumpPngHeader(opaque.encode(opaque.rect, new PNGEncoderOptions()), 0);
+
+trace("// encode(new Rectangle(1, 0, 2, 1), new PNGEncoderOptions())");
+var partialPng = transparent.encode(new Rectangle(1, 0, 2, 1), new PNGEncoderOptions());
+dumpPngHeader(partialPng, 0);
+
+trace("// encode into a ByteArray");
+var target = new ByteArray();
+target.writeUTFBytes("abc");
+var result = transparent.encode(transparent.rect, new PNGEncoderOptions(), target);
+trace("same ByteArray: " + (result === target));
+trace("prefix: " + dumpBytes(target, 0, 3));
+dumpPngHeader(target, 3);
+
+trace("// encode(new Rectangle(0, 0, 0, 0), new PNGEncoderOptions())");
+trace("length: " + transparent.encode(new Rectangle(0, 0, 0, 0), new PNGEncoderOptions()).length);
+
+var solid = new BitmapData(16, 16, false, 0x336699);
+
+trace("// encode(rect, new JPEGEncoderOptions())");
+var jpeg = solid.encode(solid.rect, new JPEGEncoderOptions());
+trace("start: " + dumpBytes(jpeg, 0, 2) + ", end: " + dumpBytes(jpeg, jpeg.length - 2, 2));
+
+trace("// JPEG quality");
+var gradient = new BitmapData(64, 64, false, 0);
+for (var y = 0; y < 64; y++) {
+    for (var x = 0; x < 64; x++) {
+        gradient.setPixel(x, y, (x * 4) << 16 | (y * 4) << 8 | (x * y) % 256);
+    }
+}
+var low = gradient.encode(gradient.rect, new JPEGEncoderOptions(10));
+var high = gradient.encode(gradient.rect, new JPEGEncoderOptions(100));
+trace("quality 10 is smaller than quality 100: " + (low.length < high.length));
+
+trace("// encode(rect, new JPEGXREncoderOptions())");
+trace("length: " + transparent.encode(transparent.rect, new JPEGXREncoderOptions()).length);
+
+trace("// encode(rect, {})");
+try {
+    transparent.encode(transparent.rect, {});
+} catch (e) {
+    trace(e);
+}
+
+trace("// encode of a disposed BitmapData");
+var disposed = new BitmapData(1, 1);
+disposed.dispose();
+try {
+    disposed.encode(disposed.rect, new PNGEncoderOptions());
+} catch (e) {
+    trace(e);
+}
+
+function decode(name, bytes, check, next) {
+    var loader = new Loader();
+    loader.contentLoaderInfo.addEventListener(Event.COMPLETE, function(e) {
+        trace("// decoded " + name);
+        check(Bitmap(loader.content).bitmapData);
+        if (next != null) {
+            next();
+        }
+    });
+    loader.loadBytes(bytes);
+}
+
+function checkPng(decoded) {
+    trace("size: " + decoded.width + "x" + decoded.height);
+    for (var y = 0; y < decoded.height; y++) {
+        var row = [];
+        for (var x = 0; x < decoded.width; x++) {
+            row.push(hex(decoded.getPixel32(x, y)));
+        }
+        trace(row.join(" "));
+    }
+}
+
+function checkJpeg(decoded) {
+    trace("size: " + decoded.width + "x" + decoded.height);
+    var close = true;
+    for (var y = 0; y < decoded.height; y++) {
+        for (var x = 0; x < decoded.width; x++) {
+            var pixel = decoded.getPixel(x, y);
+            var expected = solid.getPixel(x, y);
+            for (var shift = 0; shift <= 16; shift += 8) {
+                if (Math.abs(((pixel >> shift) & 0xFF) - ((expected >> shift) & 0xFF)) > 2) {
+                    close = false;
+                }
+            }
+        }
+    }
+    trace("pixels are close to the original: " + close);
+}
+
+decode("PNG", png, checkPng, function() {
+    decode("fast PNG", fastPng, checkPng, function() {
+        decode("partial PNG", partialPng, checkPng, function() {
+            decode("JPEG", jpeg, checkJpeg, null);
+        });
+    });
+});
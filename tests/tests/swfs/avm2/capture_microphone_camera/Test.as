@@ -0,0 +1,88 @@
+package {
+    import flash.display.BitmapData;
+    import flash.display.Sprite;
+    import flash.events.ActivityEvent;
+    import flash.events.SampleDataEvent;
+    import flash.geom.Rectangle;
+    import flash.media.Camera;
+    import flash.media.Microphone;
+    import flash.utils.ByteArray;
+
+    public class Test extends Sprite {
+        private var mic:Microphone;
+        private var camera:Camera;
+        private var events:int = 0;
+
+        public function Test() {
+            trace("Microphone.isSupported: " + Microphone.isSupported);
+            trace("Microphone.names: " + Microphone.names);
+            mic = Microphone.getMicrophone();
+            trace("same microphone: " + (Microphone.getMicrophone(0) === mic));
+            trace("missing microphone: " + Microphone.getMicrophone(1));
+            trace("name: " + mic.name + ", index: " + mic.index + ", muted: " + mic.muted);
+            trace("rate: " + mic.rate + ", gain: " + mic.gain + ", activityLevel: " + mic.activityLevel);
+            trace("silenceLevel: " + mic.silenceLevel + ", silenceTimeout: " + mic.silenceTimeout);
+            mic.rate = 12;
+            trace("rate after setting an unsupported rate: " + mic.rate);
+            mic.setSilenceLevel(10, 60);
+            trace("silenceLevel: " + mic.silenceLevel + ", silenceTimeout: " + mic.silenceTimeout);
+            mic.addEventListener(ActivityEvent.ACTIVITY, onActivity);
+            mic.addEventListener(SampleDataEvent.SAMPLE_DATA, onSampleData);
+
+            trace("");
+            trace("Camera.isSupported: " + Camera.isSupported);
+            trace("Camera.names: " + Camera.names);
+            camera = Camera.getCamera();
+            trace("same camera: " + (Camera.getCamera("0") === camera));
+            trace("missing camera: " + Camera.getCamera("1") + ", " + Camera.getCamera("front"));
+            trace("name: " + camera.name + ", index: " + camera.index + ", muted: " + camera.muted);
+            trace("width: " + camera.width + ", height: " + camera.height + ", fps: " + camera.fps);
+            trace("currentFPS: " + camera.currentFPS + ", activityLevel: " + camera.activityLevel);
+            camera.setMode(4, 2, 25);
+            trace("width: " + camera.width + ", height: " + camera.height + ", fps: " + camera.fps);
+            trace("");
+        }
+
+        private function onActivity(e:ActivityEvent):void {
+            trace("activity: " + e.activating);
+        }
+
+        private function onSampleData(e:SampleDataEvent):void {
+            events++;
+            trace("sampleData: position " + e.position + ", samples " + (e.data.length / 4) +
+                ", first " + e.data.readFloat() + ", activityLevel " + mic.activityLevel);
+            if (events == 6) {
+                mic.gain = 25;
+                mic.rate = 16;
+                trace("gain: " + mic.gain + ", rate: " + mic.rate);
+            }
+
+            // Cameras deliver their frames after microphones, so this is the frame of the last tick.
+            traceCamera();
+
+            if (events == 7) {
+                mic.removeEventListener(SampleDataEvent.SAMPLE_DATA, onSampleData);
+                mic.removeEventListener(ActivityEvent.ACTIVITY, onActivity);
+            }
+        }
+
+        private function traceCamera():void {
+            var pixels:Vector.<uint> = new Vector.<uint>();
+            camera.copyToVector(new Rectangle(0, 0, 4, 2), pixels);
+            if (pixels.length == 0) {
+                trace("camera: no frame yet, activityLevel " + camera.activityLevel);
+                return;
+            }
+
+            var bytes:ByteArray = new ByteArray();
+            camera.copyToByteArray(new Rectangle(1, 1, 10, 10), bytes);
+            bytes.position = 0;
+            var bitmapData:BitmapData = new BitmapData(2, 2, false, 0);
+            camera.drawToBitmapData(bitmapData);
+            trace("camera: vector " + pixels.length + " x " + pixels[0].toString(16) +
+                ", bytes " + bytes.length + " x " + bytes.readUnsignedInt().toString(16) +
+                ", bitmap " + bitmapData.getPixel32(1, 1).toString(16) +
+                ", activityLevel " + camera.activityLevel);
+        }
+    }
+}
@@ -0,0 +1,42 @@
+package {
+import flash.display.Sprite;
+
+public class Test extends Sprite {
+    function Test() {
+
+    }
+}
+}
+
+import flash.desktop.Clipboard;
+import flash.desktop.ClipboardFormats;
+
+function logError(f:*):void {
+    try {
+        f();
+    } catch(error) {
+        trace("Error: " + error);
+    }
+}
+
+logError(function() {
+    new Clipboard();
+});
+
+var clipboard:Clipboard = Clipboard.generalClipboard;
+trace(clipboard.setData(ClipboardFormats.TEXT_FORMAT, "text"));
+
+logError(function() {
+    trace(clipboard.getData(ClipboardFormats.TEXT_FORMAT));
+});
+logError(function() {
+    trace(clipboard.hasFormat(ClipboardFormats.TEXT_FORMAT));
+});
+logError(function() {
+    trace(clipboard.formats);
+});
+
+trace(clipboard.setData("custom", "data"));
+trace(clipboard.getData("custom"));
+
+trace(ClipboardFormats["FILE_LIST_FORMAT"]);
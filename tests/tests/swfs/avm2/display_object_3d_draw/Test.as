@@ -0,0 +1,66 @@
+package
+{
+    import flash.display.BitmapData;
+    import flash.display.Shape;
+    import flash.display.Sprite;
+    import flash.geom.Matrix3D;
+    import flash.geom.PerspectiveProjection;
+    import flash.geom.Point;
+
+    public class Test extends Sprite
+    {
+        public function Test()
+        {
+            // With a 90 degree field of view, the focal length is half of the stage width: 100.
+            var projection:PerspectiveProjection = new PerspectiveProjection();
+            projection.fieldOfView = 90;
+            projection.projectionCenter = new Point(0, 0);
+            transform.perspectiveProjection = projection;
+
+            var far:Shape = createSquare();
+            far.x = 40;
+            far.y = 40;
+            far.z = 100;
+            test("z = 100, scaled by half towards (0, 0)", far, [[25, 25], [35, 55], [55, 35], [65, 65], [15, 15], [75, 75]]);
+
+            var flipped:Shape = createSquare();
+            flipped.x = 140;
+            flipped.y = 40;
+            flipped.rotationY = 180;
+            test("rotationY = 180, mirrored around x = 140", flipped, [[60, 90], [120, 90], [30, 90], [150, 90]]);
+
+            var matrix:Shape = createSquare();
+            var matrix3D:Matrix3D = new Matrix3D();
+            matrix3D.appendTranslation(140, 40, 100);
+            matrix.transform.matrix3D = matrix3D;
+            test("Matrix3D translated to (140, 40, 100)", matrix, [[80, 45], [110, 45], [60, 45], [130, 45], [80, 80]]);
+        }
+
+        // A 100x100 square, red on its left half and blue on its right half.
+        private function createSquare():Shape
+        {
+            var square:Shape = new Shape();
+            square.graphics.beginFill(0xFF0000);
+            square.graphics.drawRect(0, 0, 50, 100);
+            square.graphics.endFill();
+            square.graphics.beginFill(0x0000FF);
+            square.graphics.drawRect(50, 0, 50, 100);
+            square.graphics.endFill();
+            return square;
+        }
+
+        private function test(name:String, child:Shape, points:Array):void
+        {
+            addChild(child);
+            var bitmapData:BitmapData = new BitmapData(200, 200, false, 0xFFFFFF);
+            bitmapData.draw(this);
+            removeChild(child);
+
+            trace("// " + name);
+            for each (var point:Array in points) {
+                trace("(" + point[0] + ", " + point[1] + "): " + bitmapData.getPixel(point[0], point[1]).toString(16));
+            }
+            trace("");
+        }
+    }
+}
@@ -0,0 +1,58 @@
+package
+{
+    import flash.display.Sprite;
+    import flash.geom.Rectangle;
+    import flash.text.TextField;
+    import flash.text.TextFieldAutoSize;
+    import flash.text.TextFormat;
+
+    public class Test extends Sprite
+    {
+        public function Test()
+        {
+            // Arabic letters take a different, joined form when they're next to each other.
+            var isolated:TextField = createTextField("ب");
+            var joined:TextField = createTextField("ببب");
+            trace("// Arabic");
+            trace("Initial beh is narrower than isolated beh: " + (bounds(joined, 0).width < bounds(isolated, 0).width));
+            trace("First beh is rightmost: " + (bounds(joined, 0).x > bounds(joined, 1).x && bounds(joined, 1).x > bounds(joined, 2).x));
+            trace("");
+
+            // A Hebrew word inside a left-to-right paragraph is reversed in place.
+            var mixed:TextField = createTextField("abc אבג def");
+            trace("// Hebrew word in a left-to-right paragraph");
+            trace("Latin letters run left to right: " + (bounds(mixed, 0).x < bounds(mixed, 1).x && bounds(mixed, 8).x < bounds(mixed, 9).x));
+            trace("Hebrew letters run right to left: " + (bounds(mixed, 4).x > bounds(mixed, 5).x && bounds(mixed, 5).x > bounds(mixed, 6).x));
+            trace("Hebrew word is between the Latin words: " + (bounds(mixed, 6).x >= right(mixed, 3) && right(mixed, 4) <= bounds(mixed, 7).x));
+            trace("");
+
+            // A right-to-left paragraph starts on the right, with numbers still running left to right.
+            var rtl:TextField = createTextField("אב 12");
+            trace("// Number in a right-to-left paragraph");
+            trace("Hebrew letters run right to left: " + (bounds(rtl, 0).x > bounds(rtl, 1).x));
+            trace("Number is left of the Hebrew word: " + (right(rtl, 4) <= bounds(rtl, 2).x && right(rtl, 2) <= bounds(rtl, 1).x));
+            trace("Digits run left to right: " + (bounds(rtl, 3).x < bounds(rtl, 4).x));
+        }
+
+        private function createTextField(text:String):TextField
+        {
+            var field:TextField = new TextField();
+            field.defaultTextFormat = new TextFormat("DejaVu Sans", 20);
+            field.autoSize = TextFieldAutoSize.LEFT;
+            field.text = text;
+            addChild(field);
+            return field;
+        }
+
+        private function bounds(field:TextField, index:int):Rectangle
+        {
+            return field.getCharBoundaries(index);
+        }
+
+        private function right(field:TextField, index:int):Number
+        {
+            var rect:Rectangle = bounds(field, index);
+            return rect.x + rect.width;
+        }
+    }
+}
@@ -0,0 +1,95 @@
+package {
+  import flash.display.Sprite;
+  public class Test extends Sprite { }
+}
+
+import flash.net.FileReference;
+import flash.net.FileFilter;
+import flash.net.URLRequest;
+import flash.net.URLRequestMethod;
+import flash.net.URLVariables;
+import flash.events.Event;
+import flash.events.DataEvent;
+import flash.events.HTTPStatusEvent;
+import flash.events.IOErrorEvent;
+import flash.events.ProgressEvent;
+
+function listen(file, next) {
+    file.addEventListener(Event.SELECT, function(e) {
+        trace("select event: " + e.target.name);
+    });
+    file.addEventListener(Event.CANCEL, function(e) {
+        trace("cancel event");
+    });
+    file.addEventListener(Event.OPEN, function(e) {
+        trace("open event");
+    });
+    file.addEventListener(ProgressEvent.PROGRESS, function(e) {
+        trace("progress event: " + e.bytesLoaded + " / " + e.bytesTotal);
+    });
+    file.addEventListener(HTTPStatusEvent.HTTP_STATUS, function(e) {
+        trace("httpStatus event: " + e.status);
+    });
+    file.addEventListener(Event.COMPLETE, function(e) {
+        trace("complete event");
+        if (next != null) {
+            trace("");
+            next();
+        }
+    });
+    file.addEventListener(DataEvent.UPLOAD_COMPLETE_DATA, function(e) {
+        trace("uploadCompleteData event: " + e.data);
+    });
+    file.addEventListener(IOErrorEvent.IO_ERROR, function(e) {
+        trace("ioError event: " + e.text + " (" + e.errorID + ")");
+        if (next != null) {
+            trace("");
+            next();
+        }
+    });
+}
+
+function download(url, next) {
+    trace("// download " + url);
+    var file = new FileReference();
+    listen(file, next);
+    file.download(new URLRequest(url), "debug-success.txt");
+}
+
+function downloadChunked() {
+    download("http://example.com/file.txt?debug-chunked", downloadMidstreamError);
+}
+
+function downloadMidstreamError() {
+    download("http://example.com/file.txt?debug-error-midstream", downloadDnsError);
+}
+
+function downloadDnsError() {
+    download("http://example.com/file.txt?debug-error-dns", upload);
+}
+
+function upload() {
+    trace("// upload");
+    var file = new FileReference();
+    var failedUpload = false;
+    listen(file, null);
+    file.addEventListener(Event.SELECT, function(e) {
+        var request = new URLRequest("http://example.com/upload?debug-success");
+        request.method = URLRequestMethod.POST;
+        var variables = new URLVariables();
+        variables.id = "42";
+        request.data = variables;
+        file.upload(request, "document");
+    });
+    file.addEventListener(DataEvent.UPLOAD_COMPLETE_DATA, function(e) {
+        if (!failedUpload) {
+            failedUpload = true;
+            trace("");
+            trace("// upload with an error status");
+            file.upload(new URLRequest("http://example.com/upload?debug-error-statuscode"));
+        }
+    });
+    file.browse([new FileFilter("debug-select-success", "*.txt")]);
+}
+
+downloadChunked();
@@ -0,0 +1,127 @@
+package {
+	import flash.display.Sprite;
+	import flash.sampler.*;
+
+	public class Test extends Sprite {
+		public function Test() {
+			trace("// getSize");
+			trace(getSize(null));
+			trace(getSize(true));
+			trace(getSize(1));
+			trace(getSize(1.5));
+			trace(getSize("abc"));
+			trace(getSize(new Object()));
+			trace(getSize({x: 1, y: 2}));
+			trace(getSize([1, 2, 3]));
+			trace(getSize(new Foo()));
+
+			trace("// getMemberNames");
+			trace(memberNames(getMemberNames(new Foo())));
+			trace(memberNames(getMemberNames(Foo)));
+			trace(memberNames(getMemberNames(Foo, true)));
+
+			trace("// Nothing is recorded before sampling starts");
+			var before:Foo = makeFoo();
+			before.bar();
+			trace(getSampleCount());
+			trace(getInvocationCount(before, new QName("", "bar")));
+
+			startSampling();
+			var f1:Foo = makeFoo();
+			var f2:Foo = makeFoo();
+			f1.bar();
+			f1.bar();
+			f2.bar();
+			var value:int = f1.baz;
+			f1.baz = value + 1;
+
+			pauseSampling();
+			var paused:Foo = makeFoo();
+			f1.bar();
+
+			startSampling();
+			f1.bar();
+			pauseSampling();
+
+			trace("// getSamples");
+			var fooSamples:Array = [];
+			for each (var sample:Sample in getSamples()) {
+				var newObject:NewObjectSample = sample as NewObjectSample;
+				if (newObject && newObject.type == Foo) {
+					fooSamples.push(newObject);
+				}
+			}
+			trace(fooSamples.length);
+			trace(fooSamples[0].id != fooSamples[1].id);
+			trace(fooSamples[0].object === f1);
+			trace(fooSamples[1].object === f2);
+			trace(fooSamples[0].size);
+			trace(fooSamples[0].stack[0]);
+			trace(fooSamples[0].stack[1]);
+
+			trace("// getInvocationCount");
+			trace(getInvocationCount(f1, new QName("", "bar")));
+			trace(getInvocationCount(this, new QName("", "makeFoo")));
+			trace(getInvocationCount(Foo, null));
+			trace(getInvocationCount(f1, new QName("", "missing")));
+			trace(getGetterInvocationCount(f1, new QName("", "baz")));
+			trace(getSetterInvocationCount(f1, new QName("", "baz")));
+			trace(isGetterSetter(f1, new QName("", "baz")));
+			trace(isGetterSetter(f1, new QName("", "bar")));
+
+			trace("// clearSamples");
+			clearSamples();
+			trace(getSampleCount());
+
+			trace("// setSamplerCallback");
+			var callbacks:int = 0;
+			setSamplerCallback(function():void {
+				callbacks++;
+				clearSamples();
+			});
+			startSampling();
+			for (var i:int = 0; i < 10000; i++) {
+				new Foo();
+			}
+			pauseSampling();
+			trace(callbacks);
+
+			trace("// stopSampling");
+			stopSampling();
+			trace(getSampleCount());
+			trace(getInvocationCount(f1, new QName("", "bar")));
+		}
+
+		public function makeFoo():Foo {
+			return new Foo();
+		}
+
+		private function memberNames(names:Object):String {
+			var result:Array = [];
+			for each (var name:QName in names) {
+				if (["a", "b", "bar", "baz", "s"].indexOf(name.localName) != -1) {
+					result.push(name.localName);
+				}
+			}
+			return result.sort().join(",");
+		}
+	}
+}
+
+class Foo {
+	public static var s:int;
+
+	public var a:int;
+	public var b:String;
+
+	public function bar():void {
+	}
+
+	public function get baz():int {
+		return a;
+	}
+
+	public function set baz(value:int):void {
+		a = value;
+	}
+}
@@ -0,0 +1,200 @@
+package {
+	import flash.display.Sprite;
+	import flash.globalization.*;
+
+	public class Test extends Sprite {
+		public function Test() {
+			testLocales();
+			testNumberFormatter();
+			testCurrencyFormatter();
+			testDateTimeFormatter();
+			testCollator();
+		}
+
+		private function show(value:String):String {
+			return value.split("\u00a0").join("<nbsp>");
+		}
+
+		private function testLocales():void {
+			trace("// Locale resolution");
+			for each (var name:String in [LocaleID.DEFAULT, "de-DE", "de_DE", "en-AU", "xx-YY"]) {
+				var formatter:NumberFormatter = new NumberFormatter(name);
+				trace(name + ": " + formatter.requestedLocaleIDName + " -> " + formatter.actualLocaleIDName + ", " + formatter.lastOperationStatus);
+			}
+			trace(NumberFormatter.getAvailableLocaleIDNames().indexOf("ja-JP") != -1);
+		}
+
+		private function testNumberFormatter():void {
+			trace("// NumberFormatter");
+			var en:NumberFormatter = new NumberFormatter("en-US");
+			trace(en.decimalSeparator + " " + en.groupingSeparator + " " + en.groupingPattern + " " + en.negativeSymbol + " " + en.negativeNumberFormat);
+			trace(en.formatNumber(1234567.891));
+			trace(en.formatInt(-1234));
+			trace(en.formatUint(4000000000));
+			trace(en.formatNumber(-0.5));
+
+			en.negativeNumberFormat = 0;
+			trace(en.formatNumber(-1234.5));
+			en.negativeNumberFormat = 4;
+			trace(en.formatNumber(-1234.5));
+			en.negativeNumberFormat = 5;
+			trace(en.negativeNumberFormat);
+			en.negativeNumberFormat = 1;
+
+			en.leadingZero = false;
+			trace(en.formatNumber(0.5));
+			en.leadingZero = true;
+			en.trailingZeros = false;
+			trace(en.formatNumber(1.5));
+			en.trailingZeros = true;
+			en.fractionalDigits = 0;
+			trace(en.formatNumber(1234.4));
+			en.groupingPattern = "3;2;*";
+			trace(en.formatNumber(12345678));
+			en.groupingPattern = "3;*";
+			en.useGrouping = false;
+			trace(en.formatNumber(12345678));
+			en.useGrouping = true;
+			en.fractionalDigits = 2;
+			en.digitsType = NationalDigitsType.ARABIC_INDIC;
+			trace(en.formatNumber(123));
+			en.digitsType = NationalDigitsType.EUROPEAN;
+
+			var result:NumberParseResult = en.parse("  -1,234.5 ");
+			trace(result.value + " " + result.startIndex + " " + result.endIndex + " " + en.lastOperationStatus);
+			result = en.parse("(42)");
+			trace(result.value + " " + result.startIndex + " " + result.endIndex);
+			trace(en.parseNumber("abc") + " " + en.lastOperationStatus);
+			trace(en.parseNumber("1,000.25") + " " + en.lastOperationStatus);
+
+			var de:NumberFormatter = new NumberFormatter("de-DE");
+			trace(de.formatNumber(-1234.5));
+			trace(de.parseNumber("1.234,5"));
+			var fr:NumberFormatter = new NumberFormatter("fr-FR");
+			trace(show(fr.formatNumber(1234567.5)));
+		}
+
+		private function testCurrencyFormatter():void {
+			trace("// CurrencyFormatter");
+			var en:CurrencyFormatter = new CurrencyFormatter("en-US");
+			trace(en.currencyISOCode + " " + en.currencySymbol + " " + en.fractionalDigits + " " + en.positiveCurrencyFormat + " " + en.negativeCurrencyFormat);
+			trace(en.format(1234.5));
+			trace(en.format(1234.5, true));
+			trace(en.format(-1234.5, true));
+			en.negativeCurrencyFormat = 0;
+			trace(en.format(-1234.5, true));
+			en.positiveCurrencyFormat = 3;
+			trace(en.format(1234.5, true));
+			try {
+				en.positiveCurrencyFormat = 4;
+			} catch (e:ArgumentError) {
+				trace("positiveCurrencyFormat = 4: " + e.errorID);
+			}
+			try {
+				en.negativeCurrencyFormat = 16;
+			} catch (e:ArgumentError) {
+				trace("negativeCurrencyFormat = 16: " + e.errorID);
+			}
+
+			en = new CurrencyFormatter("en-US");
+			trace(en.formattingWithCurrencySymbolIsSafe("USD") + " " + en.formattingWithCurrencySymbolIsSafe("EUR"));
+			en.setCurrency("EUR", "€");
+			trace(en.format(2, true) + " " + en.formattingWithCurrencySymbolIsSafe("EUR"));
+
+			var result:CurrencyParseResult = en.parse("$1,234.50");
+			trace(result.value + " " + result.currencyString + " " + en.lastOperationStatus);
+			result = en.parse("(USD 12.25)");
+			trace(result.value + " " + result.currencyString);
+			result = en.parse("abc");
+			trace(result.value + " " + en.lastOperationStatus);
+
+			var de:CurrencyFormatter = new CurrencyFormatter("de-DE");
+			trace(de.format(-1234.5, true));
+			var br:CurrencyFormatter = new CurrencyFormatter("pt-BR");
+			trace(br.format(1234.5, true));
+			var ja:CurrencyFormatter = new CurrencyFormatter("ja-JP");
+			trace(ja.fractionalDigits + " " + ja.format(1234.4, true));
+		}
+
+		private function testDateTimeFormatter():void {
+			trace("// DateTimeFormatter");
+			var date:Date = new Date(Date.UTC(2024, 0, 5, 14, 7, 9, 42));
+
+			var en:DateTimeFormatter = new DateTimeFormatter("en-US");
+			trace(en.getDateStyle() + " " + en.getTimeStyle() + ": " + en.getDateTimePattern());
+			trace(en.formatUTC(date));
+			en.setDateTimeStyles(DateTimeStyle.MEDIUM, DateTimeStyle.NONE);
+			trace(en.formatUTC(date));
+			en.setDateTimeStyles(DateTimeStyle.SHORT, DateTimeStyle.SHORT);
+			trace(en.formatUTC(date));
+
+			en.setDateTimePattern("yyyy-MM-dd'T'HH:mm:ss.SSS Z");
+			trace(en.getDateStyle() + " " + en.getTimeStyle());
+			trace(en.formatUTC(date));
+			en.setDateTimePattern("'It''s' QQQ, 'week' w, D, E EEEEE, K k");
+			trace(en.formatUTC(date));
+
+			trace(en.formatUTC(new Date(NaN)) == "");
+			trace(en.lastOperationStatus);
+
+			try {
+				en.setDateTimeStyles("bogus", DateTimeStyle.LONG);
+			} catch (e:ArgumentError) {
+				trace("setDateTimeStyles(bogus): " + e.errorID);
+			}
+
+			trace(new DateTimeFormatter("de-DE", DateTimeStyle.LONG, DateTimeStyle.SHORT).formatUTC(date));
+			trace(new DateTimeFormatter("ja-JP", DateTimeStyle.MEDIUM, DateTimeStyle.MEDIUM).formatUTC(date));
+			trace(new DateTimeFormatter("fr-FR", DateTimeStyle.NONE, DateTimeStyle.LONG).formatUTC(date));
+
+			trace(en.getMonthNames(DateTimeNameStyle.SHORT_ABBREVIATION));
+			var pl:DateTimeFormatter = new DateTimeFormatter("pl-PL");
+			trace(pl.getMonthNames(DateTimeNameStyle.FULL, DateTimeNameContext.FORMAT)[0]);
+			trace(pl.getMonthNames(DateTimeNameStyle.FULL, DateTimeNameContext.STANDALONE)[0]);
+			trace(new DateTimeFormatter("fr-FR").getWeekdayNames(DateTimeNameStyle.LONG_ABBREVIATION));
+			trace(en.getFirstWeekday() + " " + new DateTimeFormatter("en-GB").getFirstWeekday());
+		}
+
+		private function testCollator():void {
+			trace("// Collator");
+			var sorting:Collator = new Collator("en-US");
+			trace(sorting.ignoreCase + " " + sorting.ignoreDiacritics + " " + sorting.numericComparison);
+			trace(sorting.compare("a", "B") + " " + sorting.compare("a", "A") + " " + sorting.compare("A", "a"));
+			trace(sorting.compare("résumé", "resume"));
+			trace(sorting.compare("file10", "file9"));
+			sorting.numericComparison = true;
+			trace(sorting.compare("file10", "file9"));
+			sorting.numericComparison = false;
+			trace(sorting.compare("a-b", "ab"));
+			sorting.ignoreSymbols = true;
+			trace(sorting.compare("a-b", "ab"));
+			sorting.ignoreSymbols = false;
+			trace(sorting.compare("Ａ", "A"));
+			sorting.ignoreCharacterWidth = true;
+			trace(sorting.compare("Ａ", "A"));
+			sorting.ignoreCharacterWidth = false;
+			trace(sorting.compare("カ", "か"));
+			sorting.ignoreKanaType = true;
+			trace(sorting.compare("カ", "か"));
+			sorting.ignoreKanaType = false;
+			sorting.ignoreCase = true;
+			trace(sorting.compare("e", "É"));
+			sorting.ignoreCase = false;
+
+			var words:Array = ["b", "A", "a", "é", "e", "B"];
+			words.sort(sorting.compare);
+			trace(words);
+
+			var matching:Collator = new Collator("en-US", CollatorMode.MATCHING);
+			trace(matching.ignoreCase + " " + matching.ignoreDiacritics + " " + matching.ignoreCharacterWidth + " " + matching.ignoreKanaType);
+			trace(matching.equals("Résumé", "resume") + " " + matching.equals("ＡBC", "abc") + " " + matching.equals("abc", "abd"));
+			trace(matching.lastOperationStatus);
+
+			try {
+				new Collator("en-US", "bogus");
+			} catch (e:ArgumentError) {
+				trace("new Collator(bogus): " + e.errorID);
+			}
+		}
+	}
+}
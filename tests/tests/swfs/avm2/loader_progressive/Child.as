@@ -0,0 +1,17 @@
+package {
+	import flash.display.MovieClip;
+
+	public class Child extends MovieClip {
+		public function Child() {
+			addFrameScript(0, frame, 1, frame, 2, frame, 3, lastFrame);
+		}
+
+		private function frame():void {
+			trace("Child frame " + currentFrame + ", still downloading: " + (loaderInfo.bytesLoaded < loaderInfo.bytesTotal));
+		}
+
+		private function lastFrame():void {
+			stop();
+		}
+	}
+}
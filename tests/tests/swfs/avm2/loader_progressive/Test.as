@@ -0,0 +1,32 @@
+package {
+	import flash.display.Loader;
+	import flash.display.MovieClip;
+	import flash.events.Event;
+	import flash.events.ProgressEvent;
+	import flash.net.URLRequest;
+
+	public class Test extends MovieClip {
+		private var loader:Loader = new Loader();
+		private var progressEvents:int = 0;
+
+		public function Test() {
+			addChild(loader);
+			loader.contentLoaderInfo.addEventListener(ProgressEvent.PROGRESS, onProgress);
+			loader.contentLoaderInfo.addEventListener(Event.COMPLETE, onComplete);
+			// The child arrives in chunks of 1 KiB, one per frame.
+			loader.load(new URLRequest("child.swf?debug-slow"));
+		}
+
+		private function onProgress(e:ProgressEvent):void {
+			progressEvents++;
+		}
+
+		private function onComplete(e:Event):void {
+			var content:MovieClip = MovieClip(loader.content);
+			trace("complete");
+			trace("More than one progress event: " + (progressEvents > 1));
+			trace("framesLoaded: " + content.framesLoaded + " of " + content.totalFrames);
+			trace("bytesLoaded == bytesTotal: " + (loader.contentLoaderInfo.bytesLoaded == loader.contentLoaderInfo.bytesTotal));
+		}
+	}
+}
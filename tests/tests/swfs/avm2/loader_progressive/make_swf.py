@@ -0,0 +1,62 @@
+#!/usr/bin/env python3
+"""Builds child.swf from Child.swf, which is compiled from Child.as first.
+
+The child has four frames, each of which starts with 8 KiB of binary data,
+so that it takes several frames to download every one of them when loaded
+with the "?debug-slow" hint of the test navigator.
+"""
+
+import struct
+
+NUM_FRAMES = 4
+DATA_SIZE = 8 * 1024
+
+
+def tag(code, body):
+    if len(body) < 0x3F:
+        return struct.pack("<H", code << 6 | len(body)) + body
+    return struct.pack("<HI", code << 6 | 0x3F, len(body)) + body
+
+
+def define_binary_data(id):
+    return tag(87, struct.pack("<HI", id, 0) + bytes(DATA_SIZE))
+
+
+def read_tags(data):
+    tags, pos = [], 0
+    while pos < len(data):
+        (code_and_length,) = struct.unpack_from("<H", data, pos)
+        start, length = pos + 2, code_and_length & 0x3F
+        if length == 0x3F:
+            (length,) = struct.unpack_from("<I", data, start)
+            start += 4
+        tags.append((code_and_length >> 6, data[pos : start + length]))
+        pos = start + length
+    return tags
+
+
+def main():
+    with open("Child.swf", "rb") as f:
+        data = f.read()
+    assert data[:3] == b"FWS", "Child.swf should be uncompressed"
+    header_length = 8 + ((5 + 4 * (data[8] >> 3) + 7) // 8) + 4
+    tags = read_tags(data[header_length:])
+
+    # The frame count is the last field of the header.
+    body = data[8 : header_length - 2] + struct.pack("<H", NUM_FRAMES)
+    for code, tag_data in tags:
+        if code == 1:
+            body += define_binary_data(1)
+        if code not in (0, 1):
+            body += tag_data
+    body += tag(1, b"")
+    for frame in range(2, NUM_FRAMES + 1):
+        body += define_binary_data(frame) + tag(1, b"")
+    body += tag(0, b"")
+    swf = data[:4] + struct.pack("<I", 8 + len(body)) + body
+    with open("child.swf", "wb") as f:
+        f.write(swf)
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,103 @@
+package {
+    import flash.display.BitmapData;
+    import flash.display.Sprite;
+    import flash.events.Event;
+    import flash.events.MouseEvent;
+    import flash.geom.Point;
+    import flash.ui.Mouse;
+    import flash.ui.MouseCursorData;
+
+    public class Test extends Sprite {
+        private var rolledOver:int = 0;
+        private var pendingRollOver:Boolean = false;
+        private var pendingRollOut:Boolean = false;
+
+        public function Test() {
+            trace("supportsCursor: " + Mouse.supportsCursor);
+            trace("supportsNativeCursor: " + Mouse.supportsNativeCursor);
+            trace("cursor: " + Mouse.cursor);
+
+            setCursor("button");
+            setCursor("hand");
+            setCursor("ibeam");
+            setCursor("wait");
+
+            trace("// registerCursor without bitmaps");
+            try {
+                Mouse.registerCursor("empty", new MouseCursorData());
+            } catch (e:Error) {
+                trace(e);
+            }
+
+            trace("// registerCursor(\"custom\") with 2 frames");
+            var custom:MouseCursorData = new MouseCursorData();
+            custom.data = Vector.<BitmapData>([
+                new BitmapData(2, 2, true, 0xFFFF0000),
+                new BitmapData(2, 2, true, 0xFF00FF00)
+            ]);
+            custom.hotSpot = new Point(1, 1);
+            custom.frameRate = 10;
+            Mouse.registerCursor("custom", custom);
+            setCursor("custom");
+
+            trace("// registerCursor(\"custom\") again while it's in use");
+            var replaced:MouseCursorData = new MouseCursorData();
+            replaced.data = Vector.<BitmapData>([new BitmapData(3, 1, true, 0xFF0000FF)]);
+            Mouse.registerCursor("custom", replaced);
+            trace("cursor: " + Mouse.cursor);
+
+            trace("// unregisterCursor(\"custom\") while it's in use");
+            Mouse.unregisterCursor("custom");
+            trace("cursor: " + Mouse.cursor);
+            setCursor("custom");
+
+            var custom2:MouseCursorData = new MouseCursorData();
+            custom2.data = Vector.<BitmapData>([new BitmapData(1, 1, true, 0xFFFFFFFF)]);
+            Mouse.registerCursor("custom2", custom2);
+
+            var button:Sprite = new Sprite();
+            button.graphics.beginFill(0x0000FF);
+            button.graphics.drawRect(100, 100, 100, 100);
+            button.graphics.endFill();
+            button.buttonMode = true;
+            button.addEventListener(MouseEvent.ROLL_OVER, function(e:MouseEvent):void {
+                trace("// rollOver");
+                pendingRollOver = true;
+            });
+            button.addEventListener(MouseEvent.ROLL_OUT, function(e:MouseEvent):void {
+                trace("// rollOut");
+                pendingRollOut = true;
+            });
+            addChild(button);
+
+            addEventListener(Event.ENTER_FRAME, onEnterFrame);
+        }
+
+        private function setCursor(name:String):void {
+            trace("// cursor = \"" + name + "\"");
+            try {
+                Mouse.cursor = name;
+            } catch (e:Error) {
+                trace(e);
+            }
+            trace("cursor: " + Mouse.cursor);
+        }
+
+        private function onEnterFrame(e:Event):void {
+            if (pendingRollOver) {
+                pendingRollOver = false;
+                rolledOver++;
+                if (rolledOver == 1) {
+                    // The cursor of the button isn't shown while the cursor is overridden.
+                    setCursor("arrow");
+                } else {
+                    setCursor("auto");
+                }
+            }
+            if (pendingRollOut) {
+                pendingRollOut = false;
+                setCursor("custom2");
+            }
+        }
+    }
+}
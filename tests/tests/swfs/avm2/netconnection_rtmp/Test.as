@@ -0,0 +1,35 @@
+package
+{
+    import flash.display.Sprite;
+
+    public class Test extends Sprite
+    {
+    }
+}
+
+import flash.events.NetStatusEvent;
+import flash.net.NetConnection;
+import flash.net.Responder;
+
+var connection:NetConnection = new NetConnection();
+
+connection.addEventListener(NetStatusEvent.NET_STATUS, function(event:NetStatusEvent):void
+{
+    trace("Event handler: " + event.info.code + " (" + event.info.level + ")");
+    trace("  connection.connected = " + connection.connected);
+    if (event.info.code == "NetConnection.Connect.Success") {
+        trace("  info.description = " + event.info.description);
+        trace("  info.objectEncoding = " + event.info.objectEncoding + " (" + typeof event.info.objectEncoding + ")");
+        connection.call("echo", new Responder(function(result:*):void
+        {
+            trace("Result handler: " + result);
+            connection.close();
+            trace("After close: connection.connected = " + connection.connected);
+        }), "Hello!");
+    }
+});
+
+trace("connection.objectEncoding = " + connection.objectEncoding);
+trace("Before call: connection.connected = " + connection.connected);
+connection.connect("rtmp://localhost/live");
+trace("After call: connection.connected = " + connection.connected);
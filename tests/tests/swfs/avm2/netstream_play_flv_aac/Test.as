@@ -0,0 +1,55 @@
+package
+{
+    import flash.display.Sprite;
+    import flash.events.Event;
+    import flash.events.NetStatusEvent;
+    import flash.media.SoundMixer;
+    import flash.net.NetConnection;
+    import flash.net.NetStream;
+    import flash.utils.ByteArray;
+
+    // tone.flv holds three seconds of a 1 kHz AAC-LC sine tone, at about -9 dBFS.
+    public class Test extends Sprite
+    {
+        private var stream:NetStream;
+        private var samples:ByteArray = new ByteArray();
+        private var loudFrames:int = 0;
+
+        public function Test()
+        {
+            var connection:NetConnection = new NetConnection();
+            connection.connect(null);
+
+            stream = new NetStream(connection);
+            stream.client = {};
+            stream.addEventListener(NetStatusEvent.NET_STATUS, onNetStatus);
+            stream.play("tone.flv");
+
+            addEventListener(Event.ENTER_FRAME, onEnterFrame);
+        }
+
+        private function onNetStatus(event:NetStatusEvent):void
+        {
+            if (event.info.code == "NetStream.Play.Start") {
+                trace("netStatus: " + event.info.code);
+            }
+        }
+
+        private function onEnterFrame(event:Event):void
+        {
+            SoundMixer.computeSpectrum(samples, false, 0);
+            var peak:Number = 0;
+            for (var i:int = 0; i < 512; i++) {
+                peak = Math.max(peak, Math.abs(samples.readFloat()));
+            }
+
+            // Wait for the tone to be fully faded in before measuring it.
+            if (peak > 0.3) {
+                loudFrames++;
+                if (loudFrames == 2) {
+                    trace("Tone peak: " + Math.round(peak * 100) / 100);
+                }
+            }
+        }
+    }
+}
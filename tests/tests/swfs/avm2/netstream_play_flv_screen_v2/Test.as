@@ -0,0 +1,26 @@
+package {
+	import flash.display.Sprite;
+	import flash.events.NetStatusEvent;
+	import flash.media.Video;
+	import flash.net.NetConnection;
+	import flash.net.NetStream;
+
+	public class Test extends Sprite {
+		public function Test() {
+			var connection:NetConnection = new NetConnection();
+			connection.connect(null);
+
+			var stream:NetStream = new NetStream(connection);
+			stream.client = {};
+			stream.addEventListener(NetStatusEvent.NET_STATUS, function(event:NetStatusEvent):void {
+				trace(event.info.code);
+			});
+
+			var video:Video = new Video(48, 32);
+			video.attachNetStream(stream);
+			addChild(video);
+
+			stream.play("screen2.flv");
+		}
+	}
+}
@@ -0,0 +1,165 @@
+"""Generates screen2.flv, four Screen Video V2 frames, and the final frame as
+output.expected.png.
+
+The frames exercise a custom palette, both color depths, a diff block, an
+I-frame image and blocks primed with earlier data.
+"""
+
+import struct
+import zlib
+
+W, H, BLOCK = 48, 32, 16
+BLOCKS_PER_ROW = W // BLOCK
+
+DEFAULT_PALETTE_25 = 0xFFFF00
+CUSTOM_PALETTE = [0x112233, 0x8040C0]
+
+
+def compress(data, priming=None):
+    """Compress a block, optionally as a continuation of `priming`."""
+    c = zlib.compressobj()
+    if priming is not None:
+        # The encoder flushes the priming data and throws it away.
+        c.compress(priming)
+        c.flush(zlib.Z_SYNC_FLUSH)
+    return c.compress(data) + c.flush()
+
+
+def block(compressed, depth=0, diff=None, prime_previous=False, prime_current=None):
+    flags = depth << 3
+    extra = b""
+    if diff is not None:
+        flags |= 0x4
+        extra += bytes(diff)
+    if prime_current is not None:
+        flags |= 0x2
+        extra += bytes(prime_current)
+    if prime_previous:
+        flags |= 0x1
+    payload = bytes([flags]) + extra + compressed
+    return struct.pack(">H", len(payload)) + payload
+
+
+EMPTY = struct.pack(">H", 0)
+
+
+def frame(keyframe, flags, blocks):
+    out = bytes([(1 if keyframe else 2) << 4 | 6])
+    out += struct.pack(">HH", (BLOCK // 16 - 1) << 12 | W, (BLOCK // 16 - 1) << 12 | H)
+    out += bytes([flags])
+    return out + b"".join(blocks)
+
+
+def solid(bgr, rows=BLOCK):
+    return bytes(bgr) * (BLOCK * rows)
+
+
+def hybrid_rgb15(r, g, b):
+    color = (r << 10) | (g << 5) | b
+    return bytes([0x80 | color >> 8, color & 0xFF])
+
+
+# Block contents, as stored: rows go bottom-up, pixels are BGR. Blocks 0-2
+# are the bottom row of the image.
+red = solid([0, 0, 255])
+palette_halves = bytes((0 if x < 8 else 1) for y in range(BLOCK) for x in range(BLOCK))
+green15 = hybrid_rgb15(0, 31, 0) * (BLOCK * BLOCK)
+gradient = b"".join(bytes([x * 16, y * 16, 128]) for y in range(BLOCK) for x in range(BLOCK))
+blue = solid([255, 0, 0])
+default_yellow = bytes([25]) * (BLOCK * BLOCK)
+black_rows = solid([0, 0, 0], rows=4)
+cyan = solid([255, 255, 0])
+magenta = solid([255, 0, 255])
+half_white = b"".join(
+    bytes([255, 0, 255] if x < 8 else [255, 255, 255]) for y in range(BLOCK) for x in range(BLOCK)
+)
+
+palette = b"".join(bytes([c & 0xFF, c >> 8 & 0xFF, c >> 16]) for c in CUSTOM_PALETTE)
+
+frames = [
+    # A keyframe with a custom palette.
+    frame(True, 0x1, [
+        block(compress(palette)),
+        block(compress(red)),
+        block(compress(palette_halves), depth=2),
+        block(compress(green15), depth=2),
+        block(compress(gradient)),
+        block(compress(blue)),
+        block(compress(default_yellow), depth=2),
+    ]),
+    # Rows 4 to 7 of block 0 turn black, primed with the keyframe's block.
+    frame(False, 0, [
+        block(compress(black_rows, priming=red), diff=[4, 4], prime_previous=True),
+    ] + [EMPTY] * 5),
+    # Nothing visible changes, but an I-frame image replaces block 4's
+    # priming data.
+    frame(False, 0x2, [EMPTY] * 6 + [EMPTY] * 4 + [block(compress(cyan)), EMPTY]),
+    # Block 4 is primed with the I-frame image, and block 5 with block 4.
+    frame(False, 0, [EMPTY] * 4 + [
+        block(compress(magenta, priming=cyan), prime_previous=True),
+        block(compress(half_white, priming=magenta), prime_current=[1, 1]),
+    ]),
+]
+
+flv = b"FLV\x01\x01" + struct.pack(">I", 9) + struct.pack(">I", 0)
+for index, data in enumerate(frames):
+    timestamp = index * 100
+    tag = bytes([9]) + struct.pack(">I", len(data))[1:]
+    tag += struct.pack(">I", timestamp)[1:] + bytes([timestamp >> 24]) + b"\0\0\0"
+    tag += data
+    flv += tag + struct.pack(">I", len(tag))
+open("screen2.flv", "wb").write(flv)
+
+
+# The expected picture, built independently from the block contents above.
+def to_bgr(data, depth, palette):
+    if depth == 0:
+        return data
+    out = b""
+    i = 0
+    while i < len(data):
+        if data[i] & 0x80:
+            color = (data[i] & 0x7F) << 8 | data[i + 1]
+            expand = lambda c: (c << 3 | c >> 2) & 0xFF
+            out += bytes([expand(color & 0x1F), expand(color >> 5 & 0x1F), expand(color >> 10)])
+            i += 2
+        else:
+            c = palette[data[i]]
+            out += bytes([c & 0xFF, c >> 8 & 0xFF, c >> 16])
+            i += 1
+    return out
+
+
+palette_table = {0: CUSTOM_PALETTE[0], 1: CUSTOM_PALETTE[1], 25: DEFAULT_PALETTE_25}
+blocks = [
+    to_bgr(red, 0, palette_table),
+    to_bgr(palette_halves, 2, palette_table),
+    to_bgr(green15, 2, palette_table),
+    gradient,
+    blue,
+    to_bgr(default_yellow, 2, palette_table),
+]
+blocks[0] = blocks[0][: 4 * BLOCK * 3] + black_rows + blocks[0][8 * BLOCK * 3 :]
+blocks[4] = magenta
+blocks[5] = half_white
+
+rows = []
+for y in range(H - 1, -1, -1):
+    block_row, row_in_block = divmod(y, BLOCK)
+    row = b""
+    for bx in range(BLOCKS_PER_ROW):
+        data = blocks[block_row * BLOCKS_PER_ROW + bx]
+        bgr = data[row_in_block * BLOCK * 3 : (row_in_block + 1) * BLOCK * 3]
+        row += b"".join(bytes([bgr[i + 2], bgr[i + 1], bgr[i], 255]) for i in range(0, len(bgr), 3))
+    rows.append(row)
+
+
+def png_chunk(kind, data):
+    return struct.pack(">I", len(data)) + kind + data + struct.pack(">I", zlib.crc32(kind + data))
+
+
+png = b"\x89PNG\r\n\x1a\n"
+png += png_chunk(b"IHDR", struct.pack(">IIBBBBB", W, H, 8, 6, 0, 0, 0))
+png += png_chunk(b"IDAT", zlib.compress(b"".join(b"\0" + row for row in rows)))
+png += png_chunk(b"IEND", b"")
+open("output.expected.png", "wb").write(png)
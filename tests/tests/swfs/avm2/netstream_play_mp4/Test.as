@@ -0,0 +1,74 @@
+package
+{
+    import flash.display.Sprite;
+    import flash.events.Event;
+    import flash.events.NetStatusEvent;
+    import flash.net.NetConnection;
+    import flash.net.NetStream;
+
+    // video.mp4 holds ten 100 ms H.264 samples (with dummy data), with
+    // keyframes at 0 and 500 ms. broken.mp4 has a movie box without tracks.
+    public class Test extends Sprite
+    {
+        private var connection:NetConnection = new NetConnection();
+        private var video:NetStream;
+        private var broken:NetStream;
+        private var brokenFrames:int = -1;
+
+        public function Test()
+        {
+            connection.connect(null);
+
+            video = new NetStream(connection);
+            video.client = {onMetaData: onMetaData};
+            video.addEventListener(NetStatusEvent.NET_STATUS, onVideoStatus);
+            video.play("video.mp4");
+
+            addEventListener(Event.ENTER_FRAME, onEnterFrame);
+        }
+
+        private function onMetaData(info:Object):void
+        {
+            trace("onMetaData");
+            trace("  duration = " + info.duration);
+            trace("  moovposition = " + info.moovposition);
+            trace("  videocodecid = " + info.videocodecid);
+            trace("  avcprofile = " + info.avcprofile);
+            trace("  avclevel = " + info.avclevel);
+            trace("  width = " + info.width);
+            trace("  height = " + info.height);
+            trace("  videoframerate = " + info.videoframerate);
+            for each (var point:Object in info.seekpoints) {
+                trace("  seekpoint: time = " + point.time + ", offset = " + point.offset);
+            }
+        }
+
+        private function onVideoStatus(event:NetStatusEvent):void
+        {
+            trace("video.mp4: " + event.info.code);
+            if (event.info.code == "NetStream.Play.Stop") {
+                trace("  time = " + video.time);
+
+                broken = new NetStream(connection);
+                broken.client = {onMetaData: function(info:Object):void
+                {
+                    trace("broken.mp4: onMetaData");
+                }};
+                broken.addEventListener(NetStatusEvent.NET_STATUS, function(event:NetStatusEvent):void
+                {
+                    trace("broken.mp4: " + event.info.code);
+                });
+                broken.play("broken.mp4");
+                brokenFrames = 0;
+            }
+        }
+
+        private function onEnterFrame(event:Event):void
+        {
+            if (brokenFrames >= 0 && ++brokenFrames == 10) {
+                trace("broken.mp4: time = " + broken.time);
+                trace("broken.mp4: bytesLoaded = " + broken.bytesLoaded + ", bytesTotal = " + broken.bytesTotal);
+            }
+        }
+    }
+}
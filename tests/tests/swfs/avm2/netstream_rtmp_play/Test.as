@@ -0,0 +1,43 @@
+package
+{
+    import flash.display.Sprite;
+
+    public class Test extends Sprite
+    {
+    }
+}
+
+import flash.events.NetStatusEvent;
+import flash.net.NetConnection;
+import flash.net.NetStream;
+
+var connection:NetConnection = new NetConnection();
+var stream:NetStream;
+
+connection.addEventListener(NetStatusEvent.NET_STATUS, function(event:NetStatusEvent):void
+{
+    trace("NetConnection: " + event.info.code + " (" + event.info.level + ")");
+    if (event.info.code != "NetConnection.Connect.Success") {
+        return;
+    }
+
+    stream = new NetStream(connection);
+    stream.client = {
+        onMetaData: function(info:Object):void
+        {
+            trace("onMetaData: duration = " + info.duration + ", title = " + info.title);
+            connection.close();
+        }
+    };
+    stream.addEventListener(NetStatusEvent.NET_STATUS, function(event:NetStatusEvent):void
+    {
+        // Buffer events depend on how ticks line up with the data arriving.
+        if (event.info.code.indexOf("NetStream.Buffer.") != 0) {
+            trace("NetStream: " + event.info.code + " (" + event.info.level + ")");
+        }
+    });
+    stream.play("test");
+});
+
+trace("Connecting");
+connection.connect("rtmp://localhost/live");
@@ -0,0 +1,39 @@
+package
+{
+    import flash.display.Sprite;
+
+    public class Test extends Sprite
+    {
+    }
+}
+
+import flash.events.NetStatusEvent;
+import flash.net.NetConnection;
+import flash.net.NetStream;
+
+var connection:NetConnection = new NetConnection();
+var stream:NetStream;
+
+connection.addEventListener(NetStatusEvent.NET_STATUS, function(event:NetStatusEvent):void
+{
+    trace("NetConnection: " + event.info.code + " (" + event.info.level + ")");
+    if (event.info.code != "NetConnection.Connect.Success") {
+        return;
+    }
+
+    stream = new NetStream(connection);
+    stream.addEventListener(NetStatusEvent.NET_STATUS, function(event:NetStatusEvent):void
+    {
+        trace("NetStream: " + event.info.code + " (" + event.info.level + ")");
+        trace("  description = " + event.info.description);
+        if (event.info.code == "NetStream.Publish.Start") {
+            // Publishing null stops publishing and releases the stream.
+            stream.publish(null);
+            connection.close();
+        }
+    });
+    stream.publish("test", "record");
+});
+
+trace("Connecting");
+connection.connect("rtmp://localhost/live");
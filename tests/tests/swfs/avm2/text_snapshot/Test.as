@@ -0,0 +1,81 @@
+package {
+    import flash.display.Sprite;
+    import flash.text.TextSnapshot;
+
+    public class Test extends Sprite {
+        public function Test() {
+            trace("numChildren: " + numChildren);
+            trace("getChildAt(0): " + getChildAt(0));
+            trace("getChildAt(1): " + getChildAt(1));
+
+            var ts:TextSnapshot = textSnapshot;
+            trace("charCount: " + ts.charCount);
+
+            trace("// getText");
+            trace("getText(0, 100): " + ts.getText(0, 100));
+            trace("getText(0, 100, true): " + ts.getText(0, 100, true));
+            trace("getText(6, 11): " + ts.getText(6, 11));
+            trace("getText(5, 2): " + ts.getText(5, 2));
+            trace("getText(-5, 3): " + ts.getText(-5, 3));
+
+            trace("// findText");
+            trace("findText(0, \"world\", false): " + ts.findText(0, "world", false));
+            trace("findText(0, \"world\", true): " + ts.findText(0, "world", true));
+            trace("findText(0, \"World\", true): " + ts.findText(0, "World", true));
+            trace("findText(0, \"o\", false): " + ts.findText(0, "o", false));
+            trace("findText(5, \"o\", false): " + ts.findText(5, "o", false));
+            trace("findText(0, \"quiz\", false): " + ts.findText(0, "quiz", false));
+            trace("findText(0, \"\", false): " + ts.findText(0, "", false));
+            trace("findText(30, \"Quiz\", false): " + ts.findText(30, "Quiz", false));
+
+            trace("// hitTestTextNearPos");
+            trace("hitTestTextNearPos(15, 30): " + ts.hitTestTextNearPos(15, 30));
+            trace("hitTestTextNearPos(25, 50): " + ts.hitTestTextNearPos(25, 50));
+            trace("hitTestTextNearPos(300, 300): " + ts.hitTestTextNearPos(300, 300));
+            trace("hitTestTextNearPos(135, 30, 10): " + ts.hitTestTextNearPos(135, 30, 10));
+            trace("hitTestTextNearPos(135, 30, 20): " + ts.hitTestTextNearPos(135, 30, 20));
+            trace("hitTestTextNearPos(50, 120): " + ts.hitTestTextNearPos(50, 120));
+
+            trace("// setSelected");
+            ts.setSelected(6, 11, true);
+            trace("getSelected(0, 5): " + ts.getSelected(0, 5));
+            trace("getSelected(4, 7): " + ts.getSelected(4, 7));
+            trace("getSelectedText(): " + ts.getSelectedText());
+            ts.setSelected(22, 26, true);
+            trace("getSelectedText(false): " + ts.getSelectedText(false));
+            trace("getSelectedText(true): " + ts.getSelectedText(true));
+            trace("getSelected(0, 100) from a new snapshot: " + textSnapshot.getSelected(0, 100));
+            ts.setSelectColor(0xFF0000);
+
+            trace("// getTextRunInfo(11, 12)");
+            traceRun(ts.getTextRunInfo(11, 12)[0]);
+            trace("// getTextRunInfo(23, 24)");
+            traceRun(ts.getTextRunInfo(23, 24)[0]);
+            trace("getTextRunInfo(0, 100).length: " + ts.getTextRunInfo(0, 100).length);
+            trace("getTextRunInfo(5, 2).length: " + ts.getTextRunInfo(5, 2).length);
+
+            ts.setSelected(0, 100, false);
+            trace("getSelectedText() after deselecting: " + ts.getSelectedText());
+            trace("getSelected(0, 100) after deselecting: " + ts.getSelected(0, 100));
+
+            trace("// A container without static text");
+            var empty:TextSnapshot = new Sprite().textSnapshot;
+            trace("charCount: " + empty.charCount);
+            trace("getText(0, 10): " + empty.getText(0, 10));
+            trace("findText(0, \"Quiz\", false): " + empty.findText(0, "Quiz", false));
+        }
+
+        private function traceRun(run:Object):void {
+            var names:Array = [
+                "indexInRun", "selected", "font", "color", "height",
+                "matrix_a", "matrix_b", "matrix_c", "matrix_d", "matrix_tx", "matrix_ty"
+            ];
+            for (var i:int = 0; i < 4; i++) {
+                names.push("corner" + i + "x", "corner" + i + "y");
+            }
+            for each (var name:String in names) {
+                trace(name + ": " + run[name]);
+            }
+        }
+    }
+}
@@ -0,0 +1,178 @@
+#!/usr/bin/env python3
+"""Builds test.swf from Test.swf, as static text can't be authored without Flash.
+
+Test.swf is compiled from Test.as first, and the static texts are added to
+its first frame.
+
+The movie has two static texts on its root timeline, using a font whose
+glyphs have no outlines, as the test only reads the text:
+
+* depth 1, at (10, 20): "Hello World" in black, with "Second line" in red
+  on the line below it.
+* depth 2, at (10, 100), scaled twice: "Quiz" in blue.
+
+The glyphs are 10 pixels wide and 20 pixels high, with an ascent of 15 pixels.
+"""
+
+import struct
+
+LINES = [
+    # (text, color, y offset of the baseline in twips)
+    ("Hello World", 0x000000, 300),
+    ("Second line", 0xFF0000, 700),
+]
+QUIZ = ("Quiz", 0x0000FF, 300)
+CHARS = sorted(set("".join(text for text, _, _ in LINES + [QUIZ])))
+
+FONT_ID = 1
+FONT_SIZE = 400
+ADVANCE = 200
+
+
+class Bits:
+    def __init__(self):
+        self.bits = []
+
+    def ubits(self, value, n):
+        self.bits += [(value >> i) & 1 for i in reversed(range(n))]
+
+    def sbits(self, value, n):
+        self.ubits(value & ((1 << n) - 1), n)
+
+    def bytes(self):
+        bits = self.bits + [0] * (-len(self.bits) % 8)
+        return bytes(
+            int("".join(map(str, bits[i : i + 8])), 2) for i in range(0, len(bits), 8)
+        )
+
+
+def tag(code, body):
+    if len(body) < 0x3F:
+        return struct.pack("<H", code << 6 | len(body)) + body
+    return struct.pack("<HI", code << 6 | 0x3F, len(body)) + body
+
+
+def rect(x_min, x_max, y_min, y_max):
+    bits = Bits()
+    bits.ubits(16, 5)
+    for value in (x_min, x_max, y_min, y_max):
+        bits.sbits(value, 16)
+    return bits.bytes()
+
+
+def matrix(scale, tx, ty):
+    bits = Bits()
+    if scale != 1:
+        bits.ubits(1, 1)
+        bits.ubits(20, 5)
+        bits.sbits(int(scale * 65536), 20)
+        bits.sbits(int(scale * 65536), 20)
+    else:
+        bits.ubits(0, 1)
+    bits.ubits(0, 1)
+    bits.ubits(16, 5)
+    bits.sbits(tx, 16)
+    bits.sbits(ty, 16)
+    return bits.bytes()
+
+
+def define_font_2():
+    # Every glyph is an empty shape: one fill bit, no line bits, and an end record.
+    shapes = [bytes([0x10, 0x00])] * len(CHARS)
+    offsets_size = 2 * (len(CHARS) + 1)
+    offsets, offset = [], offsets_size
+    for shape in shapes:
+        offsets.append(offset)
+        offset += len(shape)
+
+    name = b"TestFont"
+    body = struct.pack("<HBBB", FONT_ID, 0x80 | 0x04, 1, len(name)) + name
+    body += struct.pack("<H", len(CHARS))
+    body += b"".join(struct.pack("<H", o) for o in offsets)
+    body += struct.pack("<H", offset)
+    body += b"".join(shapes)
+    body += b"".join(struct.pack("<H", ord(c)) for c in CHARS)
+    # Layout: an ascent of 768 and a descent of 256 in the 1024 EM square.
+    body += struct.pack("<hhh", 768, 256, 0)
+    body += b"".join(struct.pack("<h", ADVANCE) for _ in CHARS)
+    body += b"".join(rect(0, 0, 0, 0) for _ in CHARS)
+    body += struct.pack("<H", 0)
+    return tag(48, body)
+
+
+def define_text(id, lines):
+    body = struct.pack("<H", id)
+    body += rect(0, ADVANCE * 11, 0, 800)
+    body += matrix(1, 0, 0)
+    body += bytes([8, 16])
+    for i, (text, color, y) in enumerate(lines):
+        flags = 0x80 | 0x04 | 0x02 | 0x01
+        if i == 0:
+            flags |= 0x08
+        body += bytes([flags])
+        if i == 0:
+            body += struct.pack("<H", FONT_ID)
+        body += bytes([color >> 16, (color >> 8) & 0xFF, color & 0xFF])
+        body += struct.pack("<hh", 0, y)
+        if i == 0:
+            body += struct.pack("<H", FONT_SIZE)
+        body += bytes([len(text)])
+        bits = Bits()
+        for c in text:
+            bits.ubits(CHARS.index(c), 8)
+            bits.sbits(ADVANCE, 16)
+        body += bits.bytes()
+    body += bytes([0])
+    return tag(11, body)
+
+
+def place_object_2(id, depth, scale, tx, ty):
+    body = bytes([0x02 | 0x04]) + struct.pack("<HH", depth, id) + matrix(scale, tx, ty)
+    return tag(26, body)
+
+
+def static_text_tags():
+    """The tags that define and place the static texts, on the first frame."""
+    return (
+        define_font_2()
+        + define_text(2, LINES)
+        + place_object_2(2, 1, 1, 200, 400)
+        + define_text(3, [QUIZ])
+        + place_object_2(3, 2, 2, 200, 2000)
+    )
+
+
+def read_tags(data):
+    tags, pos = [], 0
+    while pos < len(data):
+        (code_and_length,) = struct.unpack_from("<H", data, pos)
+        start, length = pos + 2, code_and_length & 0x3F
+        if length == 0x3F:
+            (length,) = struct.unpack_from("<I", data, start)
+            start += 4
+        tags.append((code_and_length >> 6, data[pos : start + length]))
+        pos = start + length
+    return tags
+
+
+def main():
+    with open("Test.swf", "rb") as f:
+        data = f.read()
+    assert data[:3] == b"FWS", "Test.swf should be uncompressed"
+    header_length = 8 + ((5 + 4 * (data[8] >> 3) + 7) // 8) + 4
+    tags = read_tags(data[header_length:])
+
+    body = data[8:header_length]
+    for code, tag_data in tags:
+        if code == 1:
+            body += static_text_tags()
+        if code != 0:
+            body += tag_data
+    body += tag(0, b"")
+    swf = data[:4] + struct.pack("<I", 8 + len(body)) + body
+    with open("test.swf", "wb") as f:
+        f.write(swf)
+
+
+if __name__ == "__main__":
+    main()
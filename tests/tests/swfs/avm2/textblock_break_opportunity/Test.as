@@ -0,0 +1,46 @@
+package {
+import flash.display.Sprite;
+
+public class Test extends Sprite {
+    function Test() {
+
+    }
+}
+}
+
+import flash.text.engine.ElementFormat;
+import flash.text.engine.TextBlock;
+import flash.text.engine.TextElement;
+import flash.text.engine.TextLine;
+
+function traceLines(text:String, breakOpportunity:String):void {
+    var format:ElementFormat = new ElementFormat();
+    format.breakOpportunity = breakOpportunity;
+
+    var tb:TextBlock = new TextBlock();
+    tb.content = new TextElement(text, format);
+
+    var lengths:Array = [];
+    var line:TextLine = tb.createTextLine(null, 1000);
+    while (line != null) {
+        lengths.push(line.rawTextLength);
+        line = tb.createTextLine(line, 1000);
+    }
+    trace(breakOpportunity + ": " + lengths);
+}
+
+traceLines("ab cd", "auto");
+traceLines("ab cd", "any");
+traceLines("ab cd", "none");
+traceLines("ab cd", "all");
+traceLines("ab\u2029cd", "auto");
+traceLines("ab\u2029cd", "none");
+
+var tb:TextBlock = new TextBlock();
+tb.content = new TextElement("a\tb", new ElementFormat());
+trace("hasTabs: " + tb.createTextLine(null, 1000).hasTabs);
+tb.content = new TextElement("ab", new ElementFormat());
+var tl:TextLine = tb.createTextLine(null, 1000);
+trace("hasTabs: " + tl.hasTabs);
+trace("totalAscent: " + (tl.totalAscent == tl.ascent));
+trace("totalHeight: " + (tl.totalHeight == tl.ascent + tl.descent));
@@ -0,0 +1,94 @@
+package {
+    import flash.display.Sprite;
+    import flash.events.Event;
+    import flash.events.HTTPStatusEvent;
+    import flash.events.IOErrorEvent;
+    import flash.events.ProgressEvent;
+    import flash.net.URLRequest;
+    import flash.net.URLStream;
+    import flash.utils.ByteArray;
+
+    public class Test extends Sprite {
+        public function Test() {
+            testChunked();
+        }
+
+        // "Hello, World!" arrives in chunks of 5 bytes, and is read while it arrives.
+        private function testChunked():void {
+            trace("// Chunked download");
+            var stream:URLStream = listen(new URLStream());
+            stream.addEventListener(ProgressEvent.PROGRESS, function(e:ProgressEvent):void {
+                var bytes:ByteArray = new ByteArray();
+                stream.readBytes(bytes, 0, 3);
+                trace("readBytes(bytes, 0, 3): " + bytes.toString());
+                trace("bytesAvailable: " + stream.bytesAvailable);
+            });
+            stream.addEventListener(Event.COMPLETE, function(e:Event):void {
+                trace("readUTFBytes(bytesAvailable): " + stream.readUTFBytes(stream.bytesAvailable));
+                trace("bytesAvailable: " + stream.bytesAvailable);
+                testClose();
+            });
+            stream.load(new URLRequest("data?debug-chunked"));
+            trace("connected: " + stream.connected);
+        }
+
+        // Closing the stream in the middle of the download stops all events.
+        private function testClose():void {
+            trace("// Closed after the first chunk");
+            var stream:URLStream = listen(new URLStream());
+            stream.addEventListener(ProgressEvent.PROGRESS, function(e:ProgressEvent):void {
+                stream.close();
+                trace("connected after close(): " + stream.connected);
+                testErrorMidstream();
+            });
+            stream.load(new URLRequest("data?debug-chunked"));
+        }
+
+        // The data received before the connection failed can still be read.
+        private function testErrorMidstream():void {
+            trace("// Connection fails after the body");
+            var stream:URLStream = listen(new URLStream());
+            stream.addEventListener(IOErrorEvent.IO_ERROR, function(e:IOErrorEvent):void {
+                trace("readUTFBytes(bytesAvailable): " + stream.readUTFBytes(stream.bytesAvailable));
+                testErrorDns();
+            });
+            stream.load(new URLRequest("data?debug-error-midstream"));
+        }
+
+        private function testErrorDns():void {
+            trace("// Domain can't be resolved");
+            var stream:URLStream = listen(new URLStream());
+            stream.addEventListener(IOErrorEvent.IO_ERROR, function(e:IOErrorEvent):void {
+                testErrorStatusCode();
+            });
+            stream.load(new URLRequest("data?debug-error-dns"));
+        }
+
+        private function testErrorStatusCode():void {
+            trace("// Unsuccessful status code");
+            var stream:URLStream = listen(new URLStream());
+            stream.load(new URLRequest("data?debug-error-statuscode"));
+        }
+
+        private function listen(stream:URLStream):URLStream {
+            stream.addEventListener(Event.OPEN, function(e:Event):void {
+                trace("open: bytesAvailable=" + stream.bytesAvailable);
+            });
+            stream.addEventListener(ProgressEvent.PROGRESS, function(e:ProgressEvent):void {
+                trace("progress: bytesLoaded=" + e.bytesLoaded + " bytesTotal=" + e.bytesTotal
+                    + " bytesAvailable=" + stream.bytesAvailable);
+            });
+            stream.addEventListener(HTTPStatusEvent.HTTP_STATUS, function(e:HTTPStatusEvent):void {
+                trace("httpStatus: status=" + e.status);
+            });
+            stream.addEventListener(Event.COMPLETE, function(e:Event):void {
+                trace("complete: bytesAvailable=" + stream.bytesAvailable);
+            });
+            stream.addEventListener(IOErrorEvent.IO_ERROR, function(e:IOErrorEvent):void {
+                trace("ioError: text=" + e.text + " errorID=" + e.errorID
+                    + " bytesAvailable=" + stream.bytesAvailable);
+            });
+            return stream;
+        }
+    }
+}
@@ -0,0 +1,151 @@
+package {
+    import flash.concurrent.Condition;
+    import flash.concurrent.Mutex;
+    import flash.display.Sprite;
+    import flash.errors.IllegalOperationError;
+    import flash.events.Event;
+    import flash.net.URLLoader;
+    import flash.net.URLRequest;
+    import flash.system.MessageChannel;
+    import flash.system.Worker;
+    import flash.system.WorkerDomain;
+
+    public class Test extends Sprite {
+        private var worker:Worker;
+        private var mutex:Mutex;
+        private var condition:Condition;
+        private var toWorker:MessageChannel;
+        private var fromWorker:MessageChannel;
+        private var frames:int = 0;
+
+        public function Test() {
+            if (Worker.current.isPrimordial) {
+                startPrimordial();
+            } else {
+                startBackground();
+            }
+        }
+
+        private function startPrimordial():void {
+            trace("primordial: isSupported " + Worker.isSupported);
+            mutex = new Mutex();
+            condition = new Condition(mutex);
+            testErrors();
+
+            worker = WorkerDomain.current.createWorker(loaderInfo.bytes);
+            trace("primordial: state " + worker.state);
+            toWorker = Worker.current.createMessageChannel(worker);
+            fromWorker = worker.createMessageChannel(Worker.current);
+            worker.setSharedProperty("greeting", "Hello from the primordial worker");
+            worker.setSharedProperty("mutex", mutex);
+            worker.setSharedProperty("condition", condition);
+            worker.setSharedProperty("toWorker", toWorker);
+            worker.setSharedProperty("fromWorker", fromWorker);
+            worker.addEventListener(Event.WORKER_STATE, onWorkerState);
+            fromWorker.addEventListener(Event.CHANNEL_MESSAGE, onWorkerMessage);
+            toWorker.send("first message");
+
+            // The worker blocks on the mutex until it's unlocked a few frames later.
+            mutex.lock();
+            trace("primordial: locked the mutex");
+            worker.start();
+            addEventListener(Event.ENTER_FRAME, onPrimordialFrame);
+        }
+
+        private function testErrors():void {
+            try {
+                mutex.unlock();
+            } catch (e:Error) {
+                traceError("unlock", e);
+            }
+            try {
+                condition.notify();
+            } catch (e:Error) {
+                traceError("notify", e);
+            }
+            try {
+                condition.notifyAll();
+            } catch (e:Error) {
+                traceError("notifyAll", e);
+            }
+            try {
+                condition.wait();
+            } catch (e:Error) {
+                traceError("wait", e);
+            }
+        }
+
+        private function traceError(method:String, e:Error):void {
+            trace("primordial: " + method + " threw " + (e is IllegalOperationError) + " " + e.errorID + " " + e.message);
+        }
+
+        private function onPrimordialFrame(e:Event):void {
+            frames++;
+            if (frames == 4) {
+                trace("primordial: unlocking the mutex");
+                mutex.unlock();
+            }
+        }
+
+        private function onWorkerState(e:Event):void {
+            trace("primordial: worker state " + worker.state);
+        }
+
+        private function onWorkerMessage(e:Event):void {
+            var message:String = fromWorker.receive();
+            trace("primordial: received " + message);
+            if (message == "waiting") {
+                mutex.lock();
+                trace("primordial: notifying");
+                condition.notify();
+                mutex.unlock();
+            } else if (message == "holding") {
+                // The primordial worker keeps running the worker until it unlocks the mutex.
+                trace("primordial: locking the mutex");
+                mutex.lock();
+                trace("primordial: locked the mutex again");
+                mutex.unlock();
+                trace("primordial: terminate " + worker.terminate());
+            }
+        }
+
+        private function startBackground():void {
+            trace("worker: isPrimordial " + Worker.current.isPrimordial);
+            trace("worker: " + Worker.current.getSharedProperty("greeting"));
+            mutex = Worker.current.getSharedProperty("mutex");
+            condition = Worker.current.getSharedProperty("condition");
+            toWorker = Worker.current.getSharedProperty("toWorker");
+            fromWorker = Worker.current.getSharedProperty("fromWorker");
+            trace("worker: same mutex " + (condition.mutex == mutex));
+            trace("worker: received " + toWorker.receive());
+
+            trace("worker: tryLock " + mutex.tryLock());
+            trace("worker: locking the mutex");
+            mutex.lock();
+            trace("worker: locked the mutex");
+
+            fromWorker.send("waiting");
+            var notified:Boolean = condition.wait();
+            trace("worker: notified " + notified);
+            mutex.unlock();
+
+            var loader:URLLoader = new URLLoader();
+            loader.addEventListener(Event.COMPLETE, function(e:Event):void {
+                trace("worker: loaded " + loader.data);
+                mutex.lock();
+                fromWorker.send("holding");
+                addEventListener(Event.ENTER_FRAME, onBackgroundFrame);
+            });
+            loader.load(new URLRequest("http://example.com/data.txt?debug-success"));
+        }
+
+        private function onBackgroundFrame(e:Event):void {
+            frames++;
+            if (frames == 2) {
+                trace("worker: unlocking the mutex");
+                mutex.unlock();
+                removeEventListener(Event.ENTER_FRAME, onBackgroundFrame);
+            }
+        }
+    }
+}
@@ -46,6 +46,10 @@ impl VideoBackend for SoftwareVideoBackend {
             VideoCodec::Vp6WithAlpha => Box::new(crate::decoder::vp6::Vp6Decoder::new(true, size)),
             #[cfg(feature = "screenvideo")]
             VideoCodec::ScreenVideo => Box::new(crate::decoder::screen::ScreenVideoDecoder::new()),
+            #[cfg(feature = "screenvideo")]
+            VideoCodec::ScreenVideoV2 => {
+                Box::new(crate::decoder::screen::ScreenVideoDecoder::new_v2())
+            }
             other => return Err(Error::UnsupportedCodec(other)),
         };
         let stream = VideoStream::new(decoder);
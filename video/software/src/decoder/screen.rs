@@ -24,6 +24,15 @@ pub enum ScreenError {
 
     #[error("Not all blocks were updated by a supposed keyframe")]
     KeyframeInvalid,
+
+    #[error("Invalid color depth: {0}")]
+    InvalidColorDepth(u8),
+
+    #[error("Changed rows don't fit in their block")]
+    InvalidDiffBlock,
+
+    #[error("Missing block to prime decompression with")]
+    MissingPrimingBlock,
 }
 
 impl From<ScreenError> for Error {
@@ -32,8 +41,30 @@ impl From<ScreenError> for Error {
     }
 }
 
-/// Screen Video (V1 only) decoder.
+/// The palette used by the hybrid 15-bit/7-bit color mode of Screen Video V2
+/// blocks, as 0xRRGGBB values.
+const DEFAULT_PALETTE: [u32; 128] = [
+    0x000000, 0x333333, 0x666666, 0x999999, 0xCCCCCC, 0xFFFFFF, 0x330000, 0x660000, 0x990000,
+    0xCC0000, 0xFF0000, 0x003300, 0x006600, 0x009900, 0x00CC00, 0x00FF00, 0x000033, 0x000066,
+    0x000099, 0x0000CC, 0x0000FF, 0x333300, 0x666600, 0x999900, 0xCCCC00, 0xFFFF00, 0x003333,
+    0x006666, 0x009999, 0x00CCCC, 0x00FFFF, 0x330033, 0x660066, 0x990099, 0xCC00CC, 0xFF00FF,
+    0xFFFF33, 0xFFFF66, 0xFFFF99, 0xFFFFCC, 0xFF33FF, 0xFF66FF, 0xFF99FF, 0xFFCCFF, 0x33FFFF,
+    0x66FFFF, 0x99FFFF, 0xCCFFFF, 0xCCCC33, 0xCCCC66, 0xCCCC99, 0xCCCCFF, 0xCC33CC, 0xCC66CC,
+    0xCC99CC, 0xCCFFCC, 0x33CCCC, 0x66CCCC, 0x99CCCC, 0xFFCCCC, 0x999933, 0x999966, 0x9999CC,
+    0x9999FF, 0x993399, 0x996699, 0x99CC99, 0x99FF99, 0x339999, 0x669999, 0xCC9999, 0xFF9999,
+    0x666633, 0x666699, 0x6666CC, 0x6666FF, 0x663366, 0x669966, 0x66CC66, 0x66FF66, 0x336666,
+    0x996666, 0xCC6666, 0xFF6666, 0x333366, 0x333399, 0x3333CC, 0x3333FF, 0x336633, 0x339933,
+    0x33CC33, 0x33FF33, 0x663333, 0x993333, 0xCC3333, 0xFF3333, 0x003366, 0x336600, 0x660033,
+    0x006633, 0x330066, 0x663300, 0x336699, 0x669933, 0x993366, 0x339966, 0x663399, 0x996633,
+    0x6699CC, 0x99CC66, 0xCC6699, 0x66CC99, 0x9966CC, 0xCC9966, 0x99CCFF, 0xCCFF99, 0xFF99CC,
+    0x99FFCC, 0xCC99FF, 0xFFCC99, 0x111111, 0x222222, 0x444444, 0x555555, 0xAAAAAA, 0xBBBBBB,
+    0xDDDDDD, 0xEEEEEE,
+];
+
+/// Screen Video (V1 and V2) decoder.
 pub struct ScreenVideoDecoder {
+    is_v2: bool,
+
     w: usize,
     h: usize,
     block_w: usize,
@@ -42,6 +73,23 @@ pub struct ScreenVideoDecoder {
     tile: Vec<u8>, // acts as a scratch buffer
 
     last_frame: Option<Vec<u8>>,
+
+    /// The decompressed contents of each block of the last V2 keyframe or
+    /// I-frame image, which later frames may prime their decompression with.
+    keyframe_blocks: Vec<Vec<u8>>,
+
+    /// The palette of the hybrid color mode of V2 blocks.
+    palette: [u32; 128],
+}
+
+/// The layout of a decompressed Screen Video V2 image block.
+struct BlockV2 {
+    color_depth: u8,
+    row_start: usize,
+    row_count: usize,
+
+    /// The length of the decompressed data.
+    length: usize,
 }
 
 struct ByteReader<'a> {
@@ -82,12 +130,22 @@ impl<'a> ByteReader<'a> {
 impl ScreenVideoDecoder {
     pub fn new() -> Self {
         Self {
+            is_v2: false,
             w: 0,
             h: 0,
             block_w: 0,
             block_h: 0,
             tile: vec![],
             last_frame: None,
+            keyframe_blocks: vec![],
+            palette: DEFAULT_PALETTE,
+        }
+    }
+
+    pub fn new_v2() -> Self {
+        Self {
+            is_v2: true,
+            ..Self::new()
         }
     }
 
@@ -127,8 +185,156 @@ impl ScreenVideoDecoder {
         Ok(is_intra)
     }
 
+    /// Read an `IMAGEBLOCKV2`, decompressing its data into `self.tile`.
+    ///
+    /// `blocks` holds the decompressed blocks read so far from the same list
+    /// of blocks, which this block may be primed with. `block_index` is the
+    /// position of this block in that list, if it's an image block.
+    fn read_block_v2(
+        &mut self,
+        src: &mut ByteReader,
+        blocks: &[Vec<u8>],
+        block_index: Option<usize>,
+        cur_h: usize,
+    ) -> Result<Option<BlockV2>, Error> {
+        let data_size = src.read_u16be()? as usize;
+        if data_size == 0 {
+            return Ok(None);
+        }
+
+        let flags = src.read_byte()?;
+        let color_depth = (flags >> 3) & 0x3;
+        let has_diff = flags & 0x4 != 0;
+        let prime_current = flags & 0x2 != 0;
+        let prime_previous = flags & 0x1 != 0;
+        let mut header_size = 1;
+
+        // Interframes may only update a range of rows of the block.
+        let (row_start, row_count) = if has_diff {
+            header_size += 2;
+            (src.read_byte()? as usize, src.read_byte()? as usize)
+        } else {
+            (0, cur_h)
+        };
+        if row_start + row_count > cur_h {
+            return Err(ScreenError::InvalidDiffBlock.into());
+        }
+
+        // Blocks can be compressed as a continuation of the data of another
+        // block of this frame, or of the same block in the last I-frame image.
+        let priming_block = if prime_current {
+            header_size += 2;
+            let column = src.read_byte()? as usize;
+            let row = src.read_byte()? as usize;
+            let blocks_per_row = self.w.div_ceil(self.block_w);
+            Some(blocks.get(row * blocks_per_row + column))
+        } else if prime_previous {
+            Some(block_index.and_then(|index| self.keyframe_blocks.get(index)))
+        } else {
+            None
+        };
+        let priming_block = match priming_block {
+            Some(Some(block)) if !block.is_empty() => Some(block.as_slice()),
+            Some(_) => return Err(ScreenError::MissingPrimingBlock.into()),
+            None => None,
+        };
+
+        let compressed = src.read_buf_ref(
+            data_size
+                .checked_sub(header_size)
+                .ok_or(ScreenError::UnexpectedEOF)?,
+        )?;
+        let length = inflate_block(compressed, priming_block, &mut self.tile)?;
+
+        Ok(Some(BlockV2 {
+            color_depth,
+            row_start,
+            row_count,
+            length,
+        }))
+    }
+
+    fn decode_v2(
+        &mut self,
+        src: &mut ByteReader,
+        data: &mut [u8],
+        stride: usize,
+        is_keyframe: bool,
+    ) -> Result<(), Error> {
+        let flags = src.read_byte()?;
+        let has_iframe_image = flags & 0x2 != 0;
+        let has_palette_info = flags & 0x1 != 0;
+
+        // A custom palette replaces the start of the default one, as BGR
+        // colors, until the decoder is reset.
+        if has_palette_info {
+            if let Some(block) = self.read_block_v2(src, &[], None, self.block_h)? {
+                let colors = &self.tile[..block.length];
+                for (color, bgr) in self.palette.iter_mut().zip(colors.chunks_exact(3)) {
+                    *color = u32::from_le_bytes([bgr[0], bgr[1], bgr[2], 0]);
+                }
+            }
+        }
+
+        let blocks_per_row = self.w.div_ceil(self.block_w);
+        let block_count = blocks_per_row * self.h.div_ceil(self.block_h);
+        let mut blocks = vec![vec![]; block_count];
+        let mut pixels = vec![];
+        for (yy, row) in data.chunks_mut(stride * self.block_h).enumerate() {
+            let cur_h = (self.h - yy * self.block_h).min(self.block_h);
+            for (xx, x) in (0..self.w).step_by(self.block_w).enumerate() {
+                let cur_w = (self.w - x).min(self.block_w);
+                let block_index = yy * blocks_per_row + xx;
+
+                let Some(block) = self.read_block_v2(src, &blocks, Some(block_index), cur_h)?
+                else {
+                    continue;
+                };
+                let tile = &self.tile[..block.length];
+
+                let bgr = match block.color_depth {
+                    0 => tile,
+                    2 => {
+                        decode_hybrid(tile, cur_w * block.row_count, &self.palette, &mut pixels)?;
+                        &pixels
+                    }
+                    x => return Err(ScreenError::InvalidColorDepth(x).into()),
+                };
+                for (dst, src) in row[block.row_start * stride + x * 3..]
+                    .chunks_mut(stride)
+                    .zip(bgr.chunks(cur_w * 3).take(block.row_count))
+                {
+                    dst[..src.len()].copy_from_slice(src);
+                }
+
+                blocks[block_index] = tile.to_vec();
+            }
+        }
+
+        // The I-frame image isn't displayed, but later frames prime their
+        // blocks with it instead of with the blocks of the last keyframe.
+        if has_iframe_image {
+            let mut iframe_blocks = vec![vec![]; block_count];
+            for block_index in 0..block_count {
+                let cur_h =
+                    (self.h - (block_index / blocks_per_row) * self.block_h).min(self.block_h);
+                if let Some(block) =
+                    self.read_block_v2(src, &iframe_blocks, Some(block_index), cur_h)?
+                {
+                    iframe_blocks[block_index] = self.tile[..block.length].to_vec();
+                }
+            }
+            self.keyframe_blocks = iframe_blocks;
+        } else if is_keyframe {
+            self.keyframe_blocks = blocks;
+        }
+        Ok(())
+    }
+
     fn flush(&mut self) {
         self.last_frame = None;
+        self.keyframe_blocks.clear();
+        self.palette = DEFAULT_PALETTE;
     }
 }
 
@@ -139,7 +345,7 @@ impl VideoDecoder for ScreenVideoDecoder {
         // in FLV. This is super helpful, because it encodes whether the frame is a keyframe or not.
 
         // Just a quick sanity check for codec IDs...
-        debug_assert!(encoded_frame.data[0] & 0xF == 3);
+        debug_assert!(encoded_frame.data[0] & 0xF == if self.is_v2 { 6 } else { 3 });
 
         match encoded_frame.data[0] >> 4 {
             1 => Ok(FrameDependency::None),
@@ -184,10 +390,14 @@ impl VideoDecoder for ScreenVideoDecoder {
 
         let stride = w * 3;
 
-        let is_intra = self.decode_v1(&mut br, data.as_mut_slice(), stride)?;
+        if self.is_v2 {
+            self.decode_v2(&mut br, data.as_mut_slice(), stride, is_keyframe)?;
+        } else {
+            let is_intra = self.decode_v1(&mut br, data.as_mut_slice(), stride)?;
 
-        if is_intra != is_keyframe {
-            return Err(ScreenError::KeyframeInvalid.into());
+            if is_intra != is_keyframe {
+                return Err(ScreenError::KeyframeInvalid.into());
+            }
         }
 
         let mut rgb = vec![0u8; w * h * 3];
@@ -213,8 +423,205 @@ impl VideoDecoder for ScreenVideoDecoder {
     }
 }
 
+/// Decompress the data of a block into `output`, returning the decompressed
+/// length.
+///
+/// A block primed with another one is a continuation of the zlib stream the
+/// encoder used for the priming block, with its data flushed: we replay that
+/// data as stored deflate blocks so that back-references into it resolve.
+fn inflate_block(
+    compressed: &[u8],
+    priming_block: Option<&[u8]>,
+    output: &mut [u8],
+) -> Result<usize, ScreenError> {
+    let Some(priming_block) = priming_block else {
+        let mut decompress = Decompress::new(true);
+        decompress.decompress(compressed, output, flate2::FlushDecompress::Finish)?;
+        return Ok(decompress.total_out() as usize);
+    };
+
+    let mut decompress = Decompress::new(false);
+    let mut stored = Vec::with_capacity(priming_block.len() + 5);
+    let mut discarded = vec![0; priming_block.len()];
+    for chunk in priming_block.chunks(u16::MAX as usize) {
+        let length = chunk.len() as u16;
+        stored.clear();
+        stored.push(0); // Not final, stored
+        stored.extend_from_slice(&length.to_le_bytes());
+        stored.extend_from_slice(&(!length).to_le_bytes());
+        stored.extend_from_slice(chunk);
+        decompress.decompress(&stored, &mut discarded, flate2::FlushDecompress::Sync)?;
+    }
+
+    let primed_length = decompress.total_out();
+    decompress.decompress(compressed, output, flate2::FlushDecompress::Finish)?;
+    Ok((decompress.total_out() - primed_length) as usize)
+}
+
+/// Convert `pixel_count` pixels of the hybrid color mode of Screen Video V2
+/// into BGR, where each pixel is either a 15-bit color or an index into
+/// `palette`.
+fn decode_hybrid(
+    src: &[u8],
+    pixel_count: usize,
+    palette: &[u32; 128],
+    bgr: &mut Vec<u8>,
+) -> Result<(), ScreenError> {
+    let mut src = ByteReader::new(src);
+    bgr.clear();
+    for _ in 0..pixel_count {
+        let byte = src.read_byte()?;
+        if byte & 0x80 != 0 {
+            let color = ((byte as u16 & 0x7F) << 8) | src.read_byte()? as u16;
+            let expand = |c: u16| ((c << 3) | (c >> 2)) as u8;
+            bgr.extend_from_slice(&[
+                expand(color & 0x1F),
+                expand((color >> 5) & 0x1F),
+                expand(color >> 10),
+            ]);
+        } else {
+            let color = palette[byte as usize];
+            bgr.extend_from_slice(&[color as u8, (color >> 8) as u8, (color >> 16) as u8]);
+        }
+    }
+    Ok(())
+}
+
 impl Default for ScreenVideoDecoder {
     fn default() -> Self {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::{Compress, Compression, FlushCompress};
+
+    const KEYFRAME: u8 = 1;
+    const INTERFRAME: u8 = 2;
+
+    fn compress(priming: Option<&[u8]>, data: &[u8]) -> Vec<u8> {
+        let mut compress = Compress::new(Compression::default(), true);
+        let mut out = Vec::with_capacity(data.len() * 2 + 64);
+        if let Some(priming) = priming {
+            // Flush the priming data and throw it away, like the encoder does
+            // after sending the block it belongs to.
+            let mut discarded = Vec::with_capacity(priming.len() * 2 + 64);
+            compress
+                .compress_vec(priming, &mut discarded, FlushCompress::Sync)
+                .unwrap();
+        }
+        compress
+            .compress_vec(data, &mut out, FlushCompress::Finish)
+            .unwrap();
+        out
+    }
+
+    /// An `IMAGEBLOCKV2` with the given flags and extra header bytes.
+    fn block(flags: u8, extra: &[u8], compressed: &[u8]) -> Vec<u8> {
+        let size = (1 + extra.len() + compressed.len()) as u16;
+        let mut out = size.to_be_bytes().to_vec();
+        out.push(flags);
+        out.extend_from_slice(extra);
+        out.extend_from_slice(compressed);
+        out
+    }
+
+    /// A 32x16 frame, made of two 16x16 blocks.
+    fn frame(frame_type: u8, flags: u8, blocks: &[Vec<u8>]) -> Vec<u8> {
+        let mut out = vec![frame_type << 4 | 6];
+        out.extend_from_slice(&32u16.to_be_bytes());
+        out.extend_from_slice(&16u16.to_be_bytes());
+        out.push(flags);
+        for block in blocks {
+            out.extend_from_slice(block);
+        }
+        out
+    }
+
+    fn decode(decoder: &mut ScreenVideoDecoder, data: &[u8]) -> DecodedFrame {
+        let encoded = || EncodedFrame {
+            codec: swf::VideoCodec::ScreenVideoV2,
+            data,
+            frame_id: 0,
+        };
+        decoder.preload_frame(encoded()).unwrap();
+        decoder.decode_frame(encoded()).unwrap()
+    }
+
+    /// The RGB color at a position, with y going down like in the output.
+    fn pixel(frame: &DecodedFrame, x: usize, y: usize) -> [u8; 3] {
+        let i = (y * frame.width() as usize + x) * 3;
+        frame.data()[i..i + 3].try_into().unwrap()
+    }
+
+    fn solid_bgr(bgr: [u8; 3], pixels: usize) -> Vec<u8> {
+        bgr.repeat(pixels)
+    }
+
+    #[test]
+    fn custom_palette_and_primed_interframe() {
+        let mut decoder = ScreenVideoDecoder::new_v2();
+
+        let palette = block(0, &[], &compress(None, &[0x33, 0x22, 0x11]));
+        let hybrid = block(2 << 3, &[], &compress(None, &[0; 256]));
+        let keyframe_block = solid_bgr([0x30, 0x20, 0x10], 256);
+        let bgr = block(0, &[], &compress(None, &keyframe_block));
+        let keyframe = decode(&mut decoder, &frame(KEYFRAME, 0x1, &[palette, hybrid, bgr]));
+        assert_eq!(pixel(&keyframe, 0, 0), [0x11, 0x22, 0x33]);
+        assert_eq!(pixel(&keyframe, 31, 15), [0x10, 0x20, 0x30]);
+
+        // Replace the first four stored rows of the second block, which are
+        // the bottom ones, primed with the keyframe's block.
+        let red = solid_bgr([0, 0, 0xFF], 64);
+        let diff = block(0x4 | 0x1, &[0, 4], &compress(Some(&keyframe_block), &red));
+        let interframe = decode(&mut decoder, &frame(INTERFRAME, 0, &[vec![0, 0], diff]));
+        assert_eq!(pixel(&interframe, 0, 15), [0x11, 0x22, 0x33]);
+        assert_eq!(pixel(&interframe, 16, 11), [0x10, 0x20, 0x30]);
+        assert_eq!(pixel(&interframe, 16, 12), [0xFF, 0, 0]);
+        assert_eq!(pixel(&interframe, 31, 15), [0xFF, 0, 0]);
+    }
+
+    #[test]
+    fn iframe_image_primes_later_frames() {
+        let mut decoder = ScreenVideoDecoder::new_v2();
+
+        let white = solid_bgr([0xFF, 0xFF, 0xFF], 256);
+        let keyframe = frame(
+            KEYFRAME,
+            0,
+            &[
+                block(0, &[], &compress(None, &white)),
+                block(0, &[], &compress(None, &white)),
+            ],
+        );
+        decode(&mut decoder, &keyframe);
+
+        // The I-frame image replaces the priming data of the first block, but
+        // isn't shown itself.
+        let blue = solid_bgr([0xFF, 0, 0], 256);
+        let with_iframe = frame(
+            INTERFRAME,
+            0x2,
+            &[
+                vec![0, 0],
+                vec![0, 0],
+                block(0, &[], &compress(None, &blue)),
+                vec![0, 0],
+            ],
+        );
+        let decoded = decode(&mut decoder, &with_iframe);
+        assert_eq!(pixel(&decoded, 0, 0), [0xFF, 0xFF, 0xFF]);
+
+        let primed = frame(
+            INTERFRAME,
+            0,
+            &[block(0x1, &[], &compress(Some(&blue), &blue)), vec![0, 0]],
+        );
+        let decoded = decode(&mut decoder, &primed);
+        assert_eq!(pixel(&decoded, 0, 0), [0, 0, 0xFF]);
+        assert_eq!(pixel(&decoded, 15, 15), [0, 0, 0xFF]);
+        assert_eq!(pixel(&decoded, 16, 0), [0xFF, 0xFF, 0xFF]);
+    }
+}